@@ -16,6 +16,7 @@ pub mod utils;
 use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
 use itertools::Itertools;
 use risingwave_batch::executor::{BoxedExecutor, HashAggExecutor};
+use risingwave_batch::task::MemoryContext;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::hash;
 use risingwave_common::types::DataType;
@@ -104,6 +105,8 @@ fn create_hash_agg_executor(
         input,
         "HashAggExecutor".to_string(),
         CHUNK_SIZE,
+        MemoryContext::root(),
+        None,
     ))
 }
 
@@ -18,6 +18,7 @@ use criterion::{criterion_group, criterion_main, Criterion};
 use risingwave_batch::executor::hash_join::HashJoinExecutor;
 use risingwave_batch::executor::test_utils::{gen_projected_data, MockExecutor};
 use risingwave_batch::executor::{BoxedExecutor, JoinType};
+use risingwave_batch::task::MemoryContext;
 use risingwave_common::catalog::schema_test_utils::field_n;
 use risingwave_common::hash;
 use risingwave_common::types::{DataType, ScalarImpl};
@@ -173,6 +174,8 @@ fn create_hash_join_executor(
         cond,
         "HashJoinExecutor".into(),
         CHUNK_SIZE,
+        MemoryContext::root(),
+        None,
     ))
 }
 
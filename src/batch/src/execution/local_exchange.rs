@@ -119,6 +119,7 @@ mod tests {
                 tx.send(Ok(GetDataResponse {
                     status: None,
                     record_batch: Some(DataChunk::default()),
+                    ..Default::default()
                 }))
                 .await
                 .unwrap();
@@ -165,7 +166,7 @@ mod tests {
             task_id: Some(TaskId::default()),
             ..Default::default()
         };
-        let mut src = GrpcExchangeSource::create(client, task_output_id, None)
+        let mut src = GrpcExchangeSource::create(client, task_output_id, None, None)
             .await
             .unwrap();
         for _ in 0..3 {
@@ -12,13 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::array::DataChunk;
+use risingwave_common::buffer::BitmapBuilder;
 use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::collection::estimate_size::EstimateSize;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::hash::{HashKey, HashKeyDispatcher, PrecomputedBuildHasher};
 use risingwave_common::types::DataType;
@@ -28,8 +31,9 @@ use risingwave_pb::batch_plan::HashAggNode;
 
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    SpillConfig, SpillFileExecutor, SpillPartitioner,
 };
-use crate::task::{BatchTaskContext, TaskId};
+use crate::task::{BatchTaskContext, MemoryContext, TaskId};
 
 type AggHashMap<K> = HashMap<K, Vec<BoxedAggState>, PrecomputedBuildHasher>;
 
@@ -46,6 +50,8 @@ impl HashKeyDispatcher for HashAggExecutorBuilder {
             self.child,
             self.identity,
             self.chunk_size,
+            self.mem_context,
+            self.spill_config,
         ))
     }
 
@@ -63,6 +69,8 @@ pub struct HashAggExecutorBuilder {
     task_id: TaskId,
     identity: String,
     chunk_size: usize,
+    mem_context: MemoryContext,
+    spill_config: Option<SpillConfig>,
 }
 
 impl HashAggExecutorBuilder {
@@ -72,6 +80,8 @@ impl HashAggExecutorBuilder {
         task_id: TaskId,
         identity: String,
         chunk_size: usize,
+        mem_context: MemoryContext,
+        spill_config: Option<SpillConfig>,
     ) -> Result<BoxedExecutor> {
         let agg_factories: Vec<_> = hash_agg_node
             .get_agg_calls()
@@ -108,6 +118,8 @@ impl HashAggExecutorBuilder {
             task_id,
             identity,
             chunk_size,
+            mem_context,
+            spill_config,
         };
 
         Ok(builder.dispatch())
@@ -134,11 +146,17 @@ impl BoxedExecutorBuilder for HashAggExecutorBuilder {
             source.task_id.clone(),
             identity,
             source.context.get_config().developer.batch_chunk_size,
+            source.mem_context(),
+            SpillConfig::from_batch_config(source.context.get_config()),
         )
     }
 }
 
 /// `HashAggExecutor` implements the hash aggregate algorithm.
+///
+/// If spilling is enabled and the hash table grows beyond the memory limit, no more groups are
+/// added to it. The rows of the other groups are spilled to partitions by their group keys, each of
+/// which is aggregated by another `HashAggExecutor` after the groups in memory are output.
 pub struct HashAggExecutor<K> {
     /// Factories to construct aggregator for each groups
     agg_factories: Vec<AggStateFactory>,
@@ -151,6 +169,12 @@ pub struct HashAggExecutor<K> {
     child: BoxedExecutor,
     identity: String,
     chunk_size: usize,
+    /// Accounts the memory used by the hash table.
+    mem_context: MemoryContext,
+    /// Spills the groups beyond the memory limit if set.
+    spill_config: Option<SpillConfig>,
+    /// The number of times the input has been spilled.
+    spill_depth: usize,
     _phantom: PhantomData<K>,
 }
 
@@ -163,6 +187,8 @@ impl<K> HashAggExecutor<K> {
         child: BoxedExecutor,
         identity: String,
         chunk_size: usize,
+        mem_context: MemoryContext,
+        spill_config: Option<SpillConfig>,
     ) -> Self {
         HashAggExecutor {
             agg_factories,
@@ -172,6 +198,9 @@ impl<K> HashAggExecutor<K> {
             child,
             identity,
             chunk_size,
+            mem_context,
+            spill_config,
+            spill_depth: 0,
             _phantom: PhantomData,
         }
     }
@@ -194,30 +223,69 @@ impl<K: HashKey + Send + Sync> Executor for HashAggExecutor<K> {
 impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
     #[try_stream(boxed, ok = DataChunk, error = RwError)]
     async fn do_execute(self: Box<Self>) {
+        let child_schema = self.child.schema().clone();
         // hash map for each agg groups
         let mut groups = AggHashMap::<K>::default();
+        let mut mem_usage = 0;
+        // Set once the hash table exceeds the memory limit, after which new groups are spilled.
+        let mut spill_partitioner: Option<SpillPartitioner> = None;
 
         // consume all chunks to compute the agg result
         #[for_await]
         for chunk in self.child.execute() {
             let chunk = chunk?.compact();
             let keys = K::build(self.group_key_columns.as_slice(), &chunk)?;
+            let mut spilled_rows = BitmapBuilder::zeroed(chunk.capacity());
+            let mut has_spilled_rows = false;
             for (row_id, key) in keys.into_iter().enumerate() {
-                let states: &mut Vec<BoxedAggState> = groups.entry(key).or_insert_with(|| {
-                    self.agg_factories
-                        .iter()
-                        .map(AggStateFactory::create_agg_state)
-                        .collect()
-                });
+                let states: &mut Vec<BoxedAggState> = match groups.entry(key) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(_) if spill_partitioner.is_some() => {
+                        spilled_rows.set(row_id, true);
+                        has_spilled_rows = true;
+                        continue;
+                    }
+                    Entry::Vacant(entry) => {
+                        let states: Vec<_> = self
+                            .agg_factories
+                            .iter()
+                            .map(AggStateFactory::create_agg_state)
+                            .collect();
+                        let group_size = (entry.key().estimated_size()
+                            + states.capacity() * std::mem::size_of::<BoxedAggState>())
+                            as i64;
+                        mem_usage += group_size;
+                        self.mem_context.add(group_size);
+                        entry.insert(states)
+                    }
+                };
 
                 // TODO: currently not a vectorized implementation
                 for state in states {
                     state.update_single(&chunk, row_id)?
                 }
             }
+
+            if let Some(spill_partitioner) = &mut spill_partitioner {
+                if has_spilled_rows {
+                    spill_partitioner
+                        .write(chunk.with_visibility(spilled_rows.finish()))
+                        .await?;
+                }
+            } else if let Some(spill_config) = &self.spill_config
+                && spill_config.should_spill(mem_usage, self.spill_depth)
+            {
+                spill_partitioner = Some(SpillPartitioner::new(
+                    spill_config,
+                    self.group_key_columns.clone(),
+                    self.spill_depth,
+                )
+                .await?);
+            }
         }
 
         // generate output data chunks
+        self.mem_context.add(-mem_usage);
         let mut result = groups.into_iter();
         let cardinality = self.chunk_size;
         loop {
@@ -261,11 +329,39 @@ impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
             let output = DataChunk::new(columns, array_len);
             yield output;
         }
+
+        // Aggregate the spilled partitions one by one.
+        if let Some(spill_partitioner) = spill_partitioner {
+            for file in spill_partitioner.finish().await? {
+                let input = SpillFileExecutor::new(
+                    file,
+                    child_schema.clone(),
+                    format!("{}-SpillFile", self.identity),
+                );
+                let mut partition_agg = HashAggExecutor::<K>::new(
+                    self.agg_factories.clone(),
+                    self.group_key_columns.clone(),
+                    self.group_key_types.clone(),
+                    self.schema.clone(),
+                    Box::new(input),
+                    self.identity.clone(),
+                    self.chunk_size,
+                    self.mem_context.clone(),
+                    self.spill_config.clone(),
+                );
+                partition_agg.spill_depth = self.spill_depth + 1;
+                #[for_await]
+                for chunk in Box::new(partition_agg).execute() {
+                    yield chunk?;
+                }
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures::StreamExt;
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::test_prelude::DataChunkTestExt;
     use risingwave_pb::data::data_type::TypeName;
@@ -333,6 +429,8 @@ mod tests {
             TaskId::default(),
             "HashAggExecutor".to_string(),
             CHUNK_SIZE,
+            MemoryContext::root(),
+            None,
         )
         .unwrap();
 
@@ -401,6 +499,8 @@ mod tests {
             TaskId::default(),
             "HashAggExecutor".to_string(),
             CHUNK_SIZE,
+            MemoryContext::root(),
+            None,
         )
         .unwrap();
         let schema = Schema {
@@ -416,4 +516,81 @@ mod tests {
         );
         diff_executor_output(actual_exec, Box::new(expect_exec)).await;
     }
+
+    #[tokio::test]
+    async fn execute_with_spill() {
+        let t32 = DataType::Int32;
+        let mut src_exec = MockExecutor::new(Schema {
+            fields: vec![Field::unnamed(t32.clone()), Field::unnamed(t32)],
+        });
+        src_exec.add(DataChunk::from_pretty(
+            "i i
+             0 1
+             1 2",
+        ));
+        src_exec.add(DataChunk::from_pretty(
+            "i i
+             0 3
+             2 4
+             3 5",
+        ));
+        src_exec.add(DataChunk::from_pretty(
+            "i i
+             2 6
+             1 7
+             3 8",
+        ));
+
+        let agg_call = AggCall {
+            r#type: Type::Sum as i32,
+            args: vec![Arg {
+                input: Some(InputRefExpr { column_idx: 1 }),
+                r#type: Some(ProstDataType {
+                    type_name: TypeName::Int32 as i32,
+                    ..Default::default()
+                }),
+            }],
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Int64 as i32,
+                ..Default::default()
+            }),
+            distinct: false,
+            order_by_fields: vec![],
+            filter: None,
+        };
+
+        let agg_prost = HashAggNode {
+            group_key: vec![0],
+            agg_calls: vec![agg_call],
+        };
+
+        // Spill the groups not in memory since the second chunk.
+        let spill_config = SpillConfig {
+            memory_limit_bytes: 0,
+            dir: None,
+        };
+        let actual_exec = HashAggExecutorBuilder::deserialize(
+            &agg_prost,
+            Box::new(src_exec),
+            TaskId::default(),
+            "HashAggExecutor".to_string(),
+            CHUNK_SIZE,
+            MemoryContext::root(),
+            Some(spill_config),
+        )
+        .unwrap();
+
+        let mut outputs = vec![];
+        let mut stream = actual_exec.execute();
+        while let Some(chunk) = stream.next().await {
+            for row in chunk.unwrap().rows() {
+                outputs.push((
+                    row.value_at(0).unwrap().into_int32(),
+                    row.value_at(1).unwrap().into_int64(),
+                ));
+            }
+        }
+        outputs.sort();
+        assert_eq!(outputs, vec![(0, 4), (1, 9), (2, 10), (3, 13)]);
+    }
 }
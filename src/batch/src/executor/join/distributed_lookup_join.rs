@@ -207,6 +207,7 @@ impl BoxedExecutorBuilder for DistributedLookupJoinExecutorBuilder {
             } else {
                 None
             },
+            time_travel_retention_seconds: None,
         };
         let value_indices = table_desc
             .get_value_indices()
@@ -225,6 +226,7 @@ impl BoxedExecutorBuilder for DistributedLookupJoinExecutorBuilder {
                 distribution,
                 table_option,
                 value_indices,
+                table_desc.read_prefix_len_hint as usize,
             );
 
             let inner_side_builder = InnerSideExecutorBuilder::new(
@@ -19,11 +19,13 @@ use std::marker::PhantomData;
 use std::sync::Arc;
 
 use fixedbitset::FixedBitSet;
+use futures::StreamExt;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::array::{Array, DataChunk, RowRef};
 use risingwave_common::buffer::{Bitmap, BitmapBuilder};
 use risingwave_common::catalog::Schema;
+use risingwave_common::collection::estimate_size::EstimateSize;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::hash::{HashKey, HashKeyDispatcher, PrecomputedBuildHasher};
 use risingwave_common::row::{repeat_n, RowExt};
@@ -35,8 +37,9 @@ use risingwave_pb::batch_plan::plan_node::NodeBody;
 use super::{ChunkedData, JoinType, RowId};
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    SpillConfig, SpillFileExecutor, SpillPartitioner,
 };
-use crate::task::BatchTaskContext;
+use crate::task::{BatchTaskContext, MemoryContext};
 
 /// Hash Join Executor
 ///
@@ -47,6 +50,10 @@ use crate::task::BatchTaskContext;
 /// 3. Concatenate the matched pair of probe side row and build side row into a single row and push
 /// it into the data chunk builder.
 /// 4. Yield chunks from the builder.
+///
+/// If spilling is enabled and the build side grows beyond the memory limit, both sides are spilled
+/// to partitions by their join keys, i.e. grace hash join. Each pair of partitions is then joined
+/// by another `HashJoinExecutor`, which may spill again.
 pub struct HashJoinExecutor<K> {
     /// Join type e.g. inner, left outer, ...
     join_type: JoinType,
@@ -64,13 +71,19 @@ pub struct HashJoinExecutor<K> {
     probe_key_idxs: Vec<usize>,
     /// Column indices of right keys in equi join
     build_key_idxs: Vec<usize>,
-    /// Non-equi join condition (optional)
-    cond: Option<BoxedExpression>,
+    /// Non-equi join condition (optional), shared with the joins of the spilled partitions
+    cond: Option<Arc<BoxedExpression>>,
     /// Whether or not to enable 'IS NOT DISTINCT FROM' semantics for a specific probe/build key
     /// column
     null_matched: Vec<bool>,
     identity: String,
     chunk_size: usize,
+    /// Accounts the memory used by the build side.
+    mem_context: MemoryContext,
+    /// Spills both sides if the build side exceeds the memory limit.
+    spill_config: Option<SpillConfig>,
+    /// The number of times the inputs have been spilled.
+    spill_depth: usize,
     _phantom: PhantomData<K>,
 }
 
@@ -217,16 +230,91 @@ impl<K: HashKey> HashJoinExecutor<K> {
         let build_data_types = self.build_side_source.schema().data_types();
         let full_data_types = [probe_data_types.clone(), build_data_types.clone()].concat();
 
+        let probe_schema = self.probe_side_source.schema().clone();
+        let build_schema = self.build_side_source.schema().clone();
+
         let mut build_side = Vec::new();
         let mut build_row_count = 0;
-        #[for_await]
-        for build_chunk in self.build_side_source.execute() {
+        let mut mem_usage = 0;
+        let mut spill_config = None;
+        let mut build_side_stream = self.build_side_source.execute();
+        while let Some(build_chunk) = build_side_stream.next().await {
             let build_chunk = build_chunk?;
             if build_chunk.cardinality() > 0 {
                 build_row_count += build_chunk.cardinality();
-                build_side.push(build_chunk.compact())
+                let build_chunk = build_chunk.compact();
+                let chunk_mem = build_chunk.estimated_size() as i64;
+                mem_usage += chunk_mem;
+                self.mem_context.add(chunk_mem);
+                build_side.push(build_chunk)
+            }
+            if let Some(config) = &self.spill_config
+                && config.should_spill(mem_usage, self.spill_depth)
+            {
+                spill_config = Some(config);
+                break;
+            }
+        }
+
+        if let Some(spill_config) = spill_config {
+            // Spill the build side, including the rest of it, and then the probe side.
+            let mut build_partitioner =
+                SpillPartitioner::new(spill_config, self.build_key_idxs.clone(), self.spill_depth)
+                    .await?;
+            for build_chunk in build_side {
+                build_partitioner.write(build_chunk).await?;
+            }
+            self.mem_context.add(-mem_usage);
+            while let Some(build_chunk) = build_side_stream.next().await {
+                build_partitioner.write(build_chunk?).await?;
+            }
+            let mut probe_partitioner =
+                SpillPartitioner::new(spill_config, self.probe_key_idxs.clone(), self.spill_depth)
+                    .await?;
+            #[for_await]
+            for probe_chunk in self.probe_side_source.execute() {
+                probe_partitioner.write(probe_chunk?).await?;
+            }
+
+            // Join the spilled partitions one by one.
+            for (probe_file, build_file) in probe_partitioner
+                .finish()
+                .await?
+                .into_iter()
+                .zip_eq(build_partitioner.finish().await?)
+            {
+                let mut partition_join = HashJoinExecutor::<K>::new(
+                    self.join_type,
+                    self.output_indices.clone(),
+                    Box::new(SpillFileExecutor::new(
+                        probe_file,
+                        probe_schema.clone(),
+                        format!("{}-ProbeSpillFile", self.identity),
+                    )),
+                    Box::new(SpillFileExecutor::new(
+                        build_file,
+                        build_schema.clone(),
+                        format!("{}-BuildSpillFile", self.identity),
+                    )),
+                    self.probe_key_idxs.clone(),
+                    self.build_key_idxs.clone(),
+                    self.null_matched.clone(),
+                    None,
+                    self.identity.clone(),
+                    self.chunk_size,
+                    self.mem_context.clone(),
+                    self.spill_config.clone(),
+                );
+                partition_join.cond = self.cond.clone();
+                partition_join.spill_depth = self.spill_depth + 1;
+                #[for_await]
+                for chunk in Box::new(partition_join).execute() {
+                    yield chunk?;
+                }
             }
+            return Ok(());
         }
+
         let mut hash_map =
             JoinHashMap::with_capacity_and_hasher(build_row_count, PrecomputedBuildHasher);
         let mut next_build_row_with_same_key =
@@ -247,6 +335,10 @@ impl<K: HashKey> HashJoinExecutor<K> {
                 // Only insert key to hash map if it is consistent with the null safe restriction.
                 if build_key.null_bitmap().is_subset(&null_matched) {
                     let row_id = RowId::new(build_chunk_id, build_row_id);
+                    let entry_size =
+                        (build_key.estimated_size() + std::mem::size_of::<RowId>()) as i64;
+                    mem_usage += entry_size;
+                    self.mem_context.add(entry_size);
                     next_build_row_with_same_key[row_id] = hash_map.insert(build_key, row_id);
                 }
             }
@@ -316,6 +408,7 @@ impl<K: HashKey> HashJoinExecutor<K> {
                 yield chunk?.reorder_columns(&self.output_indices)
             }
         }
+        self.mem_context.add(-mem_usage);
     }
 
     #[try_stream(boxed, ok = DataChunk, error = RwError)]
@@ -1663,6 +1756,8 @@ impl BoxedExecutorBuilder for HashJoinExecutor<()> {
             identity: context.plan_node().get_identity().clone(),
             right_key_types,
             chunk_size: context.context.get_config().developer.batch_chunk_size,
+            mem_context: context.mem_context(),
+            spill_config: SpillConfig::from_batch_config(context.context.get_config()),
         }
         .dispatch())
     }
@@ -1680,6 +1775,8 @@ struct HashJoinExecutorArgs {
     identity: String,
     right_key_types: Vec<DataType>,
     chunk_size: usize,
+    mem_context: MemoryContext,
+    spill_config: Option<SpillConfig>,
 }
 
 impl HashKeyDispatcher for HashJoinExecutorArgs {
@@ -1697,6 +1794,8 @@ impl HashKeyDispatcher for HashJoinExecutorArgs {
             self.cond,
             self.identity,
             self.chunk_size,
+            self.mem_context,
+            self.spill_config,
         ))
     }
 
@@ -1718,6 +1817,8 @@ impl<K> HashJoinExecutor<K> {
         cond: Option<BoxedExpression>,
         identity: String,
         chunk_size: usize,
+        mem_context: MemoryContext,
+        spill_config: Option<SpillConfig>,
     ) -> Self {
         assert_eq!(probe_key_idxs.len(), build_key_idxs.len());
         assert_eq!(probe_key_idxs.len(), null_matched.len());
@@ -1748,9 +1849,12 @@ impl<K> HashJoinExecutor<K> {
             probe_key_idxs,
             build_key_idxs,
             null_matched,
-            cond,
+            cond: cond.map(Arc::new),
             identity,
             chunk_size,
+            mem_context,
+            spill_config,
+            spill_depth: 0,
             _phantom: PhantomData,
         }
     }
@@ -1765,6 +1869,7 @@ mod tests {
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::error::Result;
     use risingwave_common::hash::Key32;
+    use risingwave_common::row::Row2;
     use risingwave_common::test_prelude::DataChunkTestExt;
     use risingwave_common::types::DataType;
     use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
@@ -1775,7 +1880,8 @@ mod tests {
         ChunkedData, HashJoinExecutor, JoinType, LeftNonEquiJoinState, RightNonEquiJoinState, RowId,
     };
     use crate::executor::test_utils::MockExecutor;
-    use crate::executor::BoxedExecutor;
+    use crate::executor::{BoxedExecutor, SpillConfig};
+    use crate::task::MemoryContext;
 
     const CHUNK_SIZE: usize = 1024;
 
@@ -1966,6 +2072,15 @@ mod tests {
         }
 
         fn create_join_executor(&self, has_non_equi_cond: bool, null_safe: bool) -> BoxedExecutor {
+            self.create_join_executor_with_spill(has_non_equi_cond, null_safe, None)
+        }
+
+        fn create_join_executor_with_spill(
+            &self,
+            has_non_equi_cond: bool,
+            null_safe: bool,
+            spill_config: Option<SpillConfig>,
+        ) -> BoxedExecutor {
             let join_type = self.join_type;
 
             let left_child = self.create_left_executor();
@@ -1995,6 +2110,8 @@ mod tests {
                 cond,
                 "HashJoinExecutor".to_string(),
                 CHUNK_SIZE,
+                MemoryContext::root(),
+                spill_config,
             ))
         }
 
@@ -2459,6 +2576,52 @@ mod tests {
         test_fixture.do_test(expected_chunk, true, false).await;
     }
 
+    /// Spilling changes the order of the output rows, but not the rows.
+    #[tokio::test]
+    async fn test_join_with_spill() {
+        async fn collect_rows(executor: BoxedExecutor) -> Vec<String> {
+            let mut rows = vec![];
+            let mut stream = executor.execute();
+            while let Some(chunk) = stream.next().await {
+                for row in chunk.unwrap().rows() {
+                    rows.push(format!("{:?}", row.into_owned_row()));
+                }
+            }
+            rows.sort();
+            rows
+        }
+
+        for join_type in [
+            JoinType::Inner,
+            JoinType::LeftOuter,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightOuter,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+            JoinType::FullOuter,
+        ] {
+            let test_fixture = TestFixture::with_join_type(join_type);
+            for (has_non_equi_cond, null_safe) in [(false, false), (true, false), (false, true)] {
+                let expected =
+                    collect_rows(test_fixture.create_join_executor(has_non_equi_cond, null_safe))
+                        .await;
+                // Spill once the first chunk of the build side arrives.
+                let spill_config = SpillConfig {
+                    memory_limit_bytes: 0,
+                    dir: None,
+                };
+                let actual = collect_rows(test_fixture.create_join_executor_with_spill(
+                    has_non_equi_cond,
+                    null_safe,
+                    Some(spill_config),
+                ))
+                .await;
+                assert_eq!(expected, actual, "{:?}", join_type);
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_process_left_outer_join_non_equi_condition() {
         let chunk = DataChunk::from_pretty(
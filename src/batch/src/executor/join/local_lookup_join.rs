@@ -129,6 +129,7 @@ impl<C: BatchTaskContext> InnerSideExecutorBuilder<C> {
                 root: Some(PlanNode {
                     children: vec![],
                     identity: Uuid::new_v4().to_string(),
+                    operator_id: 0,
                     node_body: Some(self.create_row_seq_scan_node(id)?),
                 }),
                 exchange_info: Some(ExchangeInfo {
@@ -232,6 +233,7 @@ impl<C: BatchTaskContext> LookupExecutorBuilder for InnerSideExecutorBuilder<C>
         let plan_node = PlanNode {
             children: vec![],
             identity: "LocalLookupJoinExchangeExecutor".to_string(),
+            operator_id: 0,
             node_body: Some(exchange_node),
         };
 
@@ -25,11 +25,15 @@ mod limit;
 mod merge_sort_exchange;
 pub mod monitor;
 mod order_by;
+mod over_agg;
+mod profile;
 mod project;
 mod project_set;
 mod row_seq_scan;
+mod session_window;
 mod sort_agg;
 mod source;
+mod spill;
 mod sys_row_seq_scan;
 mod table_function;
 pub mod test_utils;
@@ -40,6 +44,8 @@ mod update;
 mod utils;
 mod values;
 
+use std::sync::Arc;
+
 use async_recursion::async_recursion;
 pub use delete::*;
 pub use expand::*;
@@ -55,6 +61,8 @@ pub use limit::*;
 pub use merge_sort_exchange::*;
 pub use monitor::*;
 pub use order_by::*;
+pub use over_agg::*;
+pub use profile::*;
 pub use project::*;
 pub use project_set::*;
 use risingwave_common::array::DataChunk;
@@ -63,8 +71,10 @@ use risingwave_common::error::Result;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::PlanNode;
 pub use row_seq_scan::*;
+pub use session_window::*;
 pub use sort_agg::*;
 pub use source::*;
+pub use spill::*;
 pub use table_function::*;
 pub use top_n::TopNExecutor;
 pub use trace::*;
@@ -74,7 +84,7 @@ pub use utils::*;
 pub use values::*;
 
 use crate::executor::sys_row_seq_scan::SysRowSeqScanExecutorBuilder;
-use crate::task::{BatchTaskContext, TaskId};
+use crate::task::{BatchTaskContext, MemoryContext, TaskId};
 
 pub type BoxedExecutor = Box<dyn Executor>;
 pub type BoxedDataChunkStream = BoxStream<'static, Result<DataChunk>>;
@@ -164,6 +174,21 @@ impl<'a, C: Clone> ExecutorBuilder<'a, C> {
 }
 
 impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
+    /// Returns the stats of the current plan node if the task collects executor stats.
+    pub fn executor_stats(&self) -> Option<Arc<ExecutorStats>> {
+        self.context.executor_stats().map(|collector| {
+            collector.get_or_register(self.plan_node.operator_id, &self.context.mem_context())
+        })
+    }
+
+    /// Returns the memory context which stateful executors should account their memory usage to.
+    pub fn mem_context(&self) -> MemoryContext {
+        match self.executor_stats() {
+            Some(stats) => stats.mem_context.clone(),
+            None => self.context.mem_context().new_child(),
+        }
+    }
+
     pub async fn build(&self) -> Result<BoxedExecutor> {
         self.try_build().await.map_err(|e| {
             let err_msg = format!("Failed to build executor: {e}");
@@ -190,6 +215,8 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             NodeBody::Filter => FilterExecutor,
             NodeBody::Project => ProjectExecutor,
             NodeBody::SortAgg => SortAggExecutor,
+            NodeBody::OverAgg => OverAggExecutor,
+            NodeBody::SessionWindow => SessionWindowExecutor,
             NodeBody::Sort => SortExecutor,
             NodeBody::TopN => TopNExecutor,
             NodeBody::GroupTopN => GroupTopNExecutorBuilder,
@@ -212,7 +239,11 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
         }
         .await?;
         let input_desc = real_executor.identity().to_string();
-        Ok(Box::new(TraceExecutor::new(real_executor, input_desc)) as BoxedExecutor)
+        let executor = Box::new(TraceExecutor::new(real_executor, input_desc)) as BoxedExecutor;
+        match self.executor_stats() {
+            Some(stats) => Ok(Box::new(ProfileExecutor::new(executor, stats)) as BoxedExecutor),
+            None => Ok(executor),
+        }
     }
 }
 
@@ -0,0 +1,96 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use risingwave_pb::task_service::ExecutorStats as ProstExecutorStats;
+
+use crate::task::MemoryContext;
+
+/// Runtime statistics of one executor, reported to the frontend for `EXPLAIN ANALYZE`.
+pub struct ExecutorStats {
+    pub output_rows: AtomicU64,
+    pub output_chunks: AtomicU64,
+    /// Time spent in polling the executor, including the time spent in its children.
+    pub elapsed_ns: AtomicU64,
+    /// Memory accounted by the executor. Only stateful executors report their memory usage.
+    pub mem_context: MemoryContext,
+}
+
+impl ExecutorStats {
+    fn new(mem_context: MemoryContext) -> Self {
+        Self {
+            output_rows: AtomicU64::new(0),
+            output_chunks: AtomicU64::new(0),
+            elapsed_ns: AtomicU64::new(0),
+            mem_context,
+        }
+    }
+
+    fn to_prost(&self, operator_id: u64) -> ProstExecutorStats {
+        ProstExecutorStats {
+            operator_id,
+            output_rows: self.output_rows.load(Ordering::Relaxed),
+            output_chunks: self.output_chunks.load(Ordering::Relaxed),
+            elapsed_ns: self.elapsed_ns.load(Ordering::Relaxed),
+            peak_memory_bytes: self.mem_context.get_peak_bytes().max(0) as u64,
+        }
+    }
+}
+
+/// Collects [`ExecutorStats`] of all executors in a task, keyed by the operator id of their plan
+/// node.
+#[derive(Default)]
+pub struct ExecutorStatsCollector {
+    stats: Mutex<BTreeMap<u64, Arc<ExecutorStats>>>,
+    /// Stats reported by the tasks executed remotely on behalf of this task, e.g. the input
+    /// stage of a local mode query. They are kept per task so that the frontend can tell the
+    /// number of tasks executing each plan node.
+    remote_stats: Mutex<Vec<ProstExecutorStats>>,
+}
+
+pub type ExecutorStatsCollectorRef = Arc<ExecutorStatsCollector>;
+
+impl ExecutorStatsCollector {
+    /// Returns the stats of `operator_id`, creating it with a child of `parent_mem_context` if not
+    /// registered yet.
+    pub fn get_or_register(
+        &self,
+        operator_id: u64,
+        parent_mem_context: &MemoryContext,
+    ) -> Arc<ExecutorStats> {
+        self.stats
+            .lock()
+            .entry(operator_id)
+            .or_insert_with(|| Arc::new(ExecutorStats::new(parent_mem_context.new_child())))
+            .clone()
+    }
+
+    /// Adds the stats reported by a remote task.
+    pub fn add_remote(&self, stats: Vec<ProstExecutorStats>) {
+        self.remote_stats.lock().extend(stats);
+    }
+
+    pub fn to_prost(&self) -> Vec<ProstExecutorStats> {
+        self.stats
+            .lock()
+            .iter()
+            .map(|(operator_id, stats)| stats.to_prost(*operator_id))
+            .chain(self.remote_stats.lock().iter().cloned())
+            .collect()
+    }
+}
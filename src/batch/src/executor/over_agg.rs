@@ -0,0 +1,265 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::row::{Row, Row2, RowExt};
+use risingwave_common::types::DataType;
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_expr::window_function::{evaluate_partition, WindowFuncCall};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// `OverAggExecutor` evaluates window functions over its input, which is sorted by the partition
+/// key and then by the order key. The rows of each partition are buffered until the partition
+/// ends, and output in the input order with the window function outputs appended.
+pub struct OverAggExecutor {
+    child: BoxedExecutor,
+    calls: Vec<WindowFuncCall>,
+    partition_by: Vec<usize>,
+    order_by: Vec<usize>,
+    schema: Schema,
+    identity: String,
+    chunk_size: usize,
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for OverAggExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        let [child]: [_; 1] = inputs.try_into().unwrap();
+
+        let over_agg_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::OverAgg
+        )?;
+
+        let calls: Vec<_> = over_agg_node
+            .get_calls()
+            .iter()
+            .map(WindowFuncCall::from_protobuf)
+            .try_collect()?;
+        let partition_by = over_agg_node
+            .get_partition_by()
+            .iter()
+            .map(|&idx| idx as usize)
+            .collect();
+        let order_by = over_agg_node
+            .get_order_by()
+            .iter()
+            .map(|column_order| column_order.index as usize)
+            .collect();
+
+        Ok(Box::new(Self::new(
+            child,
+            calls,
+            partition_by,
+            order_by,
+            source.plan_node().get_identity().clone(),
+            source.context.get_config().developer.batch_chunk_size,
+        )))
+    }
+}
+
+impl OverAggExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        calls: Vec<WindowFuncCall>,
+        partition_by: Vec<usize>,
+        order_by: Vec<usize>,
+        identity: String,
+        chunk_size: usize,
+    ) -> Self {
+        let schema = child
+            .schema()
+            .fields()
+            .iter()
+            .cloned()
+            .chain(
+                calls
+                    .iter()
+                    .map(|call| Field::unnamed(call.return_type.clone())),
+            )
+            .collect();
+        Self {
+            child,
+            calls,
+            partition_by,
+            order_by,
+            schema,
+            identity,
+            chunk_size,
+        }
+    }
+}
+
+impl Executor for OverAggExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl OverAggExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let input_types = self.child.schema().data_types();
+        let mut builder = DataChunkBuilder::new(self.schema.data_types(), self.chunk_size);
+
+        // The rows of the current partition, and its partition key.
+        let mut partition: Vec<Row> = vec![];
+        let mut partition_key: Option<Row> = None;
+
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?;
+            for row in chunk.rows() {
+                let key = row.project(&self.partition_by);
+                if partition_key.as_ref().map_or(true, |k| !Row2::eq(k, &key)) {
+                    for output in self.evaluate(&mut partition, &input_types, &mut builder)? {
+                        yield output;
+                    }
+                    partition_key = Some(key.into_owned_row());
+                }
+                partition.push(row.into_owned_row());
+            }
+        }
+        for output in self.evaluate(&mut partition, &input_types, &mut builder)? {
+            yield output;
+        }
+        if let Some(output) = builder.consume_all() {
+            yield output;
+        }
+    }
+
+    /// Evaluates the window functions over the rows of a partition and appends the output rows to
+    /// `builder`, returning the full chunks.
+    fn evaluate(
+        &self,
+        partition: &mut Vec<Row>,
+        input_types: &[DataType],
+        builder: &mut DataChunkBuilder,
+    ) -> Result<Vec<DataChunk>> {
+        if partition.is_empty() {
+            return Ok(vec![]);
+        }
+        let chunk = DataChunk::from_rows(partition, input_types);
+        partition.clear();
+        let outputs = evaluate_partition(&self.calls, &self.order_by, &chunk)?;
+        let cardinality = chunk.capacity();
+        let (mut columns, _) = chunk.into_parts();
+        columns.extend(outputs.into_iter().map(|array| Column::new(array.into())));
+        let chunk = DataChunk::new(columns, cardinality);
+
+        Ok(chunk
+            .rows()
+            .filter_map(|row| builder.append_one_row(row))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream::StreamExt;
+    use risingwave_common::array::DataChunkTestExt;
+    use risingwave_pb::expr::agg_call::{Arg, Type as AggType};
+    use risingwave_pb::expr::window_function::{GeneralType, Type as WindowFuncType};
+    use risingwave_pb::expr::{InputRefExpr, WindowFunction};
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    fn call(r#type: WindowFuncType, args: &[usize]) -> WindowFuncCall {
+        WindowFuncCall::from_protobuf(&WindowFunction {
+            r#type: Some(r#type),
+            args: args
+                .iter()
+                .map(|&idx| Arg {
+                    input: Some(InputRefExpr {
+                        column_idx: idx as i32,
+                    }),
+                    r#type: Some(DataType::Int64.to_protobuf()),
+                })
+                .collect(),
+            return_type: Some(DataType::Int64.to_protobuf()),
+            frame: Some(Default::default()),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_over_agg_executor() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "I I
+             1 1
+             1 2
+             1 2",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "I I
+             1 3
+             2 1
+             3 5",
+        ));
+
+        let executor = Box::new(OverAggExecutor::new(
+            Box::new(mock_executor),
+            vec![
+                call(WindowFuncType::General(GeneralType::Rank as i32), &[]),
+                call(WindowFuncType::Aggregate(AggType::Sum as i32), &[1]),
+            ],
+            vec![0],
+            vec![1],
+            "OverAggExecutor".to_string(),
+            1024,
+        ));
+        let mut stream = executor.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk,
+            DataChunk::from_pretty(
+                "I I I I
+                 1 1 1 1
+                 1 2 2 5
+                 1 2 2 5
+                 1 3 4 8
+                 2 1 1 1
+                 3 5 1 5",
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures::stream::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::RwError;
+
+use crate::executor::{BoxedDataChunkStream, BoxedExecutor, Executor, ExecutorStats};
+
+/// If executor stats are collected for the task, e.g. for `EXPLAIN ANALYZE`, we build a
+/// [`ProfileExecutor`] on top of the underlying executor to record its output and elapsed time.
+pub struct ProfileExecutor {
+    child: BoxedExecutor,
+    stats: Arc<ExecutorStats>,
+}
+
+impl ProfileExecutor {
+    pub fn new(child: BoxedExecutor, stats: Arc<ExecutorStats>) -> Self {
+        Self { child, stats }
+    }
+}
+
+impl Executor for ProfileExecutor {
+    fn schema(&self) -> &Schema {
+        self.child.schema()
+    }
+
+    fn identity(&self) -> &str {
+        "ProfileExecutor"
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl ProfileExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let stats = self.stats;
+        let mut child_stream = self.child.execute();
+
+        loop {
+            let start = Instant::now();
+            let chunk = child_stream.next().await;
+            stats
+                .elapsed_ns
+                .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+
+            let chunk = match chunk {
+                Some(chunk) => chunk?,
+                None => break,
+            };
+            stats
+                .output_rows
+                .fetch_add(chunk.cardinality() as u64, Ordering::Relaxed);
+            stats.output_chunks.fetch_add(1, Ordering::Relaxed);
+            yield chunk;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use risingwave_common::array::DataChunk;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+    use crate::executor::ExecutorStatsCollector;
+    use crate::task::MemoryContext;
+
+    #[tokio::test]
+    async fn test_profile_executor() {
+        let schema = Schema {
+            fields: vec![Field::unnamed(DataType::Int32)],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i
+             1
+             2",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i
+             3",
+        ));
+
+        let collector = ExecutorStatsCollector::default();
+        let stats = collector.get_or_register(1, &MemoryContext::root());
+        let executor = Box::new(ProfileExecutor::new(Box::new(mock_executor), stats.clone()));
+
+        let mut stream = executor.execute();
+        while let Some(chunk) = stream.next().await {
+            chunk.unwrap();
+        }
+
+        assert_eq!(stats.output_rows.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.output_chunks.load(Ordering::Relaxed), 2);
+        let prost = collector.to_prost();
+        assert_eq!(prost.len(), 1);
+        assert_eq!(prost[0].operator_id, 1);
+        assert_eq!(prost[0].output_rows, 3);
+    }
+}
@@ -217,6 +217,7 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             } else {
                 None
             },
+            time_travel_retention_seconds: None,
         };
         let value_indices = table_desc
             .get_value_indices()
@@ -251,6 +252,7 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
                 distribution,
                 table_option,
                 value_indices,
+                table_desc.read_prefix_len_hint as usize,
             );
 
             Ok(Box::new(RowSeqScanExecutor::new(
@@ -0,0 +1,230 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::row::{Row, Row2, RowExt};
+use risingwave_common::types::DataType;
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_expr::vector_op::session::{
+    session_gap_usecs, time_to_usecs, usecs_to_window_bound,
+};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// `SessionWindowExecutor` assigns session windows to its input, which is sorted by the time
+/// column. The rows of a session are buffered until the session ends, i.e. the next row is at
+/// least `gap` later. Rows with `NULL` time belong to no session and are discarded.
+pub struct SessionWindowExecutor {
+    child: BoxedExecutor,
+    time_col_idx: usize,
+    gap_usecs: i64,
+    window_type: DataType,
+    schema: Schema,
+    identity: String,
+    chunk_size: usize,
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for SessionWindowExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        let [child]: [_; 1] = inputs.try_into().unwrap();
+
+        let session_window_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::SessionWindow
+        )?;
+        let time_col_idx = session_window_node.get_time_col()?.column_idx as usize;
+        let gap_usecs = session_gap_usecs(session_window_node.get_gap()?.into())?;
+
+        Ok(Box::new(Self::new(
+            child,
+            time_col_idx,
+            gap_usecs,
+            source.plan_node().get_identity().clone(),
+            source.context.get_config().developer.batch_chunk_size,
+        )))
+    }
+}
+
+impl SessionWindowExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        time_col_idx: usize,
+        gap_usecs: i64,
+        identity: String,
+        chunk_size: usize,
+    ) -> Self {
+        let time_col_type = child.schema().fields()[time_col_idx].data_type();
+        let window_type = DataType::window_of(&time_col_type).unwrap();
+        let schema = child
+            .schema()
+            .fields()
+            .iter()
+            .cloned()
+            .chain([
+                Field::with_name(window_type.clone(), "window_start"),
+                Field::with_name(window_type.clone(), "window_end"),
+            ])
+            .collect();
+        Self {
+            child,
+            time_col_idx,
+            gap_usecs,
+            window_type,
+            schema,
+            identity,
+            chunk_size,
+        }
+    }
+}
+
+impl Executor for SessionWindowExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SessionWindowExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let mut builder = DataChunkBuilder::new(self.schema.data_types(), self.chunk_size);
+
+        // The rows of the current session, and the time of its first and last rows.
+        let mut session: Vec<Row> = vec![];
+        let mut session_start = 0;
+        let mut session_last = 0;
+
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?;
+            for row in chunk.rows() {
+                let time = match row.datum_at(self.time_col_idx) {
+                    Some(time) => time_to_usecs(time),
+                    None => continue,
+                };
+                if !session.is_empty() && time - session_last >= self.gap_usecs {
+                    for output in
+                        self.flush(&mut session, session_start, session_last, &mut builder)
+                    {
+                        yield output;
+                    }
+                }
+                if session.is_empty() {
+                    session_start = time;
+                }
+                session_last = time;
+                session.push(row.into_owned_row());
+            }
+        }
+        for output in self.flush(&mut session, session_start, session_last, &mut builder) {
+            yield output;
+        }
+        if let Some(output) = builder.consume_all() {
+            yield output;
+        }
+    }
+
+    /// Appends the rows of a finished session to `builder` with the session window, returning the
+    /// full chunks.
+    fn flush(
+        &self,
+        session: &mut Vec<Row>,
+        session_start: i64,
+        session_last: i64,
+        builder: &mut DataChunkBuilder,
+    ) -> Vec<DataChunk> {
+        let window = Row::new(vec![
+            Some(usecs_to_window_bound(session_start, &self.window_type)),
+            Some(usecs_to_window_bound(
+                session_last + self.gap_usecs,
+                &self.window_type,
+            )),
+        ]);
+        session
+            .drain(..)
+            .filter_map(|row| builder.append_one_row(row.chain(&window)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream::StreamExt;
+    use risingwave_common::array::DataChunkTestExt;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_session_window_executor() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Timestamp),
+        ]);
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "I TS
+             1 2022-02-02T10:00:00
+             2 .
+             3 2022-02-02T10:04:00
+             4 2022-02-02T10:09:00",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "I TS
+             5 2022-02-02T10:20:00
+             6 2022-02-02T10:24:59",
+        ));
+
+        // A gap of 5 minutes.
+        let executor = Box::new(SessionWindowExecutor::new(
+            Box::new(mock_executor),
+            1,
+            5 * 60 * 1_000_000,
+            "SessionWindowExecutor".to_string(),
+            1024,
+        ));
+        let mut stream = executor.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk,
+            DataChunk::from_pretty(
+                "I TS                  TS                  TS
+                 1 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:09:00
+                 3 2022-02-02T10:04:00 2022-02-02T10:00:00 2022-02-02T10:09:00
+                 4 2022-02-02T10:09:00 2022-02-02T10:09:00 2022-02-02T10:14:00
+                 5 2022-02-02T10:20:00 2022-02-02T10:20:00 2022-02-02T10:29:59
+                 6 2022-02-02T10:24:59 2022-02-02T10:20:00 2022-02-02T10:29:59",
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -11,20 +11,212 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::sync::Arc;
 
-use risingwave_common::error::Result;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema, TableId};
+use risingwave_common::error::ErrorCode::{ConnectorError, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+use risingwave_connector::source::{SplitImpl, SplitMetaData};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::catalog::source_info::SourceInfo;
+use risingwave_source::connector_source::{ConnectorSource, SourceContext};
+use risingwave_source::monitor::SourceMetrics;
+use risingwave_source::{SourceColumnDesc, SourceFormat};
 
-use crate::executor::{BoxedExecutor, BoxedExecutorBuilder, ExecutorBuilder};
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
 use crate::task::BatchTaskContext;
 
-pub struct SourceExecutor {}
+/// [`SourceExecutor`] reads a split of a bounded connector source to its end, e.g., a Kafka source
+/// with `scan.bounded.mode`.
+pub struct SourceExecutor {
+    connector_source: ConnectorSource,
+    split: SplitImpl,
+    column_ids: Vec<ColumnId>,
+    metrics: Arc<SourceMetrics>,
+    source_id: TableId,
+    schema: Schema,
+    identity: String,
+}
 
 #[async_trait::async_trait]
 impl BoxedExecutorBuilder for SourceExecutor {
     async fn new_boxed_executor<C: BatchTaskContext>(
-        _source: &ExecutorBuilder<'_, C>,
-        _inputs: Vec<BoxedExecutor>,
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
     ) -> Result<BoxedExecutor> {
-        todo!("Can't support SourceExecutor now!")
+        ensure!(inputs.is_empty(), "SourceExecutor should have no child!");
+        let source_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::Source
+        )?;
+
+        let info = match &source_node.get_info()?.source_info {
+            Some(SourceInfo::StreamSource(info)) => info,
+            _ => {
+                return Err(ProtocolError("only a connector source can be read".to_string()).into())
+            }
+        };
+        let format = SourceFormat::from_stream_source_info(info)?;
+        if format == SourceFormat::Protobuf && info.row_schema_location.is_empty() {
+            return Err(ProtocolError("protobuf file location not provided".to_string()).into());
+        }
+
+        let column_descs = source_node
+            .columns
+            .iter()
+            .map(|c| ColumnDesc::from(c.column_desc.as_ref().unwrap()))
+            .collect_vec();
+        // The hidden row id column is left null, since the rows are not stored anywhere.
+        let columns = column_descs
+            .iter()
+            .zip_eq(&source_node.columns)
+            .map(|(desc, c)| SourceColumnDesc {
+                skip_parse: c.is_hidden,
+                ..SourceColumnDesc::from(desc)
+            })
+            .collect_vec();
+
+        let source_manager = source.context.source_manager();
+        let connector_source = ConnectorSource::new(
+            format,
+            &info.row_schema_location,
+            info.use_schema_registry,
+            info.proto_message_name.clone(),
+            source_node.properties.clone(),
+            columns,
+            None,
+            source_manager.msg_buf_size(),
+        )
+        .await?;
+        let split = SplitImpl::restore_from_bytes(&source_node.split)
+            .map_err(|e| RwError::from(ConnectorError(e.into())))?;
+
+        Ok(Box::new(Self {
+            connector_source,
+            split,
+            column_ids: column_descs.iter().map(|c| c.column_id).collect(),
+            metrics: source_manager.metrics(),
+            source_id: TableId::new(source_node.source_id),
+            schema: Schema::new(column_descs.iter().map(Field::from).collect()),
+            identity: source.plan_node().get_identity().clone(),
+        }))
+    }
+}
+
+impl Executor for SourceExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SourceExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        // There is no actor in batch, so the actor id in the metrics is always 0.
+        let reader = self
+            .connector_source
+            .stream_reader(
+                Some(vec![self.split]),
+                self.column_ids,
+                self.metrics,
+                SourceContext::new(0, self.source_id),
+            )
+            .await?;
+
+        #[for_await]
+        for chunk in reader.into_stream() {
+            let (chunk, _ops) = chunk?.chunk.into_parts();
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures::TryStreamExt;
+    use risingwave_common::row::Row2;
+    use risingwave_common::types::DataType;
+    use risingwave_connector::source::{ConnectorProperties, SplitEnumeratorImpl};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_bounded_source() {
+        // A Nexmark source ends after the number of events, like a bounded Kafka source ends at
+        // the stop offsets of its splits.
+        let properties: HashMap<String, String> = [
+            ("connector", "nexmark"),
+            ("nexmark.table.type", "Bid"),
+            ("nexmark.event.num", "100"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let column_descs = ["auction", "bidder"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| ColumnDesc {
+                name: name.to_string(),
+                ..ColumnDesc::unnamed(ColumnId::new(i as i32), DataType::Int64)
+            })
+            .collect_vec();
+        let connector_source = ConnectorSource::new(
+            SourceFormat::Json,
+            "",
+            false,
+            String::new(),
+            properties.clone(),
+            column_descs.iter().map(SourceColumnDesc::from).collect(),
+            None,
+            16,
+        )
+        .await
+        .unwrap();
+        let split = SplitEnumeratorImpl::create(ConnectorProperties::extract(properties).unwrap())
+            .await
+            .unwrap()
+            .list_splits()
+            .await
+            .unwrap()
+            .into_iter()
+            .exactly_one()
+            .unwrap();
+
+        let executor = Box::new(SourceExecutor {
+            connector_source,
+            split,
+            column_ids: column_descs.iter().map(|c| c.column_id).collect(),
+            metrics: Arc::new(SourceMetrics::unused()),
+            source_id: TableId::default(),
+            schema: Schema::new(column_descs.iter().map(Field::from).collect()),
+            identity: "SourceExecutor".to_string(),
+        });
+        let chunks: Vec<DataChunk> = executor.execute().try_collect().await.unwrap();
+
+        let rows = chunks.iter().map(DataChunk::cardinality).sum::<usize>();
+        assert!(rows > 0 && rows <= 100);
+        for chunk in &chunks {
+            for row in chunk.rows() {
+                assert!(row.value_at(0).is_some());
+                assert!(row.value_at(1).is_some());
+            }
+        }
     }
 }
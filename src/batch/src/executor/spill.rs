@@ -0,0 +1,285 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::BuildHasher;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+
+use futures::future::try_join_all;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use prost::Message;
+use risingwave_common::array::DataChunk;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::config::BatchConfig;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::data::DataChunk as ProstDataChunk;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
+use twox_hash::XxHash64;
+
+use crate::executor::{BoxedDataChunkStream, Executor};
+
+/// The number of partitions that the state of an executor is spilled into.
+const SPILL_PARTITION_NUM: usize = 16;
+
+/// The maximum depth of spilling a partition again. A partition at this depth is processed in
+/// memory regardless of the limit, which only happens when too many rows have the same key, as
+/// they always fall into the same partition.
+const MAX_SPILL_DEPTH: usize = 3;
+
+/// Configures how a hash aggregation or hash join executor spills its state to local files.
+#[derive(Clone, Debug)]
+pub struct SpillConfig {
+    /// The memory limit of the state of an executor, beyond which it spills.
+    pub memory_limit_bytes: i64,
+    /// The directory of the spill files, or the temporary directory of the system if not set.
+    pub dir: Option<PathBuf>,
+}
+
+impl SpillConfig {
+    /// Returns `None` if spilling is disabled.
+    pub fn from_batch_config(config: &BatchConfig) -> Option<Self> {
+        config.spill_memory_limit_mb.map(|limit_mb| Self {
+            memory_limit_bytes: (limit_mb << 20) as i64,
+            dir: config.spill_dir.as_ref().map(PathBuf::from),
+        })
+    }
+
+    /// Whether an executor processing a partition spilled `depth` times should spill its state of
+    /// `mem_usage` bytes.
+    pub fn should_spill(&self, mem_usage: i64, depth: usize) -> bool {
+        mem_usage > self.memory_limit_bytes && depth < MAX_SPILL_DEPTH
+    }
+}
+
+/// A local file of spilled data chunks. It is removed by the OS once dropped.
+pub struct SpillFile {
+    file: File,
+    chunk_num: usize,
+}
+
+/// Writes data chunks to a [`SpillFile`], each of which is encoded as its length followed by its
+/// protobuf.
+struct SpillFileWriter {
+    writer: BufWriter<File>,
+    chunk_num: usize,
+}
+
+impl SpillFileWriter {
+    async fn new(dir: Option<&Path>) -> Result<Self> {
+        let dir = dir.map(Path::to_path_buf);
+        // Creating the file blocks, so it's done on the blocking threads.
+        let file = tokio::task::spawn_blocking(move || match dir {
+            Some(dir) => tempfile::tempfile_in(dir),
+            None => tempfile::tempfile(),
+        })
+        .await
+        .map_err(anyhow::Error::from)??;
+        Ok(Self {
+            writer: BufWriter::new(File::from_std(file)),
+            chunk_num: 0,
+        })
+    }
+
+    async fn write(&mut self, chunk: &DataChunk) -> Result<()> {
+        let bytes = chunk.to_protobuf().encode_to_vec();
+        self.writer.write_u32_le(bytes.len() as u32).await?;
+        self.writer.write_all(&bytes).await?;
+        self.chunk_num += 1;
+        Ok(())
+    }
+
+    async fn finish(mut self) -> Result<SpillFile> {
+        self.writer.flush().await?;
+        let mut file = self.writer.into_inner();
+        file.seek(SeekFrom::Start(0)).await?;
+        Ok(SpillFile {
+            file,
+            chunk_num: self.chunk_num,
+        })
+    }
+}
+
+/// Builds the hashers to partition the rows. They are seeded by the depth of spilling, so that the
+/// rows of a partition are spread over the partitions when it is spilled again.
+#[derive(Clone, Copy)]
+struct SpillHasherBuilder(u64);
+
+impl BuildHasher for SpillHasherBuilder {
+    type Hasher = XxHash64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        XxHash64::with_seed(self.0)
+    }
+}
+
+/// Spills data chunks to [`SPILL_PARTITION_NUM`] files by the hash of their key columns, so that
+/// the rows with the same key are in the same partition.
+pub struct SpillPartitioner {
+    key_indices: Vec<usize>,
+    hasher_builder: SpillHasherBuilder,
+    writers: Vec<SpillFileWriter>,
+}
+
+impl SpillPartitioner {
+    pub async fn new(config: &SpillConfig, key_indices: Vec<usize>, depth: usize) -> Result<Self> {
+        let writers = try_join_all(
+            (0..SPILL_PARTITION_NUM).map(|_| SpillFileWriter::new(config.dir.as_deref())),
+        )
+        .await?;
+        Ok(Self {
+            key_indices,
+            hasher_builder: SpillHasherBuilder(depth as u64),
+            writers,
+        })
+    }
+
+    pub async fn write(&mut self, chunk: DataChunk) -> Result<()> {
+        let chunk = chunk.compact();
+        if chunk.cardinality() == 0 {
+            return Ok(());
+        }
+        let partitions = chunk
+            .get_hash_values(&self.key_indices, self.hasher_builder)
+            .into_iter()
+            .map(|hash_code| hash_code.hash_code() as usize % SPILL_PARTITION_NUM)
+            .collect_vec();
+        for (partition, writer) in self.writers.iter_mut().enumerate() {
+            let visibility: Bitmap = partitions.iter().map(|&p| p == partition).collect();
+            if visibility.num_high_bits() > 0 {
+                writer
+                    .write(&chunk.with_visibility(visibility).compact())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the spilled partitions. The same key of the other partitioners with the same depth
+    /// falls into the partition at the same index.
+    pub async fn finish(self) -> Result<Vec<SpillFile>> {
+        try_join_all(self.writers.into_iter().map(SpillFileWriter::finish)).await
+    }
+}
+
+/// `SpillFileExecutor` reads the data chunks of a [`SpillFile`], which serves as the input of the
+/// executor processing a spilled partition.
+pub struct SpillFileExecutor {
+    file: SpillFile,
+    schema: Schema,
+    identity: String,
+}
+
+impl SpillFileExecutor {
+    pub fn new(file: SpillFile, schema: Schema, identity: String) -> Self {
+        Self {
+            file,
+            schema,
+            identity,
+        }
+    }
+}
+
+impl Executor for SpillFileExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SpillFileExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let mut reader = BufReader::new(self.file.file);
+        for _ in 0..self.file.chunk_num {
+            let len = reader.read_u32_le().await? as usize;
+            let mut bytes = vec![0; len];
+            reader.read_exact(&mut bytes).await?;
+            let chunk = ProstDataChunk::decode(&bytes[..]).map_err(anyhow::Error::from)?;
+            yield DataChunk::from_protobuf(&chunk)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures::StreamExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spill_partitioner() {
+        let config = SpillConfig {
+            memory_limit_bytes: 0,
+            dir: None,
+        };
+        let mut partitioner = SpillPartitioner::new(&config, vec![0], 0).await.unwrap();
+        partitioner
+            .write(DataChunk::from_pretty(
+                "i i
+                 1 1
+                 2 2
+                 1 3",
+            ))
+            .await
+            .unwrap();
+        partitioner
+            .write(DataChunk::from_pretty(
+                "i i
+                 3 4
+                 1 5",
+            ))
+            .await
+            .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+        ]);
+        let mut key_partitions = HashMap::new();
+        let mut rows = vec![];
+        for (partition, file) in partitioner.finish().await.unwrap().into_iter().enumerate() {
+            let executor = Box::new(SpillFileExecutor::new(
+                file,
+                schema.clone(),
+                "SpillFileExecutor".to_string(),
+            ));
+            let mut stream = executor.execute();
+            while let Some(chunk) = stream.next().await {
+                for row in chunk.unwrap().rows() {
+                    let key = row.value_at(0).unwrap().into_int32();
+                    // The rows with the same key are in the same partition.
+                    assert_eq!(*key_partitions.entry(key).or_insert(partition), partition);
+                    rows.push(row.value_at(1).unwrap().into_int32());
+                }
+            }
+        }
+        rows.sort();
+        assert_eq!(rows, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -15,6 +15,7 @@
 use std::convert::Into;
 use std::sync::Arc;
 
+use risingwave_common::error::RwError;
 use risingwave_pb::batch_plan::TaskOutputId;
 use risingwave_pb::task_service::task_service_server::TaskService;
 use risingwave_pb::task_service::{
@@ -24,7 +25,8 @@ use risingwave_pb::task_service::{
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
-use crate::rpc::service::exchange::GrpcExchangeWriter;
+use crate::executor::ExecutorStatsCollectorRef;
+use crate::rpc::service::exchange::{ExchangeWriter, GrpcExchangeWriter};
 use crate::task::{
     self, BatchEnvironment, BatchManager, BatchTaskExecution, ComputeNodeContext, TaskId,
 };
@@ -41,6 +43,15 @@ impl BatchServiceImpl {
     pub fn new(mgr: Arc<BatchManager>, env: BatchEnvironment) -> Self {
         BatchServiceImpl { mgr, env }
     }
+
+    /// The last response of a locally executed task, reporting the stats of its executors. The
+    /// output of the task has been drained, so all the executors have finished.
+    fn executor_stats_response(executor_stats: &ExecutorStatsCollectorRef) -> GetDataResponse {
+        GetDataResponse {
+            executor_stats: executor_stats.to_prost(),
+            ..Default::default()
+        }
+    }
 }
 pub(crate) type TaskInfoResponseResult = std::result::Result<TaskInfoResponse, Status>;
 #[async_trait::async_trait]
@@ -57,18 +68,24 @@ impl TaskService for BatchServiceImpl {
             task_id,
             plan,
             epoch,
+            collect_executor_stats,
         } = request.into_inner();
 
+        let mut context = ComputeNodeContext::new(
+            self.env.clone(),
+            TaskId::from(task_id.as_ref().expect("no task id found")),
+        );
+        if collect_executor_stats {
+            context = context.with_executor_stats(Default::default());
+        }
+
         let res = self
             .mgr
             .fire_task(
                 task_id.as_ref().expect("no task id found"),
                 plan.expect("no plan found").clone(),
                 epoch,
-                ComputeNodeContext::new(
-                    self.env.clone(),
-                    TaskId::from(task_id.as_ref().expect("no task id found")),
-                ),
+                context,
             )
             .await;
         match res {
@@ -108,10 +125,15 @@ impl TaskService for BatchServiceImpl {
             task_id,
             plan,
             epoch,
+            collect_executor_stats,
         } = req.into_inner();
         let task_id = task_id.expect("no task id found");
         let plan = plan.expect("no plan found").clone();
-        let context = ComputeNodeContext::new_for_local(self.env.clone());
+        let mut context = ComputeNodeContext::new_for_local(self.env.clone());
+        let executor_stats = collect_executor_stats.then(ExecutorStatsCollectorRef::default);
+        if let Some(executor_stats) = executor_stats.clone() {
+            context = context.with_executor_stats(executor_stats);
+        }
         trace!(
             "local execute request: plan:{:?} with task id:{:?}",
             plan,
@@ -147,9 +169,21 @@ impl TaskService for BatchServiceImpl {
         let finish = output
             .take_data_with_num(&mut writer, tx.capacity())
             .await?;
-        if !finish {
+        // Write the rest in background, as the channel may be full now.
+        if !finish || executor_stats.is_some() {
             self.mgr.runtime().spawn(async move {
-                match output.take_data(&mut writer).await {
+                let res = async {
+                    if !finish {
+                        output.take_data(&mut writer).await?;
+                    }
+                    if let Some(executor_stats) = executor_stats {
+                        writer
+                            .write(Self::executor_stats_response(&executor_stats))
+                            .await?;
+                    }
+                    Ok::<_, RwError>(())
+                };
+                match res.await {
                     Ok(_) => Ok(()),
                     Err(e) => tx.send(Err(e.into())).await,
                 }
@@ -21,8 +21,8 @@ use risingwave_source::TableSourceManagerRef;
 use risingwave_storage::StateStoreImpl;
 
 use super::TaskId;
-use crate::executor::BatchTaskMetricsWithTaskLabels;
-use crate::task::{BatchEnvironment, TaskOutput, TaskOutputId};
+use crate::executor::{BatchTaskMetricsWithTaskLabels, ExecutorStatsCollectorRef};
+use crate::task::{BatchEnvironment, MemoryContext, TaskOutput, TaskOutputId};
 
 /// Context for batch task execution.
 ///
@@ -53,6 +53,13 @@ pub trait BatchTaskContext: Clone + Send + Sync + 'static {
 
     /// Get config for batch environment
     fn get_config(&self) -> &BatchConfig;
+
+    /// Get the root memory context of the task.
+    fn mem_context(&self) -> MemoryContext;
+
+    /// Get the collector of executor stats.
+    /// None indicates that not collect executor stats.
+    fn executor_stats(&self) -> Option<ExecutorStatsCollectorRef>;
 }
 
 /// Batch task context on compute node.
@@ -61,6 +68,9 @@ pub struct ComputeNodeContext {
     env: BatchEnvironment,
     // None: Local mode don't record metrics.
     task_metrics: Option<BatchTaskMetricsWithTaskLabels>,
+    mem_context: MemoryContext,
+    // None: Only collected when requested, e.g. for `EXPLAIN ANALYZE`.
+    executor_stats: Option<ExecutorStatsCollectorRef>,
 }
 
 impl BatchTaskContext for ComputeNodeContext {
@@ -97,6 +107,14 @@ impl BatchTaskContext for ComputeNodeContext {
     fn get_config(&self) -> &BatchConfig {
         self.env.config()
     }
+
+    fn mem_context(&self) -> MemoryContext {
+        self.mem_context.clone()
+    }
+
+    fn executor_stats(&self) -> Option<ExecutorStatsCollectorRef> {
+        self.executor_stats.clone()
+    }
 }
 
 impl ComputeNodeContext {
@@ -105,6 +123,8 @@ impl ComputeNodeContext {
         Self {
             env: BatchEnvironment::for_test(),
             task_metrics: None,
+            mem_context: MemoryContext::root(),
+            executor_stats: None,
         }
     }
 
@@ -113,13 +133,23 @@ impl ComputeNodeContext {
         Self {
             env,
             task_metrics: Some(task_metrics),
+            mem_context: MemoryContext::root(),
+            executor_stats: None,
         }
     }
 
+    #[must_use]
+    pub fn with_executor_stats(mut self, executor_stats: ExecutorStatsCollectorRef) -> Self {
+        self.executor_stats = Some(executor_stats);
+        self
+    }
+
     pub fn new_for_local(env: BatchEnvironment) -> Self {
         Self {
             env,
             task_metrics: None,
+            mem_context: MemoryContext::root(),
+            executor_stats: None,
         }
     }
 }
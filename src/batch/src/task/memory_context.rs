@@ -0,0 +1,103 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+/// Tracks the memory used by a batch task or by one of its executors.
+///
+/// Contexts form a tree: the memory accounted to a context is also accounted to all of its
+/// ancestors, so the root context of a task reflects the memory used by the whole task. When the
+/// last clone of a context is dropped, the memory still accounted to it is released from its
+/// ancestors.
+#[derive(Clone, Default)]
+pub struct MemoryContext {
+    inner: Arc<MemoryContextInner>,
+}
+
+#[derive(Default)]
+struct MemoryContextInner {
+    parent: Option<MemoryContext>,
+    used: AtomicI64,
+    peak: AtomicI64,
+}
+
+impl MemoryContext {
+    /// Creates a context without parent, e.g. for a task.
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Creates a context whose memory is also accounted to `self`, e.g. for an executor.
+    pub fn new_child(&self) -> Self {
+        Self {
+            inner: Arc::new(MemoryContextInner {
+                parent: Some(self.clone()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Accounts `bytes` to this context and all its ancestors. A negative value releases memory.
+    pub fn add(&self, bytes: i64) {
+        let used = self.inner.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.inner.peak.fetch_max(used, Ordering::Relaxed);
+        if let Some(parent) = &self.inner.parent {
+            parent.add(bytes);
+        }
+    }
+
+    pub fn get_bytes_used(&self) -> i64 {
+        self.inner.used.load(Ordering::Relaxed)
+    }
+
+    /// The maximum of `get_bytes_used` ever observed.
+    pub fn get_peak_bytes(&self) -> i64 {
+        self.inner.peak.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for MemoryContextInner {
+    fn drop(&mut self) {
+        if let Some(parent) = &self.parent {
+            parent.add(-*self.used.get_mut());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_context() {
+        let root = MemoryContext::root();
+        let child1 = root.new_child();
+        let child2 = root.new_child();
+
+        child1.add(100);
+        child2.add(50);
+        assert_eq!(root.get_bytes_used(), 150);
+
+        child1.add(-80);
+        assert_eq!(child1.get_bytes_used(), 20);
+        assert_eq!(child1.get_peak_bytes(), 100);
+        assert_eq!(root.get_bytes_used(), 70);
+        assert_eq!(root.get_peak_bytes(), 150);
+
+        drop(child2);
+        assert_eq!(root.get_bytes_used(), 20);
+        assert_eq!(root.get_peak_bytes(), 150);
+    }
+}
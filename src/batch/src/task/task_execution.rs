@@ -138,6 +138,7 @@ impl TaskOutput {
                     let resp = GetDataResponse {
                         status: Default::default(),
                         record_batch: Some(pb),
+                        ..Default::default()
                     };
                     writer.write(resp).await?;
                 }
@@ -395,6 +396,11 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
                     task_info: Some(TaskInfo {
                         task_id: Some(TaskId::default().to_prost()),
                         task_status: task_status.into(),
+                        executor_stats: self
+                            .context
+                            .executor_stats()
+                            .map(|stats| stats.to_prost())
+                            .unwrap_or_default(),
                     }),
                     // TODO: Fill the real status.
                     ..Default::default()
@@ -262,6 +262,7 @@ mod tests {
             root: Some(PlanNode {
                 children: vec![],
                 identity: "".to_string(),
+                operator_id: 0,
                 node_body: Some(NodeBody::Values(ValuesNode {
                     tuples: vec![],
                     fields: vec![],
@@ -298,6 +299,7 @@ mod tests {
             root: Some(PlanNode {
                 children: vec![],
                 identity: "".to_string(),
+                operator_id: 0,
                 node_body: Some(NodeBody::TableFunction(TableFunctionNode {
                     table_function: Some(TableFunction {
                         function_type: Type::Generate as i32,
@@ -70,10 +70,12 @@ pub async fn run(args: Args, stop: oneshot::Receiver<()>) {
         flush_buffer_hooks: vec![hook],
     };
 
-    let cache: FileCache<Index, CacheValue> =
-        FileCache::open(options, Arc::new(FileCacheMetrics::new(Registry::new())))
-            .await
-            .unwrap();
+    let cache: FileCache<Index, CacheValue> = FileCache::open(
+        options,
+        Arc::new(FileCacheMetrics::new(Registry::new(), "bench")),
+    )
+    .await
+    .unwrap();
 
     let iostat_path = match cache.fs_type() {
         FsType::Tmpfs => panic!("file cache bench is not supported on tmpfs"),
@@ -123,6 +123,7 @@ where
             | Info::Sink(_)
             | Info::Index(_)
             | Info::View(_)
+            | Info::Function(_)
             | Info::User(_) => notification.version > catalog_version,
             Info::ParallelUnitMapping(_) => notification.version > parallel_unit_mapping_version,
             Info::Node(_) => notification.version > worker_node_version,
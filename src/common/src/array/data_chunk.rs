@@ -24,11 +24,12 @@ use crate::array::column::Column;
 use crate::array::data_chunk_iter::RowRef;
 use crate::array::{ArrayBuilderImpl, StructValue};
 use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
 use crate::hash::HashCode;
 use crate::row::{Row, Row2};
 use crate::types::struct_type::StructType;
 use crate::types::to_text::ToText;
-use crate::types::{DataType, Datum, NaiveDateTimeWrapper, ToOwnedDatum};
+use crate::types::{DataType, Datum, DatumRef, NaiveDateTimeWrapper, ScalarRefImpl, ToOwnedDatum};
 use crate::util::hash_util::finalize_hashers;
 use crate::util::value_encoding::serialize_datum;
 
@@ -436,6 +437,36 @@ impl DataChunk {
     }
 }
 
+impl EstimateSize for DataChunk {
+    fn estimated_heap_size(&self) -> usize {
+        let fixed_size = self.capacity() * self.dimension() * std::mem::size_of::<Datum>();
+        let variable_size: usize = self
+            .columns
+            .iter()
+            .flat_map(|column| column.array_ref().iter())
+            .map(datum_heap_size)
+            .sum();
+        fixed_size + variable_size
+    }
+}
+
+/// Estimates the heap size taken by the variable-length part of a datum.
+fn datum_heap_size(datum: DatumRef<'_>) -> usize {
+    let nested_size = |datums: Vec<DatumRef<'_>>| {
+        datums.len() * std::mem::size_of::<Datum>()
+            + datums.into_iter().map(datum_heap_size).sum::<usize>()
+    };
+    match datum {
+        Some(ScalarRefImpl::Utf8(s)) => s.len(),
+        Some(ScalarRefImpl::Bytea(b)) => b.len(),
+        Some(ScalarRefImpl::List(list)) => nested_size(list.values_ref()),
+        Some(ScalarRefImpl::Struct(st)) => nested_size(st.fields_ref()),
+        // The in-memory size of a json value is approximated by its encoded length.
+        Some(ScalarRefImpl::Jsonb(jsonb)) => jsonb.value_serialize().len(),
+        _ => 0,
+    }
+}
+
 impl fmt::Debug for DataChunk {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -640,6 +671,7 @@ impl DataChunkTestExt for DataChunk {
 mod tests {
 
     use crate::array::*;
+    use crate::collection::estimate_size::EstimateSize;
     use crate::{column, column_nonnull};
 
     #[test]
@@ -782,4 +814,16 @@ mod tests {
         assert_eq!(chunk.clone().reorder_columns(&[0, 1, 2]), chunk);
         assert_eq!(chunk.reorder_columns(&[]).cardinality(), 3);
     }
+
+    #[test]
+    fn test_estimated_heap_size() {
+        let datum_size = std::mem::size_of::<crate::types::Datum>();
+        let chunk = DataChunk::from_pretty(
+            "I T
+             1 abc
+             2 .
+             3 hello",
+        );
+        assert_eq!(chunk.estimated_heap_size(), 3 * 2 * datum_size + 3 + 5);
+    }
 }
@@ -0,0 +1,509 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+use risingwave_pb::data::{Array as ProstArray, ArrayType};
+use serde_json::Value;
+
+use super::value_reader::Utf8ValueReader;
+use super::{
+    read_string_array, Array, ArrayBuilder, ArrayImpl, ArrayIterator, ArrayMeta, ArrayResult,
+    Utf8ArrayBuilder,
+};
+use crate::array::ArrayBuilderImpl;
+use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::types::{Scalar, ScalarImpl, ScalarRef};
+
+/// An owned JSONB value. The value is boxed to keep `ScalarImpl` small.
+///
+/// Objects are backed by a `BTreeMap`, so the keys are always sorted and deduplicated (the last
+/// one wins) just like the `jsonb` type of PostgreSQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonbVal(Box<Value>);
+
+/// A reference to a JSONB value, which may be an element of [`JsonbArray`] or a [`JsonbVal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonbRef<'a>(&'a Value);
+
+impl JsonbVal {
+    pub fn from_serde(value: Value) -> Self {
+        Self(Box::new(value))
+    }
+
+    /// The JSON `null` value, which is different from the SQL NULL.
+    pub fn null() -> Self {
+        Self::from_serde(Value::Null)
+    }
+
+    /// Deserializes a value serialized by [`JsonbRef::value_serialize`].
+    pub fn value_deserialize(buf: &[u8]) -> Option<Self> {
+        serde_json::from_slice(buf).ok().map(Self::from_serde)
+    }
+
+    /// Deserializes a value serialized by [`JsonbRef::memcmp_serialize`].
+    pub fn memcmp_deserialize(
+        deserializer: &mut memcomparable::Deserializer<impl bytes::Buf>,
+    ) -> memcomparable::Result<Self> {
+        let text = <String as serde::Deserialize>::deserialize(deserializer)?;
+        text.parse()
+            .map_err(|e| memcomparable::Error::Message(format!("invalid jsonb: {}", e)))
+    }
+}
+
+impl FromStr for JsonbVal {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map(Self::from_serde)
+    }
+}
+
+impl fmt::Display for JsonbVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_scalar_ref().fmt(f)
+    }
+}
+
+/// The ordering of JSONB values is the ordering of their serialized text, which is consistent
+/// with the memcomparable encoding. Comparison operators are not exposed for `jsonb` yet.
+impl PartialOrd for JsonbVal {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbVal {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_scalar_ref().cmp(&other.as_scalar_ref())
+    }
+}
+
+impl PartialOrd for JsonbRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbRef<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value_serialize().cmp(&other.value_serialize())
+    }
+}
+
+impl Scalar for JsonbVal {
+    type ScalarRefType<'a> = JsonbRef<'a>;
+
+    fn as_scalar_ref(&self) -> JsonbRef<'_> {
+        JsonbRef(&self.0)
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Jsonb(self)
+    }
+}
+
+impl<'a> ScalarRef<'a> for JsonbRef<'a> {
+    type ScalarType = JsonbVal;
+
+    fn to_owned_scalar(&self) -> JsonbVal {
+        JsonbVal::from_serde(self.0.clone())
+    }
+
+    fn hash_scalar<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value_serialize().hash(state)
+    }
+}
+
+/// Formats JSONB values the way PostgreSQL does, i.e. with a space after `,` and `:`.
+struct ToTextFormatter;
+
+impl serde_json::ser::Formatter for ToTextFormatter {
+    fn begin_array_value<W>(&mut self, writer: &mut W, first: bool) -> std::io::Result<()>
+    where
+        W: ?Sized + std::io::Write,
+    {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> std::io::Result<()>
+    where
+        W: ?Sized + std::io::Write,
+    {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_value<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + std::io::Write,
+    {
+        writer.write_all(b": ")
+    }
+}
+
+impl fmt::Display for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = Vec::new();
+        let mut ser = serde_json::Serializer::with_formatter(&mut buf, ToTextFormatter);
+        serde::Serialize::serialize(self.0, &mut ser).map_err(|_| fmt::Error)?;
+        // Serialized JSON is always valid UTF-8.
+        f.write_str(std::str::from_utf8(&buf).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl<'a> JsonbRef<'a> {
+    pub fn value(&self) -> &'a Value {
+        self.0
+    }
+
+    /// Serializes the value into its compact text, which is used by the value encoding and the
+    /// memcomparable encoding.
+    pub fn value_serialize(&self) -> Vec<u8> {
+        serde_json::to_vec(self.0).unwrap()
+    }
+
+    /// Serializes the compact text as a string, so that the memcomparable encoding has the same
+    /// order as [`Ord`].
+    pub fn memcmp_serialize(
+        &self,
+        serializer: &mut memcomparable::Serializer<impl bytes::BufMut>,
+    ) -> memcomparable::Result<()> {
+        serde::Serialize::serialize(&self.0.to_string(), serializer)
+    }
+
+    /// Whether the value is the JSON `null`.
+    pub fn is_jsonb_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    /// The type of the outermost value, as returned by `jsonb_typeof`.
+    pub fn type_name(&self) -> &'static str {
+        match self.0 {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Gets the field `name` of an object. Returns `None` if it is not an object or the field
+    /// does not exist.
+    pub fn access_object_field(&self, name: &str) -> Option<Self> {
+        self.0.get(name).map(Self)
+    }
+
+    /// Gets the element at `idx` of an array, where a negative index counts from the end. Returns
+    /// `None` if it is not an array or the index is out of bounds.
+    pub fn access_array_element(&self, idx: i32) -> Option<Self> {
+        let array = self.0.as_array()?;
+        let idx = if idx < 0 {
+            array.len().checked_sub(idx.unsigned_abs() as usize)?
+        } else {
+            idx as usize
+        };
+        array.get(idx).map(Self)
+    }
+
+    /// Follows the `path` like the `#>` operator. Each element of the path is either a field name
+    /// of an object or an index of an array.
+    pub fn access_path<'b>(&self, path: impl IntoIterator<Item = &'b str>) -> Option<Self> {
+        let mut current = *self;
+        for key in path {
+            current = match current.0 {
+                Value::Object(_) => current.access_object_field(key)?,
+                Value::Array(_) => current.access_array_element(key.parse().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Converts the value to text like the `->>` operator, i.e. a string is unquoted and the JSON
+    /// `null` becomes `None`.
+    pub fn force_str(&self) -> Option<String> {
+        match self.0 {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            _ => Some(self.to_string()),
+        }
+    }
+
+    /// Whether `other` is contained in this value, as the `@>` operator. See the
+    /// [PostgreSQL documentation](https://www.postgresql.org/docs/current/datatype-json.html#JSON-CONTAINMENT)
+    /// for the semantics.
+    pub fn contains(&self, other: JsonbRef<'_>) -> bool {
+        match (self.0, other.0) {
+            // As a special exception, an array may contain a primitive value at the top level.
+            (Value::Array(array), right) if !right.is_array() && !right.is_object() => {
+                array.iter().any(|left| value_contains(left, right))
+            }
+            (left, right) => value_contains(left, right),
+        }
+    }
+
+    /// Whether the string `key` exists as a top-level key of an object or an element of an
+    /// array, as the `?` operator.
+    pub fn exists(&self, key: &str) -> bool {
+        match self.0 {
+            Value::Object(object) => object.contains_key(key),
+            Value::Array(array) => array.iter().any(|v| v.as_str() == Some(key)),
+            Value::String(s) => s == key,
+            _ => false,
+        }
+    }
+
+    /// The elements of an array, as `jsonb_array_elements`. Returns `None` if it is not an array.
+    pub fn array_elements(&self) -> Option<impl Iterator<Item = JsonbRef<'a>>> {
+        Some(self.0.as_array()?.iter().map(Self))
+    }
+}
+
+fn value_contains(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => right
+            .iter()
+            .all(|(key, r)| left.get(key).map_or(false, |l| value_contains(l, r))),
+        (Value::Array(left), Value::Array(right)) => right
+            .iter()
+            .all(|r| left.iter().any(|l| value_contains(l, r))),
+        (Value::Number(left), Value::Number(right)) => left.as_f64() == right.as_f64(),
+        (left, right) => left == right,
+    }
+}
+
+/// `JsonbArray` keeps the parsed values, where a NULL is stored as the JSON `null`. It is
+/// transmitted as text in the same layout as `Utf8Array`.
+#[derive(Debug, Clone)]
+pub struct JsonbArray {
+    bitmap: Bitmap,
+    data: Vec<Value>,
+}
+
+impl Array for JsonbArray {
+    type Builder = JsonbArrayBuilder;
+    type Iter<'a> = ArrayIterator<'a, Self>;
+    type OwnedItem = JsonbVal;
+    type RefItem<'a> = JsonbRef<'a>;
+
+    fn value_at(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null(idx) {
+            Some(JsonbRef(&self.data[idx]))
+        } else {
+            None
+        }
+    }
+
+    unsafe fn value_at_unchecked(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null_unchecked(idx) {
+            Some(JsonbRef(self.data.get_unchecked(idx)))
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> ArrayIterator<'_, Self> {
+        ArrayIterator::new(self)
+    }
+
+    fn to_protobuf(&self) -> ProstArray {
+        let mut builder = Utf8ArrayBuilder::new(self.len());
+        for value in self.iter() {
+            builder.append(value.map(|v| v.value_serialize()).as_deref().map(|v| {
+                // Serialized JSON is always valid UTF-8.
+                std::str::from_utf8(v).unwrap()
+            }));
+        }
+        ProstArray {
+            array_type: ArrayType::Jsonb as i32,
+            ..builder.finish().to_protobuf()
+        }
+    }
+
+    fn null_bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    fn into_null_bitmap(self) -> Bitmap {
+        self.bitmap
+    }
+
+    fn set_bitmap(&mut self, bitmap: Bitmap) {
+        self.bitmap = bitmap;
+    }
+
+    fn create_builder(&self, capacity: usize) -> ArrayBuilderImpl {
+        let array_builder = JsonbArrayBuilder::new(capacity);
+        ArrayBuilderImpl::Jsonb(array_builder)
+    }
+}
+
+impl JsonbArray {
+    pub fn from_protobuf(array: &ProstArray, cardinality: usize) -> ArrayResult<ArrayImpl> {
+        let text_array =
+            read_string_array::<Utf8ArrayBuilder, Utf8ValueReader>(array, cardinality)?;
+        let mut builder = JsonbArrayBuilder::new(cardinality);
+        for text in text_array.as_utf8().iter() {
+            let value = text
+                .map(|text| {
+                    text.parse::<JsonbVal>()
+                        .map_err(|e| anyhow::anyhow!("failed to parse jsonb: {}", e))
+                })
+                .transpose()?;
+            builder.append(value.as_ref().map(|v| v.as_scalar_ref()));
+        }
+        Ok(builder.finish().into())
+    }
+}
+
+/// `JsonbArrayBuilder` use `JsonbRef` to build an `JsonbArray`.
+#[derive(Debug)]
+pub struct JsonbArrayBuilder {
+    bitmap: BitmapBuilder,
+    data: Vec<Value>,
+}
+
+impl ArrayBuilder for JsonbArrayBuilder {
+    type ArrayType = JsonbArray;
+
+    fn with_meta(capacity: usize, _meta: ArrayMeta) -> Self {
+        Self {
+            bitmap: BitmapBuilder::with_capacity(capacity),
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn append(&mut self, value: Option<JsonbRef<'_>>) {
+        match value {
+            Some(x) => {
+                self.bitmap.append(true);
+                self.data.push(x.0.clone());
+            }
+            None => {
+                self.bitmap.append(false);
+                self.data.push(Value::Null);
+            }
+        }
+    }
+
+    fn append_array(&mut self, other: &JsonbArray) {
+        for bit in other.bitmap.iter() {
+            self.bitmap.append(bit);
+        }
+        self.data.extend_from_slice(&other.data);
+    }
+
+    fn pop(&mut self) -> Option<()> {
+        self.data.pop().map(|_| self.bitmap.pop().unwrap())
+    }
+
+    fn finish(self) -> JsonbArray {
+        JsonbArray {
+            bitmap: self.bitmap.finish(),
+            data: self.data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jsonb(s: &str) -> JsonbVal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_jsonb_to_text() {
+        let v = jsonb(r#"{"b": [1, 2.5, null], "a": {"c": "d"}}"#);
+        assert_eq!(v.to_string(), r#"{"a": {"c": "d"}, "b": [1, 2.5, null]}"#);
+        assert_eq!(
+            v.as_scalar_ref().value_serialize(),
+            br#"{"a":{"c":"d"},"b":[1,2.5,null]}"#
+        );
+    }
+
+    #[test]
+    fn test_jsonb_access() {
+        let v = jsonb(r#"{"a": [1, {"b": "c"}], "d": null}"#);
+        let v = v.as_scalar_ref();
+        let a = v.access_object_field("a").unwrap();
+        assert_eq!(a.type_name(), "array");
+        assert_eq!(
+            a.access_array_element(-1).unwrap().to_string(),
+            r#"{"b": "c"}"#
+        );
+        assert!(a.access_array_element(2).is_none());
+        assert!(a.access_array_element(-3).is_none());
+        assert_eq!(
+            v.access_path(["a", "1", "b"]).unwrap().force_str().unwrap(),
+            "c"
+        );
+        assert!(v.access_path(["a", "x"]).is_none());
+        assert!(v.access_object_field("d").unwrap().force_str().is_none());
+        assert!(v.access_object_field("e").is_none());
+    }
+
+    #[test]
+    fn test_jsonb_contains_exists() {
+        let contains =
+            |l: &str, r: &str| jsonb(l).as_scalar_ref().contains(jsonb(r).as_scalar_ref());
+        assert!(contains(r#"{"a": 1, "b": [1, 2]}"#, r#"{"b": [2]}"#));
+        assert!(!contains(r#"{"a": 1, "b": [1, 2]}"#, r#"{"b": 2}"#));
+        assert!(contains(r#"[1, [2, 3]]"#, r#"[[3], 1]"#));
+        assert!(!contains(r#"[1, 2]"#, r#"[[1]]"#));
+        assert!(contains(r#"["foo", "bar"]"#, r#""foo""#));
+        assert!(contains(r#"1"#, r#"1.0"#));
+
+        let v = jsonb(r#"{"a": 1, "b": ["c"]}"#);
+        assert!(v.as_scalar_ref().exists("a"));
+        assert!(!v.as_scalar_ref().exists("c"));
+        assert!(jsonb(r#"["a", 1]"#).as_scalar_ref().exists("a"));
+    }
+
+    #[test]
+    fn test_jsonb_array_protobuf() {
+        let values = [Some(jsonb(r#"{"a": 1}"#)), None, Some(JsonbVal::null())];
+        let mut builder = JsonbArrayBuilder::new(values.len());
+        for v in &values {
+            builder.append(v.as_ref().map(|v| v.as_scalar_ref()));
+        }
+        let array = builder.finish();
+        let prost = array.to_protobuf();
+        let decoded = JsonbArray::from_protobuf(&prost, values.len()).unwrap();
+        let decoded = decoded.as_jsonb();
+        assert_eq!(
+            decoded
+                .iter()
+                .map(|v| v.map(|v| v.to_owned_scalar()))
+                .collect::<Vec<_>>(),
+            values
+        );
+    }
+}
@@ -18,6 +18,8 @@ use regex::Regex;
 
 pub const RW_INTERNAL_TABLE_FUNCTION_NAME: &str = "rw_table";
 
+pub const RW_DEAD_LETTERS_FUNCTION_NAME: &str = "rw_dead_letters";
+
 pub fn generate_internal_table_name_with_type(
     mview_name: &str,
     fragment_id: u32,
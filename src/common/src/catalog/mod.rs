@@ -131,18 +131,25 @@ impl From<TableId> for u32 {
 #[derive(Clone, Debug, PartialEq, Default, Copy)]
 pub struct TableOption {
     pub retention_seconds: Option<u32>, // second
+    /// How long the historical versions of the table are kept for time-travel queries, in
+    /// seconds.
+    pub time_travel_retention_seconds: Option<u32>,
 }
 
 impl From<&risingwave_pb::hummock::TableOption> for TableOption {
     fn from(table_option: &risingwave_pb::hummock::TableOption) -> Self {
-        let retention_seconds =
-            if table_option.retention_seconds == hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND {
+        let from_prost = |seconds| {
+            if seconds == hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND {
                 None
             } else {
-                Some(table_option.retention_seconds)
-            };
+                Some(seconds)
+            }
+        };
 
-        Self { retention_seconds }
+        Self {
+            retention_seconds: from_prost(table_option.retention_seconds),
+            time_travel_retention_seconds: from_prost(table_option.time_travel_retention_seconds),
+        }
     }
 }
 
@@ -152,29 +159,37 @@ impl From<&TableOption> for risingwave_pb::hummock::TableOption {
             retention_seconds: table_option
                 .retention_seconds
                 .unwrap_or(hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND),
+            time_travel_retention_seconds: table_option
+                .time_travel_retention_seconds
+                .unwrap_or(hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND),
         }
     }
 }
 
 impl TableOption {
     pub fn build_table_option(table_properties: &HashMap<String, String>) -> Self {
-        // now we only support ttl for TableOption
-        let mut result = TableOption::default();
-        if let Some(ttl_string) = table_properties.get(hummock::PROPERTIES_RETENTION_SECOND_KEY) {
-            match ttl_string.trim().parse::<u32>() {
-                Ok(retention_seconds_u32) => result.retention_seconds = Some(retention_seconds_u32),
+        let parse_seconds = |key: &str| {
+            let seconds_string = table_properties.get(key)?;
+            match seconds_string.trim().parse::<u32>() {
+                Ok(seconds) => Some(seconds),
                 Err(e) => {
                     tracing::info!(
-                        "build_table_option parse option ttl_string {} fail {}",
-                        ttl_string,
+                        "build_table_option parse option {} {} fail {}",
+                        key,
+                        seconds_string,
                         e
                     );
-                    result.retention_seconds = None;
+                    None
                 }
-            };
+            }
+        };
+
+        TableOption {
+            retention_seconds: parse_seconds(hummock::PROPERTIES_RETENTION_SECOND_KEY),
+            time_travel_retention_seconds: parse_seconds(
+                hummock::PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY,
+            ),
         }
-
-        result
     }
 }
 
@@ -43,6 +43,29 @@ pub struct TableDesc {
     pub retention_seconds: u32,
 
     pub value_indices: Vec<usize>,
+
+    /// The number of leading pk columns used as the prefix of the bloom filter key. 0 means the
+    /// default one, see [`bloom_filter_prefix_len`].
+    pub read_prefix_len_hint: usize,
+}
+
+/// Returns the number of leading pk columns that make up the prefix of the bloom filter key of a
+/// table, or `None` if the bloom filter is built on the full key.
+///
+/// A non-zero `read_prefix_len_hint` is used as long as it does not exceed the pk. Otherwise the
+/// distribution key is used if the pk starts with it.
+pub fn bloom_filter_prefix_len(
+    read_prefix_len_hint: usize,
+    pk_indices: &[usize],
+    dist_key_indices: &[usize],
+) -> Option<usize> {
+    if read_prefix_len_hint > 0 && read_prefix_len_hint <= pk_indices.len() {
+        Some(read_prefix_len_hint)
+    } else if !dist_key_indices.is_empty() && pk_indices.starts_with(dist_key_indices) {
+        Some(dist_key_indices.len())
+    } else {
+        None
+    }
 }
 
 impl TableDesc {
@@ -70,6 +93,7 @@ impl TableDesc {
             dist_key_indices: self.distribution_key.iter().map(|&k| k as u32).collect(),
             retention_seconds: self.retention_seconds,
             value_indices: self.value_indices.iter().map(|&v| v as u32).collect(),
+            read_prefix_len_hint: self.read_prefix_len_hint as u32,
         }
     }
 
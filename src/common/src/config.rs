@@ -149,6 +149,17 @@ pub struct BatchConfig {
     #[serde(default)]
     pub worker_threads_num: Option<usize>,
 
+    /// The memory limit of the state of a hash aggregation or hash join executor. Beyond the
+    /// limit, the executor spills its state to local files by partitions and processes them one
+    /// by one. Spilling is disabled if not set.
+    #[serde(default)]
+    pub spill_memory_limit_mb: Option<usize>,
+
+    /// The directory of the spill files. The temporary directory of the system is used if not
+    /// set.
+    #[serde(default)]
+    pub spill_dir: Option<String>,
+
     #[serde(default)]
     pub developer: DeveloperConfig,
 }
@@ -304,6 +315,10 @@ pub struct FileCacheConfig {
     #[serde(default = "default::file_cache::capacity_mb")]
     pub capacity_mb: usize,
 
+    /// Capacity of the file cache for sstable metas, which is separated from the one for blocks.
+    #[serde(default = "default::file_cache::sstable_meta_capacity_mb")]
+    pub sstable_meta_capacity_mb: usize,
+
     #[serde(default = "default::file_cache::total_buffer_capacity_mb")]
     pub total_buffer_capacity_mb: usize,
 
@@ -554,6 +569,10 @@ mod default {
             1024
         }
 
+        pub fn sstable_meta_capacity_mb() -> usize {
+            256
+        }
+
         pub fn total_buffer_capacity_mb() -> usize {
             128
         }
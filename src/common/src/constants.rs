@@ -32,4 +32,5 @@ pub mod hummock {
 
     pub const TABLE_OPTION_DUMMY_RETENTION_SECOND: u32 = 0;
     pub const PROPERTIES_RETENTION_SECOND_KEY: &str = "retention_seconds";
+    pub const PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY: &str = "time_travel_retention_seconds";
 }
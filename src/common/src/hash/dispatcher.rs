@@ -103,6 +103,7 @@ fn hash_key_size(data_type: &DataType) -> HashKeySize {
         DataType::Bytea => HashKeySize::Variable,
         DataType::Struct { .. } => HashKeySize::Variable,
         DataType::List { .. } => HashKeySize::Variable,
+        DataType::Jsonb => HashKeySize::Variable,
     }
 }
 
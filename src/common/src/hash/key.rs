@@ -32,8 +32,8 @@ use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 
 use crate::array::{
-    Array, ArrayBuilder, ArrayBuilderImpl, ArrayError, ArrayImpl, ArrayResult, DataChunk, ListRef,
-    StructRef,
+    Array, ArrayBuilder, ArrayBuilderImpl, ArrayError, ArrayImpl, ArrayResult, DataChunk, JsonbRef,
+    ListRef, StructRef,
 };
 use crate::collection::estimate_size::EstimateSize;
 use crate::hash::vnode::VirtualNode;
@@ -477,6 +477,20 @@ impl<'a> HashKeySerDe<'a> for StructRef<'a> {
     }
 }
 
+impl<'a> HashKeySerDe<'a> for JsonbRef<'a> {
+    type S = Vec<u8>;
+
+    /// This should never be called
+    fn serialize(self) -> Self::S {
+        todo!()
+    }
+
+    /// This should never be called
+    fn deserialize<R: Read>(_source: &mut R) -> Self {
+        todo!()
+    }
+}
+
 impl<'a> HashKeySerDe<'a> for ListRef<'a> {
     type S = Vec<u8>;
 
@@ -0,0 +1,255 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+
+use byteorder::{NetworkEndian, ReadBytesExt};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use postgres_types::{FromSql, Type};
+
+use super::to_binary::JSONB_BINARY_VERSION;
+use super::{
+    DataType, Datum, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper,
+    NaiveTimeWrapper, ScalarImpl,
+};
+use crate::array::{JsonbVal, ListValue, StructValue};
+use crate::error::{ErrorCode, Result};
+
+type FromSqlResult<T> = std::result::Result<T, Box<dyn Error + Sync + Send>>;
+
+impl ScalarImpl {
+    /// Decode the scalar from postgresql binary format, e.g. a parameter bound in the extended
+    /// query protocol. The decoder implements decoding using <https://docs.rs/postgres-types/0.2.3/postgres_types/trait.FromSql.html>
+    pub fn from_binary(raw: &[u8], data_type: &DataType) -> Result<Self> {
+        from_binary_inner(raw, data_type).map_err(|e| {
+            ErrorCode::InvalidInputSyntax(format!(
+                "invalid binary representation for type {}: {}",
+                data_type, e
+            ))
+            .into()
+        })
+    }
+}
+
+fn from_binary_inner(raw: &[u8], data_type: &DataType) -> FromSqlResult<ScalarImpl> {
+    let scalar = match data_type {
+        DataType::Boolean => ScalarImpl::Bool(bool::from_sql(&Type::BOOL, raw)?),
+        DataType::Int16 => ScalarImpl::Int16(i16::from_sql(&Type::INT2, raw)?),
+        DataType::Int32 => ScalarImpl::Int32(i32::from_sql(&Type::INT4, raw)?),
+        DataType::Int64 => ScalarImpl::Int64(i64::from_sql(&Type::INT8, raw)?),
+        DataType::Float32 => ScalarImpl::Float32(f32::from_sql(&Type::FLOAT4, raw)?.into()),
+        DataType::Float64 => ScalarImpl::Float64(f64::from_sql(&Type::FLOAT8, raw)?.into()),
+        DataType::Varchar => ScalarImpl::Utf8(<&str>::from_sql(&Type::VARCHAR, raw)?.into()),
+        DataType::Bytea => ScalarImpl::Bytea(raw.into()),
+        DataType::Decimal => ScalarImpl::Decimal(decimal_from_binary(raw)?),
+        DataType::Date => ScalarImpl::NaiveDate(NaiveDateWrapper::new(NaiveDate::from_sql(
+            &Type::DATE,
+            raw,
+        )?)),
+        DataType::Time => ScalarImpl::NaiveTime(NaiveTimeWrapper::new(NaiveTime::from_sql(
+            &Type::TIME,
+            raw,
+        )?)),
+        DataType::Timestamp => ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper::new(
+            NaiveDateTime::from_sql(&Type::TIMESTAMP, raw)?,
+        )),
+        // Timestamp with time zone is stored as microseconds since unix epoch.
+        DataType::Timestampz => ScalarImpl::Int64(
+            DateTime::<Utc>::from_sql(&Type::TIMESTAMPTZ, raw)?.timestamp_micros(),
+        ),
+        DataType::Interval => ScalarImpl::Interval(IntervalUnit::from_sql(&Type::INTERVAL, raw)?),
+        DataType::List { datatype } => ScalarImpl::List(list_from_binary(raw, datatype)?),
+        DataType::Struct(struct_type) => {
+            ScalarImpl::Struct(struct_from_binary(raw, &struct_type.fields)?)
+        }
+        DataType::Jsonb => ScalarImpl::Jsonb(jsonb_from_binary(raw)?),
+    };
+    Ok(scalar)
+}
+
+/// `rust_decimal` does not support the special values of `numeric`, which are encoded in the sign
+/// field. See `numeric_recv` in PostgreSQL.
+fn decimal_from_binary(raw: &[u8]) -> FromSqlResult<Decimal> {
+    const NUMERIC_NAN: u16 = 0xC000;
+    const NUMERIC_PINF: u16 = 0xD000;
+    const NUMERIC_NINF: u16 = 0xF000;
+
+    let sign = raw
+        .get(4..6)
+        .map(|mut sign| sign.read_u16::<NetworkEndian>().unwrap());
+    Ok(match sign {
+        Some(NUMERIC_NAN) => Decimal::NaN,
+        Some(NUMERIC_PINF) => Decimal::PositiveInf,
+        Some(NUMERIC_NINF) => Decimal::NegativeInf,
+        _ => Decimal::Normalized(rust_decimal::Decimal::from_sql(&Type::NUMERIC, raw)?),
+    })
+}
+
+/// See `jsonb_recv` in PostgreSQL.
+fn jsonb_from_binary(raw: &[u8]) -> FromSqlResult<JsonbVal> {
+    match raw.split_first() {
+        Some((&JSONB_BINARY_VERSION, text)) => Ok(std::str::from_utf8(text)?.parse()?),
+        Some((version, _)) => Err(format!("unsupported jsonb version number {}", version).into()),
+        None => Err("empty jsonb".into()),
+    }
+}
+
+/// Reads a length-prefixed element of an array or a record. `None` stands for NULL.
+fn read_element<'a>(raw: &mut &'a [u8]) -> FromSqlResult<Option<&'a [u8]>> {
+    let len = raw.read_i32::<NetworkEndian>()?;
+    if len == -1 {
+        return Ok(None);
+    }
+    if len < 0 || len as usize > raw.len() {
+        return Err(format!("invalid element length {}", len).into());
+    }
+    let (element, rest) = raw.split_at(len as usize);
+    *raw = rest;
+    Ok(Some(element))
+}
+
+fn read_datum(raw: &mut &[u8], data_type: &DataType) -> FromSqlResult<Datum> {
+    read_element(raw)?
+        .map(|element| from_binary_inner(element, data_type))
+        .transpose()
+}
+
+/// See `array_recv` in PostgreSQL. Only one-dimensional arrays are supported.
+fn list_from_binary(mut raw: &[u8], datatype: &DataType) -> FromSqlResult<ListValue> {
+    let ndim = raw.read_i32::<NetworkEndian>()?;
+    let _has_null = raw.read_i32::<NetworkEndian>()?;
+    let _element_oid = raw.read_i32::<NetworkEndian>()?;
+    let len = match ndim {
+        0 => 0,
+        1 => {
+            let len = raw.read_i32::<NetworkEndian>()?;
+            let _lower_bound = raw.read_i32::<NetworkEndian>()?;
+            len
+        }
+        _ => return Err(format!("{}-dimensional array is not supported", ndim).into()),
+    };
+    let values = (0..len)
+        .map(|_| read_datum(&mut raw, datatype))
+        .collect::<FromSqlResult<Vec<_>>>()?;
+    Ok(ListValue::new(values))
+}
+
+/// See `record_recv` in PostgreSQL.
+fn struct_from_binary(mut raw: &[u8], fields: &[DataType]) -> FromSqlResult<StructValue> {
+    let len = raw.read_i32::<NetworkEndian>()?;
+    if len as usize != fields.len() {
+        return Err(format!("expected {} fields, got {}", fields.len(), len).into());
+    }
+    let values = fields
+        .iter()
+        .map(|field| {
+            let _field_oid = raw.read_i32::<NetworkEndian>()?;
+            read_datum(&mut raw, field)
+        })
+        .collect::<FromSqlResult<Vec<_>>>()?;
+    Ok(StructValue::new(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use postgres_types::ToSql;
+
+    use super::*;
+    use crate::types::to_binary::ToBinary;
+    use crate::types::Scalar;
+
+    fn round_trip(scalar: ScalarImpl, data_type: DataType) {
+        let raw = scalar.as_scalar_ref_impl().to_binary().unwrap().unwrap();
+        assert_eq!(ScalarImpl::from_binary(&raw, &data_type).unwrap(), scalar);
+    }
+
+    #[test]
+    fn test_from_binary() {
+        round_trip(ScalarImpl::Bool(true), DataType::Boolean);
+        round_trip(ScalarImpl::Int16(1), DataType::Int16);
+        round_trip(ScalarImpl::Int32(-2), DataType::Int32);
+        round_trip(ScalarImpl::Int64(3), DataType::Int64);
+        round_trip(ScalarImpl::Float32(1.5f32.into()), DataType::Float32);
+        round_trip(ScalarImpl::Float64((-2.5f64).into()), DataType::Float64);
+        round_trip(ScalarImpl::Utf8("it's".into()), DataType::Varchar);
+        round_trip(ScalarImpl::Bytea(vec![0, 1, 2].into()), DataType::Bytea);
+        round_trip(
+            ScalarImpl::Decimal("1.23".parse().unwrap()),
+            DataType::Decimal,
+        );
+        round_trip(ScalarImpl::Decimal(Decimal::NaN), DataType::Decimal);
+        round_trip(ScalarImpl::Decimal(Decimal::NegativeInf), DataType::Decimal);
+        round_trip(
+            NaiveDateWrapper::from_ymd_uncheck(2022, 10, 1).to_scalar_value(),
+            DataType::Date,
+        );
+        round_trip(
+            NaiveTimeWrapper::from_hms_uncheck(12, 34, 56).to_scalar_value(),
+            DataType::Time,
+        );
+        round_trip(
+            NaiveDateTimeWrapper::from_protobuf(1_664_627_696_000_000)
+                .unwrap()
+                .to_scalar_value(),
+            DataType::Timestamp,
+        );
+        round_trip(
+            IntervalUnit::new(1, 2, 3000).to_scalar_value(),
+            DataType::Interval,
+        );
+    }
+
+    #[test]
+    fn test_from_binary_timestampz() {
+        let micros = 1_664_627_696_123_456;
+        let naive = NaiveDateTimeWrapper::from_protobuf(micros).unwrap().0;
+        let mut raw = BytesMut::new();
+        DateTime::<Utc>::from_utc(naive, Utc)
+            .to_sql(&Type::TIMESTAMPTZ, &mut raw)
+            .unwrap();
+        assert_eq!(
+            ScalarImpl::from_binary(&raw, &DataType::Timestampz).unwrap(),
+            ScalarImpl::Int64(micros)
+        );
+    }
+
+    #[test]
+    fn test_from_binary_list() {
+        let mut raw = BytesMut::new();
+        vec![Some(1), None, Some(3)]
+            .to_sql(&Type::INT4_ARRAY, &mut raw)
+            .unwrap();
+        assert_eq!(
+            ScalarImpl::from_binary(
+                &raw,
+                &DataType::List {
+                    datatype: Box::new(DataType::Int32)
+                }
+            )
+            .unwrap(),
+            ScalarImpl::List(ListValue::new(vec![
+                Some(ScalarImpl::Int32(1)),
+                None,
+                Some(ScalarImpl::Int32(3)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_from_binary_invalid() {
+        assert!(ScalarImpl::from_binary(&[0, 1], &DataType::Int32).is_err());
+        assert!(ScalarImpl::from_binary(&[0xff], &DataType::Varchar).is_err());
+    }
+}
@@ -39,6 +39,7 @@ use risingwave_pb::data::data_type::{IntervalType, TypeName};
 pub use scalar_impl::*;
 pub mod chrono_wrapper;
 pub mod decimal;
+mod from_binary;
 pub mod interval;
 mod postgres_type;
 pub mod struct_type;
@@ -64,8 +65,8 @@ use self::struct_type::StructType;
 use self::to_binary::ToBinary;
 use self::to_text::ToText;
 use crate::array::{
-    read_interval_unit, ArrayBuilderImpl, ListRef, ListValue, PrimitiveArrayItemType, StructRef,
-    StructValue,
+    read_interval_unit, ArrayBuilderImpl, JsonbRef, JsonbVal, ListRef, ListValue,
+    PrimitiveArrayItemType, StructRef, StructValue,
 };
 use crate::error::Result as RwResult;
 
@@ -126,6 +127,9 @@ pub enum DataType {
     #[display("bytea")]
     #[from_str(regex = "(?i)^bytea$")]
     Bytea,
+    #[display("jsonb")]
+    #[from_str(regex = "(?i)^jsonb$")]
+    Jsonb,
 }
 
 impl std::str::FromStr for Box<DataType> {
@@ -152,7 +156,8 @@ impl DataTypeName {
             | DataTypeName::Timestampz
             | DataTypeName::Time
             | DataTypeName::Bytea
-            | DataTypeName::Interval => true,
+            | DataTypeName::Interval
+            | DataTypeName::Jsonb => true,
 
             DataTypeName::Struct | DataTypeName::List => false,
         }
@@ -174,6 +179,7 @@ impl DataTypeName {
             DataTypeName::Timestampz => DataType::Timestampz,
             DataTypeName::Time => DataType::Time,
             DataTypeName::Interval => DataType::Interval,
+            DataTypeName::Jsonb => DataType::Jsonb,
             DataTypeName::Struct | DataTypeName::List => {
                 return None;
             }
@@ -212,6 +218,7 @@ impl From<&ProstDataType> for DataType {
             TypeName::Decimal => DataType::Decimal,
             TypeName::Interval => DataType::Interval,
             TypeName::Bytea => DataType::Bytea,
+            TypeName::Jsonb => DataType::Jsonb,
             TypeName::Struct => {
                 let fields: Vec<DataType> = proto.field_type.iter().map(|f| f.into()).collect_vec();
                 let field_names: Vec<String> = proto.field_names.iter().cloned().collect_vec();
@@ -268,6 +275,7 @@ impl DataType {
             )
             .into(),
             DataType::Bytea => BytesArrayBuilder::new(capacity).into(),
+            DataType::Jsonb => JsonbArrayBuilder::new(capacity).into(),
         }
     }
 
@@ -289,6 +297,7 @@ impl DataType {
             DataType::Struct { .. } => TypeName::Struct,
             DataType::List { .. } => TypeName::List,
             DataType::Bytea => TypeName::Bytea,
+            DataType::Jsonb => TypeName::Jsonb,
         }
     }
 
@@ -346,7 +355,7 @@ impl DataType {
         match self {
             Boolean | Int16 | Int32 | Int64 => true,
             Float32 | Float64 | Decimal | Date | Varchar | Time | Timestamp | Timestampz
-            | Interval | Bytea => false,
+            | Interval | Bytea | Jsonb => false,
             Struct(t) => t.fields.iter().all(|dt| dt.mem_cmp_eq_value_enc()),
             List { datatype } => datatype.mem_cmp_eq_value_enc(),
         }
@@ -391,6 +400,7 @@ impl DataType {
                     .collect_vec(),
             )),
             DataType::List { .. } => ScalarImpl::List(ListValue::new(vec![])),
+            DataType::Jsonb => ScalarImpl::Jsonb(JsonbVal::null()),
         }
     }
 }
@@ -480,7 +490,8 @@ macro_rules! for_all_scalar_variants {
             { NaiveTime, naivetime, NaiveTimeWrapper, NaiveTimeWrapper },
             { Struct, struct, StructValue, StructRef<'scalar> },
             { List, list, ListValue, ListRef<'scalar> },
-            { Bytea, bytea, Box<[u8]>, &'scalar [u8] }
+            { Bytea, bytea, Box<[u8]>, &'scalar [u8] },
+            { Jsonb, jsonb, JsonbVal, JsonbRef<'scalar> }
         }
     };
 }
@@ -858,6 +869,7 @@ impl ScalarRefImpl<'_> {
             }
             Self::Struct(v) => v.serialize(ser)?,
             Self::List(v) => v.serialize(ser)?,
+            Self::Jsonb(v) => v.memcmp_serialize(ser)?,
         };
         Ok(())
     }
@@ -907,6 +919,7 @@ impl ScalarImpl {
             Ty::List { datatype } => ListValue::deserialize(datatype, de)?.to_scalar_value(),
             // TODO: Consider directly use get bytes
             Ty::Bytea => Self::Bytea(Bytes::deserialize(de)?.to_vec().into()),
+            Ty::Jsonb => Self::Jsonb(JsonbVal::memcmp_deserialize(de)?),
         })
     }
 
@@ -952,6 +965,7 @@ impl ScalarImpl {
                         .try_fold(0, |a, b| b.map(|b| a + b))?,
                     DataType::Varchar => deserializer.skip_bytes()?,
                     DataType::Bytea => deserializer.skip_bytes()?,
+                    DataType::Jsonb => deserializer.skip_bytes()?,
                 };
 
                 // consume offset of fixed_type
@@ -988,6 +1002,7 @@ pub fn literal_type_match(data_type: &DataType, literal: Option<&ScalarImpl>) ->
                     | (DataType::Interval, ScalarImpl::Interval(_))
                     | (DataType::Struct { .. }, ScalarImpl::Struct(_))
                     | (DataType::List { .. }, ScalarImpl::List(_))
+                    | (DataType::Jsonb, ScalarImpl::Jsonb(_))
             )
         }
         None => true,
@@ -1207,6 +1222,10 @@ mod tests {
                         datatype: Box::new(DataType::Int64),
                     },
                 ),
+                DataTypeName::Jsonb => (
+                    ScalarImpl::Jsonb(r#"{"a": [2, 3, 3]}"#.parse().unwrap()),
+                    DataType::Jsonb,
+                ),
             };
 
             test(Some(scalar), data_type.clone());
@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use bytes::{Bytes, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use postgres_types::{ToSql, Type};
 
 use super::{DatumRef, ScalarRefImpl};
+use crate::array::JsonbRef;
 use crate::error::Result;
+
+/// The only version of the binary format of `jsonb` in PostgreSQL.
+pub const JSONB_BINARY_VERSION: u8 = 1;
+
 // Used to convert ScalarRef to text format
 pub trait ToBinary {
     fn to_binary(&self) -> Result<Option<Bytes>>;
@@ -48,6 +53,16 @@ implement_using_to_sql! {
     { &[u8] }
 }
 
+/// The binary format of `jsonb` is a version number followed by the text format.
+impl ToBinary for JsonbRef<'_> {
+    fn to_binary(&self) -> Result<Option<Bytes>> {
+        let mut output = BytesMut::new();
+        output.put_u8(JSONB_BINARY_VERSION);
+        output.put_slice(self.to_string().as_bytes());
+        Ok(Some(output.freeze()))
+    }
+}
+
 impl ToBinary for ScalarRefImpl<'_> {
     fn to_binary(&self) -> Result<Option<Bytes>> {
         match self {
@@ -66,6 +81,7 @@ impl ToBinary for ScalarRefImpl<'_> {
             ScalarRefImpl::Struct(_) => todo!(),
             ScalarRefImpl::List(_) => todo!(),
             ScalarRefImpl::Bytea(v) => v.to_binary(),
+            ScalarRefImpl::Jsonb(v) => v.to_binary(),
         }
     }
 }
@@ -18,6 +18,7 @@ use std::num::FpCategory;
 use num_traits::ToPrimitive;
 
 use super::{DatumRef, ScalarRefImpl};
+use crate::array::JsonbRef;
 
 // Used to convert ScalarRef to text format
 pub trait ToText {
@@ -50,7 +51,8 @@ macro_rules! implement_using_itoa {
 
 implement_using_to_string! {
     { String },
-    { &str }
+    { &str },
+    { JsonbRef<'_> }
 }
 
 implement_using_itoa! {
@@ -145,6 +147,7 @@ impl ToText for ScalarRefImpl<'_> {
             ScalarRefImpl::Struct(s) => s.to_text(),
             ScalarRefImpl::Utf8(v) => v.to_text(),
             ScalarRefImpl::Bytea(v) => format_bytes(v),
+            ScalarRefImpl::Jsonb(v) => v.to_text(),
         }
     }
 }
@@ -78,6 +78,12 @@ impl Epoch {
         UNIX_SINGULARITY_DATE_SEC * 1000 + self.physical_time()
     }
 
+    /// Returns the epoch of the UNIX epoch time in milliseconds, or [`INVALID_EPOCH`] if it is
+    /// before the singularity date.
+    pub fn from_unix_millis(unix_millis: u64) -> Self {
+        Self::from_physical_time(unix_millis.saturating_sub(UNIX_SINGULARITY_DATE_SEC * 1000))
+    }
+
     /// Returns the epoch in real system time.
     pub fn as_system_time(&self) -> SystemTime {
         *UNIX_SINGULARITY_DATE_EPOCH + Duration::from_millis(self.physical_time())
@@ -170,4 +176,11 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_from_unix_millis() {
+        let epoch = Epoch::from_physical_time(1000);
+        assert_eq!(epoch, Epoch::from_unix_millis(epoch.as_unix_millis()));
+        assert_eq!(INVALID_EPOCH, Epoch::from_unix_millis(0).0);
+    }
 }
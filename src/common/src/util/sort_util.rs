@@ -178,7 +178,8 @@ pub fn compare_rows(lhs: &Row, rhs: &Row, order_pairs: &[OrderPair]) -> Result<O
                 NaiveTime,
                 Struct,
                 List,
-                Bytea
+                Bytea,
+                Jsonb
             ]
         );
 
@@ -19,7 +19,7 @@ use bytes::{Buf, BufMut};
 use chrono::{Datelike, Timelike};
 use itertools::Itertools;
 
-use crate::array::{ListRef, ListValue, StructRef, StructValue};
+use crate::array::{JsonbVal, ListRef, ListValue, StructRef, StructValue};
 use crate::types::struct_type::StructType;
 use crate::types::{
     DataType, Datum, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper,
@@ -85,6 +85,7 @@ fn serialize_value(value: ScalarRefImpl<'_>, buf: &mut impl BufMut) {
         }
         ScalarRefImpl::Struct(s) => serialize_struct(s, buf),
         ScalarRefImpl::List(v) => serialize_list(v, buf),
+        ScalarRefImpl::Jsonb(v) => serialize_str(&v.value_serialize(), buf),
     }
 }
 
@@ -159,6 +160,7 @@ fn deserialize_value(ty: &DataType, data: &mut impl Buf) -> Result<ScalarImpl> {
         DataType::List {
             datatype: item_type,
         } => deserialize_list(item_type, data)?,
+        DataType::Jsonb => ScalarImpl::Jsonb(deserialize_jsonb(data)?),
     })
 }
 
@@ -197,6 +199,11 @@ fn deserialize_bytea(data: &mut impl Buf) -> Vec<u8> {
     bytes
 }
 
+fn deserialize_jsonb(data: &mut impl Buf) -> Result<JsonbVal> {
+    let bytes = deserialize_bytea(data);
+    JsonbVal::value_deserialize(&bytes).ok_or(ValueEncodingError::InvalidJsonbEncoding)
+}
+
 fn deserialize_bool(data: &mut impl Buf) -> Result<bool> {
     match data.get_u8() {
         1 => Ok(true),
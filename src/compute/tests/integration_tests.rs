@@ -143,6 +143,7 @@ async fn test_table_materialize() -> StreamResult<()> {
         MOCK_SOURCE_NAME.to_string(),
         vnodes,
         state_table,
+        None,
         all_column_ids.clone(),
         all_schema.clone(),
         PkIndices::from([0]),
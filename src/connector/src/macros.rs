@@ -93,10 +93,10 @@ macro_rules! impl_split {
                     .to_string()
             }
 
-            pub fn update(&self, start_offset: String) -> Self {
-                match self {
-                    $( Self::$variant_name(inner) => Self::$variant_name(inner.copy_with_offset(start_offset)), )*
-                }
+            pub fn update(&self, start_offset: String) -> Result<Self> {
+                Ok(match self {
+                    $( Self::$variant_name(inner) => Self::$variant_name(inner.copy_with_offset(start_offset)?), )*
+                })
             }
         }
     }
@@ -108,6 +108,7 @@ fn parse_datum(datum: DatumRef<'_>) -> String {
         Some(ScalarRefImpl::NaiveDate(v)) => format!("Date({})", v),
         Some(ScalarRefImpl::NaiveTime(v)) => format!("Time({})", v),
         Some(ScalarRefImpl::Interval(v)) => format!("Interval({})", v),
+        Some(ScalarRefImpl::Jsonb(v)) => format!("Jsonb({})", v),
         Some(ScalarRefImpl::Struct(v)) => {
             format!(
                 "Struct({})",
@@ -18,23 +18,31 @@ use std::future::Future;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use itertools::Itertools;
+use rdkafka::consumer::{BaseConsumer, Consumer, DefaultConsumerContext};
 use rdkafka::error::{KafkaError, KafkaResult};
 use rdkafka::message::ToBytes;
 use rdkafka::producer::{BaseRecord, DefaultProducerContext, Producer, ThreadedProducer};
 use rdkafka::types::RDKafkaErrorCode;
-use rdkafka::ClientConfig;
-use risingwave_common::array::{ArrayResult, Op, RowRef, StreamChunk};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+use risingwave_common::array::{ArrayError, ArrayResult, Op, RowRef, StreamChunk};
 use risingwave_common::catalog::{Field, Schema};
-use risingwave_common::types::{DataType, DatumRef, ScalarRefImpl};
+use risingwave_common::types::to_text::{format_bytes, ToText};
+use risingwave_common::types::{DataType, DatumRef, NaiveDateTimeWrapper, ScalarRefImpl};
 use serde::Deserialize;
 use serde_json::{json, Map, Value};
 use tracing::warn;
 
-use super::{Sink, SinkError};
+use super::{Sink, SinkError, SinkFormat};
 use crate::sink::Result;
 
 pub const KAFKA_SINK: &str = "kafka";
 
+const DELIVERY_GUARANTEE_KEY: &str = "delivery.guarantee";
+/// The partition of the topic where the epochs of the committed transactions are recorded.
+const MARKER_PARTITION: i32 = 0;
+const DELIVERY_AT_LEAST_ONCE: &str = "at_least_once";
+const DELIVERY_EXACTLY_ONCE: &str = "exactly_once";
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct KafkaConfig {
     #[serde(rename = "kafka.brokers")]
@@ -48,8 +56,18 @@ pub struct KafkaConfig {
     // partition number. The partition number should set by meta.
     pub partition: Option<i32>,
 
-    pub format: String, // accept "append_only" or "debezium"
+    pub format: SinkFormat,
+
+    /// Whether the messages are delivered exactly once, set by `delivery.guarantee`. If so, the
+    /// transaction of the sink is flushed on checkpoint barriers and only committed once the
+    /// checkpoint is committed. The one left open by a failure is committed again on recovery
+    /// unless its epoch has been marked committed, so the consumers reading committed messages
+    /// never see duplicates. Otherwise, the transaction is committed on every barrier, and the
+    /// messages after the last checkpoint are delivered again after recovery.
+    pub exactly_once: bool,
 
+    /// It's also the transactional id of the producer, which is derived from the sink and the
+    /// vnodes it writes, so that the producer after recovery or rescaling fences the one before.
     pub identifier: String,
 
     pub timeout: Duration,
@@ -65,12 +83,32 @@ impl KafkaConfig {
         let identifier = values
             .get("identifier")
             .expect("kafka.identifier must be set");
-        let format = values.get("format").expect("format must be set");
-        if format != "append_only" && format != "debezium" {
-            return Err(SinkError::Config(
-                "format must be set to \"append_only\" or \"debezium\"".to_string(),
-            ));
-        }
+        let format = values
+            .get("format")
+            .ok_or_else(|| SinkError::Config("format must be set".to_string()))?;
+        let format = SinkFormat::from_option(
+            format,
+            &[
+                SinkFormat::AppendOnly,
+                SinkFormat::Upsert,
+                SinkFormat::Debezium,
+            ],
+        )?;
+
+        let exactly_once = match values
+            .get(DELIVERY_GUARANTEE_KEY)
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            None | Some(DELIVERY_AT_LEAST_ONCE) => false,
+            Some(DELIVERY_EXACTLY_ONCE) => true,
+            Some(other) => {
+                return Err(SinkError::Config(format!(
+                    "{} must be set to \"{}\" or \"{}\", got \"{}\"",
+                    DELIVERY_GUARANTEE_KEY, DELIVERY_AT_LEAST_ONCE, DELIVERY_EXACTLY_ONCE, other
+                )))
+            }
+        };
 
         let topic = values.get("kafka.topic").expect("kafka.topic must be set");
 
@@ -82,7 +120,8 @@ impl KafkaConfig {
             timeout: Duration::from_secs(5), // default timeout is 5 seconds
             max_retry_num: 3,                // default max retry num is 3
             retry_interval: Duration::from_millis(100), // default retry interval is 100ms
-            format: format.to_string(),
+            format,
+            exactly_once,
         })
     }
 }
@@ -99,17 +138,25 @@ pub struct KafkaSink {
     pub conductor: KafkaTransactionConductor,
     state: KafkaSinkState,
     schema: Schema,
+    pk_indices: Vec<usize>,
     in_transaction_epoch: Option<u64>,
 }
 
 impl KafkaSink {
-    pub async fn new(config: KafkaConfig, schema: Schema) -> Result<Self> {
+    pub async fn new(config: KafkaConfig, schema: Schema, pk_indices: Vec<usize>) -> Result<Self> {
+        if config.format != SinkFormat::AppendOnly && pk_indices.is_empty() {
+            return Err(SinkError::Config(format!(
+                "kafka sink of format \"{}\" requires the upstream to have a primary key",
+                config.format.as_str()
+            )));
+        }
         Ok(KafkaSink {
             config: config.clone(),
             conductor: KafkaTransactionConductor::new(config).await?,
             in_transaction_epoch: None,
             state: KafkaSinkState::Init,
             schema,
+            pk_indices,
         })
     }
 
@@ -165,6 +212,31 @@ impl KafkaSink {
         )
     }
 
+    /// Writes a tombstone, i.e. a message without payload, which deletes the key when the topic is
+    /// compacted.
+    async fn send_tombstone(&self, key: &str) -> Result<()> {
+        self.send(BaseRecord::<[u8], [u8]>::to(self.config.topic.as_str()).key(key.as_bytes()))
+            .await?;
+        Ok(())
+    }
+
+    async fn upsert(&self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        for (key, record) in upsert_messages(chunk, schema, &self.pk_indices)? {
+            match record {
+                Some(record) => {
+                    self.send(
+                        BaseRecord::to(self.config.topic.as_str())
+                            .key(key.as_bytes())
+                            .payload(record.as_bytes()),
+                    )
+                    .await?
+                }
+                None => self.send_tombstone(&key).await?,
+            }
+        }
+        Ok(())
+    }
+
     async fn debezium_update(&self, chunk: StreamChunk, schema: &Schema, ts_ms: u64) -> Result<()> {
         let mut update_cache: Option<Map<String, Value>> = None;
         for (op, row) in chunk.rows() {
@@ -173,7 +245,7 @@ impl KafkaSink {
                     "schema": schema_to_json(schema),
                     "payload": {
                         "before": null,
-                        "after": record_to_json(row, schema.fields.clone())?,
+                        "after": record_to_json(row, &schema.fields)?,
                         "op": "c",
                         "ts_ms": ts_ms,
                     }
@@ -181,14 +253,14 @@ impl KafkaSink {
                 Op::Delete => Some(json!({
                     "schema": schema_to_json(schema),
                     "payload": {
-                        "before": record_to_json(row, schema.fields.clone())?,
+                        "before": record_to_json(row, &schema.fields)?,
                         "after": null,
                         "op": "d",
                         "ts_ms": ts_ms,
                     }
                 })),
                 Op::UpdateDelete => {
-                    update_cache = Some(record_to_json(row, schema.fields.clone())?);
+                    update_cache = Some(record_to_json(row, &schema.fields)?);
                     continue;
                 }
                 Op::UpdateInsert => {
@@ -197,7 +269,7 @@ impl KafkaSink {
                             "schema": schema_to_json(schema),
                             "payload": {
                                 "before": before,
-                                "after": record_to_json(row, schema.fields.clone())?,
+                                "after": record_to_json(row, &schema.fields)?,
                                 "op": "u",
                                 "ts_ms": ts_ms,
                             }
@@ -214,7 +286,7 @@ impl KafkaSink {
             if let Some(obj) = event_object {
                 self.send(
                     BaseRecord::to(self.config.topic.as_str())
-                        .key(self.gen_message_key().as_bytes())
+                        .key(pk_to_json(row, schema, &self.pk_indices)?.as_bytes())
                         .payload(obj.to_string().as_bytes()),
                 )
                 .await?;
@@ -226,7 +298,7 @@ impl KafkaSink {
     async fn append_only(&self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
         for (op, row) in chunk.rows() {
             if op == Op::Insert {
-                let record = Value::Object(record_to_json(row, schema.fields.clone())?).to_string();
+                let record = Value::Object(record_to_json(row, &schema.fields)?).to_string();
                 self.send(
                     BaseRecord::to(self.config.topic.as_str())
                         .key(self.gen_message_key().as_bytes())
@@ -247,11 +319,10 @@ impl Sink for KafkaSink {
         // &self.in_transaction_epoch.unwrap()) && in_txn_epoch <= epoch {     return Ok(())
         // }
 
-        println!("sink chunk {:?}", chunk);
-
-        match self.config.format.as_str() {
-            "append_only" => self.append_only(chunk, &self.schema).await,
-            "debezium" => {
+        match self.config.format {
+            SinkFormat::AppendOnly => self.append_only(chunk, &self.schema).await,
+            SinkFormat::Upsert => self.upsert(chunk, &self.schema).await,
+            SinkFormat::Debezium => {
                 self.debezium_update(
                     chunk,
                     &self.schema,
@@ -262,7 +333,6 @@ impl Sink for KafkaSink {
                 )
                 .await
             }
-            _ => unreachable!(),
         }
     }
 
@@ -280,21 +350,34 @@ impl Sink for KafkaSink {
         self.do_with_retry(|conductor| conductor.flush()) // flush before commit
             .await?;
 
+        let epoch = match self.in_transaction_epoch {
+            Some(epoch) => epoch,
+            None => {
+                tracing::error!(
+                    "commit without begin_epoch, last success epoch {:?}",
+                    self.state
+                );
+                return Err(SinkError::Kafka(KafkaError::Canceled));
+            }
+        };
+        if self.config.exactly_once {
+            self.do_with_retry(|conductor| conductor.mark_committed(epoch))
+                .await?;
+        }
         self.do_with_retry(|conductor| conductor.commit_transaction())
             .await?;
-        if let Some(epoch) = self.in_transaction_epoch.take() {
-            self.state = KafkaSinkState::Running(epoch);
-        } else {
-            tracing::error!(
-                "commit without begin_epoch, last success epoch {:?}",
-                self.state
-            );
-            return Err(SinkError::Kafka(KafkaError::Canceled));
-        }
+        self.in_transaction_epoch = None;
+        self.state = KafkaSinkState::Running(epoch);
         tracing::debug!("commit epoch {:?}", self.state);
         Ok(())
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        self.do_with_retry(|conductor| conductor.flush()).await?;
+        tracing::debug!("flush epoch {:?}", self.in_transaction_epoch);
+        Ok(())
+    }
+
     async fn abort(&mut self) -> Result<()> {
         self.do_with_retry(|conductor| conductor.abort_transaction())
             .await?;
@@ -302,15 +385,38 @@ impl Sink for KafkaSink {
         self.in_transaction_epoch = None;
         Ok(())
     }
+
+    async fn recover(&mut self, epoch: u64, chunks: Vec<StreamChunk>) -> Result<()> {
+        // The transaction left open by the producer before has been aborted when this one is
+        // created, so it's committed again unless it was committed before the failure.
+        let committed_epoch = self
+            .do_with_retry(|conductor| conductor.committed_epoch())
+            .await?;
+        if committed_epoch.map_or(false, |committed_epoch| committed_epoch >= epoch) {
+            tracing::info!("transaction of epoch {} has been committed", epoch);
+            return Ok(());
+        }
+        tracing::info!("commit transaction of epoch {} on recovery", epoch);
+        self.begin_epoch(epoch).await?;
+        for chunk in chunks {
+            self.write_batch(chunk).await?;
+        }
+        self.commit().await
+    }
 }
 
 impl Debug for KafkaSink {
-    fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
-        unimplemented!();
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaSink")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .field("schema", &self.schema)
+            .field("pk_indices", &self.pk_indices)
+            .finish()
     }
 }
 
-fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value> {
+pub(crate) fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value> {
     let scalar_ref = match datum {
         None => return Ok(Value::Null),
         Some(datum) => datum,
@@ -340,44 +446,80 @@ fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value
         (DataType::Varchar, ScalarRefImpl::Utf8(v)) => {
             json!(v)
         }
+        // Decimals are written as strings to keep their precision, as well as `NaN` and infinity.
         (DataType::Decimal, ScalarRefImpl::Decimal(v)) => {
-            // fixme
-            json!(v.to_string())
+            json!(v.to_text())
+        }
+        (DataType::Date, ScalarRefImpl::NaiveDate(v)) => {
+            json!(v.to_text())
+        }
+        (DataType::Time, ScalarRefImpl::NaiveTime(v)) => {
+            json!(v.to_text())
+        }
+        (DataType::Timestamp, ScalarRefImpl::NaiveDateTime(v)) => {
+            json!(v.to_text())
         }
-        (DataType::Time, ScalarRefImpl::NaiveTime(_v)) => {
-            unimplemented!()
+        (DataType::Timestampz, ScalarRefImpl::Int64(v)) => {
+            // Timestamps with time zone are stored as microseconds since epoch in UTC.
+            json!(format!("{}+00:00", NaiveDateTimeWrapper::from_protobuf(v)?))
         }
-        (DataType::List { .. }, ScalarRefImpl::List(list_ref)) => {
-            let mut vec = Vec::with_capacity(field.sub_fields.len());
-            for (sub_datum_ref, sub_field) in list_ref
+        (DataType::Interval, ScalarRefImpl::Interval(v)) => {
+            json!(v.to_text())
+        }
+        (DataType::Bytea, ScalarRefImpl::Bytea(v)) => {
+            json!(format_bytes(v))
+        }
+        (DataType::Jsonb, ScalarRefImpl::Jsonb(v)) => v.value().clone(),
+        (DataType::List { datatype }, ScalarRefImpl::List(list_ref)) => {
+            // The elements share the sub fields of the list, e.g. the fields of a struct element.
+            let elem_field = Field {
+                data_type: datatype.as_ref().clone(),
+                name: field.name.clone(),
+                sub_fields: field.sub_fields.clone(),
+                type_name: field.type_name.clone(),
+            };
+            let values: Vec<_> = list_ref
                 .values_ref()
                 .into_iter()
-                .zip_eq(field.sub_fields.iter())
-            {
-                let value = datum_to_json_object(sub_field, sub_datum_ref)?;
-                vec.push(value);
-            }
-            json!(vec)
+                .map(|elem| datum_to_json_object(&elem_field, elem))
+                .try_collect()?;
+            json!(values)
         }
-        (DataType::Struct { .. }, ScalarRefImpl::Struct(struct_ref)) => {
-            let mut map = Map::with_capacity(field.sub_fields.len());
+        (DataType::Struct(struct_type), ScalarRefImpl::Struct(struct_ref)) => {
+            // Fall back to the struct type if the sub fields are not set.
+            let sub_fields = if field.sub_fields.len() == struct_type.fields.len() {
+                field.sub_fields.clone()
+            } else {
+                struct_type
+                    .fields
+                    .iter()
+                    .zip_eq(&struct_type.field_names)
+                    .map(|(data_type, name)| Field::with_name(data_type.clone(), name))
+                    .collect()
+            };
+            let mut map = Map::with_capacity(sub_fields.len());
             for (sub_datum_ref, sub_field) in struct_ref
                 .fields_ref()
                 .into_iter()
-                .zip_eq(field.sub_fields.iter())
+                .zip_eq(sub_fields.iter())
             {
                 let value = datum_to_json_object(sub_field, sub_datum_ref)?;
                 map.insert(sub_field.name.clone(), value);
             }
             json!(map)
         }
-        _ => unimplemented!(),
+        (data_type, scalar_ref) => {
+            return Err(ArrayError::internal(format!(
+                "mismatched data type {} and value {:?}",
+                data_type, scalar_ref
+            )))
+        }
     };
 
     Ok(value)
 }
 
-fn record_to_json(row: RowRef<'_>, schema: Vec<Field>) -> Result<Map<String, Value>> {
+pub(crate) fn record_to_json(row: RowRef<'_>, schema: &[Field]) -> Result<Map<String, Value>> {
     let mut mappings = Map::with_capacity(schema.len());
     for (field, datum_ref) in schema.iter().zip_eq(row.values()) {
         let key = field.name.clone();
@@ -388,27 +530,110 @@ fn record_to_json(row: RowRef<'_>, schema: Vec<Field>) -> Result<Map<String, Val
     Ok(mappings)
 }
 
+/// The key of a row for the upsert and Debezium formats, which is a JSON object of its primary key
+/// columns.
+fn pk_to_json(row: RowRef<'_>, schema: &Schema, pk_indices: &[usize]) -> Result<String> {
+    let mut mappings = Map::with_capacity(pk_indices.len());
+    for &idx in pk_indices {
+        let field = &schema.fields[idx];
+        let value = datum_to_json_object(field, row.value_at(idx))
+            .map_err(|e| SinkError::JsonParse(e.to_string()))?;
+        mappings.insert(field.name.clone(), value);
+    }
+    Ok(Value::Object(mappings).to_string())
+}
+
+/// The messages of a chunk in the upsert format, i.e. the keys and the payloads of the rows, where
+/// deletes are tombstones without payload.
+fn upsert_messages(
+    chunk: StreamChunk,
+    schema: &Schema,
+    pk_indices: &[usize],
+) -> Result<Vec<(String, Option<String>)>> {
+    let mut messages = Vec::with_capacity(chunk.cardinality());
+    // The key of the last `UpdateDelete`, which only needs a tombstone if the following
+    // `UpdateInsert` changes the key.
+    let mut update_delete_key: Option<String> = None;
+    for (op, row) in chunk.rows() {
+        let key = pk_to_json(row, schema, pk_indices)?;
+        match op {
+            Op::Insert | Op::UpdateInsert => {
+                if let Some(deleted_key) = update_delete_key.take() && deleted_key != key {
+                    messages.push((deleted_key, None));
+                }
+                let record = Value::Object(record_to_json(row, &schema.fields)?).to_string();
+                messages.push((key, Some(record)));
+            }
+            Op::Delete => messages.push((key, None)),
+            Op::UpdateDelete => {
+                if let Some(deleted_key) = update_delete_key.replace(key) {
+                    messages.push((deleted_key, None));
+                }
+            }
+        }
+    }
+    if let Some(deleted_key) = update_delete_key {
+        messages.push((deleted_key, None));
+    }
+    Ok(messages)
+}
+
 pub fn chunk_to_json(chunk: StreamChunk, schema: &Schema) -> Result<Vec<String>> {
     let mut records: Vec<String> = Vec::with_capacity(chunk.capacity());
     for (_, row) in chunk.rows() {
-        let record = Value::Object(record_to_json(row, schema.fields.clone())?);
+        let record = Value::Object(record_to_json(row, &schema.fields)?);
         records.push(record.to_string());
     }
 
     Ok(records)
 }
 
+/// The Debezium schema of a field, whose type is one of the types of Kafka Connect. The types
+/// without a counterpart, e.g. decimals and timestamps, are written as strings.
+fn field_to_json(field: &Field) -> Value {
+    let r#type = match field.data_type() {
+        DataType::Boolean => "boolean",
+        DataType::Int16 => "int16",
+        DataType::Int32 => "int32",
+        DataType::Int64 => "int64",
+        DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        DataType::Struct(_) => {
+            return json!({
+                "field": field.name,
+                "optional": true,
+                "type": "struct",
+                "fields": fields_to_json(&field.sub_fields),
+            })
+        }
+        DataType::List { datatype } => {
+            let elem_field = Field::with_name(datatype.as_ref().clone(), field.name.clone());
+            return json!({
+                "field": field.name,
+                "optional": true,
+                "type": "array",
+                "items": field_to_json(&elem_field),
+            });
+        }
+        DataType::Decimal
+        | DataType::Date
+        | DataType::Varchar
+        | DataType::Time
+        | DataType::Timestamp
+        | DataType::Timestampz
+        | DataType::Interval
+        | DataType::Bytea
+        | DataType::Jsonb => "string",
+    };
+    json!({
+        "field": field.name,
+        "optional": true,
+        "type": r#type,
+    })
+}
+
 fn fields_to_json(fields: &[Field]) -> Value {
-    let mut res = Vec::new();
-    fields.iter().for_each(|field| {
-        res.push(json!({
-            "field": field.name,
-            "optional": true,
-            "type": field.type_name,
-        }))
-    });
-
-    json!(res)
+    json!(fields.iter().map(field_to_json).collect_vec())
 }
 
 fn schema_to_json(schema: &Schema) -> Value {
@@ -436,9 +661,15 @@ fn schema_to_json(schema: &Schema) -> Value {
 pub struct KafkaTransactionConductor {
     properties: KafkaConfig,
     inner: ThreadedProducer<DefaultProducerContext>,
+    /// The consumer of the group named after the transactional id if the messages are delivered
+    /// exactly once. The epoch of each transaction is committed as the offset of the group within
+    /// the transaction, by which a transaction is known to be committed after a failure.
+    marker: Option<BaseConsumer>,
 }
 
 impl KafkaTransactionConductor {
+    /// Creates the transactional producer. Initializing the transactions fences the producers
+    /// before with the same transactional id, and aborts the transaction left open by them.
     async fn new(config: KafkaConfig) -> Result<Self> {
         let inner: ThreadedProducer<DefaultProducerContext> = ClientConfig::new()
             .set("bootstrap.servers", &config.brokers)
@@ -449,12 +680,62 @@ impl KafkaTransactionConductor {
 
         inner.init_transactions(config.timeout).await?;
 
+        let marker = if config.exactly_once {
+            Some(
+                ClientConfig::new()
+                    .set("bootstrap.servers", &config.brokers)
+                    .set("group.id", &config.identifier)
+                    .set("enable.auto.commit", "false")
+                    .set("isolation.level", "read_committed")
+                    .create_with_context(DefaultConsumerContext)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
         Ok(KafkaTransactionConductor {
             properties: config,
             inner,
+            marker,
         })
     }
 
+    /// The partition whose offset of the marker group is the epoch of the last committed
+    /// transaction. No message is read from it.
+    fn marker_partition(&self) -> TopicPartitionList {
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(self.properties.topic.as_str(), MARKER_PARTITION);
+        tpl
+    }
+
+    /// Marks the transaction of `epoch` as committed once it's committed.
+    async fn mark_committed(&self, epoch: u64) -> KafkaResult<()> {
+        let marker = self.marker.as_ref().ok_or(KafkaError::Canceled)?;
+        let group_metadata = marker.group_metadata().ok_or(KafkaError::Canceled)?;
+        let mut tpl = self.marker_partition();
+        tpl.set_all_offsets(Offset::Offset(epoch as i64))?;
+        self.inner
+            .send_offsets_to_transaction(&tpl, &group_metadata, self.properties.timeout)
+            .await
+    }
+
+    /// Returns the epoch of the last transaction committed with [`Self::mark_committed`].
+    async fn committed_epoch(&self) -> KafkaResult<Option<u64>> {
+        let marker = self.marker.as_ref().ok_or(KafkaError::Canceled)?;
+        let committed =
+            marker.committed_offsets(self.marker_partition(), self.properties.timeout)?;
+        Ok(
+            match committed
+                .find_partition(self.properties.topic.as_str(), MARKER_PARTITION)
+                .map(|elem| elem.offset())
+            {
+                Some(Offset::Offset(epoch)) => Some(epoch as u64),
+                _ => None,
+            },
+        )
+    }
+
     #[expect(clippy::unused_async)]
     async fn start_transaction(&self) -> KafkaResult<()> {
         self.inner.begin_transaction()
@@ -489,7 +770,10 @@ impl KafkaTransactionConductor {
 #[cfg(test)]
 mod test {
     use maplit::hashmap;
+    use risingwave_common::array::ListValue;
+    use risingwave_common::row::Row;
     use risingwave_common::test_prelude::StreamChunkTestExt;
+    use risingwave_common::types::NaiveDateWrapper;
 
     use super::*;
 
@@ -499,7 +783,7 @@ mod test {
         let properties = hashmap! {
             "kafka.brokers".to_string() => "localhost:29092".to_string(),
             "identifier".to_string() => "test_sink_1".to_string(),
-            "sink.type".to_string() => "append_only".to_string(),
+            "format".to_string() => "append_only".to_string(),
             "kafka.topic".to_string() => "test_topic".to_string(),
         };
         let schema = Schema::new(vec![
@@ -517,7 +801,9 @@ mod test {
             },
         ]);
         let kafka_config = KafkaConfig::from_hashmap(properties)?;
-        let mut sink = KafkaSink::new(kafka_config.clone(), schema).await.unwrap();
+        let mut sink = KafkaSink::new(kafka_config.clone(), schema, vec![0])
+            .await
+            .unwrap();
 
         for i in 0..10 {
             let mut fail_flag = false;
@@ -603,7 +889,7 @@ mod test {
 
         let json_chunk = chunk_to_json(chunk, &schema).unwrap();
         let schema_json = schema_to_json(&schema);
-        assert_eq!(schema_json.to_string(), "{\"fields\":[{\"field\":\"before\",\"fields\":[{\"field\":\"v1\",\"optional\":true,\"type\":\"\"},{\"field\":\"v2\",\"optional\":true,\"type\":\"\"},{\"field\":\"v3\",\"optional\":true,\"type\":\"\"}],\"optional\":true,\"type\":\"struct\"},{\"field\":\"after\",\"fields\":[{\"field\":\"v1\",\"optional\":true,\"type\":\"\"},{\"field\":\"v2\",\"optional\":true,\"type\":\"\"},{\"field\":\"v3\",\"optional\":true,\"type\":\"\"}],\"optional\":true,\"type\":\"struct\"}],\"optional\":false,\"type\":\"struct\"}");
+        assert_eq!(schema_json.to_string(), "{\"fields\":[{\"field\":\"before\",\"fields\":[{\"field\":\"v1\",\"optional\":true,\"type\":\"int32\"},{\"field\":\"v2\",\"optional\":true,\"type\":\"float\"},{\"field\":\"v3\",\"fields\":[{\"field\":\"v4\",\"optional\":true,\"type\":\"int32\"},{\"field\":\"v5\",\"optional\":true,\"type\":\"float\"}],\"optional\":true,\"type\":\"struct\"}],\"optional\":true,\"type\":\"struct\"},{\"field\":\"after\",\"fields\":[{\"field\":\"v1\",\"optional\":true,\"type\":\"int32\"},{\"field\":\"v2\",\"optional\":true,\"type\":\"float\"},{\"field\":\"v3\",\"fields\":[{\"field\":\"v4\",\"optional\":true,\"type\":\"int32\"},{\"field\":\"v5\",\"optional\":true,\"type\":\"float\"}],\"optional\":true,\"type\":\"struct\"}],\"optional\":true,\"type\":\"struct\"}],\"optional\":false,\"type\":\"struct\"}");
         assert_eq!(
             json_chunk[0].as_str(),
             "{\"v1\":0,\"v2\":0.0,\"v3\":{\"v4\":0,\"v5\":0.0}}"
@@ -611,4 +897,130 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_upsert_messages() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Varchar, "v"),
+        ]);
+        let chunk = StreamChunk::from_pretty(
+            "  i T
+            +  1 a
+            +  2 b
+            U- 1 a
+            U+ 1 c
+            U- 2 b
+            U+ 3 b
+            -  1 c",
+        );
+        let messages = upsert_messages(chunk, &schema, &[0])?;
+        let message = |key: &str, record: Option<&str>| {
+            (key.to_string(), record.map(|record| record.to_string()))
+        };
+        assert_eq!(
+            messages,
+            vec![
+                message("{\"id\":1}", Some("{\"id\":1,\"v\":\"a\"}")),
+                message("{\"id\":2}", Some("{\"id\":2,\"v\":\"b\"}")),
+                message("{\"id\":1}", Some("{\"id\":1,\"v\":\"c\"}")),
+                // The key changes in the update, so the old key is deleted.
+                message("{\"id\":2}", None),
+                message("{\"id\":3}", Some("{\"id\":3,\"v\":\"b\"}")),
+                // A delete is a tombstone with the key set and no payload.
+                message("{\"id\":1}", None),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_kafka_config_format() {
+        let properties = |format: &str| {
+            hashmap! {
+                "kafka.brokers".to_string() => "localhost:29092".to_string(),
+                "identifier".to_string() => "test_sink_1".to_string(),
+                "format".to_string() => format.to_string(),
+                "kafka.topic".to_string() => "test_topic".to_string(),
+            }
+        };
+        assert_eq!(
+            KafkaConfig::from_hashmap(properties("upsert"))
+                .unwrap()
+                .format,
+            SinkFormat::Upsert
+        );
+        assert_eq!(
+            KafkaConfig::from_hashmap(properties("Debezium"))
+                .unwrap()
+                .format,
+            SinkFormat::Debezium
+        );
+        assert!(KafkaConfig::from_hashmap(properties("xml")).is_err());
+    }
+
+    #[test]
+    fn test_kafka_config_delivery_guarantee() {
+        let config = |delivery_guarantee: Option<&str>| {
+            let mut properties = hashmap! {
+                "kafka.brokers".to_string() => "localhost:29092".to_string(),
+                "identifier".to_string() => "test_sink_1".to_string(),
+                "format".to_string() => "append_only".to_string(),
+                "kafka.topic".to_string() => "test_topic".to_string(),
+            };
+            if let Some(delivery_guarantee) = delivery_guarantee {
+                properties.insert(
+                    DELIVERY_GUARANTEE_KEY.to_string(),
+                    delivery_guarantee.to_string(),
+                );
+            }
+            KafkaConfig::from_hashmap(properties)
+        };
+        assert!(!config(None).unwrap().exactly_once);
+        assert!(!config(Some("at_least_once")).unwrap().exactly_once);
+        assert!(config(Some("Exactly_Once")).unwrap().exactly_once);
+        assert!(config(Some("at_most_once")).is_err());
+    }
+
+    #[test]
+    fn test_datum_to_json() {
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Date, "d"),
+            Field::with_name(DataType::Timestamp, "ts"),
+            Field::with_name(
+                DataType::List {
+                    datatype: Box::new(DataType::Int32),
+                },
+                "l",
+            ),
+        ]);
+        let chunk = StreamChunk::from_rows(
+            &[
+                (
+                    Op::Insert,
+                    Row::new(vec![
+                        Some(NaiveDateWrapper::from_ymd_uncheck(2022, 1, 2).into()),
+                        Some(NaiveDateTimeWrapper::from_timestamp_uncheck(1641092645, 0).into()),
+                        Some(ListValue::new(vec![Some(1.into()), Some(2.into())]).into()),
+                    ]),
+                ),
+                (
+                    Op::Insert,
+                    Row::new(vec![
+                        None,
+                        None,
+                        Some(ListValue::new(vec![Some(3.into())]).into()),
+                    ]),
+                ),
+            ],
+            &schema.data_types(),
+        );
+        let json_chunk = chunk_to_json(chunk, &schema).unwrap();
+        assert_eq!(
+            json_chunk[0].as_str(),
+            "{\"d\":\"2022-01-02\",\"l\":[1,2],\"ts\":\"2022-01-02 03:04:05\"}"
+        );
+        assert_eq!(json_chunk[1].as_str(), "{\"d\":null,\"l\":[3],\"ts\":null}");
+    }
 }
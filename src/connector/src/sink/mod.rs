@@ -22,6 +22,7 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use enum_as_inner::EnumAsInner;
+use itertools::Itertools;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, RwError};
@@ -33,7 +34,7 @@ pub use tracing;
 use crate::sink::console::{ConsoleConfig, ConsoleSink, CONSOLE_SINK};
 use crate::sink::kafka::{KafkaConfig, KafkaSink, KAFKA_SINK};
 pub use crate::sink::mysql::{MySqlConfig, MySqlSink, MYSQL_SINK};
-use crate::sink::redis::{RedisConfig, RedisSink};
+use crate::sink::redis::{RedisConfig, RedisSink, REDIS_SINK};
 use crate::sink::remote::{RemoteConfig, RemoteSink};
 use crate::ConnectorParams;
 
@@ -48,9 +49,73 @@ pub trait Sink {
     // commits the current transaction and marks all messages in the transaction success.
     async fn commit(&mut self) -> Result<()>;
 
+    // delivers all messages in the current transaction without committing it, so that it can be
+    // committed later without failing because of them.
+    async fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     // aborts the current transaction because some error happens. we should rollback to the last
     // commit point.
     async fn abort(&mut self) -> Result<()>;
+
+    // commits the transaction of `epoch` flushed before a failure, whose checkpoint has been
+    // committed, by writing its `chunks` again in a new transaction. it's skipped if the sink
+    // knows that the transaction has been committed before the failure.
+    async fn recover(&mut self, epoch: u64, chunks: Vec<StreamChunk>) -> Result<()> {
+        self.begin_epoch(epoch).await?;
+        for chunk in chunks {
+            self.write_batch(chunk).await?;
+        }
+        self.commit().await
+    }
+}
+
+pub const SINK_FORMAT_APPEND_ONLY: &str = "append_only";
+pub const SINK_FORMAT_UPSERT: &str = "upsert";
+pub const SINK_FORMAT_DEBEZIUM: &str = "debezium";
+
+/// How the changes written to a sink are represented in the external system, set by the
+/// `format` option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SinkFormat {
+    /// Only inserts are written, and the other changes are ignored.
+    AppendOnly,
+    /// Rows are written by their primary key, and a delete removes the row with the key.
+    Upsert,
+    /// Every change is written as a Debezium envelope with the row before and after it.
+    Debezium,
+}
+
+impl SinkFormat {
+    /// Parses the `format` option, which must be one of `supported`.
+    pub fn from_option(format: &str, supported: &[SinkFormat]) -> Result<Self> {
+        let sink_format = match format.to_lowercase().as_str() {
+            SINK_FORMAT_APPEND_ONLY => Some(SinkFormat::AppendOnly),
+            SINK_FORMAT_UPSERT => Some(SinkFormat::Upsert),
+            SINK_FORMAT_DEBEZIUM => Some(SinkFormat::Debezium),
+            _ => None,
+        };
+        match sink_format {
+            Some(sink_format) if supported.contains(&sink_format) => Ok(sink_format),
+            _ => Err(SinkError::Config(format!(
+                "format must be set to one of {}, got \"{}\"",
+                supported
+                    .iter()
+                    .map(|f| format!("\"{}\"", f.as_str()))
+                    .join(", "),
+                format
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SinkFormat::AppendOnly => SINK_FORMAT_APPEND_ONLY,
+            SinkFormat::Upsert => SINK_FORMAT_UPSERT,
+            SinkFormat::Debezium => SINK_FORMAT_DEBEZIUM,
+        }
+    }
 }
 
 #[derive(Clone, Debug, EnumAsInner)]
@@ -80,6 +145,7 @@ impl SinkConfig {
         match sink_type.to_lowercase().as_str() {
             KAFKA_SINK => Ok(SinkConfig::Kafka(KafkaConfig::from_hashmap(properties)?)),
             MYSQL_SINK => Ok(SinkConfig::Mysql(MySqlConfig::from_hashmap(properties)?)),
+            REDIS_SINK => Ok(SinkConfig::Redis(RedisConfig::from_hashmap(properties)?)),
             CONSOLE_SINK => Ok(SinkConfig::Console(ConsoleConfig::from_hashmap(
                 properties,
             )?)),
@@ -115,9 +181,15 @@ impl SinkImpl {
         connector_params: ConnectorParams,
     ) -> Result<Self> {
         Ok(match cfg {
-            SinkConfig::Mysql(cfg) => SinkImpl::MySql(Box::new(MySqlSink::new(cfg, schema).await?)),
-            SinkConfig::Redis(cfg) => SinkImpl::Redis(Box::new(RedisSink::new(cfg, schema)?)),
-            SinkConfig::Kafka(cfg) => SinkImpl::Kafka(Box::new(KafkaSink::new(cfg, schema).await?)),
+            SinkConfig::Mysql(cfg) => {
+                SinkImpl::MySql(Box::new(MySqlSink::new(cfg, schema, pk_indices).await?))
+            }
+            SinkConfig::Redis(cfg) => {
+                SinkImpl::Redis(Box::new(RedisSink::new(cfg, schema, pk_indices).await?))
+            }
+            SinkConfig::Kafka(cfg) => {
+                SinkImpl::Kafka(Box::new(KafkaSink::new(cfg, schema, pk_indices).await?))
+            }
             SinkConfig::Console(cfg) => SinkImpl::Console(Box::new(ConsoleSink::new(cfg, schema)?)),
             SinkConfig::Remote(cfg) => SinkImpl::Remote(Box::new(
                 RemoteSink::new(cfg, schema, pk_indices, connector_params).await?,
@@ -135,6 +207,15 @@ impl SinkImpl {
         }
     }
 
+    /// Whether the transaction of the sink should only be committed on checkpoint barriers, so
+    /// that nothing after the last checkpoint is committed to the external system.
+    pub fn commits_on_checkpoint(&self) -> bool {
+        match self {
+            SinkImpl::Kafka(sink) => sink.config.exactly_once,
+            _ => false,
+        }
+    }
+
     pub async fn prepare(&mut self) -> Result<()> {
         match self {
             SinkImpl::MySql(sink) => sink.prepare().await,
@@ -175,6 +256,13 @@ impl Sink for SinkImpl {
         }
     }
 
+    async fn flush(&mut self) -> Result<()> {
+        match self {
+            SinkImpl::Kafka(sink) => sink.flush().await,
+            _ => Ok(()),
+        }
+    }
+
     async fn abort(&mut self) -> Result<()> {
         match self {
             SinkImpl::MySql(sink) => sink.abort().await,
@@ -184,6 +272,16 @@ impl Sink for SinkImpl {
             SinkImpl::Console(sink) => sink.abort().await,
         }
     }
+
+    async fn recover(&mut self, epoch: u64, chunks: Vec<StreamChunk>) -> Result<()> {
+        match self {
+            SinkImpl::MySql(sink) => sink.recover(epoch, chunks).await,
+            SinkImpl::Redis(sink) => sink.recover(epoch, chunks).await,
+            SinkImpl::Kafka(sink) => sink.recover(epoch, chunks).await,
+            SinkImpl::Remote(sink) => sink.recover(epoch, chunks).await,
+            SinkImpl::Console(sink) => sink.recover(epoch, chunks).await,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, SinkError>;
@@ -196,6 +294,8 @@ pub enum SinkError {
     MySqlInner(#[from] mysql_async::Error),
     #[error("Kafka error: {0}")]
     Kafka(#[from] rdkafka::error::KafkaError),
+    #[error("Redis error: {0}")]
+    Redis(String),
     #[error("Remote sink error: {0}")]
     Remote(String),
     #[error("Json parse error: {0}")]
@@ -210,6 +310,12 @@ impl From<RpcError> for SinkError {
     }
 }
 
+impl From<::redis::RedisError> for SinkError {
+    fn from(value: ::redis::RedisError) -> Self {
+        SinkError::Redis(value.to_string())
+    }
+}
+
 impl From<SinkError> for RwError {
     fn from(e: SinkError) -> Self {
         ErrorCode::SinkError(Box::new(e)).into()
@@ -20,12 +20,14 @@ use itertools::{join, Itertools};
 use mysql_async::prelude::*;
 use mysql_async::*;
 use risingwave_common::array::Op::*;
-use risingwave_common::array::StreamChunk;
-use risingwave_common::catalog::Schema;
-use risingwave_common::types::{DataType, Datum, Decimal, ScalarImpl};
+use risingwave_common::array::{RowRef, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::to_text::ToText;
+use risingwave_common::types::{DataType, DatumRef, Decimal, NaiveDateTimeWrapper, ScalarRefImpl};
 use strum_macros;
 
-use crate::sink::{Result, Sink, SinkError};
+use crate::sink::kafka::datum_to_json_object;
+use crate::sink::{Result, Sink, SinkError, SinkFormat};
 
 pub const MYSQL_SINK: &str = "mysql";
 
@@ -36,6 +38,8 @@ pub struct MySqlConfig {
     pub database: Option<String>,
     pub user: Option<String>,
     pub password: Option<String>,
+    /// Either `append_only` or `upsert`, which is the default.
+    pub format: SinkFormat,
 }
 
 impl MySqlConfig {
@@ -45,6 +49,12 @@ impl MySqlConfig {
         let database = values.get("database");
         let user = values.get("user");
         let password = values.get("password");
+        let format = match values.get("format") {
+            Some(format) => {
+                SinkFormat::from_option(format, &[SinkFormat::AppendOnly, SinkFormat::Upsert])?
+            }
+            None => SinkFormat::Upsert,
+        };
 
         Ok(MySqlConfig {
             endpoint: endpoint.to_string(),
@@ -52,6 +62,7 @@ impl MySqlConfig {
             database: database.cloned(),
             user: user.cloned(),
             password: password.cloned(),
+            format,
         })
     }
 }
@@ -62,37 +73,59 @@ impl MySqlConfig {
 pub struct MySqlSink {
     cfg: MySqlConfig,
     schema: Schema,
+    pk_indices: Vec<usize>,
     conn: Conn,
     chunk_cache: Vec<(StreamChunk, Schema)>,
 }
 
 impl MySqlSink {
-    pub async fn new(cfg: MySqlConfig, schema: Schema) -> Result<Self> {
+    pub async fn new(cfg: MySqlConfig, schema: Schema, pk_indices: Vec<usize>) -> Result<Self> {
+        if cfg.format == SinkFormat::Upsert && pk_indices.is_empty() {
+            return Err(SinkError::Config(
+                "mysql upsert sink requires the upstream to have a primary key".to_string(),
+            ));
+        }
         // Build a connection and start transaction
         let conn = Conn::new(get_builder(&cfg)).await?;
         Ok(Self {
             cfg,
             conn,
             schema,
+            pk_indices,
             chunk_cache: vec![],
         })
     }
 
     pub async fn prepare(&mut self) -> Result<()> {
-        // Create a table
+        // Create a table, with the primary key that `ON DUPLICATE KEY UPDATE` relies on for upsert.
+        let mut definitions = self
+            .schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                let data_type = if self.pk_indices.contains(&idx) {
+                    MySqlDataType::key_of(&field.data_type)
+                } else {
+                    MySqlDataType::from(&field.data_type)
+                };
+                format!("`{}` {}", field.name, data_type)
+            })
+            .collect_vec();
+        if self.cfg.format == SinkFormat::Upsert {
+            definitions.push(format!(
+                "PRIMARY KEY ({})",
+                self.pk_indices
+                    .iter()
+                    .map(|&idx| format!("`{}`", self.schema.fields[idx].name))
+                    .join(", ")
+            ));
+        }
         let create_table = format!(
             r"CREATE TABLE IF NOT EXISTS `{}`.`{}` ( {} );",
             self.cfg.database.clone().unwrap(),
             self.cfg.table,
-            join(
-                self.schema
-                    .names()
-                    .iter()
-                    .zip_eq(self.schema.data_types().iter())
-                    .map(|(n, dt)| format!("`{}` {}", n, MySqlDataType::from(dt)))
-                    .collect_vec(),
-                ", ",
-            )
+            join(definitions, ", ")
         );
         self.conn.query_drop(create_table).await?;
         Ok(())
@@ -143,30 +176,45 @@ impl fmt::Display for MySqlValue {
     }
 }
 
-impl TryFrom<Datum> for MySqlValue {
-    type Error = SinkError;
-
-    fn try_from(datum: Datum) -> Result<MySqlValue> {
-        if let Some(scalar) = datum {
-            match scalar {
-                ScalarImpl::Int16(v) => Ok(MySqlValue(v.into())),
-                ScalarImpl::Int32(v) => Ok(MySqlValue(v.into())),
-                ScalarImpl::Int64(v) => Ok(MySqlValue(v.into())),
-                ScalarImpl::Float32(v) => Ok(MySqlValue(f32::from(v).into())),
-                ScalarImpl::Float64(v) => Ok(MySqlValue(f64::from(v).into())),
-                ScalarImpl::Bool(v) => Ok(MySqlValue(v.into())),
-                ScalarImpl::Decimal(Decimal::Normalized(v)) => Ok(MySqlValue(v.into())),
-                ScalarImpl::Decimal(_) => panic!("NaN, -inf, +inf are not supported by MySQL"),
-                ScalarImpl::Utf8(v) => Ok(MySqlValue(String::from(v).into())),
-                ScalarImpl::NaiveDate(v) => Ok(MySqlValue(format!("{}", v).into())),
-                ScalarImpl::NaiveTime(v) => Ok(MySqlValue(format!("{}", v).into())),
-                ScalarImpl::NaiveDateTime(v) => Ok(MySqlValue(format!("{}", v).into())),
-                // ScalarImpl::Interval(v) => Ok(MySqlValue(Value::NULL)),
-                _ => unimplemented!(),
+impl MySqlValue {
+    fn from_datum(datum: DatumRef<'_>, field: &Field) -> Result<MySqlValue> {
+        let scalar = match datum {
+            Some(scalar) => scalar,
+            None => return Ok(MySqlValue(Value::NULL)),
+        };
+        let value = match (field.data_type(), scalar) {
+            (_, ScalarRefImpl::Int16(v)) => v.into(),
+            // Timestamps with time zone are stored as microseconds since epoch in UTC.
+            (DataType::Timestampz, ScalarRefImpl::Int64(v)) => {
+                NaiveDateTimeWrapper::from_protobuf(v)
+                    .map_err(|e| SinkError::MySql(e.to_string()))?
+                    .to_text()
+                    .into()
             }
-        } else {
-            Ok(MySqlValue(Value::NULL))
-        }
+            (_, ScalarRefImpl::Int32(v)) => v.into(),
+            (_, ScalarRefImpl::Int64(v)) => v.into(),
+            (_, ScalarRefImpl::Float32(v)) => f32::from(v).into(),
+            (_, ScalarRefImpl::Float64(v)) => f64::from(v).into(),
+            (_, ScalarRefImpl::Bool(v)) => v.into(),
+            (_, ScalarRefImpl::Decimal(Decimal::Normalized(v))) => v.into(),
+            (_, ScalarRefImpl::Decimal(v)) => {
+                return Err(SinkError::MySql(format!("{} is not supported by MySQL", v)))
+            }
+            (_, ScalarRefImpl::Utf8(v)) => v.to_string().into(),
+            (_, ScalarRefImpl::NaiveDate(v)) => v.to_text().into(),
+            (_, ScalarRefImpl::NaiveTime(v)) => v.to_text().into(),
+            (_, ScalarRefImpl::NaiveDateTime(v)) => v.to_text().into(),
+            (_, ScalarRefImpl::Interval(v)) => v.to_text().into(),
+            (_, ScalarRefImpl::Bytea(v)) => v.to_vec().into(),
+            // Nested values are stored as JSON.
+            (_, ScalarRefImpl::Jsonb(_) | ScalarRefImpl::Struct(_) | ScalarRefImpl::List(_)) => {
+                datum_to_json_object(field, datum)
+                    .map_err(|e| SinkError::JsonParse(e.to_string()))?
+                    .to_string()
+                    .into()
+            }
+        };
+        Ok(MySqlValue(value))
     }
 }
 
@@ -179,9 +227,33 @@ enum MySqlDataType {
     BIGINT,
     FLOAT,
     DOUBLE,
+    #[strum(serialize = "DECIMAL(65, 30)")]
+    DECIMAL,
+    DATE,
+    #[strum(serialize = "TIME(6)")]
+    TIME,
+    #[strum(serialize = "DATETIME(6)")]
+    DATETIME,
     // TODO(nanderstabel): find solution for varchar length.
     #[strum(serialize = "VARCHAR(255)")]
     VARCHAR,
+    TEXT,
+    #[strum(serialize = "VARBINARY(255)")]
+    VARBINARY,
+    BLOB,
+    JSON,
+}
+
+impl MySqlDataType {
+    /// The type of a primary key column, as MySQL can not index `TEXT` and `BLOB` columns without
+    /// a prefix length.
+    fn key_of(data_type: &DataType) -> MySqlDataType {
+        match MySqlDataType::from(data_type) {
+            MySqlDataType::TEXT | MySqlDataType::JSON => MySqlDataType::VARCHAR,
+            MySqlDataType::BLOB => MySqlDataType::VARBINARY,
+            data_type => data_type,
+        }
+    }
 }
 
 impl From<&DataType> for MySqlDataType {
@@ -193,8 +265,15 @@ impl From<&DataType> for MySqlDataType {
             DataType::Int64 => MySqlDataType::BIGINT,
             DataType::Float32 => MySqlDataType::FLOAT,
             DataType::Float64 => MySqlDataType::DOUBLE,
+            DataType::Decimal => MySqlDataType::DECIMAL,
+            DataType::Date => MySqlDataType::DATE,
+            DataType::Time => MySqlDataType::TIME,
+            // Timestamps with time zone are written in UTC.
+            DataType::Timestamp | DataType::Timestampz => MySqlDataType::DATETIME,
             DataType::Varchar => MySqlDataType::VARCHAR,
-            _ => unimplemented!(),
+            DataType::Interval => MySqlDataType::TEXT,
+            DataType::Bytea => MySqlDataType::BLOB,
+            DataType::Jsonb | DataType::Struct(_) | DataType::List { .. } => MySqlDataType::JSON,
         }
     }
 }
@@ -213,7 +292,7 @@ impl Sink for MySqlSink {
     async fn commit(&mut self) -> Result<()> {
         let mut txn = self.conn.start_transaction(TxOpts::default()).await?;
         for (chunk, schema) in &self.chunk_cache {
-            write_to_mysql(&mut txn, chunk, schema.clone(), &self.cfg).await?;
+            write_to_mysql(&mut txn, chunk, schema, &self.pk_indices, &self.cfg).await?;
         }
         txn.commit().await?;
 
@@ -226,69 +305,112 @@ impl Sink for MySqlSink {
     }
 }
 
-async fn write_to_mysql<'a>(
-    txn: &mut Transaction<'a>,
+/// Builds the statements that write the changes of a chunk, one per row.
+///
+/// In the upsert format, inserts are written with `INSERT ... ON DUPLICATE KEY UPDATE`, and
+/// deletes are written with `DELETE` by the primary key. An update is written as an upsert of the
+/// new row, after deleting the old row if the primary key changes. In the append-only format, only
+/// inserts are written.
+fn build_statements(
     chunk: &StreamChunk,
-    schema: Schema,
+    schema: &Schema,
+    pk_indices: &[usize],
     config: &MySqlConfig,
-) -> Result<()> {
-    // Closure that takes an idx to create a vector of MySqlValues from a StreamChunk 'row'.
-    let values = |idx| -> Result<Vec<MySqlValue>> {
-        chunk
-            .columns()
-            .iter()
-            .map(|x| MySqlValue::try_from(x.array_ref().datum_at(idx)))
-            .collect_vec()
-            .into_iter()
-            .collect()
+) -> Result<Vec<String>> {
+    let values = |row: RowRef<'_>| -> Result<Vec<MySqlValue>> {
+        row.values()
+            .zip_eq(schema.fields())
+            .map(|(datum, field)| MySqlValue::from_datum(datum, field))
+            .try_collect()
     };
-
-    // Closure that builds a String containing WHERE conditions, i.e. 'v1=1 AND v2=2'.
-    // Perhaps better to replace this functionality with a new Sink trait method.
-    let conditions = |values: Vec<MySqlValue>| {
-        schema
-            .names()
+    let columns = schema
+        .names()
+        .iter()
+        .map(|name| format!("`{}`", name))
+        .join(", ");
+    let insert = |row: RowRef<'_>| -> Result<String> {
+        let stmt = format!(
+            "INSERT INTO `{}` ({}) VALUES ({})",
+            config.table,
+            columns,
+            join(values(row)?, ", ")
+        );
+        Ok(match config.format {
+            SinkFormat::Upsert => format!(
+                "{} ON DUPLICATE KEY UPDATE {};",
+                stmt,
+                schema
+                    .names()
+                    .iter()
+                    .map(|name| format!("`{0}` = VALUES(`{0}`)", name))
+                    .join(", ")
+            ),
+            _ => format!("{};", stmt),
+        })
+    };
+    // Rows are deleted by the primary key, with `<=>` that matches `NULL`s as well.
+    let delete = |row: RowRef<'_>| -> Result<String> {
+        let conditions: Vec<_> = pk_indices
             .iter()
-            .zip_eq(values.iter())
-            .map(|(c, v)| format!("{}={}", c, v))
-            .collect::<Vec<String>>()
+            .map(|&idx| {
+                let field = &schema.fields[idx];
+                MySqlValue::from_datum(row.value_at(idx), field)
+                    .map(|value| format!("`{}` <=> {}", field.name, value))
+            })
+            .try_collect()?;
+        Ok(format!(
+            "DELETE FROM `{}` WHERE {};",
+            config.table,
+            conditions.join(" AND ")
+        ))
     };
 
-    let mut iter = chunk.ops().iter().enumerate();
-    while let Some((idx, op)) = iter.next() {
-        // Get SQL statement
-        let stmt = match *op {
-            Insert => format!(
-                "INSERT INTO {} VALUES ({});",
-                &config.table,
-                join(values(idx)?, ",")
-            ),
-            Delete => format!(
-                "DELETE FROM {} WHERE ({});",
-                &config.table,
-                join(conditions(values(idx)?), " AND ")
-            ),
-            UpdateDelete => {
-                if let Some((idx2, UpdateInsert)) = iter.next() {
-                    format!(
-                        "UPDATE {} SET {} WHERE {};",
-                        &config.table,
-                        join(conditions(values(idx2)?), ","),
-                        join(conditions(values(idx)?), " AND ")
-                    )
-                } else {
-                    return Err(SinkError::MySql(
-                        "UpdateDelete should always be followed by an UpdateInsert!".into(),
-                    ));
+    let mut stmts = vec![];
+    let mut rows = chunk.rows();
+    while let Some((op, row)) = rows.next() {
+        match (config.format, op) {
+            (_, Insert) => stmts.push(insert(row)?),
+            (SinkFormat::AppendOnly, _) => {}
+            (_, Delete) => stmts.push(delete(row)?),
+            (_, UpdateDelete) => {
+                let new_row = match rows.next() {
+                    Some((UpdateInsert, new_row)) => new_row,
+                    _ => {
+                        return Err(SinkError::MySql(
+                            "UpdateDelete should always be followed by an UpdateInsert!".into(),
+                        ))
+                    }
+                };
+                let pk_changed = row
+                    .datum_refs_by_indices(pk_indices)
+                    .ne(new_row.datum_refs_by_indices(pk_indices));
+                if pk_changed {
+                    stmts.push(delete(row)?);
                 }
+                stmts.push(insert(new_row)?);
             }
-            _ => return Err(SinkError::MySql("Unsupported operation".into())),
-        };
+            (_, UpdateInsert) => {
+                return Err(SinkError::MySql(
+                    "UpdateInsert should always follow an UpdateDelete!".into(),
+                ))
+            }
+        }
+    }
+    Ok(stmts)
+}
+
+async fn write_to_mysql<'a>(
+    txn: &mut Transaction<'a>,
+    chunk: &StreamChunk,
+    schema: &Schema,
+    pk_indices: &[usize],
+    config: &MySqlConfig,
+) -> Result<()> {
+    for stmt in build_statements(chunk, schema, pk_indices, config)? {
         // TODO by doc, exec_drop will simply exec query and drop the result, we may check and retry
         // for jitter or other reasons
         txn.exec_drop(stmt, Params::Empty).await?;
     }
-
     Ok(())
 }
 
@@ -296,10 +418,11 @@ async fn write_to_mysql<'a>(
 mod test {
 
     use risingwave_common::array;
-    use risingwave_common::array::{I32Array, Op, Utf8Array};
+    use risingwave_common::array::{I32Array, Op, StructValue, Utf8Array};
     use risingwave_common::catalog::Field;
+    use risingwave_common::test_prelude::StreamChunkTestExt;
     use risingwave_common::types::chrono_wrapper::*;
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use rust_decimal::Decimal as RustDecimal;
 
     use super::*;
@@ -312,12 +435,19 @@ mod test {
         pub password: &'a str,
     }
 
+    fn to_sql(scalar: ScalarImpl, data_type: DataType) -> String {
+        MySqlValue::from_datum(
+            Some(scalar.as_scalar_ref_impl()),
+            &Field::unnamed(data_type),
+        )
+        .unwrap()
+        .to_string()
+    }
+
     #[test]
     fn test_date() {
         assert_eq!(
-            MySqlValue::try_from(Some(ScalarImpl::NaiveDate(NaiveDateWrapper::default())))
-                .unwrap()
-                .to_string(),
+            to_sql(NaiveDateWrapper::default().into(), DataType::Date),
             "'1970-01-01'"
         );
     }
@@ -325,9 +455,7 @@ mod test {
     #[test]
     fn test_time() {
         assert_eq!(
-            MySqlValue::try_from(Some(ScalarImpl::NaiveTime(NaiveTimeWrapper::default())))
-                .unwrap()
-                .to_string(),
+            to_sql(NaiveTimeWrapper::default().into(), DataType::Time),
             "'00:00:00'"
         );
     }
@@ -335,11 +463,7 @@ mod test {
     #[test]
     fn test_datetime() {
         assert_eq!(
-            MySqlValue::try_from(Some(ScalarImpl::NaiveDateTime(
-                NaiveDateTimeWrapper::default()
-            )))
-            .unwrap()
-            .to_string(),
+            to_sql(NaiveDateTimeWrapper::default().into(), DataType::Timestamp),
             "'1970-01-01 00:00:00'"
         );
     }
@@ -347,23 +471,79 @@ mod test {
     #[test]
     fn test_decimal() {
         assert_eq!(
-            MySqlValue::try_from(Some(ScalarImpl::Decimal(Decimal::Normalized(
-                RustDecimal::new(0, 0)
-            ))))
-            .unwrap()
-            .to_string(),
+            to_sql(
+                Decimal::Normalized(RustDecimal::new(0, 0)).into(),
+                DataType::Decimal
+            ),
             "'0'"
         );
         assert_eq!(
-            MySqlValue::try_from(Some(ScalarImpl::Decimal(Decimal::Normalized(
-                RustDecimal::new(124, 5)
-            ))))
-            .unwrap()
-            .to_string(),
+            to_sql(
+                Decimal::Normalized(RustDecimal::new(124, 5)).into(),
+                DataType::Decimal
+            ),
             "'0.00124'"
         );
     }
 
+    #[test]
+    fn test_nested() {
+        let data_type = DataType::new_struct(
+            vec![DataType::Int32, DataType::Varchar],
+            vec!["a".to_string(), "b".to_string()],
+        );
+        let value = StructValue::new(vec![Some(1.into()), Some("x".into())]);
+        assert_eq!(
+            to_sql(value.into(), data_type),
+            r#"'{\"a\":1,\"b\":\"x\"}'"#
+        );
+    }
+
+    #[test]
+    fn test_build_upsert_statements() {
+        let chunk = StreamChunk::from_pretty(
+            "  i T
+            +  1 a
+            -  2 b
+            U- 3 c
+            U+ 3 d
+            U- 4 e
+            U+ 5 e",
+        );
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int32, "k"),
+            Field::with_name(DataType::Varchar, "v"),
+        ]);
+        let stmts = build_statements(&chunk, &schema, &[0], &MySqlConfig::default()).unwrap();
+        let upsert = |k: i32, v: &str| {
+            format!(
+                "INSERT INTO `t` (`k`, `v`) VALUES ({}, '{}') ON DUPLICATE KEY UPDATE \
+                 `k` = VALUES(`k`), `v` = VALUES(`v`);",
+                k, v
+            )
+        };
+        assert_eq!(
+            stmts,
+            vec![
+                upsert(1, "a"),
+                "DELETE FROM `t` WHERE `k` <=> 2;".to_string(),
+                upsert(3, "d"),
+                "DELETE FROM `t` WHERE `k` <=> 4;".to_string(),
+                upsert(5, "e"),
+            ]
+        );
+
+        let config = MySqlConfig {
+            format: SinkFormat::AppendOnly,
+            ..Default::default()
+        };
+        let stmts = build_statements(&chunk, &schema, &[0], &config).unwrap();
+        assert_eq!(
+            stmts,
+            vec!["INSERT INTO `t` (`k`, `v`) VALUES (1, 'a');".to_string()]
+        );
+    }
+
     impl Default for MySqlConfig {
         fn default() -> Self {
             MySqlConfig {
@@ -372,6 +552,7 @@ mod test {
                 database: Some("test".into()),
                 user: Some("root".into()),
                 password: None,
+                format: SinkFormat::Upsert,
             }
         }
     }
@@ -387,7 +568,7 @@ mod test {
             type_name: "".into(),
         }]);
 
-        let mut sink = MySqlSink::new(cfg.clone(), schema).await?;
+        let mut sink = MySqlSink::new(cfg.clone(), schema, vec![0]).await?;
 
         let chunk = StreamChunk::new(
             vec![Op::Insert, Op::Insert],
@@ -436,7 +617,7 @@ mod test {
                 type_name: "".into(),
             },
         ]);
-        let mut sink = MySqlSink::new(cfg.clone(), schema.clone()).await?;
+        let mut sink = MySqlSink::new(cfg.clone(), schema.clone(), vec![0]).await?;
 
         let chunk = StreamChunk::new(
             vec![Op::Insert, Op::Insert, Op::Insert],
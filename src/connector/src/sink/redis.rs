@@ -12,39 +12,475 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter};
+
 use async_trait::async_trait;
-use risingwave_common::array::StreamChunk;
+use itertools::Itertools;
+use redis::aio::Connection;
+use redis::Client;
+use risingwave_common::array::{Op, RowRef, StreamChunk};
 use risingwave_common::catalog::Schema;
+use risingwave_common::types::to_text::ToText;
+use serde_json::Value;
+
+use crate::sink::kafka::record_to_json;
+use crate::sink::{Result, Sink, SinkError};
 
-use crate::sink::{Result, Sink};
+pub const REDIS_SINK: &str = "redis";
+
+const DEFAULT_KEY_DELIMITER: &str = ":";
+
+/// How a row is stored under its key in Redis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedisValueFormat {
+    /// The whole row is serialized as a JSON object and stored with `SET`.
+    Json,
+    /// Every non-null column is stored as a field of a Redis hash with `HSET`.
+    Hash,
+}
 
 #[derive(Clone, Debug)]
-pub struct RedisConfig;
+pub struct RedisConfig {
+    /// Connection url, e.g. `redis://127.0.0.1:6379/0`.
+    pub url: String,
+    /// Prepended to every key written by the sink.
+    pub key_prefix: String,
+    /// Separates the primary key columns in a key.
+    pub key_delimiter: String,
+    pub format: RedisValueFormat,
+}
+
+impl RedisConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let url = values
+            .get("redis.url")
+            .ok_or_else(|| SinkError::Config("redis.url must be set".to_string()))?;
+        let key_prefix = values.get("redis.key.prefix").cloned().unwrap_or_default();
+        let key_delimiter = values
+            .get("redis.key.delimiter")
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_KEY_DELIMITER.to_string());
+        let format = match values.get("format").map(|f| f.to_lowercase()).as_deref() {
+            None | Some("json") => RedisValueFormat::Json,
+            Some("hash") => RedisValueFormat::Hash,
+            Some(f) => {
+                return Err(SinkError::Config(format!(
+                    "format must be set to \"json\" or \"hash\", got \"{}\"",
+                    f
+                )))
+            }
+        };
 
-#[derive(Debug)]
-pub struct RedisSink;
+        Ok(RedisConfig {
+            url: url.to_string(),
+            key_prefix,
+            key_delimiter,
+            format,
+        })
+    }
+}
+
+/// The value of a key at the end of the current epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum RedisValue {
+    Json(String),
+    Hash(Vec<(String, String)>),
+}
+
+/// Writes the rows of a materialized view into Redis, one key per primary key.
+///
+/// Changes are buffered per epoch and only the latest state of each key is kept. On `commit`, the
+/// buffered changes are applied in a single `MULTI`/`EXEC` transaction, so readers never observe
+/// a partially applied epoch.
+pub struct RedisSink {
+    cfg: RedisConfig,
+    schema: Schema,
+    pk_indices: Vec<usize>,
+    conn: Connection,
+    /// `None` means the key is deleted in the current epoch.
+    pending: BTreeMap<String, Option<RedisValue>>,
+    in_transaction_epoch: Option<u64>,
+}
 
 impl RedisSink {
-    pub fn new(_cfg: RedisConfig, _schema: Schema) -> Result<Self> {
-        todo!()
+    pub async fn new(cfg: RedisConfig, schema: Schema, pk_indices: Vec<usize>) -> Result<Self> {
+        if pk_indices.is_empty() {
+            return Err(SinkError::Config(
+                "redis sink requires the upstream to have a primary key".to_string(),
+            ));
+        }
+        let conn = Client::open(cfg.url.as_str())?
+            .get_async_connection()
+            .await?;
+        Ok(Self {
+            cfg,
+            schema,
+            pk_indices,
+            conn,
+            pending: BTreeMap::new(),
+            in_transaction_epoch: None,
+        })
+    }
+
+    fn key(&self, row: RowRef<'_>) -> String {
+        let pk = row
+            .datum_refs_by_indices(&self.pk_indices)
+            .map(|datum| datum.to_text())
+            .join(&self.cfg.key_delimiter);
+        format!("{}{}", self.cfg.key_prefix, pk)
+    }
+
+    fn value(&self, row: RowRef<'_>) -> Result<RedisValue> {
+        Ok(match self.cfg.format {
+            RedisValueFormat::Json => RedisValue::Json(
+                Value::Object(record_to_json(row, &self.schema.fields)?).to_string(),
+            ),
+            RedisValueFormat::Hash => RedisValue::Hash(
+                self.schema
+                    .fields
+                    .iter()
+                    .zip_eq(row.values())
+                    .filter_map(|(field, datum)| {
+                        datum.map(|scalar| (field.name.clone(), scalar.to_text()))
+                    })
+                    .collect(),
+            ),
+        })
     }
 }
 
 #[async_trait]
 impl Sink for RedisSink {
-    async fn write_batch(&mut self, _chunk: StreamChunk) -> Result<()> {
-        todo!();
+    async fn write_batch(&mut self, chunk: StreamChunk) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            let key = self.key(row);
+            let value = match op {
+                Op::Insert | Op::UpdateInsert => Some(self.value(row)?),
+                Op::Delete | Op::UpdateDelete => None,
+            };
+            self.pending.insert(key, value);
+        }
+        Ok(())
     }
 
-    async fn begin_epoch(&mut self, _epoch: u64) -> Result<()> {
-        todo!()
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        debug_assert!(self.pending.is_empty());
+        self.in_transaction_epoch = Some(epoch);
+        Ok(())
     }
 
     async fn commit(&mut self) -> Result<()> {
-        todo!()
+        let epoch = self
+            .in_transaction_epoch
+            .take()
+            .ok_or_else(|| SinkError::Redis("commit without begin_epoch".to_string()))?;
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for (key, value) in std::mem::take(&mut self.pending) {
+            match value {
+                None => {
+                    pipe.del(key).ignore();
+                }
+                Some(RedisValue::Json(json)) => {
+                    pipe.set(key, json).ignore();
+                }
+                Some(RedisValue::Hash(fields)) => {
+                    // Overwrite the whole hash, otherwise fields that became NULL would survive.
+                    pipe.del(&key).ignore();
+                    if !fields.is_empty() {
+                        pipe.hset_multiple(key, &fields).ignore();
+                    }
+                }
+            }
+        }
+        pipe.query_async::<_, ()>(&mut self.conn).await?;
+        tracing::debug!("redis sink commit epoch {}", epoch);
+        Ok(())
     }
 
     async fn abort(&mut self) -> Result<()> {
-        todo!()
+        tracing::debug!("redis sink abort epoch {:?}", self.in_transaction_epoch);
+        self.pending.clear();
+        self.in_transaction_epoch = None;
+        Ok(())
+    }
+}
+
+impl Debug for RedisSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisSink")
+            .field("cfg", &self.cfg)
+            .field("pk_indices", &self.pk_indices)
+            .field("in_transaction_epoch", &self.in_transaction_epoch)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::{Arc, Mutex};
+
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::DataType;
+    use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum MockValue {
+        Str(String),
+        Hash(BTreeMap<String, String>),
+    }
+
+    type MockStore = Arc<Mutex<HashMap<String, MockValue>>>;
+
+    /// A minimal in-process Redis server which understands the commands issued by the sink.
+    async fn start_mock_redis() -> (String, MockStore) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let store = MockStore::default();
+        let store_clone = store.clone();
+        tokio::spawn(async move {
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                let store = store_clone.clone();
+                tokio::spawn(async move {
+                    let (read, mut write) = socket.into_split();
+                    let mut reader = BufReader::new(read);
+                    let mut queued: Option<Vec<Vec<String>>> = None;
+                    while let Some(cmd) = read_command(&mut reader).await {
+                        let name = cmd[0].to_uppercase();
+                        let reply = if name == "MULTI" {
+                            queued = Some(vec![]);
+                            "+OK\r\n".to_string()
+                        } else if name == "EXEC" && queued.is_some() {
+                            let cmds = queued.take().unwrap();
+                            let mut reply = format!("*{}\r\n", cmds.len());
+                            for cmd in cmds {
+                                reply.push_str(&apply(&store, cmd));
+                            }
+                            reply
+                        } else if let Some(queued) = queued.as_mut() {
+                            queued.push(cmd);
+                            "+QUEUED\r\n".to_string()
+                        } else {
+                            apply(&store, cmd)
+                        };
+                        write.write_all(reply.as_bytes()).await.unwrap();
+                    }
+                });
+            }
+        });
+        (format!("redis://{}", addr), store)
+    }
+
+    async fn read_command<R: AsyncBufRead + Unpin>(reader: &mut R) -> Option<Vec<String>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await.unwrap() == 0 {
+            return None;
+        }
+        let n: usize = line.trim_end().strip_prefix('*')?.parse().unwrap();
+        let mut args = Vec::with_capacity(n);
+        for _ in 0..n {
+            line.clear();
+            reader.read_line(&mut line).await.unwrap();
+            let len: usize = line.trim_end().strip_prefix('$')?.parse().unwrap();
+            let mut buf = vec![0; len + 2];
+            reader.read_exact(&mut buf).await.unwrap();
+            buf.truncate(len);
+            args.push(String::from_utf8(buf).unwrap());
+        }
+        Some(args)
+    }
+
+    fn apply(store: &MockStore, cmd: Vec<String>) -> String {
+        let mut store = store.lock().unwrap();
+        match cmd[0].to_uppercase().as_str() {
+            "SET" => {
+                store.insert(cmd[1].clone(), MockValue::Str(cmd[2].clone()));
+                "+OK\r\n".to_string()
+            }
+            "DEL" => {
+                let n = cmd[1..]
+                    .iter()
+                    .filter(|key| store.remove(key.as_str()).is_some())
+                    .count();
+                format!(":{}\r\n", n)
+            }
+            "HSET" => {
+                let entry = store
+                    .entry(cmd[1].clone())
+                    .or_insert_with(|| MockValue::Hash(BTreeMap::new()));
+                let MockValue::Hash(hash) = entry else {
+                    return "-WRONGTYPE\r\n".to_string();
+                };
+                let n = cmd[2..]
+                    .chunks(2)
+                    .filter(|kv| hash.insert(kv[0].clone(), kv[1].clone()).is_none())
+                    .count();
+                format!(":{}\r\n", n)
+            }
+            other => format!("-ERR unknown command '{}'\r\n", other),
+        }
+    }
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::with_name(DataType::Int64, "id"),
+            Field::with_name(DataType::Varchar, "name"),
+            Field::with_name(DataType::Int64, "v"),
+        ])
+    }
+
+    fn config(url: String, format: &str) -> RedisConfig {
+        RedisConfig::from_hashmap(maplit::hashmap! {
+            "connector".to_string() => "redis".to_string(),
+            "redis.url".to_string() => url,
+            "redis.key.prefix".to_string() => "t:".to_string(),
+            "format".to_string() => format.to_string(),
+        })
+        .unwrap()
+    }
+
+    fn get(store: &MockStore, key: &str) -> Option<MockValue> {
+        store.lock().unwrap().get(key).cloned()
+    }
+
+    #[test]
+    fn test_config() {
+        let cfg = RedisConfig::from_hashmap(maplit::hashmap! {
+            "redis.url".to_string() => "redis://127.0.0.1:6379".to_string(),
+        })
+        .unwrap();
+        assert_eq!(cfg.format, RedisValueFormat::Json);
+        assert_eq!(cfg.key_prefix, "");
+        assert_eq!(cfg.key_delimiter, ":");
+
+        assert!(RedisConfig::from_hashmap(HashMap::new()).is_err());
+        assert!(RedisConfig::from_hashmap(maplit::hashmap! {
+            "redis.url".to_string() => "redis://127.0.0.1:6379".to_string(),
+            "format".to_string() => "xml".to_string(),
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_format() {
+        let (url, store) = start_mock_redis().await;
+        let mut sink = RedisSink::new(config(url, "json"), schema(), vec![0])
+            .await
+            .unwrap();
+
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            " I T I
+            + 1 a 10
+            + 2 b 20",
+        ))
+        .await
+        .unwrap();
+        // Nothing is visible before the epoch is committed.
+        assert_eq!(get(&store, "t:1"), None);
+        sink.commit().await.unwrap();
+        assert_eq!(
+            get(&store, "t:1"),
+            Some(MockValue::Str(r#"{"id":1,"name":"a","v":10}"#.to_string()))
+        );
+
+        sink.begin_epoch(2).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            "  I T I
+            -  1 a 10
+            U- 2 b 20
+            U+ 2 b 21",
+        ))
+        .await
+        .unwrap();
+        sink.commit().await.unwrap();
+        assert_eq!(get(&store, "t:1"), None);
+        assert_eq!(
+            get(&store, "t:2"),
+            Some(MockValue::Str(r#"{"id":2,"name":"b","v":21}"#.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_format() {
+        let (url, store) = start_mock_redis().await;
+        let mut sink = RedisSink::new(config(url, "hash"), schema(), vec![0, 1])
+            .await
+            .unwrap();
+
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            " I T I
+            + 1 a 10",
+        ))
+        .await
+        .unwrap();
+        sink.commit().await.unwrap();
+        assert_eq!(
+            get(&store, "t:1:a"),
+            Some(MockValue::Hash(BTreeMap::from([
+                ("id".to_string(), "1".to_string()),
+                ("name".to_string(), "a".to_string()),
+                ("v".to_string(), "10".to_string()),
+            ])))
+        );
+
+        // A column updated to NULL must be removed from the hash.
+        sink.begin_epoch(2).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            "  I T I
+            U- 1 a 10
+            U+ 1 a .",
+        ))
+        .await
+        .unwrap();
+        sink.commit().await.unwrap();
+        assert_eq!(
+            get(&store, "t:1:a"),
+            Some(MockValue::Hash(BTreeMap::from([
+                ("id".to_string(), "1".to_string()),
+                ("name".to_string(), "a".to_string()),
+            ])))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abort() {
+        let (url, store) = start_mock_redis().await;
+        let mut sink = RedisSink::new(config(url, "json"), schema(), vec![0])
+            .await
+            .unwrap();
+
+        sink.begin_epoch(1).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            " I T I
+            + 1 a 10",
+        ))
+        .await
+        .unwrap();
+        sink.abort().await.unwrap();
+
+        sink.begin_epoch(2).await.unwrap();
+        sink.write_batch(StreamChunk::from_pretty(
+            " I T I
+            + 2 b 20",
+        ))
+        .await
+        .unwrap();
+        sink.commit().await.unwrap();
+
+        assert_eq!(get(&store, "t:1"), None);
+        assert!(get(&store, "t:2").is_some());
     }
 }
@@ -318,6 +318,7 @@ fn parse_datum(datum: DatumRef<'_>) -> Value {
         Some(ScalarRefImpl::NaiveDate(v)) => Value::from(v.to_string()),
         Some(ScalarRefImpl::NaiveTime(v)) => Value::from(v.to_string()),
         Some(ScalarRefImpl::Interval(v)) => Value::from(v.to_string()),
+        Some(ScalarRefImpl::Jsonb(v)) => v.value().clone(),
         Some(ScalarRefImpl::Struct(v)) => Value::from(
             v.fields_ref()
                 .iter()
@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, LazyLock};
 
 use anyhow::{anyhow, Result};
+use arrow_array::RecordBatch;
 use async_trait::async_trait;
 use bytes::Bytes;
 use enum_as_inner::EnumAsInner;
@@ -29,6 +30,10 @@ use serde::{Deserialize, Serialize};
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc;
 
+use crate::source::cdc::postgres::{
+    PostgresCdcProperties, PostgresCdcSplit, PostgresCdcSplitEnumerator, PostgresCdcSplitReader,
+    POSTGRES_CDC_CONNECTOR,
+};
 use crate::source::cdc::{
     CdcProperties, CdcSplit, CdcSplitReader, DebeziumSplitEnumerator, CDC_CONNECTOR,
 };
@@ -36,7 +41,12 @@ use crate::source::datagen::{
     DatagenProperties, DatagenSplit, DatagenSplitEnumerator, DatagenSplitReader, DATAGEN_CONNECTOR,
 };
 use crate::source::dummy_connector::DummySplitReader;
-use crate::source::filesystem::s3::{S3Properties, S3_CONNECTOR};
+use crate::source::filesystem::posix_fs::{
+    PosixFsProperties, PosixFsSplit, PosixFsSplitEnumerator, PosixFsSplitReader, POSIX_FS_CONNECTOR,
+};
+use crate::source::filesystem::s3::{
+    S3Properties, S3Split, S3SplitEnumerator, S3SplitReader, S3_CONNECTOR,
+};
 use crate::source::google_pubsub::{
     PubsubProperties, PubsubSplit, PubsubSplitEnumerator, PubsubSplitReader,
     GOOGLE_PUBSUB_CONNECTOR,
@@ -100,6 +110,9 @@ pub enum SplitImpl {
     Datagen(DatagenSplit),
     Cdc(CdcSplit),
     GooglePubsub(PubsubSplit),
+    PosixFs(PosixFsSplit),
+    PostgresCdc(PostgresCdcSplit),
+    S3(S3Split),
 }
 
 pub enum SplitReaderImpl {
@@ -111,6 +124,9 @@ pub enum SplitReaderImpl {
     Datagen(Box<DatagenSplitReader>),
     Cdc(Box<CdcSplitReader>),
     GooglePubsub(Box<PubsubSplitReader>),
+    PosixFs(Box<PosixFsSplitReader>),
+    PostgresCdc(Box<PostgresCdcSplitReader>),
+    S3(Box<S3SplitReader>),
 }
 
 pub enum SplitEnumeratorImpl {
@@ -121,6 +137,9 @@ pub enum SplitEnumeratorImpl {
     Datagen(DatagenSplitEnumerator),
     Cdc(DebeziumSplitEnumerator),
     GooglePubsub(PubsubSplitEnumerator),
+    PosixFs(PosixFsSplitEnumerator),
+    PostgresCdc(PostgresCdcSplitEnumerator),
+    S3(S3SplitEnumerator),
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -134,6 +153,8 @@ pub enum ConnectorProperties {
     Cdc(Box<CdcProperties>),
     Dummy(Box<()>),
     GooglePubsub(Box<PubsubProperties>),
+    PosixFs(Box<PosixFsProperties>),
+    PostgresCdc(Box<PostgresCdcProperties>),
 }
 
 impl_connector_properties! {
@@ -144,7 +165,9 @@ impl_connector_properties! {
     { Datagen, DATAGEN_CONNECTOR },
     { S3, S3_CONNECTOR },
     { Cdc, CDC_CONNECTOR },
-    { GooglePubsub, GOOGLE_PUBSUB_CONNECTOR}
+    { GooglePubsub, GOOGLE_PUBSUB_CONNECTOR },
+    { PosixFs, POSIX_FS_CONNECTOR },
+    { PostgresCdc, POSTGRES_CDC_CONNECTOR }
 }
 
 impl_split_enumerator! {
@@ -154,7 +177,10 @@ impl_split_enumerator! {
     { Nexmark, NexmarkSplitEnumerator },
     { Datagen, DatagenSplitEnumerator },
     { Cdc, DebeziumSplitEnumerator },
-    { GooglePubsub, PubsubSplitEnumerator}
+    { GooglePubsub, PubsubSplitEnumerator },
+    { PosixFs, PosixFsSplitEnumerator },
+    { PostgresCdc, PostgresCdcSplitEnumerator },
+    { S3, S3SplitEnumerator }
 }
 
 impl_split! {
@@ -164,7 +190,10 @@ impl_split! {
     { Nexmark, NEXMARK_CONNECTOR, NexmarkSplit },
     { Datagen, DATAGEN_CONNECTOR, DatagenSplit },
     { Cdc, CDC_CONNECTOR, CdcSplit },
-    { GooglePubsub, GOOGLE_PUBSUB_CONNECTOR, PubsubSplit }
+    { GooglePubsub, GOOGLE_PUBSUB_CONNECTOR, PubsubSplit },
+    { PosixFs, POSIX_FS_CONNECTOR, PosixFsSplit },
+    { PostgresCdc, POSTGRES_CDC_CONNECTOR, PostgresCdcSplit },
+    { S3, S3_CONNECTOR, S3Split }
 }
 
 impl_split_reader! {
@@ -175,6 +204,9 @@ impl_split_reader! {
     { Datagen, DatagenSplitReader },
     { Cdc, CdcSplitReader},
     { GooglePubsub, PubsubSplitReader },
+    { PosixFs, PosixFsSplitReader },
+    { PostgresCdc, PostgresCdcSplitReader },
+    { S3, S3SplitReader },
     { Dummy, DummySplitReader }
 }
 
@@ -191,11 +223,16 @@ pub type SplitId = Arc<str>;
 
 /// The message pumped from the external source service.
 /// The third-party message structs will eventually be transformed into this struct.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SourceMessage {
+    /// The key of the message, e.g. the message key of Kafka, which is the primary key of the
+    /// message for an upsert source.
+    pub key: Option<Bytes>,
     pub payload: Option<Bytes>,
     pub offset: String,
     pub split_id: SplitId,
+    /// The rows of the message if it's a batch read from a Parquet file, which has no payload.
+    pub batch: Option<RecordBatch>,
 }
 
 /// The metadata of a split.
@@ -301,4 +338,52 @@ mod tests {
             panic!("extract nexmark config failed");
         }
     }
+
+    #[test]
+    fn test_extract_postgres_cdc_properties() {
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "connector" => "postgres-cdc",
+            "database.name" => "mydb",
+            "database.hostname" => "127.0.0.1",
+            "database.user" => "postgres",
+            "table.name" => "products",
+        ));
+
+        let props = ConnectorProperties::extract(props).unwrap();
+
+        if let ConnectorProperties::PostgresCdc(props) = props {
+            assert_eq!(props.database_port, "5432");
+            assert_eq!(props.schema_name, "public");
+            assert_eq!(props.table_name, "products");
+            assert_eq!(props.slot_name, None);
+        } else {
+            panic!("extract postgres cdc config failed");
+        }
+    }
+
+    #[test]
+    fn test_extract_kafka_commit_offsets() {
+        let props = |commit_offsets: &str, group_id: Option<&str>| {
+            let mut props: HashMap<String, String> = convert_args!(hashmap!(
+                "connector" => "kafka",
+                "properties.bootstrap.server" => "127.0.0.1:9092",
+                "topic" => "test",
+                "commit.offsets" => commit_offsets,
+            ));
+            if let Some(group_id) = group_id {
+                props.insert("properties.group.id".to_string(), group_id.to_string());
+            }
+            match ConnectorProperties::extract(props).unwrap() {
+                ConnectorProperties::Kafka(props) => props,
+                _ => panic!("extract kafka config failed"),
+            }
+        };
+
+        assert_eq!(props("false", None).commit_group_id().unwrap(), None);
+        assert_eq!(
+            props("true", Some("rw")).commit_group_id().unwrap(),
+            Some("rw")
+        );
+        assert!(props("true", None).commit_group_id().is_err());
+    }
 }
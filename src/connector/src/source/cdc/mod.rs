@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod enumerator;
+pub mod postgres;
 pub mod source;
 pub mod split;
 
@@ -0,0 +1,47 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::source::cdc::postgres::protocol::Lsn;
+use crate::source::cdc::postgres::{PostgresCdcProperties, PostgresCdcSplit};
+
+/// Reports the LSN of the last checkpoint to the reader of a Postgres CDC source, which flushes it
+/// to the server so that the write-ahead log before it can be recycled. The LSN must not be
+/// reported before the checkpoint is committed, or the changes after it would be lost on recovery.
+pub struct PostgresCdcOffsetCommitter {
+    checkpointed_lsn: Arc<AtomicU64>,
+}
+
+impl PostgresCdcOffsetCommitter {
+    pub fn new(props: &PostgresCdcProperties) -> Self {
+        Self {
+            checkpointed_lsn: props.checkpointed_lsn.clone(),
+        }
+    }
+
+    /// Sets the LSN of the splits, which are in the state of the last checkpoint.
+    pub fn commit(&self, splits: &[PostgresCdcSplit]) -> Result<()> {
+        for split in splits {
+            if let Some(lsn) = &split.start_lsn {
+                let lsn: Lsn = lsn.parse()?;
+                self.checkpointed_lsn.fetch_max(lsn.0, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+
+use crate::source::cdc::postgres::{PostgresCdcProperties, PostgresCdcSplit};
+use crate::source::SplitEnumerator;
+
+#[derive(Debug)]
+pub struct PostgresCdcSplitEnumerator {
+    /// The source_id in the catalog
+    source_id: u32,
+}
+
+#[async_trait]
+impl SplitEnumerator for PostgresCdcSplitEnumerator {
+    type Properties = PostgresCdcProperties;
+    type Split = PostgresCdcSplit;
+
+    async fn new(props: PostgresCdcProperties) -> anyhow::Result<Self> {
+        Ok(Self {
+            source_id: props.source_id,
+        })
+    }
+
+    async fn list_splits(&mut self) -> anyhow::Result<Vec<PostgresCdcSplit>> {
+        // A replication slot can only be read by one reader.
+        Ok(vec![PostgresCdcSplit::new(self.source_id, None)])
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A native Postgres CDC source, which reads the changes of a table by logical replication with
+//! the `pgoutput` plugin, without the connector service.
+
+pub mod committer;
+pub mod enumerator;
+mod pgoutput;
+mod protocol;
+pub mod source;
+pub mod split;
+
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+
+pub use committer::*;
+pub use enumerator::*;
+use serde::Deserialize;
+pub use source::*;
+pub use split::*;
+
+pub const POSTGRES_CDC_CONNECTOR: &str = "postgres-cdc";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PostgresCdcProperties {
+    /// The id of the source in the catalog, which is set by meta rather than the `WITH` options.
+    #[serde(default)]
+    pub source_id: u32,
+    #[serde(rename = "database.hostname")]
+    pub database_host: String,
+    #[serde(rename = "database.port", default = "default_port")]
+    pub database_port: String,
+    #[serde(rename = "database.user")]
+    pub database_user: String,
+    #[serde(rename = "database.password", default)]
+    pub database_password: String,
+    #[serde(rename = "database.name")]
+    pub database_name: String,
+    #[serde(rename = "schema.name", default = "default_schema")]
+    pub schema_name: String,
+    #[serde(rename = "table.name")]
+    pub table_name: String,
+    /// The replication slot to read the changes from, which is created with the initial snapshot.
+    /// It defaults to `rw_cdc_<source_id>`.
+    #[serde(rename = "slot.name", default)]
+    pub slot_name: Option<String>,
+    /// The publication of the table, which is created if not exists. It defaults to
+    /// `rw_cdc_<source_id>`.
+    #[serde(rename = "publication.name", default)]
+    pub publication_name: Option<String>,
+    /// The LSN of the last checkpoint, which is set by [`PostgresCdcOffsetCommitter`] and flushed
+    /// to the server by the reader. It's shared by the clones of the properties, or 0 if
+    /// unknown.
+    #[serde(skip)]
+    pub checkpointed_lsn: Arc<AtomicU64>,
+}
+
+fn default_port() -> String {
+    "5432".to_string()
+}
+
+fn default_schema() -> String {
+    "public".to_string()
+}
+
+impl PostgresCdcProperties {
+    fn slot_name(&self, source_id: u32) -> String {
+        self.slot_name
+            .clone()
+            .unwrap_or_else(|| format!("rw_cdc_{}", source_id))
+    }
+
+    fn publication_name(&self, source_id: u32) -> String {
+        self.publication_name
+            .clone()
+            .unwrap_or_else(|| format!("rw_cdc_{}", source_id))
+    }
+}
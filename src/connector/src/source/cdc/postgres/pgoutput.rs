@@ -0,0 +1,265 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The messages of the `pgoutput` plugin in the logical replication protocol version 1, see
+//! <https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html>.
+
+use anyhow::{anyhow, bail, Result};
+use bytes::{Buf, Bytes};
+
+/// A column of a relation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelationColumn {
+    pub name: String,
+    pub type_oid: u32,
+}
+
+/// A value of a tuple.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TupleValue {
+    Null,
+    /// An unchanged value of TOAST, which is not sent by the server.
+    Unchanged,
+    Text(Bytes),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PgOutputMessage {
+    Begin,
+    Commit {
+        end_lsn: u64,
+    },
+    Relation {
+        oid: u32,
+        namespace: String,
+        name: String,
+        columns: Vec<RelationColumn>,
+    },
+    Insert {
+        relation: u32,
+        new: Vec<TupleValue>,
+    },
+    Update {
+        relation: u32,
+        /// The old tuple, which is only sent if the replica identity is `FULL` or the key is
+        /// changed.
+        old: Option<Vec<TupleValue>>,
+        new: Vec<TupleValue>,
+    },
+    Delete {
+        relation: u32,
+        old: Vec<TupleValue>,
+    },
+    Truncate,
+    /// The messages not used, e.g. `Origin` and `Type`.
+    Other,
+}
+
+impl PgOutputMessage {
+    pub fn parse(data: Bytes) -> Result<Self> {
+        let mut reader = Reader(data);
+        let msg = match reader.u8()? {
+            b'B' => Self::Begin,
+            b'C' => {
+                // The flags and the LSN of the commit.
+                reader.skip(9)?;
+                Self::Commit {
+                    end_lsn: reader.u64()?,
+                }
+            }
+            b'R' => {
+                let oid = reader.u32()?;
+                let namespace = reader.cstring()?;
+                let name = reader.cstring()?;
+                // The replica identity.
+                reader.skip(1)?;
+                let num = reader.u16()?;
+                let mut columns = Vec::with_capacity(num as usize);
+                for _ in 0..num {
+                    // The flags.
+                    reader.skip(1)?;
+                    let name = reader.cstring()?;
+                    let type_oid = reader.u32()?;
+                    // The type modifier.
+                    reader.skip(4)?;
+                    columns.push(RelationColumn { name, type_oid });
+                }
+                Self::Relation {
+                    oid,
+                    namespace,
+                    name,
+                    columns,
+                }
+            }
+            b'I' => {
+                let relation = reader.u32()?;
+                reader.expect(b'N')?;
+                Self::Insert {
+                    relation,
+                    new: reader.tuple()?,
+                }
+            }
+            b'U' => {
+                let relation = reader.u32()?;
+                let old = match reader.u8()? {
+                    b'K' | b'O' => {
+                        let old = reader.tuple()?;
+                        reader.expect(b'N')?;
+                        Some(old)
+                    }
+                    b'N' => None,
+                    tag => bail!("unexpected tuple of update: {}", tag as char),
+                };
+                Self::Update {
+                    relation,
+                    old,
+                    new: reader.tuple()?,
+                }
+            }
+            b'D' => {
+                let relation = reader.u32()?;
+                match reader.u8()? {
+                    b'K' | b'O' => {}
+                    tag => bail!("unexpected tuple of delete: {}", tag as char),
+                }
+                Self::Delete {
+                    relation,
+                    old: reader.tuple()?,
+                }
+            }
+            b'T' => Self::Truncate,
+            _ => Self::Other,
+        };
+        Ok(msg)
+    }
+}
+
+/// Reads the fields of a message, which fails rather than panics if the message is truncated.
+struct Reader(Bytes);
+
+impl Reader {
+    fn ensure(&self, len: usize) -> Result<()> {
+        if self.0.remaining() < len {
+            bail!("pgoutput message is truncated");
+        }
+        Ok(())
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.ensure(len)?;
+        self.0.advance(len);
+        Ok(())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        self.ensure(1)?;
+        Ok(self.0.get_u8())
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        self.ensure(2)?;
+        Ok(self.0.get_u16())
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.ensure(4)?;
+        Ok(self.0.get_u32())
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        self.ensure(8)?;
+        Ok(self.0.get_u64())
+    }
+
+    fn expect(&mut self, tag: u8) -> Result<()> {
+        match self.u8()? {
+            t if t == tag => Ok(()),
+            t => bail!("expect tuple {}, got {}", tag as char, t as char),
+        }
+    }
+
+    fn cstring(&mut self) -> Result<String> {
+        let end = self
+            .0
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| anyhow!("unterminated string"))?;
+        let s = String::from_utf8(self.0.split_to(end).to_vec())?;
+        self.0.advance(1);
+        Ok(s)
+    }
+
+    fn tuple(&mut self) -> Result<Vec<TupleValue>> {
+        let num = self.u16()?;
+        let mut values = Vec::with_capacity(num as usize);
+        for _ in 0..num {
+            let value = match self.u8()? {
+                b'n' => TupleValue::Null,
+                b'u' => TupleValue::Unchanged,
+                b't' => {
+                    let len = self.u32()? as usize;
+                    self.ensure(len)?;
+                    TupleValue::Text(self.0.split_to(len))
+                }
+                kind => bail!("unexpected kind of tuple value: {}", kind as char),
+            };
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_update() {
+        let mut buf = BytesMut::new();
+        buf.put_u8(b'U');
+        buf.put_u32(16384);
+        buf.put_u8(b'O');
+        buf.put_u16(2);
+        buf.put_u8(b't');
+        buf.put_u32(1);
+        buf.put_slice(b"1");
+        buf.put_u8(b'n');
+        buf.put_u8(b'N');
+        buf.put_u16(2);
+        buf.put_u8(b't');
+        buf.put_u32(1);
+        buf.put_slice(b"1");
+        buf.put_u8(b't');
+        buf.put_u32(3);
+        buf.put_slice(b"abc");
+
+        assert_eq!(
+            PgOutputMessage::parse(buf.clone().freeze()).unwrap(),
+            PgOutputMessage::Update {
+                relation: 16384,
+                old: Some(vec![TupleValue::Text(Bytes::from("1")), TupleValue::Null]),
+                new: vec![
+                    TupleValue::Text(Bytes::from("1")),
+                    TupleValue::Text(Bytes::from("abc"))
+                ],
+            }
+        );
+
+        // A truncated message fails rather than panics.
+        buf.truncate(buf.len() - 1);
+        assert!(PgOutputMessage::parse(buf.freeze()).is_err());
+    }
+}
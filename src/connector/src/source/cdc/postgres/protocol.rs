@@ -0,0 +1,336 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A minimal client of the Postgres protocol, which only supports the simple queries and the
+//! streaming of logical replication.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Result};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use postgres_protocol::authentication::md5_hash;
+use postgres_protocol::authentication::sasl::{ChannelBinding, ScramSha256, SCRAM_SHA_256};
+use postgres_protocol::message::frontend;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::source::cdc::postgres::PostgresCdcProperties;
+
+/// The seconds from the Unix epoch to the Postgres epoch, i.e. 2000-01-01.
+const POSTGRES_EPOCH_SECS: u64 = 946_684_800;
+
+/// A position in the write-ahead log, displayed as `XXX/XXX` like Postgres does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Lsn(pub u64);
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xFFFF_FFFF)
+    }
+}
+
+impl FromStr for Lsn {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (high, low) = s
+            .split_once('/')
+            .ok_or_else(|| anyhow!("invalid lsn: {}", s))?;
+        let high = u64::from_str_radix(high, 16).map_err(|_| anyhow!("invalid lsn: {}", s))?;
+        let low = u64::from_str_radix(low, 16).map_err(|_| anyhow!("invalid lsn: {}", s))?;
+        Ok(Self((high << 32) | low))
+    }
+}
+
+/// A column of the rows returned by a query.
+#[derive(Clone, Debug)]
+pub struct QueryColumn {
+    pub name: String,
+    pub type_oid: u32,
+}
+
+/// The messages of a simple query, in which the values are in text.
+#[derive(Debug)]
+pub enum QueryMessage {
+    RowDescription(Vec<QueryColumn>),
+    DataRow(Vec<Option<Bytes>>),
+    /// The query is completed and the connection is ready for the next one.
+    Done,
+}
+
+/// A connection in the replication mode, in which both the simple queries and the replication
+/// commands can be run.
+pub struct PgConnection {
+    stream: TcpStream,
+    read_buf: BytesMut,
+    write_buf: BytesMut,
+}
+
+impl PgConnection {
+    pub async fn connect(props: &PostgresCdcProperties) -> Result<Self> {
+        let stream =
+            TcpStream::connect(format!("{}:{}", props.database_host, props.database_port)).await?;
+        let mut conn = Self {
+            stream,
+            read_buf: BytesMut::new(),
+            write_buf: BytesMut::new(),
+        };
+        frontend::startup_message(
+            [
+                ("user", props.database_user.as_str()),
+                ("database", props.database_name.as_str()),
+                ("replication", "database"),
+            ],
+            &mut conn.write_buf,
+        )?;
+        conn.flush().await?;
+        conn.authenticate(&props.database_user, &props.database_password)
+            .await?;
+        Ok(conn)
+    }
+
+    async fn authenticate(&mut self, user: &str, password: &str) -> Result<()> {
+        let mut scram = None;
+        loop {
+            let (tag, mut body) = self.recv().await?;
+            match tag {
+                b'R' => match body.get_i32() {
+                    // Ok
+                    0 => {}
+                    // Cleartext password
+                    3 => frontend::password_message(password.as_bytes(), &mut self.write_buf)?,
+                    // MD5 password
+                    5 => {
+                        let salt = body.get_u32().to_be_bytes();
+                        let hash = md5_hash(user.as_bytes(), password.as_bytes(), salt);
+                        frontend::password_message(hash.as_bytes(), &mut self.write_buf)?;
+                    }
+                    // SASL
+                    10 => {
+                        let mechanisms = body
+                            .split(|b| *b == 0)
+                            .map(String::from_utf8_lossy)
+                            .collect::<Vec<_>>();
+                        if !mechanisms.iter().any(|m| m == SCRAM_SHA_256) {
+                            bail!("unsupported SASL mechanisms: {:?}", mechanisms);
+                        }
+                        let s =
+                            ScramSha256::new(password.as_bytes(), ChannelBinding::unsupported());
+                        frontend::sasl_initial_response(
+                            SCRAM_SHA_256,
+                            s.message(),
+                            &mut self.write_buf,
+                        )?;
+                        scram = Some(s);
+                    }
+                    // SASL continue
+                    11 => {
+                        let s = scram
+                            .as_mut()
+                            .ok_or_else(|| anyhow!("unexpected SASL continue"))?;
+                        s.update(&body)?;
+                        frontend::sasl_response(s.message(), &mut self.write_buf)?;
+                    }
+                    // SASL final
+                    12 => {
+                        scram
+                            .take()
+                            .ok_or_else(|| anyhow!("unexpected SASL final"))?
+                            .finish(&body)?;
+                    }
+                    code => bail!("unsupported authentication method: {}", code),
+                },
+                // Backend key data
+                b'K' => {}
+                // Ready for query
+                b'Z' => return Ok(()),
+                tag => bail!("unexpected message during authentication: {}", tag as char),
+            }
+            self.flush().await?;
+        }
+    }
+
+    /// Runs a query and collects all the rows returned.
+    pub async fn simple_query(&mut self, query: &str) -> Result<Vec<Vec<Option<String>>>> {
+        self.send_query(query).await?;
+        let mut rows = vec![];
+        loop {
+            match self.recv_query_message().await? {
+                QueryMessage::RowDescription(_) => {}
+                QueryMessage::DataRow(row) => rows.push(
+                    row.into_iter()
+                        .map(|value| value.map(|v| String::from_utf8_lossy(&v).into_owned()))
+                        .collect(),
+                ),
+                QueryMessage::Done => return Ok(rows),
+            }
+        }
+    }
+
+    /// Sends a query, whose results are received by [`Self::recv_query_message`] until
+    /// [`QueryMessage::Done`].
+    pub async fn send_query(&mut self, query: &str) -> Result<()> {
+        frontend::query(query, &mut self.write_buf)?;
+        self.flush().await
+    }
+
+    pub async fn recv_query_message(&mut self) -> Result<QueryMessage> {
+        loop {
+            let (tag, mut body) = self.recv().await?;
+            match tag {
+                b'T' => {
+                    let num = body.get_i16();
+                    let mut columns = Vec::with_capacity(num as usize);
+                    for _ in 0..num {
+                        let name = get_cstring(&mut body)?;
+                        // The table oid and the column number.
+                        body.advance(6);
+                        let type_oid = body.get_u32();
+                        // The type size, the type modifier and the format code.
+                        body.advance(8);
+                        columns.push(QueryColumn { name, type_oid });
+                    }
+                    return Ok(QueryMessage::RowDescription(columns));
+                }
+                b'D' => {
+                    let num = body.get_i16();
+                    let mut row = Vec::with_capacity(num as usize);
+                    for _ in 0..num {
+                        let len = body.get_i32();
+                        row.push((len >= 0).then(|| body.split_to(len as usize)));
+                    }
+                    return Ok(QueryMessage::DataRow(row));
+                }
+                b'Z' => return Ok(QueryMessage::Done),
+                // Command complete, empty query response
+                b'C' | b'I' => {}
+                tag => bail!("unexpected message of a query: {}", tag as char),
+            }
+        }
+    }
+
+    /// Runs a `START_REPLICATION` command, after which the changes are received by
+    /// [`Self::recv_copy_data`].
+    pub async fn start_replication(&mut self, command: &str) -> Result<()> {
+        self.send_query(command).await?;
+        match self.recv().await? {
+            // Copy both response
+            (b'W', _) => Ok(()),
+            (tag, _) => bail!("unexpected message of replication: {}", tag as char),
+        }
+    }
+
+    /// Receives the next message of replication, or `None` if it times out.
+    pub async fn recv_copy_data(&mut self, timeout: Duration) -> Result<Option<Bytes>> {
+        match tokio::time::timeout(timeout, self.recv()).await {
+            Ok(msg) => match msg? {
+                (b'd', body) => Ok(Some(body)),
+                (b'c', _) => bail!("replication stopped by the server"),
+                (tag, _) => bail!("unexpected message of replication: {}", tag as char),
+            },
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Sends a standby status update to report the progress of replication, by which the server
+    /// recycles the write-ahead log before `flushed`.
+    pub async fn send_standby_status(&mut self, written: Lsn, flushed: Lsn) -> Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let now = now.as_micros() as i64 - (POSTGRES_EPOCH_SECS * 1_000_000) as i64;
+        let mut data = BytesMut::with_capacity(34);
+        data.put_u8(b'r');
+        data.put_u64(written.0);
+        data.put_u64(flushed.0);
+        data.put_u64(flushed.0);
+        data.put_i64(now);
+        data.put_u8(0);
+        self.write_buf.put_u8(b'd');
+        self.write_buf.put_i32(4 + data.len() as i32);
+        self.write_buf.put_slice(&data);
+        self.flush().await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.stream.write_all(&self.write_buf).await?;
+        self.write_buf.clear();
+        Ok(())
+    }
+
+    /// Receives a message as its tag and body. The notices and the parameter statuses are
+    /// skipped, and an error response is returned as an error.
+    async fn recv(&mut self) -> Result<(u8, Bytes)> {
+        loop {
+            if self.read_buf.len() >= 5 {
+                let len = i32::from_be_bytes(self.read_buf[1..5].try_into().unwrap()) as usize;
+                if self.read_buf.len() > len {
+                    let mut frame = self.read_buf.split_to(len + 1);
+                    let tag = frame.get_u8();
+                    frame.advance(4);
+                    let body = frame.freeze();
+                    match tag {
+                        b'E' => return Err(error_response(body)),
+                        b'N' | b'S' => continue,
+                        tag => return Ok((tag, body)),
+                    }
+                }
+            }
+            if self.stream.read_buf(&mut self.read_buf).await? == 0 {
+                bail!("connection closed by the server");
+            }
+        }
+    }
+}
+
+fn get_cstring(buf: &mut Bytes) -> Result<String> {
+    let end = buf
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or_else(|| anyhow!("unterminated string"))?;
+    let s = String::from_utf8(buf.split_to(end).to_vec())?;
+    buf.advance(1);
+    Ok(s)
+}
+
+fn error_response(mut body: Bytes) -> anyhow::Error {
+    let mut code = String::new();
+    let mut message = String::new();
+    while body.has_remaining() {
+        let field = body.get_u8();
+        if field == 0 {
+            break;
+        }
+        let value = match get_cstring(&mut body) {
+            Ok(value) => value,
+            Err(e) => return e,
+        };
+        match field {
+            b'C' => code = value,
+            b'M' => message = value,
+            _ => {}
+        }
+    }
+    anyhow!("postgres error {}: {}", code, message)
+}
+
+/// Quotes an identifier for the queries.
+pub fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Quotes a literal for the queries.
+pub fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\'', "''"))
+}
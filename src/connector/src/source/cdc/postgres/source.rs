@@ -0,0 +1,735 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use bytes::{Buf, Bytes};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+use crate::source::cdc::postgres::pgoutput::{PgOutputMessage, RelationColumn, TupleValue};
+use crate::source::cdc::postgres::protocol::{
+    quote_ident, quote_literal, Lsn, PgConnection, QueryMessage,
+};
+use crate::source::cdc::postgres::{PostgresCdcProperties, PostgresCdcSplit};
+use crate::source::{
+    BoxSourceStream, Column, ConnectorState, SourceMessage, SplitImpl, SplitMetaData, SplitReader,
+    MAX_CHUNK_SIZE,
+};
+
+/// The interval to report the progress of replication to the server if nothing is received.
+const STATUS_INTERVAL: Duration = Duration::from_secs(10);
+
+const BOOL_OID: u32 = 16;
+const INT8_OID: u32 = 20;
+const INT2_OID: u32 = 21;
+const INT4_OID: u32 = 23;
+const OID_OID: u32 = 26;
+const JSON_OID: u32 = 114;
+const FLOAT4_OID: u32 = 700;
+const FLOAT8_OID: u32 = 701;
+const NUMERIC_OID: u32 = 1700;
+const JSONB_OID: u32 = 3802;
+
+/// Reads the changes of a table by the logical replication of Postgres, without the connector
+/// node. The changes are yielded in the Debezium JSON format, so the source must be created with
+/// `ROW FORMAT DEBEZIUM_JSON`.
+///
+/// If the split has no LSN to start from, a replication slot is created with its snapshot, in
+/// which the table is read as the initial data. The changes are then streamed from the slot, each
+/// transaction in a batch with the LSN of its end as the offset, from which the replication is
+/// started after recovery.
+///
+/// The snapshot is read in the order of the primary key, with the key of each row in its offset.
+/// If it's recovered in the middle, the rest of the rows are read after the last key without the
+/// snapshot, which is gone with the transaction. Some of them may be changed after the consistent
+/// point of the slot, whose changes are streamed again later and upserted by the primary key of the
+/// table, which is required by `ROW FORMAT DEBEZIUM_JSON`.
+pub struct PostgresCdcSplitReader {
+    props: PostgresCdcProperties,
+    split: PostgresCdcSplit,
+}
+
+#[async_trait]
+impl SplitReader for PostgresCdcSplitReader {
+    type Properties = PostgresCdcProperties;
+
+    async fn new(
+        props: PostgresCdcProperties,
+        state: ConnectorState,
+        _columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        if let Some(splits) = state {
+            let split = splits
+                .into_iter()
+                .exactly_one()
+                .map_err(|e| anyhow!("failed to create postgres cdc split reader: {e}"))?;
+
+            if let SplitImpl::PostgresCdc(split) = split {
+                return Ok(Self { props, split });
+            }
+        }
+        Err(anyhow!(
+            "failed to create postgres cdc split reader: invalid state"
+        ))
+    }
+
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
+    }
+}
+
+impl PostgresCdcSplitReader {
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn into_stream(self) {
+        let props = &self.props;
+        let split_id = self.split.id();
+        let slot = props.slot_name(self.split.source_id);
+        let publication = props.publication_name(self.split.source_id);
+        let table = format!(
+            "{}.{}",
+            quote_ident(&props.schema_name),
+            quote_ident(&props.table_name)
+        );
+
+        let mut conn = PgConnection::connect(props).await?;
+        if conn
+            .simple_query(&format!(
+                "SELECT 1 FROM pg_publication WHERE pubname = {}",
+                quote_literal(&publication)
+            ))
+            .await?
+            .is_empty()
+        {
+            conn.simple_query(&format!(
+                "CREATE PUBLICATION {} FOR TABLE {}",
+                quote_ident(&publication),
+                table
+            ))
+            .await?;
+        }
+
+        // The key of the last row of the snapshot read before recovery, if the snapshot is to be
+        // read.
+        let (start_lsn, snapshot_key) = match &self.split.start_lsn {
+            Some(lsn) => (
+                lsn.parse::<Lsn>()?,
+                self.split.snapshot_key.clone().map(Some),
+            ),
+            None => {
+                // The slot is left by a snapshot failed before any of its rows is checkpointed,
+                // whose changes are included in the new snapshot.
+                if !conn
+                    .simple_query(&format!(
+                        "SELECT 1 FROM pg_replication_slots WHERE slot_name = {}",
+                        quote_literal(&slot)
+                    ))
+                    .await?
+                    .is_empty()
+                {
+                    conn.simple_query(&format!("DROP_REPLICATION_SLOT {}", quote_ident(&slot)))
+                        .await?;
+                }
+
+                conn.simple_query("BEGIN ISOLATION LEVEL REPEATABLE READ")
+                    .await?;
+                let rows = conn
+                    .simple_query(&format!(
+                        "CREATE_REPLICATION_SLOT {} LOGICAL pgoutput USE_SNAPSHOT",
+                        quote_ident(&slot)
+                    ))
+                    .await?;
+                let consistent_point: Lsn = rows
+                    .first()
+                    .and_then(|row| row.get(1).cloned().flatten())
+                    .ok_or_else(|| anyhow!("no consistent point of replication slot {}", slot))?
+                    .parse()?;
+                tracing::info!(
+                    "replication slot {} is created at {}, reading the snapshot of {}",
+                    slot,
+                    consistent_point,
+                    table
+                );
+                (consistent_point, Some(None))
+            }
+        };
+
+        if let Some(last_key) = snapshot_key {
+            let key_columns = primary_key(&mut conn, &table).await?;
+            let order_by = key_columns.iter().map(|c| quote_ident(c)).join(", ");
+            let query = match &last_key {
+                Some(key) => {
+                    tracing::info!("resuming the snapshot of {} after key {:?}", table, key);
+                    format!(
+                        "SELECT * FROM {} WHERE ({}) > ({}) ORDER BY {}",
+                        table,
+                        order_by,
+                        key.iter().map(|value| quote_literal(value)).join(", "),
+                        order_by
+                    )
+                }
+                None => format!("SELECT * FROM {} ORDER BY {}", table, order_by),
+            };
+            conn.send_query(&query).await?;
+            let mut columns = vec![];
+            let mut key_indices = vec![];
+            let mut msgs = vec![];
+            loop {
+                match conn.recv_query_message().await? {
+                    QueryMessage::RowDescription(desc) => {
+                        key_indices = key_columns
+                            .iter()
+                            .map(|key| {
+                                desc.iter().position(|c| &c.name == key).ok_or_else(|| {
+                                    anyhow!("key column {} is not in the snapshot", key)
+                                })
+                            })
+                            .collect::<Result<_>>()?;
+                        columns = desc
+                            .into_iter()
+                            .map(|c| RelationColumn {
+                                name: c.name,
+                                type_oid: c.type_oid,
+                            })
+                            .collect();
+                    }
+                    QueryMessage::DataRow(row) => {
+                        if msgs.len() == MAX_CHUNK_SIZE {
+                            yield std::mem::take(&mut msgs);
+                        }
+                        let key = key_indices
+                            .iter()
+                            .map(|&i| {
+                                row[i]
+                                    .as_ref()
+                                    .map(|value| String::from_utf8_lossy(value).into_owned())
+                                    .ok_or_else(|| anyhow!("null primary key in {}", table))
+                            })
+                            .collect::<Result<_>>()?;
+                        let row = row
+                            .into_iter()
+                            .map(|value| value.map_or(TupleValue::Null, TupleValue::Text))
+                            .collect_vec();
+                        let after = tuple_to_json(&columns, &row, None)?;
+                        msgs.push(SourceMessage {
+                            key: None,
+                            payload: Some(debezium_payload("r", Value::Null, after)),
+                            offset: PostgresCdcSplit::snapshot_offset(start_lsn.to_string(), key),
+                            split_id: split_id.clone(),
+                            batch: None,
+                        });
+                    }
+                    QueryMessage::Done => break,
+                }
+            }
+            if last_key.is_none() {
+                conn.simple_query("COMMIT").await?;
+            }
+            if !msgs.is_empty() {
+                yield msgs;
+            }
+        }
+
+        conn.start_replication(&format!(
+            "START_REPLICATION SLOT {} LOGICAL {} (\"proto_version\" '1', \"publication_names\" {})",
+            quote_ident(&slot),
+            start_lsn,
+            quote_literal(&publication)
+        ))
+        .await?;
+
+        let mut relations: HashMap<u32, Vec<RelationColumn>> = HashMap::new();
+        let mut payloads = vec![];
+        let mut written = start_lsn;
+        // Nothing before the LSN started from is needed again, which is either checkpointed or the
+        // consistent point of a new slot.
+        let flushed = || {
+            Lsn(props
+                .checkpointed_lsn
+                .load(Ordering::Relaxed)
+                .max(start_lsn.0))
+        };
+        loop {
+            let mut data = match conn.recv_copy_data(STATUS_INTERVAL).await? {
+                Some(data) => data,
+                None => {
+                    conn.send_standby_status(written, flushed()).await?;
+                    continue;
+                }
+            };
+            if data.is_empty() {
+                bail!("empty message of replication");
+            }
+            match data.get_u8() {
+                // XLogData
+                b'w' => {
+                    if data.len() < 24 {
+                        bail!("XLogData is truncated");
+                    }
+                    // The start and the end of the WAL, and the time of sending.
+                    data.advance(24);
+                    match PgOutputMessage::parse(data)? {
+                        PgOutputMessage::Begin => payloads.clear(),
+                        PgOutputMessage::Relation { oid, columns, .. } => {
+                            relations.insert(oid, columns);
+                        }
+                        PgOutputMessage::Insert { relation, new } => {
+                            let columns = get_relation(&relations, relation)?;
+                            let after = tuple_to_json(columns, &new, None)?;
+                            payloads.push(debezium_payload("c", Value::Null, after));
+                        }
+                        PgOutputMessage::Update { relation, old, new } => {
+                            let columns = get_relation(&relations, relation)?;
+                            let after = tuple_to_json(columns, &new, old.as_deref())?;
+                            // The old tuple is not sent if the key is unchanged, in which case the
+                            // row is updated by the new one with the same key.
+                            let before = match &old {
+                                Some(old) => tuple_to_json(columns, old, None)?,
+                                None => after.clone(),
+                            };
+                            payloads.push(debezium_payload("u", before, after));
+                        }
+                        PgOutputMessage::Delete { relation, old } => {
+                            let columns = get_relation(&relations, relation)?;
+                            let before = tuple_to_json(columns, &old, None)?;
+                            payloads.push(debezium_payload("d", before, Value::Null));
+                        }
+                        PgOutputMessage::Truncate => {
+                            tracing::warn!("truncate of {} is not replicated", table);
+                        }
+                        PgOutputMessage::Commit { end_lsn } => {
+                            written = Lsn(end_lsn);
+                            if !payloads.is_empty() {
+                                yield payloads
+                                    .drain(..)
+                                    .map(|payload| SourceMessage {
+                                        key: None,
+                                        payload: Some(payload),
+                                        offset: written.to_string(),
+                                        split_id: split_id.clone(),
+                                        batch: None,
+                                    })
+                                    .collect_vec();
+                            }
+                        }
+                        PgOutputMessage::Other => {}
+                    }
+                }
+                // Primary keepalive
+                b'k' => {
+                    if data.len() < 17 {
+                        bail!("keepalive is truncated");
+                    }
+                    // The end of the WAL and the time of sending, followed by whether a reply is
+                    // requested.
+                    if data[16] == 1 {
+                        conn.send_standby_status(written, flushed()).await?;
+                    }
+                }
+                tag => bail!("unexpected message of replication: {}", tag as char),
+            }
+        }
+    }
+}
+
+fn get_relation(
+    relations: &HashMap<u32, Vec<RelationColumn>>,
+    relation: u32,
+) -> Result<&[RelationColumn]> {
+    relations
+        .get(&relation)
+        .map(Vec::as_slice)
+        .ok_or_else(|| anyhow!("unknown relation {}", relation))
+}
+
+/// Returns the columns of the primary key of a table in order.
+async fn primary_key(conn: &mut PgConnection, table: &str) -> Result<Vec<String>> {
+    let rows = conn
+        .simple_query(&format!(
+            "SELECT a.attname FROM pg_index i \
+            JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey) \
+            WHERE i.indrelid = {}::regclass AND i.indisprimary \
+            ORDER BY array_position(i.indkey::int2[], a.attnum)",
+            quote_literal(table)
+        ))
+        .await?;
+    if rows.is_empty() {
+        bail!(
+            "{} has no primary key, which is required to read its snapshot",
+            table
+        );
+    }
+    rows.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .next()
+                .flatten()
+                .ok_or_else(|| anyhow!("no column name of the primary key of {}", table))
+        })
+        .collect()
+}
+
+/// Converts a tuple to a JSON object keyed by the column names. An unchanged value of TOAST is
+/// taken from the old tuple, which is only sent with all the values if the replica identity of the
+/// table is full.
+fn tuple_to_json(
+    columns: &[RelationColumn],
+    tuple: &[TupleValue],
+    old: Option<&[TupleValue]>,
+) -> Result<Value> {
+    if columns.len() != tuple.len() {
+        bail!(
+            "expect {} columns in the tuple, got {}",
+            columns.len(),
+            tuple.len()
+        );
+    }
+    let mut object = serde_json::Map::with_capacity(columns.len());
+    for (i, (column, value)) in columns.iter().zip_eq(tuple).enumerate() {
+        let value = match value {
+            TupleValue::Unchanged => match old.and_then(|old| old.get(i)) {
+                Some(TupleValue::Unchanged) | None => bail!(
+                    "the unchanged value of column {} is unknown, please set the replica \
+                    identity of the table to full",
+                    column.name
+                ),
+                Some(value) => value,
+            },
+            value => value,
+        };
+        let value = match value {
+            TupleValue::Text(text) => text_to_json(column.type_oid, &String::from_utf8_lossy(text)),
+            _ => Value::Null,
+        };
+        object.insert(column.name.clone(), value);
+    }
+    Ok(Value::Object(object))
+}
+
+/// Converts a value in the text format of Postgres to JSON by its type, so that it can be parsed
+/// by the Debezium JSON parser.
+fn text_to_json(type_oid: u32, text: &str) -> Value {
+    match type_oid {
+        BOOL_OID => Value::Bool(text == "t"),
+        INT2_OID | INT4_OID | INT8_OID | OID_OID => text
+            .parse::<i64>()
+            .map_or_else(|_| Value::from(text), Value::from),
+        // NaN and infinity are kept as strings, which are not numbers of JSON.
+        FLOAT4_OID | FLOAT8_OID => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map_or_else(|| Value::from(text), Value::Number),
+        // A numeric is kept as a string, which would lose its precision as a float.
+        NUMERIC_OID => Value::from(text),
+        JSON_OID | JSONB_OID => serde_json::from_str(text).unwrap_or_else(|_| Value::from(text)),
+        _ => Value::from(text),
+    }
+}
+
+fn debezium_payload(op: &str, before: Value, after: Value) -> Bytes {
+    Bytes::from(
+        json!({
+            "payload": {
+                "op": op,
+                "before": before,
+                "after": after,
+            }
+        })
+        .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+    use futures::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    fn put_message(buf: &mut BytesMut, tag: u8, body: &[u8]) {
+        buf.put_u8(tag);
+        buf.put_i32(4 + body.len() as i32);
+        buf.put_slice(body);
+    }
+
+    fn put_rows(buf: &mut BytesMut, columns: &[(&str, u32)], rows: &[Vec<Option<&str>>]) {
+        let mut body = BytesMut::new();
+        body.put_i16(columns.len() as i16);
+        for (name, type_oid) in columns {
+            body.put_slice(name.as_bytes());
+            body.put_u8(0);
+            body.put_u32(0);
+            body.put_i16(0);
+            body.put_u32(*type_oid);
+            body.put_i16(-1);
+            body.put_i32(-1);
+            body.put_i16(0);
+        }
+        put_message(buf, b'T', &body);
+        for row in rows {
+            let mut body = BytesMut::new();
+            body.put_i16(row.len() as i16);
+            for value in row {
+                match value {
+                    Some(value) => {
+                        body.put_i32(value.len() as i32);
+                        body.put_slice(value.as_bytes());
+                    }
+                    None => body.put_i32(-1),
+                }
+            }
+            put_message(buf, b'D', &body);
+        }
+    }
+
+    fn put_xlog_data(buf: &mut BytesMut, msg: &[u8]) {
+        let mut body = BytesMut::new();
+        body.put_u8(b'w');
+        body.put_slice(&[0; 24]);
+        body.put_slice(msg);
+        put_message(buf, b'd', &body);
+    }
+
+    /// Reads a query sent by the client.
+    async fn read_query(stream: &mut TcpStream) -> String {
+        assert_eq!(stream.read_u8().await.unwrap(), b'Q');
+        let len = stream.read_i32().await.unwrap() as usize;
+        let mut body = vec![0; len - 4];
+        stream.read_exact(&mut body).await.unwrap();
+        body.pop();
+        String::from_utf8(body).unwrap()
+    }
+
+    /// Serves a connection of a source, which reads a snapshot of one row by `snapshot_query` and a
+    /// transaction inserting another. The slot is only created for a new source.
+    async fn serve(listener: TcpListener, snapshot_query: &'static str, snapshot_row: [&str; 2]) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let len = stream.read_i32().await.unwrap() as usize;
+        let mut startup = vec![0; len - 4];
+        stream.read_exact(&mut startup).await.unwrap();
+
+        let mut buf = BytesMut::new();
+        put_message(&mut buf, b'R', &0i32.to_be_bytes());
+        put_message(&mut buf, b'Z', b"I");
+        stream.write_all(&buf).await.unwrap();
+
+        loop {
+            let query = read_query(&mut stream).await;
+            let mut buf = BytesMut::new();
+            if query.starts_with("SELECT 1 FROM pg_publication") {
+                put_rows(&mut buf, &[("?column?", INT4_OID)], &[vec![Some("1")]]);
+            } else if query.starts_with("CREATE_REPLICATION_SLOT") {
+                assert!(!snapshot_query.contains("WHERE"));
+                put_rows(
+                    &mut buf,
+                    &[
+                        ("slot_name", 25),
+                        ("consistent_point", 25),
+                        ("snapshot_name", 25),
+                        ("output_plugin", 25),
+                    ],
+                    &[vec![
+                        Some("rw_cdc_1"),
+                        Some("0/16B3748"),
+                        None,
+                        Some("pgoutput"),
+                    ]],
+                );
+            } else if query.starts_with("SELECT a.attname") {
+                put_rows(&mut buf, &[("attname", 25)], &[vec![Some("id")]]);
+            } else if query.starts_with("SELECT * FROM") {
+                assert_eq!(query, snapshot_query);
+                put_rows(
+                    &mut buf,
+                    &[("id", INT4_OID), ("name", 25)],
+                    &[snapshot_row.map(Some).to_vec()],
+                );
+            } else if query.starts_with("START_REPLICATION") {
+                assert_eq!(
+                    query,
+                    "START_REPLICATION SLOT \"rw_cdc_1\" LOGICAL 0/16B3748 \
+                    (\"proto_version\" '1', \"publication_names\" 'rw_cdc_1')"
+                );
+                put_message(&mut buf, b'W', &[0, 0, 0]);
+                put_xlog_data(&mut buf, &[b'B'; 21]);
+                let mut relation = BytesMut::new();
+                relation.put_u8(b'R');
+                relation.put_u32(16384);
+                relation.put_slice(b"public\0t\0d");
+                relation.put_u16(2);
+                for (name, type_oid) in [("id", INT4_OID), ("name", 25)] {
+                    relation.put_u8(1);
+                    relation.put_slice(name.as_bytes());
+                    relation.put_u8(0);
+                    relation.put_u32(type_oid);
+                    relation.put_i32(-1);
+                }
+                put_xlog_data(&mut buf, &relation);
+                let mut insert = BytesMut::new();
+                insert.put_u8(b'I');
+                insert.put_u32(16384);
+                insert.put_u8(b'N');
+                insert.put_u16(2);
+                insert.put_u8(b't');
+                insert.put_u32(1);
+                insert.put_slice(b"2");
+                insert.put_u8(b'n');
+                put_xlog_data(&mut buf, &insert);
+                let mut commit = BytesMut::new();
+                commit.put_u8(b'C');
+                commit.put_u8(0);
+                commit.put_u64(0x16B3800);
+                commit.put_u64(0x16B3830);
+                commit.put_i64(0);
+                put_xlog_data(&mut buf, &commit);
+                stream.write_all(&buf).await.unwrap();
+                // Keep the connection until the client is dropped.
+                let _ = stream.read_u8().await;
+                return;
+            }
+            put_message(&mut buf, b'C', b"SELECT 1\0");
+            put_message(&mut buf, b'Z', b"I");
+            stream.write_all(&buf).await.unwrap();
+        }
+    }
+
+    /// Reads the first two batches of a source from the server, which are the snapshot and the
+    /// changes.
+    async fn read(
+        split: PostgresCdcSplit,
+        snapshot_query: &'static str,
+        snapshot_row: [&'static str; 2],
+    ) -> (Vec<SourceMessage>, Vec<SourceMessage>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve(listener, snapshot_query, snapshot_row));
+
+        let props: PostgresCdcProperties = serde_json::from_value(json!({
+            "database.hostname": "127.0.0.1",
+            "database.port": port.to_string(),
+            "database.user": "postgres",
+            "database.name": "mydb",
+            "table.name": "t",
+        }))
+        .unwrap();
+        let reader =
+            PostgresCdcSplitReader::new(props, Some(vec![SplitImpl::PostgresCdc(split)]), None)
+                .await
+                .unwrap();
+        let mut stream = reader.into_stream();
+        let snapshot = stream.next().await.unwrap().unwrap();
+        let changes = stream.next().await.unwrap().unwrap();
+        drop(stream);
+        server.await.unwrap();
+        (snapshot, changes)
+    }
+
+    fn payload(msg: &SourceMessage) -> Value {
+        serde_json::from_slice(msg.payload.as_ref().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_and_replication() {
+        let (snapshot, changes) = read(
+            PostgresCdcSplit::new(1, None),
+            "SELECT * FROM \"public\".\"t\" ORDER BY \"id\"",
+            ["1", "a"],
+        )
+        .await;
+
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(
+            snapshot[0].offset,
+            PostgresCdcSplit::snapshot_offset("0/16B3748".to_string(), vec!["1".to_string()])
+        );
+        assert_eq!(
+            payload(&snapshot[0]),
+            json!({"payload": {"op": "r", "before": null, "after": {"id": 1, "name": "a"}}})
+        );
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].offset, "0/16B3830");
+        assert_eq!(
+            payload(&changes[0]),
+            json!({"payload": {"op": "c", "before": null, "after": {"id": 2, "name": null}}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_snapshot() {
+        // The snapshot is recovered after the first row, and resumed after its key without
+        // creating the slot again.
+        let split = PostgresCdcSplit::new(1, None)
+            .copy_with_offset(PostgresCdcSplit::snapshot_offset(
+                "0/16B3748".to_string(),
+                vec!["1".to_string()],
+            ))
+            .unwrap();
+        let (snapshot, changes) = read(
+            split,
+            "SELECT * FROM \"public\".\"t\" WHERE (\"id\") > ('1') ORDER BY \"id\"",
+            ["2", "b"],
+        )
+        .await;
+
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(
+            snapshot[0].offset,
+            PostgresCdcSplit::snapshot_offset("0/16B3748".to_string(), vec!["2".to_string()])
+        );
+        assert_eq!(
+            payload(&snapshot[0]),
+            json!({"payload": {"op": "r", "before": null, "after": {"id": 2, "name": "b"}}})
+        );
+        assert_eq!(changes[0].offset, "0/16B3830");
+    }
+
+    #[test]
+    fn test_tuple_to_json() {
+        let columns = vec![
+            RelationColumn {
+                name: "id".to_string(),
+                type_oid: INT4_OID,
+            },
+            RelationColumn {
+                name: "price".to_string(),
+                type_oid: NUMERIC_OID,
+            },
+        ];
+        let text = |s: &str| TupleValue::Text(Bytes::copy_from_slice(s.as_bytes()));
+
+        // A numeric keeps all of its digits.
+        let old = vec![text("1"), text("12345678901234567890.123456789")];
+        assert_eq!(
+            tuple_to_json(&columns, &old, None).unwrap(),
+            json!({"id": 1, "price": "12345678901234567890.123456789"})
+        );
+
+        // An unchanged value is taken from the old tuple, and must not be guessed without it.
+        let new = vec![text("2"), TupleValue::Unchanged];
+        assert_eq!(
+            tuple_to_json(&columns, &new, Some(&old)).unwrap(),
+            json!({"id": 2, "price": "12345678901234567890.123456789"})
+        );
+        assert!(tuple_to_json(&columns, &new, None).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::source::{SplitId, SplitMetaData};
+
+/// The only split of a Postgres CDC source. Its offset is the LSN after the last transaction read,
+/// or a [`SnapshotOffset`] while reading the initial snapshot. It's `None` before the replication
+/// slot is created.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Hash)]
+pub struct PostgresCdcSplit {
+    pub source_id: u32,
+    pub start_lsn: Option<String>,
+    /// The primary key of the last row read of the initial snapshot, which is resumed after it.
+    /// It's `None` if the snapshot is done or not started.
+    #[serde(default)]
+    pub snapshot_key: Option<Vec<String>>,
+}
+
+/// The offset of a row of the initial snapshot, which is the consistent point of the replication
+/// slot and the primary key of the row, since the snapshot is read in the order of the key.
+#[derive(Serialize, Deserialize)]
+struct SnapshotOffset {
+    lsn: String,
+    key: Vec<String>,
+}
+
+impl SplitMetaData for PostgresCdcSplit {
+    fn id(&self) -> SplitId {
+        format!("{}", self.source_id).into()
+    }
+
+    fn encode_to_bytes(&self) -> Bytes {
+        Bytes::from(serde_json::to_string(self).unwrap())
+    }
+
+    fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    }
+}
+
+impl PostgresCdcSplit {
+    pub fn new(source_id: u32, start_lsn: Option<String>) -> Self {
+        Self {
+            source_id,
+            start_lsn,
+            snapshot_key: None,
+        }
+    }
+
+    /// Returns the offset of a row of the initial snapshot with the primary key `key`.
+    pub fn snapshot_offset(lsn: String, key: Vec<String>) -> String {
+        serde_json::to_string(&SnapshotOffset { lsn, key }).unwrap()
+    }
+
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        let split = match serde_json::from_str::<SnapshotOffset>(&start_offset) {
+            Ok(SnapshotOffset { lsn, key }) => Self {
+                source_id: self.source_id,
+                start_lsn: Some(lsn),
+                snapshot_key: Some(key),
+            },
+            Err(_) => Self::new(self.source_id, Some(start_offset)),
+        };
+        Ok(split)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_with_offset() {
+        let split = PostgresCdcSplit::new(1, None);
+        let offset = PostgresCdcSplit::snapshot_offset("0/16B3748".to_string(), vec!["1".into()]);
+        let split = split.copy_with_offset(offset).unwrap();
+        assert_eq!(split.start_lsn.as_deref(), Some("0/16B3748"));
+        assert_eq!(split.snapshot_key, Some(vec!["1".to_string()]));
+
+        // The snapshot is done once a change is read.
+        let split = split.copy_with_offset("0/16B3830".to_string()).unwrap();
+        assert_eq!(split.start_lsn.as_deref(), Some("0/16B3830"));
+        assert_eq!(split.snapshot_key, None);
+    }
+}
@@ -20,9 +20,11 @@ use crate::source::base::SourceMessage;
 impl From<CdcMessage> for SourceMessage {
     fn from(message: CdcMessage) -> Self {
         SourceMessage {
+            key: None,
             payload: Some(Bytes::from(message.payload)),
             offset: message.offset,
             split_id: message.partition.into(),
+            batch: None,
         }
     }
 }
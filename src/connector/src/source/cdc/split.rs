@@ -51,7 +51,11 @@ impl CdcSplit {
         }
     }
 
-    pub fn copy_with_offset(&self, start_offset: String) -> Self {
-        Self::new(self.source_id, self.partition.clone(), start_offset)
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        Ok(Self::new(
+            self.source_id,
+            self.partition.clone(),
+            start_offset,
+        ))
     }
 }
@@ -68,9 +68,11 @@ impl DatagenEventGenerator {
                         .collect(),
                 );
                 msgs.push(SourceMessage {
+                    key: None,
                     payload: Some(Bytes::from(value.to_string())),
                     offset: self.offset.to_string(),
                     split_id: self.split_id.clone(),
+                    batch: None,
                 });
                 self.offset += 1;
             }
@@ -50,11 +50,12 @@ impl DatagenSplit {
         }
     }
 
-    pub fn copy_with_offset(&self, start_offset: String) -> Self {
-        Self::new(
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        let start_offset = start_offset.parse().map_err(|e| anyhow!(e))?;
+        Ok(Self::new(
             self.split_index,
             self.split_num,
-            Some(start_offset.as_str().parse::<u64>().unwrap()),
-        )
+            Some(start_offset),
+        ))
     }
 }
@@ -93,6 +93,16 @@ pub trait Directory: Send + Sync {
     fn entry_discover(&self) -> EntryDiscover;
 }
 
+/// Removes the trailing `\n` or `\r\n` of a line.
+pub(crate) fn trim_newline(line: &mut Vec<u8>) {
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -12,4 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 mod file_common;
+mod parquet_file;
+pub mod posix_fs;
 pub mod s3;
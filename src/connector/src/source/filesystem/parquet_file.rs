@@ -0,0 +1,296 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use arrow_array::RecordBatch;
+use bytes::{Buf, Bytes};
+use futures_async_stream::try_stream;
+use parquet::arrow::arrow_reader::{
+    ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder, RowSelection, RowSelector,
+};
+use parquet::arrow::ProjectionMask;
+use parquet::errors::ParquetError;
+use parquet::file::footer::{decode_footer, parse_metadata};
+use parquet::file::metadata::ParquetMetaData;
+use parquet::file::reader::{ChunkReader, Length};
+
+/// The size of the footer at the end of a Parquet file, which is the length of the metadata right
+/// before it followed by the magic number.
+pub const FOOTER_SIZE: usize = 8;
+
+/// Returns the number of row groups of a Parquet file, or `None` if it's not complete yet.
+pub async fn num_row_groups(path: PathBuf) -> Result<Option<usize>> {
+    tokio::task::spawn_blocking(move || {
+        let file = File::open(&path)?;
+        match parse_metadata(&file) {
+            Ok(metadata) => Ok(Some(metadata.num_row_groups())),
+            Err(e) => {
+                tracing::warn!(
+                    "failed to read the metadata of parquet file {}: {}",
+                    path.display(),
+                    e
+                );
+                Ok(None)
+            }
+        }
+    })
+    .await?
+}
+
+/// Returns the length of the metadata of a Parquet file from its footer.
+pub fn metadata_len(footer: &[u8]) -> Result<usize> {
+    Ok(decode_footer(footer.try_into()?)?)
+}
+
+/// A Parquet file of which only some byte ranges are fetched, e.g. an object of S3. It can be read
+/// as long as the ranges cover the metadata at the end of the file and the row groups to read.
+#[derive(Debug)]
+pub struct PartialFile {
+    len: u64,
+    ranges: Vec<(u64, Bytes)>,
+}
+
+impl PartialFile {
+    pub fn new(len: u64) -> Self {
+        Self {
+            len,
+            ranges: vec![],
+        }
+    }
+
+    /// Adds the bytes fetched from `offset`.
+    pub fn add_range(&mut self, offset: u64, bytes: Bytes) {
+        self.ranges.push((offset, bytes));
+    }
+
+    pub fn metadata(&self) -> Result<ParquetMetaData> {
+        Ok(parse_metadata(self)?)
+    }
+}
+
+impl Length for PartialFile {
+    fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+impl ChunkReader for PartialFile {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64, length: usize) -> parquet::errors::Result<Self::T> {
+        let end = start + length as u64;
+        self.ranges
+            .iter()
+            .find(|(offset, bytes)| *offset <= start && end <= offset + bytes.len() as u64)
+            .map(|(offset, bytes)| {
+                let begin = (start - offset) as usize;
+                bytes.slice(begin..begin + length).reader()
+            })
+            .ok_or_else(|| {
+                ParquetError::General(format!("bytes {}..{} are not fetched", start, end))
+            })
+    }
+}
+
+/// Returns the byte range of a row group as its start and end offsets in the file.
+pub fn row_group_range(metadata: &ParquetMetaData, row_group: usize) -> (u64, u64) {
+    metadata
+        .row_group(row_group)
+        .columns()
+        .iter()
+        .map(|column| {
+            let (start, len) = column.byte_range();
+            (start, start + len)
+        })
+        .reduce(|(start, end), (s, e)| (start.min(s), end.max(e)))
+        .unwrap_or_default()
+}
+
+/// Reads a row group of a Parquet file from its `offset`-th row, yielding the batches of at most
+/// `batch_size` rows along with the number of rows read so far. Only the columns with the names in
+/// `columns` are read, or all of them if it's `None`.
+#[try_stream(boxed, ok = (RecordBatch, u64), error = anyhow::Error)]
+pub async fn read_row_group<R: ChunkReader + 'static>(
+    file: R,
+    row_group: usize,
+    mut offset: u64,
+    columns: Option<Vec<String>>,
+    batch_size: usize,
+) {
+    let mut reader = tokio::task::spawn_blocking(move || {
+        open_row_group(file, row_group, offset, columns.as_deref(), batch_size)
+    })
+    .await??;
+    loop {
+        let (returned_reader, batch) = tokio::task::spawn_blocking(move || {
+            let batch = reader.next();
+            (reader, batch)
+        })
+        .await?;
+        reader = returned_reader;
+        match batch {
+            Some(batch) => {
+                let batch = batch?;
+                offset += batch.num_rows() as u64;
+                yield (batch, offset);
+            }
+            None => break,
+        }
+    }
+}
+
+fn open_row_group<R: ChunkReader + 'static>(
+    file: R,
+    row_group: usize,
+    offset: u64,
+    columns: Option<&[String]>,
+    batch_size: usize,
+) -> Result<ParquetRecordBatchReader> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+    let num_rows = builder.metadata().row_group(row_group).num_rows() as usize;
+    let projection = match columns {
+        Some(columns) => {
+            let indices = builder
+                .parquet_schema()
+                .root_schema()
+                .get_fields()
+                .iter()
+                .enumerate()
+                .filter(|(_, field)| {
+                    columns
+                        .iter()
+                        .any(|name| name.eq_ignore_ascii_case(field.name()))
+                })
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+            ProjectionMask::roots(builder.parquet_schema(), indices)
+        }
+        None => ProjectionMask::all(),
+    };
+    let offset = (offset as usize).min(num_rows);
+    let selection = RowSelection::from(vec![
+        RowSelector::skip(offset),
+        RowSelector::select(num_rows - offset),
+    ]);
+    Ok(builder
+        .with_row_groups(vec![row_group])
+        .with_projection(projection)
+        .with_row_selection(selection)
+        .with_batch_size(batch_size)
+        .build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{Array, ArrayRef, Int32Array, StringArray};
+    use futures::TryStreamExt;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::WriterProperties;
+
+    use super::*;
+
+    /// Writes the rows `(1, a), (2, b), (3, c)` in row groups of 2 rows.
+    fn write_parquet(file: impl std::io::Write) {
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a", "b", "c"])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(2)
+            .build();
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+    }
+
+    fn names(batch: &RecordBatch) -> Vec<&str> {
+        let names = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        names.iter().map(Option::unwrap).collect()
+    }
+
+    #[tokio::test]
+    async fn test_read_row_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.parquet");
+        write_parquet(File::create(&path).unwrap());
+        assert_eq!(num_row_groups(path.clone()).await.unwrap(), Some(2));
+
+        // The first row group is read from its second row, with only the projected column.
+        let batches: Vec<_> = read_row_group(
+            File::open(&path).unwrap(),
+            0,
+            1,
+            Some(vec!["NAME".to_string()]),
+            1024,
+        )
+        .try_collect()
+        .await
+        .unwrap();
+        assert_eq!(batches.len(), 1);
+        let (batch, offset) = &batches[0];
+        assert_eq!(*offset, 2);
+        assert_eq!(batch.num_columns(), 1);
+        assert_eq!(names(batch), vec!["b"]);
+
+        // A file being written is not a complete Parquet file yet.
+        let path = dir.path().join("b.parquet");
+        std::fs::write(&path, b"PAR1").unwrap();
+        assert_eq!(num_row_groups(path).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_read_partial_file() {
+        let mut buf = vec![];
+        write_parquet(&mut buf);
+        let bytes = Bytes::from(buf);
+        let len = bytes.len() as u64;
+
+        // Only the metadata and the row group to read are fetched.
+        let mut file = PartialFile::new(len);
+        let metadata_start =
+            len - (metadata_len(&bytes[bytes.len() - FOOTER_SIZE..]).unwrap() + FOOTER_SIZE) as u64;
+        file.add_range(metadata_start, bytes.slice(metadata_start as usize..));
+        let metadata = file.metadata().unwrap();
+        assert_eq!(metadata.num_row_groups(), 2);
+        let (start, end) = row_group_range(&metadata, 1);
+        file.add_range(start, bytes.slice(start as usize..end as usize));
+
+        let batches: Vec<_> = read_row_group(file, 1, 0, Some(vec!["name".to_string()]), 1024)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].1, 1);
+        assert_eq!(names(&batches[0].0), vec!["c"]);
+
+        // The row groups not fetched can't be read.
+        let mut file = PartialFile::new(len);
+        file.add_range(metadata_start, bytes.slice(metadata_start as usize..));
+        let result: Result<Vec<_>> = read_row_group(file, 0, 0, None, 1024).try_collect().await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use globset::{Glob, GlobMatcher};
+
+use crate::source::filesystem::parquet_file::num_row_groups;
+use crate::source::filesystem::posix_fs::{PosixFsProperties, PosixFsSplit};
+use crate::source::SplitEnumerator;
+
+/// Lists the files in the root directory whose names match the pattern. Each file becomes a split
+/// once it appears, so the files dropped into the directory later are picked up by the periodic
+/// listing of the meta service. A Parquet file becomes a split per row group instead.
+#[derive(Debug)]
+pub struct PosixFsSplitEnumerator {
+    root: PathBuf,
+    matcher: Option<GlobMatcher>,
+    parquet: bool,
+}
+
+#[async_trait]
+impl SplitEnumerator for PosixFsSplitEnumerator {
+    type Properties = PosixFsProperties;
+    type Split = PosixFsSplit;
+
+    async fn new(properties: PosixFsProperties) -> Result<PosixFsSplitEnumerator> {
+        let root = PathBuf::from(properties.root);
+        if !tokio::fs::metadata(&root).await?.is_dir() {
+            return Err(anyhow!("{} is not a directory", root.display()));
+        }
+        let matcher = properties
+            .match_pattern
+            .map(|pattern| Glob::new(&pattern).map(|glob| glob.compile_matcher()))
+            .transpose()?;
+        Ok(Self {
+            root,
+            matcher,
+            parquet: properties.parquet,
+        })
+    }
+
+    async fn list_splits(&mut self) -> Result<Vec<PosixFsSplit>> {
+        let mut splits = vec![];
+        let mut entries = tokio::fs::read_dir(&self.root).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            let name = entry
+                .file_name()
+                .into_string()
+                .map_err(|name| anyhow!("file name {:?} is not valid unicode", name))?;
+            if let Some(matcher) = &self.matcher && !matcher.is_match(&name) {
+                continue;
+            }
+            if self.parquet {
+                // The file being written is listed once complete.
+                if let Some(num) = num_row_groups(entry.path()).await? {
+                    splits.extend(
+                        (0..num).map(|row_group| {
+                            PosixFsSplit::with_row_group(name.clone(), row_group, 0)
+                        }),
+                    );
+                }
+            } else {
+                splits.push(PosixFsSplit::new(name, 0));
+            }
+        }
+        splits.sort_by(|a, b| (&a.name, a.row_group).cmp(&(&b.name, b.row_group)));
+        Ok(splits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_splits() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.json"), "").unwrap();
+        std::fs::write(dir.path().join("a.json"), "").unwrap();
+        std::fs::write(dir.path().join("c.csv"), "").unwrap();
+        std::fs::create_dir(dir.path().join("d.json")).unwrap();
+
+        let mut enumerator = PosixFsSplitEnumerator::new(PosixFsProperties {
+            root: dir.path().to_str().unwrap().to_string(),
+            match_pattern: Some("*.json".to_string()),
+            parquet: false,
+            csv_header: false,
+        })
+        .await
+        .unwrap();
+        let names = enumerator
+            .list_splits()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|split| split.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a.json", "b.json"]);
+
+        // The files dropped later are listed as well.
+        std::fs::write(dir.path().join("0.json"), "").unwrap();
+        assert_eq!(enumerator.list_splits().await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod enumerator;
+pub mod source;
+pub mod split;
+
+pub use enumerator::*;
+use serde::Deserialize;
+pub use source::*;
+pub use split::*;
+
+pub const POSIX_FS_CONNECTOR: &str = "posix_fs";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PosixFsProperties {
+    /// The directory to list the files from. The files in its sub-directories are not read.
+    #[serde(rename = "posix_fs.root")]
+    pub root: String,
+
+    /// The glob pattern that the names of the files to read must match, e.g. `*.json`. All the
+    /// files in the directory are read if not set.
+    #[serde(rename = "match_pattern", default)]
+    pub match_pattern: Option<String>,
+
+    /// Whether the files are in Parquet, which are split by row groups instead of read as lines.
+    /// It's set by the row format of the source rather than the `WITH` options.
+    #[serde(skip)]
+    pub parquet: bool,
+
+    /// Whether the files are in CSV with a header, which is read again before the remaining lines
+    /// when a file is resumed from the middle. It's set by the row format of the source as well.
+    #[serde(skip)]
+    pub csv_header: bool,
+}
@@ -0,0 +1,357 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_async_stream::try_stream;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+
+use crate::source::filesystem::file_common::trim_newline;
+use crate::source::filesystem::parquet_file::read_row_group;
+use crate::source::filesystem::posix_fs::{PosixFsProperties, PosixFsSplit};
+use crate::source::{
+    BoxSourceStream, Column, ConnectorState, SourceMessage, SplitId, SplitMetaData, SplitReader,
+    MAX_CHUNK_SIZE,
+};
+
+/// The interval to check the files for the lines appended, after all of them are read to the end.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads the lines of the assigned files, each of which is a message. The offset of a message is
+/// the byte offset right after its line, from which the file is read after recovery.
+///
+/// The files are tailed after reaching the end, and a line is only read once it is terminated by a
+/// newline, so that a file being written is never read in the middle of a line. If the files are
+/// in CSV with a header, the header is read again before the remaining lines of a file resumed from
+/// the middle, with the offset it's resumed from.
+///
+/// A row group of a Parquet file is read in batches instead, each of which is a message without
+/// payload carrying the record batch, with the number of rows read as its offset.
+pub struct PosixFsSplitReader {
+    root: PathBuf,
+    splits: Vec<PosixFsSplit>,
+    /// The names of the columns to read from Parquet files.
+    columns: Option<Vec<String>>,
+    csv_header: bool,
+}
+
+#[async_trait]
+impl SplitReader for PosixFsSplitReader {
+    type Properties = PosixFsProperties;
+
+    async fn new(
+        properties: PosixFsProperties,
+        state: ConnectorState,
+        columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        let splits = state
+            .into_iter()
+            .flatten()
+            .map(|split| {
+                split
+                    .into_posix_fs()
+                    .map_err(|split| anyhow!("split {:?} is not a posix_fs split", split))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        tracing::debug!("Splits for posix_fs found! {:?}", splits);
+        Ok(Self {
+            root: PathBuf::from(properties.root),
+            splits,
+            columns: columns.map(|columns| columns.into_iter().map(|c| c.name).collect()),
+            csv_header: properties.csv_header,
+        })
+    }
+
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
+    }
+}
+
+/// An assigned file being read.
+struct FileReader {
+    split_id: SplitId,
+    reader: BufReader<File>,
+    /// The byte offset right after the last line read.
+    offset: u64,
+    /// The bytes read of the line not terminated yet.
+    line: Vec<u8>,
+    /// The header of CSV to read before the remaining lines.
+    header: Option<Bytes>,
+}
+
+impl FileReader {
+    /// Opens the file at the offset of the split, or returns `None` if it has been removed.
+    async fn open(root: &Path, split: &PosixFsSplit, csv_header: bool) -> Result<Option<Self>> {
+        let mut file = match File::open(root.join(&split.name)).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                tracing::warn!("file {} of posix_fs source is removed", split.name);
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut reader = BufReader::new(file);
+        let header = if csv_header && split.offset > 0 {
+            Self::read_header(&mut reader).await?
+        } else {
+            None
+        };
+        reader.seek(SeekFrom::Start(split.offset)).await?;
+        Ok(Some(Self {
+            split_id: split.id(),
+            reader,
+            offset: split.offset,
+            line: vec![],
+            header,
+        }))
+    }
+
+    /// Reads the first non-empty line of the file, which is the header of CSV.
+    async fn read_header(reader: &mut BufReader<File>) -> Result<Option<Bytes>> {
+        let mut line = vec![];
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line).await? == 0 {
+                return Ok(None);
+            }
+            trim_newline(&mut line);
+            if !line.is_empty() {
+                return Ok(Some(Bytes::from(line)));
+            }
+        }
+    }
+
+    /// Reads at most `max_lines` lines into `msgs`. Returns whether the end of the file is reached.
+    async fn read_lines(
+        &mut self,
+        msgs: &mut Vec<SourceMessage>,
+        max_lines: usize,
+    ) -> Result<bool> {
+        if let Some(header) = self.header.take() {
+            msgs.push(SourceMessage {
+                key: None,
+                payload: Some(header),
+                offset: self.offset.to_string(),
+                split_id: self.split_id.clone(),
+                batch: None,
+            });
+        }
+        for _ in 0..max_lines {
+            if self.reader.read_until(b'\n', &mut self.line).await? == 0
+                || self.line.last() != Some(&b'\n')
+            {
+                return Ok(true);
+            }
+            self.offset += self.line.len() as u64;
+            let mut line = std::mem::take(&mut self.line);
+            trim_newline(&mut line);
+            if line.is_empty() {
+                continue;
+            }
+            msgs.push(SourceMessage {
+                key: None,
+                payload: Some(Bytes::from(line)),
+                offset: self.offset.to_string(),
+                split_id: self.split_id.clone(),
+                batch: None,
+            });
+        }
+        Ok(false)
+    }
+}
+
+impl PosixFsSplitReader {
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn into_stream(self) {
+        let (row_groups, files): (Vec<_>, Vec<_>) = self
+            .splits
+            .into_iter()
+            .partition(|split| split.row_group.is_some());
+
+        for split in row_groups {
+            let split_id = split.id();
+            let file = File::open(self.root.join(&split.name))
+                .await?
+                .into_std()
+                .await;
+            #[for_await]
+            for batch in read_row_group(
+                file,
+                split.row_group.unwrap(),
+                split.offset,
+                self.columns.clone(),
+                MAX_CHUNK_SIZE,
+            ) {
+                let (batch, offset) = batch?;
+                yield vec![SourceMessage {
+                    key: None,
+                    payload: None,
+                    offset: offset.to_string(),
+                    split_id: split_id.clone(),
+                    batch: Some(batch),
+                }];
+            }
+        }
+
+        let mut readers = vec![];
+        for split in &files {
+            if let Some(reader) = FileReader::open(&self.root, split, self.csv_header).await? {
+                readers.push(reader);
+            }
+        }
+        if readers.is_empty() {
+            return Ok(());
+        }
+
+        loop {
+            let mut all_read = true;
+            for reader in &mut readers {
+                let mut msgs = vec![];
+                all_read &= reader.read_lines(&mut msgs, MAX_CHUNK_SIZE).await?;
+                if !msgs.is_empty() {
+                    yield msgs;
+                }
+            }
+            if all_read {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::source::SplitImpl;
+
+    async fn read_messages(
+        root: &str,
+        splits: Vec<PosixFsSplit>,
+        csv_header: bool,
+        num: usize,
+    ) -> Vec<(String, String)> {
+        let reader = PosixFsSplitReader::new(
+            PosixFsProperties {
+                root: root.to_string(),
+                match_pattern: None,
+                parquet: false,
+                csv_header,
+            },
+            Some(splits.into_iter().map(SplitImpl::PosixFs).collect()),
+            None,
+        )
+        .await
+        .unwrap();
+        let mut stream = reader.into_stream();
+        let mut messages = vec![];
+        while messages.len() < num {
+            for msg in stream.next().await.unwrap().unwrap() {
+                messages.push((
+                    String::from_utf8(msg.payload.unwrap().to_vec()).unwrap(),
+                    msg.offset,
+                ));
+            }
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn test_read_and_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        std::fs::write(
+            dir.path().join("a.json"),
+            "{\"v\":1}\n{\"v\":2}\r\n\n{\"v\":3}\n{\"v\"",
+        )
+        .unwrap();
+
+        let messages = read_messages(
+            root,
+            vec![PosixFsSplit::new("a.json".to_string(), 0)],
+            false,
+            3,
+        )
+        .await;
+        assert_eq!(
+            messages,
+            vec![
+                ("{\"v\":1}".to_string(), "8".to_string()),
+                ("{\"v\":2}".to_string(), "17".to_string()),
+                ("{\"v\":3}".to_string(), "26".to_string()),
+            ]
+        );
+
+        // The split is read from its offset after recovery, including the line completed since.
+        std::fs::write(
+            dir.path().join("a.json"),
+            "{\"v\":1}\n{\"v\":2}\r\n\n{\"v\":3}\n{\"v\":4}\n",
+        )
+        .unwrap();
+        let split = PosixFsSplit::new("a.json".to_string(), 0)
+            .copy_with_offset("17".to_string())
+            .unwrap();
+        let messages = read_messages(root, vec![split], false, 2).await;
+        assert_eq!(
+            messages,
+            vec![
+                ("{\"v\":3}".to_string(), "26".to_string()),
+                ("{\"v\":4}".to_string(), "34".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_csv_with_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("a.csv"), "\nid,name\r\n1,alice\n2,bob\n").unwrap();
+
+        let messages = read_messages(
+            root,
+            vec![PosixFsSplit::new("a.csv".to_string(), 0)],
+            true,
+            3,
+        )
+        .await;
+        assert_eq!(
+            messages,
+            vec![
+                ("id,name".to_string(), "10".to_string()),
+                ("1,alice".to_string(), "18".to_string()),
+                ("2,bob".to_string(), "24".to_string()),
+            ]
+        );
+
+        // The header is read again with the offset resumed from, before the remaining lines.
+        let split = PosixFsSplit::new("a.csv".to_string(), 0)
+            .copy_with_offset("18".to_string())
+            .unwrap();
+        let messages = read_messages(root, vec![split], true, 2).await;
+        assert_eq!(
+            messages,
+            vec![
+                ("id,name".to_string(), "18".to_string()),
+                ("2,bob".to_string(), "24".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::source::{SplitId, SplitMetaData};
+
+/// A file in the directory of the source, or a row group of it for Parquet files.
+///
+/// A file is read from `offset`, the byte offset right after the last line read. A row group is
+/// read from `offset`, the number of its rows read.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Hash)]
+pub struct PosixFsSplit {
+    /// The name of the file relative to the root directory.
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) row_group: Option<usize>,
+    pub(crate) offset: u64,
+}
+
+impl SplitMetaData for PosixFsSplit {
+    fn id(&self) -> SplitId {
+        match self.row_group {
+            Some(row_group) => format!("{}#{}", self.name, row_group).into(),
+            None => self.name.as_str().into(),
+        }
+    }
+
+    fn encode_to_bytes(&self) -> Bytes {
+        Bytes::from(serde_json::to_string(self).unwrap())
+    }
+
+    fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    }
+}
+
+impl PosixFsSplit {
+    pub fn new(name: String, offset: u64) -> Self {
+        Self {
+            name,
+            row_group: None,
+            offset,
+        }
+    }
+
+    pub fn with_row_group(name: String, row_group: usize, offset: u64) -> Self {
+        Self {
+            name,
+            row_group: Some(row_group),
+            offset,
+        }
+    }
+
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        let offset = start_offset
+            .parse()
+            .map_err(|e| anyhow!("invalid offset of file {}: {}", self.name, e))?;
+        Ok(Self {
+            offset,
+            ..self.clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_with_offset() {
+        let split = PosixFsSplit::new("a.json".to_string(), 0);
+        assert_eq!(split.copy_with_offset("17".to_string()).unwrap().offset, 17);
+        assert!(split.copy_with_offset("a.json:17".to_string()).is_err());
+    }
+}
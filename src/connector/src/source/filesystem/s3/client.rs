@@ -0,0 +1,99 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::client as s3_client;
+use aws_smithy_http::byte_stream::ByteStream;
+use bytes::Bytes;
+
+use crate::aws_utils::{default_conn_config, s3_client, AwsConfigV2, AwsCredentialV2};
+use crate::source::filesystem::parquet_file::{metadata_len, PartialFile, FOOTER_SIZE};
+use crate::source::filesystem::s3::S3Properties;
+
+/// Creates the client of S3 in the region of the source, with the default credentials of the
+/// environment if no credentials are given.
+pub async fn new_client(properties: &S3Properties) -> s3_client::Client {
+    let credential = if properties.access.is_empty() || properties.secret.is_empty() {
+        AwsCredentialV2::None
+    } else {
+        AwsCredentialV2::Static {
+            access_key: properties.access.clone(),
+            secret_access: properties.secret.clone(),
+            session_token: None,
+        }
+    };
+    let config = AwsConfigV2 {
+        region: Some(properties.region_name.clone()),
+        arn: None,
+        credential,
+        endpoint: None,
+    };
+    s3_client(&config.load_config(None).await, Some(default_conn_config()))
+}
+
+/// Gets the body of an object from the byte offset `start`, to `end` exclusively if given.
+pub async fn get_object(
+    client: &s3_client::Client,
+    bucket: &str,
+    key: &str,
+    start: u64,
+    end: Option<u64>,
+) -> Result<ByteStream> {
+    let range = match end {
+        Some(end) => format!("bytes={}-{}", start, end - 1),
+        None => format!("bytes={}-", start),
+    };
+    let output = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .range(range)
+        .send()
+        .await?;
+    Ok(output.body)
+}
+
+async fn get_object_bytes(
+    client: &s3_client::Client,
+    bucket: &str,
+    key: &str,
+    start: u64,
+    end: u64,
+) -> Result<Bytes> {
+    let body = get_object(client, bucket, key, start, Some(end)).await?;
+    Ok(body.collect().await?.into_bytes())
+}
+
+/// Fetches the metadata at the end of a Parquet object of `size` bytes, with which the row groups
+/// can be fetched on demand.
+pub async fn fetch_parquet_metadata(
+    client: &s3_client::Client,
+    bucket: &str,
+    key: &str,
+    size: u64,
+) -> Result<PartialFile> {
+    let footer_start = size
+        .checked_sub(FOOTER_SIZE as u64)
+        .ok_or_else(|| anyhow!("object {} is too small to be a parquet file", key))?;
+    let footer = get_object_bytes(client, bucket, key, footer_start, size).await?;
+    let metadata_start = footer_start
+        .checked_sub(metadata_len(&footer)? as u64)
+        .ok_or_else(|| anyhow!("object {} has a corrupted parquet footer", key))?;
+    let mut file = PartialFile::new(size);
+    file.add_range(
+        metadata_start,
+        get_object_bytes(client, bucket, key, metadata_start, size).await?,
+    );
+    Ok(file)
+}
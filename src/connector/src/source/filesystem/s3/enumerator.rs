@@ -0,0 +1,124 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use aws_sdk_s3::client as s3_client;
+use globset::{Glob, GlobMatcher};
+
+use crate::source::filesystem::s3::client::{fetch_parquet_metadata, new_client};
+use crate::source::filesystem::s3::{S3Properties, S3Split};
+use crate::source::SplitEnumerator;
+
+/// Lists the objects in the bucket whose keys match the pattern. Each object becomes a split once
+/// it appears, so the objects uploaded later are picked up by the periodic listing of the meta
+/// service. A Parquet object becomes a split per row group instead.
+#[derive(Debug)]
+pub struct S3SplitEnumerator {
+    bucket: String,
+    client: s3_client::Client,
+    matcher: Option<GlobMatcher>,
+    parquet: bool,
+    /// The number of row groups of the Parquet objects listed, so that the metadata of an object
+    /// is only fetched once.
+    num_row_groups: HashMap<String, usize>,
+}
+
+#[async_trait]
+impl SplitEnumerator for S3SplitEnumerator {
+    type Properties = S3Properties;
+    type Split = S3Split;
+
+    async fn new(properties: S3Properties) -> Result<S3SplitEnumerator> {
+        let matcher = properties
+            .match_pattern
+            .as_ref()
+            .map(|pattern| Glob::new(pattern).map(|glob| glob.compile_matcher()))
+            .transpose()?;
+        Ok(Self {
+            client: new_client(&properties).await,
+            bucket: properties.bucket_name,
+            matcher,
+            parquet: properties.parquet,
+            num_row_groups: HashMap::new(),
+        })
+    }
+
+    async fn list_splits(&mut self) -> Result<Vec<S3Split>> {
+        let mut splits = vec![];
+        let mut continuation_token = None;
+        loop {
+            let mut request = self.client.list_objects_v2().bucket(&self.bucket);
+            if let Some(token) = continuation_token.take() {
+                request = request.continuation_token(token);
+            }
+            let output = request.send().await?;
+            for object in output.contents().unwrap_or_default() {
+                let key = match object.key() {
+                    Some(key) => key,
+                    None => continue,
+                };
+                if let Some(matcher) = &self.matcher && !matcher.is_match(key) {
+                    continue;
+                }
+                let size = object.size() as u64;
+                if self.parquet {
+                    if let Some(num) = self.num_row_groups(key, size).await {
+                        splits.extend((0..num).map(|row_group| {
+                            S3Split::with_row_group(key.to_string(), size, row_group, 0)
+                        }));
+                    }
+                } else {
+                    splits.push(S3Split::new(key.to_string(), size, 0));
+                }
+            }
+            continuation_token = output.next_continuation_token;
+            if !output.is_truncated {
+                break;
+            }
+        }
+        splits.sort_by(|a, b| (&a.key, a.row_group).cmp(&(&b.key, b.row_group)));
+        Ok(splits)
+    }
+}
+
+impl S3SplitEnumerator {
+    /// Returns the number of row groups of a Parquet object, or `None` if its metadata fails to be
+    /// fetched, in which case it's tried again by the next listing.
+    async fn num_row_groups(&mut self, key: &str, size: u64) -> Option<usize> {
+        if let Some(num) = self.num_row_groups.get(key) {
+            return Some(*num);
+        }
+        let metadata = fetch_parquet_metadata(&self.client, &self.bucket, key, size)
+            .await
+            .and_then(|file| file.metadata());
+        match metadata {
+            Ok(metadata) => {
+                let num = metadata.num_row_groups();
+                self.num_row_groups.insert(key.to_string(), num);
+                Some(num)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "failed to read the metadata of parquet object {}: {}",
+                    key,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
@@ -11,11 +11,17 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+mod client;
+pub mod enumerator;
 mod s3_dir;
 mod s3_notification_event;
-mod source;
+pub mod source;
+pub mod split;
 
+pub use enumerator::*;
 use serde::Deserialize;
+pub use source::*;
+pub use split::*;
 
 pub const S3_CONNECTOR: &str = "s3";
 
@@ -25,12 +31,26 @@ pub struct S3Properties {
     pub region_name: String,
     #[serde(rename = "s3.bucket_name")]
     pub bucket_name: String,
-    #[serde(rename = "sqs_queue_name")]
+    /// The queue notified of the objects uploaded, which isn't needed as the bucket is listed.
+    #[serde(rename = "sqs_queue_name", default)]
     pub sqs_queue_name: String,
+    /// The glob pattern that the keys of the objects to read must match, e.g. `*.parquet`. All the
+    /// objects in the bucket are read if not set.
     #[serde(rename = "match_pattern", default)]
     pub match_pattern: Option<String>,
     #[serde(rename = "s3.credentials.access", default)]
     pub access: String,
     #[serde(rename = "s3.credentials.secret", default)]
     pub secret: String,
+
+    /// Whether the objects are in Parquet, which are split by row groups instead of read as lines.
+    /// It's set by the row format of the source rather than the `WITH` options.
+    #[serde(skip)]
+    pub parquet: bool,
+
+    /// Whether the objects are in CSV with a header, which is read again before the remaining
+    /// lines when an object is resumed from the middle. It's set by the row format of the source
+    /// as well.
+    #[serde(skip)]
+    pub csv_header: bool,
 }
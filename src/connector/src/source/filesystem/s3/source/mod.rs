@@ -11,4 +11,7 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+mod reader;
 mod s3_file_reader;
+
+pub use reader::*;
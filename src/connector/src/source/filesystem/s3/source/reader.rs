@@ -0,0 +1,269 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use aws_sdk_s3::client as s3_client;
+use aws_smithy_http::byte_stream::ByteStream;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use futures_async_stream::try_stream;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+use tokio_util::io::StreamReader;
+
+use crate::source::filesystem::file_common::trim_newline;
+use crate::source::filesystem::parquet_file::{read_row_group, row_group_range};
+use crate::source::filesystem::s3::client::{fetch_parquet_metadata, get_object, new_client};
+use crate::source::filesystem::s3::{S3Properties, S3Split};
+use crate::source::{
+    BoxSourceStream, Column, ConnectorState, SourceMessage, SplitId, SplitMetaData, SplitReader,
+    MAX_CHUNK_SIZE,
+};
+
+/// Reads the lines of the assigned objects, each of which is a message. The offset of a message is
+/// the byte offset right after its line, from which the object is read after recovery. If the
+/// objects are in CSV with a header, the header is read again before the remaining lines of an
+/// object resumed from the middle, with the offset it's resumed from.
+///
+/// A row group of a Parquet object is read in batches instead, each of which is a message without
+/// payload carrying the record batch, with the number of rows read as its offset. Only the metadata
+/// and the row group are fetched from the object.
+pub struct S3SplitReader {
+    client: s3_client::Client,
+    bucket: String,
+    splits: Vec<S3Split>,
+    /// The names of the columns to read from Parquet objects.
+    columns: Option<Vec<String>>,
+    csv_header: bool,
+}
+
+#[async_trait]
+impl SplitReader for S3SplitReader {
+    type Properties = S3Properties;
+
+    async fn new(
+        properties: S3Properties,
+        state: ConnectorState,
+        columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        let splits = state
+            .into_iter()
+            .flatten()
+            .map(|split| {
+                split
+                    .into_s3()
+                    .map_err(|split| anyhow!("split {:?} is not an s3 split", split))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        tracing::debug!("Splits for s3 found! {:?}", splits);
+        Ok(Self {
+            client: new_client(&properties).await,
+            bucket: properties.bucket_name,
+            splits,
+            columns: columns.map(|columns| columns.into_iter().map(|c| c.name).collect()),
+            csv_header: properties.csv_header,
+        })
+    }
+
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
+    }
+}
+
+/// Reads the lines from `reader` into the messages of `split_id`, starting from the byte offset
+/// `offset`. The last line of an object is read even if it's not terminated by a newline, as an
+/// object is immutable once uploaded.
+#[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+async fn read_lines(
+    mut reader: impl AsyncBufRead + Send + Unpin + 'static,
+    split_id: SplitId,
+    mut offset: u64,
+    header: Option<Bytes>,
+) {
+    let mut msgs = vec![];
+    if let Some(header) = header {
+        msgs.push(SourceMessage {
+            key: None,
+            payload: Some(header),
+            offset: offset.to_string(),
+            split_id: split_id.clone(),
+            batch: None,
+        });
+    }
+    loop {
+        let mut line = vec![];
+        let len = reader.read_until(b'\n', &mut line).await?;
+        if len == 0 {
+            break;
+        }
+        offset += len as u64;
+        trim_newline(&mut line);
+        if line.is_empty() {
+            continue;
+        }
+        msgs.push(SourceMessage {
+            key: None,
+            payload: Some(Bytes::from(line)),
+            offset: offset.to_string(),
+            split_id: split_id.clone(),
+            batch: None,
+        });
+        if msgs.len() >= MAX_CHUNK_SIZE {
+            yield std::mem::take(&mut msgs);
+        }
+    }
+    if !msgs.is_empty() {
+        yield msgs;
+    }
+}
+
+/// Reads the first non-empty line, which is the header of CSV.
+async fn read_header(mut reader: impl AsyncBufRead + Unpin) -> Result<Option<Bytes>> {
+    loop {
+        let mut line = vec![];
+        if reader.read_until(b'\n', &mut line).await? == 0 {
+            return Ok(None);
+        }
+        trim_newline(&mut line);
+        if !line.is_empty() {
+            return Ok(Some(Bytes::from(line)));
+        }
+    }
+}
+
+/// Reads the body of an object in lines.
+fn body_reader(body: ByteStream) -> impl AsyncBufRead + Send + Unpin + 'static {
+    BufReader::new(StreamReader::new(
+        body.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
+    ))
+}
+
+impl S3SplitReader {
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn into_stream(self) {
+        for split in &self.splits {
+            let split_id = split.id();
+            match split.row_group {
+                Some(row_group) => {
+                    let mut file =
+                        fetch_parquet_metadata(&self.client, &self.bucket, &split.key, split.size)
+                            .await?;
+                    let (start, end) = row_group_range(&file.metadata()?, row_group);
+                    let body = get_object(&self.client, &self.bucket, &split.key, start, Some(end))
+                        .await?;
+                    file.add_range(start, body.collect().await?.into_bytes());
+                    #[for_await]
+                    for batch in read_row_group(
+                        file,
+                        row_group,
+                        split.offset,
+                        self.columns.clone(),
+                        MAX_CHUNK_SIZE,
+                    ) {
+                        let (batch, offset) = batch?;
+                        yield vec![SourceMessage {
+                            key: None,
+                            payload: None,
+                            offset: offset.to_string(),
+                            split_id: split_id.clone(),
+                            batch: Some(batch),
+                        }];
+                    }
+                }
+                None => {
+                    if split.offset >= split.size {
+                        continue;
+                    }
+                    let header = if self.csv_header && split.offset > 0 {
+                        let body = get_object(
+                            &self.client,
+                            &self.bucket,
+                            &split.key,
+                            0,
+                            Some(split.offset),
+                        )
+                        .await?;
+                        read_header(body_reader(body)).await?
+                    } else {
+                        None
+                    };
+                    let body =
+                        get_object(&self.client, &self.bucket, &split.key, split.offset, None)
+                            .await?;
+                    let reader = body_reader(body);
+                    #[for_await]
+                    for msgs in read_lines(reader, split_id, split.offset, header) {
+                        yield msgs?;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    async fn read_messages(
+        content: &'static str,
+        offset: u64,
+        header: Option<&'static str>,
+    ) -> Vec<(String, String)> {
+        let reader = BufReader::new(content.as_bytes());
+        let mut stream = read_lines(reader, "a.csv".into(), offset, header.map(Bytes::from));
+        let mut messages = vec![];
+        while let Some(msgs) = stream.next().await {
+            for msg in msgs.unwrap() {
+                messages.push((
+                    String::from_utf8(msg.payload.unwrap().to_vec()).unwrap(),
+                    msg.offset,
+                ));
+            }
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn test_read_lines() {
+        let content = "\nid,name\r\n1,alice\n2,bob";
+        assert_eq!(
+            read_header(BufReader::new(content.as_bytes()))
+                .await
+                .unwrap(),
+            Some(Bytes::from("id,name"))
+        );
+
+        // The last line is read without the newline.
+        assert_eq!(
+            read_messages(content, 0, None).await,
+            vec![
+                ("id,name".to_string(), "10".to_string()),
+                ("1,alice".to_string(), "18".to_string()),
+                ("2,bob".to_string(), "23".to_string()),
+            ]
+        );
+
+        // The header is read again with the offset resumed from, before the remaining lines.
+        assert_eq!(
+            read_messages(&content[18..], 18, Some("id,name")).await,
+            vec![
+                ("id,name".to_string(), "18".to_string()),
+                ("2,bob".to_string(), "23".to_string()),
+            ]
+        );
+    }
+}
@@ -327,9 +327,11 @@ impl S3FileReader {
                     };
                     self.split_offset.insert(msg_id.clone(), new_offset);
                     SourceMessage {
+                        key: None,
                         payload: Some(msg.payload),
                         offset: new_offset.to_string(),
                         split_id: msg_id.into(),
+                        batch: None,
                     }
                 })
                 .collect();
@@ -358,6 +360,8 @@ mod test {
             match_pattern: None,
             access: "".to_string(),
             secret: "".to_string(),
+            parquet: false,
+            csv_header: false,
         }
     }
 
@@ -37,6 +37,7 @@ impl From<TaggedReceivedMessage> for SourceMessage {
             .unwrap_or_default();
 
         Self {
+            key: None,
             payload: {
                 let payload = message.message.data;
                 match payload.len() {
@@ -48,6 +49,8 @@ impl From<TaggedReceivedMessage> for SourceMessage {
             offset: timestamp.timestamp_nanos().to_string(),
 
             split_id,
+
+            batch: None,
         }
     }
 }
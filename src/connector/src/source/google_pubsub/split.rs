@@ -36,13 +36,13 @@ pub struct PubsubSplit {
 }
 
 impl PubsubSplit {
-    pub fn copy_with_offset(&self, start_offset: String) -> Self {
-        Self {
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        Ok(Self {
             start_offset: Some(start_offset),
             index: self.index,
             subscription: self.subscription.clone(),
             stop_offset: None,
-        }
+        })
     }
 }
 
@@ -38,7 +38,7 @@ pub struct KafkaSplitEnumerator {
     client: BaseConsumer,
     start_offset: KafkaEnumeratorOffset,
 
-    // maybe used in the future for batch processing
+    /// Where to stop reading, if the source is bounded.
     stop_offset: KafkaEnumeratorOffset,
 }
 
@@ -52,6 +52,13 @@ impl SplitEnumerator for KafkaSplitEnumerator {
     async fn new(properties: KafkaProperties) -> anyhow::Result<KafkaSplitEnumerator> {
         let broker_address = properties.brokers.clone();
         let topic = properties.topic.clone();
+        properties.commit_group_id()?;
+
+        let time_offset = properties
+            .time_offset
+            .as_ref()
+            .map(|s| s.parse::<i64>().map_err(|e| anyhow!(e)))
+            .transpose()?;
 
         let mut scan_start_offset = match properties
             .scan_startup_mode
@@ -61,19 +68,41 @@ impl SplitEnumerator for KafkaSplitEnumerator {
         {
             Some("earliest") => KafkaEnumeratorOffset::Earliest,
             Some("latest") => KafkaEnumeratorOffset::Latest,
+            Some("timestamp") => match time_offset {
+                Some(time_offset) => KafkaEnumeratorOffset::Timestamp(time_offset),
+                None => {
+                    return Err(anyhow!(
+                        "properties `scan.startup.timestamp_millis` must be specified for the timestamp startup mode"
+                    ));
+                }
+            },
             None => KafkaEnumeratorOffset::Earliest,
             _ => {
                 return Err(anyhow!(
-                    "properties `scan_startup_mode` only support earliest and latest or leave it empty"
+                    "properties `scan_startup_mode` only support earliest, latest and timestamp or leave it empty"
                 ));
             }
         };
 
-        if let Some(s) = &properties.time_offset {
-            let time_offset = s.parse::<i64>().map_err(|e| anyhow!(e))?;
+        if let Some(time_offset) = time_offset {
             scan_start_offset = KafkaEnumeratorOffset::Timestamp(time_offset)
         }
 
+        let scan_stop_offset = match properties
+            .scan_bounded_mode
+            .as_ref()
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            Some("latest_at_start") => KafkaEnumeratorOffset::Latest,
+            None => KafkaEnumeratorOffset::None,
+            _ => {
+                return Err(anyhow!(
+                    "properties `scan.bounded.mode` only support latest_at_start or leave it empty"
+                ));
+            }
+        };
+
         let mut config = rdkafka::ClientConfig::new();
         config.set("bootstrap.servers", &broker_address);
         properties.set_security_properties(&mut config);
@@ -84,7 +113,7 @@ impl SplitEnumerator for KafkaSplitEnumerator {
             topic,
             client,
             start_offset: scan_start_offset,
-            stop_offset: KafkaEnumeratorOffset::None,
+            stop_offset: scan_stop_offset,
         })
     }
 
@@ -163,9 +192,9 @@ impl KafkaSplitEnumerator {
                 }
                 Ok(map)
             }
-            KafkaEnumeratorOffset::Timestamp(time) => {
-                self.fetch_offset_for_time(partitions, time).await
-            }
+            KafkaEnumeratorOffset::Timestamp(time) => Ok(start_offsets_for_time(
+                self.fetch_offset_for_time(partitions, time).await?,
+            )),
             KafkaEnumeratorOffset::None => partitions
                 .iter()
                 .map(|partition| Ok((*partition, None)))
@@ -236,3 +265,77 @@ impl KafkaSplitEnumerator {
             .collect())
     }
 }
+
+/// Converts the offsets of the first messages at or after a time to the start offsets of the
+/// splits, which are the offsets of the last messages consumed right before them.
+fn start_offsets_for_time(offsets: HashMap<i32, Option<i64>>) -> HashMap<i32, Option<i64>> {
+    offsets
+        .into_iter()
+        .map(|(partition, offset)| (partition, offset.map(|offset| offset - 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+    use serde_json::json;
+
+    use super::*;
+
+    async fn new_enumerator(options: serde_json::Value) -> anyhow::Result<KafkaSplitEnumerator> {
+        let mut properties = json!({
+            "properties.bootstrap.server": "localhost:9092",
+            "topic": "test",
+        });
+        properties
+            .as_object_mut()
+            .unwrap()
+            .extend(options.as_object().unwrap().clone());
+        KafkaSplitEnumerator::new(serde_json::from_value(properties).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn test_startup_and_bounded_mode() {
+        let enumerator = new_enumerator(json!({})).await.unwrap();
+        assert_eq!(enumerator.start_offset, KafkaEnumeratorOffset::Earliest);
+        assert_eq!(enumerator.stop_offset, KafkaEnumeratorOffset::None);
+
+        let enumerator = new_enumerator(json!({
+            "scan.startup.mode": "timestamp",
+            "scan.startup.timestamp_millis": "1668000000000",
+            "scan.bounded.mode": "LATEST_AT_START",
+        }))
+        .await
+        .unwrap();
+        assert_eq!(
+            enumerator.start_offset,
+            KafkaEnumeratorOffset::Timestamp(1668000000000)
+        );
+        assert_eq!(enumerator.stop_offset, KafkaEnumeratorOffset::Latest);
+
+        assert!(new_enumerator(json!({"scan.startup.mode": "timestamp"}))
+            .await
+            .is_err());
+        assert!(new_enumerator(json!({"scan.bounded.mode": "earliest"}))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_start_offsets_for_time() {
+        // Partition 1 has no message after the time, whose offset is the high watermark.
+        let offsets = hashmap! {
+            0 => Some(5),
+            1 => Some(10),
+            2 => Some(0),
+        };
+        assert_eq!(
+            start_offsets_for_time(offsets),
+            hashmap! {
+                0 => Some(4),
+                1 => Some(9),
+                2 => Some(-1),
+            }
+        );
+    }
+}
@@ -14,8 +14,10 @@
 
 use std::time::Duration;
 
+use anyhow::{anyhow, Result};
 use rdkafka::ClientConfig;
 use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
 
 pub mod enumerator;
 pub mod source;
@@ -27,6 +29,10 @@ pub use split::*;
 
 pub const KAFKA_CONNECTOR: &str = "kafka";
 
+/// The option to make a source bounded, so that it can be read as a finite batch.
+pub const SCAN_BOUNDED_MODE_KEY: &str = "scan.bounded.mode";
+
+#[serde_as]
 #[derive(Clone, Debug, Deserialize)]
 pub struct KafkaProperties {
     #[serde(rename = "properties.bootstrap.server", alias = "kafka.brokers")]
@@ -41,9 +47,21 @@ pub struct KafkaProperties {
     #[serde(rename = "scan.startup.timestamp_millis", alias = "kafka.time.offset")]
     pub time_offset: Option<String>,
 
+    /// Where to stop reading the partitions. Could be `latest_at_start`, which stops at the high
+    /// watermarks when the splits are listed, or empty to read the partitions endlessly.
+    #[serde(rename = "scan.bounded.mode")]
+    pub scan_bounded_mode: Option<String>,
+
     #[serde(rename = "properties.group.id", alias = "kafka.consumer.group")]
     pub consumer_group: Option<String>,
 
+    /// Whether to commit the checkpointed offsets back to Kafka under the consumer group, so that
+    /// the progress of the source can be monitored by Kafka tools. The source is always recovered
+    /// from the offsets checkpointed by RisingWave instead.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(rename = "commit.offsets", default)]
+    pub commit_offsets: bool,
+
     /// Security protocol used for RisingWave to communicate with Kafka brokers. Could be
     /// PLAINTEXT, SSL, SASL_PLAINTEXT or SASL_SSL.
     #[serde(rename = "properties.security.protocol")]
@@ -104,6 +122,19 @@ pub struct KafkaProperties {
 }
 
 impl KafkaProperties {
+    /// Returns the consumer group to commit the offsets under, if committing offsets is enabled.
+    pub fn commit_group_id(&self) -> Result<Option<&str>> {
+        if !self.commit_offsets {
+            return Ok(None);
+        }
+        match self.consumer_group.as_deref() {
+            Some(group_id) => Ok(Some(group_id)),
+            None => Err(anyhow!(
+                "`properties.group.id` must be specified to commit offsets"
+            )),
+        }
+    }
+
     fn set_security_properties(&self, config: &mut ClientConfig) {
         // Security protocol
         if let Some(security_protocol) = self.security_protocol.as_ref() {
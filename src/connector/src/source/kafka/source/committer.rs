@@ -0,0 +1,139 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use anyhow::{Context, Result};
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer, DefaultConsumerContext};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+
+use crate::source::kafka::{KafkaProperties, KafkaSplit, KAFKA_SYNC_CALL_TIMEOUT};
+
+/// Reports the progress of a Kafka source to Kafka. It commits the checkpointed offsets of the
+/// splits under the consumer group of the source if enabled, and fetches the high watermarks of
+/// their partitions to compute the consumer lag.
+///
+/// All the methods are blocking calls to the brokers.
+pub struct KafkaOffsetCommitter {
+    consumer: BaseConsumer,
+    commit_offsets: bool,
+}
+
+impl KafkaOffsetCommitter {
+    pub async fn new(properties: &KafkaProperties) -> Result<Self> {
+        let mut config = ClientConfig::new();
+        config.set("enable.auto.commit", "false");
+        config.set("bootstrap.servers", &properties.brokers);
+        properties.set_security_properties(&mut config);
+
+        let group_id = properties.commit_group_id()?;
+        if let Some(group_id) = group_id {
+            config.set("group.id", group_id);
+        }
+
+        let consumer: BaseConsumer = config
+            .create_with_context(DefaultConsumerContext)
+            .await
+            .context("failed to create kafka consumer")?;
+
+        Ok(Self {
+            consumer,
+            commit_offsets: group_id.is_some(),
+        })
+    }
+
+    /// Commits the offsets of the splits if enabled. The committed offset of a partition is the
+    /// offset of the next message to consume, following the convention of Kafka.
+    pub fn commit(&self, splits: &[KafkaSplit]) -> Result<()> {
+        if !self.commit_offsets {
+            return Ok(());
+        }
+        let mut tpl = TopicPartitionList::with_capacity(splits.len());
+        for split in splits {
+            if let Some(offset) = split.start_offset {
+                tpl.add_partition_offset(
+                    split.topic.as_str(),
+                    split.partition,
+                    Offset::Offset(offset + 1),
+                )?;
+            }
+        }
+        if tpl.count() > 0 {
+            self.consumer.commit(&tpl, CommitMode::Sync)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of messages in the partition of the split after its offset, or `None` if
+    /// nothing has been consumed from the split.
+    pub fn fetch_lag(&self, split: &KafkaSplit) -> Result<Option<i64>> {
+        let offset = match split.start_offset {
+            Some(offset) => offset,
+            None => return Ok(None),
+        };
+        let (_low, high) = self.consumer.fetch_watermarks(
+            split.topic.as_str(),
+            split.partition,
+            KAFKA_SYNC_CALL_TIMEOUT,
+        )?;
+        Ok(Some((high - offset - 1).max(0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    async fn new_committer(options: serde_json::Value) -> Result<KafkaOffsetCommitter> {
+        let mut properties = json!({
+            "properties.bootstrap.server": "localhost:9092",
+            "topic": "test",
+        });
+        properties
+            .as_object_mut()
+            .unwrap()
+            .extend(options.as_object().unwrap().clone());
+        KafkaOffsetCommitter::new(&serde_json::from_value(properties).unwrap()).await
+    }
+
+    fn split(start_offset: Option<i64>) -> KafkaSplit {
+        KafkaSplit::new(0, start_offset, None, "test".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_commit() {
+        // Offsets are not committed by default, so that no broker is requested.
+        let committer = new_committer(json!({})).await.unwrap();
+        assert!(!committer.commit_offsets);
+        committer.commit(&[split(Some(10))]).unwrap();
+
+        assert!(new_committer(json!({"commit.offsets": "true"}))
+            .await
+            .is_err());
+        let committer = new_committer(json!({
+            "commit.offsets": "true",
+            "properties.group.id": "test-group",
+        }))
+        .await
+        .unwrap();
+        assert!(committer.commit_offsets);
+        // Nothing to commit if none of the splits has been consumed.
+        committer.commit(&[split(None)]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_lag_of_unconsumed_split() {
+        let committer = new_committer(json!({})).await.unwrap();
+        assert_eq!(committer.fetch_lag(&split(None)).unwrap(), None);
+    }
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod committer;
 mod message;
 mod reader;
 
+pub use committer::*;
 pub use reader::*;
@@ -28,6 +28,7 @@ use crate::source::{BoxSourceStream, Column, ConnectorState, SplitImpl};
 
 pub struct KafkaSplitReader {
     consumer: StreamConsumer<DefaultConsumerContext>,
+    start_offset: Option<i64>,
     stop_offset: Option<i64>,
 }
 
@@ -52,8 +53,9 @@ impl SplitReader for KafkaSplitReader {
 
         properties.set_security_properties(&mut config);
 
-        if config.get("group.id").is_none() {
-            config.set(
+        match properties.consumer_group.as_ref() {
+            Some(group_id) => config.set("group.id", group_id),
+            None => config.set(
                 "group.id",
                 format!(
                     "consumer-{}",
@@ -62,8 +64,8 @@ impl SplitReader for KafkaSplitReader {
                         .unwrap()
                         .as_micros()
                 ),
-            );
-        }
+            ),
+        };
 
         let consumer: StreamConsumer = config
             .set_log_level(RDKafkaLogLevel::Info)
@@ -71,6 +73,7 @@ impl SplitReader for KafkaSplitReader {
             .await
             .context("failed to create kafka consumer")?;
 
+        let mut start_offset = None;
         let mut stop_offset = None;
         if let Some(splits) = state {
             assert_eq!(splits.len(), 1);
@@ -87,6 +90,7 @@ impl SplitReader for KafkaSplitReader {
                     } else {
                         tpl.add_partition(k.topic.as_str(), k.partition);
                     }
+                    start_offset = k.start_offset;
                     stop_offset = k.stop_offset;
                 }
             }
@@ -96,6 +100,7 @@ impl SplitReader for KafkaSplitReader {
 
         Ok(Self {
             consumer,
+            start_offset,
             stop_offset,
         })
     }
@@ -108,6 +113,11 @@ impl SplitReader for KafkaSplitReader {
 impl KafkaSplitReader {
     #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
     pub async fn into_stream(self) {
+        // Nothing is left before the stop offset, so it must not wait for the messages after it.
+        if is_exhausted(self.start_offset, self.stop_offset) {
+            return Ok(());
+        }
+
         #[for_await]
         'for_outer_loop: for msgs in self.consumer.stream().ready_chunks(MAX_CHUNK_SIZE) {
             let mut res = Vec::with_capacity(msgs.len());
@@ -130,3 +140,29 @@ impl KafkaSplitReader {
         }
     }
 }
+
+/// Returns whether a split has no message to read before its stop offset, given the offset of the
+/// last message consumed.
+fn is_exhausted(start_offset: Option<i64>, stop_offset: Option<i64>) -> bool {
+    match (start_offset, stop_offset) {
+        (Some(start_offset), Some(stop_offset)) => start_offset + 1 >= stop_offset,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_exhausted() {
+        // A bounded split of an empty partition, read from the earliest.
+        assert!(is_exhausted(Some(-1), Some(0)));
+        // The last message before the stop offset is consumed.
+        assert!(is_exhausted(Some(9), Some(10)));
+        assert!(!is_exhausted(Some(8), Some(10)));
+        // The split is unbounded, or read from the offset reset by the consumer.
+        assert!(!is_exhausted(Some(9), None));
+        assert!(!is_exhausted(None, Some(10)));
+    }
+}
@@ -56,13 +56,14 @@ impl KafkaSplit {
         }
     }
 
-    pub fn copy_with_offset(&self, start_offset: String) -> Self {
-        Self::new(
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        let start_offset = start_offset.parse().map_err(|e| anyhow!(e))?;
+        Ok(Self::new(
             self.partition,
-            Some(start_offset.as_str().parse::<i64>().unwrap()),
+            Some(start_offset),
             self.stop_offset,
             self.topic.clone(),
-        )
+        ))
     }
 
     pub fn get_topic_and_partition(&self) -> (String, i32) {
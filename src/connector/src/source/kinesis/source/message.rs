@@ -28,9 +28,11 @@ pub struct KinesisMessage {
 impl From<KinesisMessage> for SourceMessage {
     fn from(msg: KinesisMessage) -> Self {
         SourceMessage {
+            key: None,
             payload: Some(msg.payload),
             offset: msg.sequence_number.clone(),
             split_id: msg.shard_id,
+            batch: None,
         }
     }
 }
@@ -21,6 +21,7 @@ impl From<Message<Vec<u8>>> for SourceMessage {
         let message_id = msg.message_id.id;
 
         SourceMessage {
+            key: None,
             payload: Some(msg.payload.data.into()),
             offset: format!(
                 "{}:{}:{}:{}",
@@ -30,6 +31,7 @@ impl From<Message<Vec<u8>>> for SourceMessage {
                 message_id.batch_index.unwrap_or(-1)
             ),
             split_id: msg.topic.into(),
+            batch: None,
         }
     }
 }
@@ -52,6 +52,7 @@ pub fn build_compaction_config_vec(
     target_file_size_base: Option<u64>,
     compaction_filter_mask: Option<u32>,
     max_sub_compaction: Option<u32>,
+    tombstone_reclaim_ratio: Option<u32>,
 ) -> Vec<MutableConfig> {
     let mut configs = vec![];
     if let Some(c) = max_bytes_for_level_base {
@@ -81,5 +82,8 @@ pub fn build_compaction_config_vec(
     if let Some(c) = max_sub_compaction {
         configs.push(MutableConfig::MaxSubCompaction(c));
     }
+    if let Some(c) = tombstone_reclaim_ratio {
+        configs.push(MutableConfig::TombstoneReclaimRatio(c));
+    }
     configs
 }
@@ -15,6 +15,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use cmd_impl::bench::BenchCommands;
+use risingwave_meta::backup_restore::RestoreOpts;
 
 use crate::cmd_impl::hummock::{
     build_compaction_config_vec, list_pinned_snapshots, list_pinned_versions,
@@ -140,6 +141,10 @@ enum HummockCommands {
         compaction_filter_mask: Option<u32>,
         #[clap(long)]
         max_sub_compaction: Option<u32>,
+        /// Percentage of tombstones in an SST's keys that makes it picked for compaction. 0
+        /// disables it.
+        #[clap(long)]
+        tombstone_reclaim_ratio: Option<u32>,
     },
 }
 
@@ -190,6 +195,9 @@ enum MetaCommands {
         #[clap(long)]
         dry_run: bool,
     },
+    /// Restore the meta store from a meta snapshot. The cluster must be stopped and the meta
+    /// store must be empty.
+    RestoreMeta(RestoreOpts),
 }
 
 pub async fn start(opts: CliOpts) -> Result<()> {
@@ -242,6 +250,7 @@ pub async fn start(opts: CliOpts) -> Result<()> {
             target_file_size_base,
             compaction_filter_mask,
             max_sub_compaction,
+            tombstone_reclaim_ratio,
         }) => {
             cmd_impl::hummock::update_compaction_config(
                 compaction_group_ids,
@@ -255,6 +264,7 @@ pub async fn start(opts: CliOpts) -> Result<()> {
                     target_file_size_base,
                     compaction_filter_mask,
                     max_sub_compaction,
+                    tombstone_reclaim_ratio,
                 ),
             )
             .await?
@@ -271,6 +281,9 @@ pub async fn start(opts: CliOpts) -> Result<()> {
         Commands::Meta(MetaCommands::Reschedule { plan, dry_run }) => {
             cmd_impl::meta::reschedule(plan, dry_run).await?
         }
+        Commands::Meta(MetaCommands::RestoreMeta(opts)) => {
+            cmd_impl::meta::restore_meta(opts).await?
+        }
         Commands::Trace => cmd_impl::trace::trace().await?,
         Commands::Profile { sleep } => cmd_impl::profile::profile(sleep).await?,
     }
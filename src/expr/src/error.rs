@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
 pub use anyhow::anyhow;
 use regex;
 use risingwave_common::array::ArrayError;
@@ -50,6 +52,9 @@ pub enum ExprError {
     #[error("More than one row returned by {0} used as an expression")]
     MaxOneRow(&'static str),
 
+    #[error("User-defined function {name} timed out after {timeout:?}")]
+    UdfTimeout { name: String, timeout: Duration },
+
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
 }
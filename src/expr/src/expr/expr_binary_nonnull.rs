@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use risingwave_common::array::{
-    Array, BoolArray, DecimalArray, I32Array, I64Array, IntervalArray, ListArray, NaiveDateArray,
-    NaiveDateTimeArray, StructArray, Utf8Array,
+    Array, BoolArray, DecimalArray, I32Array, I64Array, IntervalArray, JsonbArray, ListArray,
+    NaiveDateArray, NaiveDateTimeArray, StructArray, Utf8Array,
 };
 use risingwave_common::types::*;
 use risingwave_pb::expr::expr_node::Type;
@@ -30,6 +30,7 @@ use crate::vector_op::date_trunc::{date_trunc_interval, date_trunc_timestamp};
 use crate::vector_op::extract::{
     extract_from_date, extract_from_timestamp, extract_from_timestampz,
 };
+use crate::vector_op::jsonb_info::{jsonb_contains, jsonb_exists};
 use crate::vector_op::like::like_default;
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
@@ -613,6 +614,17 @@ pub fn new_binary_expr(
         )),
         Type::TumbleStart => new_tumble_start(l, r, ret)?,
         Type::ConcatOp => new_concat_op(l, r, ret),
+        Type::JsonbContains => Box::new(
+            BinaryExpression::<JsonbArray, JsonbArray, BoolArray, _>::new(
+                l,
+                r,
+                ret,
+                jsonb_contains,
+            ),
+        ),
+        Type::JsonbExists => Box::new(
+            BinaryExpression::<JsonbArray, Utf8Array, BoolArray, _>::new(l, r, ret, jsonb_exists),
+        ),
 
         tp => {
             return Err(ExprError::UnsupportedFunction(format!(
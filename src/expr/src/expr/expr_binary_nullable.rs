@@ -29,6 +29,10 @@ use crate::vector_op::cmp::{
     str_is_not_distinct_from,
 };
 use crate::vector_op::conjunction::{and, or};
+use crate::vector_op::jsonb_access::{
+    jsonb_access_path, jsonb_array_element, jsonb_array_element_str, jsonb_object_field,
+    jsonb_object_field_str,
+};
 use crate::{for_all_cmp_variants, ExprError, Result};
 
 macro_rules! gen_nullable_cmp_impl {
@@ -200,6 +204,9 @@ pub fn new_nullable_binary_expr(
         Type::Or => Box::new(BinaryShortCircuitExpression::new(l, r, expr_type)),
         Type::IsDistinctFrom => new_distinct_from_expr(l, r, ret)?,
         Type::IsNotDistinctFrom => new_not_distinct_from_expr(l, r, ret)?,
+        Type::JsonbAccessInner | Type::JsonbAccessStr | Type::JsonbAccessPath => {
+            build_jsonb_access_expr(expr_type, ret, l, r)?
+        }
         tp => {
             return Err(ExprError::UnsupportedFunction(format!(
                 "{:?}({:?}, {:?})",
@@ -247,9 +254,69 @@ fn build_array_access_expr(
         DataType::Interval => array_access_expression!(IntervalArray),
         DataType::Struct { .. } => array_access_expression!(StructArray),
         DataType::List { .. } => array_access_expression!(ListArray),
+        DataType::Jsonb => array_access_expression!(JsonbArray),
     }
 }
 
+fn build_jsonb_access_expr(
+    expr_type: Type,
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match (expr_type, r.return_type()) {
+        (Type::JsonbAccessInner, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_object_field))
+        }
+        (Type::JsonbAccessInner, DataType::Int32) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                I32Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_array_element))
+        }
+        (Type::JsonbAccessStr, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                Utf8Array,
+                _,
+            >::new(l, r, ret, jsonb_object_field_str))
+        }
+        (Type::JsonbAccessStr, DataType::Int32) => Box::new(BinaryNullableExpression::<
+            JsonbArray,
+            I32Array,
+            Utf8Array,
+            _,
+        >::new(
+            l, r, ret, jsonb_array_element_str
+        )),
+        (Type::JsonbAccessPath, DataType::List { .. }) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                ListArray,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_access_path))
+        }
+        (tp, r_type) => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "{:?}({:?}, {:?})",
+                tp,
+                l.return_type(),
+                r_type,
+            )));
+        }
+    };
+    Ok(expr)
+}
+
 pub fn new_distinct_from_expr(
     l: BoxedExpression,
     r: BoxedExpression,
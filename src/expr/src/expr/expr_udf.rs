@@ -0,0 +1,420 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+use std::sync::{mpsc, Arc, LazyLock};
+use std::time::Duration;
+
+use arrow_array::{
+    Array, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array,
+    RecordBatch, RecordBatchOptions, StringArray,
+};
+use arrow_ipc::reader::StreamReader;
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{DataType as ArrowDataType, Field, Schema};
+use itertools::Itertools;
+use risingwave_common::array::{ArrayImpl, ArrayRef, DataChunk};
+use risingwave_common::row::Row;
+use risingwave_common::types::{
+    DataType, Datum, OrderedF32, OrderedF64, ScalarImpl, ScalarRefImpl,
+};
+use risingwave_pb::expr::expr_node::{RexNode, Type};
+use risingwave_pb::expr::udf_service_client::UdfServiceClient;
+use risingwave_pb::expr::{ExprNode, UdfEvalRequest};
+use tokio::runtime::Runtime;
+use tonic::transport::{Channel, Endpoint};
+
+use super::{build_from_prost, BoxedExpression, Expression};
+use crate::{bail, ensure, ExprError, Result};
+
+/// The runtime where the UDF servers are called. Expressions are evaluated synchronously, possibly
+/// on a current-thread runtime, so the calls can't be made on the runtime of the caller.
+static UDF_CLIENT_RUNTIME: LazyLock<std::io::Result<Runtime>> = LazyLock::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .thread_name("risingwave-udf-client")
+        .worker_threads(2)
+        .enable_all()
+        .build()
+});
+
+/// The timeout of the calls to UDF servers, which can be set by the environment variable
+/// `RW_UDF_TIMEOUT_MS`.
+static UDF_TIMEOUT: LazyLock<Duration> = LazyLock::new(|| {
+    let timeout_ms = std::env::var("RW_UDF_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60_000);
+    Duration::from_millis(timeout_ms)
+});
+
+fn udf_client_runtime() -> Result<&'static Runtime> {
+    match &*UDF_CLIENT_RUNTIME {
+        Ok(runtime) => Ok(runtime),
+        Err(e) => bail!("failed to create the runtime of UDF clients: {}", e),
+    }
+}
+
+/// `UdfExpression` evaluates a user-defined function on the external UDF server at `link`. The
+/// arguments of a chunk are sent to the server in a single request, encoded as an Arrow IPC
+/// stream of one record batch, and so is the result.
+#[derive(Debug)]
+pub struct UdfExpression {
+    children: Vec<BoxedExpression>,
+    arg_types: Vec<DataType>,
+    return_type: DataType,
+    name: String,
+    client: UdfServiceClient<Channel>,
+    timeout: Duration,
+}
+
+impl UdfExpression {
+    pub fn new(
+        children: Vec<BoxedExpression>,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+        name: String,
+        link: String,
+    ) -> Result<Self> {
+        // The connection is established on the first evaluation, by the tasks of the channel
+        // spawned on the runtime of UDF clients.
+        let _guard = udf_client_runtime()?.enter();
+        let channel = Endpoint::from_shared(link)
+            .map_err(anyhow::Error::from)?
+            .connect_lazy();
+        Ok(Self {
+            children,
+            arg_types,
+            return_type,
+            name,
+            client: UdfServiceClient::new(channel),
+            timeout: *UDF_TIMEOUT,
+        })
+    }
+
+    /// Calls the UDF server with the arguments, returning the result of the same length.
+    fn call(&self, args: Vec<ArrayRef>, len: usize) -> Result<ArrayImpl> {
+        let fields = self
+            .arg_types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| Ok(Field::new(&format!("arg{}", i), to_arrow_type(t)?, true)))
+            .try_collect::<_, Vec<_>, ExprError>()?;
+        let columns = args.iter().map(|arg| to_arrow_array(arg)).try_collect()?;
+        let input = RecordBatch::try_new_with_options(
+            Arc::new(Schema::new(fields)),
+            columns,
+            &RecordBatchOptions::new().with_row_count(Some(len)),
+        )
+        .map_err(anyhow::Error::from)?;
+
+        let request = UdfEvalRequest {
+            name: self.name.clone(),
+            input: encode_record_batch(&input)?,
+        };
+        let mut client = self.client.clone();
+        // The call is made on the runtime of UDF clients, while the current thread waits for it
+        // until the timeout.
+        let (tx, rx) = mpsc::sync_channel(1);
+        let handle = udf_client_runtime()?.spawn(async move {
+            let _ = tx.send(client.eval(request).await);
+        });
+        let response = match rx.recv_timeout(self.timeout) {
+            Ok(response) => response,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                handle.abort();
+                return Err(ExprError::UdfTimeout {
+                    name: self.name.clone(),
+                    timeout: self.timeout,
+                });
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                bail!("the call to the UDF server is aborted")
+            }
+        };
+        let response = response.map_err(anyhow::Error::from)?.into_inner();
+
+        let output = decode_record_batch(&response.output)?;
+        ensure!(output.num_columns() == 1);
+        ensure!(output.num_rows() == len);
+        from_arrow_array(output.column(0).as_ref(), &self.return_type)
+    }
+}
+
+impl<'a> TryFrom<&'a ExprNode> for UdfExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type()? == Type::Udf);
+        let return_type = DataType::from(prost.get_return_type()?);
+        let RexNode::Udf(udf) = prost.get_rex_node()? else {
+            bail!("Expected RexNode::Udf");
+        };
+        let children = udf.children.iter().map(build_from_prost).try_collect()?;
+        let arg_types = udf.arg_types.iter().map(DataType::from).collect();
+        Self::new(
+            children,
+            arg_types,
+            return_type,
+            udf.name.clone(),
+            udf.link.clone(),
+        )
+    }
+}
+
+impl Expression for UdfExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let args = self
+            .children
+            .iter()
+            .map(|child| child.eval_checked(input))
+            .try_collect()?;
+        Ok(Arc::new(self.call(args, input.capacity())?))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let args = self
+            .children
+            .iter()
+            .map(|child| {
+                let datum = child.eval_row(input)?;
+                let mut builder = child.return_type().create_array_builder(1);
+                builder.append_datum(&datum);
+                Ok(Arc::new(builder.finish()))
+            })
+            .try_collect::<_, Vec<_>, ExprError>()?;
+        let output = self.call(args, 1)?;
+        Ok(output.value_at(0).map(|s| s.into_scalar_impl()))
+    }
+}
+
+fn to_arrow_type(data_type: &DataType) -> Result<ArrowDataType> {
+    Ok(match data_type {
+        DataType::Boolean => ArrowDataType::Boolean,
+        DataType::Int16 => ArrowDataType::Int16,
+        DataType::Int32 => ArrowDataType::Int32,
+        DataType::Int64 => ArrowDataType::Int64,
+        DataType::Float32 => ArrowDataType::Float32,
+        DataType::Float64 => ArrowDataType::Float64,
+        DataType::Varchar => ArrowDataType::Utf8,
+        _ => bail!("unsupported type of user-defined function: {}", data_type),
+    })
+}
+
+fn to_arrow_array(array: &ArrayImpl) -> Result<arrow_array::ArrayRef> {
+    macro_rules! convert {
+        ($arrow_array:ty, $variant:ident, $value:expr) => {{
+            let array: $arrow_array = array
+                .iter()
+                .map(|datum| match datum {
+                    Some(ScalarRefImpl::$variant(v)) => Some($value(v)),
+                    _ => None,
+                })
+                .collect();
+            Arc::new(array) as arrow_array::ArrayRef
+        }};
+    }
+
+    Ok(match array {
+        ArrayImpl::Bool(_) => convert!(BooleanArray, Bool, |v| v),
+        ArrayImpl::Int16(_) => convert!(Int16Array, Int16, |v| v),
+        ArrayImpl::Int32(_) => convert!(Int32Array, Int32, |v| v),
+        ArrayImpl::Int64(_) => convert!(Int64Array, Int64, |v| v),
+        ArrayImpl::Float32(_) => convert!(Float32Array, Float32, |v: OrderedF32| v.0),
+        ArrayImpl::Float64(_) => convert!(Float64Array, Float64, |v: OrderedF64| v.0),
+        ArrayImpl::Utf8(_) => convert!(StringArray, Utf8, |v| v),
+        _ => bail!(
+            "unsupported type of user-defined function: {}",
+            array.get_ident()
+        ),
+    })
+}
+
+fn from_arrow_array(array: &dyn Array, data_type: &DataType) -> Result<ArrayImpl> {
+    macro_rules! convert {
+        ($arrow_array:ty) => {{
+            let Some(array) = array.as_any().downcast_ref::<$arrow_array>() else {
+                bail!("expect {} returned by the UDF server, got {}", data_type, array.data_type());
+            };
+            let mut builder = data_type.create_array_builder(array.len());
+            for value in array.iter() {
+                builder.append_datum(&value.map(ScalarImpl::from));
+            }
+            builder.finish()
+        }};
+    }
+
+    Ok(match data_type {
+        DataType::Boolean => convert!(BooleanArray),
+        DataType::Int16 => convert!(Int16Array),
+        DataType::Int32 => convert!(Int32Array),
+        DataType::Int64 => convert!(Int64Array),
+        DataType::Float32 => convert!(Float32Array),
+        DataType::Float64 => convert!(Float64Array),
+        DataType::Varchar => convert!(StringArray),
+        _ => bail!("unsupported type of user-defined function: {}", data_type),
+    })
+}
+
+/// Encodes a record batch as an Arrow IPC stream.
+pub fn encode_record_batch(batch: &RecordBatch) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    let mut writer =
+        StreamWriter::try_new(&mut buf, &batch.schema()).map_err(anyhow::Error::from)?;
+    writer.write(batch).map_err(anyhow::Error::from)?;
+    writer.finish().map_err(anyhow::Error::from)?;
+    drop(writer);
+    Ok(buf)
+}
+
+/// Decodes the only record batch of an Arrow IPC stream.
+pub fn decode_record_batch(bytes: &[u8]) -> Result<RecordBatch> {
+    let mut reader =
+        StreamReader::try_new(Cursor::new(bytes), None).map_err(anyhow::Error::from)?;
+    match reader.next() {
+        Some(batch) => Ok(batch.map_err(anyhow::Error::from)?),
+        None => bail!("no record batch in the Arrow IPC stream"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::DataChunkTestExt;
+    use risingwave_pb::expr::udf_service_server::{UdfService, UdfServiceServer};
+    use risingwave_pb::expr::UdfEvalResponse;
+    use tonic::{Request, Response, Status};
+
+    use super::*;
+    use crate::expr::InputRefExpression;
+
+    /// A UDF server that serves `gcd(int, int) -> int`, and `sleep(int, int) -> int`, which never
+    /// returns.
+    struct StubUdfServer;
+
+    #[tonic::async_trait]
+    impl UdfService for StubUdfServer {
+        async fn eval(
+            &self,
+            request: Request<UdfEvalRequest>,
+        ) -> std::result::Result<Response<UdfEvalResponse>, Status> {
+            let request = request.into_inner();
+            if request.name == "sleep" {
+                std::future::pending::<()>().await;
+            }
+            assert_eq!(request.name, "gcd");
+            let input = decode_record_batch(&request.input).unwrap();
+            let a = input
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            let b = input
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            let gcd: Int32Array = a
+                .iter()
+                .zip_eq(b.iter())
+                .map(|(a, b)| {
+                    let (mut a, mut b) = (a?, b?);
+                    while b != 0 {
+                        (a, b) = (b, a % b);
+                    }
+                    Some(a)
+                })
+                .collect();
+            let schema = Schema::new(vec![Field::new("gcd", ArrowDataType::Int32, true)]);
+            let output = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(gcd)]).unwrap();
+            Ok(Response::new(UdfEvalResponse {
+                output: encode_record_batch(&output).unwrap(),
+            }))
+        }
+    }
+
+    /// Starts a [`StubUdfServer`], returning its address.
+    async fn start_server() -> String {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        // The server runs on its own runtime, as the runtime of the test is blocked.
+        std::thread::spawn(move || {
+            tokio::runtime::Runtime::new().unwrap().block_on(
+                tonic::transport::Server::builder()
+                    .add_service(UdfServiceServer::new(StubUdfServer))
+                    .serve(addr),
+            )
+        });
+        // Wait for the server to start.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        format!("http://{}", addr)
+    }
+
+    fn create_udf(name: &str, link: String) -> UdfExpression {
+        UdfExpression::new(
+            vec![
+                InputRefExpression::new(DataType::Int32, 0).boxed(),
+                InputRefExpression::new(DataType::Int32, 1).boxed(),
+            ],
+            vec![DataType::Int32, DataType::Int32],
+            DataType::Int32,
+            name.to_string(),
+            link,
+        )
+        .unwrap()
+    }
+
+    /// Evaluates the UDF on a current-thread runtime, which is blocked during the calls.
+    #[tokio::test]
+    async fn test_udf_expression() {
+        let udf = create_udf("gcd", start_server().await);
+
+        let input = DataChunk::from_pretty(
+            "i  i
+             12 18
+             7  .
+             25 15",
+        );
+        let output = udf.eval(&input).unwrap();
+        let expected = DataChunk::from_pretty(
+            "i
+             6
+             .
+             5",
+        );
+        assert_eq!(&*output, expected.column_at(0).array_ref());
+
+        let row = Row::new(vec![Some(ScalarImpl::Int32(9)), Some(ScalarImpl::Int32(6))]);
+        assert_eq!(udf.eval_row(&row).unwrap(), Some(ScalarImpl::Int32(3)));
+    }
+
+    #[tokio::test]
+    async fn test_udf_expression_timeout() {
+        let mut udf = create_udf("sleep", start_server().await);
+        udf.timeout = Duration::from_millis(100);
+
+        let input = DataChunk::from_pretty(
+            "i  i
+             12 18",
+        );
+        assert!(matches!(
+            udf.eval(&input),
+            Err(ExprError::UdfTimeout { name, .. }) if name == "sleep"
+        ));
+    }
+}
@@ -28,6 +28,7 @@ use crate::vector_op::bitwise_op::general_bitnot;
 use crate::vector_op::cast::*;
 use crate::vector_op::cmp::{is_false, is_not_false, is_not_true, is_true};
 use crate::vector_op::conjunction;
+use crate::vector_op::jsonb_info::jsonb_typeof;
 use crate::vector_op::length::{bit_length, length_default, octet_length};
 use crate::vector_op::lower::lower;
 use crate::vector_op::ltrim::ltrim;
@@ -220,6 +221,11 @@ pub fn new_unary_expr(
             return_type,
             md5,
         )),
+        (ProstType::JsonbTypeof, _, _) => Box::new(UnaryBytesExpression::<JsonbArray, _>::new(
+            child_expr,
+            return_type,
+            jsonb_typeof,
+        )),
         (ProstType::Ascii, _, _) => Box::new(UnaryExpression::<Utf8Array, I32Array, _>::new(
             child_expr,
             return_type,
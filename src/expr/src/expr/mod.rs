@@ -32,6 +32,7 @@ mod expr_quaternary_bytes;
 mod expr_regexp;
 mod expr_ternary_bytes;
 mod expr_to_char_const_tmpl;
+mod expr_udf;
 pub mod expr_unary;
 mod expr_vnode;
 mod template;
@@ -57,6 +58,7 @@ use crate::expr::expr_field::FieldExpression;
 use crate::expr::expr_in::InExpression;
 use crate::expr::expr_nested_construct::NestedConstructExpression;
 use crate::expr::expr_regexp::RegexpMatchExpression;
+use crate::expr::expr_udf::UdfExpression;
 use crate::expr::expr_vnode::VnodeExpression;
 use crate::ExprError;
 
@@ -103,14 +105,15 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         // Fixed number of arguments and based on `Unary/Binary/Ternary/...Expression`
         Cast | Upper | Lower | Md5 | Not | IsTrue | IsNotTrue | IsFalse | IsNotFalse | IsNull
         | IsNotNull | Neg | Ascii | Abs | Ceil | Floor | Round | BitwiseNot | CharLength
-        | BoolOut | OctetLength | BitLength | ToTimestamp => build_unary_expr_prost(prost),
+        | BoolOut | OctetLength | BitLength | ToTimestamp | JsonbTypeof => {
+            build_unary_expr_prost(prost)
+        }
         Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Add
         | Subtract | Multiply | Divide | Modulus | Extract | RoundDigit | TumbleStart
         | Position | BitwiseShiftLeft | BitwiseShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor
-        | ConcatOp | AtTimeZone => build_binary_expr_prost(prost),
-        And | Or | IsDistinctFrom | IsNotDistinctFrom | ArrayAccess => {
-            build_nullable_binary_expr_prost(prost)
-        }
+        | ConcatOp | AtTimeZone | JsonbContains | JsonbExists => build_binary_expr_prost(prost),
+        And | Or | IsDistinctFrom | IsNotDistinctFrom | ArrayAccess | JsonbAccessInner
+        | JsonbAccessStr | JsonbAccessPath => build_nullable_binary_expr_prost(prost),
         ToChar => build_to_char_expr(prost),
         Length => build_length_expr(prost),
         Replace => build_replace_expr(prost),
@@ -145,6 +148,7 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
             ArrayConcatExpression::try_from(prost).map(Expression::boxed)
         }
         Vnode => VnodeExpression::try_from(prost).map(Expression::boxed),
+        Udf => UdfExpression::try_from(prost).map(Expression::boxed),
         _ => Err(ExprError::UnsupportedFunction(format!(
             "{:?}",
             prost.get_expr_type()
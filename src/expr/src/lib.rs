@@ -25,11 +25,13 @@
 #![feature(generators)]
 #![feature(iterator_try_collect)]
 #![feature(exclusive_range_pattern)]
+#![feature(once_cell)]
 
 pub mod error;
 pub mod expr;
 pub mod table_function;
 pub mod vector_op;
+pub mod window_function;
 
 pub use error::ExprError;
 pub use risingwave_common::{bail, ensure};
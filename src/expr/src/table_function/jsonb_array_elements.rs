@@ -0,0 +1,97 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::array::{
+    Array, ArrayBuilder, ArrayRef, DataChunk, JsonbArray, JsonbArrayBuilder, JsonbRef,
+};
+
+use super::*;
+use crate::ExprError;
+
+/// Expands the top-level elements of a jsonb array into rows, as `jsonb_array_elements` in
+/// PostgreSQL.
+#[derive(Debug)]
+pub struct JsonbArrayElements {
+    jsonb: BoxedExpression,
+    chunk_size: usize,
+}
+
+impl JsonbArrayElements {
+    fn eval_row(&self, jsonb: JsonbRef<'_>) -> Result<ArrayRef> {
+        let elements = jsonb
+            .array_elements()
+            .ok_or_else(|| ExprError::InvalidParam {
+                name: "jsonb",
+                reason: format!("cannot extract elements from a {}", jsonb.type_name()),
+            })?;
+        let mut builder = JsonbArrayBuilder::new(self.chunk_size);
+        for element in elements {
+            builder.append(Some(element));
+        }
+        Ok(Arc::new(builder.finish().into()))
+    }
+}
+
+impl TableFunction for JsonbArrayElements {
+    fn return_type(&self) -> DataType {
+        DataType::Jsonb
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<Vec<ArrayRef>> {
+        let ret_jsonb = self.jsonb.eval_checked(input)?;
+        let arr_jsonb: &JsonbArray = ret_jsonb.as_ref().into();
+
+        let bitmap = input.get_visibility_ref();
+        let mut output_arrays: Vec<ArrayRef> = vec![];
+
+        match bitmap {
+            Some(bitmap) => {
+                for (jsonb, visible) in arr_jsonb.iter().zip_eq(bitmap.iter()) {
+                    let array = if !visible {
+                        empty_array(self.return_type())
+                    } else if let Some(jsonb) = jsonb {
+                        self.eval_row(jsonb)?
+                    } else {
+                        empty_array(self.return_type())
+                    };
+                    output_arrays.push(array);
+                }
+            }
+            None => {
+                for jsonb in arr_jsonb.iter() {
+                    let array = if let Some(jsonb) = jsonb {
+                        self.eval_row(jsonb)?
+                    } else {
+                        empty_array(self.return_type())
+                    };
+                    output_arrays.push(array);
+                }
+            }
+        }
+
+        Ok(output_arrays)
+    }
+}
+
+pub fn new_jsonb_array_elements(
+    prost: &TableFunctionProst,
+    chunk_size: usize,
+) -> Result<BoxedTableFunction> {
+    let args: Vec<_> = prost.args.iter().map(expr_build_from_prost).try_collect()?;
+    let [jsonb]: [_; 1] = args.try_into().unwrap();
+
+    Ok(JsonbArrayElements { jsonb, chunk_size }.boxed())
+}
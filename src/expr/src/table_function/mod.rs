@@ -32,6 +32,8 @@ mod unnest;
 use unnest::*;
 mod regexp_matches;
 use regexp_matches::*;
+mod jsonb_array_elements;
+use jsonb_array_elements::*;
 
 /// Instance of a table function.
 ///
@@ -63,6 +65,7 @@ pub fn build_from_prost(
         Unnest => new_unnest(prost, chunk_size),
         RegexpMatches => new_regexp_matches(prost, chunk_size),
         Range => new_generate_series::<false>(prost, chunk_size),
+        JsonbArrayElements => new_jsonb_array_elements(prost, chunk_size),
         Unspecified => unreachable!(),
     }
 }
@@ -57,6 +57,7 @@ dyn_clone::clone_trait_object!(Aggregator);
 
 pub type BoxedAggState = Box<dyn Aggregator>;
 
+#[derive(Clone)]
 pub struct AggStateFactory {
     /// Return type of the agg call.
     return_type: DataType,
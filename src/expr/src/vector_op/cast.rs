@@ -20,7 +20,9 @@ use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use itertools::Itertools;
 use num_traits::ToPrimitive;
 use postgres_types::ToSql;
-use risingwave_common::array::{Array, ListRef, ListValue, StructRef, StructValue};
+use risingwave_common::array::{
+    Array, JsonbRef, JsonbVal, ListRef, ListValue, StructRef, StructValue,
+};
 use risingwave_common::types::struct_type::StructType;
 use risingwave_common::types::to_text::ToText;
 use risingwave_common::types::{
@@ -191,6 +193,34 @@ pub fn str_to_bytea(elem: &str) -> Result<Box<[u8]>> {
     }
 }
 
+#[inline(always)]
+pub fn str_to_jsonb(elem: &str) -> Result<JsonbVal> {
+    elem.parse()
+        .map_err(|_| ExprError::Parse("invalid input syntax for type jsonb"))
+}
+
+/// A `jsonb` value can only be cast to a SQL type when it holds a JSON value of the same kind.
+pub fn jsonb_to_bool(v: JsonbRef<'_>) -> Result<bool> {
+    v.value()
+        .as_bool()
+        .ok_or(ExprError::Cast("jsonb", "boolean"))
+}
+
+pub fn jsonb_to_dec(v: JsonbRef<'_>) -> Result<Decimal> {
+    if v.value().is_number() {
+        str_parse(&v.to_string())
+    } else {
+        Err(ExprError::Cast("jsonb", "numeric"))
+    }
+}
+
+pub fn jsonb_to_f64(v: JsonbRef<'_>) -> Result<OrderedF64> {
+    v.value()
+        .as_f64()
+        .map(Into::into)
+        .ok_or(ExprError::Cast("jsonb", "double precision"))
+}
+
 // Refer to Materialize: https://github.com/MaterializeInc/materialize/blob/1766ab3978bc90abf75eb9b1fbadfcc95eca1993/src/repr/src/strconv.rs#L623
 pub fn parse_bytes_hex(s: &str) -> Result<Vec<u8>> {
     // Can't use `hex::decode` here, as it doesn't tolerate whitespace
@@ -435,6 +465,7 @@ macro_rules! for_all_cast_variants {
             { varchar, decimal, str_parse },
             { varchar, boolean, str_to_bool },
             { varchar, bytea, str_to_bytea },
+            { varchar, jsonb, str_to_jsonb },
             // `str_to_list` requires `target_elem_type` and is handled elsewhere
 
             { boolean, varchar, bool_to_varchar },
@@ -450,6 +481,7 @@ macro_rules! for_all_cast_variants {
             { timestamp, varchar, general_to_text },
             { timestampz, varchar, |x| Ok(timestampz_to_utc_string(x)) },
             { list, varchar, |x| general_to_text(x) },
+            { jsonb, varchar, |x| general_to_text(x) },
 
             { boolean, int32, general_cast },
             { int32, boolean, int32_to_bool },
@@ -491,7 +523,11 @@ macro_rules! for_all_cast_variants {
             { time, interval, general_cast },
             { timestamp, date, timestamp_to_date },
             { timestamp, time, timestamp_to_time },
-            { interval, time, interval_to_time }
+            { interval, time, interval_to_time },
+
+            { jsonb, boolean, |x| jsonb_to_bool(x) },
+            { jsonb, decimal, |x| jsonb_to_dec(x) },
+            { jsonb, float64, |x| jsonb_to_f64(x) }
         }
     };
 }
@@ -935,6 +971,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_jsonb_cast() {
+        let v = str_to_jsonb(r#"{"b": 1.5, "a": true}"#).unwrap();
+        assert_eq!(
+            general_to_text(v.as_scalar_ref()).unwrap().as_ref(),
+            r#"{"a": true, "b": 1.5}"#
+        );
+        assert!(str_to_jsonb("{").is_err());
+
+        let a = v.as_scalar_ref().access_object_field("a").unwrap();
+        let b = v.as_scalar_ref().access_object_field("b").unwrap();
+        assert!(jsonb_to_bool(a).unwrap());
+        assert!(jsonb_to_bool(b).is_err());
+        assert_eq!(jsonb_to_dec(b).unwrap(), Decimal::from_f64(1.5).unwrap());
+        assert_eq!(jsonb_to_f64(b).unwrap(), 1.5.into());
+        assert!(jsonb_to_f64(a).is_err());
+    }
+
     #[test]
     fn test_struct_cast() {
         assert_eq!(
@@ -0,0 +1,134 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{JsonbRef, JsonbVal, ListRef};
+use risingwave_common::types::ScalarRef;
+
+use crate::Result;
+
+/// `jsonb -> text`, which returns NULL if the field does not exist.
+#[inline(always)]
+pub fn jsonb_object_field(v: Option<JsonbRef<'_>>, p: Option<&str>) -> Result<Option<JsonbVal>> {
+    Ok(v.zip(p)
+        .and_then(|(v, p)| v.access_object_field(p))
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb -> int`, where a negative index counts from the end of the array.
+#[inline(always)]
+pub fn jsonb_array_element(v: Option<JsonbRef<'_>>, p: Option<i32>) -> Result<Option<JsonbVal>> {
+    Ok(v.zip(p)
+        .and_then(|(v, p)| v.access_array_element(p))
+        .map(|v| v.to_owned_scalar()))
+}
+
+/// `jsonb ->> text`, which returns the field as text.
+#[inline(always)]
+pub fn jsonb_object_field_str(
+    v: Option<JsonbRef<'_>>,
+    p: Option<&str>,
+) -> Result<Option<Box<str>>> {
+    Ok(v.zip(p)
+        .and_then(|(v, p)| v.access_object_field(p))
+        .and_then(|v| v.force_str())
+        .map(String::into_boxed_str))
+}
+
+/// `jsonb ->> int`, which returns the element as text.
+#[inline(always)]
+pub fn jsonb_array_element_str(
+    v: Option<JsonbRef<'_>>,
+    p: Option<i32>,
+) -> Result<Option<Box<str>>> {
+    Ok(v.zip(p)
+        .and_then(|(v, p)| v.access_array_element(p))
+        .and_then(|v| v.force_str())
+        .map(String::into_boxed_str))
+}
+
+/// `jsonb #> text[]`. Returns NULL if any element of the path is NULL or does not exist.
+#[inline(always)]
+pub fn jsonb_access_path(
+    v: Option<JsonbRef<'_>>,
+    p: Option<ListRef<'_>>,
+) -> Result<Option<JsonbVal>> {
+    match (v, p) {
+        (Some(v), Some(p)) => {
+            let path = p
+                .values_ref()
+                .into_iter()
+                .map(|key| key.map(|key| key.into_utf8()))
+                .collect::<Option<Vec<_>>>();
+            Ok(path
+                .and_then(|path| v.access_path(path))
+                .map(|v| v.to_owned_scalar()))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::ListValue;
+    use risingwave_common::types::{Scalar, ScalarImpl};
+
+    use super::*;
+
+    #[test]
+    fn test_jsonb_access() {
+        let v: JsonbVal = r#"{"a": [1, "b", null], "c": {"d": "e"}}"#.parse().unwrap();
+        let v = Some(v.as_scalar_ref());
+
+        let a = jsonb_object_field(v, Some("a")).unwrap().unwrap();
+        assert_eq!(a.to_string(), r#"[1, "b", null]"#);
+        assert_eq!(jsonb_object_field(v, Some("x")).unwrap(), None);
+        assert_eq!(jsonb_object_field(v, None).unwrap(), None);
+
+        let a = Some(a.as_scalar_ref());
+        assert_eq!(
+            jsonb_array_element(a, Some(-3))
+                .unwrap()
+                .unwrap()
+                .to_string(),
+            "1"
+        );
+        assert_eq!(jsonb_array_element(v, Some(0)).unwrap(), None);
+        assert_eq!(
+            jsonb_array_element_str(a, Some(1)).unwrap().as_deref(),
+            Some("b")
+        );
+        assert_eq!(jsonb_array_element_str(a, Some(2)).unwrap(), None);
+        assert_eq!(
+            jsonb_object_field_str(v, Some("c")).unwrap().as_deref(),
+            Some(r#"{"d": "e"}"#)
+        );
+
+        let path = ListValue::new(vec![
+            Some(ScalarImpl::Utf8("c".into())),
+            Some(ScalarImpl::Utf8("d".into())),
+        ]);
+        assert_eq!(
+            jsonb_access_path(v, Some(path.as_scalar_ref()))
+                .unwrap()
+                .unwrap()
+                .to_string(),
+            r#""e""#
+        );
+        let path = ListValue::new(vec![Some(ScalarImpl::Utf8("a".into())), None]);
+        assert_eq!(
+            jsonb_access_path(v, Some(path.as_scalar_ref())).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{JsonbRef, StringWriter, WrittenGuard};
+
+use crate::Result;
+
+#[inline(always)]
+pub fn jsonb_typeof(v: JsonbRef<'_>, writer: StringWriter<'_>) -> Result<WrittenGuard> {
+    Ok(writer.write_ref(v.type_name()))
+}
+
+/// `jsonb @> jsonb`
+#[inline(always)]
+pub fn jsonb_contains(left: JsonbRef<'_>, right: JsonbRef<'_>) -> Result<bool> {
+    Ok(left.contains(right))
+}
+
+/// `jsonb ? text`
+#[inline(always)]
+pub fn jsonb_exists(left: JsonbRef<'_>, key: &str) -> Result<bool> {
+    Ok(left.exists(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{Array, ArrayBuilder, JsonbVal, Utf8ArrayBuilder};
+    use risingwave_common::types::Scalar;
+
+    use super::*;
+
+    #[test]
+    fn test_jsonb_typeof() {
+        let cases = [
+            ("null", "null"),
+            ("true", "boolean"),
+            ("1.5", "number"),
+            (r#""a""#, "string"),
+            ("[1]", "array"),
+            (r#"{"a": 1}"#, "object"),
+        ];
+        for (v, expected) in cases {
+            let v: JsonbVal = v.parse().unwrap();
+            let mut builder = Utf8ArrayBuilder::new(1);
+            let _guard = jsonb_typeof(v.as_scalar_ref(), builder.writer()).unwrap();
+            assert_eq!(builder.finish().value_at(0), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_jsonb_contains_exists() {
+        let l: JsonbVal = r#"{"a": [1, 2], "b": "c"}"#.parse().unwrap();
+        let r: JsonbVal = r#"{"a": [2]}"#.parse().unwrap();
+        assert!(jsonb_contains(l.as_scalar_ref(), r.as_scalar_ref()).unwrap());
+        assert!(!jsonb_contains(r.as_scalar_ref(), l.as_scalar_ref()).unwrap());
+        assert!(jsonb_exists(l.as_scalar_ref(), "b").unwrap());
+        assert!(!jsonb_exists(l.as_scalar_ref(), "c").unwrap());
+    }
+}
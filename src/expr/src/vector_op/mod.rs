@@ -23,6 +23,8 @@ pub mod concat_op;
 pub mod conjunction;
 pub mod date_trunc;
 pub mod extract;
+pub mod jsonb_access;
+pub mod jsonb_info;
 pub mod length;
 pub mod like;
 pub mod lower;
@@ -34,6 +36,7 @@ pub mod repeat;
 pub mod replace;
 pub mod round;
 pub mod rtrim;
+pub mod session;
 pub mod split_part;
 pub mod substr;
 pub mod timestampz;
@@ -0,0 +1,128 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers of the `SESSION` window table function. Two rows belong to the same session if they are
+//! connected by a chain of rows, each within `gap` of the previous one. The session window of a row
+//! starts at the earliest time of its session and ends at the latest time plus `gap`.
+//!
+//! Times are handled as microseconds since the epoch, which preserves their order.
+
+use risingwave_common::types::{
+    DataType, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, ScalarImpl, ScalarRefImpl,
+};
+
+use crate::{ExprError, Result};
+
+/// Returns the session gap in microseconds.
+pub fn session_gap_usecs(gap: IntervalUnit) -> Result<i64> {
+    if gap.get_months() != 0 {
+        return Err(ExprError::InvalidParam {
+            name: "gap",
+            reason: "session gap can only contain days and milliseconds".to_string(),
+        });
+    }
+    let gap_usecs = gap.get_days() as i64 * 24 * 60 * 60 * 1_000_000 + gap.get_ms() * 1000;
+    if gap_usecs <= 0 {
+        return Err(ExprError::InvalidParam {
+            name: "gap",
+            reason: "session gap must be positive".to_string(),
+        });
+    }
+    Ok(gap_usecs)
+}
+
+/// Returns the time of a `timestamp with time zone`, `timestamp` or `date` value in microseconds.
+pub fn time_to_usecs(time: ScalarRefImpl<'_>) -> i64 {
+    match time {
+        ScalarRefImpl::Int64(usecs) => usecs,
+        ScalarRefImpl::NaiveDateTime(datetime) => datetime.0.timestamp_micros(),
+        ScalarRefImpl::NaiveDate(date) => NaiveDateTimeWrapper::from(date).0.timestamp_micros(),
+        _ => unreachable!("unexpected time type of session window: {:?}", time),
+    }
+}
+
+/// Converts a time in microseconds to a bound of the session window, whose type is `window_type`.
+pub fn usecs_to_window_bound(usecs: i64, window_type: &DataType) -> ScalarImpl {
+    match window_type {
+        DataType::Timestampz => ScalarImpl::Int64(usecs),
+        DataType::Timestamp => {
+            ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper::from_timestamp_uncheck(
+                usecs.div_euclid(1_000_000),
+                (usecs.rem_euclid(1_000_000) * 1000) as u32,
+            ))
+        }
+        _ => unreachable!(
+            "unexpected window type of session window: {:?}",
+            window_type
+        ),
+    }
+}
+
+/// Converts a time in microseconds to a value of `time_type`, the type of the time column. A
+/// `date` is the day containing the time.
+pub fn usecs_to_time(usecs: i64, time_type: &DataType) -> ScalarImpl {
+    match time_type {
+        DataType::Date => match usecs_to_window_bound(usecs, &DataType::Timestamp) {
+            ScalarImpl::NaiveDateTime(datetime) => {
+                ScalarImpl::NaiveDate(NaiveDateWrapper::new(datetime.0.date()))
+            }
+            _ => unreachable!(),
+        },
+        _ => usecs_to_window_bound(usecs, time_type),
+    }
+}
+
+/// Assigns the session windows of `times`, which must be sorted in ascending order. Returns the
+/// `[window_start, window_end)` of each time in microseconds.
+pub fn assign_sessions(times: &[i64], gap_usecs: i64) -> Vec<(i64, i64)> {
+    let mut windows = Vec::with_capacity(times.len());
+    let mut start = 0;
+    for i in 1..=times.len() {
+        if i == times.len() || times[i] - times[i - 1] >= gap_usecs {
+            let window = (times[start], times[i - 1] + gap_usecs);
+            windows.extend(std::iter::repeat(window).take(i - start));
+            start = i;
+        }
+    }
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_sessions() {
+        assert_eq!(assign_sessions(&[], 10), vec![]);
+        assert_eq!(
+            assign_sessions(&[1, 5, 5, 14, 24, 40], 10),
+            vec![(1, 24), (1, 24), (1, 24), (1, 24), (24, 34), (40, 50)]
+        );
+    }
+
+    #[test]
+    fn test_usecs_to_window_bound() {
+        let datetime = NaiveDateTimeWrapper::from_timestamp_uncheck(-2, 500_000_000);
+        let usecs = time_to_usecs(ScalarRefImpl::NaiveDateTime(datetime));
+        assert_eq!(usecs, -1_500_000);
+        assert_eq!(
+            usecs_to_window_bound(usecs, &DataType::Timestamp),
+            ScalarImpl::NaiveDateTime(datetime)
+        );
+        assert_eq!(
+            usecs_to_time(usecs, &DataType::Date),
+            ScalarImpl::NaiveDate(NaiveDateWrapper::from_ymd_uncheck(1969, 12, 31))
+        );
+    }
+}
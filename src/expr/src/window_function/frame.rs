@@ -0,0 +1,176 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::bail;
+use risingwave_pb::expr::window_frame::{Bound as ProstBound, BoundType, Type as ProstFrameType};
+use risingwave_pb::expr::WindowFrame as ProstWindowFrame;
+
+use crate::Result;
+
+/// The unit of the frame bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameUnits {
+    /// Offsets are counted in rows.
+    Rows,
+    /// Offsets are counted in peer groups, i.e. rows with equal `ORDER BY` values. Only
+    /// `UNBOUNDED` and `CURRENT ROW` bounds are supported.
+    Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(usize),
+    CurrentRow,
+    Following(usize),
+    UnboundedFollowing,
+}
+
+/// The frame of a window function, e.g. `ROWS BETWEEN 1 PRECEDING AND CURRENT ROW`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Frame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+impl Default for Frame {
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, which covers the whole partition when
+    /// there's no `ORDER BY`.
+    fn default() -> Self {
+        Self {
+            units: FrameUnits::Range,
+            start: FrameBound::UnboundedPreceding,
+            end: FrameBound::CurrentRow,
+        }
+    }
+}
+
+impl Frame {
+    /// A frame of exactly one row, which is `offset` rows before the current row if `offset` is
+    /// negative, or after the current row otherwise. Used for `LAG` and `LEAD`.
+    pub fn single_row(offset: i64) -> Self {
+        let bound = match offset {
+            0 => FrameBound::CurrentRow,
+            o if o < 0 => FrameBound::Preceding(o.unsigned_abs() as usize),
+            o => FrameBound::Following(o as usize),
+        };
+        Self {
+            units: FrameUnits::Rows,
+            start: bound,
+            end: bound,
+        }
+    }
+
+    /// Returns the range `[start, end)` of the frame of the `row`-th row in a partition of `len`
+    /// rows. `peers` is the range of the peer group of the row. The range is empty if `start >=
+    /// end`.
+    pub fn range_of(&self, row: usize, len: usize, peers: (usize, usize)) -> (usize, usize) {
+        let (peer_start, peer_end) = peers;
+        let start = match (self.units, self.start) {
+            (_, FrameBound::UnboundedPreceding) => 0,
+            (_, FrameBound::UnboundedFollowing) => len,
+            (FrameUnits::Rows, FrameBound::Preceding(n)) => row.saturating_sub(n),
+            (FrameUnits::Rows, FrameBound::CurrentRow) => row,
+            (FrameUnits::Rows, FrameBound::Following(n)) => (row + n).min(len),
+            (FrameUnits::Range, _) => peer_start,
+        };
+        let end = match (self.units, self.end) {
+            (_, FrameBound::UnboundedPreceding) => 0,
+            (_, FrameBound::UnboundedFollowing) => len,
+            (FrameUnits::Rows, FrameBound::Preceding(n)) => (row + 1).saturating_sub(n),
+            (FrameUnits::Rows, FrameBound::CurrentRow) => row + 1,
+            (FrameUnits::Rows, FrameBound::Following(n)) => (row + n + 1).min(len),
+            (FrameUnits::Range, _) => peer_end,
+        };
+        (start, end)
+    }
+
+    pub fn from_protobuf(prost: &ProstWindowFrame) -> Result<Self> {
+        let units = match prost.get_type()? {
+            ProstFrameType::Rows => FrameUnits::Rows,
+            ProstFrameType::Range => FrameUnits::Range,
+            ProstFrameType::Unspecified => bail!("unspecified window frame type"),
+        };
+        Ok(Self {
+            units,
+            start: FrameBound::from_protobuf(prost.get_start()?)?,
+            end: FrameBound::from_protobuf(prost.get_end()?)?,
+        })
+    }
+
+    pub fn to_protobuf(&self) -> ProstWindowFrame {
+        let r#type = match self.units {
+            FrameUnits::Rows => ProstFrameType::Rows,
+            FrameUnits::Range => ProstFrameType::Range,
+        };
+        ProstWindowFrame {
+            r#type: r#type as i32,
+            start: Some(self.start.to_protobuf()),
+            end: Some(self.end.to_protobuf()),
+        }
+    }
+}
+
+impl FrameBound {
+    fn from_protobuf(prost: &ProstBound) -> Result<Self> {
+        let offset = prost.offset as usize;
+        Ok(match prost.get_type()? {
+            BoundType::UnboundedPreceding => Self::UnboundedPreceding,
+            BoundType::Preceding => Self::Preceding(offset),
+            BoundType::CurrentRow => Self::CurrentRow,
+            BoundType::Following => Self::Following(offset),
+            BoundType::UnboundedFollowing => Self::UnboundedFollowing,
+            BoundType::Unspecified => bail!("unspecified window frame bound"),
+        })
+    }
+
+    fn to_protobuf(self) -> ProstBound {
+        let (r#type, offset) = match self {
+            Self::UnboundedPreceding => (BoundType::UnboundedPreceding, 0),
+            Self::Preceding(n) => (BoundType::Preceding, n),
+            Self::CurrentRow => (BoundType::CurrentRow, 0),
+            Self::Following(n) => (BoundType::Following, n),
+            Self::UnboundedFollowing => (BoundType::UnboundedFollowing, 0),
+        };
+        ProstBound {
+            r#type: r#type as i32,
+            offset: offset as u64,
+        }
+    }
+}
+
+impl fmt::Display for FrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnboundedPreceding => write!(f, "UNBOUNDED PRECEDING"),
+            Self::Preceding(n) => write!(f, "{} PRECEDING", n),
+            Self::CurrentRow => write!(f, "CURRENT ROW"),
+            Self::Following(n) => write!(f, "{} FOLLOWING", n),
+            Self::UnboundedFollowing => write!(f, "UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = match self.units {
+            FrameUnits::Rows => "ROWS",
+            FrameUnits::Range => "RANGE",
+        };
+        write!(f, "{} BETWEEN {} AND {}", units, self.start, self.end)
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Window functions evaluated by the `OverAgg` executors in batch and streaming.
+
+use risingwave_common::bail;
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::window_function::{GeneralType, Type as ProstWindowFuncType};
+use risingwave_pb::expr::{AggCall as ProstAggCall, WindowFunction as ProstWindowFunction};
+
+use crate::expr::AggKind;
+use crate::vector_op::agg::AggStateFactory;
+use crate::Result;
+
+mod frame;
+pub use frame::*;
+mod partition;
+pub use partition::*;
+
+/// Kind of window function. `LAG` and `LEAD` are planned as [`WindowFuncKind::FirstValue`] over a
+/// single-row frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowFuncKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+    FirstValue,
+    LastValue,
+    Aggregate(AggKind),
+}
+
+/// A window function call, whose arguments refer to the columns of the input.
+pub struct WindowFuncCall {
+    pub kind: WindowFuncKind,
+    pub args: Vec<(usize, DataType)>,
+    pub return_type: DataType,
+    pub frame: Frame,
+    /// Creates the states of [`WindowFuncKind::Aggregate`].
+    agg_state_factory: Option<AggStateFactory>,
+}
+
+impl WindowFuncCall {
+    pub fn from_protobuf(prost: &ProstWindowFunction) -> Result<Self> {
+        let kind = match prost.r#type.as_ref() {
+            Some(ProstWindowFuncType::General(general)) => {
+                match GeneralType::from_i32(*general).unwrap_or(GeneralType::Unspecified) {
+                    GeneralType::RowNumber => WindowFuncKind::RowNumber,
+                    GeneralType::Rank => WindowFuncKind::Rank,
+                    GeneralType::DenseRank => WindowFuncKind::DenseRank,
+                    GeneralType::FirstValue => WindowFuncKind::FirstValue,
+                    GeneralType::LastValue => WindowFuncKind::LastValue,
+                    GeneralType::Unspecified => bail!("unrecognized window function"),
+                }
+            }
+            Some(ProstWindowFuncType::Aggregate(agg_type)) => {
+                let agg_type = risingwave_pb::expr::agg_call::Type::from_i32(*agg_type)
+                    .unwrap_or(risingwave_pb::expr::agg_call::Type::Unspecified);
+                WindowFuncKind::Aggregate(AggKind::try_from(agg_type)?)
+            }
+            None => bail!("window function type not set"),
+        };
+        let args = prost
+            .args
+            .iter()
+            .map(|arg| {
+                Ok((
+                    arg.get_input()?.column_idx as usize,
+                    DataType::from(arg.get_type()?),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let agg_state_factory = match kind {
+            WindowFuncKind::Aggregate(agg_kind) => Some(AggStateFactory::new(&ProstAggCall {
+                r#type: agg_kind.to_prost() as i32,
+                args: prost.args.clone(),
+                return_type: prost.return_type.clone(),
+                distinct: false,
+                order_by_fields: vec![],
+                filter: None,
+            })?),
+            _ => None,
+        };
+        Ok(Self {
+            kind,
+            args,
+            return_type: DataType::from(prost.get_return_type()?),
+            frame: Frame::from_protobuf(prost.get_frame()?)?,
+            agg_state_factory,
+        })
+    }
+}
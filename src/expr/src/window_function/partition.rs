@@ -0,0 +1,404 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::{Range, RangeInclusive};
+
+use risingwave_common::array::{ArrayBuilderImpl, ArrayImpl, DataChunk};
+use risingwave_common::types::ScalarImpl;
+
+use super::{FrameBound, FrameUnits, WindowFuncCall, WindowFuncKind};
+use crate::Result;
+
+/// Evaluates the window function `calls` over a whole partition, returning one array for each
+/// call. The rows of `partition` must be compacted and sorted by the `ORDER BY` of the window,
+/// whose columns are `order_by`.
+pub fn evaluate_partition(
+    calls: &[WindowFuncCall],
+    order_by: &[usize],
+    partition: &DataChunk,
+) -> Result<Vec<ArrayImpl>> {
+    evaluate_partition_rows(calls, order_by, partition, 0..partition.capacity())
+}
+
+/// Same as [`evaluate_partition`], but only evaluates the window functions for `rows`, whose
+/// frames still cover the whole partition. The arrays returned have one element for each row.
+pub fn evaluate_partition_rows(
+    calls: &[WindowFuncCall],
+    order_by: &[usize],
+    partition: &DataChunk,
+    rows: Range<usize>,
+) -> Result<Vec<ArrayImpl>> {
+    let peers = peer_groups(order_by, partition);
+    calls
+        .iter()
+        .map(|call| {
+            let mut builder = call.return_type.create_array_builder(rows.len());
+            evaluate_call(call, partition, &peers, rows.clone(), &mut builder)?;
+            Ok(builder.finish())
+        })
+        .collect()
+}
+
+/// Returns the rows of `partition` whose outputs of `calls` may have changed after rows are
+/// inserted into or deleted from it. `changes` are the positions of the changes in `partition`,
+/// which has the changes applied, where the position of a deleted row is the index of the row
+/// after it. The outputs of the rows out of the range remain the same.
+pub fn affected_rows(
+    calls: &[WindowFuncCall],
+    order_by: &[usize],
+    partition: &DataChunk,
+    changes: RangeInclusive<usize>,
+) -> Range<usize> {
+    let len = partition.capacity();
+    let (first, last) = changes.into_inner();
+    let peers = peer_groups(order_by, partition);
+    // The inserted rows are always evaluated.
+    let mut start = first.min(len);
+    let mut end = (last + 1).min(len);
+    for call in calls {
+        let (call_start, call_end) = match call.kind {
+            // The numbering of all rows after the changes shifts.
+            WindowFuncKind::RowNumber | WindowFuncKind::Rank | WindowFuncKind::DenseRank => {
+                (first, len)
+            }
+            // The frames move forward with the current row, so the rows whose frames overlap with
+            // the changes are adjacent. Other rows see the same rows in their frames as before.
+            WindowFuncKind::FirstValue
+            | WindowFuncKind::LastValue
+            | WindowFuncKind::Aggregate(_) => {
+                let frame_of = |row: usize| call.frame.range_of(row, len, peers[row]);
+                let call_start = (0..len)
+                    .find(|&row| frame_of(row).1 >= first)
+                    .unwrap_or(len);
+                let call_end = (0..len)
+                    .rfind(|&row| frame_of(row).0 <= last)
+                    .map_or(0, |row| row + 1);
+                (call_start, call_end)
+            }
+        };
+        start = start.min(call_start);
+        end = end.max(call_end);
+    }
+    start..end.max(start)
+}
+
+/// Returns how many rows before the first change and after the last change of a partition are
+/// needed to evaluate `calls` again, i.e. the rows whose frames may overlap with the changes and
+/// the rows in their frames. Returns `None` if the whole partition is needed, as some function
+/// numbers the rows, or some frame is unbounded or made of peer groups.
+pub fn rows_around_changes(calls: &[WindowFuncCall]) -> Option<usize> {
+    let mut rows = 0;
+    for call in calls {
+        match call.kind {
+            WindowFuncKind::RowNumber | WindowFuncKind::Rank | WindowFuncKind::DenseRank => {
+                return None
+            }
+            WindowFuncKind::FirstValue
+            | WindowFuncKind::LastValue
+            | WindowFuncKind::Aggregate(_) => {
+                if call.frame.units != FrameUnits::Rows {
+                    return None;
+                }
+                // How far the frame reaches before and after the current row.
+                let reach = |bound: FrameBound| match bound {
+                    FrameBound::Preceding(n) => Some((n, 0)),
+                    FrameBound::CurrentRow => Some((0, 0)),
+                    FrameBound::Following(n) => Some((0, n)),
+                    FrameBound::UnboundedPreceding | FrameBound::UnboundedFollowing => None,
+                };
+                let (preceding, _) = reach(call.frame.start)?;
+                let (_, following) = reach(call.frame.end)?;
+                // The rows affected are at most `following` rows before the first change and
+                // `preceding` rows after the last change, and their frames reach as far again.
+                rows = rows.max(preceding + following);
+            }
+        }
+    }
+    Some(rows)
+}
+
+/// Returns the range of the peer group of each row, i.e. the adjacent rows with equal `order_by`
+/// values. Without `ORDER BY`, all rows of the partition are peers.
+fn peer_groups(order_by: &[usize], partition: &DataChunk) -> Vec<(usize, usize)> {
+    let len = partition.capacity();
+    let mut peers = Vec::with_capacity(len);
+    let mut start = 0;
+    for row in 1..=len {
+        let is_peer = row < len
+            && order_by.iter().all(|&idx| {
+                let array = partition.column_at(idx).array_ref();
+                array.value_at(row) == array.value_at(row - 1)
+            });
+        if !is_peer {
+            peers.extend(std::iter::repeat((start, row)).take(row - start));
+            start = row;
+        }
+    }
+    peers
+}
+
+fn evaluate_call(
+    call: &WindowFuncCall,
+    partition: &DataChunk,
+    peers: &[(usize, usize)],
+    rows: Range<usize>,
+    builder: &mut ArrayBuilderImpl,
+) -> Result<()> {
+    let len = peers.len();
+    match call.kind {
+        WindowFuncKind::RowNumber => {
+            for row in rows {
+                builder.append_datum(Some(ScalarImpl::Int64(row as i64 + 1)));
+            }
+        }
+        WindowFuncKind::Rank => {
+            for &(peer_start, _) in &peers[rows] {
+                builder.append_datum(Some(ScalarImpl::Int64(peer_start as i64 + 1)));
+            }
+        }
+        WindowFuncKind::DenseRank => {
+            // The number of peer groups before the first row to evaluate.
+            let mut rank = (0..rows.start).filter(|&row| peers[row].0 == row).count() as i64;
+            for row in rows {
+                if row == peers[row].0 {
+                    rank += 1;
+                }
+                builder.append_datum(Some(ScalarImpl::Int64(rank)));
+            }
+        }
+        WindowFuncKind::FirstValue | WindowFuncKind::LastValue => {
+            let array = partition.column_at(call.args[0].0).array_ref();
+            for row in rows {
+                let (start, end) = call.frame.range_of(row, len, peers[row]);
+                if start >= end {
+                    builder.append_null();
+                } else if call.kind == WindowFuncKind::FirstValue {
+                    builder.append_datum(array.value_at(start));
+                } else {
+                    builder.append_datum(array.value_at(end - 1));
+                }
+            }
+        }
+        WindowFuncKind::Aggregate(_) => {
+            let factory = call.agg_state_factory.as_ref().unwrap();
+            if call.frame.start == FrameBound::UnboundedPreceding {
+                // The frames only grow as the current row moves forward, so the rows can be
+                // accumulated into a single state.
+                let mut state = factory.create_agg_state();
+                let mut accumulated = 0;
+                for row in rows {
+                    let (_, end) = call.frame.range_of(row, len, peers[row]);
+                    if end > accumulated {
+                        state.update_multi(partition, accumulated, end)?;
+                        accumulated = end;
+                    }
+                    // `output` resets the state, so output a copy of it.
+                    state.clone().output(builder)?;
+                }
+            } else {
+                for row in rows {
+                    let (start, end) = call.frame.range_of(row, len, peers[row]);
+                    let mut state = factory.create_agg_state();
+                    if start < end {
+                        state.update_multi(partition, start, end)?;
+                    }
+                    state.output(builder)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use risingwave_common::array::{Column, DataChunkTestExt};
+    use risingwave_common::types::DataType;
+    use risingwave_pb::expr::agg_call::{Arg, Type as AggType};
+    use risingwave_pb::expr::window_function::{GeneralType, Type as WindowFuncType};
+    use risingwave_pb::expr::{InputRefExpr, WindowFunction};
+
+    use super::*;
+    use crate::window_function::{Frame, FrameUnits};
+
+    fn call(r#type: WindowFuncType, args: &[usize], frame: Frame) -> WindowFuncCall {
+        WindowFuncCall::from_protobuf(&WindowFunction {
+            r#type: Some(r#type),
+            args: args
+                .iter()
+                .map(|&idx| Arg {
+                    input: Some(InputRefExpr {
+                        column_idx: idx as i32,
+                    }),
+                    r#type: Some(DataType::Int64.to_protobuf()),
+                })
+                .collect(),
+            return_type: Some(DataType::Int64.to_protobuf()),
+            frame: Some(frame.to_protobuf()),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_partition() {
+        let partition = DataChunk::from_pretty(
+            "I I
+             1 10
+             2 20
+             2 30
+             3 40",
+        );
+        let calls = [
+            call(
+                WindowFuncType::General(GeneralType::Rank as i32),
+                &[],
+                Frame::default(),
+            ),
+            call(
+                WindowFuncType::General(GeneralType::DenseRank as i32),
+                &[],
+                Frame::default(),
+            ),
+            // LAG(v, 1)
+            call(
+                WindowFuncType::General(GeneralType::FirstValue as i32),
+                &[1],
+                Frame::single_row(-1),
+            ),
+            // LEAD(v, 1)
+            call(
+                WindowFuncType::General(GeneralType::FirstValue as i32),
+                &[1],
+                Frame::single_row(1),
+            ),
+            call(
+                WindowFuncType::Aggregate(AggType::Sum as i32),
+                &[1],
+                Frame::default(),
+            ),
+            call(
+                WindowFuncType::Aggregate(AggType::Count as i32),
+                &[1],
+                Frame {
+                    units: FrameUnits::Rows,
+                    start: FrameBound::Preceding(1),
+                    end: FrameBound::Following(1),
+                },
+            ),
+            call(
+                WindowFuncType::General(GeneralType::LastValue as i32),
+                &[1],
+                Frame {
+                    units: FrameUnits::Rows,
+                    start: FrameBound::CurrentRow,
+                    end: FrameBound::UnboundedFollowing,
+                },
+            ),
+        ];
+        let outputs = evaluate_partition(&calls, &[0], &partition).unwrap();
+        let outputs = DataChunk::new(
+            outputs
+                .into_iter()
+                .map(|array| Column::new(array.into()))
+                .collect(),
+            partition.capacity(),
+        );
+        assert_eq!(
+            outputs,
+            DataChunk::from_pretty(
+                "I I I  I  I   I I
+                 1 1 .  20 10  2 40
+                 2 2 10 30 60  3 40
+                 2 2 20 40 60  3 40
+                 4 3 30 .  100 2 40",
+            )
+        );
+    }
+
+    #[test]
+    fn test_evaluate_partition_rows() {
+        let partition = DataChunk::from_pretty(
+            "I I
+             1 10
+             2 20
+             2 30
+             3 40
+             4 50",
+        );
+        let calls = [
+            // LAG(v, 1)
+            call(
+                WindowFuncType::General(GeneralType::FirstValue as i32),
+                &[1],
+                Frame::single_row(-1),
+            ),
+            call(
+                WindowFuncType::General(GeneralType::DenseRank as i32),
+                &[],
+                Frame::default(),
+            ),
+            call(
+                WindowFuncType::Aggregate(AggType::Sum as i32),
+                &[1],
+                Frame {
+                    units: FrameUnits::Rows,
+                    start: FrameBound::Preceding(1),
+                    end: FrameBound::CurrentRow,
+                },
+            ),
+        ];
+
+        // The row at index 2 is inserted, so the row after it also has a different lag.
+        assert_eq!(affected_rows(&calls[0..1], &[0], &partition, 2..=2), 2..4);
+        // A row is deleted before the row at index 2. The rows whose frames are next to it are
+        // evaluated as well.
+        assert_eq!(affected_rows(&calls[2..3], &[0], &partition, 2..=2), 1..4);
+        // Ranks of all rows after the changes may shift.
+        assert_eq!(affected_rows(&calls[1..2], &[0], &partition, 3..=3), 3..5);
+
+        assert_eq!(rows_around_changes(&calls[0..1]), Some(1));
+        assert_eq!(rows_around_changes(&calls[2..3]), Some(1));
+        assert_eq!(rows_around_changes(&calls[1..2]), None);
+        assert_eq!(
+            rows_around_changes(&[call(
+                WindowFuncType::Aggregate(AggType::Count as i32),
+                &[1],
+                Frame {
+                    units: FrameUnits::Rows,
+                    start: FrameBound::Preceding(2),
+                    end: FrameBound::Following(1),
+                },
+            )]),
+            Some(3)
+        );
+        assert_eq!(
+            rows_around_changes(&[call(
+                WindowFuncType::Aggregate(AggType::Sum as i32),
+                &[1],
+                Frame::default(),
+            )]),
+            None
+        );
+
+        let all = evaluate_partition(&calls, &[0], &partition).unwrap();
+        let rows = evaluate_partition_rows(&calls, &[0], &partition, 2..4).unwrap();
+        for (all, rows) in all.iter().zip_eq(rows.iter()) {
+            assert_eq!(rows.len(), 2);
+            for row in 0..2 {
+                assert_eq!(rows.value_at(row), all.value_at(row + 2));
+            }
+        }
+    }
+}
@@ -0,0 +1,512 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use itertools::Itertools;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+
+use super::{Binder, BoundQuery, BoundSelect, BoundSetExpr, BoundStatement, Relation};
+use crate::expr::{ExprImpl, ExprRewriter, Literal, Parameter, Subquery};
+
+impl Binder {
+    pub(super) fn bind_parameter(&mut self, index: u64) -> Result<ExprImpl> {
+        match &self.param_types {
+            Some(param_types) if index > 0 => Ok(Parameter::new(index, param_types.clone()).into()),
+            _ => Err(ErrorCode::BindError(format!("there is no parameter ${}", index)).into()),
+        }
+    }
+}
+
+/// Decodes the parameter values provided in the `Bind` message of the extended query protocol.
+/// `param_types` are exported by the binder of the statement, and `params` are in text format
+/// unless `binary` is set. `None` stands for a NULL value.
+pub fn parse_parameters(
+    param_types: &[DataType],
+    params: &[Option<Bytes>],
+    binary: bool,
+) -> Result<Vec<Literal>> {
+    if params.len() != param_types.len() {
+        return Err(ErrorCode::BindError(format!(
+            "bind message supplies {} parameters, but prepared statement requires {}",
+            params.len(),
+            param_types.len()
+        ))
+        .into());
+    }
+    params
+        .iter()
+        .zip_eq(param_types)
+        .map(|(param, data_type)| {
+            let datum = match param {
+                Some(raw) => parse_param(raw, data_type, binary)?,
+                None => None,
+            };
+            Ok(Literal::new(datum, data_type.clone()))
+        })
+        .collect()
+}
+
+impl BoundStatement {
+    /// Replaces the [`Parameter`]s with `params`, the values decoded by [`parse_parameters`].
+    pub fn bind_parameter(mut self, params: &[Literal]) -> BoundStatement {
+        let mut rewriter = ParamRewriter {
+            params: params.to_vec(),
+        };
+        rewriter.rewrite_statement(&mut self);
+        self
+    }
+}
+
+fn parse_param(raw: &[u8], data_type: &DataType, binary: bool) -> Result<Datum> {
+    if binary {
+        return ScalarImpl::from_binary(raw, data_type).map(Some);
+    }
+    let text = std::str::from_utf8(raw).map_err(|e| {
+        ErrorCode::InvalidInputSyntax(format!("invalid UTF-8 in parameter value: {}", e))
+    })?;
+    // A text parameter is parsed just like a string literal cast to the parameter type.
+    ExprImpl::literal_varchar(text.to_string())
+        .cast_explicit(data_type.clone())?
+        .eval_row_const()
+}
+
+/// Rewrites every [`Parameter`] in a bound statement, including the ones in subqueries, to the
+/// literal of its value.
+struct ParamRewriter {
+    /// The values of parameters, where `params[i]` is the value of `$(i + 1)`.
+    params: Vec<Literal>,
+}
+
+impl ExprRewriter for ParamRewriter {
+    fn rewrite_parameter(&mut self, parameter: Parameter) -> ExprImpl {
+        self.params[parameter.index as usize - 1].clone().into()
+    }
+
+    fn rewrite_subquery(&mut self, mut subquery: Subquery) -> ExprImpl {
+        self.rewrite_query(&mut subquery.query);
+        subquery.into()
+    }
+}
+
+impl ParamRewriter {
+    fn rewrite_in_place(&mut self, expr: &mut ExprImpl) {
+        let owned = std::mem::replace(expr, ExprImpl::literal_bool(true));
+        *expr = self.rewrite_expr(owned);
+    }
+
+    fn rewrite_statement(&mut self, stmt: &mut BoundStatement) {
+        match stmt {
+            BoundStatement::Insert(insert) => {
+                self.rewrite_query(&mut insert.source);
+                insert
+                    .cast_exprs
+                    .iter_mut()
+                    .for_each(|expr| self.rewrite_in_place(expr));
+            }
+            BoundStatement::Delete(delete) => {
+                delete
+                    .selection
+                    .iter_mut()
+                    .for_each(|expr| self.rewrite_in_place(expr));
+            }
+            BoundStatement::Update(update) => {
+                self.rewrite_relation(&mut update.table);
+                update
+                    .selection
+                    .iter_mut()
+                    .chain(update.exprs.iter_mut())
+                    .for_each(|expr| self.rewrite_in_place(expr));
+            }
+            BoundStatement::Query(query) => self.rewrite_query(query),
+        }
+    }
+
+    fn rewrite_query(&mut self, query: &mut BoundQuery) {
+        self.rewrite_set_expr(&mut query.body);
+        query
+            .extra_order_exprs
+            .iter_mut()
+            .for_each(|expr| self.rewrite_in_place(expr));
+    }
+
+    fn rewrite_set_expr(&mut self, set_expr: &mut BoundSetExpr) {
+        match set_expr {
+            BoundSetExpr::Select(select) => self.rewrite_select(select),
+            BoundSetExpr::Values(values) => values
+                .exprs_mut()
+                .for_each(|expr| self.rewrite_in_place(expr)),
+            BoundSetExpr::Query(query) => self.rewrite_query(query),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                self.rewrite_set_expr(left);
+                self.rewrite_set_expr(right);
+            }
+        }
+    }
+
+    fn rewrite_select(&mut self, select: &mut BoundSelect) {
+        if let Some(from) = &mut select.from {
+            self.rewrite_relation(from);
+        }
+        select
+            .exprs_mut()
+            .for_each(|expr| self.rewrite_in_place(expr));
+    }
+
+    fn rewrite_relation(&mut self, relation: &mut Relation) {
+        match relation {
+            Relation::Subquery(subquery) => self.rewrite_query(&mut subquery.query),
+            Relation::Join(join) => {
+                self.rewrite_relation(&mut join.left);
+                self.rewrite_relation(&mut join.right);
+                self.rewrite_in_place(&mut join.cond);
+            }
+            Relation::WindowTableFunction(window) => {
+                self.rewrite_relation(&mut window.input);
+                window
+                    .args
+                    .iter_mut()
+                    .for_each(|expr| self.rewrite_in_place(expr));
+            }
+            Relation::TableFunction(table_function) => table_function
+                .args
+                .iter_mut()
+                .for_each(|expr| self.rewrite_in_place(expr)),
+            Relation::Source(_) | Relation::BaseTable(_) | Relation::SystemTable(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use postgres_types::{ToSql, Type};
+    use risingwave_common::types::to_binary::ToBinary;
+    use risingwave_common::types::{
+        Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, Scalar,
+    };
+    use risingwave_sqlparser::ast::Statement;
+    use risingwave_sqlparser::parser::Parser;
+
+    use super::*;
+    use crate::binder::test_utils::{mock_binder, mock_binder_with_param_types};
+    use crate::expr::Expr;
+
+    fn parse(sql: &str) -> Statement {
+        Parser::parse_sql(sql).unwrap().swap_remove(0)
+    }
+
+    fn select_items(query: &BoundQuery) -> Vec<ExprImpl> {
+        match &query.body {
+            BoundSetExpr::Select(select) => select.select_items.clone(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn stmt_select_items(bound: &BoundStatement) -> Vec<ExprImpl> {
+        match bound {
+            BoundStatement::Query(query) => select_items(query),
+            _ => unreachable!(),
+        }
+    }
+
+    fn literals(values: Vec<ScalarImpl>, types: &[DataType]) -> Vec<Literal> {
+        values
+            .into_iter()
+            .zip_eq(types)
+            .map(|(value, data_type)| Literal::new(Some(value), data_type.clone()))
+            .collect()
+    }
+
+    fn to_sql(value: impl ToSql) -> Option<Bytes> {
+        let mut raw = BytesMut::new();
+        value.to_sql(&Type::ANY, &mut raw).unwrap();
+        Some(raw.freeze())
+    }
+
+    #[tokio::test]
+    async fn test_bind_param() {
+        let mut binder = mock_binder_with_param_types(vec![None, Some(DataType::Int64)]);
+        let bound = binder
+            .bind(parse("SELECT $1 + 1, $2, $3, (SELECT $3)"))
+            .unwrap();
+        let param_types = binder.export_param_types();
+        assert_eq!(
+            param_types,
+            vec![DataType::Int32, DataType::Int64, DataType::Varchar]
+        );
+
+        let params = parse_parameters(
+            &param_types,
+            &[Some(Bytes::from("2")), None, Some(Bytes::from("it's"))],
+            false,
+        )
+        .unwrap();
+        let bound = bound.bind_parameter(&params);
+        let items = stmt_select_items(&bound);
+        assert_eq!(
+            items[0].as_function_call().unwrap().inputs()[0],
+            Literal::new(Some(ScalarImpl::Int32(2)), DataType::Int32).into()
+        );
+        assert_eq!(items[1], ExprImpl::literal_null(DataType::Int64));
+        assert_eq!(items[2], ExprImpl::literal_varchar("it's".to_string()));
+        let subquery = items[3].as_subquery().unwrap();
+        assert_eq!(
+            select_items(&subquery.query)[0],
+            ExprImpl::literal_varchar("it's".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bind_param_binary() {
+        let mut binder = mock_binder_with_param_types(vec![Some(DataType::Int64)]);
+        let bound = binder.bind(parse("SELECT $1")).unwrap();
+        let params = parse_parameters(
+            &binder.export_param_types(),
+            &[Some(Bytes::from(42i64.to_be_bytes().to_vec()))],
+            true,
+        )
+        .unwrap();
+        let items = stmt_select_items(&bound.bind_parameter(&params));
+        assert_eq!(items[0].return_type(), DataType::Int64);
+        assert_eq!(
+            items[0],
+            Literal::new(Some(ScalarImpl::Int64(42)), DataType::Int64).into()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bind_param_error() {
+        let mut binder = mock_binder_with_param_types(vec![Some(DataType::Int32)]);
+        binder.bind(parse("SELECT $1")).unwrap();
+        let param_types = binder.export_param_types();
+        assert!(parse_parameters(&param_types, &[], false).is_err());
+        assert!(parse_parameters(&param_types, &[Some(Bytes::from("abc"))], false).is_err());
+
+        // Parameters are only allowed in prepared statements.
+        let mut binder = mock_binder();
+        assert!(binder.bind(parse("SELECT $1")).is_err());
+
+        // A parameter can't be inferred as two different types.
+        let mut binder = mock_binder_with_param_types(vec![]);
+        assert!(binder.bind(parse("SELECT $1::int, $1::varchar")).is_err());
+    }
+
+    #[test]
+    fn test_parse_params_text() {
+        let types = vec![DataType::Varchar; 3];
+        let params = parse_parameters(
+            &types,
+            &[Some("A".into()), Some("B".into()), Some("C".into())],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    ScalarImpl::Utf8("A".into()),
+                    ScalarImpl::Utf8("B".into()),
+                    ScalarImpl::Utf8("C".into())
+                ],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Boolean; 2];
+        let params =
+            parse_parameters(&types, &[Some("false".into()), Some("true".into())], false).unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![ScalarImpl::Bool(false), ScalarImpl::Bool(true)],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Int16, DataType::Int32, DataType::Int64];
+        let params = parse_parameters(
+            &types,
+            &[Some("1".into()), Some("2".into()), Some("3".into())],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    ScalarImpl::Int16(1),
+                    ScalarImpl::Int32(2),
+                    ScalarImpl::Int64(3)
+                ],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Float32, DataType::Float64, DataType::Decimal];
+        let params = parse_parameters(
+            &types,
+            &[Some("1.0".into()), Some("2.0".into()), Some("3".into())],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    ScalarImpl::Float32(1.0_f32.into()),
+                    ScalarImpl::Float64(2.0_f64.into()),
+                    ScalarImpl::Decimal("3".parse().unwrap())
+                ],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Date, DataType::Time, DataType::Timestamp];
+        let params = parse_parameters(
+            &types,
+            &[
+                Some("2021-01-01".into()),
+                Some("12:00:00".into()),
+                Some("2021-01-07 06:13:20".into()),
+            ],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    NaiveDateWrapper::from_ymd_uncheck(2021, 1, 1).to_scalar_value(),
+                    NaiveTimeWrapper::from_hms_uncheck(12, 0, 0).to_scalar_value(),
+                    NaiveDateTimeWrapper::from_timestamp_uncheck(1610000000, 0).to_scalar_value()
+                ],
+                &types
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_params_binary() {
+        let types = vec![DataType::Varchar; 3];
+        let params =
+            parse_parameters(&types, &[to_sql("A"), to_sql("B"), to_sql("C")], true).unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    ScalarImpl::Utf8("A".into()),
+                    ScalarImpl::Utf8("B".into()),
+                    ScalarImpl::Utf8("C".into())
+                ],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Boolean; 2];
+        let params = parse_parameters(&types, &[to_sql(false), to_sql(true)], true).unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![ScalarImpl::Bool(false), ScalarImpl::Bool(true)],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Int16, DataType::Int32, DataType::Int64];
+        let params =
+            parse_parameters(&types, &[to_sql(1_i16), to_sql(2_i32), to_sql(3_i64)], true).unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    ScalarImpl::Int16(1),
+                    ScalarImpl::Int32(2),
+                    ScalarImpl::Int64(3)
+                ],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Float32, DataType::Float64, DataType::Decimal];
+        let decimal: Decimal = "3".parse().unwrap();
+        let raw = [
+            to_sql(1.0_f32),
+            to_sql(2.0_f64),
+            decimal.to_binary().unwrap(),
+        ];
+        let params = parse_parameters(&types, &raw, true).unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    ScalarImpl::Float32(1.0_f32.into()),
+                    ScalarImpl::Float64(2.0_f64.into()),
+                    ScalarImpl::Decimal("3".parse().unwrap())
+                ],
+                &types
+            )
+        );
+
+        let types = vec![DataType::Float32, DataType::Float64, DataType::Float64];
+        let params = parse_parameters(
+            &types,
+            &[
+                to_sql(f32::NAN),
+                to_sql(f64::INFINITY),
+                to_sql(f64::NEG_INFINITY),
+            ],
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    ScalarImpl::Float32(f32::NAN.into()),
+                    ScalarImpl::Float64(f64::INFINITY.into()),
+                    ScalarImpl::Float64(f64::NEG_INFINITY.into())
+                ],
+                &types
+            )
+        );
+
+        let date = NaiveDateWrapper::from_ymd_uncheck(2021, 1, 1);
+        let time = NaiveTimeWrapper::from_hms_uncheck(12, 0, 0);
+        let timestamp = NaiveDateTimeWrapper::from_timestamp_uncheck(1610000000, 0);
+        let types = vec![DataType::Date, DataType::Time, DataType::Timestamp];
+        let params = parse_parameters(
+            &types,
+            &[to_sql(date.0), to_sql(time.0), to_sql(timestamp.0)],
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            literals(
+                vec![
+                    date.to_scalar_value(),
+                    time.to_scalar_value(),
+                    timestamp.to_scalar_value()
+                ],
+                &types
+            )
+        );
+
+        let interval = IntervalUnit::new(1, 1, 24000);
+        let types = vec![DataType::Interval];
+        let params = parse_parameters(&types, &[to_sql(interval)], true).unwrap();
+        assert_eq!(params, literals(vec![interval.to_scalar_value()], &types));
+    }
+}
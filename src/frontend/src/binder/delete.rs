@@ -18,7 +18,7 @@ use risingwave_sqlparser::ast::{Expr, ObjectName};
 use super::{Binder, BoundBaseTable, BoundTableSource};
 use crate::expr::ExprImpl;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BoundDelete {
     /// Used for injecting deletion chunks to the source.
     pub table_source: BoundTableSource,
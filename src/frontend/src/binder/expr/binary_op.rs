@@ -54,6 +54,11 @@ impl Binder {
             BinaryOperator::PGRegexNotMatch => {
                 return self.bind_regex_not_match(bound_left, bound_right)
             }
+            BinaryOperator::Arrow => ExprType::JsonbAccessInner,
+            BinaryOperator::LongArrow => ExprType::JsonbAccessStr,
+            BinaryOperator::HashArrow => ExprType::JsonbAccessPath,
+            BinaryOperator::AtArrow => ExprType::JsonbContains,
+            BinaryOperator::QuestionMark => ExprType::JsonbExists,
 
             _ => {
                 return Err(
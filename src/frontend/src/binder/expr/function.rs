@@ -22,13 +22,18 @@ use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::session_config::USER_NAME_WILD_CARD;
 use risingwave_common::types::DataType;
 use risingwave_expr::expr::AggKind;
-use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, WindowSpec};
+use risingwave_expr::window_function::{Frame, FrameBound, FrameUnits};
+use risingwave_sqlparser::ast::{
+    Function, FunctionArg, FunctionArgExpr, WindowFrame, WindowFrameBound, WindowFrameUnits,
+    WindowSpec,
+};
 
 use crate::binder::bind_context::Clause;
 use crate::binder::{Binder, BoundQuery, BoundSetExpr};
+use crate::catalog::root_catalog::SchemaPath;
 use crate::expr::{
     AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal, OrderBy, Subquery, SubqueryKind,
-    TableFunction, TableFunctionType, WindowFunction, WindowFunctionType,
+    TableFunction, TableFunctionType, UserDefinedFunction, WindowFunction, WindowFunctionType,
 };
 use crate::utils::Condition;
 
@@ -57,15 +62,13 @@ impl Binder {
             }
         };
 
+        // window function
+        if f.over.is_some() {
+            return self.bind_window_function(f, function_name);
+        }
+
         // agg calls
         if let Ok(kind) = function_name.parse() {
-            if f.over.is_some() {
-                return Err(ErrorCode::NotImplemented(
-                    format!("aggregate function as over window function: {}", kind),
-                    4978.into(),
-                )
-                .into());
-            }
             return self.bind_agg(f, kind);
         }
 
@@ -84,11 +87,6 @@ impl Binder {
             .flatten_ok()
             .try_collect()?;
 
-        // window function
-        if let Some(window_spec) = f.over {
-            return self.bind_window_function(window_spec, function_name, inputs);
-        }
-
         // table function
         let table_function_type = TableFunctionType::from_str(function_name.as_str());
         if let Ok(function_type) = table_function_type {
@@ -158,6 +156,8 @@ impl Binder {
             "array_cat" => ExprType::ArrayCat,
             "array_append" => ExprType::ArrayAppend,
             "array_prepend" => ExprType::ArrayPrepend,
+            // jsonb
+            "jsonb_typeof" => ExprType::JsonbTypeof,
             // System information operations.
             "pg_typeof" if inputs.len() == 1 => {
                 let input = &inputs[0];
@@ -261,6 +261,15 @@ impl Binder {
             "pg_table_is_visible" => return Ok(ExprImpl::literal_bool(true)),
             // internal
             "rw_vnode" => ExprType::Vnode,
+            // user-defined function
+            _ if let Ok((catalog, _)) = self.catalog.get_function_by_name_args(
+                &self.db_name,
+                SchemaPath::Path(&self.search_path, &self.auth_context.user_name),
+                &function_name,
+                &inputs.iter().map(|arg| arg.return_type()).collect_vec(),
+            ) => {
+                return Ok(UserDefinedFunction::new(catalog.clone(), inputs).into());
+            }
             _ => {
                 return Err(ErrorCode::NotImplemented(
                     format!("unsupported function: {:?}", function_name),
@@ -367,25 +376,36 @@ impl Binder {
         )?)))
     }
 
+    /// Binds a window function, which is either a general window function like `ROW_NUMBER` and
+    /// `LAG`, or an aggregate function with an `OVER` clause.
     pub(super) fn bind_window_function(
         &mut self,
-        WindowSpec {
-            partition_by,
-            order_by,
-            window_frame,
-        }: WindowSpec,
+        f: Function,
         function_name: String,
-        inputs: Vec<ExprImpl>,
     ) -> Result<ExprImpl> {
         self.ensure_window_function_allowed()?;
-        if let Some(window_frame) = window_frame {
+        if f.distinct || !f.order_by.is_empty() || f.filter.is_some() {
             return Err(ErrorCode::NotImplemented(
-                format!("window frame: {}", window_frame),
+                format!(
+                    "DISTINCT, ORDER BY or FILTER in window function: {}",
+                    function_name
+                ),
                 None.into(),
             )
             .into());
         }
+        let WindowSpec {
+            partition_by,
+            order_by,
+            window_frame,
+        } = f.over.unwrap();
         let window_function_type = WindowFunctionType::from_str(&function_name)?;
+        let inputs = f
+            .args
+            .into_iter()
+            .map(|arg| self.bind_function_arg(arg))
+            .flatten_ok()
+            .try_collect()?;
         let partition_by = partition_by
             .into_iter()
             .map(|arg| self.bind_expr(arg))
@@ -397,7 +417,66 @@ impl Binder {
                 .map(|order_by_expr| self.bind_order_by_expr(order_by_expr))
                 .collect::<Result<_>>()?,
         );
-        Ok(WindowFunction::new(window_function_type, partition_by, order_by, inputs)?.into())
+        let frame = window_frame.map(Self::bind_window_frame).transpose()?;
+        Ok(
+            WindowFunction::new(window_function_type, partition_by, order_by, inputs, frame)?
+                .into(),
+        )
+    }
+
+    fn bind_window_frame(frame: WindowFrame) -> Result<Frame> {
+        let units = match &frame.units {
+            WindowFrameUnits::Rows => FrameUnits::Rows,
+            WindowFrameUnits::Range => FrameUnits::Range,
+            WindowFrameUnits::Groups => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("window frame in GROUPS mode: {}", frame),
+                    None.into(),
+                )
+                .into());
+            }
+        };
+        let bind_bound = |bound: &WindowFrameBound| match bound {
+            WindowFrameBound::CurrentRow => FrameBound::CurrentRow,
+            WindowFrameBound::Preceding(None) => FrameBound::UnboundedPreceding,
+            WindowFrameBound::Preceding(Some(n)) => FrameBound::Preceding(*n as usize),
+            WindowFrameBound::Following(None) => FrameBound::UnboundedFollowing,
+            WindowFrameBound::Following(Some(n)) => FrameBound::Following(*n as usize),
+        };
+        let start = bind_bound(&frame.start_bound);
+        let end = frame
+            .end_bound
+            .as_ref()
+            .map_or(FrameBound::CurrentRow, bind_bound);
+
+        let has_offset = |bound: FrameBound| {
+            matches!(bound, FrameBound::Preceding(_) | FrameBound::Following(_))
+        };
+        if units == FrameUnits::Range && (has_offset(start) || has_offset(end)) {
+            return Err(ErrorCode::NotImplemented(
+                format!("window frame in RANGE mode with offset: {}", frame),
+                None.into(),
+            )
+            .into());
+        }
+        // The bounds must be in the order of `UNBOUNDED PRECEDING`, `PRECEDING`, `CURRENT ROW`,
+        // `FOLLOWING` and `UNBOUNDED FOLLOWING`.
+        let bound_order = |bound: FrameBound| match bound {
+            FrameBound::UnboundedPreceding => 0,
+            FrameBound::Preceding(_) => 1,
+            FrameBound::CurrentRow => 2,
+            FrameBound::Following(_) => 3,
+            FrameBound::UnboundedFollowing => 4,
+        };
+        if start == FrameBound::UnboundedFollowing
+            || end == FrameBound::UnboundedPreceding
+            || bound_order(start) > bound_order(end)
+        {
+            return Err(
+                ErrorCode::InvalidInputSyntax(format!("invalid window frame: {}", frame)).into(),
+            );
+        }
+        Ok(Frame { units, start, end })
     }
 
     fn rewrite_concat_to_concat_ws(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
@@ -41,6 +41,7 @@ impl Binder {
                 s.cast_explicit(bind_data_type(&data_type)?)
             }
             Expr::Row(exprs) => self.bind_row(exprs),
+            Expr::Parameter { index } => self.bind_parameter(index),
             // input ref
             Expr::Identifier(ident) => {
                 if ["session_user", "current_schema"]
@@ -472,6 +473,7 @@ pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
                 "float4" => DataType::Float32,
                 "float8" => DataType::Float64,
                 "timestamptz" => DataType::Timestampz,
+                "jsonb" => DataType::Jsonb,
                 _ => return Err(new_err().into()),
             }
         }
@@ -17,9 +17,11 @@ use std::sync::Arc;
 
 use risingwave_common::error::Result;
 use risingwave_common::session_config::SearchPath;
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{Statement, TableAlias};
 
 mod bind_context;
+mod bind_param;
 mod delete;
 mod expr;
 mod insert;
@@ -33,6 +35,7 @@ mod update;
 mod values;
 
 pub use bind_context::{BindContext, LateralBindContext};
+pub use bind_param::parse_parameters;
 pub use delete::BoundDelete;
 pub use expr::{bind_data_type, bind_struct_field};
 pub use insert::BoundInsert;
@@ -49,6 +52,7 @@ pub use update::BoundUpdate;
 pub use values::BoundValues;
 
 use crate::catalog::catalog_service::CatalogReadGuard;
+use crate::expr::ParameterTypes;
 use crate::session::{AuthContext, SessionImpl};
 
 /// `Binder` binds the identifiers in AST to columns in relations
@@ -77,6 +81,10 @@ pub struct Binder {
     cte_to_relation: HashMap<String, (BoundQuery, TableAlias)>,
 
     search_path: SearchPath,
+
+    /// The types of parameters in a prepared statement. It is `None` when binding a statement
+    /// that is not prepared, where parameters are not allowed.
+    param_types: Option<ParameterTypes>,
 }
 
 impl Binder {
@@ -92,14 +100,34 @@ impl Binder {
             next_values_id: 0,
             cte_to_relation: HashMap::new(),
             search_path: session.config().get_search_path(),
+            param_types: None,
         }
     }
 
+    /// Creates a binder for a prepared statement. `param_types` are the parameter types provided by
+    /// the client, where `None` means the type should be inferred.
+    pub fn new_with_param_types(
+        session: &SessionImpl,
+        param_types: Vec<Option<DataType>>,
+    ) -> Binder {
+        let mut binder = Self::new(session);
+        binder.param_types = Some(ParameterTypes::new(param_types));
+        binder
+    }
+
     /// Bind a [`Statement`].
     pub fn bind(&mut self, stmt: Statement) -> Result<BoundStatement> {
         self.bind_statement(stmt)
     }
 
+    /// The types of all parameters in the bound statement, ordered by index.
+    pub fn export_param_types(&self) -> Vec<DataType> {
+        self.param_types
+            .as_ref()
+            .map(ParameterTypes::export)
+            .unwrap_or_default()
+    }
+
     fn push_context(&mut self) {
         let new_context = std::mem::take(&mut self.context);
         let new_lateral_contexts = std::mem::take(&mut self.lateral_contexts);
@@ -165,6 +193,8 @@ impl Binder {
 
 #[cfg(test)]
 pub mod test_utils {
+    use risingwave_common::types::DataType;
+
     use super::Binder;
     use crate::session::SessionImpl;
 
@@ -172,6 +202,11 @@ pub mod test_utils {
     pub fn mock_binder() -> Binder {
         Binder::new(&SessionImpl::mock())
     }
+
+    #[cfg(test)]
+    pub fn mock_binder_with_param_types(param_types: Vec<Option<DataType>>) -> Binder {
+        Binder::new_with_param_types(&SessionImpl::mock(), param_types)
+    }
 }
 
 /// The column name stored in [`BindContext`] for a column without an alias.
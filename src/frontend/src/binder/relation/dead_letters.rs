@@ -0,0 +1,118 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::catalog::RW_DEAD_LETTERS_FUNCTION_NAME;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_source::dead_letter::DEAD_LETTER_SOURCE_ID_KEY;
+use risingwave_sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, Ident, Statement, TableAlias, Value,
+};
+use risingwave_sqlparser::parser::Parser;
+
+use super::BoundSubquery;
+use crate::binder::{Binder, Relation};
+use crate::catalog::root_catalog::SchemaPath;
+
+impl Binder {
+    /// `rw_dead_letters(source_name[,schema_name])` which queries the dead letters of a source.
+    ///
+    /// It's bound as a view of all the dead-letter tables of the source, one for each job reading
+    /// it with the dead-letter error policy. The name of the dead-letter table is prepended to
+    /// their columns, i.e. `(table_name, split_id, offset, payload, error)`.
+    pub(super) fn bind_dead_letters(
+        &mut self,
+        args: Vec<FunctionArg>,
+        alias: Option<TableAlias>,
+    ) -> Result<Relation> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(ErrorCode::BindError(format!(
+                "usage: {}(source_name[,schema_name])",
+                RW_DEAD_LETTERS_FUNCTION_NAME
+            ))
+            .into());
+        }
+        let args: Vec<String> = args
+            .into_iter()
+            .map(|arg| match arg.get_expr() {
+                FunctionArgExpr::Expr(Expr::Identifier(ident)) => Ok(ident.real_value()),
+                FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(name))) => Ok(name),
+                arg => Err(ErrorCode::BindError(format!("invalid name: {}", arg))),
+            })
+            .try_collect()?;
+
+        let schema_path = match args.get(1) {
+            Some(schema_name) => SchemaPath::Name(schema_name),
+            None => SchemaPath::Path(&self.search_path, &self.auth_context.user_name),
+        };
+        let (source, _) = self
+            .catalog
+            .get_source_by_name(&self.db_name, schema_path, &args[0])?;
+        let source_id = source.id.to_string();
+
+        // The dead-letter tables are in the schemas of the jobs, which may differ from the one of
+        // the source.
+        let selects = self
+            .catalog
+            .iter_schemas(&self.db_name)?
+            .flat_map(|schema| {
+                schema
+                    .iter_internal_table()
+                    .filter(|table| {
+                        table.properties.get(DEAD_LETTER_SOURCE_ID_KEY) == Some(&source_id)
+                    })
+                    .map(move |table| (table.id(), schema.name(), table.name().to_string()))
+            })
+            .sorted()
+            .map(|(_, schema_name, table_name)| {
+                format!(
+                    "SELECT {} AS table_name, split_id, \"offset\", payload, error FROM {}.{}",
+                    Value::SingleQuotedString(table_name.clone()),
+                    Ident::with_quote('"', schema_name),
+                    Ident::with_quote('"', table_name),
+                )
+            })
+            .collect_vec();
+        if selects.is_empty() {
+            return Err(ErrorCode::BindError(format!(
+                "source \"{}\" has no dead letters, which are only kept by the jobs reading it with error_policy = 'dead_letter'",
+                args[0]
+            ))
+            .into());
+        }
+
+        let sql = selects.join(" UNION ALL ");
+        let query = match Parser::parse_sql(&sql)
+            .map_err(|e| ErrorCode::InternalError(e.to_string()))?
+            .into_iter()
+            .exactly_one()
+        {
+            Ok(Statement::Query(query)) => *query,
+            _ => unreachable!("the dead letters should be queried by a query statement"),
+        };
+        let query = self.bind_query(query)?;
+
+        self.bind_table_to_context(
+            query
+                .body
+                .schema()
+                .fields
+                .iter()
+                .map(|f| (false, f.clone())),
+            RW_DEAD_LETTERS_FUNCTION_NAME.to_string(),
+            alias,
+        )?;
+        Ok(Relation::Subquery(Box::new(BoundSubquery { query })))
+    }
+}
@@ -17,15 +17,20 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 use risingwave_common::catalog::{
-    Field, TableId, DEFAULT_SCHEMA_NAME, RW_INTERNAL_TABLE_FUNCTION_NAME,
+    Field, TableId, DEFAULT_SCHEMA_NAME, RW_DEAD_LETTERS_FUNCTION_NAME,
+    RW_INTERNAL_TABLE_FUNCTION_NAME,
 };
 use risingwave_common::error::{internal_error, ErrorCode, Result, RwError};
+use risingwave_common::util::epoch::Epoch;
+use risingwave_expr::vector_op::cast::{str_to_timestamp, str_to_timestampz};
 use risingwave_sqlparser::ast::{FunctionArg, Ident, ObjectName, TableAlias, TableFactor};
 
 use super::bind_context::ColumnBinding;
 use crate::binder::{Binder, BoundSetExpr};
+use crate::catalog::TableCatalog;
 use crate::expr::{Expr, ExprImpl, TableFunction, TableFunctionType};
 
+mod dead_letters;
 mod join;
 mod subquery;
 mod table_or_source;
@@ -311,12 +316,48 @@ impl Binder {
 
     pub(super) fn bind_table_factor(&mut self, table_factor: TableFactor) -> Result<Relation> {
         match table_factor {
-            TableFactor::Table { name, alias } => self.bind_relation_by_name(name, alias),
+            TableFactor::Table {
+                name,
+                alias,
+                for_system_time_as_of_proctime,
+                for_system_time_as_of_timestamp,
+            } => {
+                let mut relation = self.bind_relation_by_name(name, alias)?;
+                if let Some(timestamp) = for_system_time_as_of_timestamp {
+                    match &mut relation {
+                        Relation::BaseTable(table) => {
+                            table.for_system_time_as_of_epoch =
+                                Some(bind_time_travel_epoch(&table.table_catalog, &timestamp)?)
+                        }
+                        _ => {
+                            return Err(ErrorCode::BindError(
+                                "FOR SYSTEM_TIME AS OF '<timestamp>' can only be applied to tables and materialized views".to_string(),
+                            )
+                            .into())
+                        }
+                    }
+                }
+                if for_system_time_as_of_proctime {
+                    match &mut relation {
+                        Relation::BaseTable(table) => table.for_system_time_as_of_proctime = true,
+                        _ => {
+                            return Err(ErrorCode::BindError(
+                                "FOR SYSTEM_TIME AS OF PROCTIME() can only be applied to tables and materialized views".to_string(),
+                            )
+                            .into())
+                        }
+                    }
+                }
+                Ok(relation)
+            }
             TableFactor::TableFunction { name, alias, args } => {
                 let func_name = &name.0[0].real_value();
                 if func_name.eq_ignore_ascii_case(RW_INTERNAL_TABLE_FUNCTION_NAME) {
                     return self.bind_internal_table(args, alias);
                 }
+                if func_name.eq_ignore_ascii_case(RW_DEAD_LETTERS_FUNCTION_NAME) {
+                    return self.bind_dead_letters(args, alias);
+                }
                 if let Ok(table_function_type) = TableFunctionType::from_str(func_name) {
                     let args: Vec<ExprImpl> = args
                         .into_iter()
@@ -388,3 +429,48 @@ impl Binder {
         }
     }
 }
+
+/// Binds the `timestamp` of `FOR SYSTEM_TIME AS OF '<timestamp>'` to the epoch the table is read as
+/// of, which must be within the time-travel retention of the table. A timestamp without time zone
+/// is in UTC.
+fn bind_time_travel_epoch(table_catalog: &TableCatalog, timestamp: &str) -> Result<u64> {
+    let unix_millis = str_to_timestampz(timestamp)
+        .map(|micros| micros / 1000)
+        .or_else(|_| str_to_timestamp(timestamp).map(|t| t.0.timestamp_millis()))
+        .map_err(|_| {
+            ErrorCode::BindError(format!(
+                "invalid timestamp of FOR SYSTEM_TIME AS OF: {}",
+                timestamp
+            ))
+        })?;
+    let retention_seconds = table_catalog
+        .properties
+        .time_travel_retention_seconds()
+        .ok_or_else(|| {
+            ErrorCode::BindError(format!(
+                "time travel is not enabled on table {}, set `time_travel_retention_seconds` to enable it",
+                table_catalog.name()
+            ))
+        })?;
+
+    let epoch = Epoch::from_unix_millis(unix_millis.try_into().unwrap_or_default());
+    let now = Epoch::now();
+    if epoch > now {
+        return Err(ErrorCode::BindError(format!(
+            "cannot read table {} as of a future time {}",
+            table_catalog.name(),
+            timestamp
+        ))
+        .into());
+    }
+    if epoch < now.subtract_ms(retention_seconds.get() as u64 * 1000) {
+        return Err(ErrorCode::BindError(format!(
+            "time {} is out of the time-travel retention of table {}, which is {} seconds",
+            timestamp,
+            table_catalog.name(),
+            retention_seconds
+        ))
+        .into());
+    }
+    Ok(epoch.0)
+}
@@ -39,10 +39,16 @@ pub struct BoundBaseTable {
     pub table_id: TableId,
     pub table_catalog: TableCatalog,
     pub table_indexes: Vec<Arc<IndexCatalog>>,
+    /// Whether the table is read as of the process time, i.e. as the right side of a temporal
+    /// join.
+    pub for_system_time_as_of_proctime: bool,
+    /// The epoch the table is read as of in a time-travel query, i.e. `FOR SYSTEM_TIME AS OF
+    /// '<timestamp>'`.
+    pub for_system_time_as_of_epoch: Option<u64>,
 }
 
 /// `BoundTableSource` is used by DML statement on table source like insert, update.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BoundTableSource {
     pub name: String,       // explain-only
     pub source_id: TableId, // TODO: refactor to source id
@@ -221,6 +227,8 @@ impl Binder {
             table_id,
             table_catalog,
             table_indexes,
+            for_system_time_as_of_proctime: false,
+            for_system_time_as_of_epoch: None,
         };
 
         Ok::<_, RwError>((Relation::BaseTable(Box::new(table)), columns))
@@ -294,6 +302,8 @@ impl Binder {
             table_id,
             table_catalog,
             table_indexes,
+            for_system_time_as_of_proctime: false,
+            for_system_time_as_of_epoch: None,
         })
     }
 
@@ -20,9 +20,11 @@ use risingwave_common::catalog::{CatalogVersion, IndexId, TableId};
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Sink as ProstSink, Source as ProstSource, Table as ProstTable,
+    View as ProstView,
 };
+use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_rpc_client::MetaClient;
 use tokio::sync::watch::Receiver;
@@ -87,6 +89,8 @@ pub trait CatalogWriter: Send + Sync {
 
     async fn create_sink(&self, sink: ProstSink, graph: StreamFragmentGraph) -> Result<()>;
 
+    async fn create_function(&self, function: ProstFunction) -> Result<()>;
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()>;
 
     async fn drop_materialized_view(&self, table_id: TableId) -> Result<()>;
@@ -95,6 +99,8 @@ pub trait CatalogWriter: Send + Sync {
 
     async fn drop_source(&self, source_id: u32) -> Result<()>;
 
+    async fn alter_source(&self, source_id: u32, columns: Vec<ProstColumnCatalog>) -> Result<()>;
+
     async fn drop_sink(&self, sink_id: u32) -> Result<()>;
 
     async fn drop_database(&self, database_id: u32) -> Result<()>;
@@ -102,6 +108,8 @@ pub trait CatalogWriter: Send + Sync {
     async fn drop_schema(&self, schema_id: u32) -> Result<()>;
 
     async fn drop_index(&self, index_id: IndexId) -> Result<()>;
+
+    async fn drop_function(&self, function_id: u32) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -193,6 +201,11 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn create_function(&self, function: ProstFunction) -> Result<()> {
+        let (_, version) = self.meta_client.create_function(function).await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()> {
         let version = self
             .meta_client
@@ -216,6 +229,11 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn alter_source(&self, source_id: u32, columns: Vec<ProstColumnCatalog>) -> Result<()> {
+        let version = self.meta_client.alter_source(source_id, columns).await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_sink(&self, sink_id: u32) -> Result<()> {
         let version = self.meta_client.drop_sink(sink_id).await?;
         self.wait_version(version).await
@@ -226,6 +244,11 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn drop_function(&self, function_id: u32) -> Result<()> {
+        let version = self.meta_client.drop_function(function_id).await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_schema(&self, schema_id: u32) -> Result<()> {
         let version = self.meta_client.drop_schema(schema_id).await?;
         self.wait_version(version).await
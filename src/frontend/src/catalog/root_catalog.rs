@@ -18,15 +18,18 @@ use std::sync::Arc;
 use itertools::Itertools;
 use risingwave_common::catalog::{CatalogVersion, IndexId, TableId};
 use risingwave_common::session_config::{SearchPath, USER_NAME_WILD_CARD};
+use risingwave_common::types::DataType;
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Sink as ProstSink, Source as ProstSource, Table as ProstTable,
+    View as ProstView,
 };
 
+use super::function_catalog::FunctionCatalog;
 use super::source_catalog::SourceCatalog;
 use super::system_catalog::get_sys_catalogs_in_schema;
 use super::view_catalog::ViewCatalog;
-use super::{CatalogError, CatalogResult, SinkId, SourceId, ViewId};
+use super::{CatalogError, CatalogResult, FunctionId, SinkId, SourceId, ViewId};
 use crate::catalog::database_catalog::DatabaseCatalog;
 use crate::catalog::schema_catalog::SchemaCatalog;
 use crate::catalog::sink_catalog::SinkCatalog;
@@ -147,6 +150,14 @@ impl Catalog {
             .create_source(proto);
     }
 
+    pub fn update_source(&mut self, proto: &ProstSource) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .update_source(proto);
+    }
+
     pub fn create_sink(&mut self, proto: &ProstSink) {
         self.get_database_mut(proto.database_id)
             .unwrap()
@@ -163,6 +174,14 @@ impl Catalog {
             .create_view(proto);
     }
 
+    pub fn create_function(&mut self, proto: &ProstFunction) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .create_function(proto);
+    }
+
     pub fn drop_database(&mut self, db_id: DatabaseId) {
         let name = self.db_name_by_id.remove(&db_id).unwrap();
         let _database = self.database_by_name.remove(&name).unwrap();
@@ -222,6 +241,19 @@ impl Catalog {
             .drop_view(view_id);
     }
 
+    pub fn drop_function(
+        &mut self,
+        db_id: DatabaseId,
+        schema_id: SchemaId,
+        function_id: FunctionId,
+    ) {
+        self.get_database_mut(db_id)
+            .unwrap()
+            .get_schema_mut(schema_id)
+            .unwrap()
+            .drop_function(function_id);
+    }
+
     pub fn get_database_by_name(&self, db_name: &str) -> CatalogResult<&DatabaseCatalog> {
         self.database_by_name
             .get(db_name)
@@ -534,6 +566,59 @@ impl Catalog {
         }
     }
 
+    #[inline(always)]
+    fn get_function_by_name_args_with_schema_name(
+        &self,
+        db_name: &str,
+        schema_name: &str,
+        function_name: &str,
+        args: &[DataType],
+    ) -> CatalogResult<&Arc<FunctionCatalog>> {
+        self.get_schema_by_name(db_name, schema_name)?
+            .get_function_by_name_args(function_name, args)
+            .ok_or_else(|| CatalogError::NotFound("function", function_name.to_string()))
+    }
+
+    pub fn get_function_by_name_args<'a>(
+        &self,
+        db_name: &str,
+        schema_path: SchemaPath<'a>,
+        function_name: &str,
+        args: &[DataType],
+    ) -> CatalogResult<(&Arc<FunctionCatalog>, &'a str)> {
+        match schema_path {
+            SchemaPath::Name(schema_name) => self
+                .get_function_by_name_args_with_schema_name(
+                    db_name,
+                    schema_name,
+                    function_name,
+                    args,
+                )
+                .map(|function_catalog| (function_catalog, schema_name)),
+            SchemaPath::Path(search_path, user_name) => {
+                for path in search_path.path() {
+                    let mut schema_name: &str = path;
+                    if schema_name == USER_NAME_WILD_CARD {
+                        schema_name = user_name;
+                    }
+
+                    if let Ok(function_catalog) = self.get_function_by_name_args_with_schema_name(
+                        db_name,
+                        schema_name,
+                        function_name,
+                        args,
+                    ) {
+                        return Ok((function_catalog, schema_name));
+                    }
+                }
+                Err(CatalogError::NotFound(
+                    "function",
+                    function_name.to_string(),
+                ))
+            }
+        }
+    }
+
     /// Check the name if duplicated with existing table, materialized view or source.
     pub fn check_relation_name_duplicated(
         &self,
@@ -17,13 +17,15 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use risingwave_common::catalog::{valid_table_name, IndexId, TableId};
+use risingwave_common::types::DataType;
 use risingwave_pb::catalog::{
-    Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
-    Table as ProstTable, View as ProstView,
+    Function as ProstFunction, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
+    Source as ProstSource, Table as ProstTable, View as ProstView,
 };
 
+use super::function_catalog::FunctionCatalog;
 use super::source_catalog::{SourceCatalog, SourceKind};
-use super::ViewId;
+use super::{FunctionId, ViewId};
 use crate::catalog::index_catalog::IndexCatalog;
 use crate::catalog::sink_catalog::SinkCatalog;
 use crate::catalog::system_catalog::SystemCatalog;
@@ -49,6 +51,9 @@ pub struct SchemaCatalog {
     indexes_by_table_id: HashMap<TableId, Vec<Arc<IndexCatalog>>>,
     view_by_name: HashMap<String, Arc<ViewCatalog>>,
     view_by_id: HashMap<ViewId, Arc<ViewCatalog>>,
+    /// Functions are overloaded by their argument types.
+    function_by_name: HashMap<String, HashMap<Vec<DataType>, Arc<FunctionCatalog>>>,
+    function_by_id: HashMap<FunctionId, Arc<FunctionCatalog>>,
 
     // This field only available when schema is "pg_catalog". Meanwhile, others will be empty.
     system_table_by_name: HashMap<String, SystemCatalog>,
@@ -143,6 +148,16 @@ impl SchemaCatalog {
         self.source_by_id.try_insert(id, source_ref).unwrap();
     }
 
+    pub fn update_source(&mut self, prost: &ProstSource) {
+        let name = prost.name.clone();
+        let id = prost.id;
+        let source = SourceCatalog::from(prost);
+        let source_ref = Arc::new(source);
+
+        self.source_by_name.insert(name, source_ref.clone());
+        self.source_by_id.insert(id, source_ref);
+    }
+
     pub fn drop_source(&mut self, id: SourceId) {
         let source_ref = self.source_by_id.remove(&id).unwrap();
         self.source_by_name.remove(&source_ref.name).unwrap();
@@ -182,6 +197,34 @@ impl SchemaCatalog {
         self.view_by_name.remove(&view_ref.name).unwrap();
     }
 
+    pub fn create_function(&mut self, prost: &ProstFunction) {
+        let name = prost.name.clone();
+        let id = prost.id;
+        let function = FunctionCatalog::from(prost);
+        let args = function.arg_types.clone();
+        let function_ref = Arc::new(function);
+
+        self.function_by_name
+            .entry(name)
+            .or_default()
+            .try_insert(args, function_ref.clone())
+            .unwrap();
+        self.function_by_id.try_insert(id, function_ref).unwrap();
+    }
+
+    pub fn drop_function(&mut self, id: FunctionId) {
+        let function_ref = self.function_by_id.remove(&id).unwrap();
+        match self.function_by_name.entry(function_ref.name.clone()) {
+            Occupied(mut entry) => {
+                entry.get_mut().remove(&function_ref.arg_types).unwrap();
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+            Vacant(_entry) => unreachable!(),
+        };
+    }
+
     pub fn iter_table(&self) -> impl Iterator<Item = &Arc<TableCatalog>> {
         self.table_by_name
             .iter()
@@ -200,6 +243,13 @@ impl SchemaCatalog {
             .filter_map(|(key, v)| valid_table_name(key).then_some(v))
     }
 
+    /// Iterate all internal tables, i.e. the state tables of the streaming jobs.
+    pub fn iter_internal_table(&self) -> impl Iterator<Item = &Arc<TableCatalog>> {
+        self.table_by_name
+            .iter()
+            .filter_map(|(key, v)| (!valid_table_name(key)).then_some(v))
+    }
+
     /// Iterate all materialized views, excluding the indices.
     pub fn iter_mv(&self) -> impl Iterator<Item = &Arc<TableCatalog>> {
         self.table_by_name
@@ -241,6 +291,10 @@ impl SchemaCatalog {
         self.view_by_name.values()
     }
 
+    pub fn iter_function(&self) -> impl Iterator<Item = &Arc<FunctionCatalog>> {
+        self.function_by_id.values()
+    }
+
     pub fn iter_system_tables(&self) -> impl Iterator<Item = &SystemCatalog> {
         self.system_table_by_name.values()
     }
@@ -257,6 +311,10 @@ impl SchemaCatalog {
         self.source_by_name.get(source_name)
     }
 
+    pub fn get_source_by_id(&self, source_id: &SourceId) -> Option<&Arc<SourceCatalog>> {
+        self.source_by_id.get(source_id)
+    }
+
     pub fn get_sink_by_name(&self, sink_name: &str) -> Option<&Arc<SinkCatalog>> {
         self.sink_by_name.get(sink_name)
     }
@@ -290,6 +348,22 @@ impl SchemaCatalog {
         self.view_by_name.get(view_name)
     }
 
+    pub fn get_function_by_name_args(
+        &self,
+        name: &str,
+        args: &[DataType],
+    ) -> Option<&Arc<FunctionCatalog>> {
+        self.function_by_name.get(name)?.get(args)
+    }
+
+    /// Returns all the overloads of the function with the given name.
+    pub fn get_functions_by_name(&self, name: &str) -> Vec<&Arc<FunctionCatalog>> {
+        self.function_by_name
+            .get(name)
+            .map(|functions| functions.values().collect())
+            .unwrap_or_default()
+    }
+
     pub fn id(&self) -> SchemaId {
         self.id
     }
@@ -321,6 +395,8 @@ impl From<&ProstSchema> for SchemaCatalog {
             system_table_by_name: HashMap::new(),
             view_by_name: HashMap::new(),
             view_by_id: HashMap::new(),
+            function_by_name: HashMap::new(),
+            function_by_id: HashMap::new(),
         }
     }
 }
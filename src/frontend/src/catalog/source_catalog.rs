@@ -16,10 +16,10 @@ use std::collections::HashMap;
 
 use risingwave_pb::catalog::source::Info;
 use risingwave_pb::catalog::source_info::SourceInfo;
-use risingwave_pb::catalog::Source as ProstSource;
+use risingwave_pb::catalog::{ColumnIndex as ProstColumnIndex, Source as ProstSource};
 
 use super::column_catalog::ColumnCatalog;
-use super::{ColumnId, SourceId};
+use super::{ColumnId, DatabaseId, SchemaId, SourceId};
 use crate::WithOptions;
 
 pub const KAFKA_CONNECTOR: &str = "kafka";
@@ -54,6 +54,27 @@ impl SourceCatalog {
             SourceInfo::TableSource(_) => SourceKind::Table,
         }
     }
+
+    pub fn to_prost(&self, schema_id: SchemaId, database_id: DatabaseId) -> ProstSource {
+        let info = match &self.info {
+            SourceInfo::StreamSource(info) => Info::StreamSource(info.clone()),
+            SourceInfo::TableSource(info) => Info::TableSource(info.clone()),
+        };
+        ProstSource {
+            id: self.id,
+            schema_id,
+            database_id,
+            name: self.name.clone(),
+            row_id_index: self
+                .row_id_index
+                .map(|index| ProstColumnIndex { index: index as _ }),
+            columns: self.columns.iter().map(|c| c.to_protobuf()).collect(),
+            pk_column_ids: self.pk_col_ids.iter().map(|id| id.get_id()).collect(),
+            properties: self.properties.clone(),
+            info: Some(info),
+            owner: self.owner,
+        }
+    }
 }
 
 impl From<&ProstSource> for SourceCatalog {
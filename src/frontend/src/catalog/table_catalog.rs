@@ -105,6 +105,10 @@ pub struct TableCatalog {
     pub definition: String,
 
     pub handle_pk_conflict: bool,
+
+    /// The number of leading columns of the order key used as the prefix of the bloom filter key.
+    /// If 0, the distribution key is used when the order key starts with it.
+    pub read_prefix_len_hint: usize,
 }
 
 pub enum TableKind {
@@ -130,6 +134,10 @@ impl TableCatalog {
         self.handle_pk_conflict
     }
 
+    pub fn read_prefix_len_hint(&self) -> usize {
+        self.read_prefix_len_hint
+    }
+
     pub fn kind(&self) -> TableKind {
         if self.is_index {
             TableKind::Index
@@ -173,6 +181,7 @@ impl TableCatalog {
                 .retention_seconds
                 .unwrap_or(TABLE_OPTION_DUMMY_RETENTION_SECOND),
             value_indices: self.value_indices.clone(),
+            read_prefix_len_hint: self.read_prefix_len_hint,
         }
     }
 
@@ -222,6 +231,7 @@ impl TableCatalog {
             value_indices: self.value_indices.iter().map(|x| *x as _).collect(),
             definition: self.definition.clone(),
             handle_pk_conflict: self.handle_pk_conflict,
+            read_prefix_len_hint: self.read_prefix_len_hint as u32,
         }
     }
 }
@@ -269,6 +279,7 @@ impl From<ProstTable> for TableCatalog {
             value_indices: tb.value_indices.iter().map(|x| *x as _).collect(),
             definition: tb.definition.clone(),
             handle_pk_conflict: tb.handle_pk_conflict,
+            read_prefix_len_hint: tb.read_prefix_len_hint as usize,
         }
     }
 }
@@ -354,6 +365,7 @@ mod tests {
             value_indices: vec![0],
             definition: "".into(),
             handle_pk_conflict: false,
+            read_prefix_len_hint: 0,
         }
         .into();
 
@@ -411,7 +423,8 @@ mod tests {
                 vnode_col_idx: None,
                 value_indices: vec![0],
                 definition: "".into(),
-                handle_pk_conflict: false
+                handle_pk_conflict: false,
+                read_prefix_len_hint: 0,
             }
         );
         assert_eq!(table, TableCatalog::from(table.to_prost(0, 0)));
@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use super::{
-    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery,
-    TableFunction, WindowFunction,
+    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Parameter, Subquery,
+    TableFunction, UserDefinedFunction, WindowFunction,
 };
 
 /// with the same visit logic of `ExprVisitor`, but mutable.
@@ -29,6 +29,8 @@ pub trait ExprMutator {
             ExprImpl::CorrelatedInputRef(inner) => self.visit_correlated_input_ref(inner),
             ExprImpl::TableFunction(inner) => self.visit_table_function(inner),
             ExprImpl::WindowFunction(inner) => self.visit_window_function(inner),
+            ExprImpl::Parameter(inner) => self.visit_parameter(inner),
+            ExprImpl::UserDefinedFunction(inner) => self.visit_user_defined_function(inner),
         }
     }
     fn visit_function_call(&mut self, func_call: &mut FunctionCall) {
@@ -49,6 +51,7 @@ pub trait ExprMutator {
     fn visit_input_ref(&mut self, _: &mut InputRef) {}
     fn visit_subquery(&mut self, _: &mut Subquery) {}
     fn visit_correlated_input_ref(&mut self, _: &mut CorrelatedInputRef) {}
+    fn visit_parameter(&mut self, _: &mut Parameter) {}
     fn visit_table_function(&mut self, func_call: &mut TableFunction) {
         func_call
             .args
@@ -61,4 +64,10 @@ pub trait ExprMutator {
             .iter_mut()
             .for_each(|expr| self.visit_expr(expr));
     }
+    fn visit_user_defined_function(&mut self, func_call: &mut UserDefinedFunction) {
+        func_call
+            .args
+            .iter_mut()
+            .for_each(|expr| self.visit_expr(expr));
+    }
 }
@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use super::{
-    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery,
-    TableFunction, WindowFunction,
+    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Parameter, Subquery,
+    TableFunction, UserDefinedFunction, WindowFunction,
 };
 
 /// Traverse an expression tree.
@@ -41,6 +41,8 @@ pub trait ExprVisitor<R: Default> {
             ExprImpl::CorrelatedInputRef(inner) => self.visit_correlated_input_ref(inner),
             ExprImpl::TableFunction(inner) => self.visit_table_function(inner),
             ExprImpl::WindowFunction(inner) => self.visit_window_function(inner),
+            ExprImpl::Parameter(inner) => self.visit_parameter(inner),
+            ExprImpl::UserDefinedFunction(inner) => self.visit_user_defined_function(inner),
         }
     }
     fn visit_function_call(&mut self, func_call: &FunctionCall) -> R {
@@ -74,6 +76,9 @@ pub trait ExprVisitor<R: Default> {
     fn visit_correlated_input_ref(&mut self, _: &CorrelatedInputRef) -> R {
         R::default()
     }
+    fn visit_parameter(&mut self, _: &Parameter) -> R {
+        R::default()
+    }
     fn visit_table_function(&mut self, func_call: &TableFunction) -> R {
         func_call
             .args
@@ -90,4 +95,12 @@ pub trait ExprVisitor<R: Default> {
             .reduce(Self::merge)
             .unwrap_or_default()
     }
+    fn visit_user_defined_function(&mut self, func_call: &UserDefinedFunction) -> R {
+        func_call
+            .args
+            .iter()
+            .map(|expr| self.visit_expr(expr))
+            .reduce(Self::merge)
+            .unwrap_or_default()
+    }
 }
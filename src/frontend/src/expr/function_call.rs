@@ -107,7 +107,16 @@ impl FunctionCall {
     }
 
     /// Create a cast expr over `child` to `target` type in `allows` context.
-    pub fn new_cast(child: ExprImpl, target: DataType, allows: CastContext) -> Result<ExprImpl> {
+    pub fn new_cast(
+        mut child: ExprImpl,
+        target: DataType,
+        allows: CastContext,
+    ) -> Result<ExprImpl> {
+        // The first cast of a parameter with unknown type decides its type, like PostgreSQL.
+        if let ExprImpl::Parameter(parameter) = &mut child && !parameter.has_infer() {
+            parameter.cast_infer_type(target)?;
+            return Ok(child);
+        }
         if is_row_function(&child) {
             return Self::cast_nested(child, target, allows);
         }
@@ -26,8 +26,10 @@ mod correlated_input_ref;
 mod function_call;
 mod input_ref;
 mod literal;
+mod parameter;
 mod subquery;
 mod table_function;
+mod user_defined_function;
 mod window_function;
 
 mod order_by_expr;
@@ -44,8 +46,10 @@ pub use correlated_input_ref::{CorrelatedId, CorrelatedInputRef, Depth};
 pub use function_call::{FunctionCall, FunctionCallDisplay};
 pub use input_ref::{input_ref_to_column_indices, InputRef, InputRefDisplay};
 pub use literal::Literal;
+pub use parameter::{bind_parameters_in_proto, Parameter, ParameterTypes};
 pub use subquery::{Subquery, SubqueryKind};
 pub use table_function::{TableFunction, TableFunctionType};
+pub use user_defined_function::UserDefinedFunction;
 pub use window_function::{WindowFunction, WindowFunctionType};
 
 pub type ExprType = risingwave_pb::expr::expr_node::Type;
@@ -92,7 +96,9 @@ impl_expr_impl!(
     AggCall,
     Subquery,
     TableFunction,
-    WindowFunction
+    WindowFunction,
+    Parameter,
+    UserDefinedFunction
 );
 
 impl ExprImpl {
@@ -161,9 +167,11 @@ impl ExprImpl {
         matches!(self, ExprImpl::Literal(literal) if literal.get_data().is_none())
     }
 
-    /// Check whether self is a literal NULL or literal string.
+    /// Check whether self is a literal NULL or literal string, or a parameter whose type is
+    /// neither provided nor inferred yet.
     pub fn is_unknown(&self) -> bool {
         matches!(self, ExprImpl::Literal(literal) if literal.return_type() == DataType::Varchar)
+            || matches!(self, ExprImpl::Parameter(parameter) if !parameter.has_infer())
     }
 
     /// Shorthand to create cast expr to `target` type in implicit context.
@@ -248,7 +256,7 @@ macro_rules! impl_has_variant {
     };
 }
 
-impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery, TableFunction, WindowFunction}
+impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery, TableFunction, WindowFunction, UserDefinedFunction}
 
 impl ExprImpl {
     /// This function is not meant to be called. In most cases you would want
@@ -658,6 +666,8 @@ impl Expr for ExprImpl {
             ExprImpl::CorrelatedInputRef(expr) => expr.return_type(),
             ExprImpl::TableFunction(expr) => expr.return_type(),
             ExprImpl::WindowFunction(expr) => expr.return_type(),
+            ExprImpl::Parameter(expr) => expr.return_type(),
+            ExprImpl::UserDefinedFunction(expr) => expr.return_type(),
         }
     }
 
@@ -675,6 +685,8 @@ impl Expr for ExprImpl {
             ExprImpl::WindowFunction(_e) => {
                 unreachable!("Window function should not be converted to ExprNode")
             }
+            ExprImpl::Parameter(e) => e.to_expr_proto(),
+            ExprImpl::UserDefinedFunction(e) => e.to_expr_proto(),
         }
     }
 }
@@ -706,6 +718,10 @@ impl std::fmt::Debug for ExprImpl {
                 }
                 Self::TableFunction(arg0) => f.debug_tuple("TableFunction").field(arg0).finish(),
                 Self::WindowFunction(arg0) => f.debug_tuple("WindowFunction").field(arg0).finish(),
+                Self::Parameter(arg0) => f.debug_tuple("Parameter").field(arg0).finish(),
+                Self::UserDefinedFunction(arg0) => {
+                    f.debug_tuple("UserDefinedFunction").field(arg0).finish()
+                }
             };
         }
         match self {
@@ -717,6 +733,8 @@ impl std::fmt::Debug for ExprImpl {
             Self::CorrelatedInputRef(x) => write!(f, "{:?}", x),
             Self::TableFunction(x) => write!(f, "{:?}", x),
             Self::WindowFunction(x) => write!(f, "{:?}", x),
+            Self::Parameter(x) => write!(f, "{:?}", x),
+            Self::UserDefinedFunction(x) => write!(f, "{:?}", x),
         }
     }
 }
@@ -758,6 +776,8 @@ impl std::fmt::Debug for ExprDisplay<'_> {
                 // TODO: WindowFunctionCallVerboseDisplay
                 write!(f, "{:?}", x)
             }
+            ExprImpl::Parameter(x) => write!(f, "{:?}", x),
+            ExprImpl::UserDefinedFunction(x) => write!(f, "{:?}", x),
         }
     }
 }
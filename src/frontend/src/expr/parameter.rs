@@ -0,0 +1,207 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::expr_node::RexNode;
+use risingwave_pb::expr::{ExprNode, ParameterExpr};
+
+use super::{Expr, ExprType};
+
+/// The types of parameters in a prepared statement, shared by all the [`Parameter`]s bound from
+/// it. A type is either provided by the client in the `Parse` message, or inferred from the first
+/// usage of the parameter, e.g. `$1` in `v1 = $1` gets the type of `v1`.
+#[derive(Clone, Debug, Default)]
+pub struct ParameterTypes(Arc<RwLock<BTreeMap<u64, Option<DataType>>>>);
+
+impl ParameterTypes {
+    /// `specified` are the types provided by the client, where `None` means unspecified.
+    pub fn new(specified: Vec<Option<DataType>>) -> Self {
+        let map = specified
+            .into_iter()
+            .enumerate()
+            .map(|(index, data_type)| (index as u64 + 1, data_type))
+            .collect();
+        Self(Arc::new(RwLock::new(map)))
+    }
+
+    pub fn has_infer(&self, index: u64) -> bool {
+        matches!(self.0.read().get(&index), Some(Some(_)))
+    }
+
+    pub fn read_type(&self, index: u64) -> Option<DataType> {
+        self.0.read().get(&index).cloned().flatten()
+    }
+
+    pub fn record_new_param(&self, index: u64) {
+        self.0.write().entry(index).or_insert(None);
+    }
+
+    /// Records the type inferred for a parameter. A parameter can't be inferred as two different
+    /// types, e.g. `$1::int` and `$1::varchar` in the same statement.
+    pub fn record_infer_type(&self, index: u64, data_type: DataType) -> Result<()> {
+        let mut types = self.0.write();
+        match types.get(&index) {
+            Some(Some(inferred)) if *inferred != data_type => Err(ErrorCode::BindError(format!(
+                "inconsistent types deduced for parameter ${}: {} versus {}",
+                index, inferred, data_type
+            ))
+            .into()),
+            _ => {
+                types.insert(index, Some(data_type));
+                Ok(())
+            }
+        }
+    }
+
+    /// The types of all parameters, ordered by index. A parameter neither specified nor used is
+    /// treated as `varchar`, like an unknown-typed string literal in PostgreSQL.
+    pub fn export(&self) -> Vec<DataType> {
+        let types = self.0.read();
+        let count = types.keys().next_back().copied().unwrap_or(0);
+        (1..=count)
+            .map(|index| {
+                types
+                    .get(&index)
+                    .cloned()
+                    .flatten()
+                    .unwrap_or(DataType::Varchar)
+            })
+            .collect()
+    }
+}
+
+/// A parameter of a prepared statement, e.g. `$1`. The plan of a prepared statement is generated
+/// once with the parameters in it, and they are replaced by the values provided for each execution.
+/// A parameter is never constant-folded, as its value is unknown at planning time.
+#[derive(Clone)]
+pub struct Parameter {
+    /// The index of the parameter, starting from 1.
+    pub index: u64,
+    param_types: ParameterTypes,
+}
+
+impl Parameter {
+    pub fn new(index: u64, param_types: ParameterTypes) -> Self {
+        param_types.record_new_param(index);
+        Self { index, param_types }
+    }
+
+    /// Whether the type of the parameter is provided by the client or has been inferred.
+    pub fn has_infer(&self) -> bool {
+        self.param_types.has_infer(self.index)
+    }
+
+    /// Infers the type of the parameter from a cast, e.g. `$1::int` or an implicit cast added by
+    /// function type inference.
+    pub fn cast_infer_type(&mut self, data_type: DataType) -> Result<()> {
+        self.param_types.record_infer_type(self.index, data_type)
+    }
+}
+
+impl PartialEq for Parameter {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for Parameter {}
+
+impl Hash for Parameter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl Expr for Parameter {
+    fn return_type(&self) -> DataType {
+        self.param_types
+            .read_type(self.index)
+            .unwrap_or(DataType::Varchar)
+    }
+
+    /// A parameter is serialized as a placeholder, which is replaced by the constant of its value
+    /// with [`bind_parameters_in_proto`] before the plan is executed.
+    fn to_expr_proto(&self) -> ExprNode {
+        ExprNode {
+            expr_type: ExprType::Parameter as i32,
+            return_type: Some(self.return_type().to_protobuf()),
+            rex_node: Some(RexNode::Parameter(ParameterExpr { index: self.index })),
+        }
+    }
+}
+
+/// Replaces the parameter placeholders in a serialized expression with `params`, the constants
+/// of the values of `$1`, `$2`, ....
+pub fn bind_parameters_in_proto(expr: &mut ExprNode, params: &[ExprNode]) {
+    match &mut expr.rex_node {
+        Some(RexNode::Parameter(parameter)) => {
+            *expr = params[parameter.index as usize - 1].clone();
+        }
+        Some(RexNode::FuncCall(func_call)) => func_call
+            .children
+            .iter_mut()
+            .for_each(|child| bind_parameters_in_proto(child, params)),
+        Some(RexNode::Udf(udf)) => udf
+            .children
+            .iter_mut()
+            .for_each(|child| bind_parameters_in_proto(child, params)),
+        Some(RexNode::InputRef(_)) | Some(RexNode::Constant(_)) | None => {}
+    }
+}
+
+impl fmt::Debug for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${}", self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parameter_types() {
+        let param_types = ParameterTypes::new(vec![None, Some(DataType::Int64)]);
+        let mut p1 = Parameter::new(1, param_types.clone());
+        let p2 = Parameter::new(2, param_types.clone());
+        let p4 = Parameter::new(4, param_types.clone());
+
+        assert!(!p1.has_infer());
+        assert_eq!(p1.return_type(), DataType::Varchar);
+        p1.cast_infer_type(DataType::Int32).unwrap();
+        assert!(Parameter::new(1, param_types.clone()).has_infer());
+        // The same type can be inferred again, but not a different one.
+        p1.cast_infer_type(DataType::Int32).unwrap();
+        assert!(p1.cast_infer_type(DataType::Varchar).is_err());
+
+        assert_eq!(p2.return_type(), DataType::Int64);
+        assert!(!p4.has_infer());
+        assert_eq!(
+            param_types.export(),
+            vec![
+                DataType::Int32,
+                DataType::Int64,
+                DataType::Varchar,
+                DataType::Varchar
+            ]
+        );
+    }
+}
@@ -40,6 +40,7 @@ pub enum TableFunctionType {
     Range,
     Unnest,
     RegexpMatches,
+    JsonbArrayElements,
 }
 
 impl TableFunctionType {
@@ -49,6 +50,7 @@ impl TableFunctionType {
             TableFunctionType::Range => Type::Range,
             TableFunctionType::Unnest => Type::Unnest,
             TableFunctionType::RegexpMatches => Type::RegexpMatches,
+            TableFunctionType::JsonbArrayElements => Type::JsonbArrayElements,
         }
     }
 }
@@ -60,6 +62,7 @@ impl TableFunctionType {
             TableFunctionType::Range => "range",
             TableFunctionType::Unnest => "unnest",
             TableFunctionType::RegexpMatches => "regexp_matches",
+            TableFunctionType::JsonbArrayElements => "jsonb_array_elements",
         }
     }
 }
@@ -76,6 +79,8 @@ impl FromStr for TableFunctionType {
             Ok(TableFunctionType::Unnest)
         } else if s.eq_ignore_ascii_case("regexp_matches") {
             Ok(TableFunctionType::RegexpMatches)
+        } else if s.eq_ignore_ascii_case("jsonb_array_elements") {
+            Ok(TableFunctionType::JsonbArrayElements)
         } else {
             Err(())
         }
@@ -182,6 +187,22 @@ impl TableFunction {
                     function_type: TableFunctionType::RegexpMatches,
                 })
             }
+            TableFunctionType::JsonbArrayElements => {
+                if args.len() != 1 {
+                    return Err(ErrorCode::BindError(
+                        "the length of args of jsonb_array_elements function should be 1"
+                            .to_string(),
+                    )
+                    .into());
+                }
+
+                let expr = args.into_iter().next().unwrap();
+                Ok(TableFunction {
+                    args: vec![expr.cast_implicit(DataType::Jsonb)?],
+                    return_type: DataType::Jsonb,
+                    function_type: TableFunctionType::JsonbArrayElements,
+                })
+            }
         }
     }
 
@@ -226,6 +226,14 @@ pub static CAST_MAP: LazyLock<CastMap> = LazyLock::new(|| {
     m.insert((T::Timestampz, T::Time), CastContext::Assign);
     m.insert((T::Boolean, T::Int32), CastContext::Explicit);
     m.insert((T::Int32, T::Boolean), CastContext::Explicit);
+
+    // Casting to and from jsonb. A cast from jsonb to a non-string type fails at runtime unless the
+    // jsonb value is of the matching kind.
+    m.insert((T::Jsonb, T::Varchar), CastContext::Assign);
+    m.insert((T::Varchar, T::Jsonb), CastContext::Explicit);
+    for t in [T::Boolean, T::Decimal, T::Float64] {
+        m.insert((T::Jsonb, t), CastContext::Explicit);
+    }
     m
 });
 
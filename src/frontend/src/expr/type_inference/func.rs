@@ -290,6 +290,22 @@ fn infer_type_for_special(
                 .into()),
             }
         }
+        ExprType::JsonbAccessPath => {
+            ensure_arity!("jsonb_access_path", | inputs | == 2);
+            let inputs_owned = std::mem::take(inputs);
+            *inputs = inputs_owned
+                .into_iter()
+                .enumerate()
+                .map(|(i, input)| match i {
+                    0 => input.cast_implicit(DataType::Jsonb),
+                    // the path is a text array, e.g. `'{a,0}'`
+                    _ => input.cast_implicit(DataType::List {
+                        datatype: Box::new(DataType::Varchar),
+                    }),
+                })
+                .try_collect()?;
+            Ok(Some(DataType::Jsonb))
+        }
         ExprType::Vnode => {
             ensure_arity!("vnode", 1 <= | inputs |);
             Ok(Some(DataType::Int16))
@@ -867,6 +883,15 @@ fn build_type_derive_map() -> FuncSigMap {
     // TODO: Support more `to_char` types.
     map.insert(E::ToChar, vec![T::Timestamp, T::Varchar], T::Varchar);
 
+    // jsonb expressions
+    for t in [T::Varchar, T::Int32] {
+        map.insert(E::JsonbAccessInner, vec![T::Jsonb, t], T::Jsonb);
+        map.insert(E::JsonbAccessStr, vec![T::Jsonb, t], T::Varchar);
+    }
+    map.insert(E::JsonbContains, vec![T::Jsonb, T::Jsonb], T::Boolean);
+    map.insert(E::JsonbExists, vec![T::Jsonb, T::Varchar], T::Boolean);
+    map.insert(E::JsonbTypeof, vec![T::Jsonb], T::Varchar);
+
     map
 }
 
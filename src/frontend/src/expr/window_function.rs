@@ -12,14 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
 use std::str::FromStr;
 
 use itertools::Itertools;
-use parse_display::Display;
 use risingwave_common::error::ErrorCode;
 use risingwave_common::types::DataType;
+use risingwave_expr::expr::AggKind;
+use risingwave_expr::window_function::Frame;
 
-use super::{Expr, ExprImpl, OrderBy, Result};
+use super::{AggCall, Expr, ExprImpl, OrderBy, Result};
 
 /// A window function performs a calculation across a set of table rows that are somehow related to
 /// the current row, according to the window spec `OVER (PARTITION BY .. ORDER BY ..)`.
@@ -34,14 +36,22 @@ pub struct WindowFunction {
     pub function_type: WindowFunctionType,
     pub partition_by: Vec<ExprImpl>,
     pub order_by: OrderBy,
+    /// The frame of the window. It's ignored by rank functions, and is a single row for `LAG`
+    /// and `LEAD`.
+    pub frame: Frame,
 }
 
-#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, Hash)]
-#[display(style = "SNAKE_CASE")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum WindowFunctionType {
     RowNumber,
     Rank,
     DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    /// An aggregate function used as a window function, e.g. `SUM(x) OVER (..)`.
+    Aggregate(AggKind),
 }
 
 impl WindowFunctionType {
@@ -55,6 +65,23 @@ impl WindowFunctionType {
     }
 }
 
+impl fmt::Display for WindowFunctionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFunctionType::RowNumber => write!(f, "ROW_NUMBER"),
+            WindowFunctionType::Rank => write!(f, "RANK"),
+            WindowFunctionType::DenseRank => write!(f, "DENSE_RANK"),
+            WindowFunctionType::Lag => write!(f, "LAG"),
+            WindowFunctionType::Lead => write!(f, "LEAD"),
+            WindowFunctionType::FirstValue => write!(f, "FIRST_VALUE"),
+            WindowFunctionType::LastValue => write!(f, "LAST_VALUE"),
+            WindowFunctionType::Aggregate(kind) => {
+                write!(f, "{}", kind.to_string().to_ascii_uppercase())
+            }
+        }
+    }
+}
+
 impl FromStr for WindowFunctionType {
     type Err = ErrorCode;
 
@@ -63,38 +90,110 @@ impl FromStr for WindowFunctionType {
             "row_number" => Ok(WindowFunctionType::RowNumber),
             "rank" => Ok(WindowFunctionType::Rank),
             "dense_rank" => Ok(WindowFunctionType::DenseRank),
-            _ => Err(ErrorCode::NotImplemented(
-                format!("unknown table function kind: {s}"),
-                None.into(),
-            )),
+            "lag" => Ok(WindowFunctionType::Lag),
+            "lead" => Ok(WindowFunctionType::Lead),
+            "first_value" => Ok(WindowFunctionType::FirstValue),
+            "last_value" => Ok(WindowFunctionType::LastValue),
+            name => match name.parse() {
+                Ok(kind) => Ok(WindowFunctionType::Aggregate(kind)),
+                Err(_) => Err(ErrorCode::NotImplemented(
+                    format!("unknown window function kind: {s}"),
+                    None.into(),
+                )),
+            },
         }
     }
 }
 
 impl WindowFunction {
     /// Create a `WindowFunction` expr with the return type inferred from `func_type` and types of
-    /// `inputs`.
+    /// `inputs`. `frame` is the window frame specified in the window spec, if any.
     pub fn new(
         function_type: WindowFunctionType,
         partition_by: Vec<ExprImpl>,
         order_by: OrderBy,
-        args: Vec<ExprImpl>,
+        mut args: Vec<ExprImpl>,
+        frame: Option<Frame>,
     ) -> Result<Self> {
-        if !args.is_empty() {
-            return Err(ErrorCode::BindError(format!(
-                "the length of args of {function_type} function should be 0"
+        let invalid_args = |expected: &str| -> Result<Self> {
+            Err(ErrorCode::BindError(format!(
+                "the length of args of {function_type} function should be {expected}"
             ))
-            .into());
-        }
+            .into())
+        };
+
+        let (return_type, frame) = match function_type {
+            WindowFunctionType::RowNumber
+            | WindowFunctionType::Rank
+            | WindowFunctionType::DenseRank => {
+                if !args.is_empty() {
+                    return invalid_args("0");
+                }
+                (DataType::Int64, Frame::default())
+            }
+            WindowFunctionType::Lag | WindowFunctionType::Lead => {
+                let offset = match args.len() {
+                    1 => 1,
+                    2 => Self::eval_offset(function_type, args.pop().unwrap())?,
+                    // The default value is returned when the offset goes beyond the partition,
+                    // which can't be expressed by a frame.
+                    3 => {
+                        return Err(ErrorCode::NotImplemented(
+                            format!("{function_type} function with a default value"),
+                            None.into(),
+                        )
+                        .into())
+                    }
+                    _ => return invalid_args("1 or 2"),
+                };
+                let offset = match function_type {
+                    WindowFunctionType::Lag => -offset,
+                    _ => offset,
+                };
+                (args[0].return_type(), Frame::single_row(offset))
+            }
+            WindowFunctionType::FirstValue | WindowFunctionType::LastValue => {
+                if args.len() != 1 {
+                    return invalid_args("1");
+                }
+                (args[0].return_type(), frame.unwrap_or_default())
+            }
+            WindowFunctionType::Aggregate(kind) => {
+                let arg_types = args.iter().map(|arg| arg.return_type()).collect_vec();
+                (
+                    AggCall::infer_return_type(&kind, &arg_types)?,
+                    frame.unwrap_or_default(),
+                )
+            }
+        };
 
         Ok(Self {
             args,
-            return_type: DataType::Int64,
+            return_type,
             function_type,
             partition_by,
             order_by,
+            frame,
         })
     }
+
+    /// Evaluates the constant offset argument of `LAG` and `LEAD`.
+    fn eval_offset(function_type: WindowFunctionType, offset: ExprImpl) -> Result<i64> {
+        if !offset.is_const() {
+            return Err(ErrorCode::NotImplemented(
+                format!("non-constant offset of {function_type} function"),
+                None.into(),
+            )
+            .into());
+        }
+        match offset.cast_implicit(DataType::Int64)?.eval_row_const()? {
+            Some(offset) => Ok(offset.into_int64()),
+            None => Err(ErrorCode::InvalidInputSyntax(format!(
+                "the offset of {function_type} function cannot be null"
+            ))
+            .into()),
+        }
+    }
 }
 
 impl std::fmt::Debug for WindowFunction {
@@ -106,9 +205,15 @@ impl std::fmt::Debug for WindowFunction {
                 .field("args", &self.args)
                 .field("partition_by", &self.partition_by)
                 .field("order_by", &format_args!("{}", self.order_by))
+                .field("frame", &format_args!("{}", self.frame))
                 .finish()
         } else {
-            write!(f, "{}() OVER(", self.function_type)?;
+            write!(
+                f,
+                "{}({:?}) OVER(",
+                self.function_type,
+                self.args.iter().format(", ")
+            )?;
 
             let mut delim = "";
             if !self.partition_by.is_empty() {
@@ -120,8 +225,12 @@ impl std::fmt::Debug for WindowFunction {
                 )?;
             }
             if !self.order_by.sort_exprs.is_empty() {
+                delim = " ";
                 write!(f, "{delim}{}", self.order_by)?;
             }
+            if !self.function_type.is_rank_function() {
+                write!(f, "{delim}{}", self.frame)?;
+            }
             f.write_str(")")?;
 
             Ok(())
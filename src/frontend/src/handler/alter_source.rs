@@ -0,0 +1,224 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::{InvalidInputSyntax, PermissionDenied, ProtocolError};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_pb::catalog::source_info::SourceInfo;
+use risingwave_pb::plan_common::{
+    ColumnCatalog as ProstColumnCatalog, ColumnDesc as ProstColumnDesc, RowFormatType,
+};
+use risingwave_sqlparser::ast::{AlterSourceOperation, ObjectName};
+
+use super::create_source::{extract_avro_table_schema, extract_protobuf_table_schema};
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::catalog::source_catalog::{SourceCatalog, SourceKind};
+use crate::session::OptimizerContext;
+
+pub async fn handle_alter_source(
+    context: OptimizerContext,
+    name: ObjectName,
+    operation: AlterSourceOperation,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, source_name) = Binder::resolve_schema_qualified_name(db_name, name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let source = {
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let (source, schema_name) =
+            catalog_reader.get_source_by_name(db_name, schema_path, &source_name)?;
+
+        let schema_catalog = catalog_reader
+            .get_schema_by_name(db_name, schema_name)
+            .unwrap();
+        let schema_owner = schema_catalog.owner();
+        if session.user_id() != source.owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        // The columns of the table of a materialized source are not altered with the source.
+        if source.kind() == SourceKind::Table
+            || catalog_reader
+                .get_table_by_name(db_name, SchemaPath::Name(schema_name), &source_name)
+                .is_ok()
+        {
+            return Err(InvalidInputSyntax(
+                "Only a non-materialized source can be altered.".to_owned(),
+            )
+            .into());
+        }
+
+        source.clone()
+    };
+
+    let columns = match operation {
+        AlterSourceOperation::RefreshSchema => refresh_schema(&source).await?,
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.alter_source(source.id, columns).await?;
+
+    Ok(PgResponse::empty_result(StatementType::ALTER_SOURCE))
+}
+
+/// Fetches the latest schema of a source of the row format Avro or Protobuf, and returns the
+/// columns of the source with the fields added to the schema appended.
+async fn refresh_schema(source: &SourceCatalog) -> Result<Vec<ProstColumnCatalog>> {
+    let info = match &source.info {
+        SourceInfo::StreamSource(info) => info,
+        SourceInfo::TableSource(_) => unreachable!(),
+    };
+    let refreshed = match info.row_format() {
+        RowFormatType::Avro => {
+            extract_avro_table_schema(
+                &info.row_schema_location,
+                info.use_schema_registry,
+                source.properties.clone(),
+            )
+            .await?
+        }
+        RowFormatType::Protobuf => {
+            extract_protobuf_table_schema(
+                &info.row_schema_location,
+                &info.proto_message_name,
+                info.use_schema_registry,
+                source.properties.clone(),
+            )
+            .await?
+        }
+        row_format => {
+            return Err(ProtocolError(format!(
+                "REFRESH SCHEMA is only supported for the row format AVRO or PROTOBUF, got {:?}",
+                row_format
+            ))
+            .into())
+        }
+    };
+    merge_refreshed_columns(&source.columns, refreshed)
+}
+
+/// Appends the columns of the refreshed schema that are not in `columns`, with new column ids.
+///
+/// The existing columns are kept even if they are removed from the schema, in which case they are
+/// read as null, while the type of an existing column must not be changed.
+fn merge_refreshed_columns(
+    columns: &[ColumnCatalog],
+    refreshed: Vec<ProstColumnCatalog>,
+) -> Result<Vec<ProstColumnCatalog>> {
+    let mut merged = columns.iter().map(ColumnCatalog::to_protobuf).collect_vec();
+    let mut next_column_id = merged
+        .iter()
+        .map(|column| max_column_id(column.column_desc.as_ref().unwrap()))
+        .max()
+        .unwrap_or(0)
+        + 1;
+    for mut column in refreshed {
+        let desc = column.column_desc.as_mut().unwrap();
+        if let Some(existing) = columns.iter().find(|c| c.name() == desc.name) {
+            let data_type = DataType::from(desc.column_type.as_ref().unwrap());
+            if existing.data_type() != &data_type {
+                return Err(ProtocolError(format!(
+                    "the type of column {} is changed from {:?} to {:?}, which is not supported",
+                    desc.name,
+                    existing.data_type(),
+                    data_type
+                ))
+                .into());
+            }
+            continue;
+        }
+        assign_column_ids(desc, &mut next_column_id);
+        merged.push(column);
+    }
+    Ok(merged)
+}
+
+fn max_column_id(desc: &ProstColumnDesc) -> i32 {
+    desc.field_descs
+        .iter()
+        .map(max_column_id)
+        .fold(desc.column_id, i32::max)
+}
+
+/// Assigns the ids to a column and its nested fields, which take the ids before the column as
+/// `map_to_columns` of the parsers does.
+fn assign_column_ids(desc: &mut ProstColumnDesc, next_column_id: &mut i32) {
+    for field in &mut desc.field_descs {
+        assign_column_ids(field, next_column_id);
+    }
+    desc.column_id = *next_column_id;
+    *next_column_id += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{ColumnDesc, ColumnId};
+
+    use super::*;
+    use crate::catalog::row_id_column_name;
+
+    fn prost_column(name: &str, data_type: DataType, column_id: i32) -> ProstColumnCatalog {
+        ProstColumnCatalog {
+            column_desc: Some(ColumnDesc::new_atomic(data_type, name, column_id).to_protobuf()),
+            is_hidden: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_refreshed_columns() {
+        let columns = vec![
+            ColumnCatalog::row_id_column(ColumnId::new(0)),
+            ColumnCatalog::from(prost_column("id", DataType::Int32, 1)),
+            ColumnCatalog::from(prost_column("removed", DataType::Varchar, 2)),
+        ];
+        let refreshed = vec![
+            prost_column("id", DataType::Int32, 1),
+            prost_column("name", DataType::Varchar, 2),
+        ];
+        let merged = merge_refreshed_columns(&columns, refreshed)
+            .unwrap()
+            .into_iter()
+            .map(|c| {
+                let desc = c.column_desc.unwrap();
+                (desc.name, desc.column_id)
+            })
+            .collect_vec();
+        assert_eq!(
+            merged,
+            vec![
+                (row_id_column_name(), 0),
+                ("id".to_string(), 1),
+                ("removed".to_string(), 2),
+                ("name".to_string(), 3),
+            ]
+        );
+
+        let refreshed = vec![prost_column("id", DataType::Int64, 1)];
+        assert!(merge_refreshed_columns(&columns, refreshed).is_err());
+    }
+}
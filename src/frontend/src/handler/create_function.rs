@@ -0,0 +1,192 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Handle creation of user-defined functions, which are evaluated by an external UDF server.
+
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::catalog::Function as ProstFunction;
+use risingwave_sqlparser::ast::{
+    CreateFunctionArg, CreateFunctionBody, DataType as AstDataType, ObjectName,
+};
+
+use super::RwPgResponse;
+use crate::binder::{bind_data_type, Binder};
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+/// The languages of the functions served by an external UDF server.
+const SUPPORTED_LANGUAGES: [&str; 2] = ["python", "java"];
+
+pub async fn handle_create_function(
+    context: OptimizerContext,
+    name: ObjectName,
+    args: Option<Vec<CreateFunctionArg>>,
+    return_type: Option<AstDataType>,
+    bodies: Vec<CreateFunctionBody>,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+
+    let mut language = None;
+    let mut link = None;
+    for body in bodies {
+        match body {
+            CreateFunctionBody::Language(lang) => language = Some(lang.real_value()),
+            CreateFunctionBody::UsingLink(l) => link = Some(l),
+            body => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("CREATE FUNCTION with {}", body),
+                    None.into(),
+                )
+                .into())
+            }
+        }
+    }
+    let language = match language {
+        Some(language) if SUPPORTED_LANGUAGES.contains(&language.to_lowercase().as_str()) => {
+            language.to_lowercase()
+        }
+        Some(language) => {
+            return Err(ErrorCode::InvalidParameterValue(format!(
+                "language {} is not supported, expect one of {:?}",
+                language, SUPPORTED_LANGUAGES
+            ))
+            .into())
+        }
+        None => {
+            return Err(
+                ErrorCode::InvalidInputSyntax("LANGUAGE must be specified".to_string()).into(),
+            )
+        }
+    };
+    let link = link
+        .ok_or_else(|| ErrorCode::InvalidInputSyntax("USING LINK must be specified".to_string()))?;
+
+    let arg_types: Vec<_> = args
+        .unwrap_or_default()
+        .iter()
+        .map(|arg| bind_data_type(&arg.data_type))
+        .try_collect()?;
+    let return_type = match return_type {
+        Some(return_type) => bind_data_type(&return_type)?,
+        None => {
+            return Err(
+                ErrorCode::InvalidInputSyntax("RETURNS must be specified".to_string()).into(),
+            )
+        }
+    };
+
+    let db_name = session.database();
+    let (schema_name, function_name) = Binder::resolve_schema_qualified_name(db_name, name)?;
+    let (database_id, schema_id) = session.get_database_and_schema_id_for_create(schema_name)?;
+
+    {
+        let reader = session.env().catalog_reader().read_guard();
+        let schema = reader.get_schema_by_id(&database_id, &schema_id)?;
+        if schema
+            .get_function_by_name_args(&function_name, &arg_types)
+            .is_some()
+        {
+            return Err(CatalogError::Duplicated("function", function_name).into());
+        }
+    }
+
+    let function = ProstFunction {
+        id: 0,
+        schema_id,
+        database_id,
+        name: function_name,
+        owner: session.user_id(),
+        arg_types: arg_types.iter().map(|t| t.to_protobuf()).collect(),
+        return_type: Some(return_type.to_protobuf()),
+        language,
+        link,
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.create_function(function).await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_FUNCTION))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::DataType;
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_function() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql(
+                "create function gcd(int, int) returns int language python \
+                 using link 'http://localhost:8815'",
+            )
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let schema_path = SchemaPath::Name("public");
+            let (function, _) = catalog_reader
+                .get_function_by_name_args(
+                    "dev",
+                    schema_path,
+                    "gcd",
+                    &[DataType::Int32, DataType::Int32],
+                )
+                .unwrap();
+            assert_eq!(function.return_type, DataType::Int32);
+            assert_eq!(function.language, "python");
+            assert_eq!(function.link, "http://localhost:8815");
+        }
+
+        // The function is bound by its name and argument types.
+        frontend
+            .run_sql("create table t (a int, b int)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("explain select gcd(a, b) from t")
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql("explain select gcd(a, 1.5) from t")
+            .await
+            .is_err());
+
+        // Overloads are allowed, but not the same signature.
+        frontend
+            .run_sql(
+                "create function gcd(int, int, int) returns int language python \
+                 using link 'http://localhost:8815'",
+            )
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql(
+                "create function gcd(int, int) returns bigint language python \
+                 using link 'http://localhost:8815'",
+            )
+            .await
+            .is_err());
+
+        // `DROP FUNCTION` by name is ambiguous with overloads.
+        assert!(frontend.run_sql("drop function gcd").await.is_err());
+    }
+}
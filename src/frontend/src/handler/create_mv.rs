@@ -160,9 +160,11 @@ pub async fn handle_create_mv(
 #[cfg(test)]
 pub mod tests {
     use std::collections::HashMap;
+    use std::num::NonZeroU32;
 
     use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, NaiveDateTimeWrapper};
+    use risingwave_common::util::epoch::Epoch;
 
     use crate::catalog::root_catalog::SchemaPath;
     use crate::catalog::row_id_column_name;
@@ -252,4 +254,80 @@ pub mod tests {
             "Bind error: An alias must be specified for an expression"
         );
     }
+    #[tokio::test]
+    async fn test_create_mv_time_travel() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let sql = "create table t (v int) with (time_travel_retention_seconds = 3600)";
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+            let (table, _) = catalog_reader
+                .get_table_by_name(DEFAULT_DATABASE_NAME, schema_path, "t")
+                .unwrap();
+            assert_eq!(
+                table.properties.time_travel_retention_seconds(),
+                NonZeroU32::new(3600)
+            );
+        }
+
+        // A time out of the time-travel retention of the table is forbidden.
+        let sql = "create materialized view mv1 as select * from t for system_time as of '2021-04-01 00:00:00'";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("is out of the time-travel retention of table t"));
+
+        // Streaming queries always read the latest changes of the tables.
+        let now =
+            NaiveDateTimeWrapper::with_secs_nsecs(Epoch::now().as_unix_millis() as i64 / 1000, 0)
+                .unwrap();
+        let sql = format!(
+            "create materialized view mv1 as select * from t for system_time as of '{}'",
+            now.0
+        );
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert!(err.to_string().contains("in streaming queries"));
+    }
+
+    #[tokio::test]
+    async fn test_create_mv_bloom_filter_prefix_columns() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let sql = "create table t (v1 int, v2 int)";
+        frontend.run_sql(sql).await.unwrap();
+
+        let sql = "create materialized view mv1 with (bloom_filter_prefix_columns = 1) as select v1, v2, count(*) as cnt from t group by v1, v2";
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+            let (table, _) = catalog_reader
+                .get_table_by_name(DEFAULT_DATABASE_NAME, schema_path, "mv1")
+                .unwrap();
+            assert_eq!(table.read_prefix_len_hint(), 1);
+            assert_eq!(table.table_desc().read_prefix_len_hint, 1);
+        }
+
+        // The prefix can not be longer than the order key.
+        let sql = "create materialized view mv2 with (bloom_filter_prefix_columns = 3) as select v1, v2, count(*) as cnt from t group by v1, v2";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("bloom_filter_prefix_columns 3 exceeds the 2 columns of the order key"));
+
+        // The prefix must be a positive integer.
+        for value in ["0", "'abc'"] {
+            let sql = format!("create materialized view mv3 with (bloom_filter_prefix_columns = {}) as select v1, v2, count(*) as cnt from t group by v1, v2", value);
+            let err = frontend.run_sql(sql).await.unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("bloom_filter_prefix_columns must be a positive integer"));
+        }
+    }
 }
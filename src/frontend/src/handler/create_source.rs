@@ -18,11 +18,14 @@ use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::ErrorCode::{self, ProtocolError};
 use risingwave_common::error::{Result, RwError};
+use risingwave_connector::source::filesystem::posix_fs::POSIX_FS_CONNECTOR;
+use risingwave_connector::source::filesystem::s3::S3_CONNECTOR;
 use risingwave_pb::catalog::source::Info;
 use risingwave_pb::catalog::{
     ColumnIndex as ProstColumnIndex, Source as ProstSource, StreamSourceInfo,
 };
 use risingwave_pb::plan_common::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
+use risingwave_source::dead_letter::ErrorPolicy;
 use risingwave_source::{AvroParser, ProtobufParser};
 use risingwave_sqlparser::ast::{
     AvroSchema, CreateSourceStatement, ObjectName, ProtobufSchema, SourceSchema,
@@ -63,16 +66,12 @@ pub(crate) fn make_prost_source(
 }
 
 /// Map an Avro schema to a relational schema.
-async fn extract_avro_table_schema(
-    schema: &AvroSchema,
+pub(crate) async fn extract_avro_table_schema(
+    row_schema_location: &str,
+    use_schema_registry: bool,
     with_properties: HashMap<String, String>,
 ) -> Result<Vec<ProstColumnCatalog>> {
-    let parser = AvroParser::new(
-        schema.row_schema_location.0.as_str(),
-        schema.use_schema_registry,
-        with_properties,
-    )
-    .await?;
+    let parser = AvroParser::new(row_schema_location, use_schema_registry, with_properties).await?;
     let vec_column_desc = parser.map_to_columns()?;
     Ok(vec_column_desc
         .into_iter()
@@ -84,14 +83,16 @@ async fn extract_avro_table_schema(
 }
 
 /// Map a protobuf schema to a relational schema.
-async fn extract_protobuf_table_schema(
-    schema: &ProtobufSchema,
+pub(crate) async fn extract_protobuf_table_schema(
+    row_schema_location: &str,
+    message_name: &str,
+    use_schema_registry: bool,
     with_properties: HashMap<String, String>,
 ) -> Result<Vec<ProstColumnCatalog>> {
     let parser = ProtobufParser::new(
-        &schema.row_schema_location.0,
-        &schema.message_name.0,
-        schema.use_schema_registry,
+        row_schema_location,
+        message_name,
+        use_schema_registry,
         with_properties,
     )
     .await?;
@@ -106,6 +107,28 @@ async fn extract_protobuf_table_schema(
         .collect_vec())
 }
 
+/// An upsert source is keyed by the message key, which is the primary key of the messages, so it
+/// must be from Kafka and have a primary key specified.
+fn check_upsert_source(
+    is_kafka: bool,
+    row_id_index: Option<usize>,
+    row_format: &str,
+) -> Result<()> {
+    if !is_kafka {
+        return Err(RwError::from(ProtocolError(format!(
+            "The connector must be kafka when row format {} is used.",
+            row_format
+        ))));
+    }
+    if row_id_index.is_some() {
+        return Err(RwError::from(ProtocolError(format!(
+            "Primary key must be specified when creating source with row format {}.",
+            row_format
+        ))));
+    }
+    Ok(())
+}
+
 // TODO(Yuanxin): Only create a source w/o materializing.
 pub async fn handle_create_source(
     context: OptimizerContext,
@@ -122,6 +145,8 @@ pub async fn handle_create_source(
         .into());
     }
     let with_properties = context.with_options.inner().clone();
+    // Validate the error policy, which is used by the source executors.
+    ErrorPolicy::from_properties(&with_properties)?;
     const UPSTREAM_SOURCE_KEY: &str = "connector";
     // confluent schema registry must be used with kafka
     let is_kafka = with_properties
@@ -138,6 +163,9 @@ pub async fn handle_create_source(
             }) | SourceSchema::Avro(AvroSchema {
                 use_schema_registry: true,
                 ..
+            }) | SourceSchema::UpsertAvro(AvroSchema {
+                use_schema_registry: true,
+                ..
             })
         )
     {
@@ -153,7 +181,13 @@ pub async fn handle_create_source(
             assert_eq!(row_id_index, Some(0));
 
             columns.extend(
-                extract_protobuf_table_schema(protobuf_schema, with_properties.clone()).await?,
+                extract_protobuf_table_schema(
+                    &protobuf_schema.row_schema_location.0,
+                    &protobuf_schema.message_name.0,
+                    protobuf_schema.use_schema_registry,
+                    with_properties.clone(),
+                )
+                .await?,
             );
 
             (
@@ -163,6 +197,7 @@ pub async fn handle_create_source(
                     row_schema_location: protobuf_schema.row_schema_location.0.clone(),
                     use_schema_registry: protobuf_schema.use_schema_registry,
                     proto_message_name: protobuf_schema.message_name.0.clone(),
+                    ..Default::default()
                 },
             )
         }
@@ -170,14 +205,21 @@ pub async fn handle_create_source(
             assert_eq!(columns.len(), 1);
             assert_eq!(pk_column_ids, vec![0.into()]);
             assert_eq!(row_id_index, Some(0));
-            columns.extend(extract_avro_table_schema(avro_schema, with_properties.clone()).await?);
+            columns.extend(
+                extract_avro_table_schema(
+                    &avro_schema.row_schema_location.0,
+                    avro_schema.use_schema_registry,
+                    with_properties.clone(),
+                )
+                .await?,
+            );
             (
                 columns,
                 StreamSourceInfo {
                     row_format: RowFormatType::Avro as i32,
                     row_schema_location: avro_schema.row_schema_location.0.clone(),
                     use_schema_registry: avro_schema.use_schema_registry,
-                    proto_message_name: "".to_owned(),
+                    ..Default::default()
                 },
             )
         }
@@ -236,6 +278,65 @@ pub async fn handle_create_source(
                 },
             )
         }
+        SourceSchema::Csv(csv_info) => (
+            columns,
+            StreamSourceInfo {
+                row_format: RowFormatType::Csv as i32,
+                csv_delimiter: csv_info.delimiter as i32,
+                csv_has_header: csv_info.has_header,
+                ..Default::default()
+            },
+        ),
+        SourceSchema::UpsertJson => {
+            check_upsert_source(is_kafka, row_id_index, "upsert_json")?;
+            (
+                columns,
+                StreamSourceInfo {
+                    row_format: RowFormatType::UpsertJson as i32,
+                    ..Default::default()
+                },
+            )
+        }
+        SourceSchema::UpsertAvro(avro_schema) => {
+            check_upsert_source(is_kafka, row_id_index, "upsert_avro")?;
+            // The message key is decoded with the key schema of the topic in the registry.
+            if !avro_schema.use_schema_registry {
+                return Err(RwError::from(ProtocolError(
+                    "CONFLUENT SCHEMA REGISTRY must be used with row format upsert_avro."
+                        .to_string(),
+                )));
+            }
+            (
+                columns,
+                StreamSourceInfo {
+                    row_format: RowFormatType::UpsertAvro as i32,
+                    row_schema_location: avro_schema.row_schema_location.0.clone(),
+                    use_schema_registry: avro_schema.use_schema_registry,
+                    ..Default::default()
+                },
+            )
+        }
+        SourceSchema::Parquet => {
+            if !with_properties
+                .get(UPSTREAM_SOURCE_KEY)
+                .map_or(false, |connector| {
+                    connector.eq_ignore_ascii_case(POSIX_FS_CONNECTOR)
+                        || connector.eq_ignore_ascii_case(S3_CONNECTOR)
+                })
+            {
+                return Err(RwError::from(ProtocolError(format!(
+                    "The {} must be {} or {} when row format parquet is used",
+                    UPSTREAM_SOURCE_KEY, POSIX_FS_CONNECTOR, S3_CONNECTOR
+                ))));
+            }
+            (
+                columns,
+                StreamSourceInfo {
+                    row_format: RowFormatType::Parquet as i32,
+                    ..Default::default()
+                },
+            )
+        }
     };
 
     let row_id_index = row_id_index.map(|index| ProstColumnIndex { index: index as _ });
@@ -329,4 +430,48 @@ pub mod tests {
         };
         assert_eq!(columns, expected_columns);
     }
+
+    #[tokio::test]
+    async fn test_create_upsert_table_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let err = frontend
+            .run_sql(
+                "create table t (k int, v int) with (connector = 'kafka', kafka.topic = 'abc') \
+                 row format upsert_json",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "Primary key must be specified when creating source with row format upsert_json"
+        ));
+
+        frontend
+            .run_sql(
+                "create table t (k int primary key, v int) \
+                 with (connector = 'kafka', kafka.topic = 'abc', kafka.brokers = 'localhost:1001') \
+                 row format upsert_json",
+            )
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let (table, _) = catalog_reader
+            .get_table_by_name(
+                DEFAULT_DATABASE_NAME,
+                SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                "t",
+            )
+            .unwrap();
+        // The messages of the same key are upserted to the table by its primary key.
+        assert!(table.handle_pk_conflict());
+        assert_eq!(
+            table
+                .columns()
+                .iter()
+                .map(|col| col.name())
+                .collect::<Vec<_>>(),
+            vec!["k", "v"]
+        );
+    }
 }
@@ -0,0 +1,132 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::session_config::USER_NAME_WILD_CARD;
+use risingwave_sqlparser::ast::ObjectName;
+
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+/// Drops a function by its name. It fails if the function is overloaded, as the arguments can not
+/// be specified yet.
+pub async fn handle_drop_function(
+    context: OptimizerContext,
+    function_name: ObjectName,
+    if_exists: bool,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, function_name) =
+        Binder::resolve_schema_qualified_name(db_name, function_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let function_id = {
+        let reader = session.env().catalog_reader().read_guard();
+        let schema_names = match &schema_name {
+            Some(schema_name) => vec![schema_name.as_str()],
+            None => search_path
+                .path()
+                .iter()
+                .map(|path| match path.as_str() {
+                    USER_NAME_WILD_CARD => user_name.as_str(),
+                    path => path,
+                })
+                .collect(),
+        };
+        // The functions with the name in the first schema that has any.
+        let found = schema_names.into_iter().find_map(|schema_name| {
+            let schema = reader.get_schema_by_name(db_name, schema_name).ok()?;
+            let functions = schema.get_functions_by_name(&function_name);
+            (!functions.is_empty()).then_some((schema, functions))
+        });
+        let (schema_catalog, functions) = match found {
+            Some(found) => found,
+            None => {
+                return if if_exists {
+                    Ok(RwPgResponse::empty_result_with_notice(
+                        StatementType::DROP_FUNCTION,
+                        format!("function \"{}\" does not exist, skipping", function_name),
+                    ))
+                } else {
+                    Err(CatalogError::NotFound("function", function_name).into())
+                }
+            }
+        };
+        if functions.len() > 1 {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "function name \"{}\" is not unique",
+                function_name
+            ))
+            .into());
+        }
+        let function = functions[0];
+
+        if session.user_id() != function.owner
+            && session.user_id() != schema_catalog.owner()
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        function.id
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.drop_function(function_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::DROP_FUNCTION))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::DataType;
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_drop_function() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql(
+                "create function gcd(int, int) returns int language python \
+                 using link 'http://localhost:8815'",
+            )
+            .await
+            .unwrap();
+        frontend.run_sql("drop function gcd").await.unwrap();
+        frontend
+            .run_sql("drop function if exists gcd")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        assert!(catalog_reader
+            .get_function_by_name_args(
+                "dev",
+                SchemaPath::Name("public"),
+                "gcd",
+                &[DataType::Int32, DataType::Int32],
+            )
+            .is_err());
+    }
+}
@@ -13,11 +13,15 @@
 // limitations under the License.
 
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
+use futures::StreamExt;
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
-use risingwave_common::error::{ErrorCode, Result};
+use risingwave_batch::executor::ExecutorStatsCollectorRef;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::session_config::QueryMode;
 use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{ExplainOptions, ExplainType, Statement};
 
@@ -28,19 +32,22 @@ use super::create_table::gen_create_table_plan;
 use super::query::gen_batch_query_plan;
 use super::RwPgResponse;
 use crate::optimizer::plan_node::Convention;
-use crate::scheduler::BatchPlanFragmenter;
-use crate::session::OptimizerContext;
+use crate::scheduler::{
+    BatchPlanFragmenter, ExecutionContext, LocalQueryExecution, QueryExecutorStats,
+    QueryExecutorStatsRef,
+};
+use crate::session::{OptimizerContext, OptimizerContextRef};
 use crate::stream_fragmenter::build_graph;
 use crate::utils::explain_stream_graph;
 
-pub(super) fn handle_explain(
+pub(super) async fn handle_explain(
     context: OptimizerContext,
     stmt: Statement,
     options: ExplainOptions,
     analyze: bool,
 ) -> Result<RwPgResponse> {
     if analyze {
-        return Err(ErrorCode::NotImplemented("explain analyze".to_string(), 4856.into()).into());
+        return handle_explain_analyze(context, stmt, options).await;
     }
     if options.explain_type == ExplainType::Logical {
         return Err(ErrorCode::NotImplemented("explain logical".to_string(), 4856.into()).into());
@@ -157,7 +164,134 @@ pub(super) fn handle_explain(
         }
     }
 
-    Ok(PgResponse::new_for_stream(
+    Ok(explain_response(rows))
+}
+
+/// Executes the batch query and annotates each plan node with the runtime stats of its executors.
+async fn handle_explain_analyze(
+    context: OptimizerContext,
+    stmt: Statement,
+    options: ExplainOptions,
+) -> Result<RwPgResponse> {
+    if options.explain_type != ExplainType::Physical {
+        return Err(ErrorCode::NotImplemented(
+            format!("explain analyze with {}", options.explain_type),
+            4856.into(),
+        )
+        .into());
+    }
+    if !matches!(
+        stmt,
+        Statement::Query(_)
+            | Statement::Insert { .. }
+            | Statement::Delete { .. }
+            | Statement::Update { .. }
+    ) {
+        return Err(ErrorCode::NotImplemented(
+            "explain analyze for statements other than batch queries".to_string(),
+            4856.into(),
+        )
+        .into());
+    }
+
+    let session = context.session_ctx.clone();
+    context
+        .explain_verbose
+        .store(options.verbose, Ordering::Release);
+
+    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
+    let (plan_lines, query, query_mode, time_travel_epoch) = {
+        let context: OptimizerContextRef = context.into();
+        let (plan, query_mode, _) = gen_batch_query_plan(&session, context.clone(), stmt)?;
+        let mut plan_lines = vec![];
+        plan.explain_with_id(&mut vec![], 0, &mut |id, line| {
+            plan_lines.push((id, line));
+            Ok(())
+        })
+        .map_err(|e| ErrorCode::InternalError(format!("failed to explain: {}", e)))?;
+        let plan_fragmenter = BatchPlanFragmenter::new(
+            session.env().worker_node_manager_ref(),
+            session.env().catalog_reader().clone(),
+        );
+        (
+            plan_lines,
+            plan_fragmenter.split(plan)?,
+            query_mode,
+            context.time_travel_epoch(),
+        )
+    };
+
+    let hummock_snapshot_manager = session.env().hummock_snapshot_manager();
+    let query_id = query.query_id().clone();
+    let pinned_snapshot = match time_travel_epoch {
+        Some(epoch) => {
+            hummock_snapshot_manager
+                .acquire_as_of(&query_id, epoch)
+                .await?
+        }
+        None => hummock_snapshot_manager.acquire(&query_id).await?,
+    };
+
+    let executor_stats = match query_mode {
+        QueryMode::Local => {
+            // The executors running in frontend and the input stage executed on compute nodes
+            // all report their stats into the collector of the frontend task.
+            let executor_stats: QueryExecutorStatsRef = Arc::new(QueryExecutorStats::new(1));
+            let collector = ExecutorStatsCollectorRef::default();
+            let execution = LocalQueryExecution::new(
+                query,
+                session.env().clone(),
+                "",
+                pinned_snapshot,
+                session.auth_context(),
+            )
+            .with_executor_stats(collector.clone());
+            let mut data_stream = execution.run();
+            while let Some(chunk) = data_stream.next().await {
+                chunk?;
+            }
+            executor_stats.merge(&collector.to_prost());
+            executor_stats.finish_stage();
+            executor_stats
+        }
+        QueryMode::Distributed => {
+            let executor_stats: QueryExecutorStatsRef =
+                Arc::new(QueryExecutorStats::new(query.stage_graph.stages.len()));
+            let execution_context =
+                ExecutionContext::new(session.clone()).with_executor_stats(executor_stats.clone());
+            let mut data_stream = session
+                .env()
+                .query_manager()
+                .schedule(execution_context.into(), query, pinned_snapshot)
+                .await
+                .map_err(RwError::from)?;
+            while let Some(chunk) = data_stream.next().await {
+                chunk.map_err(|e| ErrorCode::InternalError(e.to_string()))?;
+            }
+            executor_stats
+        }
+    };
+    // The remote tasks report their stats after the output has been consumed.
+    executor_stats.wait_all_stages_finished().await;
+
+    let rows = plan_lines
+        .into_iter()
+        .map(|(id, line)| {
+            let line = match executor_stats.get(id) {
+                Some(stats) if options.timing => format!("{} ({})", line, stats),
+                // Only report the row count, which stays the same across runs.
+                Some(stats) => format!("{} (actual rows={})", line, stats.output_rows),
+                None => format!("{} (never executed)", line),
+            };
+            Row::new(vec![Some(line.into())])
+        })
+        .collect::<Vec<_>>();
+
+    Ok(explain_response(rows))
+}
+
+fn explain_response(rows: Vec<Row>) -> RwPgResponse {
+    PgResponse::new_for_stream(
         StatementType::EXPLAIN,
         Some(rows.len() as i32),
         rows.into(),
@@ -166,5 +300,5 @@ pub(super) fn handle_explain(
             DataType::VARCHAR.to_oid(),
             DataType::VARCHAR.type_len(),
         )],
-    ))
+    )
 }
@@ -0,0 +1,501 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::time::Instant;
+
+use bytes::Bytes;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::session_config::QueryMode;
+use risingwave_common::types::DataType;
+use risingwave_sqlparser::ast::Statement;
+
+use super::privilege::{check_privileges, resolve_privileges, ObjectCheckItem};
+use super::query::BatchQuery;
+use super::{handle, query, RwPgResponse};
+use crate::binder::{parse_parameters, Binder, BoundStatement};
+use crate::expr::{Expr, Literal};
+use crate::scheduler::plan_fragmenter::Query;
+use crate::session::{OptimizerContext, SessionImpl};
+use crate::utils::WithOptions;
+
+/// A statement prepared by the `Parse` message of the extended query protocol.
+#[derive(Clone)]
+pub enum PrepareStatement {
+    Empty,
+    /// A query or DML statement, which is bound and planned once here. Each portal fills in the
+    /// values of the parameters.
+    Prepared {
+        statement: Statement,
+        bound_statement: BoundStatement,
+        /// The types of all parameters, either specified by the client or inferred by the binder.
+        param_types: Vec<DataType>,
+        /// `None` if the statement can't be planned before the values of parameters are known,
+        /// in which case it's planned on each execution.
+        plan: Option<Arc<CachedPlan>>,
+    },
+    /// Other statements don't support parameters, and are handled as in the simple query protocol.
+    PureStatement(Statement),
+}
+
+/// A portal created by the `Bind` message of the extended query protocol.
+#[derive(Clone)]
+pub enum Portal {
+    Empty,
+    Portal {
+        statement: Statement,
+        /// The bound statement where all parameters have been replaced with their values.
+        bound_statement: BoundStatement,
+        /// The values of the parameters.
+        params: Vec<Literal>,
+        plan: Option<Arc<CachedPlan>>,
+        result_format: bool,
+    },
+    PureStatement {
+        statement: Statement,
+        result_format: bool,
+    },
+}
+
+impl Display for Portal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Portal::Empty => write!(f, "Empty"),
+            Portal::Portal { statement, .. } | Portal::PureStatement { statement, .. } => {
+                write!(f, "{}", statement)
+            }
+        }
+    }
+}
+
+/// The plan of a prepared statement, generated when the statement is parsed and re-used by all
+/// its executions. The parameters stay in the plan as placeholders, and are replaced by the values
+/// of each portal before the plan is scheduled.
+///
+/// As the values are unknown at planning time, predicates on parameters are not used to derive the
+/// scan ranges of tables.
+pub struct CachedPlan {
+    stmt_type: StatementType,
+    query_mode: QueryMode,
+    /// The plan split into stages.
+    query: Query,
+    schema: Schema,
+    time_travel_epoch: Option<u64>,
+    /// Privileges are checked again on each execution, as they may have been revoked.
+    check_items: Vec<ObjectCheckItem>,
+    /// The plan is generated against these versions of the catalog, of the worker nodes and of the
+    /// session configurations (e.g. `QUERY_MODE`), and becomes stale once any of them changes.
+    catalog_version: u64,
+    worker_node_version: u64,
+    config_version: u64,
+}
+
+impl CachedPlan {
+    fn new(
+        session: Arc<SessionImpl>,
+        statement: &Statement,
+        bound_statement: BoundStatement,
+    ) -> Result<Self> {
+        // Read the versions before planning, so that a change during planning makes the plan stale.
+        let catalog_version = session.env().catalog_reader().read_guard().version();
+        let worker_node_version = session.env().worker_node_manager().version();
+        let config_version = session.config_version();
+
+        let stmt_type = query::to_statement_type(statement)?;
+        let check_items = resolve_privileges(&bound_statement);
+        let context = OptimizerContext::new(
+            session.clone(),
+            Arc::from(statement.to_string().as_str()),
+            WithOptions::try_from(statement)?,
+        );
+        let BatchQuery {
+            query,
+            query_mode,
+            schema,
+            time_travel_epoch,
+        } = query::gen_batch_query(&session, context, stmt_type, bound_statement)?;
+        if query.has_source_scan() {
+            return Err(ErrorCode::NotImplemented(
+                "caching the plan of a prepared statement reading from sources".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+
+        Ok(Self {
+            stmt_type,
+            query_mode,
+            query,
+            schema,
+            time_travel_epoch,
+            check_items,
+            catalog_version,
+            worker_node_version,
+            config_version,
+        })
+    }
+
+    fn is_stale(&self, session: &SessionImpl) -> bool {
+        self.catalog_version != session.env().catalog_reader().read_guard().version()
+            || self.worker_node_version != session.env().worker_node_manager().version()
+            || self.config_version != session.config_version()
+    }
+
+    /// Creates a query to schedule from the plan with the values of parameters.
+    fn instantiate(&self, params: &[Literal]) -> BatchQuery {
+        let params = params.iter().map(Expr::to_expr_proto).collect::<Vec<_>>();
+        BatchQuery {
+            query: self.query.bind_parameters(&params),
+            query_mode: self.query_mode,
+            schema: self.schema.clone(),
+            time_travel_epoch: self.time_travel_epoch,
+        }
+    }
+}
+
+pub fn handle_parse(
+    session: Arc<SessionImpl>,
+    statement: Statement,
+    specified_param_types: Vec<Option<DataType>>,
+) -> Result<PrepareStatement> {
+    match statement {
+        Statement::Query(_)
+        | Statement::Insert { .. }
+        | Statement::Delete { .. }
+        | Statement::Update { .. } => {
+            let (bound_statement, param_types) = {
+                let mut binder = Binder::new_with_param_types(&session, specified_param_types);
+                let bound_statement = binder.bind(statement.clone())?;
+                (bound_statement, binder.export_param_types())
+            };
+            let plan = match CachedPlan::new(session, &statement, bound_statement.clone()) {
+                Ok(plan) => Some(Arc::new(plan)),
+                Err(e) => {
+                    tracing::debug!(
+                        "failed to plan the prepared statement, it will be planned on each execution: {}",
+                        e
+                    );
+                    None
+                }
+            };
+            Ok(PrepareStatement::Prepared {
+                statement,
+                bound_statement,
+                param_types,
+                plan,
+            })
+        }
+        _ => Ok(PrepareStatement::PureStatement(statement)),
+    }
+}
+
+pub fn handle_bind(
+    prepare_statement: PrepareStatement,
+    params: Vec<Option<Bytes>>,
+    param_format: bool,
+    result_format: bool,
+) -> Result<Portal> {
+    match prepare_statement {
+        PrepareStatement::Prepared {
+            statement,
+            bound_statement,
+            param_types,
+            plan,
+        } => {
+            let params = parse_parameters(&param_types, &params, param_format)?;
+            Ok(Portal::Portal {
+                statement,
+                bound_statement: bound_statement.bind_parameter(&params),
+                params,
+                plan,
+                result_format,
+            })
+        }
+        PrepareStatement::PureStatement(_) | PrepareStatement::Empty if !params.is_empty() => {
+            Err(ErrorCode::BindError(format!(
+                "bind message supplies {} parameters, but prepared statement requires 0",
+                params.len()
+            ))
+            .into())
+        }
+        PrepareStatement::PureStatement(statement) => Ok(Portal::PureStatement {
+            statement,
+            result_format,
+        }),
+        PrepareStatement::Empty => Ok(Portal::Empty),
+    }
+}
+
+pub async fn handle_execute(session: Arc<SessionImpl>, portal: Portal) -> Result<RwPgResponse> {
+    match portal {
+        Portal::Empty => Ok(PgResponse::empty_result(StatementType::EMPTY)),
+        Portal::Portal {
+            plan: Some(plan),
+            params,
+            result_format,
+            ..
+        } if !plan.is_stale(&session) => {
+            let query_start_time = Instant::now();
+            check_privileges(&session, &plan.check_items)?;
+            let batch_query = plan.instantiate(&params);
+            query::execute_batch_query(
+                session,
+                plan.stmt_type,
+                batch_query,
+                result_format,
+                query_start_time,
+            )
+            .await
+        }
+        Portal::Portal {
+            statement,
+            bound_statement,
+            result_format,
+            ..
+        } => {
+            let sql = statement.to_string();
+            let context = OptimizerContext::new(
+                session,
+                Arc::from(sql.as_str()),
+                WithOptions::try_from(&statement)?,
+            );
+            let stmt_type = query::to_statement_type(&statement)?;
+            query::handle_bound_query(context, stmt_type, bound_statement, result_format).await
+        }
+        Portal::PureStatement {
+            statement,
+            result_format,
+        } => {
+            let sql = statement.to_string();
+            handle(session, statement, &sql, result_format).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::ScalarImpl;
+    use risingwave_sqlparser::parser::Parser;
+
+    use super::*;
+    use crate::test_utils::LocalFrontend;
+
+    async fn create_frontend() -> LocalFrontend {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table test_table (id int, name varchar)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table test (index int, data varchar)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table coffees (sales int, cof_name varchar)")
+            .await
+            .unwrap();
+        frontend
+    }
+
+    /// Prepares `sql`, binds the prepared statement with `params` in text format, and returns
+    /// the inferred parameter types and the parsed values.
+    fn prepare_and_bind(
+        frontend: &LocalFrontend,
+        sql: &str,
+        specified_param_types: Vec<Option<DataType>>,
+        params: &[&str],
+    ) -> (Vec<DataType>, Vec<Literal>) {
+        let statement = Parser::parse_sql(sql).unwrap().swap_remove(0);
+        let prepare_statement =
+            handle_parse(frontend.session_ref(), statement, specified_param_types).unwrap();
+        let PrepareStatement::Prepared { param_types, .. } = &prepare_statement else {
+            panic!("expect prepared statement");
+        };
+        let param_types = param_types.clone();
+        let params = params
+            .iter()
+            .map(|param| Some(Bytes::copy_from_slice(param.as_bytes())))
+            .collect();
+        let Portal::Portal { params, .. } =
+            handle_bind(prepare_statement, params, false, false).unwrap() else {
+            panic!("expect portal");
+        };
+        (param_types, params)
+    }
+
+    fn int(v: i32) -> Literal {
+        Literal::new(Some(ScalarImpl::Int32(v)), DataType::Int32)
+    }
+
+    fn varchar(v: &str) -> Literal {
+        Literal::new(Some(ScalarImpl::Utf8(v.into())), DataType::Varchar)
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statement_without_param() {
+        let frontend = create_frontend().await;
+        let (param_types, params) =
+            prepare_and_bind(&frontend, "SELECT * FROM test_table", vec![], &[]);
+        assert!(param_types.is_empty());
+        assert!(params.is_empty());
+
+        let statement = Parser::parse_sql("SHOW TABLES").unwrap().swap_remove(0);
+        let prepare_statement = handle_parse(frontend.session_ref(), statement, vec![]).unwrap();
+        assert!(matches!(
+            prepare_statement,
+            PrepareStatement::PureStatement(_)
+        ));
+        assert!(handle_bind(prepare_statement, vec![None], false, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statement_with_explicit_param() {
+        let frontend = create_frontend().await;
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "SELECT * FROM test_table WHERE id = $1",
+            vec![Some(DataType::Int32)],
+            &["1"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32]);
+        assert_eq!(params, vec![int(1)]);
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "INSERT INTO test (index,data) VALUES ($1,$2)",
+            vec![Some(DataType::Int32), Some(DataType::Varchar)],
+            &["1", "DATA"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32, DataType::Varchar]);
+        assert_eq!(params, vec![int(1), varchar("DATA")]);
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "UPDATE COFFEES SET SALES = $1 WHERE COF_NAME LIKE $2",
+            vec![Some(DataType::Int32), Some(DataType::Varchar)],
+            &["1", "DATA"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32, DataType::Varchar]);
+        assert_eq!(params, vec![int(1), varchar("DATA")]);
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "SELECT * FROM test_table WHERE id = $1 AND name = $3",
+            vec![
+                Some(DataType::Int32),
+                Some(DataType::Varchar),
+                Some(DataType::Varchar),
+            ],
+            &["1", "DATA", "NAME"],
+        );
+        assert_eq!(
+            param_types,
+            vec![DataType::Int32, DataType::Varchar, DataType::Varchar]
+        );
+        assert_eq!(params, vec![int(1), varchar("DATA"), varchar("NAME")]);
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statement_with_implicit_param() {
+        let frontend = create_frontend().await;
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "SELECT * FROM test_table WHERE id = $1::INT",
+            vec![],
+            &["1"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32]);
+        assert_eq!(params, vec![int(1)]);
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "INSERT INTO test (index,data) VALUES ($1::INT4,$2::VARCHAR)",
+            vec![],
+            &["1", "DATA"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32, DataType::Varchar]);
+        assert_eq!(params, vec![int(1), varchar("DATA")]);
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "UPDATE COFFEES SET SALES = $1::INT WHERE COF_NAME LIKE $2::VARCHAR",
+            vec![],
+            &["1", "DATA"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32, DataType::Varchar]);
+        assert_eq!(params, vec![int(1), varchar("DATA")]);
+    }
+
+    #[tokio::test]
+    async fn test_cached_plan_stale_after_set() {
+        let frontend = create_frontend().await;
+        let session = frontend.session_ref();
+        let statement = Parser::parse_sql("SELECT $1::INT + 1")
+            .unwrap()
+            .swap_remove(0);
+        let PrepareStatement::Prepared { plan: Some(plan), .. } =
+            handle_parse(session.clone(), statement, vec![]).unwrap() else {
+            panic!("expect cached plan");
+        };
+        assert!(!plan.is_stale(&session));
+
+        // A failed `SET` changes nothing.
+        assert!(session
+            .set_config("query_mode", vec!["unknown".to_string()])
+            .is_err());
+        assert!(!plan.is_stale(&session));
+
+        session
+            .set_config("query_mode", vec!["distributed".to_string()])
+            .unwrap();
+        assert!(plan.is_stale(&session));
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statement_with_mix_param() {
+        let frontend = create_frontend().await;
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "SELECT * FROM test_table WHERE id = $1 AND name = $2::VARCHAR",
+            vec![Some(DataType::Int32)],
+            &["1", "DATA"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32, DataType::Varchar]);
+        assert_eq!(params, vec![int(1), varchar("DATA")]);
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "INSERT INTO test (index,data) VALUES ($1,$2)",
+            vec![Some(DataType::Int32)],
+            &["1", "DATA"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32, DataType::Varchar]);
+        assert_eq!(params, vec![int(1), varchar("DATA")]);
+
+        let (param_types, params) = prepare_and_bind(
+            &frontend,
+            "UPDATE COFFEES SET SALES = $1 WHERE COF_NAME LIKE $2::VARCHAR",
+            vec![Some(DataType::Int32)],
+            &["1", "DATA"],
+        );
+        assert_eq!(param_types, vec![DataType::Int32, DataType::Varchar]);
+        assert_eq!(params, vec![int(1), varchar("DATA")]);
+    }
+}
@@ -23,15 +23,19 @@ use pgwire::pg_response::{PgResponse, RowSetResult};
 use pgwire::pg_server::BoxedError;
 use pgwire::types::Row;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::{DropStatement, ObjectType, Statement};
+use risingwave_sqlparser::ast::{
+    CreateSourceStatement, DropStatement, ObjectType, Statement, WithProperties,
+};
 
 use self::util::DataChunkToRowSetAdapter;
 use crate::scheduler::{DistributedQueryStream, LocalQueryStream};
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::utils::WithOptions;
 
+mod alter_source;
 pub mod alter_user;
 mod create_database;
+mod create_function;
 pub mod create_index;
 pub mod create_mv;
 mod create_schema;
@@ -42,6 +46,7 @@ pub mod create_user;
 mod create_view;
 mod describe;
 mod drop_database;
+mod drop_function;
 mod drop_index;
 pub mod drop_mv;
 mod drop_schema;
@@ -51,6 +56,7 @@ pub mod drop_table;
 pub mod drop_user;
 mod drop_view;
 mod explain;
+pub mod extended_handle;
 mod flush;
 pub mod handle_privilege;
 pub mod privilege;
@@ -102,7 +108,7 @@ pub async fn handle(
             statement,
             analyze,
             options,
-        } => explain::handle_explain(context, *statement, options, analyze),
+        } => explain::handle_explain(context, *statement, options, analyze).await,
         Statement::CreateSource {
             is_materialized,
             stmt,
@@ -113,8 +119,9 @@ pub async fn handle(
             columns,
             constraints,
             query,
+            with_options,
+            source_schema,
 
-            with_options: _, // It is put in OptimizerContext
             // Not supported things
             or_replace,
             temporary,
@@ -137,6 +144,19 @@ pub async fn handle(
             if query.is_some() {
                 return Err(ErrorCode::NotImplemented("CREATE AS".to_string(), 6215.into()).into());
             }
+            // A table from a connector is created as a materialized source, whose messages are
+            // written to the table.
+            if let Some(source_schema) = source_schema {
+                let stmt = CreateSourceStatement {
+                    if_not_exists,
+                    columns,
+                    constraints,
+                    source_name: name,
+                    with_properties: WithProperties(with_options),
+                    source_schema,
+                };
+                return create_source::handle_create_source(context, true, stmt).await;
+            }
             create_table::handle_create_table(context, name, columns, constraints, if_not_exists)
                 .await
         }
@@ -150,6 +170,9 @@ pub async fn handle(
         } => create_schema::handle_create_schema(context, schema_name, if_not_exists).await,
         Statement::CreateUser(stmt) => create_user::handle_create_user(context, stmt).await,
         Statement::AlterUser(stmt) => alter_user::handle_alter_user(context, stmt).await,
+        Statement::AlterSource { name, operation } => {
+            alter_source::handle_alter_source(context, name, operation).await
+        }
         Statement::Grant { .. } => handle_privilege::handle_grant_privilege(context, stmt).await,
         Statement::Revoke { .. } => handle_privilege::handle_revoke_privilege(context, stmt).await,
         Statement::Describe { name } => describe::handle_describe(context, name),
@@ -190,6 +213,9 @@ pub async fn handle(
                 drop_user::handle_drop_user(context, object_name, if_exists, drop_mode.into()).await
             }
             ObjectType::View => drop_view::handle_drop_view(context, object_name, if_exists).await,
+            ObjectType::Function => {
+                drop_function::handle_drop_function(context, object_name, if_exists).await
+            }
             ObjectType::MaterializedSource => Err((ErrorCode::InvalidInputSyntax(
                 "Use `DROP SOURCE` to drop a materialized source.".to_owned(),
             ))
@@ -221,6 +247,22 @@ pub async fn handle(
                 create_view::handle_create_view(context, name, columns, *query).await
             }
         }
+        Statement::CreateFunction {
+            or_replace,
+            name,
+            args,
+            return_type,
+            bodies,
+        } => {
+            if or_replace {
+                return Err(ErrorCode::NotImplemented(
+                    "CREATE OR REPLACE FUNCTION".to_string(),
+                    None.into(),
+                )
+                .into());
+            }
+            create_function::handle_create_function(context, name, args, return_type, bodies).await
+        }
         Statement::Flush => flush::handle_flush(context).await,
         Statement::SetVariable {
             local: _,
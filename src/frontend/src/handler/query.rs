@@ -50,6 +50,17 @@ pub fn gen_batch_query_plan(
         binder.bind(stmt)?
     };
 
+    gen_batch_query_plan_for_bound(session, context, stmt_type, bound)
+}
+
+/// Generates the batch plan of a statement that has been bound, e.g. a prepared statement with its
+/// parameters bound to values.
+fn gen_batch_query_plan_for_bound(
+    session: &SessionImpl,
+    context: OptimizerContextRef,
+    stmt_type: StatementType,
+    bound: BoundStatement,
+) -> Result<(PlanRef, QueryMode, Schema)> {
     let check_items = resolve_privileges(&bound);
     check_privileges(session, &check_items)?;
 
@@ -78,6 +89,13 @@ pub fn gen_batch_query_plan(
     };
 
     let mut logical = planner.plan(bound)?;
+    if must_dist && planner.ctx().time_travel_epoch().is_some() {
+        return Err(ErrorCode::NotImplemented(
+            "FOR SYSTEM_TIME AS OF '<timestamp>' in DML statements".to_string(),
+            None.into(),
+        )
+        .into());
+    }
     let schema = logical.schema().clone();
 
     let physical = match query_mode {
@@ -93,25 +111,79 @@ pub async fn handle_query(
     format: bool,
 ) -> Result<RwPgResponse> {
     let stmt_type = to_statement_type(&stmt)?;
+    let bound = {
+        let mut binder = Binder::new(&context.session_ctx);
+        binder.bind(stmt)?
+    };
+    handle_bound_query(context, stmt_type, bound, format).await
+}
+
+/// Executes a statement that has been bound. It's used by the extended query protocol to plan a
+/// prepared statement on each execution, when its plan can't be cached.
+pub async fn handle_bound_query(
+    context: OptimizerContext,
+    stmt_type: StatementType,
+    bound: BoundStatement,
+    format: bool,
+) -> Result<RwPgResponse> {
     let session = context.session_ctx.clone();
     let query_start_time = Instant::now();
+    let plan = gen_batch_query(&session, context, stmt_type, bound)?;
+    execute_batch_query(session, stmt_type, plan, format, query_start_time).await
+}
 
-    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
-    let (query, query_mode, output_schema) = {
-        let (plan, query_mode, schema) = gen_batch_query_plan(&session, context.into(), stmt)?;
-
-        tracing::trace!(
-            "Generated query plan: {:?}, query_mode:{:?}",
-            plan.explain_to_string()?,
-            query_mode
-        );
-        let plan_fragmenter = BatchPlanFragmenter::new(
-            session.env().worker_node_manager_ref(),
-            session.env().catalog_reader().clone(),
-        );
-        (plan_fragmenter.split(plan)?, query_mode, schema)
-    };
+/// A batch query that has been planned and split into stages, ready to be scheduled.
+pub struct BatchQuery {
+    pub query: Query,
+    pub query_mode: QueryMode,
+    pub schema: Schema,
+    pub time_travel_epoch: Option<u64>,
+}
+
+/// Plans a statement that has been bound and splits the plan into stages.
+pub fn gen_batch_query(
+    session: &SessionImpl,
+    context: OptimizerContext,
+    stmt_type: StatementType,
+    bound: BoundStatement,
+) -> Result<BatchQuery> {
+    let context: OptimizerContextRef = context.into();
+    let (plan, query_mode, schema) =
+        gen_batch_query_plan_for_bound(session, context.clone(), stmt_type, bound)?;
+
+    tracing::trace!(
+        "Generated query plan: {:?}, query_mode:{:?}",
+        plan.explain_to_string()?,
+        query_mode
+    );
+    let plan_fragmenter = BatchPlanFragmenter::new(
+        session.env().worker_node_manager_ref(),
+        session.env().catalog_reader().clone(),
+    );
+    let query = plan_fragmenter.split(plan)?;
     tracing::trace!("Generated query after plan fragmenter: {:?}", &query);
+    Ok(BatchQuery {
+        query,
+        query_mode,
+        schema,
+        time_travel_epoch: context.time_travel_epoch(),
+    })
+}
+
+/// Executes a batch query and returns the response.
+pub async fn execute_batch_query(
+    session: Arc<SessionImpl>,
+    stmt_type: StatementType,
+    batch_query: BatchQuery,
+    format: bool,
+    query_start_time: Instant,
+) -> Result<RwPgResponse> {
+    let BatchQuery {
+        query,
+        query_mode,
+        schema: output_schema,
+        time_travel_epoch,
+    } = batch_query;
 
     let pg_descs = output_schema
         .fields()
@@ -129,7 +201,14 @@ pub async fn handle_query(
         // TODO: if there's no table scan, we don't need to acquire snapshot.
         let hummock_snapshot_manager = session.env().hummock_snapshot_manager();
         let query_id = query.query_id().clone();
-        let pinned_snapshot = hummock_snapshot_manager.acquire(&query_id).await?;
+        let pinned_snapshot = match time_travel_epoch {
+            Some(epoch) => {
+                hummock_snapshot_manager
+                    .acquire_as_of(&query_id, epoch)
+                    .await?
+            }
+            None => hummock_snapshot_manager.acquire(&query_id).await?,
+        };
 
         match query_mode {
             QueryMode::Local => PgResponseStream::LocalQuery(DataChunkToRowSetAdapter::new(
@@ -204,7 +283,7 @@ pub async fn handle_query(
     ))
 }
 
-fn to_statement_type(stmt: &Statement) -> Result<StatementType> {
+pub fn to_statement_type(stmt: &Statement) -> Result<StatementType> {
     use StatementType::*;
 
     match stmt {
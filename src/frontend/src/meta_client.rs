@@ -28,6 +28,8 @@ use risingwave_rpc_client::{HummockMetaClient, MetaClient};
 pub trait FrontendMetaClient: Send + Sync {
     async fn pin_snapshot(&self) -> Result<HummockSnapshot>;
 
+    async fn pin_specific_snapshot(&self, epoch: u64) -> Result<HummockSnapshot>;
+
     async fn get_epoch(&self) -> Result<HummockSnapshot>;
 
     async fn flush(&self, checkpoint: bool) -> Result<HummockSnapshot>;
@@ -50,6 +52,10 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
         self.0.pin_snapshot().await
     }
 
+    async fn pin_specific_snapshot(&self, epoch: u64) -> Result<HummockSnapshot> {
+        self.0.pin_specific_snapshot(epoch).await
+    }
+
     async fn get_epoch(&self) -> Result<HummockSnapshot> {
         self.0.get_epoch().await
     }
@@ -53,7 +53,8 @@ impl ObserverState for FrontendObserverNode {
             | Info::Source(_)
             | Info::Index(_)
             | Info::Sink(_)
-            | Info::View(_) => {
+            | Info::View(_)
+            | Info::Function(_) => {
                 self.handle_catalog_notification(resp);
             }
             Info::Node(node) => {
@@ -112,6 +113,9 @@ impl ObserverState for FrontendObserverNode {
         for view in snapshot.views {
             catalog_guard.create_view(&view)
         }
+        for function in snapshot.functions {
+            catalog_guard.create_function(&function)
+        }
         self.worker_node_manager.refresh(
             snapshot.nodes,
             snapshot
@@ -189,6 +193,7 @@ impl FrontendObserverNode {
                 Operation::Delete => {
                     catalog_guard.drop_source(source.database_id, source.schema_id, source.id)
                 }
+                Operation::Update => catalog_guard.update_source(source),
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
             Info::Sink(sink) => match resp.operation() {
@@ -212,6 +217,15 @@ impl FrontendObserverNode {
                 }
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
+            Info::Function(function) => match resp.operation() {
+                Operation::Add => catalog_guard.create_function(function),
+                Operation::Delete => catalog_guard.drop_function(
+                    function.database_id,
+                    function.schema_id,
+                    function.id,
+                ),
+                _ => panic!("receive an unsupported notify {:?}", resp),
+            },
             _ => unreachable!(),
         }
         assert!(
@@ -38,7 +38,6 @@ use self::plan_node::{
 };
 use self::plan_visitor::{
     has_batch_exchange, has_batch_seq_scan, has_batch_seq_scan_where, has_logical_apply,
-    has_logical_over_agg,
 };
 use self::property::RequiredDist;
 use self::rule::*;
@@ -328,14 +327,6 @@ impl PlanRoot {
             ],
             ApplyOrder::TopDown,
         );
-        if has_logical_over_agg(plan.clone()) {
-            return Err(ErrorCode::InternalError(format!(
-                "OverAgg can not be transformed. Plan:\n{}",
-                plan.explain_to_string().unwrap()
-            ))
-            .into());
-        }
-
         Ok(plan)
     }
 
@@ -445,6 +436,15 @@ impl PlanRoot {
         let ctx = self.plan.ctx();
         let explain_trace = ctx.is_explain_trace();
 
+        // A streaming job always reads the latest changes of the tables.
+        if ctx.time_travel_epoch().is_some() {
+            return Err(ErrorCode::NotImplemented(
+                "FOR SYSTEM_TIME AS OF '<timestamp>' in streaming queries".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+
         let plan = match self.plan.convention() {
             Convention::Logical => {
                 let plan = self.gen_optimized_logical_plan()?;
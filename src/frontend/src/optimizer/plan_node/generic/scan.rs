@@ -36,6 +36,8 @@ pub struct Scan {
     pub indexes: Vec<Rc<IndexCatalog>>,
     /// The pushed down predicates. It refers to column indexes of the table.
     pub predicate: Condition,
+    /// Whether the table is read as of the process time, i.e. `FOR SYSTEM_TIME AS OF PROCTIME()`.
+    pub for_system_time_as_of_proctime: bool,
 }
 
 impl GenericPlanNode for Scan {
@@ -18,11 +18,13 @@ use std::rc::Rc;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::types::DataType;
 use risingwave_common::util::sort_util::OrderType;
+use risingwave_source::dead_letter::{ErrorPolicy, DEAD_LETTER_SOURCE_ID_KEY};
 
 use super::super::utils::TableCatalogBuilder;
 use super::{GenericPlanNode, GenericPlanRef};
 use crate::catalog::source_catalog::SourceCatalog;
 use crate::session::OptimizerContextRef;
+use crate::utils::WithOptions;
 use crate::TableCatalog;
 
 /// [`Source`] returns contents of a table or other equivalent object
@@ -89,4 +91,39 @@ impl Source {
 
         builder.build(vec![])
     }
+
+    /// Infers the table of the messages failed to parse, which is only needed if the error policy
+    /// of the source is dead letter.
+    pub fn infer_dead_letter_table_catalog(
+        &self,
+        me: &impl GenericPlanRef,
+    ) -> Option<TableCatalog> {
+        if ErrorPolicy::from_properties(&self.catalog.properties).ok()
+            != Some(ErrorPolicy::DeadLetter)
+        {
+            return None;
+        }
+        // Records the source in the properties, so that the dead letters of a source can be found
+        // by `rw_dead_letters`.
+        let mut properties = me
+            .ctx()
+            .inner()
+            .with_options
+            .internal_table_subset()
+            .into_inner();
+        properties.insert(
+            DEAD_LETTER_SOURCE_ID_KEY.to_string(),
+            self.catalog.id.to_string(),
+        );
+        let mut builder = TableCatalogBuilder::new(WithOptions::new(properties));
+
+        let split_id_idx = builder.add_column(&Field::with_name(DataType::Varchar, "split_id"));
+        let offset_idx = builder.add_column(&Field::with_name(DataType::Varchar, "offset"));
+        builder.add_column(&Field::with_name(DataType::Bytea, "payload"));
+        builder.add_column(&Field::with_name(DataType::Varchar, "error"));
+        builder.add_order_column(split_id_idx, OrderType::Ascending);
+        builder.add_order_column(offset_idx, OrderType::Ascending);
+
+        Some(builder.build(vec![]))
+    }
 }
@@ -17,15 +17,17 @@ use std::fmt;
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
-use risingwave_common::catalog::Schema;
+use risingwave_common::catalog::{Schema, TableDesc};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_pb::plan_common::JoinType;
 
 use super::generic::GenericPlanNode;
 use super::{
     generic, BatchProject, ColPrunable, CollectInputRef, LogicalProject, PlanBase, PlanRef,
-    PlanTreeNodeBinary, PredicatePushdown, StreamHashJoin, StreamProject, ToBatch, ToStream,
+    PlanTreeNodeBinary, PredicatePushdown, StreamHashJoin, StreamProject, StreamTemporalJoin,
+    ToBatch, ToStream,
 };
+use crate::catalog::ColumnId;
 use crate::expr::{Expr, ExprImpl, ExprRewriter, ExprType, InputRef};
 use crate::optimizer::max_one_row_visitor::MaxOneRowVisitor;
 use crate::optimizer::plan_node::generic::GenericPlanRef;
@@ -50,6 +52,20 @@ pub struct LogicalJoin {
     core: generic::Join<PlanRef>,
 }
 
+/// The parts of a join whose right side is looked up from the storage of a table, i.e. a batch
+/// lookup join or a stream temporal join.
+struct LookupJoinParts {
+    /// The join whose right side is the scan of the table without predicates.
+    logical_join: LogicalJoin,
+    /// The join predicate, whose first `lookup_prefix_len` equal keys are the prefix of the
+    /// primary key of the table.
+    predicate: EqJoinPredicate,
+    table_desc: TableDesc,
+    /// The ids of the columns output by the scan of the table.
+    output_column_ids: Vec<ColumnId>,
+    lookup_prefix_len: usize,
+}
+
 impl fmt::Display for LogicalJoin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let verbose = self.base.ctx.is_explain_verbose();
@@ -479,6 +495,26 @@ impl LogicalJoin {
             _ => return None,
         };
 
+        let lookup = self.to_lookup_join_parts(predicate, logical_join)?;
+        Some(BatchLookupJoin::new(
+            lookup.logical_join,
+            lookup.predicate,
+            lookup.table_desc,
+            lookup.output_column_ids,
+            lookup.lookup_prefix_len,
+            false,
+        ))
+    }
+
+    /// Rewrites the join so that its right side, which must be a table scan, can be looked up
+    /// from the storage of the table directly. The right join key must contain a prefix of the
+    /// primary key of the table that also contains its distribution key. `logical_join` is the
+    /// join whose left side has been converted.
+    fn to_lookup_join_parts(
+        &self,
+        predicate: EqJoinPredicate,
+        logical_join: LogicalJoin,
+    ) -> Option<LookupJoinParts> {
         let right = self.right();
         // Lookup Join only supports basic tables on the join's right side.
         let logical_scan: &LogicalScan = right.as_logical_scan()?;
@@ -506,13 +542,16 @@ impl LogicalJoin {
         };
 
         // Reorder the join equal predicate to match the order key.
+        // Stop at the first order column not in the join key, as only a prefix can be looked up.
         let mut reorder_idx = vec![];
         for order_col_id in order_col_ids {
-            for (i, eq_idx) in predicate.right_eq_indexes().into_iter().enumerate() {
-                if order_col_id == output_column_ids[eq_idx] {
-                    reorder_idx.push(i);
-                    break;
-                }
+            match predicate
+                .right_eq_indexes()
+                .into_iter()
+                .position(|eq_idx| order_col_id == output_column_ids[eq_idx])
+            {
+                Some(i) => reorder_idx.push(i),
+                None => break,
             }
         }
         if reorder_idx.len() < at_least_prefix_len {
@@ -615,14 +654,13 @@ impl LogicalJoin {
             new_join_output_indices,
         );
 
-        Some(BatchLookupJoin::new(
-            new_logical_join,
-            new_predicate,
+        Some(LookupJoinParts {
+            logical_join: new_logical_join,
+            predicate: new_predicate,
             table_desc,
-            new_scan_output_column_ids,
+            output_column_ids: new_scan_output_column_ids,
             lookup_prefix_len,
-            false,
-        ))
+        })
     }
 
     pub fn decompose(self) -> (PlanRef, PlanRef, Condition, JoinType, Vec<usize>) {
@@ -932,6 +970,45 @@ impl LogicalJoin {
         }
     }
 
+    fn to_stream_temporal_join(&self, predicate: EqJoinPredicate) -> Result<PlanRef> {
+        let not_supported = |reason: &str| {
+            RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "Temporal join {}",
+                reason
+            )))
+        };
+        if !matches!(self.join_type(), JoinType::Inner | JoinType::LeftOuter) {
+            return Err(not_supported(
+                "only supports inner join and left outer join",
+            ));
+        }
+        if !predicate.has_eq() {
+            return Err(not_supported("requires an equal join condition"));
+        }
+
+        let left = self.left().to_stream()?;
+        if !left.append_only() {
+            return Err(not_supported("requires an append-only left input"));
+        }
+        let logical_join = self.clone_with_left_right(left, self.right());
+        let lookup = self
+            .to_lookup_join_parts(predicate, logical_join)
+            .ok_or_else(|| {
+                not_supported(
+                    "requires the join key to contain a prefix of the primary key of the right table that contains its distribution key",
+                )
+            })?;
+
+        Ok(StreamTemporalJoin::new(
+            lookup.logical_join,
+            lookup.predicate,
+            lookup.table_desc,
+            lookup.output_column_ids,
+            lookup.lookup_prefix_len,
+        )
+        .into())
+    }
+
     fn to_stream_dynamic_filter(&self, predicate: EqJoinPredicate) -> Result<Option<PlanRef>> {
         assert!(!predicate.has_eq());
         // If there is exactly one predicate, it is a comparison (<, <=, >, >=), and the
@@ -1118,7 +1195,9 @@ impl ToStream for LogicalJoin {
             self.on().clone(),
         );
 
-        if predicate.has_eq() {
+        if let Some(scan) = self.right().as_logical_scan() && scan.for_system_time_as_of_proctime() {
+            self.to_stream_temporal_join(predicate)
+        } else if predicate.has_eq() {
             self.to_stream_hash_join(predicate)
         } else if let Some(dynamic_filter) = self.to_stream_dynamic_filter(predicate)? {
             Ok(dynamic_filter)
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::fmt;
 
 use fixedbitset::FixedBitSet;
@@ -19,24 +20,94 @@ use itertools::Itertools;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_expr::expr::AggKind;
+use risingwave_expr::window_function::Frame;
+use risingwave_pb::expr::window_function::{GeneralType, Type as ProstWindowFuncType};
+use risingwave_pb::expr::WindowFunction as ProstWindowFunction;
 
 use super::generic::{PlanAggOrderByField, PlanAggOrderByFieldDisplay};
+use super::utils::TableCatalogBuilder;
 use super::{
-    gen_filter_and_pushdown, ColPrunable, LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchOverAgg, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, StreamOverAgg, ToBatch, ToStream,
 };
 use crate::expr::{Expr, ExprImpl, InputRef, InputRefDisplay, WindowFunction, WindowFunctionType};
+use crate::optimizer::property::{FieldOrder, Order, RequiredDist};
 use crate::utils::{ColIndexMapping, Condition};
+use crate::TableCatalog;
 
 /// Rewritten version of [`WindowFunction`] which uses `InputRef` instead of `ExprImpl`.
 #[derive(Debug, Clone)]
 pub struct PlanWindowFunction {
     pub function_type: WindowFunctionType,
     pub return_type: DataType,
+    pub args: Vec<InputRef>,
     pub partition_by: Vec<InputRef>,
     /// TODO: rename & move `PlanAggOrderByField` so that it can be better shared like
     /// [`crate::expr::OrderByExpr`]
     pub order_by: Vec<PlanAggOrderByField>,
+    pub frame: Frame,
+}
+
+impl PlanWindowFunction {
+    /// Whether the two window functions are over the same window, i.e. with the same `PARTITION
+    /// BY` and `ORDER BY`, so that they can be evaluated by the same `OverAgg`.
+    fn same_window(&self, other: &Self) -> bool {
+        self.partition_by == other.partition_by
+            && self.order_by.len() == other.order_by.len()
+            && self.order_by.iter().zip_eq(&other.order_by).all(|(a, b)| {
+                a.input == b.input && a.direction == b.direction && a.nulls_first == b.nulls_first
+            })
+    }
+
+    fn rewrite_with_input_col_change(&self, input_col_change: &ColIndexMapping) -> Self {
+        let rewrite = |input_ref: &InputRef| {
+            InputRef::new(
+                input_col_change.map(input_ref.index),
+                input_ref.data_type.clone(),
+            )
+        };
+        Self {
+            function_type: self.function_type,
+            return_type: self.return_type.clone(),
+            args: self.args.iter().map(rewrite).collect(),
+            partition_by: self.partition_by.iter().map(rewrite).collect(),
+            order_by: self
+                .order_by
+                .iter()
+                .map(|field| PlanAggOrderByField {
+                    input: rewrite(&field.input),
+                    direction: field.direction,
+                    nulls_first: field.nulls_first,
+                })
+                .collect(),
+            frame: self.frame,
+        }
+    }
+
+    pub fn to_protobuf(&self) -> ProstWindowFunction {
+        let general = |general: GeneralType| ProstWindowFuncType::General(general as i32);
+        let r#type = match self.function_type {
+            WindowFunctionType::RowNumber => general(GeneralType::RowNumber),
+            WindowFunctionType::Rank => general(GeneralType::Rank),
+            WindowFunctionType::DenseRank => general(GeneralType::DenseRank),
+            // `LAG` and `LEAD` are `FIRST_VALUE` over a single-row frame.
+            WindowFunctionType::Lag | WindowFunctionType::Lead | WindowFunctionType::FirstValue => {
+                general(GeneralType::FirstValue)
+            }
+            WindowFunctionType::LastValue => general(GeneralType::LastValue),
+            WindowFunctionType::Aggregate(kind) => {
+                ProstWindowFuncType::Aggregate(kind.to_prost() as i32)
+            }
+        };
+        ProstWindowFunction {
+            r#type: Some(r#type),
+            args: self.args.iter().map(InputRef::to_agg_arg_proto).collect(),
+            return_type: Some(self.return_type.to_protobuf()),
+            frame: Some(self.frame.to_protobuf()),
+        }
+    }
 }
 
 struct PlanWindowFunctionDisplay<'a> {
@@ -51,11 +122,28 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
             f.debug_struct("WindowFunction")
                 .field("function_type", &window_function.function_type)
                 .field("return_type", &window_function.return_type)
+                .field("args", &window_function.args)
                 .field("partition_by", &window_function.partition_by)
                 .field("order_by", &window_function.order_by)
+                .field("frame", &format_args!("{}", window_function.frame))
                 .finish()
         } else {
-            write!(f, "{}() OVER(", window_function.function_type)?;
+            let input_ref_display =
+                |input_ref: &InputRef, f: &mut dyn FnMut(&dyn fmt::Display) -> fmt::Result| {
+                    f(&InputRefDisplay {
+                        input_ref,
+                        input_schema: self.input_schema,
+                    })
+                };
+            write!(
+                f,
+                "{}({}) OVER(",
+                window_function.function_type,
+                window_function
+                    .args
+                    .iter()
+                    .format_with(", ", input_ref_display)
+            )?;
 
             let mut delim = "";
             if !window_function.partition_by.is_empty() {
@@ -66,12 +154,7 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
                     window_function
                         .partition_by
                         .iter()
-                        .format_with(", ", |input_ref, f| {
-                            f(&InputRefDisplay {
-                                input_ref,
-                                input_schema: self.input_schema,
-                            })
-                        })
+                        .format_with(", ", input_ref_display)
                 )?;
             }
             if !window_function.order_by.is_empty() {
@@ -85,6 +168,10 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
                         })
                     })
                 )?;
+                delim = " ";
+            }
+            if !window_function.function_type.is_rank_function() {
+                write!(f, "{delim}{}", window_function.frame)?;
             }
             f.write_str(")")?;
 
@@ -95,27 +182,30 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
 
 /// `LogicalOverAgg` performs `OVER` window aggregates ([`WindowFunction`]) to its input.
 ///
-/// The output schema is the input schema plus the window functions.
+/// The output schema is the input schema plus the window functions. All window functions of an
+/// `LogicalOverAgg` are over the same window, i.e. with the same `PARTITION BY` and `ORDER BY`.
 #[derive(Debug, Clone)]
 pub struct LogicalOverAgg {
     pub base: PlanBase,
-    pub window_function: PlanWindowFunction,
+    pub window_functions: Vec<PlanWindowFunction>,
     input: PlanRef,
 }
 
 impl LogicalOverAgg {
-    fn new(window_function: PlanWindowFunction, input: PlanRef) -> Self {
+    fn new(window_functions: Vec<PlanWindowFunction>, input: PlanRef) -> Self {
+        assert!(!window_functions.is_empty());
         let ctx = input.ctx();
         let mut schema = input.schema().clone();
-        schema.fields.push(Field::with_name(
-            window_function.return_type.clone(),
-            window_function.function_type.to_string(),
-        ));
+        for window_function in &window_functions {
+            schema.fields.push(Field::with_name(
+                window_function.return_type.clone(),
+                window_function.function_type.to_string(),
+            ));
+        }
 
         let logical_pk = input.logical_pk().to_vec();
 
-        let mapping =
-            ColIndexMapping::identity_or_none(input.schema().len(), input.schema().len() + 1);
+        let mapping = ColIndexMapping::identity_or_none(input.schema().len(), schema.len());
         let fd_set = input.functional_dependency().clone();
         let fd_set = mapping.rewrite_functional_dependency_set(fd_set);
 
@@ -123,27 +213,39 @@ impl LogicalOverAgg {
 
         Self {
             base,
-            window_function,
+            window_functions,
             input,
         }
     }
 
+    /// Plans the window functions in `select_exprs` as a stack of `LogicalOverAgg`s, one for each
+    /// distinct window, and replaces them with `InputRef`s to the output columns.
     pub fn create(
         input: PlanRef,
         mut select_exprs: Vec<ExprImpl>,
     ) -> Result<(PlanRef, Vec<ExprImpl>)> {
         let input_len = input.schema().len();
-        let mut window_funcs = vec![];
-        for expr in &mut select_exprs {
+        // The window functions grouped by window, and the position of each window function in
+        // the select list.
+        let mut windows: Vec<Vec<PlanWindowFunction>> = vec![];
+        let mut positions = vec![];
+        for (i, expr) in select_exprs.iter().enumerate() {
             if let ExprImpl::WindowFunction(f) = expr {
-                window_funcs.push(*(f.clone()));
-                *expr = InputRef::new(
-                    input_len + window_funcs.len() - 1,
-                    expr.return_type().clone(),
-                )
-                .into();
-            }
-            if expr.has_window_function() {
+                let window_function = Self::rewrite_window_function(*f.clone())?;
+                match windows
+                    .iter()
+                    .position(|w| w[0].same_window(&window_function))
+                {
+                    Some(w) => {
+                        positions.push((i, w, windows[w].len()));
+                        windows[w].push(window_function);
+                    }
+                    None => {
+                        positions.push((i, windows.len(), 0));
+                        windows.push(vec![window_function]);
+                    }
+                }
+            } else if expr.has_window_function() {
                 return Err(ErrorCode::NotImplemented(
                     format!("window function in expression: {:?}", expr),
                     None.into(),
@@ -151,42 +253,64 @@ impl LogicalOverAgg {
                 .into());
             }
         }
-        for f in &window_funcs {
-            if f.function_type.is_rank_function() {
-                if f.order_by.sort_exprs.is_empty() {
-                    return Err(ErrorCode::InvalidInputSyntax(format!(
-                        "window rank function without order by: {:?}",
-                        f
-                    ))
-                    .into());
-                }
-                if f.function_type == WindowFunctionType::DenseRank {
-                    return Err(ErrorCode::NotImplemented(
-                        format!("window rank function: {}", f.function_type),
-                        4847.into(),
-                    )
-                    .into());
-                }
-            }
+
+        let mut window_offsets = Vec::with_capacity(windows.len());
+        let mut offset = input_len;
+        for window in &windows {
+            window_offsets.push(offset);
+            offset += window.len();
+        }
+        for (i, w, j) in positions {
+            let return_type = select_exprs[i].return_type();
+            select_exprs[i] = InputRef::new(window_offsets[w] + j, return_type).into();
+        }
+
+        let mut plan = input;
+        for window in windows {
+            plan = Self::new(window, plan).into();
+        }
+        Ok((plan, select_exprs))
+    }
+
+    fn rewrite_window_function(f: WindowFunction) -> Result<PlanWindowFunction> {
+        if f.function_type.is_rank_function() && f.order_by.sort_exprs.is_empty() {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "window rank function without order by: {:?}",
+                f
+            ))
+            .into());
         }
-        if window_funcs.len() > 1 {
+        if let WindowFunctionType::Aggregate(kind @ (AggKind::Avg | AggKind::Sum0)) =
+            f.function_type
+        {
             return Err(ErrorCode::NotImplemented(
-                "Multiple window functions".to_string(),
+                format!("aggregate function as window function: {}", kind),
                 None.into(),
             )
             .into());
         }
+
         let WindowFunction {
             args,
             return_type,
             function_type,
             partition_by,
             order_by,
-        } = window_funcs.into_iter().next().unwrap();
-        assert!(args.is_empty());
-        assert!(return_type == DataType::Int64);
+            frame,
+        } = f;
 
-        // TODO: rewrite ORDER BY & PARTITION BY expr to InputRef like `LogicalAgg`
+        // TODO: rewrite the arguments, ORDER BY & PARTITION BY expr to InputRef like `LogicalAgg`
+        let args = args
+            .into_iter()
+            .map(|e| match e.as_input_ref() {
+                Some(i) => Ok(*i.clone()),
+                None => Err(ErrorCode::NotImplemented(
+                    "argument expression in window function".to_string(),
+                    None.into(),
+                )
+                .into()),
+            })
+            .collect::<Result<Vec<_>>>()?;
         let order_by = order_by
             .sort_exprs
             .into_iter()
@@ -215,16 +339,100 @@ impl LogicalOverAgg {
             })
             .collect::<Result<Vec<_>>>()?;
 
-        let over_agg = Self::new(
-            PlanWindowFunction {
-                function_type,
-                return_type,
-                partition_by,
-                order_by,
-            },
-            input,
+        Ok(PlanWindowFunction {
+            function_type,
+            return_type,
+            args,
+            partition_by,
+            order_by,
+            frame,
+        })
+    }
+
+    pub fn window_functions(&self) -> &[PlanWindowFunction] {
+        &self.window_functions
+    }
+
+    /// The indices of the `PARTITION BY` columns in the input.
+    pub fn partition_key_indices(&self) -> Vec<usize> {
+        self.window_functions[0]
+            .partition_by
+            .iter()
+            .map(|i| i.index)
+            .unique()
+            .collect()
+    }
+
+    /// The `ORDER BY` of the window.
+    pub fn order_key(&self) -> Order {
+        Order::new(
+            self.window_functions[0]
+                .order_by
+                .iter()
+                .map(|f| FieldOrder {
+                    index: f.input.index,
+                    direct: f.direction,
+                })
+                .collect(),
+        )
+    }
+
+    /// The order that the input is required to be sorted by, i.e. first by the `PARTITION BY`
+    /// columns and then by the `ORDER BY`.
+    pub fn input_order(&self) -> Order {
+        let mut field_order = self
+            .partition_key_indices()
+            .into_iter()
+            .map(FieldOrder::ascending)
+            .collect_vec();
+        field_order.extend(self.order_key().field_order);
+        Order::new(field_order)
+    }
+
+    /// Infers the state table of the streaming `OverAgg`, which stores all input rows ordered
+    /// by the partition key, the order key and then the stream key, so that the rows of a
+    /// partition can be scanned in order with the partition key as prefix.
+    pub fn infer_state_table(&self) -> TableCatalog {
+        let input = self.input();
+        let mut builder =
+            TableCatalogBuilder::new(self.base.ctx.inner().with_options.internal_table_subset());
+        input.schema().fields().iter().for_each(|field| {
+            builder.add_column(field);
+        });
+
+        let mut order_cols = HashSet::new();
+        for idx in self.partition_key_indices() {
+            if order_cols.insert(idx) {
+                builder.add_order_column(idx, OrderType::Ascending);
+            }
+        }
+        for field_order in self.order_key().field_order {
+            if order_cols.insert(field_order.index) {
+                builder.add_order_column(field_order.index, OrderType::from(field_order.direct));
+            }
+        }
+        for &idx in input.logical_pk() {
+            if order_cols.insert(idx) {
+                builder.add_order_column(idx, OrderType::Ascending);
+            }
+        }
+        builder.build(self.partition_key_indices())
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let mut builder = f.debug_struct(name);
+        builder.field(
+            "window_functions",
+            &self
+                .window_functions
+                .iter()
+                .map(|window_function| PlanWindowFunctionDisplay {
+                    window_function,
+                    input_schema: self.input.schema(),
+                })
+                .collect_vec(),
         );
-        Ok((over_agg.into(), select_exprs))
+        builder.finish()
     }
 }
 
@@ -234,7 +442,36 @@ impl PlanTreeNodeUnary for LogicalOverAgg {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        Self::new(self.window_function.clone(), input)
+        Self::new(self.window_functions.clone(), input)
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let window_functions = self
+            .window_functions
+            .iter()
+            .map(|f| f.rewrite_with_input_col_change(&input_col_change))
+            .collect();
+        let new_input_len = input.schema().len();
+        let old_input_len = self.input.schema().len();
+        let over_agg = Self::new(window_functions, input);
+        let out_col_change = ColIndexMapping::with_target_size(
+            (0..self.schema().len())
+                .map(|i| {
+                    if i < old_input_len {
+                        input_col_change.try_map(i)
+                    } else {
+                        Some(i - old_input_len + new_input_len)
+                    }
+                })
+                .collect(),
+            over_agg.schema().len(),
+        );
+        (over_agg, out_col_change)
     }
 }
 
@@ -242,15 +479,7 @@ impl_plan_tree_node_for_unary! { LogicalOverAgg }
 
 impl fmt::Display for LogicalOverAgg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut builder = f.debug_struct("LogicalOverAgg");
-        builder.field(
-            "window_function",
-            &PlanWindowFunctionDisplay {
-                window_function: &self.window_function,
-                input_schema: self.input.schema(),
-            },
-        );
-        builder.finish()
+        self.fmt_with_name(f, "LogicalOverAgg")
     }
 }
 
@@ -264,7 +493,7 @@ impl ColPrunable for LogicalOverAgg {
 impl PredicatePushdown for LogicalOverAgg {
     fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
         let mut window_col = FixedBitSet::with_capacity(self.schema().len());
-        window_col.insert(self.schema().len() - 1);
+        window_col.insert_range(self.input.schema().len()..self.schema().len());
         let (window_pred, other_pred) = predicate.split_disjoint(&window_col);
         gen_filter_and_pushdown(self, window_pred, other_pred)
     }
@@ -272,16 +501,29 @@ impl PredicatePushdown for LogicalOverAgg {
 
 impl ToBatch for LogicalOverAgg {
     fn to_batch(&self) -> Result<PlanRef> {
-        Err(ErrorCode::NotImplemented("OverAgg to batch".to_string(), 4847.into()).into())
+        let new_input = self
+            .input()
+            .to_batch_with_order_required(&self.input_order())?;
+        Ok(BatchOverAgg::new(self.clone_with_input(new_input)).into())
     }
 }
 
 impl ToStream for LogicalOverAgg {
     fn to_stream(&self) -> Result<PlanRef> {
-        Err(ErrorCode::NotImplemented("OverAgg to stream".to_string(), 4847.into()).into())
+        let input = self.input().to_stream()?;
+        let partition_key = self.partition_key_indices();
+        let required_dist = if partition_key.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::hash_shard(&partition_key)
+        };
+        let input = required_dist.enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(StreamOverAgg::new(self.clone_with_input(input)).into())
     }
 
-    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, crate::utils::ColIndexMapping)> {
-        Err(ErrorCode::NotImplemented("OverAgg to stream".to_string(), 4847.into()).into())
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (over_agg, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((over_agg.into(), out_col_change))
     }
 }
@@ -53,6 +53,7 @@ impl LogicalScan {
         indexes: Vec<Rc<IndexCatalog>>,
         ctx: OptimizerContextRef,
         predicate: Condition, // refers to column indexes of the table
+        for_system_time_as_of_proctime: bool,
     ) -> Self {
         // here we have 3 concepts
         // 1. column_id: ColumnId, stored in catalog and a ID to access data from storage.
@@ -81,6 +82,7 @@ impl LogicalScan {
             table_desc,
             indexes,
             predicate,
+            for_system_time_as_of_proctime,
         };
 
         let schema = core.schema();
@@ -107,6 +109,7 @@ impl LogicalScan {
         table_desc: Rc<TableDesc>,
         indexes: Vec<Rc<IndexCatalog>>,
         ctx: OptimizerContextRef,
+        for_system_time_as_of_proctime: bool,
     ) -> Self {
         Self::new(
             table_name,
@@ -116,6 +119,7 @@ impl LogicalScan {
             indexes,
             ctx,
             Condition::true_cond(),
+            for_system_time_as_of_proctime,
         )
     }
 
@@ -169,6 +173,12 @@ impl LogicalScan {
         self.core.is_sys_table
     }
 
+    /// Whether the table is read as of the process time, i.e. as the right side of a temporal
+    /// join.
+    pub fn for_system_time_as_of_proctime(&self) -> bool {
+        self.core.for_system_time_as_of_proctime
+    }
+
     /// Get a reference to the logical scan's table desc.
     pub fn table_desc(&self) -> &TableDesc {
         self.core.table_desc.as_ref()
@@ -292,6 +302,7 @@ impl LogicalScan {
             vec![],
             self.ctx(),
             new_predicate,
+            false,
         )
     }
 
@@ -328,6 +339,7 @@ impl LogicalScan {
             self.indexes().to_vec(),
             self.ctx(),
             Condition::true_cond(),
+            self.for_system_time_as_of_proctime(),
         );
         let project_expr = if self.required_col_idx() != self.output_col_idx() {
             Some(self.output_idx_to_input_ref())
@@ -346,6 +358,7 @@ impl LogicalScan {
             self.indexes().to_vec(),
             self.base.ctx.clone(),
             predicate,
+            self.for_system_time_as_of_proctime(),
         )
     }
 
@@ -358,6 +371,7 @@ impl LogicalScan {
             self.indexes().to_vec(),
             self.base.ctx.clone(),
             self.predicate().clone(),
+            self.for_system_time_as_of_proctime(),
         )
     }
 
@@ -0,0 +1,236 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, IntervalUnit};
+use risingwave_common::util::sort_util::OrderType;
+
+use super::utils::TableCatalogBuilder;
+use super::{
+    gen_filter_and_pushdown, BatchSessionWindow, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, StreamSessionWindow, ToBatch, ToStream,
+};
+use crate::expr::{InputRef, InputRefDisplay};
+use crate::optimizer::property::{FieldOrder, Order, RequiredDist};
+use crate::utils::{ColIndexMapping, Condition};
+use crate::TableCatalog;
+
+/// `LogicalSessionWindow` implements the `SESSION` table function. Rows whose `time_col` are
+/// connected by a chain of rows, each within `gap` of the previous one, belong to the same session.
+///
+/// The output schema is the input schema plus `window_start` and `window_end`, which are the
+/// earliest time of the session and the latest time plus `gap`.
+#[derive(Debug, Clone)]
+pub struct LogicalSessionWindow {
+    pub base: PlanBase,
+    input: PlanRef,
+    time_col: InputRef,
+    gap: IntervalUnit,
+}
+
+impl LogicalSessionWindow {
+    pub fn new(input: PlanRef, time_col: InputRef, gap: IntervalUnit) -> Self {
+        let ctx = input.ctx();
+        let output_type = DataType::window_of(&time_col.data_type).unwrap();
+        let schema: Schema = input
+            .schema()
+            .fields()
+            .iter()
+            .cloned()
+            .chain([
+                Field::with_name(output_type.clone(), "window_start"),
+                Field::with_name(output_type, "window_end"),
+            ])
+            .collect();
+        let input_len = input.schema().len();
+        let mut functional_dependency = ColIndexMapping::identity_or_none(input_len, schema.len())
+            .rewrite_functional_dependency_set(input.functional_dependency().clone());
+        // Sessions never overlap, so either bound identifies the session.
+        functional_dependency
+            .add_functional_dependency_by_column_indices(&[input_len], &[input_len + 1]);
+        functional_dependency
+            .add_functional_dependency_by_column_indices(&[input_len + 1], &[input_len]);
+        let base = PlanBase::new_logical(
+            ctx,
+            schema,
+            input.logical_pk().to_vec(),
+            functional_dependency,
+        );
+        LogicalSessionWindow {
+            base,
+            input,
+            time_col,
+            gap,
+        }
+    }
+
+    pub fn create(input: PlanRef, time_col: InputRef, gap: IntervalUnit) -> PlanRef {
+        Self::new(input, time_col, gap).into()
+    }
+
+    pub fn time_col(&self) -> &InputRef {
+        &self.time_col
+    }
+
+    pub fn gap(&self) -> IntervalUnit {
+        self.gap
+    }
+
+    /// The order that the input is required to be sorted by in batch.
+    pub fn input_order(&self) -> Order {
+        Order::new(vec![FieldOrder::ascending(self.time_col.index())])
+    }
+
+    /// Infers the state table of the streaming `SessionWindow`, which stores all input rows
+    /// ordered by the time column and then the stream key.
+    pub fn infer_state_table(&self) -> TableCatalog {
+        let mut builder =
+            TableCatalogBuilder::new(self.base.ctx.inner().with_options.internal_table_subset());
+        self.input.schema().fields().iter().for_each(|field| {
+            builder.add_column(field);
+        });
+        builder.add_order_column(self.time_col.index(), OrderType::Ascending);
+        for &idx in self.input.logical_pk() {
+            if idx != self.time_col.index() {
+                builder.add_order_column(idx, OrderType::Ascending);
+            }
+        }
+        builder.build(vec![])
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ time_col: {}, gap: {} }}",
+            name,
+            InputRefDisplay {
+                input_ref: &self.time_col,
+                input_schema: self.input.schema()
+            },
+            self.gap,
+        )
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.time_col.clone(), self.gap)
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let mut time_col = self.time_col.clone();
+        time_col.index = input_col_change.map(time_col.index);
+        let new_input_len = input.schema().len();
+        let old_input_len = self.input.schema().len();
+        let session_window = Self::new(input, time_col, self.gap);
+        let out_col_change = ColIndexMapping::with_target_size(
+            (0..self.schema().len())
+                .map(|i| {
+                    if i < old_input_len {
+                        input_col_change.try_map(i)
+                    } else {
+                        Some(i - old_input_len + new_input_len)
+                    }
+                })
+                .collect(),
+            session_window.schema().len(),
+        );
+        (session_window, out_col_change)
+    }
+}
+
+impl_plan_tree_node_for_unary! { LogicalSessionWindow }
+
+impl fmt::Display for LogicalSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalSessionWindow")
+    }
+}
+
+impl ColPrunable for LogicalSessionWindow {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_len = self.input.schema().len();
+        let input_required_cols = {
+            let mut tmp = FixedBitSet::with_capacity(input_len);
+            tmp.extend(required_cols.iter().copied().filter(|&idx| idx < input_len));
+            // The time column is always required to assign the sessions.
+            tmp.insert(self.time_col.index());
+            tmp.ones().collect_vec()
+        };
+        let input_col_change =
+            ColIndexMapping::with_remaining_columns(&input_required_cols, input_len);
+        let (session_window, out_col_change) =
+            self.rewrite_with_input(self.input.prune_col(&input_required_cols), input_col_change);
+
+        let output_required_cols = required_cols
+            .iter()
+            .map(|&idx| out_col_change.map(idx))
+            .collect_vec();
+        let src_size = session_window.schema().len();
+        if output_required_cols.iter().copied().eq(0..src_size) {
+            session_window.into()
+        } else {
+            LogicalProject::with_mapping(
+                session_window.into(),
+                ColIndexMapping::with_remaining_columns(&output_required_cols, src_size),
+            )
+            .into()
+        }
+    }
+}
+
+impl PredicatePushdown for LogicalSessionWindow {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        // Filtering the input rows changes the sessions, so no predicate can be pushed down.
+        gen_filter_and_pushdown(self, predicate, Condition::true_cond())
+    }
+}
+
+impl ToBatch for LogicalSessionWindow {
+    fn to_batch(&self) -> Result<PlanRef> {
+        let new_input = self
+            .input()
+            .to_batch_with_order_required(&self.input_order())?;
+        Ok(BatchSessionWindow::new(self.clone_with_input(new_input)).into())
+    }
+}
+
+impl ToStream for LogicalSessionWindow {
+    fn to_stream(&self) -> Result<PlanRef> {
+        let input = self.input().to_stream()?;
+        // Sessions are not partitioned, so all rows are processed by a single actor.
+        let input = RequiredDist::single().enforce_if_not_satisfies(input, &Order::any())?;
+        Ok(StreamSessionWindow::new(self.clone_with_input(input)).into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (session_window, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((session_window.into(), out_col_change))
+    }
+}
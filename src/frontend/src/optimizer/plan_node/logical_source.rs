@@ -16,11 +16,14 @@ use std::fmt;
 use std::rc::Rc;
 
 use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_connector::source::kafka::SCAN_BOUNDED_MODE_KEY;
+use risingwave_pb::catalog::source_info::SourceInfo;
+use risingwave_pb::plan_common::RowFormatType;
 
 use super::generic::GenericPlanNode;
 use super::{
-    generic, ColPrunable, LogicalFilter, LogicalProject, PlanBase, PlanRef, PredicatePushdown,
-    StreamSource, ToBatch, ToStream,
+    generic, BatchSource, ColPrunable, LogicalFilter, LogicalProject, PlanBase, PlanRef,
+    PredicatePushdown, StreamSource, ToBatch, ToStream,
 };
 use crate::catalog::source_catalog::SourceCatalog;
 use crate::optimizer::property::FunctionalDependencySet;
@@ -72,6 +75,10 @@ impl LogicalSource {
     pub fn infer_internal_table_catalog(&self) -> TableCatalog {
         generic::Source::infer_internal_table_catalog(&self.base)
     }
+
+    pub fn infer_dead_letter_table_catalog(&self) -> Option<TableCatalog> {
+        self.core.infer_dead_letter_table_catalog(&self.base)
+    }
 }
 
 impl_plan_tree_node_for_leaf! {LogicalSource}
@@ -102,10 +109,32 @@ impl PredicatePushdown for LogicalSource {
 
 impl ToBatch for LogicalSource {
     fn to_batch(&self) -> Result<PlanRef> {
-        Err(RwError::from(ErrorCode::NotImplemented(
-            "there is no batch source operator".to_string(),
-            None.into(),
-        )))
+        let catalog = self.source_catalog();
+        match &catalog.info {
+            // Only a bounded source can be read in batch, which ends at some point.
+            SourceInfo::StreamSource(info)
+                if catalog.properties.contains_key(SCAN_BOUNDED_MODE_KEY) =>
+            {
+                if matches!(
+                    info.get_row_format()?,
+                    RowFormatType::DebeziumJson
+                        | RowFormatType::Maxwell
+                        | RowFormatType::CanalJson
+                        | RowFormatType::UpsertJson
+                        | RowFormatType::UpsertAvro
+                ) {
+                    return Err(RwError::from(ErrorCode::NotImplemented(
+                        "batch read of a source with changelog row format".to_string(),
+                        None.into(),
+                    )));
+                }
+                Ok(BatchSource::new(self.clone()).into())
+            }
+            _ => Err(RwError::from(ErrorCode::NotImplemented(
+                "there is no batch source operator".to_string(),
+                None.into(),
+            ))),
+        }
     }
 }
 
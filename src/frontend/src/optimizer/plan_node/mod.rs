@@ -70,7 +70,7 @@ pub trait PlanNode:
 impl_downcast!(PlanNode);
 pub type PlanRef = Rc<dyn PlanNode>;
 
-#[derive(Clone, Debug, Copy, Serialize)]
+#[derive(Clone, Debug, Copy, Serialize, Hash, Eq, PartialEq)]
 pub struct PlanNodeId(pub i32);
 
 #[derive(Debug, PartialEq)]
@@ -112,31 +112,46 @@ impl dyn PlanNode {
         level: usize,
         f: &mut impl std::fmt::Write,
     ) -> std::fmt::Result {
+        self.explain_with_id(is_last, level, &mut |_, line| writeln!(f, "{}", line))
+    }
+
+    /// Explain the whole plan tree like `explain`, but pass each line together with the id of the
+    /// plan node it describes to `f`.
+    pub fn explain_with_id(
+        &self,
+        is_last: &mut Vec<bool>,
+        level: usize,
+        f: &mut impl FnMut(PlanNodeId, String) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        let mut line = String::new();
         if level > 0 {
             let mut last_iter = is_last.iter().peekable();
             while let Some(last) = last_iter.next() {
                 // We are at the current level
                 if last_iter.peek().is_none() {
                     if *last {
-                        writeln!(f, "└─{}", self)?;
+                        write!(line, "└─{}", self)?;
                     } else {
-                        writeln!(f, "├─{}", self)?;
+                        write!(line, "├─{}", self)?;
                     }
                 } else if *last {
-                    write!(f, "  ")?;
+                    write!(line, "  ")?;
                 } else {
-                    write!(f, "| ")?;
+                    write!(line, "| ")?;
                 }
             }
         } else {
-            writeln!(f, "{}", self)?;
+            write!(line, "{}", self)?;
         }
+        f(self.id(), line)?;
         let inputs = self.inputs();
         let mut inputs_iter = inputs.iter().peekable();
         while let Some(input) = inputs_iter.next() {
             let last = inputs_iter.peek().is_none();
             is_last.push(last);
-            input.explain(is_last, level + 1, f)?;
+            input.explain_with_id(is_last, level + 1, f)?;
             is_last.pop();
         }
         Ok(())
@@ -233,6 +248,7 @@ impl dyn PlanNode {
             } else {
                 "".into()
             },
+            operator_id: self.id().0 as u64,
             node_body,
         }
     }
@@ -274,9 +290,11 @@ mod batch_insert;
 mod batch_limit;
 mod batch_lookup_join;
 mod batch_nested_loop_join;
+mod batch_over_agg;
 mod batch_project;
 mod batch_project_set;
 mod batch_seq_scan;
+mod batch_session_window;
 mod batch_simple_agg;
 mod batch_sort;
 mod batch_sort_agg;
@@ -300,6 +318,7 @@ mod logical_over_agg;
 mod logical_project;
 mod logical_project_set;
 mod logical_scan;
+mod logical_session_window;
 mod logical_source;
 mod logical_table_function;
 mod logical_topn;
@@ -320,12 +339,15 @@ mod stream_hop_window;
 mod stream_index_scan;
 mod stream_local_simple_agg;
 mod stream_materialize;
+mod stream_over_agg;
 mod stream_project;
 mod stream_project_set;
 mod stream_row_id_gen;
+mod stream_session_window;
 mod stream_sink;
 mod stream_source;
 mod stream_table_scan;
+mod stream_temporal_join;
 mod stream_topn;
 
 mod stream_union;
@@ -343,9 +365,11 @@ pub use batch_insert::BatchInsert;
 pub use batch_limit::BatchLimit;
 pub use batch_lookup_join::BatchLookupJoin;
 pub use batch_nested_loop_join::BatchNestedLoopJoin;
+pub use batch_over_agg::BatchOverAgg;
 pub use batch_project::BatchProject;
 pub use batch_project_set::BatchProjectSet;
 pub use batch_seq_scan::BatchSeqScan;
+pub use batch_session_window::BatchSessionWindow;
 pub use batch_simple_agg::BatchSimpleAgg;
 pub use batch_sort::BatchSort;
 pub use batch_sort_agg::BatchSortAgg;
@@ -369,6 +393,7 @@ pub use logical_over_agg::{LogicalOverAgg, PlanWindowFunction};
 pub use logical_project::LogicalProject;
 pub use logical_project_set::LogicalProjectSet;
 pub use logical_scan::LogicalScan;
+pub use logical_session_window::LogicalSessionWindow;
 pub use logical_source::LogicalSource;
 pub use logical_table_function::LogicalTableFunction;
 pub use logical_topn::LogicalTopN;
@@ -389,12 +414,15 @@ pub use stream_hop_window::StreamHopWindow;
 pub use stream_index_scan::StreamIndexScan;
 pub use stream_local_simple_agg::StreamLocalSimpleAgg;
 pub use stream_materialize::StreamMaterialize;
+pub use stream_over_agg::StreamOverAgg;
 pub use stream_project::StreamProject;
 pub use stream_project_set::StreamProjectSet;
 pub use stream_row_id_gen::StreamRowIdGen;
+pub use stream_session_window::StreamSessionWindow;
 pub use stream_sink::StreamSink;
 pub use stream_source::StreamSource;
 pub use stream_table_scan::StreamTableScan;
+pub use stream_temporal_join::StreamTemporalJoin;
 pub use stream_topn::StreamTopN;
 pub use stream_union::StreamUnion;
 
@@ -437,6 +465,7 @@ macro_rules! for_all_plan_nodes {
             , { Logical, ProjectSet }
             , { Logical, Union }
             , { Logical, OverAgg }
+            , { Logical, SessionWindow }
             // , { Logical, Sort } we don't need a LogicalSort, just require the Order
             , { Batch, SimpleAgg }
             , { Batch, HashAgg }
@@ -462,6 +491,8 @@ macro_rules! for_all_plan_nodes {
             , { Batch, Union }
             , { Batch, GroupTopN }
             , { Batch, Source }
+            , { Batch, OverAgg }
+            , { Batch, SessionWindow }
             , { Stream, Project }
             , { Stream, Filter }
             , { Stream, TableScan }
@@ -484,6 +515,9 @@ macro_rules! for_all_plan_nodes {
             , { Stream, Union }
             , { Stream, RowIdGen }
             , { Stream, Dml }
+            , { Stream, OverAgg }
+            , { Stream, SessionWindow }
+            , { Stream, TemporalJoin }
         }
     };
 }
@@ -513,6 +547,7 @@ macro_rules! for_logical_plan_nodes {
             , { Logical, ProjectSet }
             , { Logical, Union }
             , { Logical, OverAgg }
+            , { Logical, SessionWindow }
             // , { Logical, Sort} not sure if we will support Order by clause in subquery/view/MV
             // if we don't support that, we don't need LogicalSort, just require the Order at the top of query
         }
@@ -548,6 +583,8 @@ macro_rules! for_batch_plan_nodes {
             , { Batch, Union }
             , { Batch, GroupTopN }
             , { Batch, Source }
+            , { Batch, OverAgg }
+            , { Batch, SessionWindow }
         }
     };
 }
@@ -579,6 +616,9 @@ macro_rules! for_stream_plan_nodes {
             , { Stream, Union }
             , { Stream, RowIdGen }
             , { Stream, Dml }
+            , { Stream, OverAgg }
+            , { Stream, SessionWindow }
+            , { Stream, TemporalJoin }
         }
     };
 }
@@ -239,6 +239,7 @@ impl HashJoin {
             .collect();
 
         // The pk of hash join internal and degree table should be join_key + input_pk.
+        let join_key_len = join_key_indices.len();
         let mut pk_indices = join_key_indices;
         // TODO(yuhao): dedup the dist key and pk.
         pk_indices.extend(input.logical_pk());
@@ -255,6 +256,8 @@ impl HashJoin {
         pk_indices.iter().for_each(|idx| {
             internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending)
         });
+        // Both tables are looked up by the join key.
+        internal_table_catalog_builder.set_read_prefix_len_hint(join_key_len);
 
         // Build degree table.
         let mut degree_table_catalog_builder =
@@ -269,6 +272,7 @@ impl HashJoin {
         degree_table_catalog_builder.add_column(&degree_column_field);
         degree_table_catalog_builder
             .set_value_indices(vec![degree_table_catalog_builder.columns().len() - 1]);
+        degree_table_catalog_builder.set_read_prefix_len_hint(join_key_len);
 
         (
             internal_table_catalog_builder.build(internal_table_dist_keys),
@@ -682,9 +686,15 @@ pub fn to_stream_prost_body(
                 table_id: me.table.id().into(),
                 column_ids: vec![], // TODO(nanderstabel): fix empty Vector
                 properties: me.table.properties.inner().clone(),
+                state_table: Some(
+                    super::StreamSink::infer_internal_table_catalog(base)
+                        .with_id(state.gen_table_id_wrapped())
+                        .to_internal_table_prost(),
+                ),
             })
         }
         Node::Source(me) => {
+            let dead_letter_table = me.core.infer_dead_letter_table_catalog(base);
             let me = &me.core.catalog;
             ProstNode::Source(SourceNode {
                 source_id: me.id,
@@ -703,6 +713,11 @@ pub fn to_stream_prost_body(
                 columns: me.columns.iter().map(|c| c.to_protobuf()).collect(),
                 pk_column_ids: me.pk_col_ids.iter().map(Into::into).collect(),
                 properties: me.properties.clone(),
+                dead_letter_table: dead_letter_table.map(|table| {
+                    table
+                        .with_id(state.gen_table_id_wrapped())
+                        .to_internal_table_prost()
+                }),
             })
         }
         Node::TopN(me) => {
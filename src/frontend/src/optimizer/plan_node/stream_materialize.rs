@@ -19,7 +19,7 @@ use std::fmt;
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::{ColumnDesc, TableId};
-use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::ErrorCode::{self, InternalError};
 use risingwave_common::error::Result;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
@@ -150,7 +150,20 @@ impl StreamMaterialize {
         }
 
         let ctx = input.ctx();
-        let properties = ctx.inner().with_options.internal_table_subset();
+        let with_options = &ctx.inner().with_options;
+        let read_prefix_len_hint = match with_options.bloom_filter_prefix_columns()? {
+            Some(prefix_columns) if prefix_columns.get() > pk_list.len() => {
+                return Err(ErrorCode::InvalidParameterValue(format!(
+                    "bloom_filter_prefix_columns {} exceeds the {} columns of the order key",
+                    prefix_columns,
+                    pk_list.len()
+                ))
+                .into());
+            }
+            Some(prefix_columns) => prefix_columns.get(),
+            None => 0,
+        };
+        let properties = with_options.materialized_table_subset();
         let table = TableCatalog {
             id: TableId::placeholder(),
             associated_source_id: None,
@@ -169,6 +182,7 @@ impl StreamMaterialize {
             value_indices,
             definition,
             handle_pk_conflict,
+            read_prefix_len_hint,
         };
 
         Ok(Self { base, input, table })
@@ -0,0 +1,88 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+
+use super::{LogicalOverAgg, PlanBase, PlanRef, PlanTreeNodeUnary, StreamNode};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+
+/// `StreamOverAgg` evaluates window functions over its input. It keeps all input rows in a state
+/// table, and emits retractions for the rows whose window function outputs are changed.
+#[derive(Debug, Clone)]
+pub struct StreamOverAgg {
+    pub base: PlanBase,
+    logical: LogicalOverAgg,
+}
+
+impl StreamOverAgg {
+    pub fn new(logical: LogicalOverAgg) -> Self {
+        let input = logical.input();
+        let base = PlanBase::new_stream(
+            input.ctx(),
+            logical.schema().clone(),
+            input.logical_pk().to_vec(),
+            logical.functional_dependency().clone(),
+            input.distribution().clone(),
+            false,
+        );
+        StreamOverAgg { base, logical }
+    }
+}
+
+impl fmt::Display for StreamOverAgg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamOverAgg")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamOverAgg {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamOverAgg }
+
+impl StreamNode for StreamOverAgg {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+        let state_table = self
+            .logical
+            .infer_state_table()
+            .with_id(state.gen_table_id_wrapped());
+
+        ProstStreamNode::OverAgg(OverAggNode {
+            calls: self
+                .logical
+                .window_functions()
+                .iter()
+                .map(|f| f.to_protobuf())
+                .collect(),
+            partition_by: self
+                .logical
+                .partition_key_indices()
+                .into_iter()
+                .map(|idx| idx as u32)
+                .collect(),
+            order_by: self.logical.order_key().to_protobuf(self.input().schema()),
+            state_table: Some(state_table.to_internal_table_prost()),
+        })
+    }
+}
@@ -14,8 +14,13 @@
 
 use std::fmt;
 
+use risingwave_common::catalog::Field;
+use risingwave_common::types::DataType;
+use risingwave_common::util::sort_util::OrderType;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
+use super::generic::GenericPlanRef;
+use super::utils::TableCatalogBuilder;
 use super::{PlanBase, PlanRef, StreamNode};
 use crate::optimizer::plan_node::PlanTreeNodeUnary;
 use crate::stream_fragmenter::BuildFragmentGraphState;
@@ -48,6 +53,24 @@ impl StreamSink {
     pub fn sink_catalog(&self) -> &TableCatalog {
         &self.sink_catalog
     }
+
+    /// Infers the state table of the sink executor, which keeps the transactions flushed on
+    /// checkpoints until they are committed. It's keyed by the slot, i.e. the first vnode of the
+    /// actor writing the transactions, and the sequence number in the slot.
+    pub fn infer_internal_table_catalog(me: &impl GenericPlanRef) -> TableCatalog {
+        let mut builder =
+            TableCatalogBuilder::new(me.ctx().inner().with_options.internal_table_subset());
+
+        let slot_idx = builder.add_column(&Field::with_name(DataType::Int32, "slot"));
+        let seq_idx = builder.add_column(&Field::with_name(DataType::Int64, "seq"));
+        builder.add_column(&Field::with_name(DataType::Varchar, "transactional_id"));
+        builder.add_column(&Field::with_name(DataType::Int64, "epoch"));
+        builder.add_column(&Field::with_name(DataType::Bytea, "chunk"));
+        builder.add_order_column(slot_idx, OrderType::Ascending);
+        builder.add_order_column(seq_idx, OrderType::Ascending);
+
+        builder.build(vec![])
+    }
 }
 
 impl PlanTreeNodeUnary for StreamSink {
@@ -71,13 +94,18 @@ impl fmt::Display for StreamSink {
 }
 
 impl StreamNode for StreamSink {
-    fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
         use risingwave_pb::stream_plan::*;
 
         ProstStreamNode::Sink(SinkNode {
             table_id: self.sink_catalog.id().into(),
             column_ids: vec![], // TODO(nanderstabel): fix empty Vector
             properties: self.sink_catalog.properties.inner().clone(),
+            state_table: Some(
+                Self::infer_internal_table_catalog(&self.base)
+                    .with_id(state.gen_table_id_wrapped())
+                    .to_internal_table_prost(),
+            ),
         })
     }
 }
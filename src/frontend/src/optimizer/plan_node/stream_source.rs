@@ -93,6 +93,11 @@ impl StreamNode for StreamSource {
                 .map(Into::into)
                 .collect_vec(),
             properties: source_catalog.properties.clone(),
+            dead_letter_table: self.logical.infer_dead_letter_table_catalog().map(|table| {
+                table
+                    .with_id(state.gen_table_id_wrapped())
+                    .to_internal_table_prost()
+            }),
         })
     }
 }
@@ -0,0 +1,188 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::{ColumnId, Schema, TableDesc};
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::TemporalJoinNode;
+
+use super::{LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, PlanTreeNodeUnary, StreamNode};
+use crate::expr::Expr;
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::plan_node::{EqJoinPredicate, EqJoinPredicateDisplay};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+
+/// [`StreamTemporalJoin`] joins an append-only stream with a table as of the process time, i.e.
+/// `FOR SYSTEM_TIME AS OF PROCTIME()`. Each left row is joined with the rows of the table at the
+/// time it arrives, which are looked up from the storage of the table directly. So no side is kept
+/// in the state, and changes of the table never update the rows joined before.
+#[derive(Debug, Clone)]
+pub struct StreamTemporalJoin {
+    pub base: PlanBase,
+    logical: LogicalJoin,
+
+    /// The join condition must be equivalent to `logical.on`, but separated into equal and
+    /// non-equal parts to facilitate execution later
+    eq_join_predicate: EqJoinPredicate,
+
+    /// Table description of the right side table
+    right_table_desc: TableDesc,
+
+    /// Output column ids of the right side table
+    right_output_column_ids: Vec<ColumnId>,
+
+    /// The prefix length of the order key of right side table.
+    lookup_prefix_len: usize,
+}
+
+impl StreamTemporalJoin {
+    pub fn new(
+        logical: LogicalJoin,
+        eq_join_predicate: EqJoinPredicate,
+        right_table_desc: TableDesc,
+        right_output_column_ids: Vec<ColumnId>,
+        lookup_prefix_len: usize,
+    ) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let l2o = logical
+            .l2i_col_mapping()
+            .composite(&logical.i2o_col_mapping());
+        let dist = l2o.rewrite_provided_distribution(logical.left().distribution());
+        // The left side is append-only, and the joined rows are never updated.
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            logical.base.logical_pk.to_vec(),
+            logical.functional_dependency().clone(),
+            dist,
+            true,
+        );
+
+        Self {
+            base,
+            logical,
+            eq_join_predicate,
+            right_table_desc,
+            right_output_column_ids,
+            lookup_prefix_len,
+        }
+    }
+
+    /// Get a reference to the temporal join's eq join predicate.
+    pub fn eq_join_predicate(&self) -> &EqJoinPredicate {
+        &self.eq_join_predicate
+    }
+}
+
+impl fmt::Display for StreamTemporalJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verbose = self.base.ctx.is_explain_verbose();
+        let mut builder = f.debug_struct("StreamTemporalJoin");
+        builder.field("type", &self.logical.join_type());
+
+        let mut concat_schema = self.logical.left().schema().fields.clone();
+        concat_schema.extend(self.logical.right().schema().fields.clone());
+        let concat_schema = Schema::new(concat_schema);
+        builder.field(
+            "predicate",
+            &EqJoinPredicateDisplay {
+                eq_join_predicate: self.eq_join_predicate(),
+                input_schema: &concat_schema,
+            },
+        );
+
+        if verbose {
+            if self
+                .logical
+                .output_indices()
+                .iter()
+                .copied()
+                .eq(0..self.logical.internal_column_num())
+            {
+                builder.field("output", &format_args!("all"));
+            } else {
+                builder.field(
+                    "output",
+                    &IndicesDisplay {
+                        indices: self.logical.output_indices(),
+                        input_schema: &concat_schema,
+                    },
+                );
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+impl PlanTreeNodeUnary for StreamTemporalJoin {
+    fn input(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    // Only change left side
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(
+            self.logical
+                .clone_with_left_right(input, self.logical.right()),
+            self.eq_join_predicate.clone(),
+            self.right_table_desc.clone(),
+            self.right_output_column_ids.clone(),
+            self.lookup_prefix_len,
+        )
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamTemporalJoin }
+
+impl StreamNode for StreamTemporalJoin {
+    fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> NodeBody {
+        NodeBody::TemporalJoin(TemporalJoinNode {
+            join_type: self.logical.join_type() as i32,
+            left_key: self
+                .eq_join_predicate
+                .left_eq_indexes()
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            right_key: self
+                .eq_join_predicate
+                .right_eq_indexes()
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            null_safe: self.eq_join_predicate.null_safes(),
+            condition: self
+                .eq_join_predicate
+                .other_cond()
+                .as_expr_unless_true()
+                .map(|x| x.to_expr_proto()),
+            output_indices: self
+                .logical
+                .output_indices()
+                .iter()
+                .map(|&x| x as u32)
+                .collect(),
+            table_desc: Some(self.right_table_desc.to_protobuf()),
+            table_output_column_ids: self
+                .right_output_column_ids
+                .iter()
+                .map(ColumnId::get_id)
+                .collect(),
+            lookup_prefix_len: self.lookup_prefix_len as u32,
+        })
+    }
+}
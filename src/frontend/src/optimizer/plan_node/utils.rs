@@ -33,6 +33,7 @@ pub struct TableCatalogBuilder {
     value_indices: Option<Vec<usize>>,
     vnode_col_idx: Option<usize>,
     column_names: HashMap<String, i32>,
+    read_prefix_len_hint: usize,
 }
 
 /// For DRY, mainly used for construct internal table catalog in stateful streaming executors.
@@ -84,6 +85,12 @@ impl TableCatalogBuilder {
         self.value_indices = Some(value_indices);
     }
 
+    /// Set the number of leading order columns used as the prefix of the bloom filter key, which
+    /// should be the prefix the table is mostly read with.
+    pub fn set_read_prefix_len_hint(&mut self, read_prefix_len_hint: usize) {
+        self.read_prefix_len_hint = read_prefix_len_hint;
+    }
+
     /// Check the column name whether exist before. if true, record occurrence and change the name
     /// to avoid duplicate.
     fn avoid_duplicate_col_name(&mut self, column_desc: &mut ColumnDesc) {
@@ -124,6 +131,7 @@ impl TableCatalogBuilder {
                 .unwrap_or_else(|| (0..self.columns.len()).collect_vec()),
             definition: "".into(),
             handle_pk_conflict: false,
+            read_prefix_len_hint: self.read_prefix_len_hint,
         }
     }
 
@@ -189,6 +189,7 @@ impl IndexSelectionRule {
             index.index_table.table_desc().into(),
             vec![],
             logical_scan.ctx(),
+            false,
         );
 
         let primary_table_scan = LogicalScan::create(
@@ -197,6 +198,7 @@ impl IndexSelectionRule {
             index.primary_table.table_desc().into(),
             vec![],
             logical_scan.ctx(),
+            false,
         );
 
         let conjunctions = index
@@ -286,6 +288,7 @@ impl IndexSelectionRule {
             primary_table_desc.clone().into(),
             vec![],
             logical_scan.ctx(),
+            false,
         );
 
         let conjunctions = primary_table_desc
@@ -509,6 +512,7 @@ impl IndexSelectionRule {
             Condition {
                 conjunctions: conjunctions.to_vec(),
             },
+            false,
         );
 
         result.push(primary_access.into());
@@ -555,6 +559,7 @@ impl IndexSelectionRule {
                 vec![],
                 ctx,
                 new_predicate,
+                false,
             )
             .into(),
         )
@@ -702,6 +707,7 @@ impl<'a> TableScanIoEstimator<'a> {
             DataType::Bytea => 20,
             DataType::Struct { .. } => 20,
             DataType::List { .. } => 20,
+            DataType::Jsonb => 20,
         }
     }
 
@@ -63,14 +63,18 @@ impl Rule for OverAggToTopNRule {
 
         let PlanWindowFunction {
             function_type,
-            return_type: _,
             partition_by,
             order_by,
-        } = &over_agg.window_function;
+            ..
+        } = match over_agg.window_functions() {
+            [window_function] => window_function,
+            _ => return None,
+        };
         let with_ties = match function_type {
             WindowFunctionType::RowNumber => false,
             WindowFunctionType::Rank => true,
-            WindowFunctionType::DenseRank => unreachable!("Not implemented. Banned in planner."),
+            // Other window functions are evaluated by `OverAgg` directly.
+            _ => return None,
         };
 
         let (rank_pred, other_pred) = {
@@ -17,6 +17,7 @@ use std::rc::Rc;
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::ScalarImpl;
+use risingwave_expr::vector_op::session::session_gap_usecs;
 
 use crate::binder::{
     BoundBaseTable, BoundJoin, BoundSource, BoundSystemTable, BoundWindowTableFunction, Relation,
@@ -24,8 +25,8 @@ use crate::binder::{
 };
 use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef, TableFunction};
 use crate::optimizer::plan_node::{
-    LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSource,
-    LogicalTableFunction, PlanRef,
+    LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSessionWindow,
+    LogicalSource, LogicalTableFunction, PlanRef,
 };
 use crate::planner::Planner;
 
@@ -50,11 +51,14 @@ impl Planner {
             Rc::new(sys_table.sys_table_catalog.table_desc()),
             vec![],
             self.ctx(),
+            false,
         )
         .into())
     }
 
     pub(super) fn plan_base_table(&mut self, base_table: BoundBaseTable) -> Result<PlanRef> {
+        self.ctx()
+            .record_read_epoch(base_table.for_system_time_as_of_epoch)?;
         Ok(LogicalScan::create(
             base_table.table_catalog.name().to_string(),
             false,
@@ -65,6 +69,7 @@ impl Planner {
                 .map(|x| x.as_ref().clone().into())
                 .collect(),
             self.ctx(),
+            base_table.for_system_time_as_of_proctime,
         )
         .into())
     }
@@ -97,6 +102,11 @@ impl Planner {
                 table_function.time_col,
                 table_function.args,
             ),
+            Session => self.plan_session_window(
+                table_function.input,
+                table_function.time_col,
+                table_function.args,
+            ),
         }
     }
 
@@ -206,4 +216,37 @@ impl Planner {
             window_size,
         ))
     }
+
+    fn plan_session_window(
+        &mut self,
+        input: Relation,
+        time_col: InputRef,
+        args: Vec<ExprImpl>,
+    ) -> Result<PlanRef> {
+        let input = self.plan_relation(input)?;
+        let gap = match args.as_slice() {
+            [ExprImpl::Literal(gap)] => match gap.get_data() {
+                Some(ScalarImpl::Interval(gap)) => *gap,
+                _ => {
+                    return Err(ErrorCode::BindError(
+                        "Invalid arguments for SESSION window function".to_string(),
+                    )
+                    .into())
+                }
+            },
+            _ => {
+                return Err(ErrorCode::BindError(
+                    "Invalid arguments for SESSION window function".to_string(),
+                )
+                .into())
+            }
+        };
+        session_gap_usecs(gap).map_err(|e| {
+            ErrorCode::BindError(format!(
+                "Invalid arguments for SESSION window function: {}",
+                e
+            ))
+        })?;
+        Ok(LogicalSessionWindow::create(input, time_col, gap))
+    }
 }
@@ -78,6 +78,10 @@ struct QueryRunner {
     query: Arc<Query>,
     stage_executions: HashMap<StageId, Arc<StageExecution>>,
     scheduled_stages_count: usize,
+    /// Whether the query is executed for `EXPLAIN ANALYZE`, which is the only case that stages
+    /// report their completion.
+    explain_analyze: bool,
+    completed_stages_count: usize,
     /// Query messages receiver. For example, stage state change events, query commands.
     msg_receiver: Receiver<QueryMessage>,
 
@@ -122,6 +126,7 @@ impl QueryExecution {
     ) -> SchedulerResult<QueryResultFetcher> {
         let mut state = self.state.write().await;
         let cur_state = mem::replace(&mut *state, QueryState::Failed);
+        let explain_analyze = context.executor_stats().is_some();
 
         // Because the snapshot may be released before all stages are scheduled, we only pass a
         // reference of `pinned_snapshot`. Its ownership will be moved into `QueryRunner` so that it
@@ -148,6 +153,8 @@ impl QueryExecution {
                     msg_receiver,
                     root_stage_sender: Some(root_stage_sender),
                     scheduled_stages_count: 0,
+                    explain_analyze,
+                    completed_stages_count: 0,
                     compute_client_pool,
                     query_execution_info,
                 };
@@ -268,6 +275,18 @@ impl QueryRunner {
                         }
                     }
                 }
+                Stage(StageEvent::Completed(stage_id)) if self.explain_analyze => {
+                    tracing::trace!(
+                        "Query stage {:?}-{:?} completed.",
+                        self.query.query_id,
+                        stage_id
+                    );
+                    self.completed_stages_count += 1;
+                    if self.completed_stages_count == self.query.stage_graph.stages.len() {
+                        // All stages completed, nothing left to schedule.
+                        break;
+                    }
+                }
                 Stage(ScheduledRoot(receiver)) => {
                     // We already schedule the root fragment, therefore we can notify query result
                     // fetcher.
@@ -477,9 +496,11 @@ pub(crate) mod tests {
                 appendonly: false,
                 retention_seconds: TABLE_OPTION_DUMMY_RETENTION_SECOND,
                 value_indices: vec![0, 1, 2],
+                read_prefix_len_hint: 0,
             }),
             vec![],
             ctx,
+            false,
         )
         .to_batch()
         .unwrap()
@@ -24,7 +24,7 @@ use futures::{stream, StreamExt};
 use futures_async_stream::for_await;
 use itertools::Itertools;
 use rand::seq::SliceRandom;
-use risingwave_batch::executor::ExecutorBuilder;
+use risingwave_batch::executor::{ExecutorBuilder, ExecutorStatsCollectorRef};
 use risingwave_batch::task::TaskId as TaskIdBatch;
 use risingwave_common::array::DataChunk;
 use risingwave_common::hash::VnodeMapping;
@@ -277,7 +277,11 @@ impl StageExecution {
 
 impl StageRunner {
     async fn run(mut self, shutdown_rx: oneshot::Receiver<StageMessage>) {
-        if let Err(e) = self.schedule_tasks_for_all(shutdown_rx).await {
+        let res = self.schedule_tasks_for_all(shutdown_rx).await;
+        if let Some(executor_stats) = self.ctx.executor_stats() {
+            executor_stats.finish_stage();
+        }
+        if let Err(e) = res {
             error!(
                 "Stage {:?}-{:?} failed to schedule tasks, error: {:?}",
                 self.stage.query_id, self.stage.id, e
@@ -287,6 +291,11 @@ impl StageRunner {
                 reason: e,
             }))
             .await;
+        } else if self.ctx.executor_stats().is_some() {
+            // Only `EXPLAIN ANALYZE` tracks the completion of stages, as it has to wait for all
+            // tasks to report their stats. Normal queries end with the root stage.
+            self.send_event(QueryMessage::Stage(StageEvent::Completed(self.stage.id)))
+                .await;
         }
     }
 
@@ -389,7 +398,13 @@ impl StageRunner {
                             // Do not process this as task status like Running/Finished/ etc.
 
                             use risingwave_pb::task_service::task_info::TaskStatus as TaskStatusProst;
-                            match TaskStatusProst::from_i32(status.task_info.as_ref().unwrap().task_status).unwrap() {
+                            let task_info = status.task_info.as_ref().unwrap();
+                            let task_status = TaskStatusProst::from_i32(task_info.task_status).unwrap();
+                            // Executor stats are reported along with the final status of the task.
+                            if task_status != TaskStatusProst::Running && let Some(executor_stats) = self.ctx.executor_stats() {
+                                executor_stats.merge(&task_info.executor_stats);
+                            }
+                            match task_status {
                                 TaskStatusProst::Running => {
                                     running_task_cnt += 1;
                                     // The task running count should always less or equal than the registered tasks
@@ -465,12 +480,15 @@ impl StageRunner {
         self.send_event(QueryMessage::Stage(StageEvent::ScheduledRoot(result_rx)))
             .await;
 
-        let executor = ExecutorBuilder::new(
-            &plan_node,
-            &task_id,
-            self.ctx.to_batch_task_context(),
-            self.epoch,
-        );
+        let mut context = self.ctx.to_batch_task_context();
+        let executor_stats = self
+            .ctx
+            .executor_stats()
+            .map(|_| ExecutorStatsCollectorRef::default());
+        if let Some(executor_stats) = executor_stats.clone() {
+            context = context.with_executor_stats(executor_stats);
+        }
+        let executor = ExecutorBuilder::new(&plan_node, &task_id, context, self.epoch);
 
         let executor = executor.build().await?;
         let chunk_stream = executor.execute();
@@ -493,6 +511,10 @@ impl StageRunner {
             }
         }
 
+        if let Some(executor_stats) = executor_stats && let Some(query_stats) = self.ctx.executor_stats() {
+            query_stats.merge(&executor_stats.to_prost());
+        }
+
         if let Some(err) = terminated_chunk_stream.take_result() {
             let stage_message = err.expect("Sender should always exist!");
 
@@ -700,7 +722,12 @@ impl StageRunner {
 
         let t_id = task_id.task_id;
         let stream_status = compute_client
-            .create_task(task_id, plan_fragment, self.epoch)
+            .create_task(
+                task_id,
+                plan_fragment,
+                self.epoch,
+                self.ctx.executor_stats().is_some(),
+            )
             .await
             .map_err(|e| anyhow!(e))?;
 
@@ -761,6 +788,7 @@ impl StageRunner {
                     NodeBody::Exchange(_exchange_node) => PlanNodeProst {
                         children: vec![],
                         identity,
+                        operator_id: execution_plan_node.plan_node_id.0 as u64,
                         node_body: Some(NodeBody::Exchange(ExchangeNode {
                             sources: exchange_sources,
                             input_schema: execution_plan_node.schema.clone(),
@@ -769,6 +797,7 @@ impl StageRunner {
                     NodeBody::MergeSortExchange(sort_merge_exchange_node) => PlanNodeProst {
                         children: vec![],
                         identity,
+                        operator_id: execution_plan_node.plan_node_id.0 as u64,
                         node_body: Some(NodeBody::MergeSortExchange(MergeSortExchangeNode {
                             exchange: Some(ExchangeNode {
                                 sources: exchange_sources,
@@ -794,6 +823,7 @@ impl StageRunner {
                 PlanNodeProst {
                     children: vec![],
                     identity,
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(NodeBody::RowSeqScan(scan_node)),
                 }
             }
@@ -810,6 +840,7 @@ impl StageRunner {
                 PlanNodeProst {
                     children: vec![],
                     identity,
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(NodeBody::Source(source_node)),
                 }
             }
@@ -825,6 +856,7 @@ impl StageRunner {
                 PlanNodeProst {
                     children,
                     identity,
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(execution_plan_node.node.clone()),
                 }
             }
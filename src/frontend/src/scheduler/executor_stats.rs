@@ -0,0 +1,148 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime stats of batch executors, collected for `EXPLAIN ANALYZE`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use risingwave_pb::task_service::ExecutorStats as ProstExecutorStats;
+use tokio::sync::Notify;
+
+use crate::optimizer::plan_node::PlanNodeId;
+
+/// Runtime stats of a plan node, merged from all the tasks executing it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlanNodeStats {
+    /// Sum of the rows produced by all tasks.
+    pub output_rows: u64,
+    /// Sum of the chunks produced by all tasks.
+    pub output_chunks: u64,
+    /// Max elapsed time among all tasks, as the tasks run in parallel.
+    pub elapsed_ns: u64,
+    /// Max peak memory among all tasks.
+    pub peak_memory_bytes: u64,
+    /// Number of tasks executing the plan node.
+    pub tasks: u64,
+}
+
+impl PlanNodeStats {
+    fn merge(&mut self, stats: &ProstExecutorStats) {
+        self.output_rows += stats.output_rows;
+        self.output_chunks += stats.output_chunks;
+        self.elapsed_ns = self.elapsed_ns.max(stats.elapsed_ns);
+        self.peak_memory_bytes = self.peak_memory_bytes.max(stats.peak_memory_bytes);
+        self.tasks += 1;
+    }
+}
+
+impl fmt::Display for PlanNodeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "actual rows={}, chunks={}, time={:.3}ms, peak memory={} bytes, tasks={}",
+            self.output_rows,
+            self.output_chunks,
+            self.elapsed_ns as f64 / 1_000_000.0,
+            self.peak_memory_bytes,
+            self.tasks
+        )
+    }
+}
+
+/// Collects the executor stats reported by all stages of a query.
+pub struct QueryExecutorStats {
+    stats: Mutex<HashMap<PlanNodeId, PlanNodeStats>>,
+    unfinished_stages: AtomicUsize,
+    all_stages_finished: Notify,
+}
+
+pub type QueryExecutorStatsRef = Arc<QueryExecutorStats>;
+
+impl QueryExecutorStats {
+    pub fn new(stage_count: usize) -> Self {
+        Self {
+            stats: Mutex::new(HashMap::new()),
+            unfinished_stages: AtomicUsize::new(stage_count),
+            all_stages_finished: Notify::new(),
+        }
+    }
+
+    /// Merges the stats reported by one task.
+    pub fn merge(&self, stats: &[ProstExecutorStats]) {
+        let mut guard = self.stats.lock();
+        for stats in stats {
+            guard
+                .entry(PlanNodeId(stats.operator_id as i32))
+                .or_default()
+                .merge(stats);
+        }
+    }
+
+    pub fn get(&self, plan_node_id: PlanNodeId) -> Option<PlanNodeStats> {
+        self.stats.lock().get(&plan_node_id).cloned()
+    }
+
+    /// Marks a stage as finished, successfully or not. All the tasks of the stage should have
+    /// reported their stats.
+    pub fn finish_stage(&self) {
+        if self.unfinished_stages.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.all_stages_finished.notify_one();
+        }
+    }
+
+    /// Waits until all stages of the query are finished.
+    pub async fn wait_all_stages_finished(&self) {
+        while self.unfinished_stages.load(Ordering::Acquire) > 0 {
+            self.all_stages_finished.notified().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_merge_stats() {
+        let stats = QueryExecutorStats::new(2);
+        let task_stats = |output_rows, elapsed_ns| ProstExecutorStats {
+            operator_id: 1,
+            output_rows,
+            output_chunks: 1,
+            elapsed_ns,
+            peak_memory_bytes: 0,
+        };
+        stats.merge(&[task_stats(10, 100)]);
+        stats.finish_stage();
+        stats.merge(&[task_stats(5, 200)]);
+        stats.finish_stage();
+        stats.wait_all_stages_finished().await;
+
+        assert_eq!(
+            stats.get(PlanNodeId(1)),
+            Some(PlanNodeStats {
+                output_rows: 15,
+                output_chunks: 2,
+                elapsed_ns: 200,
+                peak_memory_bytes: 0,
+                tasks: 2,
+            })
+        );
+        assert_eq!(stats.get(PlanNodeId(2)), None);
+    }
+}
@@ -21,8 +21,10 @@ use anyhow::anyhow;
 use arc_swap::ArcSwap;
 use risingwave_common::util::epoch::INVALID_EPOCH;
 use risingwave_pb::hummock::HummockSnapshot;
+use risingwave_rpc_client::error::RpcError;
 use tokio::sync::mpsc::UnboundedSender;
-use tokio::sync::oneshot::{channel as once_channel, Sender as Callback};
+use tokio::sync::oneshot::{channel as once_channel, Receiver, Sender as Callback};
+use tonic::Code;
 use tracing::error;
 
 use crate::meta_client::FrontendMetaClient;
@@ -35,6 +37,7 @@ pub type HummockSnapshotManagerRef = Arc<HummockSnapshotManager>;
 pub type PinnedHummockSnapshot = HummockSnapshotGuard;
 
 type SnapshotRef = Arc<ArcSwap<HummockSnapshot>>;
+type SnapshotCallback = Callback<SchedulerResult<HummockSnapshot>>;
 
 /// Cache of hummock snapshot in meta.
 pub struct HummockSnapshotManager {
@@ -57,7 +60,13 @@ pub struct HummockSnapshotManager {
 enum EpochOperation {
     RequestEpoch {
         query_id: QueryId,
-        sender: Callback<SchedulerResult<HummockSnapshot>>,
+        sender: SnapshotCallback,
+    },
+    /// Requests a historical epoch for a time-travel query.
+    RequestSpecificEpoch {
+        query_id: QueryId,
+        epoch: u64,
+        sender: SnapshotCallback,
     },
     ReleaseEpoch {
         query_id: QueryId,
@@ -110,6 +119,7 @@ impl HummockSnapshotManager {
             let mut manager = HummockSnapshotManagerCore::new(meta_client, latest_snapshot_cloned);
             let mut unpin_batches = vec![];
             let mut pin_batches = vec![];
+            let mut pin_specific_batches = vec![];
             let mut unpin_interval =
                 tokio::time::interval(Duration::from_secs(UNPIN_INTERVAL_SECS));
             let mut last_unpin_time = Instant::now();
@@ -124,6 +134,13 @@ impl HummockSnapshotManager {
                     Some(EpochOperation::RequestEpoch { query_id, sender }) => {
                         pin_batches.push((query_id, sender));
                     }
+                    Some(EpochOperation::RequestSpecificEpoch {
+                        query_id,
+                        epoch,
+                        sender,
+                    }) => {
+                        pin_specific_batches.push((query_id, epoch, sender));
+                    }
                     Some(EpochOperation::ReleaseEpoch { query_id, epoch }) => {
                         unpin_batches.push((query_id, epoch));
                     }
@@ -135,6 +152,13 @@ impl HummockSnapshotManager {
                         EpochOperation::RequestEpoch { query_id, sender } => {
                             pin_batches.push((query_id, sender));
                         }
+                        EpochOperation::RequestSpecificEpoch {
+                            query_id,
+                            epoch,
+                            sender,
+                        } => {
+                            pin_specific_batches.push((query_id, epoch, sender));
+                        }
                         EpochOperation::ReleaseEpoch { query_id, epoch } => {
                             unpin_batches.push((query_id, epoch));
                         }
@@ -144,6 +168,11 @@ impl HummockSnapshotManager {
                 if !unpin_batches.is_empty() {
                     manager.release_epoch(&mut unpin_batches);
                 }
+                if !pin_specific_batches.is_empty() {
+                    manager
+                        .pin_specific_epoch_for_queries(&mut pin_specific_batches)
+                        .await;
+                }
 
                 let need_unpin = last_unpin_time.elapsed().as_secs() >= UNPIN_INTERVAL_SECS;
                 if !pin_batches.is_empty() || need_unpin {
@@ -171,6 +200,31 @@ impl HummockSnapshotManager {
             query_id: query_id.clone(),
             sender,
         };
+        self.acquire_inner(query_id, msg, rc).await
+    }
+
+    /// Acquires the snapshot as of a historical `epoch` for a time-travel query, which is pinned
+    /// in meta until the query finishes.
+    pub async fn acquire_as_of(
+        &self,
+        query_id: &QueryId,
+        epoch: u64,
+    ) -> SchedulerResult<PinnedHummockSnapshot> {
+        let (sender, rc) = once_channel();
+        let msg = EpochOperation::RequestSpecificEpoch {
+            query_id: query_id.clone(),
+            epoch,
+            sender,
+        };
+        self.acquire_inner(query_id, msg, rc).await
+    }
+
+    async fn acquire_inner(
+        &self,
+        query_id: &QueryId,
+        msg: EpochOperation,
+        rc: Receiver<SchedulerResult<HummockSnapshot>>,
+    ) -> SchedulerResult<PinnedHummockSnapshot> {
         self.sender.send(msg).map_err(|_| {
             SchedulerError::Internal(anyhow!("Failed to get epoch for query: {:?}", query_id,))
         })?;
@@ -222,7 +276,7 @@ impl HummockSnapshotManagerCore {
     /// better epoch freshness.
     async fn get_epoch_for_query_from_rpc(
         &mut self,
-        batches: &mut Vec<(QueryId, Callback<SchedulerResult<HummockSnapshot>>)>,
+        batches: &mut Vec<(QueryId, SnapshotCallback)>,
     ) -> HummockSnapshot {
         let ret = self.meta_client.get_epoch().await;
         match ret {
@@ -250,7 +304,7 @@ impl HummockSnapshotManagerCore {
     /// maintained by meta's notification service.
     fn get_epoch_for_query_from_push(
         &mut self,
-        batches: &mut Vec<(QueryId, Callback<SchedulerResult<HummockSnapshot>>)>,
+        batches: &mut Vec<(QueryId, SnapshotCallback)>,
     ) -> HummockSnapshot {
         let snapshot = HummockSnapshot::clone(&self.latest_snapshot.load());
         self.notify_epoch_assigned_for_queries(&snapshot, batches);
@@ -262,7 +316,7 @@ impl HummockSnapshotManagerCore {
     fn notify_epoch_assigned_for_queries(
         &mut self,
         snapshot: &HummockSnapshot,
-        batches: &mut Vec<(QueryId, Callback<SchedulerResult<HummockSnapshot>>)>,
+        batches: &mut Vec<(QueryId, SnapshotCallback)>,
     ) {
         if batches.is_empty() {
             return;
@@ -282,6 +336,51 @@ impl HummockSnapshotManagerCore {
         }
     }
 
+    /// Pin the historical epochs of time-travel queries in meta with rpcs, and notify the queries
+    /// with the snapshots as of the epochs.
+    async fn pin_specific_epoch_for_queries(
+        &mut self,
+        batches: &mut Vec<(QueryId, u64, SnapshotCallback)>,
+    ) {
+        for (id, epoch, cb) in batches.drain(..) {
+            // Record the query before pinning, so that the epoch is not unpinned by the following
+            // `unpin_snapshot_before` until the query finishes.
+            self.epoch_to_query_ids
+                .entry(epoch)
+                .or_default()
+                .insert(id.clone());
+            match self.meta_client.pin_specific_snapshot(epoch).await {
+                Ok(_) => {
+                    let _ = cb.send(Ok(HummockSnapshot {
+                        committed_epoch: epoch,
+                        current_epoch: epoch,
+                    }));
+                }
+                Err(e) => {
+                    self.release_epoch(&mut vec![(id.clone(), epoch)]);
+                    let err = match &e {
+                        // The epoch is before the safe epoch, and its versions may have been
+                        // compacted.
+                        RpcError::GrpcStatus(status) if status.code() == Code::InvalidArgument => {
+                            anyhow!(
+                                "cannot read the data as of epoch {}, which is older than the time-travel retention: {}",
+                                epoch,
+                                status.message()
+                            )
+                        }
+                        _ => anyhow!(
+                            "Failed to pin epoch {} for query: {:?} because of RPC Error: {:?}",
+                            epoch,
+                            id,
+                            e
+                        ),
+                    };
+                    let _ = cb.send(Err(SchedulerError::Internal(err)));
+                }
+            }
+        }
+    }
+
     pub fn release_epoch(&mut self, queries: &mut Vec<(QueryId, u64)>) {
         for (query_id, epoch) in queries.drain(..) {
             let query_ids = self.epoch_to_query_ids.get_mut(&epoch);
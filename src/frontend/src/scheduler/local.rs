@@ -22,7 +22,9 @@ use futures::Stream;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use pgwire::pg_server::BoxedError;
-use risingwave_batch::executor::{BoxedDataChunkStream, ExecutorBuilder};
+use risingwave_batch::executor::{
+    BoxedDataChunkStream, ExecutorBuilder, ExecutorStatsCollectorRef,
+};
 use risingwave_batch::task::TaskId;
 use risingwave_common::array::DataChunk;
 use risingwave_common::bail;
@@ -74,6 +76,8 @@ pub struct LocalQueryExecution {
     // The snapshot will be released when LocalQueryExecution is dropped.
     snapshot: HummockSnapshotGuard,
     auth_context: Arc<AuthContext>,
+    /// Collects stats of the executors running in frontend, e.g. for `EXPLAIN ANALYZE`.
+    executor_stats: Option<ExecutorStatsCollectorRef>,
 }
 
 impl LocalQueryExecution {
@@ -90,9 +94,16 @@ impl LocalQueryExecution {
             front_env,
             snapshot,
             auth_context,
+            executor_stats: None,
         }
     }
 
+    #[must_use]
+    pub fn with_executor_stats(mut self, executor_stats: ExecutorStatsCollectorRef) -> Self {
+        self.executor_stats = Some(executor_stats);
+        self
+    }
+
     #[try_stream(ok = DataChunk, error = RwError)]
     pub async fn run_inner(self) {
         debug!(
@@ -100,8 +111,11 @@ impl LocalQueryExecution {
             self.query.query_id, self.sql
         );
 
-        let context =
+        let mut context =
             FrontendBatchTaskContext::new(self.front_env.clone(), self.auth_context.clone());
+        if let Some(executor_stats) = self.executor_stats.clone() {
+            context = context.with_executor_stats(executor_stats);
+        }
 
         let task_id = TaskId {
             query_id: self.query.query_id.id.clone(),
@@ -347,6 +361,7 @@ impl LocalQueryExecution {
                     /// there is no children any more.
                     children: vec![],
                     identity: Uuid::new_v4().to_string(),
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(node_body),
                 })
             }
@@ -370,6 +385,7 @@ impl LocalQueryExecution {
                     children: vec![],
                     // TODO: Generate meaningful identify
                     identity: Uuid::new_v4().to_string(),
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(node_body),
                 })
             }
@@ -391,6 +407,7 @@ impl LocalQueryExecution {
                     children: vec![],
                     // TODO: Generate meaningful identify
                     identity: Uuid::new_v4().to_string(),
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(node_body),
                 })
             }
@@ -430,6 +447,7 @@ impl LocalQueryExecution {
                 Ok(PlanNodeProst {
                     children: vec![left_child],
                     identity: Uuid::new_v4().to_string(),
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(node_body),
                 })
             }
@@ -444,6 +462,7 @@ impl LocalQueryExecution {
                     children,
                     // TODO: Generate meaningful identify
                     identity: Uuid::new_v4().to_string(),
+                    operator_id: execution_plan_node.plan_node_id.0 as u64,
                     node_body: Some(execution_plan_node.node.clone()),
                 })
             }
@@ -24,6 +24,8 @@ use crate::session::SessionImpl;
 
 mod distributed;
 pub use distributed::*;
+mod executor_stats;
+pub use executor_stats::*;
 mod hummock_snapshot_manager;
 pub use hummock_snapshot_manager::*;
 pub mod plan_fragmenter;
@@ -45,19 +47,34 @@ pub trait DataChunkStream = Stream<Item = Result<DataChunk>>;
 /// Context for mpp query execution.
 pub struct ExecutionContext {
     session: Arc<SessionImpl>,
+    /// Set when executor stats should be collected, e.g. for `EXPLAIN ANALYZE`.
+    executor_stats: Option<QueryExecutorStatsRef>,
 }
 
 pub type ExecutionContextRef = Arc<ExecutionContext>;
 
 impl ExecutionContext {
     pub fn new(session: Arc<SessionImpl>) -> Self {
-        Self { session }
+        Self {
+            session,
+            executor_stats: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_executor_stats(mut self, executor_stats: QueryExecutorStatsRef) -> Self {
+        self.executor_stats = Some(executor_stats);
+        self
     }
 
     pub fn session(&self) -> &SessionImpl {
         &self.session
     }
 
+    pub fn executor_stats(&self) -> Option<&QueryExecutorStatsRef> {
+        self.executor_stats.as_ref()
+    }
+
     pub fn to_batch_task_context(&self) -> FrontendBatchTaskContext {
         FrontendBatchTaskContext::new(self.session.env().clone(), self.session.auth_context())
     }
@@ -29,12 +29,15 @@ use risingwave_connector::source::{ConnectorProperties, SplitEnumeratorImpl, Spl
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::{ExchangeInfo, ScanRange as ScanRangeProto};
 use risingwave_pb::common::Buffer;
+use risingwave_pb::expr::project_set_select_item::SelectItem;
+use risingwave_pb::expr::{AggCall, ExprNode, TableFunction};
 use risingwave_pb::plan_common::Field as FieldProst;
 use serde::ser::SerializeStruct;
 use serde::Serialize;
 use uuid::Uuid;
 
 use crate::catalog::catalog_service::CatalogReader;
+use crate::expr::bind_parameters_in_proto;
 use crate::optimizer::plan_node::generic::GenericPlanRef;
 use crate::optimizer::plan_node::{PlanNodeId, PlanNodeType};
 use crate::optimizer::property::Distribution;
@@ -110,6 +113,96 @@ impl ExecutionPlanNode {
     pub fn node_type(&self) -> PlanNodeType {
         self.plan_node_type
     }
+
+    /// Replaces the parameters in the expressions of the plan tree with `params`. See
+    /// [`Query::bind_parameters`].
+    fn bind_parameters(&self, params: &[ExprNode]) -> Self {
+        let mut node = self.node.clone();
+        for expr in node_exprs_mut(&mut node) {
+            bind_parameters_in_proto(expr, params);
+        }
+        Self {
+            plan_node_id: self.plan_node_id,
+            plan_node_type: self.plan_node_type,
+            node,
+            schema: self.schema.clone(),
+            children: self
+                .children
+                .iter()
+                .map(|child| Arc::new(child.bind_parameters(params)))
+                .collect(),
+            source_stage_id: self.source_stage_id,
+        }
+    }
+}
+
+/// Returns all the expressions of a plan node, which are where the parameters of prepared
+/// statements may appear.
+fn node_exprs_mut(node: &mut NodeBody) -> Vec<&mut ExprNode> {
+    fn table_function_args(table_function: &mut TableFunction) -> Vec<&mut ExprNode> {
+        table_function.args.iter_mut().collect()
+    }
+    fn agg_call_filters(agg_calls: &mut [AggCall]) -> Vec<&mut ExprNode> {
+        agg_calls
+            .iter_mut()
+            .filter_map(|agg_call| agg_call.filter.as_mut())
+            .collect()
+    }
+
+    match node {
+        NodeBody::Project(project) => project.select_list.iter_mut().collect(),
+        NodeBody::Filter(filter) => filter.search_condition.iter_mut().collect(),
+        NodeBody::Update(update) => update.exprs.iter_mut().collect(),
+        NodeBody::Values(values) => values
+            .tuples
+            .iter_mut()
+            .flat_map(|tuple| tuple.cells.iter_mut())
+            .collect(),
+        NodeBody::NestedLoopJoin(join) => join.join_cond.iter_mut().collect(),
+        NodeBody::HashJoin(join) => join.condition.iter_mut().collect(),
+        NodeBody::LocalLookupJoin(join) => join.condition.iter_mut().collect(),
+        NodeBody::DistributedLookupJoin(join) => join.condition.iter_mut().collect(),
+        NodeBody::HashAgg(agg) => agg_call_filters(&mut agg.agg_calls),
+        NodeBody::SortAgg(agg) => agg
+            .group_key
+            .iter_mut()
+            .chain(agg_call_filters(&mut agg.agg_calls))
+            .collect(),
+        NodeBody::TableFunction(table_function) => table_function
+            .table_function
+            .as_mut()
+            .map(table_function_args)
+            .unwrap_or_default(),
+        NodeBody::ProjectSet(project_set) => project_set
+            .select_list
+            .iter_mut()
+            .flat_map(|item| match &mut item.select_item {
+                Some(SelectItem::Expr(expr)) => vec![expr],
+                Some(SelectItem::TableFunction(table_function)) => {
+                    table_function_args(table_function)
+                }
+                None => vec![],
+            })
+            .collect(),
+        // Listed one by one, so that a new node type with expressions can't be missed.
+        NodeBody::Insert(_)
+        | NodeBody::Delete(_)
+        | NodeBody::Exchange(_)
+        | NodeBody::Sort(_)
+        | NodeBody::TopN(_)
+        | NodeBody::RowSeqScan(_)
+        | NodeBody::Limit(_)
+        | NodeBody::MergeSortExchange(_)
+        | NodeBody::SortMergeJoin(_)
+        | NodeBody::HopWindow(_)
+        | NodeBody::SysRowSeqScan(_)
+        | NodeBody::Expand(_)
+        | NodeBody::Union(_)
+        | NodeBody::GroupTopN(_)
+        | NodeBody::Source(_)
+        | NodeBody::OverAgg(_)
+        | NodeBody::SessionWindow(_) => vec![],
+    }
 }
 
 /// `BatchPlanFragmenter` splits a query plan into fragments.
@@ -177,6 +270,48 @@ impl Query {
         &self.query_id
     }
 
+    /// Whether any stage reads from a source. The splits of a source are enumerated when the query
+    /// is fragmented and may change afterwards.
+    pub fn has_source_scan(&self) -> bool {
+        self.stage_graph
+            .stages
+            .values()
+            .any(|stage| stage.source_info.is_some())
+    }
+
+    /// Creates a new instance of the query with a new query id, where the parameters of the
+    /// prepared statement it's planned from are replaced with `params`, the constants of the
+    /// values of `$1`, `$2`, ....
+    pub fn bind_parameters(&self, params: &[ExprNode]) -> Query {
+        let query_id = QueryId::default();
+        let stages = self
+            .stage_graph
+            .stages
+            .iter()
+            .map(|(stage_id, stage)| {
+                let stage = QueryStage {
+                    query_id: query_id.clone(),
+                    id: stage.id,
+                    root: Arc::new(stage.root.bind_parameters(params)),
+                    exchange_info: stage.exchange_info.clone(),
+                    parallelism: stage.parallelism,
+                    table_scan_info: stage.table_scan_info.clone(),
+                    source_info: stage.source_info.clone(),
+                };
+                (*stage_id, Arc::new(stage))
+            })
+            .collect();
+        Query {
+            query_id,
+            stage_graph: StageGraph {
+                root_stage_id: self.stage_graph.root_stage_id,
+                stages,
+                child_edges: self.stage_graph.child_edges.clone(),
+                parent_edges: self.stage_graph.parent_edges.clone(),
+            },
+        }
+    }
+
     pub fn stages_with_table_scan(&self) -> HashSet<StageId> {
         self.stage_graph
             .stages
@@ -785,13 +920,19 @@ fn derive_partitions(
 #[cfg(test)]
 mod tests {
     use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
 
     use risingwave_common::hash::ParallelUnitId;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_pb::batch_plan::plan_node::NodeBody;
+    use risingwave_pb::batch_plan::FilterNode;
     use risingwave_pb::common::ParallelUnit;
 
+    use crate::expr::{
+        Expr, ExprImpl, ExprType, FunctionCall, InputRef, Literal, Parameter, ParameterTypes,
+    };
     use crate::optimizer::plan_node::PlanNodeType;
-    use crate::scheduler::plan_fragmenter::StageId;
+    use crate::scheduler::plan_fragmenter::{ExecutionPlanNode, QueryStage, StageId};
 
     #[tokio::test]
     async fn test_fragmenter() {
@@ -874,6 +1015,71 @@ mod tests {
         assert!(scan_node2.has_table_scan());
     }
 
+    #[tokio::test]
+    async fn test_bind_parameters() {
+        let mut query = crate::scheduler::distributed::tests::create_query().await;
+
+        // Replace the condition of the filter with `a = $1`.
+        let param_types = ParameterTypes::new(vec![Some(DataType::Int32)]);
+        let condition: ExprImpl = FunctionCall::new(
+            ExprType::Equal,
+            vec![
+                InputRef::new(0, DataType::Int32).into(),
+                Parameter::new(1, param_types).into(),
+            ],
+        )
+        .unwrap()
+        .into();
+        let filter_stage = query.stage_graph.stages.get(&3).unwrap();
+        let filter_stage = QueryStage {
+            query_id: filter_stage.query_id.clone(),
+            id: filter_stage.id,
+            root: Arc::new(ExecutionPlanNode {
+                node: NodeBody::Filter(FilterNode {
+                    search_condition: Some(condition.to_expr_proto()),
+                }),
+                ..(*filter_stage.root).clone()
+            }),
+            exchange_info: filter_stage.exchange_info.clone(),
+            parallelism: filter_stage.parallelism,
+            table_scan_info: filter_stage.table_scan_info.clone(),
+            source_info: filter_stage.source_info.clone(),
+        };
+        query.stage_graph.stages.insert(3, Arc::new(filter_stage));
+
+        let value = Literal::new(Some(ScalarImpl::Int32(42)), DataType::Int32);
+        let bound = query.bind_parameters(&[value.to_expr_proto()]);
+        assert_ne!(bound.query_id, query.query_id);
+        assert_eq!(
+            bound.stage_graph.stages.len(),
+            query.stage_graph.stages.len()
+        );
+        assert_eq!(bound.stage_graph.child_edges, query.stage_graph.child_edges);
+        assert_eq!(
+            bound.stage_graph.parent_edges,
+            query.stage_graph.parent_edges
+        );
+        for stage in bound.stage_graph.stages.values() {
+            assert_eq!(stage.query_id, bound.query_id);
+        }
+
+        let expected: ExprImpl = FunctionCall::new(
+            ExprType::Equal,
+            vec![InputRef::new(0, DataType::Int32).into(), value.into()],
+        )
+        .unwrap()
+        .into();
+        let NodeBody::Filter(filter) = &bound.stage_graph.stages[&3].root.node else {
+            panic!("expect filter node");
+        };
+        assert_eq!(filter.search_condition, Some(expected.to_expr_proto()));
+        // The cached query is left unchanged.
+        let NodeBody::Filter(filter) = &query.stage_graph.stages[&3].root.node else {
+            panic!("expect filter node");
+        };
+        assert_eq!(filter.search_condition, Some(condition.to_expr_proto()));
+    }
+
     fn generate_parallel_units(
         start_id: ParallelUnitId,
         node_id: ParallelUnitId,
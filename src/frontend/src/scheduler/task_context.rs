@@ -14,8 +14,8 @@
 
 use std::sync::Arc;
 
-use risingwave_batch::executor::BatchTaskMetricsWithTaskLabels;
-use risingwave_batch::task::{BatchTaskContext, TaskOutput, TaskOutputId};
+use risingwave_batch::executor::{BatchTaskMetricsWithTaskLabels, ExecutorStatsCollectorRef};
+use risingwave_batch::task::{BatchTaskContext, MemoryContext, TaskOutput, TaskOutputId};
 use risingwave_common::catalog::SysCatalogReaderRef;
 use risingwave_common::config::BatchConfig;
 use risingwave_common::error::Result;
@@ -31,11 +31,24 @@ use crate::session::{AuthContext, FrontendEnv};
 pub struct FrontendBatchTaskContext {
     env: FrontendEnv,
     auth_context: Arc<AuthContext>,
+    mem_context: MemoryContext,
+    executor_stats: Option<ExecutorStatsCollectorRef>,
 }
 
 impl FrontendBatchTaskContext {
     pub fn new(env: FrontendEnv, auth_context: Arc<AuthContext>) -> Self {
-        Self { env, auth_context }
+        Self {
+            env,
+            auth_context,
+            mem_context: MemoryContext::root(),
+            executor_stats: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_executor_stats(mut self, executor_stats: ExecutorStatsCollectorRef) -> Self {
+        self.executor_stats = Some(executor_stats);
+        self
     }
 }
 
@@ -77,4 +90,12 @@ impl BatchTaskContext for FrontendBatchTaskContext {
     fn get_config(&self) -> &BatchConfig {
         self.env.batch_config()
     }
+
+    fn mem_context(&self) -> MemoryContext {
+        self.mem_context.clone()
+    }
+
+    fn executor_stats(&self) -> Option<ExecutorStatsCollectorRef> {
+        self.executor_stats.clone()
+    }
 }
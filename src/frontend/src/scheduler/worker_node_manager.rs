@@ -34,6 +34,8 @@ struct WorkerNodeManagerInner {
     worker_nodes: Vec<WorkerNode>,
     /// fragment vnode mapping info.
     fragment_vnode_mapping: HashMap<FragmentId, VnodeMapping>,
+    /// Bumped on every change of the worker nodes or the vnode mappings.
+    version: u64,
 }
 
 pub type WorkerNodeManagerRef = Arc<WorkerNodeManager>;
@@ -56,6 +58,7 @@ impl WorkerNodeManager {
         let inner = RwLock::new(WorkerNodeManagerInner {
             worker_nodes,
             fragment_vnode_mapping: HashMap::new(),
+            version: 0,
         });
         Self { inner }
     }
@@ -64,22 +67,29 @@ impl WorkerNodeManager {
         self.inner.read().unwrap().worker_nodes.clone()
     }
 
+    /// The version of the worker nodes and the vnode mappings. Plans cached for prepared
+    /// statements are scheduled against them, and become stale once the version changes.
+    pub fn version(&self) -> u64 {
+        self.inner.read().unwrap().version
+    }
+
     pub fn add_worker_node(&self, node: WorkerNode) {
-        self.inner.write().unwrap().worker_nodes.push(node);
+        let mut write_guard = self.inner.write().unwrap();
+        write_guard.worker_nodes.push(node);
+        write_guard.version += 1;
     }
 
     pub fn remove_worker_node(&self, node: WorkerNode) {
-        self.inner
-            .write()
-            .unwrap()
-            .worker_nodes
-            .retain(|x| *x != node);
+        let mut write_guard = self.inner.write().unwrap();
+        write_guard.worker_nodes.retain(|x| *x != node);
+        write_guard.version += 1;
     }
 
     pub fn refresh(&self, nodes: Vec<WorkerNode>, mapping: HashMap<FragmentId, VnodeMapping>) {
         let mut write_guard = self.inner.write().unwrap();
         write_guard.worker_nodes = nodes;
         write_guard.fragment_vnode_mapping = mapping;
+        write_guard.version += 1;
     }
 
     /// Get a random worker node.
@@ -136,30 +146,30 @@ impl WorkerNodeManager {
     }
 
     pub fn insert_fragment_mapping(&self, fragment_id: FragmentId, vnode_mapping: VnodeMapping) {
-        self.inner
-            .write()
-            .unwrap()
+        let mut write_guard = self.inner.write().unwrap();
+        write_guard
             .fragment_vnode_mapping
             .try_insert(fragment_id, vnode_mapping)
             .unwrap();
+        write_guard.version += 1;
     }
 
     pub fn update_fragment_mapping(&self, fragment_id: FragmentId, vnode_mapping: VnodeMapping) {
-        self.inner
-            .write()
-            .unwrap()
+        let mut write_guard = self.inner.write().unwrap();
+        write_guard
             .fragment_vnode_mapping
             .insert(fragment_id, vnode_mapping)
             .unwrap();
+        write_guard.version += 1;
     }
 
     pub fn remove_fragment_mapping(&self, fragment_id: &FragmentId) {
-        self.inner
-            .write()
-            .unwrap()
+        let mut write_guard = self.inner.write().unwrap();
+        write_guard
             .fragment_vnode_mapping
             .remove(fragment_id)
             .unwrap();
+        write_guard.version += 1;
     }
 }
 
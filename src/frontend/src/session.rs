@@ -16,11 +16,12 @@ use std::collections::HashMap;
 use std::fmt::Formatter;
 use std::io::{Error, ErrorKind};
 use std::marker::Sync;
-use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 // use tokio::sync::Mutex;
 use std::time::Duration;
 
+use bytes::Bytes;
 use parking_lot::{RwLock, RwLockReadGuard};
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::PgResponse;
@@ -32,7 +33,7 @@ use risingwave_common::catalog::{
     DEFAULT_DATABASE_NAME, DEFAULT_SUPER_USER, DEFAULT_SUPER_USER_ID,
 };
 use risingwave_common::config::{load_config, BatchConfig};
-use risingwave_common::error::{Result, RwError};
+use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::monitor::process_linux::monitor_process;
 use risingwave_common::session_config::ConfigMap;
 use risingwave_common::types::DataType;
@@ -50,11 +51,14 @@ use tokio::sync::oneshot::Sender;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
-use crate::binder::Binder;
+use crate::binder::{Binder, BoundStatement};
 use crate::catalog::catalog_service::{CatalogReader, CatalogWriter, CatalogWriterImpl};
 use crate::catalog::root_catalog::{Catalog, SchemaPath};
 use crate::catalog::{check_schema_writable, DatabaseId, SchemaId};
 use crate::expr::CorrelatedId;
+use crate::handler::extended_handle::{
+    handle_bind, handle_execute, handle_parse, Portal, PrepareStatement,
+};
 use crate::handler::handle;
 use crate::handler::privilege::{check_privileges, ObjectCheckItem};
 use crate::handler::util::to_pg_field;
@@ -63,7 +67,6 @@ use crate::meta_client::{FrontendMetaClient, FrontendMetaClientImpl};
 use crate::monitor::FrontendMetrics;
 use crate::observer::FrontendObserverNode;
 use crate::optimizer::plan_node::PlanNodeId;
-use crate::planner::Planner;
 use crate::scheduler::worker_node_manager::{WorkerNodeManager, WorkerNodeManagerRef};
 use crate::scheduler::{HummockSnapshotManager, HummockSnapshotManagerRef, QueryManager};
 use crate::user::user_authentication::md5_hash_with_salt;
@@ -91,6 +94,9 @@ pub struct OptimizerContext {
     pub next_correlated_id: AtomicU32,
     /// Store options or properties from the `with` clause
     pub with_options: WithOptions,
+    /// The epoch the base tables are read as of, recorded when the first one is planned. The
+    /// inner `None` means the latest committed epoch, otherwise it is a time-travel query.
+    pub read_epoch: Mutex<Option<Option<u64>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -144,6 +150,29 @@ impl OptimizerContextRef {
         let mut guard = self.inner.optimizer_trace.lock().unwrap();
         guard.drain(..).collect()
     }
+
+    /// Records the epoch a base table is read as of, where `None` means the latest committed
+    /// epoch. All the base tables of a query must be read as of the same epoch.
+    pub fn record_read_epoch(&self, epoch: Option<u64>) -> Result<()> {
+        let mut guard = self.inner.read_epoch.lock().unwrap();
+        match *guard {
+            None => *guard = Some(epoch),
+            Some(read_epoch) if read_epoch == epoch => {}
+            Some(_) => {
+                return Err(ErrorCode::NotImplemented(
+                    "reading tables as of different times in a query".to_string(),
+                    None.into(),
+                )
+                .into())
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the epoch the query reads as of, if it is a time-travel query.
+    pub fn time_travel_epoch(&self) -> Option<u64> {
+        self.inner.read_epoch.lock().unwrap().flatten()
+    }
 }
 
 impl OptimizerContext {
@@ -157,6 +186,7 @@ impl OptimizerContext {
             optimizer_trace: Arc::new(Mutex::new(vec![])),
             next_correlated_id: AtomicU32::new(1),
             with_options,
+            read_epoch: Mutex::new(None),
         }
     }
 
@@ -173,6 +203,7 @@ impl OptimizerContext {
             optimizer_trace: Arc::new(Mutex::new(vec![])),
             next_correlated_id: AtomicU32::new(1),
             with_options: Default::default(),
+            read_epoch: Mutex::new(None),
         }
         .into()
     }
@@ -463,6 +494,9 @@ pub struct SessionImpl {
     user_authenticator: UserAuthenticator,
     /// Stores the value of configurations.
     config_map: RwLock<ConfigMap>,
+    /// Bumped on each change of the configurations, so that the plans cached for prepared
+    /// statements can tell whether they were generated with the current configurations.
+    config_version: AtomicU64,
 
     /// Identified by process_id, secret_key. Corresponds to SessionManager.
     id: (i32, i32),
@@ -480,6 +514,7 @@ impl SessionImpl {
             auth_context,
             user_authenticator,
             config_map: RwLock::new(Default::default()),
+            config_version: AtomicU64::new(0),
             id,
         }
     }
@@ -495,6 +530,7 @@ impl SessionImpl {
             )),
             user_authenticator: UserAuthenticator::None,
             config_map: Default::default(),
+            config_version: AtomicU64::new(0),
             // Mock session use non-sense id.
             id: (0, 0),
         }
@@ -525,7 +561,14 @@ impl SessionImpl {
     }
 
     pub fn set_config(&self, key: &str, value: Vec<String>) -> Result<()> {
-        self.config_map.write().set(key, value)
+        let mut config_map = self.config_map.write();
+        config_map.set(key, value)?;
+        self.config_version.fetch_add(1, Ordering::AcqRel);
+        Ok(())
+    }
+
+    pub fn config_version(&self) -> u64 {
+        self.config_version.load(Ordering::Acquire)
     }
 
     pub fn session_id(&self) -> SessionId {
@@ -755,6 +798,9 @@ impl SessionManagerImpl {
 
 #[async_trait::async_trait]
 impl Session<PgResponseStream> for SessionImpl {
+    type Portal = Portal;
+    type PreparedStatement = PrepareStatement;
+
     async fn run_statement(
         self: Arc<Self>,
         sql: &str,
@@ -801,15 +847,16 @@ impl Session<PgResponseStream> for SessionImpl {
         Ok(rsp)
     }
 
-    async fn infer_return_type(
+    fn parse(
         self: Arc<Self>,
         sql: &str,
-    ) -> std::result::Result<Vec<PgFieldDescriptor>, BoxedError> {
+        param_types: Vec<Option<DataType>>,
+    ) -> std::result::Result<PrepareStatement, BoxedError> {
         // Parse sql.
         let mut stmts = Parser::parse_sql(sql)
             .inspect_err(|e| tracing::error!("failed to parse sql:\n{}:\n{}", sql, e))?;
         if stmts.is_empty() {
-            return Ok(vec![]);
+            return Ok(PrepareStatement::Empty);
         }
         if stmts.len() > 1 {
             return Err(Box::new(Error::new(
@@ -818,121 +865,175 @@ impl Session<PgResponseStream> for SessionImpl {
             )));
         }
         let stmt = stmts.swap_remove(0);
-        // This part refers from src/frontend/handler/ so the Vec<PgFieldDescriptor> is same as
-        // result of run_statement().
-        let rsp = match stmt {
-            Statement::Query(_) => infer(self, stmt, sql)
-                .inspect_err(|e| tracing::error!("failed to handle sql:\n{}:\n{}", sql, e))?,
-            Statement::ShowObjects(show_object) => match show_object {
-                ShowObject::Columns { table: _ } => {
-                    vec![
-                        PgFieldDescriptor::new(
-                            "Name".to_owned(),
-                            DataType::VARCHAR.to_oid(),
-                            DataType::VARCHAR.type_len(),
-                        ),
-                        PgFieldDescriptor::new(
-                            "Type".to_owned(),
-                            DataType::VARCHAR.to_oid(),
-                            DataType::VARCHAR.type_len(),
-                        ),
-                    ]
-                }
-                _ => {
-                    vec![PgFieldDescriptor::new(
+        Ok(handle_parse(self, stmt, param_types)
+            .inspect_err(|e| tracing::error!("failed to parse sql:\n{}:\n{}", sql, e))?)
+    }
+
+    fn bind(
+        self: Arc<Self>,
+        prepare_statement: PrepareStatement,
+        params: Vec<Option<Bytes>>,
+        param_format: bool,
+        result_format: bool,
+    ) -> std::result::Result<Portal, BoxedError> {
+        Ok(handle_bind(
+            prepare_statement,
+            params,
+            param_format,
+            result_format,
+        )?)
+    }
+
+    async fn execute(
+        self: Arc<Self>,
+        portal: Portal,
+    ) -> std::result::Result<PgResponse<PgResponseStream>, BoxedError> {
+        let sql = portal.to_string();
+        let rsp = handle_execute(self, portal)
+            .await
+            .inspect_err(|e| tracing::error!("failed to handle sql:\n{}:\n{}", sql, e))?;
+        Ok(rsp)
+    }
+
+    fn describe_statement(
+        self: Arc<Self>,
+        prepare_statement: PrepareStatement,
+    ) -> std::result::Result<(Vec<DataType>, Vec<PgFieldDescriptor>), BoxedError> {
+        Ok(match prepare_statement {
+            PrepareStatement::Empty => (vec![], vec![]),
+            PrepareStatement::Prepared {
+                bound_statement,
+                param_types,
+                ..
+            } => (param_types, infer_bound(&bound_statement)),
+            PrepareStatement::PureStatement(statement) => (vec![], infer(statement)),
+        })
+    }
+
+    fn describe_portal(
+        self: Arc<Self>,
+        portal: Portal,
+    ) -> std::result::Result<Vec<PgFieldDescriptor>, BoxedError> {
+        let (mut row_description, result_format) = match portal {
+            Portal::Empty => (vec![], false),
+            Portal::Portal {
+                bound_statement,
+                result_format,
+                ..
+            } => (infer_bound(&bound_statement), result_format),
+            Portal::PureStatement {
+                statement,
+                result_format,
+            } => (infer(statement), result_format),
+        };
+        if result_format {
+            row_description
+                .iter_mut()
+                .for_each(|desc| desc.set_to_binary());
+        }
+        Ok(row_description)
+    }
+
+    fn user_authenticator(&self) -> &UserAuthenticator {
+        &self.user_authenticator
+    }
+
+    fn id(&self) -> SessionId {
+        self.id
+    }
+}
+
+/// Returns the row description of a bound statement. Only queries return rows.
+fn infer_bound(bound_statement: &BoundStatement) -> Vec<PgFieldDescriptor> {
+    match bound_statement {
+        BoundStatement::Query(query) => query.schema().fields().iter().map(to_pg_field).collect(),
+        BoundStatement::Insert(_) | BoundStatement::Delete(_) | BoundStatement::Update(_) => {
+            vec![]
+        }
+    }
+}
+
+/// Returns the row description of a statement other than queries and DMLs.
+fn infer(stmt: Statement) -> Vec<PgFieldDescriptor> {
+    // This part refers from src/frontend/handler/ so the Vec<PgFieldDescriptor> is same as
+    // result of run_statement().
+    match stmt {
+        Statement::ShowObjects(show_object) => match show_object {
+            ShowObject::Columns { table: _ } => {
+                vec![
+                    PgFieldDescriptor::new(
                         "Name".to_owned(),
                         DataType::VARCHAR.to_oid(),
                         DataType::VARCHAR.type_len(),
-                    )]
-                }
-            },
-            Statement::ShowVariable { variable } => {
-                let name = &variable[0].real_value().to_lowercase();
-                if name.eq_ignore_ascii_case("ALL") {
-                    vec![
-                        PgFieldDescriptor::new(
-                            "Name".to_string(),
-                            DataType::VARCHAR.to_oid(),
-                            DataType::VARCHAR.type_len(),
-                        ),
-                        PgFieldDescriptor::new(
-                            "Setting".to_string(),
-                            DataType::VARCHAR.to_oid(),
-                            DataType::VARCHAR.type_len(),
-                        ),
-                        PgFieldDescriptor::new(
-                            "Description".to_string(),
-                            DataType::VARCHAR.to_oid(),
-                            DataType::VARCHAR.type_len(),
-                        ),
-                    ]
-                } else {
-                    vec![PgFieldDescriptor::new(
-                        name.to_ascii_lowercase(),
+                    ),
+                    PgFieldDescriptor::new(
+                        "Type".to_owned(),
                         DataType::VARCHAR.to_oid(),
                         DataType::VARCHAR.type_len(),
-                    )]
-                }
+                    ),
+                ]
             }
-            Statement::Describe { name: _ } => {
+            _ => {
+                vec![PgFieldDescriptor::new(
+                    "Name".to_owned(),
+                    DataType::VARCHAR.to_oid(),
+                    DataType::VARCHAR.type_len(),
+                )]
+            }
+        },
+        Statement::ShowVariable { variable } => {
+            let name = &variable[0].real_value().to_lowercase();
+            if name.eq_ignore_ascii_case("ALL") {
                 vec![
                     PgFieldDescriptor::new(
-                        "Name".to_owned(),
+                        "Name".to_string(),
                         DataType::VARCHAR.to_oid(),
                         DataType::VARCHAR.type_len(),
                     ),
                     PgFieldDescriptor::new(
-                        "Type".to_owned(),
+                        "Setting".to_string(),
+                        DataType::VARCHAR.to_oid(),
+                        DataType::VARCHAR.type_len(),
+                    ),
+                    PgFieldDescriptor::new(
+                        "Description".to_string(),
                         DataType::VARCHAR.to_oid(),
                         DataType::VARCHAR.type_len(),
                     ),
                 ]
-            }
-            Statement::Explain { .. } => {
+            } else {
                 vec![PgFieldDescriptor::new(
-                    "QUERY PLAN".to_owned(),
+                    name.to_ascii_lowercase(),
                     DataType::VARCHAR.to_oid(),
                     DataType::VARCHAR.type_len(),
                 )]
             }
-            _ => {
-                panic!("infer_return_type only support query statement");
-            }
-        };
-        Ok(rsp)
-    }
-
-    fn user_authenticator(&self) -> &UserAuthenticator {
-        &self.user_authenticator
-    }
-
-    fn id(&self) -> SessionId {
-        self.id
+        }
+        Statement::Describe { name: _ } => {
+            vec![
+                PgFieldDescriptor::new(
+                    "Name".to_owned(),
+                    DataType::VARCHAR.to_oid(),
+                    DataType::VARCHAR.type_len(),
+                ),
+                PgFieldDescriptor::new(
+                    "Type".to_owned(),
+                    DataType::VARCHAR.to_oid(),
+                    DataType::VARCHAR.type_len(),
+                ),
+            ]
+        }
+        Statement::Explain { .. } => {
+            vec![PgFieldDescriptor::new(
+                "QUERY PLAN".to_owned(),
+                DataType::VARCHAR.to_oid(),
+                DataType::VARCHAR.type_len(),
+            )]
+        }
+        _ => vec![],
     }
 }
 
-/// Returns row description of the statement
-fn infer(session: Arc<SessionImpl>, stmt: Statement, sql: &str) -> Result<Vec<PgFieldDescriptor>> {
-    let context = OptimizerContext::new(session, Arc::from(sql), WithOptions::try_from(&stmt)?);
-    let session = context.session_ctx.clone();
-
-    let bound = {
-        let mut binder = Binder::new(&session);
-        binder.bind(stmt)?
-    };
-
-    let root = Planner::new(context.into()).plan(bound)?;
-
-    let pg_descs = root
-        .schema()
-        .fields()
-        .iter()
-        .map(to_pg_field)
-        .collect::<Vec<PgFieldDescriptor>>();
-
-    Ok(pg_descs)
-}
-
 #[cfg(test)]
 mod tests {
     use assert_impl::assert_impl;
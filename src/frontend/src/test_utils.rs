@@ -29,11 +29,13 @@ use risingwave_common::catalog::{
 use risingwave_common::error::Result;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Sink as ProstSink, Source as ProstSource, Table as ProstTable,
+    View as ProstView,
 };
 use risingwave_pb::hummock::HummockSnapshot;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
+use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_pb::user::update_user_request::UpdateField;
 use risingwave_pb::user::{GrantPrivilege, UserInfo};
@@ -249,6 +251,13 @@ impl CatalogWriter for MockCatalogWriter {
         self.create_sink_inner(sink, graph)
     }
 
+    async fn create_function(&self, mut function: ProstFunction) -> Result<()> {
+        function.id = self.gen_id();
+        self.catalog.write().create_function(&function);
+        self.add_table_or_source_id(function.id, function.schema_id, function.database_id);
+        Ok(())
+    }
+
     async fn create_index(
         &self,
         mut index: ProstIndex,
@@ -315,6 +324,21 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
+    async fn alter_source(&self, source_id: u32, columns: Vec<ProstColumnCatalog>) -> Result<()> {
+        let schema_id = *self.table_id_to_schema_id.read().get(&source_id).unwrap();
+        let database_id = self.get_database_id_by_schema(schema_id);
+        let mut source = self
+            .catalog
+            .read()
+            .get_schema_by_id(&database_id, &schema_id)?
+            .get_source_by_id(&source_id)
+            .unwrap()
+            .to_prost(schema_id, database_id);
+        source.columns = columns;
+        self.catalog.write().update_source(&source);
+        Ok(())
+    }
+
     async fn drop_sink(&self, sink_id: u32) -> Result<()> {
         let (database_id, schema_id) = self.drop_table_or_sink_id(sink_id);
         self.catalog
@@ -360,6 +384,14 @@ impl CatalogWriter for MockCatalogWriter {
         self.catalog.write().drop_schema(database_id, schema_id);
         Ok(())
     }
+
+    async fn drop_function(&self, function_id: u32) -> Result<()> {
+        let (database_id, schema_id) = self.drop_table_or_source_id(function_id);
+        self.catalog
+            .write()
+            .drop_function(database_id, schema_id, function_id);
+        Ok(())
+    }
 }
 
 impl MockCatalogWriter {
@@ -623,6 +655,13 @@ impl FrontendMetaClient for MockFrontendMetaClient {
         })
     }
 
+    async fn pin_specific_snapshot(&self, epoch: u64) -> RpcResult<HummockSnapshot> {
+        Ok(HummockSnapshot {
+            committed_epoch: epoch,
+            current_epoch: epoch,
+        })
+    }
+
     async fn get_epoch(&self) -> RpcResult<HummockSnapshot> {
         Ok(HummockSnapshot {
             committed_epoch: 0,
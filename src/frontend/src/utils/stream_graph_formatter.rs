@@ -216,6 +216,14 @@ impl StreamGraphFormatter {
                     "state table: {}",
                     self.add_table(node.get_table().unwrap())
                 )),
+                stream_node::NodeBody::OverAgg(node) => Some(format!(
+                    "state table: {}",
+                    self.add_table(node.get_state_table().unwrap())
+                )),
+                stream_node::NodeBody::SessionWindow(node) => Some(format!(
+                    "state table: {}",
+                    self.add_table(node.get_state_table().unwrap())
+                )),
                 _ => None,
             };
         if let Some(explain_table_oneline) = explain_table_oneline {
@@ -14,7 +14,7 @@
 
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroUsize};
 
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, RwError};
@@ -25,11 +25,15 @@ use risingwave_sqlparser::ast::{
 use crate::catalog::source_catalog::KAFKA_CONNECTOR;
 
 mod options {
-    use risingwave_common::catalog::hummock::PROPERTIES_RETENTION_SECOND_KEY;
+    use risingwave_common::catalog::hummock::{
+        PROPERTIES_RETENTION_SECOND_KEY, PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY,
+    };
 
     pub const APPEND_ONLY: &str = "appendonly";
     pub const CONNECTOR: &str = "connector";
     pub const RETENTION_SECONDS: &str = PROPERTIES_RETENTION_SECOND_KEY;
+    pub const TIME_TRAVEL_RETENTION_SECONDS: &str = PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY;
+    pub const BLOOM_FILTER_PREFIX_COLUMNS: &str = "bloom_filter_prefix_columns";
 }
 
 /// Options or properties extracted from the `WITH` clause of DDLs.
@@ -69,6 +73,30 @@ impl WithOptions {
             .and_then(|s| s.parse().ok())
     }
 
+    /// Parse the time-travel retention seconds from the options.
+    pub fn time_travel_retention_seconds(&self) -> Option<NonZeroU32> {
+        self.inner
+            .get(options::TIME_TRAVEL_RETENTION_SECONDS)
+            .and_then(|s| s.parse().ok())
+    }
+
+    /// Parse the number of leading order key columns to build the bloom filters on from the
+    /// options, which must be a positive integer if set.
+    pub fn bloom_filter_prefix_columns(&self) -> Result<Option<NonZeroUsize>, RwError> {
+        self.inner
+            .get(options::BLOOM_FILTER_PREFIX_COLUMNS)
+            .map(|s| {
+                s.parse().map_err(|_| {
+                    RwError::from(ErrorCode::InvalidParameterValue(format!(
+                        "{} must be a positive integer, got \"{}\"",
+                        options::BLOOM_FILTER_PREFIX_COLUMNS,
+                        s
+                    )))
+                })
+            })
+            .transpose()
+    }
+
     /// Parse the append only property from the options.
     pub fn append_only(&self) -> bool {
         if let Some(val) = self.inner.get(options::APPEND_ONLY) {
@@ -105,6 +133,17 @@ impl WithOptions {
     pub fn internal_table_subset(&self) -> Self {
         self.subset([options::RETENTION_SECONDS])
     }
+
+    /// Get the subset of the options for materialized table catalogs.
+    ///
+    /// Besides the ones of internal tables, `time_travel_retention_seconds` is included, since only
+    /// materialized tables can be read by time-travel queries.
+    pub fn materialized_table_subset(&self) -> Self {
+        self.subset([
+            options::RETENTION_SECONDS,
+            options::TIME_TRAVEL_RETENTION_SECONDS,
+        ])
+    }
 }
 
 impl TryFrom<&[SqlOption]> for WithOptions {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use risingwave_common::error::BoxedError;
+use risingwave_hummock_sdk::HummockSstableId;
 use thiserror::Error;
 
 use crate::model::MetadataModelError;
@@ -49,6 +50,10 @@ pub enum BackupError {
     ),
     #[error("Checksum mismatch: expected {expected}, found: {found}.")]
     ChecksumMismatch { expected: u64, found: u64 },
+    #[error("Meta storage is not empty before being restored")]
+    NonemptyMetaStorage,
+    #[error("SSTs referenced by meta snapshot are missing: {0:?}")]
+    MissingSsts(Vec<HummockSstableId>),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -19,7 +19,7 @@ use anyhow::anyhow;
 use bytes::{Buf, BufMut};
 use itertools::Itertools;
 use risingwave_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
+use risingwave_pb::catalog::{Database, Function, Index, Schema, Sink, Source, Table, View};
 use risingwave_pb::hummock::{
     CompactionGroup, HummockVersion, HummockVersionDelta, HummockVersionStats,
 };
@@ -122,6 +122,7 @@ impl<S: MetaStore> MetaSnapshotBuilder<S> {
         let sink = Sink::list_at_snapshot::<S>(&meta_store_snapshot).await?;
         let source = Source::list_at_snapshot::<S>(&meta_store_snapshot).await?;
         let view = View::list_at_snapshot::<S>(&meta_store_snapshot).await?;
+        let function = Function::list_at_snapshot::<S>(&meta_store_snapshot).await?;
 
         self.snapshot.metadata = ClusterMetadata {
             default_cf,
@@ -135,6 +136,7 @@ impl<S: MetaStore> MetaSnapshotBuilder<S> {
             sink,
             source,
             view,
+            function,
             table_fragments,
             user_info,
         };
@@ -177,6 +179,7 @@ pub struct ClusterMetadata {
     pub sink: Vec<Sink>,
     pub source: Vec<Source>,
     pub view: Vec<View>,
+    pub function: Vec<Function>,
 
     pub table_fragments: Vec<TableFragments>,
     pub user_info: Vec<UserInfo>,
@@ -200,6 +203,7 @@ impl ClusterMetadata {
         Self::encode_prost_message_list(&self.sink.iter().collect_vec(), buf);
         Self::encode_prost_message_list(&self.source.iter().collect_vec(), buf);
         Self::encode_prost_message_list(&self.view.iter().collect_vec(), buf);
+        Self::encode_prost_message_list(&self.function.iter().collect_vec(), buf);
     }
 
     pub fn decode(mut buf: &[u8]) -> BackupResult<Self> {
@@ -221,6 +225,12 @@ impl ClusterMetadata {
         let sink: Vec<Sink> = Self::decode_prost_message_list(&mut buf)?;
         let source: Vec<Source> = Self::decode_prost_message_list(&mut buf)?;
         let view: Vec<View> = Self::decode_prost_message_list(&mut buf)?;
+        // Functions are absent in the snapshots taken before they are supported.
+        let function: Vec<Function> = if buf.has_remaining() {
+            Self::decode_prost_message_list(&mut buf)?
+        } else {
+            vec![]
+        };
 
         Ok(Self {
             default_cf,
@@ -234,6 +244,7 @@ impl ClusterMetadata {
             sink,
             source,
             view,
+            function,
             table_fragments,
             user_info,
         })
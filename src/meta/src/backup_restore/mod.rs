@@ -18,6 +18,8 @@ mod backup_manager;
 pub use backup_manager::*;
 mod error;
 mod meta_snapshot;
+mod restore;
+pub use restore::*;
 mod storage;
 pub use storage::*;
 mod utils;
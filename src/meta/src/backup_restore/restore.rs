@@ -0,0 +1,330 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use etcd_client::{Client as EtcdClient, ConnectOptions};
+use itertools::Itertools;
+use risingwave_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
+use risingwave_object_store::object::object_metrics::ObjectStoreMetrics;
+use risingwave_object_store::object::{parse_remote_object_store, ObjectStoreImpl};
+
+use crate::backup_restore::error::{BackupError, BackupResult};
+use crate::backup_restore::meta_snapshot::MetaSnapshot;
+use crate::backup_restore::{MetaSnapshotId, MetaSnapshotStorage, ObjectStoreMetaSnapshotStorage};
+use crate::hummock::compaction_group::CompactionGroup;
+use crate::model::{MetadataModel, TableFragments};
+use crate::storage::{EtcdMetaStore, MemStore, MetaStore, DEFAULT_COLUMN_FAMILY};
+use crate::Backend;
+
+/// Command-line arguments for restoring a meta store from a meta snapshot.
+#[derive(clap::Args, Debug, Clone)]
+pub struct RestoreOpts {
+    /// Id of the meta snapshot to restore from.
+    #[clap(long)]
+    pub meta_snapshot_id: MetaSnapshotId,
+
+    /// Type of the meta store to restore to. Restoring to `mem` only validates the meta snapshot,
+    /// because the in-memory store is dropped on exit.
+    #[clap(long, arg_enum, default_value_t = Backend::Etcd)]
+    pub meta_store_type: Backend,
+
+    #[clap(long, default_value_t = String::from(""))]
+    pub etcd_endpoints: String,
+
+    /// Enable authentication with etcd. By default disabled.
+    #[clap(long)]
+    pub etcd_auth: bool,
+
+    /// Username of etcd, required when --etcd-auth is enabled.
+    /// Default value is read from the 'ETCD_USERNAME' environment variable.
+    #[clap(long, env = "ETCD_USERNAME", default_value = "")]
+    pub etcd_username: String,
+
+    /// Password of etcd, required when --etcd-auth is enabled.
+    /// Default value is read from the 'ETCD_PASSWORD' environment variable.
+    #[clap(long, env = "ETCD_PASSWORD", default_value = "")]
+    pub etcd_password: String,
+
+    /// Remote storage url of meta snapshots, i.e. `storage_url` in the `[backup]` section of
+    /// `risingwave.toml`.
+    #[clap(long)]
+    pub backup_storage_url: String,
+
+    /// Remote directory of meta snapshots, i.e. `storage_directory` in the `[backup]` section of
+    /// `risingwave.toml`.
+    #[clap(long)]
+    pub backup_storage_directory: String,
+
+    /// Url of the hummock state store, e.g. `hummock+minio://...`. It's used to check that all
+    /// SSTs referenced by the meta snapshot still exist.
+    #[clap(long)]
+    pub hummock_storage_url: String,
+
+    /// Directory of SSTs in the hummock state store, i.e. `data_directory` in the `[storage]`
+    /// section of `risingwave.toml`.
+    #[clap(long, default_value = "hummock_001")]
+    pub hummock_storage_directory: String,
+}
+
+/// Restores the meta store from the meta snapshot `opts.meta_snapshot_id`.
+///
+/// The cluster must be stopped and the meta store must be empty before restoring, otherwise
+/// [`BackupError::NonemptyMetaStorage`] is returned without writing anything.
+pub async fn restore(opts: RestoreOpts) -> BackupResult<()> {
+    tracing::info!("restore with opts: {:#?}", opts);
+    let backup_object_store = Arc::new(
+        parse_remote_object_store(
+            &opts.backup_storage_url,
+            Arc::new(ObjectStoreMetrics::unused()),
+            true,
+        )
+        .await,
+    );
+    let backup_store =
+        ObjectStoreMetaSnapshotStorage::new(&opts.backup_storage_directory, backup_object_store)
+            .await?;
+    let snapshot = get_snapshot(&backup_store, opts.meta_snapshot_id).await?;
+
+    let hummock_object_store = parse_remote_object_store(
+        opts.hummock_storage_url
+            .strip_prefix("hummock+")
+            .unwrap_or(&opts.hummock_storage_url),
+        Arc::new(ObjectStoreMetrics::unused()),
+        true,
+    )
+    .await;
+    validate_ssts(
+        &hummock_object_store,
+        &opts.hummock_storage_directory,
+        &snapshot,
+    )
+    .await?;
+
+    match opts.meta_store_type {
+        Backend::Etcd => {
+            let mut options = ConnectOptions::default()
+                .with_keep_alive(Duration::from_secs(3), Duration::from_secs(5));
+            if opts.etcd_auth {
+                options = options.with_user(opts.etcd_username, opts.etcd_password);
+            }
+            let endpoints = opts
+                .etcd_endpoints
+                .split(',')
+                .map(|x| x.to_string())
+                .collect_vec();
+            let client = EtcdClient::connect(endpoints, Some(options))
+                .await
+                .map_err(|e| anyhow!("failed to connect etcd {}", e))?;
+            restore_metadata(&EtcdMetaStore::new(client), snapshot).await?;
+        }
+        Backend::Mem => {
+            restore_metadata(&MemStore::new(), snapshot).await?;
+        }
+    }
+    tracing::info!("restored meta snapshot {}", opts.meta_snapshot_id);
+    Ok(())
+}
+
+/// Gets a meta snapshot that is still valid, i.e. listed in the manifest of `backup_store`.
+async fn get_snapshot(
+    backup_store: &impl MetaSnapshotStorage,
+    id: MetaSnapshotId,
+) -> BackupResult<MetaSnapshot> {
+    if !backup_store.list().await?.iter().any(|m| m.id == id) {
+        return Err(anyhow!("meta snapshot {} not found", id).into());
+    }
+    backup_store.get(id).await
+}
+
+/// Checks that all SSTs referenced by the hummock version of `snapshot` exist in
+/// `hummock_object_store`.
+async fn validate_ssts(
+    hummock_object_store: &ObjectStoreImpl,
+    hummock_storage_directory: &str,
+    snapshot: &MetaSnapshot,
+) -> BackupResult<()> {
+    let sst_ids = snapshot.metadata.hummock_version.get_sst_ids();
+    let exists = futures::future::join_all(sst_ids.iter().map(|sst_id| {
+        // SSTs referenced by meta are always remote ones.
+        let path = format!(
+            "{}/{}{}.data",
+            hummock_storage_directory,
+            hummock_object_store.get_object_prefix(*sst_id, true),
+            sst_id
+        );
+        async move { hummock_object_store.metadata(&path).await.is_ok() }
+    }))
+    .await;
+    let missing = sst_ids
+        .into_iter()
+        .zip_eq(exists)
+        .filter_map(|(sst_id, exists)| (!exists).then_some(sst_id))
+        .collect_vec();
+    if !missing.is_empty() {
+        return Err(BackupError::MissingSsts(missing));
+    }
+    Ok(())
+}
+
+async fn ensure_empty<S: MetaStore, T: MetadataModel + Send + Sync>(
+    meta_store: &S,
+) -> BackupResult<()> {
+    if !T::list(meta_store).await?.is_empty() {
+        return Err(BackupError::NonemptyMetaStorage);
+    }
+    Ok(())
+}
+
+async fn restore_metadata_model<S: MetaStore, T: MetadataModel + Send + Sync>(
+    meta_store: &S,
+    metadata: &[T],
+) -> BackupResult<()> {
+    for m in metadata {
+        m.insert(meta_store).await?;
+    }
+    Ok(())
+}
+
+/// Writes the metadata of `snapshot` to `meta_store`. The hummock version is written as the
+/// version checkpoint, so no version delta is needed to recover it.
+async fn restore_metadata<S: MetaStore>(
+    meta_store: &S,
+    snapshot: MetaSnapshot,
+) -> BackupResult<()> {
+    // Check all metadata before writing any, so that a failed restore leaves the meta store as is.
+    if !meta_store.list_cf(DEFAULT_COLUMN_FAMILY).await?.is_empty() {
+        return Err(BackupError::NonemptyMetaStorage);
+    }
+    ensure_empty::<S, risingwave_pb::hummock::HummockVersion>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::hummock::HummockVersionDelta>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::hummock::HummockVersionStats>(meta_store).await?;
+    ensure_empty::<S, CompactionGroup>(meta_store).await?;
+    ensure_empty::<S, TableFragments>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::user::UserInfo>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::Database>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::Schema>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::Table>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::Index>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::Sink>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::Source>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::View>(meta_store).await?;
+    ensure_empty::<S, risingwave_pb::catalog::Function>(meta_store).await?;
+
+    let metadata = snapshot.metadata;
+    for (key, value) in metadata.default_cf {
+        meta_store.put_cf(DEFAULT_COLUMN_FAMILY, key, value).await?;
+    }
+    restore_metadata_model(
+        meta_store,
+        &metadata
+            .compaction_groups
+            .into_iter()
+            .map(CompactionGroup::from_protobuf)
+            .collect_vec(),
+    )
+    .await?;
+    restore_metadata_model(
+        meta_store,
+        &metadata
+            .table_fragments
+            .into_iter()
+            .map(TableFragments::from_protobuf)
+            .collect_vec(),
+    )
+    .await?;
+    restore_metadata_model(meta_store, &metadata.user_info).await?;
+    restore_metadata_model(meta_store, &metadata.database).await?;
+    restore_metadata_model(meta_store, &metadata.schema).await?;
+    restore_metadata_model(meta_store, &metadata.table).await?;
+    restore_metadata_model(meta_store, &metadata.index).await?;
+    restore_metadata_model(meta_store, &metadata.sink).await?;
+    restore_metadata_model(meta_store, &metadata.source).await?;
+    restore_metadata_model(meta_store, &metadata.view).await?;
+    restore_metadata_model(meta_store, &metadata.function).await?;
+    restore_metadata_model(meta_store, &[metadata.version_stats]).await?;
+    // Write the hummock version last, as a meta store without it is treated as uninitialized.
+    restore_metadata_model(meta_store, &[metadata.hummock_version]).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+    use risingwave_pb::catalog::Table;
+    use risingwave_pb::hummock::{HummockVersion, HummockVersionStats};
+
+    use crate::backup_restore::error::BackupError;
+    use crate::backup_restore::meta_snapshot::MetaSnapshotBuilder;
+    use crate::backup_restore::restore::restore_metadata;
+    use crate::model::MetadataModel;
+    use crate::storage::{MemStore, MetaStore, DEFAULT_COLUMN_FAMILY};
+
+    #[tokio::test]
+    async fn test_restore_metadata() {
+        let meta_store = Arc::new(MemStore::new());
+        let hummock_version = HummockVersion {
+            id: 10,
+            max_committed_epoch: 100,
+            ..Default::default()
+        };
+        hummock_version.insert(meta_store.deref()).await.unwrap();
+        let version_stats = HummockVersionStats {
+            hummock_version_id: hummock_version.id,
+            ..Default::default()
+        };
+        version_stats.insert(meta_store.deref()).await.unwrap();
+        let table = Table {
+            id: 1,
+            name: "t".to_string(),
+            ..Default::default()
+        };
+        table.insert(meta_store.deref()).await.unwrap();
+        meta_store
+            .put_cf(DEFAULT_COLUMN_FAMILY, vec![0, 1, 2], vec![100])
+            .await
+            .unwrap();
+        let mut builder = MetaSnapshotBuilder::new(meta_store);
+        builder.build(1).await.unwrap();
+        let snapshot = builder.finish().unwrap();
+
+        let restored = MemStore::new();
+        restore_metadata(&restored, snapshot.clone()).await.unwrap();
+        assert_eq!(
+            HummockVersion::list(&restored).await.unwrap(),
+            vec![hummock_version]
+        );
+        assert_eq!(
+            HummockVersionStats::list(&restored).await.unwrap(),
+            vec![version_stats]
+        );
+        assert_eq!(Table::list(&restored).await.unwrap(), vec![table]);
+        assert_eq!(
+            restored
+                .get_cf(DEFAULT_COLUMN_FAMILY, &[0, 1, 2])
+                .await
+                .unwrap(),
+            vec![100]
+        );
+
+        // Restoring to a nonempty meta store is rejected.
+        let err = restore_metadata(&restored, snapshot).await.unwrap_err();
+        assert_matches!(err, BackupError::NonemptyMetaStorage);
+    }
+}
@@ -24,6 +24,7 @@ const DEFAULT_MAX_BYTES_FOR_LEVEL_BASE: u64 = 512 * 1024 * 1024; // 512MB
 const DEFAULT_TIER_COMPACT_TRIGGER_NUMBER: u64 = 8;
 const DEFAULT_TARGET_FILE_SIZE_BASE: u64 = 32 * 1024 * 1024; // 32MB
 const DEFAULT_MAX_SUB_COMPACTION: u32 = 4;
+const DEFAULT_TOMBSTONE_RECLAIM_RATIO: u32 = 40; // 40%
 const MAX_LEVEL: u64 = 6;
 
 pub struct CompactionConfigBuilder {
@@ -59,6 +60,7 @@ impl CompactionConfigBuilder {
                     | CompactionFilterFlag::TTL)
                     .into(),
                 max_sub_compaction: DEFAULT_MAX_SUB_COMPACTION,
+                tombstone_reclaim_ratio: DEFAULT_TOMBSTONE_RECLAIM_RATIO,
             },
         }
     }
@@ -103,4 +105,5 @@ builder_field! {
     compression_algorithm: Vec<String>,
     compaction_filter_mask: u32,
     max_sub_compaction: u32,
+    tombstone_reclaim_ratio: u32,
 }
@@ -381,6 +381,8 @@ pub mod tests {
             stale_key_count: 0,
             total_key_count: 0,
             divide_version: 0,
+            delete_key_count: 0,
+            range_tombstone_count: 0,
         }
     }
 
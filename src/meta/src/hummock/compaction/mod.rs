@@ -19,6 +19,7 @@ mod min_overlap_compaction_picker;
 mod overlap_strategy;
 mod prost_type;
 mod tier_compaction_picker;
+mod tombstone_reclaim_compaction_picker;
 use risingwave_hummock_sdk::prost_key_range::KeyRangeExt;
 use risingwave_pb::hummock::compact_task::TaskStatus;
 pub use tier_compaction_picker::TierCompactionPicker;
@@ -28,6 +29,7 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
 pub use base_level_compaction_picker::LevelCompactionPicker;
+use risingwave_common::util::epoch::INVALID_EPOCH;
 use risingwave_hummock_sdk::{CompactionGroupId, HummockCompactionTaskId, HummockEpoch};
 use risingwave_pb::hummock::compaction_config::CompactionMode;
 use risingwave_pb::hummock::hummock_version::Levels;
@@ -36,11 +38,17 @@ use risingwave_pb::hummock::{CompactTask, CompactionConfig, InputLevel, KeyRange
 use crate::hummock::compaction::level_selector::{DynamicLevelSelector, LevelSelector};
 use crate::hummock::compaction::manual_compaction_picker::ManualCompactionSelector;
 use crate::hummock::compaction::overlap_strategy::{OverlapStrategy, RangeOverlapStrategy};
+use crate::hummock::compaction::tombstone_reclaim_compaction_picker::TombstoneReclaimSelector;
 use crate::hummock::level_handler::LevelHandler;
 
 pub struct CompactStatus {
     compaction_group_id: CompactionGroupId,
     pub(crate) level_handlers: Vec<LevelHandler>,
+    /// The max committed epoch when the tombstones in the bottommost level were last reclaimed, or
+    /// `INVALID_EPOCH` if they have never been. The tombstones left by the reclaim can't be
+    /// dropped before the watermark reaches the epoch, so the bottommost level is not
+    /// reclaimed again until then.
+    tombstone_reclaim_epoch: HummockEpoch,
 }
 
 impl Debug for CompactStatus {
@@ -48,6 +56,7 @@ impl Debug for CompactStatus {
         f.debug_struct("CompactStatus")
             .field("compaction_group_id", &self.compaction_group_id)
             .field("level_handlers", &self.level_handlers)
+            .field("tombstone_reclaim_epoch", &self.tombstone_reclaim_epoch)
             .finish()
     }
 }
@@ -56,6 +65,7 @@ impl PartialEq for CompactStatus {
     fn eq(&self, other: &Self) -> bool {
         self.level_handlers.eq(&other.level_handlers)
             && self.compaction_group_id == other.compaction_group_id
+            && self.tombstone_reclaim_epoch == other.tombstone_reclaim_epoch
     }
 }
 
@@ -64,6 +74,7 @@ impl Clone for CompactStatus {
         Self {
             compaction_group_id: self.compaction_group_id,
             level_handlers: self.level_handlers.clone(),
+            tombstone_reclaim_epoch: self.tombstone_reclaim_epoch,
         }
     }
 }
@@ -108,9 +119,13 @@ impl CompactStatus {
         CompactStatus {
             compaction_group_id,
             level_handlers,
+            tombstone_reclaim_epoch: INVALID_EPOCH,
         }
     }
 
+    /// Picks a compaction task, of which the delete keys below `watermark` can be dropped, while
+    /// the versions up to `max_committed_epoch` are committed.
+    #[allow(clippy::too_many_arguments)]
     pub fn get_compact_task(
         &mut self,
         levels: &Levels,
@@ -118,6 +133,8 @@ impl CompactStatus {
         compaction_group_id: CompactionGroupId,
         manual_compaction_option: Option<ManualCompactionOption>,
         compaction_config: CompactionConfig,
+        watermark: HummockEpoch,
+        max_committed_epoch: HummockEpoch,
     ) -> Option<CompactTask> {
         // When we compact the files, we must make the result of compaction meet the following
         // conditions, for any user key, the epoch of it in the file existing in the lower
@@ -131,7 +148,13 @@ impl CompactStatus {
                 compaction_config,
             )?
         } else {
-            self.pick_compaction(levels, task_id, compaction_config)?
+            self.pick_compaction(
+                levels,
+                task_id,
+                compaction_config,
+                watermark,
+                max_committed_epoch,
+            )?
         };
 
         let target_level_id = ret.input.target_level;
@@ -193,9 +216,30 @@ impl CompactStatus {
         levels: &Levels,
         task_id: HummockCompactionTaskId,
         compaction_config: CompactionConfig,
+        watermark: HummockEpoch,
+        max_committed_epoch: HummockEpoch,
     ) -> Option<CompactionTask> {
-        self.create_level_selector(compaction_config)
+        let tombstone_reclaim_ratio = compaction_config.tombstone_reclaim_ratio;
+        let overlap_strategy = create_overlap_strategy(compaction_config.compaction_mode());
+        let config = Arc::new(compaction_config);
+        if let Some(task) = self
+            .create_level_selector(config.clone(), overlap_strategy.clone())
             .pick_compaction(task_id, levels, &mut self.level_handlers)
+        {
+            return Some(task);
+        }
+
+        // Tombstones are reclaimed only when there is no size-driven compaction to do.
+        if tombstone_reclaim_ratio == 0 {
+            return None;
+        }
+        let reclaim_bottommost = watermark >= self.tombstone_reclaim_epoch;
+        let task = TombstoneReclaimSelector::new(config, overlap_strategy, reclaim_bottommost)
+            .pick_compaction(task_id, levels, &mut self.level_handlers)?;
+        if task.input.target_level == self.level_handlers.len() - 1 {
+            self.tombstone_reclaim_epoch = max_committed_epoch;
+        }
+        Some(task)
     }
 
     fn manual_pick_compaction(
@@ -244,10 +288,13 @@ impl CompactStatus {
     ///
     /// The method should be lightweight because we recreate a level selector everytime so that the
     /// latest compaction config is applied to it.
-    fn create_level_selector(&self, compaction_config: CompactionConfig) -> Box<dyn LevelSelector> {
-        let overlap_strategy = create_overlap_strategy(compaction_config.compaction_mode());
+    fn create_level_selector(
+        &self,
+        compaction_config: Arc<CompactionConfig>,
+        overlap_strategy: Arc<dyn OverlapStrategy>,
+    ) -> Box<dyn LevelSelector> {
         Box::new(DynamicLevelSelector::new(
-            Arc::new(compaction_config),
+            compaction_config,
             overlap_strategy,
         ))
     }
@@ -48,6 +48,7 @@ impl From<&CompactStatus> for risingwave_pb::hummock::CompactStatus {
         risingwave_pb::hummock::CompactStatus {
             compaction_group_id: status.compaction_group_id,
             level_handlers: status.level_handlers.iter().map_into().collect(),
+            tombstone_reclaim_epoch: status.tombstone_reclaim_epoch,
         }
     }
 }
@@ -63,6 +64,7 @@ impl From<&risingwave_pb::hummock::CompactStatus> for CompactStatus {
         CompactStatus {
             compaction_group_id: status.compaction_group_id,
             level_handlers: status.level_handlers.iter().map_into().collect(),
+            tombstone_reclaim_epoch: status.tombstone_reclaim_epoch,
         }
     }
 }
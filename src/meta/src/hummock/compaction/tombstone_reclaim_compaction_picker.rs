@@ -0,0 +1,382 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_hummock_sdk::compaction_group::hummock_version_ext::HummockLevelsExt;
+use risingwave_hummock_sdk::HummockCompactionTaskId;
+use risingwave_pb::hummock::hummock_version::Levels;
+use risingwave_pb::hummock::{CompactionConfig, InputLevel, SstableInfo};
+
+use crate::hummock::compaction::level_selector::LevelSelectorCore;
+use crate::hummock::compaction::overlap_strategy::OverlapStrategy;
+use crate::hummock::compaction::{CompactionInput, CompactionPicker, CompactionTask};
+use crate::hummock::level_handler::LevelHandler;
+
+/// Returns the percentage of point and range delete tombstones in the keys of `sst`.
+pub fn tombstone_ratio(sst: &SstableInfo) -> u64 {
+    if sst.total_key_count == 0 {
+        return 0;
+    }
+    (sst.delete_key_count + sst.range_tombstone_count) * 100 / sst.total_key_count
+}
+
+/// Picks the SST with the highest tombstone ratio from `base_level` and the levels below it, as
+/// long as the ratio reaches `tombstone_reclaim_ratio`. The SST is compacted into the next level
+/// together with the SSTs it overlaps, or within the bottommost level where the tombstones can be
+/// dropped, unless `reclaim_bottommost` is false because the tombstones left there by the last
+/// reclaim can't be dropped yet.
+pub struct TombstoneReclaimCompactionPicker {
+    base_level: usize,
+    max_compaction_bytes: u64,
+    tombstone_reclaim_ratio: u64,
+    reclaim_bottommost: bool,
+    overlap_strategy: Arc<dyn OverlapStrategy>,
+}
+
+impl TombstoneReclaimCompactionPicker {
+    pub fn new(
+        base_level: usize,
+        max_compaction_bytes: u64,
+        tombstone_reclaim_ratio: u32,
+        reclaim_bottommost: bool,
+        overlap_strategy: Arc<dyn OverlapStrategy>,
+    ) -> TombstoneReclaimCompactionPicker {
+        TombstoneReclaimCompactionPicker {
+            base_level,
+            max_compaction_bytes,
+            tombstone_reclaim_ratio: tombstone_reclaim_ratio as u64,
+            reclaim_bottommost,
+            overlap_strategy,
+        }
+    }
+
+    fn pick_table(
+        &self,
+        levels: &Levels,
+        level_idx: usize,
+        sst: &SstableInfo,
+        level_handlers: &[LevelHandler],
+    ) -> Option<CompactionInput> {
+        let target_level = if level_idx == levels.levels.len() {
+            level_idx
+        } else {
+            level_idx + 1
+        };
+        let target_input_ssts = if target_level == level_idx {
+            vec![]
+        } else {
+            self.overlap_strategy.check_base_level_overlap(
+                std::slice::from_ref(sst),
+                &levels.get_level(target_level).table_infos,
+            )
+        };
+        if target_input_ssts
+            .iter()
+            .any(|table| level_handlers[target_level].is_pending_compact(&table.id))
+        {
+            return None;
+        }
+        let select_file_size = sst.file_size
+            + target_input_ssts
+                .iter()
+                .map(|table| table.file_size)
+                .sum::<u64>();
+        if select_file_size > self.max_compaction_bytes {
+            return None;
+        }
+
+        Some(CompactionInput {
+            input_levels: vec![
+                InputLevel {
+                    level_idx: level_idx as u32,
+                    level_type: levels.get_level(level_idx).level_type,
+                    table_infos: vec![sst.clone()],
+                },
+                InputLevel {
+                    level_idx: target_level as u32,
+                    level_type: levels.get_level(target_level).level_type,
+                    table_infos: target_input_ssts,
+                },
+            ],
+            target_level,
+            target_sub_level_id: 0,
+        })
+    }
+}
+
+impl CompactionPicker for TombstoneReclaimCompactionPicker {
+    fn pick_compaction(
+        &self,
+        levels: &Levels,
+        level_handlers: &[LevelHandler],
+    ) -> Option<CompactionInput> {
+        if self.tombstone_reclaim_ratio == 0 {
+            return None;
+        }
+        let mut candidates = vec![];
+        for level in &levels.levels {
+            let level_idx = level.level_idx as usize;
+            if level_idx < self.base_level
+                || (level_idx == levels.levels.len() && !self.reclaim_bottommost)
+            {
+                continue;
+            }
+            for sst in &level.table_infos {
+                if level_handlers[level_idx].is_pending_compact(&sst.id) {
+                    continue;
+                }
+                let ratio = tombstone_ratio(sst);
+                if ratio >= self.tombstone_reclaim_ratio {
+                    candidates.push((ratio, level_idx, sst));
+                }
+            }
+        }
+        // sort reverse to try the SST with the most tombstones first.
+        candidates.sort_by(|a, b| b.0.cmp(&a.0));
+        candidates
+            .into_iter()
+            .find_map(|(_, level_idx, sst)| self.pick_table(levels, level_idx, sst, level_handlers))
+    }
+}
+
+/// Selects compaction tasks to reclaim the space taken by tombstones, which are not taken into
+/// account by the size-driven [`super::level_selector::DynamicLevelSelector`].
+pub struct TombstoneReclaimSelector {
+    inner: LevelSelectorCore,
+    reclaim_bottommost: bool,
+}
+
+impl TombstoneReclaimSelector {
+    pub fn new(
+        config: Arc<CompactionConfig>,
+        overlap_strategy: Arc<dyn OverlapStrategy>,
+        reclaim_bottommost: bool,
+    ) -> Self {
+        Self {
+            inner: LevelSelectorCore::new(config, overlap_strategy),
+            reclaim_bottommost,
+        }
+    }
+
+    fn create_compaction_picker(&self, base_level: usize) -> TombstoneReclaimCompactionPicker {
+        TombstoneReclaimCompactionPicker::new(
+            base_level,
+            self.inner.get_config().max_compaction_bytes,
+            self.inner.get_config().tombstone_reclaim_ratio,
+            self.reclaim_bottommost,
+            self.inner.get_overlap_strategy(),
+        )
+    }
+
+    pub fn pick_compaction(
+        &self,
+        task_id: HummockCompactionTaskId,
+        levels: &Levels,
+        level_handlers: &mut [LevelHandler],
+    ) -> Option<CompactionTask> {
+        let ctx = self.inner.calculate_level_base_size(levels);
+        let ret = self
+            .create_compaction_picker(ctx.base_level)
+            .pick_compaction(levels, level_handlers)?;
+        ret.add_pending_task(task_id, level_handlers);
+        Some(self.inner.create_compaction_task(ret, ctx.base_level))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::hummock::compaction::compaction_config::CompactionConfigBuilder;
+    use crate::hummock::compaction::level_selector::tests::{
+        assert_compaction_task, generate_l0_nonoverlapping_sublevels, generate_level,
+        generate_table,
+    };
+    use crate::hummock::compaction::overlap_strategy::RangeOverlapStrategy;
+    use crate::hummock::compaction::CompactStatus;
+
+    fn generate_table_with_tombstones(
+        id: u64,
+        left: usize,
+        right: usize,
+        epoch: u64,
+        delete_key_count: u64,
+        range_tombstone_count: u64,
+    ) -> SstableInfo {
+        let mut sst = generate_table(id, 1, left, right, epoch);
+        sst.total_key_count = 100;
+        sst.delete_key_count = delete_key_count;
+        sst.range_tombstone_count = range_tombstone_count;
+        sst
+    }
+
+    #[test]
+    fn test_pick_tombstone_reclaim() {
+        let picker = TombstoneReclaimCompactionPicker::new(
+            1,
+            10000,
+            40,
+            true,
+            Arc::new(RangeOverlapStrategy::default()),
+        );
+        let levels = Levels {
+            levels: vec![
+                generate_level(
+                    1,
+                    vec![
+                        generate_table_with_tombstones(0, 0, 100, 2, 10, 0),
+                        generate_table_with_tombstones(1, 101, 200, 2, 30, 20),
+                        generate_table_with_tombstones(2, 201, 300, 2, 40, 0),
+                    ],
+                ),
+                generate_level(
+                    2,
+                    vec![
+                        generate_table_with_tombstones(3, 0, 150, 1, 0, 0),
+                        generate_table_with_tombstones(4, 151, 400, 1, 0, 0),
+                    ],
+                ),
+            ],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        let mut level_handlers = (0..3).map(LevelHandler::new).collect_vec();
+
+        // The SST with the highest ratio is compacted with the overlapping SSTs in next level.
+        let ret = picker.pick_compaction(&levels, &level_handlers).unwrap();
+        assert_eq!(ret.target_level, 2);
+        assert_eq!(ret.input_levels[0].level_idx, 1);
+        assert_eq!(
+            ret.input_levels[0]
+                .table_infos
+                .iter()
+                .map(|sst| sst.id)
+                .collect_vec(),
+            vec![1]
+        );
+        assert_eq!(
+            ret.input_levels[1]
+                .table_infos
+                .iter()
+                .map(|sst| sst.id)
+                .collect_vec(),
+            vec![3, 4]
+        );
+        ret.add_pending_task(0, &mut level_handlers);
+
+        // SST 2 overlaps with the pending SST 4 in next level.
+        assert!(picker.pick_compaction(&levels, &level_handlers).is_none());
+
+        level_handlers[1].remove_task(0);
+        level_handlers[2].remove_task(0);
+        let ret = picker.pick_compaction(&levels, &level_handlers).unwrap();
+        assert_eq!(ret.input_levels[0].table_infos[0].id, 1);
+
+        // No SST reaches a higher ratio.
+        let picker = TombstoneReclaimCompactionPicker::new(
+            1,
+            10000,
+            60,
+            true,
+            Arc::new(RangeOverlapStrategy::default()),
+        );
+        assert!(picker.pick_compaction(&levels, &level_handlers).is_none());
+    }
+
+    #[test]
+    fn test_tombstone_reclaim_selector() {
+        let config = CompactionConfigBuilder::new()
+            .max_bytes_for_level_base(100)
+            .max_level(2)
+            .max_bytes_for_level_multiplier(5)
+            .tombstone_reclaim_ratio(40)
+            .build();
+        let selector = TombstoneReclaimSelector::new(
+            Arc::new(config),
+            Arc::new(RangeOverlapStrategy::default()),
+            true,
+        );
+        let levels = Levels {
+            levels: vec![
+                generate_level(1, vec![]),
+                generate_level(
+                    2,
+                    vec![
+                        generate_table_with_tombstones(0, 0, 100, 1, 10, 0),
+                        generate_table_with_tombstones(1, 101, 200, 1, 50, 0),
+                    ],
+                ),
+            ],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        let mut level_handlers = (0..3).map(LevelHandler::new).collect_vec();
+
+        // Tombstones in the bottommost level are reclaimed by an intra-level compaction.
+        let task = selector
+            .pick_compaction(1, &levels, &mut level_handlers)
+            .unwrap();
+        assert_compaction_task(&task, &level_handlers);
+        assert_eq!(task.input.target_level, 2);
+        assert_eq!(task.input.input_levels[0].level_idx, 2);
+        assert_eq!(task.input.input_levels[0].table_infos[0].id, 1);
+        assert!(task.input.input_levels[1].table_infos.is_empty());
+
+        // SST 0 doesn't reach the ratio.
+        assert!(selector
+            .pick_compaction(2, &levels, &mut level_handlers)
+            .is_none());
+    }
+
+    #[test]
+    fn test_no_reclaim_before_watermark() {
+        let config = CompactionConfigBuilder::new()
+            .max_bytes_for_level_base(1000)
+            .max_level(2)
+            .max_bytes_for_level_multiplier(5)
+            .tombstone_reclaim_ratio(40)
+            .build();
+        let mut levels = Levels {
+            levels: vec![
+                generate_level(1, vec![]),
+                generate_level(2, vec![generate_table_with_tombstones(0, 0, 100, 1, 50, 0)]),
+            ],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        let mut status = CompactStatus::new(1, 2);
+
+        // The tombstones in the bottommost level are reclaimed with the watermark at epoch 5, when
+        // the versions up to epoch 10 are committed.
+        let task = status
+            .get_compact_task(&levels, 1, 1, None, config.clone(), 5, 10)
+            .unwrap();
+        assert_eq!(task.target_level, 2);
+        assert_eq!(task.input_ssts[0].table_infos[0].id, 0);
+        status.report_compact_task(&task);
+        // The epoch of the reclaim is persisted along with the status, as it's reloaded when the
+        // meta node restarts.
+        let mut status = CompactStatus::from(&risingwave_pb::hummock::CompactStatus::from(&status));
+
+        // The output still has the tombstones after the watermark, which can't be dropped by
+        // reclaiming it again until the watermark reaches epoch 10.
+        levels.levels[1].table_infos = vec![generate_table_with_tombstones(1, 0, 100, 10, 45, 0)];
+        assert!(status
+            .get_compact_task(&levels, 2, 1, None, config.clone(), 9, 12)
+            .is_none());
+        let task = status
+            .get_compact_task(&levels, 3, 1, None, config, 10, 12)
+            .unwrap();
+        assert_eq!(task.input_ssts[0].table_infos[0].id, 1);
+    }
+}
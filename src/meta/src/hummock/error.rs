@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use risingwave_hummock_sdk::compaction_group::StateTableId;
-use risingwave_hummock_sdk::{CompactionGroupId, HummockContextId, HummockSstableId};
+use risingwave_hummock_sdk::{CompactionGroupId, HummockContextId, HummockEpoch, HummockSstableId};
 use thiserror::Error;
 
 use crate::model::MetadataModelError;
@@ -37,6 +37,8 @@ pub enum Error {
     InvalidCompactionGroupMember(StateTableId),
     #[error("SST {0} is invalid")]
     InvalidSst(HummockSstableId),
+    #[error("epoch {0} is expired, which is before the safe epoch {1}")]
+    ExpiredEpoch(HummockEpoch, HummockEpoch),
     #[error(transparent)]
     Internal(anyhow::Error),
 }
@@ -74,7 +76,11 @@ impl From<MetadataModelError> for Error {
 
 impl From<Error> for tonic::Status {
     fn from(err: Error) -> Self {
-        tonic::Status::new(tonic::Code::Internal, format!("{}", err))
+        let code = match err {
+            Error::ExpiredEpoch(..) => tonic::Code::InvalidArgument,
+            _ => tonic::Code::Internal,
+        };
+        tonic::Status::new(code, format!("{}", err))
     }
 }
 
@@ -268,6 +268,13 @@ impl<S: MetaStore> HummockManager<S> {
         compaction_group_manager.all_table_ids()
     }
 
+    /// Returns the longest time-travel retention of the tables in each compaction group, in
+    /// seconds. The groups without any table enabling time travel are not included.
+    pub async fn time_travel_retention_seconds(&self) -> HashMap<CompactionGroupId, u32> {
+        let compaction_group_manager = self.compaction_group_manager.read().await;
+        compaction_group_manager.time_travel_retention_seconds()
+    }
+
     pub async fn update_compaction_config(
         &self,
         compaction_group_ids: &[CompactionGroupId],
@@ -664,6 +671,20 @@ impl<S: MetaStore> CompactionGroupManagerInner<S> {
         self.index.keys().cloned().collect()
     }
 
+    fn time_travel_retention_seconds(&self) -> HashMap<CompactionGroupId, u32> {
+        self.compaction_groups
+            .iter()
+            .filter_map(|(group_id, group)| {
+                group
+                    .table_id_to_options()
+                    .values()
+                    .filter_map(|table_option| table_option.time_travel_retention_seconds)
+                    .max()
+                    .map(|retention_seconds| (*group_id, retention_seconds))
+            })
+            .collect()
+    }
+
     async fn update_compaction_config(
         &mut self,
         compaction_group_ids: &[CompactionGroupId],
@@ -715,6 +736,9 @@ fn update_compaction_config(target: &mut CompactionConfig, items: &[MutableConfi
             MutableConfig::MaxSubCompaction(c) => {
                 target.max_sub_compaction = *c;
             }
+            MutableConfig::TombstoneReclaimRatio(c) => {
+                target.tombstone_reclaim_ratio = *c;
+            }
         }
     }
 }
@@ -599,6 +599,7 @@ where
     ) -> Result<HummockSnapshot> {
         let snapshot = self.latest_snapshot.load();
         let mut guard = write_lock!(self, versioning).await;
+        let safe_epoch = guard.current_version.safe_epoch;
         let mut pinned_snapshots = BTreeMapTransaction::new(&mut guard.pinned_snapshots);
         let mut context_pinned_snapshot = pinned_snapshots.new_entry_txn_or_default(
             context_id,
@@ -608,7 +609,16 @@ where
             },
         );
         let epoch_to_pin = std::cmp::min(epoch, snapshot.committed_epoch);
-        if context_pinned_snapshot.minimal_pinned_snapshot == INVALID_EPOCH {
+        // The versions before the safe epoch may have been compacted, so they can't be read by a
+        // time-travel query.
+        if epoch_to_pin < safe_epoch {
+            return Err(Error::ExpiredEpoch(epoch_to_pin, safe_epoch));
+        }
+        // A historical epoch pinned by a time-travel query lowers the pinned snapshot of the
+        // context.
+        if context_pinned_snapshot.minimal_pinned_snapshot == INVALID_EPOCH
+            || epoch_to_pin < context_pinned_snapshot.minimal_pinned_snapshot
+        {
             context_pinned_snapshot.minimal_pinned_snapshot = epoch_to_pin;
             commit_multi_var!(
                 self,
@@ -750,6 +760,10 @@ where
             .await
             .ok_or(Error::InvalidCompactionGroup(compaction_group_id))?;
         let all_table_ids = self.all_table_ids().await;
+        let time_travel_watermark = self
+            .time_travel_watermarks()
+            .await
+            .remove(&compaction_group_id);
         if !compaction
             .compaction_statuses
             .contains_key(&compaction_group_id)
@@ -776,10 +790,13 @@ where
         let (current_version, watermark) = {
             let versioning_guard = read_lock!(self, versioning).await;
             let max_committed_epoch = versioning_guard.current_version.max_committed_epoch;
+            // The versions within the time-travel retention of the tables in the group are kept
+            // by the compaction, so that they can be read by time-travel queries.
             let watermark = versioning_guard
                 .pinned_snapshots
                 .values()
                 .map(|v| v.minimal_pinned_snapshot)
+                .chain(time_travel_watermark)
                 .fold(max_committed_epoch, std::cmp::min);
             (versioning_guard.current_version.clone(), watermark)
         };
@@ -794,6 +811,8 @@ where
             compaction_group_id,
             manual_compaction_option,
             group_config.compaction_config(),
+            watermark,
+            current_version.max_committed_epoch,
         );
         let mut compact_task = match compact_task {
             None => {
@@ -877,6 +896,20 @@ where
         Ok(Some(compact_task))
     }
 
+    /// Returns the epoch before which the versions of each compaction group are not kept for
+    /// time-travel queries, according to the longest time-travel retention of the tables in it.
+    /// The groups without any table enabling time travel are not included.
+    async fn time_travel_watermarks(&self) -> HashMap<CompactionGroupId, HummockEpoch> {
+        let now = Epoch::now();
+        self.time_travel_retention_seconds()
+            .await
+            .into_iter()
+            .map(|(group_id, retention_seconds)| {
+                (group_id, now.subtract_ms(retention_seconds as u64 * 1000).0)
+            })
+            .collect()
+    }
+
     /// Cancels a compaction task no matter it's assigned or unassigned.
     pub async fn cancel_compact_task(
         &self,
@@ -1057,6 +1090,7 @@ where
         let start_time = Instant::now();
         let compaction_groups: HashSet<_> =
             HashSet::from_iter(self.compaction_group_ids().await.into_iter());
+        let time_travel_watermark = self.time_travel_watermarks().await.into_values().min();
         let original_keys = compaction.compaction_statuses.keys().cloned().collect_vec();
         let mut compact_statuses = BTreeMapTransaction::new(&mut compaction.compaction_statuses);
         for group_id in original_keys {
@@ -1138,6 +1172,7 @@ where
                     compact_task,
                     CompactStatus::is_trivial_move_task(compact_task),
                     deterministic_mode,
+                    time_travel_watermark,
                 );
                 let mut version_stats = VarTransaction::new(&mut versioning.version_stats);
                 if let Some(table_stats_change) = table_stats_change {
@@ -1753,6 +1788,7 @@ where
     /// Returns the diff between new and old checkpoint id.
     #[named]
     pub async fn proceed_version_checkpoint(&self) -> Result<u64> {
+        let time_travel_watermarks = self.time_travel_watermarks().await;
         let mut versioning_guard = write_lock!(self, versioning).await;
         let min_pinned_version_id = versioning_guard.min_pinned_version_id();
         if min_pinned_version_id <= versioning_guard.checkpoint_version.id {
@@ -1766,6 +1802,12 @@ where
             .hummock_version_deltas
             .range((Excluded(old_checkpoint_id), Included(new_checkpoint_id)))
         {
+            // The deltas removing SSTs within the time-travel retention of their compaction
+            // groups are not checkpointed, so that neither they nor the SSTs removed by them are
+            // vacuumed. The deltas before them are checkpointed regardless of the retention.
+            if removes_ssts_within_time_travel_retention(version_delta, &time_travel_watermarks) {
+                break;
+            }
             checkpoint.apply_version_delta(version_delta);
         }
         new_checkpoint_id = checkpoint.id;
@@ -2179,6 +2221,30 @@ fn drop_sst(
     }
 }
 
+/// Whether `version_delta` removes any SST from a compaction group within the time-travel
+/// retention of the group, whose watermark is given by `time_travel_watermarks`.
+fn removes_ssts_within_time_travel_retention(
+    version_delta: &HummockVersionDelta,
+    time_travel_watermarks: &HashMap<CompactionGroupId, HummockEpoch>,
+) -> bool {
+    version_delta
+        .group_deltas
+        .iter()
+        .any(|(compaction_group_id, group_deltas)| {
+            time_travel_watermarks
+                .get(compaction_group_id)
+                .map_or(false, |watermark| {
+                    version_delta.max_committed_epoch >= *watermark
+                })
+                && group_deltas.group_deltas.iter().any(|group_delta| {
+                    matches!(
+                        &group_delta.delta_type,
+                        Some(DeltaType::IntraLevel(delta)) if !delta.removed_table_ids.is_empty()
+                    )
+                })
+        })
+}
+
 fn gen_version_delta<'a>(
     txn: &mut BTreeMapTransaction<'a, HummockVersionId, HummockVersionDelta>,
     branched_ssts: &mut BTreeMapTransaction<'a, HummockSstableId, HashMap<CompactionGroupId, u64>>,
@@ -2186,6 +2252,7 @@ fn gen_version_delta<'a>(
     compact_task: &CompactTask,
     trivial_move: bool,
     deterministic_mode: bool,
+    time_travel_watermark: Option<HummockEpoch>,
 ) -> HummockVersionDelta {
     let mut version_delta = HummockVersionDelta {
         prev_id: old_version.id,
@@ -2231,7 +2298,12 @@ fn gen_version_delta<'a>(
     };
     group_deltas.push(group_delta);
     version_delta.gc_sst_ids.append(&mut gc_sst_ids);
-    version_delta.safe_epoch = std::cmp::max(old_version.safe_epoch, compact_task.watermark);
+    // The safe epoch doesn't exceed the time-travel watermark of any compaction group, since the
+    // versions after it are kept in that group, even if not in the group of the task.
+    let safe_epoch = time_travel_watermark.map_or(compact_task.watermark, |watermark| {
+        std::cmp::min(watermark, compact_task.watermark)
+    });
+    version_delta.safe_epoch = std::cmp::max(old_version.safe_epoch, safe_epoch);
     version_delta.id = old_version.id + 1;
     // Don't persist version delta generated by compaction to meta store in deterministic mode.
     // Because it will overwrite existing version delta that has same ID generated in the data
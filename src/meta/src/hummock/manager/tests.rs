@@ -1268,3 +1268,67 @@ async fn test_version_stats() {
     assert_eq!(compact_table3_stats.total_value_size, 0);
     assert_eq!(compact_table3_stats.total_key_size, 0);
 }
+
+#[tokio::test]
+async fn test_pin_specific_snapshot_before_safe_epoch() {
+    let (env, hummock_manager, _, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+    let original_tables = generate_test_tables(1, get_sst_ids(&hummock_manager, 2).await);
+    register_sstable_infos_to_compaction_group(
+        &hummock_manager,
+        &original_tables,
+        StaticCompactionGroupId::StateDefault.into(),
+    )
+    .await;
+    commit_from_meta_node(
+        hummock_manager.borrow(),
+        1,
+        to_local_sstable_info(&original_tables),
+    )
+    .await
+    .unwrap();
+    commit_from_meta_node(hummock_manager.borrow(), 2, vec![])
+        .await
+        .unwrap();
+
+    // The compaction advances the safe epoch to the max committed epoch, since no snapshot is
+    // pinned and no table enables time travel.
+    let mut compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    hummock_manager
+        .compactor_manager_ref_for_test()
+        .add_compactor(context_id, u64::MAX);
+    hummock_manager
+        .assign_compaction_task(&compact_task, context_id)
+        .await
+        .unwrap();
+    compact_task.set_task_status(TaskStatus::Success);
+    assert!(hummock_manager
+        .report_compact_task(context_id, &mut compact_task, None)
+        .await
+        .unwrap());
+    assert_eq!(hummock_manager.get_current_version().await.safe_epoch, 2);
+
+    // An epoch before the safe epoch is rejected without being pinned.
+    assert!(matches!(
+        hummock_manager.pin_specific_snapshot(context_id, 1).await,
+        Err(Error::ExpiredEpoch(1, 2))
+    ));
+    assert!(HummockPinnedSnapshot::list(env.meta_store())
+        .await
+        .unwrap()
+        .is_empty());
+
+    // The safe epoch itself can still be read.
+    hummock_manager
+        .pin_specific_snapshot(context_id, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        pin_snapshots_epoch(&HummockPinnedSnapshot::list(env.meta_store()).await.unwrap()),
+        vec![2]
+    );
+}
@@ -159,6 +159,8 @@ pub fn generate_test_tables(epoch: u64, sst_ids: Vec<HummockSstableId>) -> Vec<S
             stale_key_count: 0,
             total_key_count: 0,
             divide_version: 0,
+            delete_key_count: 0,
+            range_tombstone_count: 0,
         });
     }
     sst_info
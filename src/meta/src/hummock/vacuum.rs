@@ -63,7 +63,9 @@ where
         }
     }
 
-    /// Tries to make checkpoint at the minimum pinned version.
+    /// Tries to make checkpoint at the minimum pinned version. The deltas removing SSTs within the
+    /// time-travel retention of their compaction groups are not checkpointed, so they are not
+    /// vacuumed.
     ///
     /// Returns number of deleted deltas
     pub async fn vacuum_metadata(&self) -> MetaResult<usize> {
@@ -16,9 +16,9 @@ use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use itertools::Itertools;
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
+use risingwave_pb::catalog::{Database, Function, Index, Schema, Sink, Source, Table, View};
 
-use super::{DatabaseId, RelationId, SchemaId, SinkId, SourceId, ViewId};
+use super::{DatabaseId, FunctionId, RelationId, SchemaId, SinkId, SourceId, ViewId};
 use crate::manager::{IndexId, MetaSrvEnv, TableId};
 use crate::model::MetadataModel;
 use crate::storage::MetaStore;
@@ -32,6 +32,7 @@ pub type Catalog = (
     Vec<Sink>,
     Vec<Index>,
     Vec<View>,
+    Vec<Function>,
 );
 
 type DatabaseKey = String;
@@ -55,6 +56,8 @@ pub struct DatabaseManager {
     pub(super) tables: BTreeMap<TableId, Table>,
     /// Cached view information.
     pub(super) views: BTreeMap<ViewId, View>,
+    /// Cached function information.
+    pub(super) functions: BTreeMap<FunctionId, Function>,
 
     /// Relation refer count mapping.
     // TODO(zehua): avoid key conflicts after distinguishing table's and source's id generator.
@@ -78,6 +81,7 @@ impl DatabaseManager {
         let tables = Table::list(env.meta_store()).await?;
         let indexes = Index::list(env.meta_store()).await?;
         let views = View::list(env.meta_store()).await?;
+        let functions = Function::list(env.meta_store()).await?;
 
         let mut relation_ref_count = HashMap::new();
 
@@ -102,6 +106,11 @@ impl DatabaseManager {
             }
             (view.id, view)
         }));
+        let functions = BTreeMap::from_iter(
+            functions
+                .into_iter()
+                .map(|function| (function.id, function)),
+        );
 
         Ok(Self {
             databases,
@@ -111,6 +120,7 @@ impl DatabaseManager {
             views,
             tables,
             indexes,
+            functions,
             relation_ref_count,
             in_progress_creation_tracker: HashSet::default(),
             in_progress_creation_streaming_job: HashSet::default(),
@@ -127,6 +137,7 @@ impl DatabaseManager {
             self.sinks.values().cloned().collect_vec(),
             self.indexes.values().cloned().collect_vec(),
             self.views.values().cloned().collect_vec(),
+            self.functions.values().cloned().collect_vec(),
         )
     }
 
@@ -166,6 +177,21 @@ impl DatabaseManager {
         }
     }
 
+    /// Functions are overloaded by their argument types, so only a function with the same name and
+    /// argument types is a duplicate.
+    pub fn check_function_duplicated(&self, function: &Function) -> MetaResult<()> {
+        if self.functions.values().any(|x| {
+            x.database_id == function.database_id
+                && x.schema_id == function.schema_id
+                && x.name.eq(&function.name)
+                && x.arg_types == function.arg_types
+        }) {
+            Err(MetaError::catalog_duplicated("function", &function.name))
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn list_creating_tables(&self) -> Vec<Table> {
         self.in_progress_creating_tables
             .values()
@@ -231,6 +257,7 @@ impl DatabaseManager {
             && self.sinks.values().all(|s| s.schema_id != schema_id)
             && self.indexes.values().all(|i| i.schema_id != schema_id)
             && self.views.values().all(|v| v.schema_id != schema_id)
+            && self.functions.values().all(|f| f.schema_id != schema_id)
     }
 
     pub fn increase_ref_count(&mut self, relation_id: RelationId) {
@@ -31,8 +31,9 @@ use risingwave_common::catalog::{
 };
 use risingwave_common::{bail, ensure};
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
+use risingwave_pb::catalog::{Database, Function, Index, Schema, Sink, Source, Table, View};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
+use risingwave_pb::plan_common::ColumnCatalog;
 use risingwave_pb::user::grant_privilege::{ActionWithGrantOption, Object};
 use risingwave_pb::user::update_user_request::UpdateField;
 use risingwave_pb::user::{GrantPrivilege, UserInfo};
@@ -52,6 +53,7 @@ pub type SinkId = u32;
 pub type RelationId = u32;
 pub type IndexId = u32;
 pub type ViewId = u32;
+pub type FunctionId = u32;
 
 pub type UserId = u32;
 
@@ -221,6 +223,7 @@ where
         let mut tables = BTreeMapTransaction::new(&mut database_core.tables);
         let mut indexes = BTreeMapTransaction::new(&mut database_core.indexes);
         let mut views = BTreeMapTransaction::new(&mut database_core.views);
+        let mut functions = BTreeMapTransaction::new(&mut database_core.functions);
         let mut users = BTreeMapTransaction::new(&mut user_core.user_info);
 
         /// `drop_by_database_id` provides a wrapper for dropping relations by database id, it will
@@ -250,6 +253,7 @@ where
             let tables_to_drop = drop_by_database_id!(tables, database_id);
             let indexes_to_drop = drop_by_database_id!(indexes, database_id);
             let views_to_drop = drop_by_database_id!(views, database_id);
+            let functions_to_drop = drop_by_database_id!(functions, database_id);
 
             let objects = std::iter::once(Object::DatabaseId(database_id))
                 .chain(
@@ -267,7 +271,9 @@ where
                 .collect_vec();
             let users_need_update = Self::update_user_privileges(&mut users, &objects);
 
-            commit_meta!(self, databases, schemas, sources, sinks, tables, indexes, views, users)?;
+            commit_meta!(
+                self, databases, schemas, sources, sinks, tables, indexes, views, functions, users
+            )?;
 
             std::iter::once(database.owner)
                 .chain(schemas_to_drop.iter().map(|schema| schema.owner))
@@ -281,6 +287,7 @@ where
                 )
                 .chain(indexes_to_drop.iter().map(|index| index.owner))
                 .chain(views_to_drop.iter().map(|view| view.owner))
+                .chain(functions_to_drop.iter().map(|function| function.owner))
                 .for_each(|owner_id| user_core.decrease_ref(owner_id));
 
             // Update relation ref count.
@@ -452,6 +459,49 @@ where
         }
     }
 
+    pub async fn create_function(&self, function: &Function) -> MetaResult<NotificationVersion> {
+        let core = &mut *self.core.lock().await;
+        let database_core = &mut core.database;
+        let user_core = &mut core.user;
+        database_core.ensure_database_id(function.database_id)?;
+        database_core.ensure_schema_id(function.schema_id)?;
+        database_core.check_function_duplicated(function)?;
+        #[cfg(not(test))]
+        user_core.ensure_user_id(function.owner)?;
+
+        let mut functions = BTreeMapTransaction::new(&mut database_core.functions);
+        functions.insert(function.id, function.clone());
+        commit_meta!(self, functions)?;
+
+        user_core.increase_ref(function.owner);
+
+        let version = self
+            .notify_frontend(Operation::Add, Info::Function(function.to_owned()))
+            .await;
+
+        Ok(version)
+    }
+
+    pub async fn drop_function(&self, function_id: FunctionId) -> MetaResult<NotificationVersion> {
+        let core = &mut *self.core.lock().await;
+        let database_core = &mut core.database;
+        let user_core = &mut core.user;
+        let mut functions = BTreeMapTransaction::new(&mut database_core.functions);
+
+        let function = functions
+            .remove(function_id)
+            .ok_or_else(|| MetaError::catalog_id_not_found("function", function_id))?;
+        commit_meta!(self, functions)?;
+
+        user_core.decrease_ref(function.owner);
+
+        let version = self
+            .notify_frontend(Operation::Delete, Info::Function(function))
+            .await;
+
+        Ok(version)
+    }
+
     pub async fn start_create_stream_job_procedure(
         &self,
         stream_job: &StreamingJob,
@@ -892,6 +942,58 @@ where
         }
     }
 
+    /// Appends columns to a source. The `columns` must start with the current columns of the
+    /// source, so that the relations on the source are unaffected.
+    pub async fn alter_source_columns(
+        &self,
+        source_id: SourceId,
+        columns: Vec<ColumnCatalog>,
+    ) -> MetaResult<NotificationVersion> {
+        let core = &mut *self.core.lock().await;
+        let database_core = &mut core.database;
+        let mut source = database_core
+            .sources
+            .get(&source_id)
+            .cloned()
+            .ok_or_else(|| MetaError::catalog_id_not_found("source", source_id))?;
+        if database_core.tables.values().any(|table| {
+            table.optional_associated_source_id
+                == Some(OptionalAssociatedSourceId::AssociatedSourceId(source_id))
+        }) {
+            bail!("materialized source `{}` cannot be altered", source.name);
+        }
+        let column_key = |column: &ColumnCatalog| {
+            column
+                .column_desc
+                .as_ref()
+                .map(|desc| (desc.column_id, desc.name.clone()))
+        };
+        if columns.len() < source.columns.len()
+            || !source
+                .columns
+                .iter()
+                .zip_eq(&columns[..source.columns.len()])
+                .all(|(current, new)| column_key(current) == column_key(new))
+        {
+            bail!(
+                "the columns of source `{}` can only be appended",
+                source.name
+            );
+        }
+
+        let num_columns = source.columns.len();
+        source.columns.extend(columns.into_iter().skip(num_columns));
+        let mut sources = BTreeMapTransaction::new(&mut database_core.sources);
+        sources.insert(source_id, source.clone());
+        commit_meta!(self, sources)?;
+
+        let version = self
+            .notify_frontend(Operation::Update, Info::Source(source))
+            .await;
+
+        Ok(version)
+    }
+
     pub async fn start_create_materialized_source_procedure(
         &self,
         source: &Source,
@@ -140,6 +140,7 @@ pub mod IdCategory {
     pub const Sink: IdCategoryType = 13;
     pub const Index: IdCategoryType = 14;
     pub const CompactionGroup: IdCategoryType = 15;
+    pub const Function: IdCategoryType = 16;
 }
 
 pub type IdGeneratorManagerRef<S> = Arc<IdGeneratorManager<S>>;
@@ -161,6 +162,7 @@ pub struct IdGeneratorManager<S> {
     hummock_compaction_task: Arc<StoredIdGenerator<S>>,
     parallel_unit: Arc<StoredIdGenerator<S>>,
     compaction_group: Arc<StoredIdGenerator<S>>,
+    function: Arc<StoredIdGenerator<S>>,
 }
 
 impl<S> IdGeneratorManager<S>
@@ -216,6 +218,7 @@ where
                 )
                 .await,
             ),
+            function: Arc::new(StoredIdGenerator::new(meta_store.clone(), "function", None).await),
         }
     }
 
@@ -235,6 +238,7 @@ where
             IdCategory::ParallelUnit => &self.parallel_unit,
             IdCategory::HummockCompactionTask => &self.hummock_compaction_task,
             IdCategory::CompactionGroup => &self.compaction_group,
+            IdCategory::Function => &self.function,
             _ => unreachable!(),
         }
     }
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
+use risingwave_pb::catalog::{Database, Function, Index, Schema, Sink, Source, Table, View};
 
 use crate::model::{MetadataModel, MetadataModelResult};
 
@@ -30,6 +30,8 @@ const CATALOG_TABLE_CF_NAME: &str = "cf/catalog_table";
 const CATALOG_SCHEMA_CF_NAME: &str = "cf/catalog_schema";
 /// Column family name for database catalog.
 const CATALOG_DATABASE_CF_NAME: &str = "cf/catalog_database";
+/// Column family name for function catalog.
+const CATALOG_FUNCTION_CF_NAME: &str = "cf/catalog_function";
 
 macro_rules! impl_model_for_catalog {
     ($name:ident, $cf:ident, $key_ty:ty, $key_fn:ident) => {
@@ -63,6 +65,7 @@ impl_model_for_catalog!(Index, CATALOG_INDEX_CF_NAME, u32, get_id);
 impl_model_for_catalog!(Table, CATALOG_TABLE_CF_NAME, u32, get_id);
 impl_model_for_catalog!(Schema, CATALOG_SCHEMA_CF_NAME, u32, get_id);
 impl_model_for_catalog!(Database, CATALOG_DATABASE_CF_NAME, u32, get_id);
+impl_model_for_catalog!(Function, CATALOG_FUNCTION_CF_NAME, u32, get_id);
 
 #[cfg(test)]
 mod tests {
@@ -201,6 +201,22 @@ where
         }))
     }
 
+    async fn alter_source(
+        &self,
+        request: Request<AlterSourceRequest>,
+    ) -> Result<Response<AlterSourceResponse>, Status> {
+        let req = request.into_inner();
+        let version = self
+            .catalog_manager
+            .alter_source_columns(req.source_id, req.columns)
+            .await?;
+
+        Ok(Response::new(AlterSourceResponse {
+            status: None,
+            version,
+        }))
+    }
+
     async fn create_sink(
         &self,
         request: Request<CreateSinkRequest>,
@@ -414,6 +430,37 @@ where
         }))
     }
 
+    async fn create_function(
+        &self,
+        request: Request<CreateFunctionRequest>,
+    ) -> Result<Response<CreateFunctionResponse>, Status> {
+        let req = request.into_inner();
+        let mut function = req.get_function()?.clone();
+        let id = self.gen_unique_id::<{ IdCategory::Function }>().await?;
+        function.id = id;
+
+        let version = self.catalog_manager.create_function(&function).await?;
+
+        Ok(Response::new(CreateFunctionResponse {
+            status: None,
+            function_id: id,
+            version,
+        }))
+    }
+
+    async fn drop_function(
+        &self,
+        request: Request<DropFunctionRequest>,
+    ) -> Result<Response<DropFunctionResponse>, Status> {
+        let req = request.into_inner();
+        let function_id = req.get_function_id();
+        let version = self.catalog_manager.drop_function(function_id).await?;
+        Ok(Response::new(DropFunctionResponse {
+            status: None,
+            version,
+        }))
+    }
+
     async fn risectl_list_state_tables(
         &self,
         _request: Request<RisectlListStateTablesRequest>,
@@ -771,6 +818,9 @@ fn get_dependent_relations(fragment_graph: &StreamFragmentGraph) -> MetaResult<V
             NodeBody::Chain(chain_node) => {
                 dependent_relations.insert(chain_node.get_table_id());
             }
+            NodeBody::TemporalJoin(temporal_join_node) => {
+                dependent_relations.insert(temporal_join_node.get_table_desc().unwrap().table_id);
+            }
             _ => {}
         }
         for child in &stream_node.input {
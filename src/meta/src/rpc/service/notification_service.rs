@@ -62,12 +62,14 @@ where
 
     async fn get_catalog_snapshot(&self) -> (Catalog, Vec<UserInfo>, NotificationVersion) {
         let catalog_guard = self.catalog_manager.get_catalog_core_guard().await;
-        let (databases, schemas, tables, sources, sinks, indexes, views) =
+        let (databases, schemas, tables, sources, sinks, indexes, views, functions) =
             catalog_guard.database.get_catalog();
         let users = catalog_guard.user.list_users();
         let notification_version = self.env.notification_manager().current_version().await;
         (
-            (databases, schemas, tables, sources, sinks, indexes, views),
+            (
+                databases, schemas, tables, sources, sinks, indexes, views, functions,
+            ),
             users,
             notification_version,
         )
@@ -111,8 +113,11 @@ where
     }
 
     async fn frontend_subscribe(&self) -> MetaSnapshot {
-        let ((databases, schemas, tables, sources, sinks, indexes, views), users, catalog_version) =
-            self.get_catalog_snapshot().await;
+        let (
+            (databases, schemas, tables, sources, sinks, indexes, views, functions),
+            users,
+            catalog_version,
+        ) = self.get_catalog_snapshot().await;
         let (parallel_unit_mappings, parallel_unit_mapping_version) =
             self.get_parallel_unit_mapping_snapshot().await;
         let (nodes, worker_node_version) = self.get_worker_node_snapshot().await;
@@ -127,6 +132,7 @@ where
             tables,
             indexes,
             views,
+            functions,
             users,
             parallel_unit_mappings,
             nodes,
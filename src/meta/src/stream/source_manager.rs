@@ -26,6 +26,7 @@ use risingwave_connector::source::{
 };
 use risingwave_pb::catalog::source::Info::StreamSource;
 use risingwave_pb::catalog::Source;
+use risingwave_pb::plan_common::RowFormatType;
 use risingwave_pb::source::{ConnectorSplit, ConnectorSplits};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::sync::{oneshot, Mutex};
@@ -66,6 +67,18 @@ impl ConnectorSourceWorker {
         if let ConnectorProperties::Cdc(prop) = &mut properties {
             prop.as_mut().source_id = source.id;
         }
+        if let ConnectorProperties::PostgresCdc(prop) = &mut properties {
+            prop.as_mut().source_id = source.id;
+        }
+        // Parquet files are split by row groups, which depends on the row format.
+        if let Some(StreamSource(info)) = &source.info {
+            let parquet = info.row_format() == RowFormatType::Parquet;
+            match &mut properties {
+                ConnectorProperties::PosixFs(prop) => prop.as_mut().parquet = parquet,
+                ConnectorProperties::S3(prop) => prop.as_mut().parquet = parquet,
+                _ => {}
+            }
+        }
         let enumerator = SplitEnumeratorImpl::create(properties).await?;
         let splits = Arc::new(Mutex::new(SharedSplitMap { splits: None }));
         Ok(Self {
@@ -570,6 +570,15 @@ impl StreamGraphBuilder {
                         if let Some(table) = &mut node.state_table {
                             update_table(table, "SourceInternalTable");
                         }
+                        if let Some(table) = &mut node.dead_letter_table {
+                            update_table(table, "SourceDeadLetter");
+                        }
+                    }
+
+                    NodeBody::Sink(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "SinkState");
+                        }
                     }
 
                     NodeBody::Lookup(node) => {
@@ -619,6 +628,18 @@ impl StreamGraphBuilder {
                         }
                     }
 
+                    NodeBody::OverAgg(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "OverAggNode");
+                        }
+                    }
+
+                    NodeBody::SessionWindow(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "SessionWindowNode");
+                        }
+                    }
+
                     NodeBody::GlobalSimpleAgg(node) => {
                         assert_eq!(node.agg_call_states.len(), node.agg_calls.len());
                         // In-place update the table id. Convert from local to global.
@@ -1075,9 +1096,9 @@ impl ActorGraphBuilder {
             NodeBody::Materialize(node) => {
                 vec![node.get_table_id()]
             }
-            NodeBody::Source(node) => {
-                vec![node.state_table.as_ref().unwrap().id]
-            }
+            NodeBody::Source(node) => iter::once(node.state_table.as_ref().unwrap().id)
+                .chain(node.dead_letter_table.as_ref().map(|table| table.id))
+                .collect_vec(),
             NodeBody::Arrange(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
@@ -1127,6 +1148,13 @@ impl ActorGraphBuilder {
             NodeBody::GroupTopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
+            NodeBody::OverAgg(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
+            NodeBody::SessionWindow(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
+            NodeBody::Sink(node) => node.state_table.iter().map(|table| table.id).collect_vec(),
             NodeBody::TopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
@@ -27,8 +27,9 @@ use risingwave_hummock_sdk::{
     SstIdRange,
 };
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Sink as ProstSink, Source as ProstSource, Table as ProstTable,
+    View as ProstView,
 };
 use risingwave_pb::common::WorkerType;
 use risingwave_pb::ddl_service::ddl_service_client::DdlServiceClient;
@@ -45,6 +46,7 @@ use risingwave_pb::meta::reschedule_request::Reschedule as ProstReschedule;
 use risingwave_pb::meta::scale_service_client::ScaleServiceClient;
 use risingwave_pb::meta::stream_manager_service_client::StreamManagerServiceClient;
 use risingwave_pb::meta::*;
+use risingwave_pb::plan_common::ColumnCatalog;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_pb::user::update_user_request::UpdateField;
 use risingwave_pb::user::user_service_client::UserServiceClient;
@@ -249,6 +251,14 @@ impl MetaClient {
         Ok((resp.view_id, resp.version))
     }
 
+    pub async fn create_function(&self, function: ProstFunction) -> Result<(u32, CatalogVersion)> {
+        let request = CreateFunctionRequest {
+            function: Some(function),
+        };
+        let resp = self.inner.create_function(request).await?;
+        Ok((resp.function_id, resp.version))
+    }
+
     pub async fn create_index(
         &self,
         index: ProstIndex,
@@ -285,12 +295,28 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    pub async fn drop_function(&self, function_id: u32) -> Result<CatalogVersion> {
+        let request = DropFunctionRequest { function_id };
+        let resp = self.inner.drop_function(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn drop_source(&self, source_id: u32) -> Result<CatalogVersion> {
         let request = DropSourceRequest { source_id };
         let resp = self.inner.drop_source(request).await?;
         Ok(resp.version)
     }
 
+    pub async fn alter_source(
+        &self,
+        source_id: u32,
+        columns: Vec<ColumnCatalog>,
+    ) -> Result<CatalogVersion> {
+        let request = AlterSourceRequest { source_id, columns };
+        let resp = self.inner.alter_source(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn drop_sink(&self, sink_id: u32) -> Result<CatalogVersion> {
         let request = DropSinkRequest { sink_id };
         let resp = self.inner.drop_sink(request).await?;
@@ -915,10 +941,13 @@ macro_rules! for_all_meta_rpc {
             ,{ ddl_client, drop_materialized_view, DropMaterializedViewRequest, DropMaterializedViewResponse }
             ,{ ddl_client, drop_view, DropViewRequest, DropViewResponse }
             ,{ ddl_client, drop_source, DropSourceRequest, DropSourceResponse }
+            ,{ ddl_client, alter_source, AlterSourceRequest, AlterSourceResponse }
             ,{ ddl_client, drop_sink, DropSinkRequest, DropSinkResponse }
             ,{ ddl_client, drop_database, DropDatabaseRequest, DropDatabaseResponse }
             ,{ ddl_client, drop_schema, DropSchemaRequest, DropSchemaResponse }
             ,{ ddl_client, drop_index, DropIndexRequest, DropIndexResponse }
+            ,{ ddl_client, create_function, CreateFunctionRequest, CreateFunctionResponse }
+            ,{ ddl_client, drop_function, DropFunctionRequest, DropFunctionResponse }
             ,{ ddl_client, risectl_list_state_tables, RisectlListStateTablesRequest, RisectlListStateTablesResponse }
             ,{ hummock_client, unpin_version_before, UnpinVersionBeforeRequest, UnpinVersionBeforeResponse }
             ,{ hummock_client, get_current_version, GetCurrentVersionRequest, GetCurrentVersionResponse }
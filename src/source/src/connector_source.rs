@@ -32,10 +32,9 @@ use risingwave_connector::ConnectorParams;
 use risingwave_pb::catalog::{
     ColumnIndex as ProstColumnIndex, StreamSourceInfo as ProstStreamSourceInfo,
 };
-use risingwave_pb::plan_common::{
-    ColumnCatalog as ProstColumnCatalog, RowFormatType as ProstRowFormatType,
-};
+use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 
+use crate::dead_letter::{DeadLetter, ErrorPolicy};
 use crate::monitor::SourceMetrics;
 use crate::{
     SourceColumnDesc, SourceFormat, SourceParserImpl, SourceStreamChunkBuilder,
@@ -80,6 +79,10 @@ struct InnerConnectorSourceReader {
 pub struct ConnectorSourceReader {
     parser: Arc<SourceParserImpl>,
     columns: Vec<SourceColumnDesc>,
+    error_policy: ErrorPolicy,
+
+    metrics: Arc<SourceMetrics>,
+    context: SourceContext,
 
     // merge all streams of inner reader into one
     // TODO: make this static dispatch instead of box
@@ -149,29 +152,65 @@ impl InnerConnectorSourceReader {
 impl ConnectorSourceReader {
     #[try_stream(boxed, ok = StreamChunkWithState, error = RwError)]
     pub async fn into_stream(self) {
+        let actor_id = self.context.actor_id.to_string();
+        let source_id = self.context.source_id.to_string();
         #[for_await]
         for batch in self.stream {
             let batch = batch?;
             let mut builder =
                 SourceStreamChunkBuilder::with_capacity(self.columns.clone(), batch.len());
             let mut split_offset_mapping: HashMap<SplitId, String> = HashMap::new();
+            let mut dead_letters = vec![];
 
             for msg in batch {
-                if let Some(content) = msg.payload {
-                    split_offset_mapping.insert(msg.split_id, msg.offset);
-                    if let Err(e) = self
-                        .parser
-                        .parse(content.as_ref(), builder.row_writer())
+                // A message without payload is a delete of its key for an upsert source, and is
+                // skipped otherwise.
+                let result = if self.parser.is_upsert() {
+                    self.parser
+                        .parse_upsert(
+                            msg.key.as_deref(),
+                            msg.payload.as_deref(),
+                            builder.row_writer(),
+                        )
                         .await
-                    {
-                        tracing::warn!("message parsing failed {}, skipping", e.to_string());
-                        continue;
+                } else if let Some(batch) = &msg.batch {
+                    self.parser.parse_batch(batch, builder.row_writer())
+                } else if let Some(content) = &msg.payload {
+                    self.parser
+                        .parse(&msg.split_id, content.as_ref(), builder.row_writer())
+                        .await
+                } else {
+                    continue;
+                };
+                split_offset_mapping.insert(msg.split_id.clone(), msg.offset.clone());
+                if let Err(e) = result {
+                    self.metrics
+                        .parse_error_count
+                        .with_label_values(&[
+                            &actor_id,
+                            &source_id,
+                            &msg.split_id,
+                            self.error_policy.as_str(),
+                        ])
+                        .inc();
+                    match self.error_policy {
+                        ErrorPolicy::Fail => return Err(e),
+                        ErrorPolicy::Skip => {
+                            tracing::warn!("message parsing failed {}, skipping", e.to_string())
+                        }
+                        ErrorPolicy::DeadLetter => dead_letters.push(DeadLetter {
+                            split_id: msg.split_id,
+                            offset: msg.offset,
+                            payload: msg.payload.unwrap_or_default(),
+                            error: e.to_string(),
+                        }),
                     }
                 }
             }
             yield StreamChunkWithState {
                 chunk: builder.finish(),
                 split_offset_mapping: Some(split_offset_mapping),
+                dead_letters,
             };
         }
     }
@@ -182,6 +221,7 @@ pub struct ConnectorSource {
     pub config: ConnectorProperties,
     pub columns: Vec<SourceColumnDesc>,
     pub parser: Arc<SourceParserImpl>,
+    pub error_policy: ErrorPolicy,
     pub connector_message_buffer_size: usize,
 }
 
@@ -202,8 +242,22 @@ impl ConnectorSource {
             HashMap::from_iter(properties.clone().into_iter());
         connector_node_addr
             .map(|addr| source_props.insert("connector_node_addr".to_string(), addr));
-        let config =
+        let mut config =
             ConnectorProperties::extract(source_props).map_err(|e| ConnectorError(e.into()))?;
+        // The header of a CSV file is read again when it's resumed from the middle.
+        let csv_header = matches!(
+            format,
+            SourceFormat::Csv {
+                has_header: true,
+                ..
+            }
+        );
+        match &mut config {
+            ConnectorProperties::PosixFs(prop) => prop.as_mut().csv_header = csv_header,
+            ConnectorProperties::S3(prop) => prop.as_mut().csv_header = csv_header,
+            _ => {}
+        }
+        let error_policy = ErrorPolicy::from_properties(&properties)?;
         let parser = SourceParserImpl::create(
             &format,
             &properties,
@@ -216,6 +270,7 @@ impl ConnectorSource {
             config,
             columns,
             parser,
+            error_policy,
             connector_message_buffer_size,
         })
     }
@@ -271,9 +326,16 @@ impl ConnectorSource {
 
         let stream = select_all(readers.into_iter().map(|r| r.into_stream())).boxed();
 
+        // The readers start over from the offsets of the splits, e.g. reading the header of CSV
+        // files again.
+        self.parser.reset();
+
         Ok(ConnectorSourceReader {
             parser: self.parser.clone(),
             columns,
+            error_policy: self.error_policy,
+            metrics,
+            context,
             stream,
         })
     }
@@ -326,15 +388,7 @@ impl SourceDescBuilderV2 {
     }
 
     pub async fn build(self) -> Result<SourceDescV2> {
-        let format = match self.source_info.get_row_format()? {
-            ProstRowFormatType::Json => SourceFormat::Json,
-            ProstRowFormatType::Protobuf => SourceFormat::Protobuf,
-            ProstRowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
-            ProstRowFormatType::Avro => SourceFormat::Avro,
-            ProstRowFormatType::Maxwell => SourceFormat::Maxwell,
-            ProstRowFormatType::CanalJson => SourceFormat::CanalJson,
-            ProstRowFormatType::RowUnspecified => unreachable!(),
-        };
+        let format = SourceFormat::from_stream_source_info(&self.source_info)?;
 
         if format == SourceFormat::Protobuf && self.source_info.row_schema_location.is_empty() {
             return Err(ProtocolError("protobuf file location not provided".to_string()).into());
@@ -0,0 +1,123 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use bytes::Bytes;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::row::Row;
+use risingwave_common::types::ScalarImpl;
+use risingwave_connector::source::SplitId;
+
+/// The option of a source to specify its [`ErrorPolicy`].
+pub const ERROR_POLICY_KEY: &str = "error_policy";
+
+/// The property of a dead-letter table holding the id of the source it belongs to, by which
+/// `rw_dead_letters('<source>')` finds the dead-letter tables of a source.
+pub const DEAD_LETTER_SOURCE_ID_KEY: &str = "dead_letter.source_id";
+
+/// How a source handles the messages that fail to parse.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Fails the actor, which is recovered to read the message again.
+    Fail,
+    /// Skips the message with a log.
+    #[default]
+    Skip,
+    /// Writes the message as a [`DeadLetter`] to the dead-letter table of the source executor.
+    ///
+    /// Each job reading the source has its own dead-letter table. They can be queried together by
+    /// `SELECT * FROM rw_dead_letters('<source>')`, which returns the name of the dead-letter
+    /// table as the first column, followed by the columns of the table.
+    DeadLetter,
+}
+
+impl ErrorPolicy {
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Self> {
+        properties
+            .get(ERROR_POLICY_KEY)
+            .map_or(Ok(Self::default()), |policy| policy.parse())
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+            Self::DeadLetter => "dead_letter",
+        }
+    }
+}
+
+impl FromStr for ErrorPolicy {
+    type Err = RwError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "fail" => Ok(Self::Fail),
+            "skip" => Ok(Self::Skip),
+            "dead_letter" => Ok(Self::DeadLetter),
+            _ => Err(ErrorCode::InvalidParameterValue(format!(
+                "{} must be one of fail, skip or dead_letter, got {}",
+                ERROR_POLICY_KEY, s
+            ))
+            .into()),
+        }
+    }
+}
+
+/// A message that fails to parse, together with where it is from and why it fails.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    pub split_id: SplitId,
+    pub offset: String,
+    pub payload: Bytes,
+    pub error: String,
+}
+
+impl DeadLetter {
+    /// Converts into a row of the dead-letter table, which is `(split_id, offset, payload, error)`
+    /// keyed by the split id and the offset.
+    pub fn into_row(self) -> Row {
+        Row::new(vec![
+            Some(ScalarImpl::Utf8(self.split_id.as_ref().into())),
+            Some(ScalarImpl::Utf8(self.offset.into_boxed_str())),
+            Some(ScalarImpl::Bytea(self.payload.to_vec().into())),
+            Some(ScalarImpl::Utf8(self.error.into_boxed_str())),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+
+    use super::*;
+
+    #[test]
+    fn test_error_policy_from_properties() {
+        let policy = |value: &str| {
+            ErrorPolicy::from_properties(&hashmap! {
+                ERROR_POLICY_KEY.to_string() => value.to_string(),
+            })
+        };
+        assert_eq!(
+            ErrorPolicy::from_properties(&HashMap::new()).unwrap(),
+            ErrorPolicy::Skip
+        );
+        assert_eq!(policy("fail").unwrap(), ErrorPolicy::Fail);
+        assert_eq!(policy("Dead_Letter").unwrap(), ErrorPolicy::DeadLetter);
+        assert!(policy("retry").is_err());
+    }
+}
@@ -32,9 +32,12 @@ pub use parser::*;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::error::RwError;
 use risingwave_connector::source::SplitId;
+use risingwave_pb::catalog::StreamSourceInfo;
+use risingwave_pb::plan_common::RowFormatType;
 pub use table::*;
 
 use crate::connector_source::ConnectorSource;
+use crate::dead_letter::DeadLetter;
 
 pub mod parser;
 
@@ -46,7 +49,9 @@ pub mod dml_manager;
 mod common;
 pub mod connector_source;
 pub use connector_source::test_utils as connector_test_utils;
+pub mod dead_letter;
 pub mod monitor;
+pub mod offset_committer;
 pub mod row_id;
 mod table;
 
@@ -59,6 +64,32 @@ pub enum SourceFormat {
     Avro,
     Maxwell,
     CanalJson,
+    Csv { delimiter: u8, has_header: bool },
+    Parquet,
+    UpsertJson,
+    UpsertAvro,
+}
+
+impl SourceFormat {
+    pub fn from_stream_source_info(info: &StreamSourceInfo) -> Result<Self, RwError> {
+        let format = match info.get_row_format()? {
+            RowFormatType::Json => Self::Json,
+            RowFormatType::Protobuf => Self::Protobuf,
+            RowFormatType::DebeziumJson => Self::DebeziumJson,
+            RowFormatType::Avro => Self::Avro,
+            RowFormatType::Maxwell => Self::Maxwell,
+            RowFormatType::CanalJson => Self::CanalJson,
+            RowFormatType::Csv => Self::Csv {
+                delimiter: info.csv_delimiter as u8,
+                has_header: info.csv_has_header,
+            },
+            RowFormatType::Parquet => Self::Parquet,
+            RowFormatType::UpsertJson => Self::UpsertJson,
+            RowFormatType::UpsertAvro => Self::UpsertAvro,
+            RowFormatType::RowUnspecified => unreachable!(),
+        };
+        Ok(format)
+    }
 }
 
 #[derive(Debug, EnumAsInner)]
@@ -76,6 +107,8 @@ pub type BoxSourceWithStateStream = BoxStream<'static, Result<StreamChunkWithSta
 pub struct StreamChunkWithState {
     pub chunk: StreamChunk,
     pub split_offset_mapping: Option<HashMap<SplitId, String>>,
+    /// The messages failed to parse, if the error policy of the source is dead letter.
+    pub dead_letters: Vec<DeadLetter>,
 }
 
 /// The `split_offset_mapping` field is unused for the table source, so we implement `From` for it.
@@ -84,6 +117,7 @@ impl From<StreamChunk> for StreamChunkWithState {
         Self {
             chunk,
             split_offset_mapping: None,
+            dead_letters: vec![],
         }
     }
 }
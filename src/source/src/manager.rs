@@ -27,9 +27,10 @@ use risingwave_connector::source::ConnectorProperties;
 use risingwave_connector::ConnectorParams;
 use risingwave_pb::catalog::source_info::SourceInfo as ProstSourceInfo;
 use risingwave_pb::catalog::ColumnIndex as ProstColumnIndex;
-use risingwave_pb::plan_common::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
+use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 
 use crate::connector_source::DEFAULT_CONNECTOR_MESSAGE_BUFFER_SIZE;
+use crate::dead_letter::ErrorPolicy;
 use crate::monitor::SourceMetrics;
 use crate::table::TableSource;
 use crate::{ConnectorSource, SourceFormat, SourceImpl, SourceParserImpl};
@@ -167,11 +168,11 @@ impl TableSourceManager {
         self.sources.lock().clear()
     }
 
-    fn metrics(&self) -> Arc<SourceMetrics> {
+    pub fn metrics(&self) -> Arc<SourceMetrics> {
         self.metrics.clone()
     }
 
-    fn msg_buf_size(&self) -> usize {
+    pub fn msg_buf_size(&self) -> usize {
         self.connector_message_buffer_size
     }
 }
@@ -250,15 +251,7 @@ impl SourceDescBuilder {
 
     async fn build_stream_source(&self) -> Result<SourceDescRef> {
         let info = try_match_expand!(&self.info, ProstSourceInfo::StreamSource).unwrap();
-        let format = match info.get_row_format()? {
-            RowFormatType::Json => SourceFormat::Json,
-            RowFormatType::Protobuf => SourceFormat::Protobuf,
-            RowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
-            RowFormatType::Avro => SourceFormat::Avro,
-            RowFormatType::Maxwell => SourceFormat::Maxwell,
-            RowFormatType::CanalJson => SourceFormat::CanalJson,
-            RowFormatType::RowUnspecified => unreachable!(),
-        };
+        let format = SourceFormat::from_stream_source_info(info)?;
 
         if format == SourceFormat::Protobuf && info.row_schema_location.is_empty() {
             return Err(RwError::from(ProtocolError(
@@ -302,11 +295,13 @@ impl SourceDescBuilder {
             .map(|addr| source_props.insert("connector_node_addr".to_string(), addr.clone()));
         let config = ConnectorProperties::extract(source_props)
             .map_err(|e| RwError::from(ConnectorError(e.into())))?;
+        let error_policy = ErrorPolicy::from_properties(&self.properties)?;
 
         let source = SourceImpl::Connector(ConnectorSource {
             config,
             columns: columns.clone(),
             parser,
+            error_policy,
             connector_message_buffer_size: self.source_manager.msg_buf_size(),
         });
 
@@ -12,13 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use prometheus::core::{AtomicU64, GenericCounterVec};
-use prometheus::{register_int_counter_vec_with_registry, Registry};
+use prometheus::core::{AtomicI64, AtomicU64, GenericCounterVec, GenericGaugeVec};
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, Registry,
+};
 
 #[derive(Debug)]
 pub struct SourceMetrics {
     pub registry: Registry,
     pub partition_input_count: GenericCounterVec<AtomicU64>,
+    pub parse_error_count: GenericCounterVec<AtomicU64>,
+    pub partition_lag: GenericGaugeVec<AtomicI64>,
 }
 
 impl SourceMetrics {
@@ -30,9 +34,25 @@ impl SourceMetrics {
             registry
         )
         .unwrap();
+        let parse_error_count = register_int_counter_vec_with_registry!(
+            "source_parse_error_count",
+            "Total number of messages from specific partition that failed to parse",
+            &["actor_id", "source_id", "partition", "error_policy"],
+            registry
+        )
+        .unwrap();
+        let partition_lag = register_int_gauge_vec_with_registry!(
+            "source_partition_lag",
+            "Number of messages in specific partition after the offset checkpointed by the source",
+            &["actor_id", "source_id", "partition"],
+            registry
+        )
+        .unwrap();
         SourceMetrics {
             registry,
             partition_input_count,
+            parse_error_count,
+            partition_lag,
         }
     }
 
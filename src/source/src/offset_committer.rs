@@ -0,0 +1,305 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::FutureExt;
+use itertools::Itertools;
+use risingwave_common::error::ErrorCode::ConnectorError;
+use risingwave_common::error::Result;
+use risingwave_connector::source::cdc::postgres::PostgresCdcOffsetCommitter;
+use risingwave_connector::source::kafka::KafkaOffsetCommitter;
+use risingwave_connector::source::{ConnectorProperties, SplitId, SplitImpl, SplitMetaData};
+use risingwave_storage::error::StorageResult;
+use tokio::task::JoinHandle;
+
+use crate::connector_source::{ConnectorSource, SourceContext};
+use crate::monitor::SourceMetrics;
+
+/// [`SourceOffsetCommitter`] reports the progress of a source executor to the external system
+/// after each checkpoint, and exports the lag of its splits to [`SourceMetrics`]. Kafka and
+/// Postgres CDC are supported for now.
+///
+/// The offsets are reported once the checkpoint is committed. The ones of Kafka are only for
+/// monitoring, while the LSN of Postgres allows the server to recycle the write-ahead log before
+/// it, so it must not be reported before the source can be recovered from it.
+pub struct SourceOffsetCommitter {
+    committer: Committer,
+    /// The latest offsets of the splits assigned to the executor.
+    splits: HashMap<SplitId, SplitImpl>,
+    /// The task reporting the last checkpoint. A checkpoint is skipped if the last one is still
+    /// being reported, since the offsets will be reported again by the next one anyway.
+    task: Option<JoinHandle<()>>,
+
+    metrics: Arc<SourceMetrics>,
+    context: SourceContext,
+}
+
+impl SourceOffsetCommitter {
+    /// Returns `None` if the connector of the source doesn't support reporting offsets.
+    pub async fn new(
+        source: &ConnectorSource,
+        metrics: Arc<SourceMetrics>,
+        context: SourceContext,
+    ) -> Result<Option<Self>> {
+        let committer = match &source.config {
+            ConnectorProperties::Kafka(properties) => Committer::Kafka(Arc::new(
+                KafkaOffsetCommitter::new(properties)
+                    .await
+                    .map_err(|e| ConnectorError(e.into()))?,
+            )),
+            ConnectorProperties::PostgresCdc(properties) => {
+                Committer::PostgresCdc(Arc::new(PostgresCdcOffsetCommitter::new(properties)))
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(Self {
+            committer,
+            splits: HashMap::new(),
+            task: None,
+            metrics,
+            context,
+        }))
+    }
+
+    /// Replaces the splits with the ones newly assigned to the executor.
+    pub fn assign(&mut self, splits: &[SplitImpl]) {
+        let actor_id = self.context.actor_id.to_string();
+        let source_id = self.context.source_id.to_string();
+        for split_id in self.splits.keys() {
+            if !splits.iter().any(|split| &split.id() == split_id) {
+                // The gauge is absent if the lag of the split has never been fetched.
+                let _ = self
+                    .metrics
+                    .partition_lag
+                    .remove_label_values(&[&actor_id, &source_id, split_id]);
+            }
+        }
+        self.splits.clear();
+        self.update(splits);
+    }
+
+    /// Updates the offsets of the splits that have been read.
+    pub fn update<'a>(&mut self, splits: impl IntoIterator<Item = &'a SplitImpl>) {
+        for split in splits {
+            self.splits.insert(split.id(), split.clone());
+        }
+    }
+
+    /// Reports the offsets of the splits and refreshes their lag in the background, once
+    /// `committed` completes after the checkpoint is committed.
+    pub fn checkpoint(
+        &mut self,
+        committed: impl Future<Output = StorageResult<()>> + Send + 'static,
+    ) {
+        if let Some(mut task) = self.task.take() {
+            if (&mut task).now_or_never().is_none() {
+                tracing::debug!(
+                    actor_id = self.context.actor_id,
+                    "last checkpoint is still being reported, skipping"
+                );
+                self.task = Some(task);
+                return;
+            }
+        }
+
+        let committer = self.committer.clone();
+        let splits = self.splits.values().cloned().collect_vec();
+        let metrics = self.metrics.clone();
+        let actor_id = self.context.actor_id.to_string();
+        let source_id = self.context.source_id.to_string();
+        self.task = Some(tokio::spawn(async move {
+            if let Err(e) = committed.await {
+                tracing::warn!("failed to wait for the checkpoint to commit: {}", e);
+                return;
+            }
+            match committer {
+                Committer::Kafka(committer) => {
+                    let splits = splits
+                        .into_iter()
+                        .filter_map(|split| match split {
+                            SplitImpl::Kafka(split) => Some(split),
+                            _ => None,
+                        })
+                        .collect_vec();
+                    let _ = tokio::task::spawn_blocking(move || {
+                        if let Err(e) = committer.commit(&splits) {
+                            tracing::warn!("failed to commit offsets to kafka: {}", e);
+                        }
+                        for split in &splits {
+                            match committer.fetch_lag(split) {
+                                Ok(Some(lag)) => metrics
+                                    .partition_lag
+                                    .with_label_values(&[&actor_id, &source_id, &split.id()])
+                                    .set(lag),
+                                Ok(None) => {}
+                                Err(e) => tracing::warn!(
+                                    "failed to fetch the lag of kafka partition {}: {}",
+                                    split.id(),
+                                    e
+                                ),
+                            }
+                        }
+                    })
+                    .await;
+                }
+                Committer::PostgresCdc(committer) => {
+                    let splits = splits
+                        .into_iter()
+                        .filter_map(|split| match split {
+                            SplitImpl::PostgresCdc(split) => Some(split),
+                            _ => None,
+                        })
+                        .collect_vec();
+                    if let Err(e) = committer.commit(&splits) {
+                        tracing::warn!("failed to commit lsn to postgres: {}", e);
+                    }
+                }
+            }
+        }));
+    }
+}
+
+#[derive(Clone)]
+enum Committer {
+    Kafka(Arc<KafkaOffsetCommitter>),
+    PostgresCdc(Arc<PostgresCdcOffsetCommitter>),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use prometheus::core::Collector;
+    use risingwave_common::catalog::TableId;
+    use risingwave_connector::source::cdc::postgres::{PostgresCdcProperties, PostgresCdcSplit};
+    use serde_json::json;
+    use tokio::sync::oneshot;
+
+    use super::*;
+
+    fn new_postgres_committer() -> (SourceOffsetCommitter, PostgresCdcProperties) {
+        let properties: PostgresCdcProperties = serde_json::from_value(json!({
+            "database.hostname": "localhost",
+            "database.user": "postgres",
+            "database.name": "test",
+            "table.name": "t",
+        }))
+        .unwrap();
+        let committer = SourceOffsetCommitter {
+            committer: Committer::PostgresCdc(Arc::new(PostgresCdcOffsetCommitter::new(
+                &properties,
+            ))),
+            splits: HashMap::new(),
+            task: None,
+            metrics: Arc::new(SourceMetrics::unused()),
+            context: SourceContext::new(1, TableId::new(2)),
+        };
+        (committer, properties)
+    }
+
+    fn postgres_split(source_id: u32, lsn: &str) -> SplitImpl {
+        SplitImpl::PostgresCdc(PostgresCdcSplit::new(source_id, Some(lsn.to_string())))
+    }
+
+    /// Returns a fake future of the checkpoint to commit, which completes when the sender is sent.
+    fn fake_committed() -> (
+        oneshot::Sender<()>,
+        impl Future<Output = StorageResult<()>> + Send + 'static,
+    ) {
+        let (tx, rx) = oneshot::channel();
+        (tx, async move {
+            rx.await.unwrap();
+            Ok(())
+        })
+    }
+
+    async fn wait_reported(committer: &mut SourceOffsetCommitter) {
+        committer.task.take().unwrap().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_report_lsn_after_commit() {
+        let (mut committer, properties) = new_postgres_committer();
+        committer.assign(&[postgres_split(2, "0/10")]);
+
+        let (tx, committed) = fake_committed();
+        committer.checkpoint(committed);
+        tokio::task::yield_now().await;
+        // The LSN is not reported before the checkpoint is committed.
+        assert_eq!(properties.checkpointed_lsn.load(Ordering::Relaxed), 0);
+
+        tx.send(()).unwrap();
+        wait_reported(&mut committer).await;
+        assert_eq!(properties.checkpointed_lsn.load(Ordering::Relaxed), 0x10);
+    }
+
+    #[tokio::test]
+    async fn test_skip_checkpoint_while_reporting() {
+        let (mut committer, properties) = new_postgres_committer();
+        committer.assign(&[postgres_split(2, "0/10")]);
+        let (tx1, committed1) = fake_committed();
+        committer.checkpoint(committed1);
+
+        // The last checkpoint is still being reported, so this one is skipped.
+        committer.update(&[postgres_split(2, "0/20")]);
+        let (tx2, committed2) = fake_committed();
+        committer.checkpoint(committed2);
+        tx2.send(()).unwrap_err();
+
+        tx1.send(()).unwrap();
+        wait_reported(&mut committer).await;
+        assert_eq!(properties.checkpointed_lsn.load(Ordering::Relaxed), 0x10);
+
+        // The offsets are reported again by the next checkpoint.
+        let (tx3, committed3) = fake_committed();
+        committer.checkpoint(committed3);
+        tx3.send(()).unwrap();
+        wait_reported(&mut committer).await;
+        assert_eq!(properties.checkpointed_lsn.load(Ordering::Relaxed), 0x20);
+    }
+
+    #[tokio::test]
+    async fn test_assign_removes_lag() {
+        let (mut committer, _) = new_postgres_committer();
+        committer.assign(&[postgres_split(2, "0/10"), postgres_split(3, "0/10")]);
+        for split_id in ["2", "3"] {
+            committer
+                .metrics
+                .partition_lag
+                .with_label_values(&["1", "2", split_id])
+                .set(1);
+        }
+
+        committer.assign(&[postgres_split(3, "0/10")]);
+        let splits_with_lag = committer
+            .metrics
+            .partition_lag
+            .collect()
+            .into_iter()
+            .flat_map(|family| family.get_metric().to_vec())
+            .map(|metric| {
+                metric
+                    .get_label()
+                    .iter()
+                    .find(|label| label.get_name() == "partition")
+                    .unwrap()
+                    .get_value()
+                    .to_string()
+            })
+            .collect_vec();
+        assert_eq!(splits_with_lag, vec!["3".to_string()]);
+    }
+}
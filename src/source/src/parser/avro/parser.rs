@@ -19,7 +19,7 @@ use apache_avro::types::Value;
 use apache_avro::{from_avro_datum, Reader, Schema};
 use chrono::Datelike;
 use itertools::Itertools;
-use risingwave_common::array::{ListValue, StructValue};
+use risingwave_common::array::{ListValue, Op, StructValue};
 use risingwave_common::error::ErrorCode::{InternalError, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{
@@ -33,7 +33,7 @@ use super::schema_resolver::*;
 use crate::parser::schema_registry::{extract_schema_id, Client};
 use crate::parser::util::get_kafka_topic;
 use crate::parser::ParseFuture;
-use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
+use crate::{SourceColumnDesc, SourceParser, SourceStreamChunkRowWriter, WriteGuard};
 
 fn unix_epoch_days() -> i32 {
     NaiveDateWrapper::from_ymd_uncheck(1970, 1, 1)
@@ -56,31 +56,53 @@ impl AvroParser {
         let url = Url::parse(schema_location).map_err(|e| {
             InternalError(format!("failed to parse url ({}): {}", schema_location, e))
         })?;
-        let (schema, schema_resolver) = if use_schema_registry {
-            let kafka_topic = get_kafka_topic(&props)?;
-            let client = Client::new(url)?;
-            let (schema, resolver) =
-                ConfluentSchemaResolver::new(format!("{}-value", kafka_topic).as_str(), client)
-                    .await?;
-            (schema, Some(resolver))
-        } else {
-            let schema_content = match url.scheme() {
-                "file" => read_schema_from_local(url.path()),
-                "s3" => read_schema_from_s3(&url, props).await,
-                "https" | "http" => read_schema_from_http(&url).await,
-                scheme => Err(RwError::from(ProtocolError(format!(
-                    "path scheme {} is not supported",
-                    scheme
-                )))),
-            }?;
-            let schema = Schema::parse_str(&schema_content).map_err(|e| {
-                RwError::from(InternalError(format!("Avro schema parse error {}", e)))
-            })?;
-            (schema, None)
-        };
+        if use_schema_registry {
+            return Self::from_schema_registry(url, &props, "value").await;
+        }
+        let schema_content = match url.scheme() {
+            "file" => read_schema_from_local(url.path()),
+            "s3" => read_schema_from_s3(&url, props).await,
+            "https" | "http" => read_schema_from_http(&url).await,
+            scheme => Err(RwError::from(ProtocolError(format!(
+                "path scheme {} is not supported",
+                scheme
+            )))),
+        }?;
+        let schema = Schema::parse_str(&schema_content)
+            .map_err(|e| RwError::from(InternalError(format!("Avro schema parse error {}", e))))?;
+        Ok(Self {
+            schema,
+            schema_resolver: None,
+        })
+    }
+
+    /// Creates a parser of the message keys of the Kafka topic, whose schema is the key schema of
+    /// the topic in the confluent schema registry.
+    pub(crate) async fn new_key_parser(
+        schema_location: &str,
+        props: &HashMap<String, String>,
+    ) -> Result<Self> {
+        let url = Url::parse(schema_location).map_err(|e| {
+            InternalError(format!("failed to parse url ({}): {}", schema_location, e))
+        })?;
+        Self::from_schema_registry(url, props, "key").await
+    }
+
+    /// The subject of the schema is "${topic-name}-${kind}", where the kind is either "key" or
+    /// "value".
+    async fn from_schema_registry(
+        url: Url,
+        props: &HashMap<String, String>,
+        kind: &str,
+    ) -> Result<Self> {
+        let kafka_topic = get_kafka_topic(props)?;
+        let client = Client::new(url)?;
+        let (schema, resolver) =
+            ConfluentSchemaResolver::new(format!("{}-{}", kafka_topic, kind).as_str(), client)
+                .await?;
         Ok(Self {
             schema,
-            schema_resolver,
+            schema_resolver: Some(resolver),
         })
     }
 
@@ -108,6 +130,7 @@ impl AvroParser {
         schema: &Schema,
         index: &mut i32,
     ) -> Result<ColumnDesc> {
+        let schema = unwrap_nullable(schema);
         let data_type = Self::avro_type_mapping(schema)?;
         match schema {
             Schema::Record {
@@ -141,7 +164,7 @@ impl AvroParser {
     }
 
     fn avro_type_mapping(schema: &Schema) -> Result<DataType> {
-        let data_type = match schema {
+        let data_type = match unwrap_nullable(schema) {
             Schema::String => DataType::Varchar,
             Schema::Int => DataType::Int32,
             Schema::Long => DataType::Int64,
@@ -178,13 +201,12 @@ impl AvroParser {
         Ok(data_type)
     }
 
-    async fn parse_inner(
-        &self,
-        payload: &[u8],
-        mut writer: SourceStreamChunkRowWriter<'_>,
-    ) -> Result<WriteGuard> {
+    /// Decodes the payload as a record, of which the fields are returned.
+    pub(crate) async fn decode_record(&self, payload: &[u8]) -> Result<Vec<(String, Value)>> {
         // parse payload to avro value
-        // if use confluent schema, get writer schema from confluent schema registry
+        // if use confluent schema, get writer schema from confluent schema registry, which is
+        // resolved to the reader schema, so that the messages of an older or newer schema are
+        // read as the reader schema
         let avro_value = if let Some(resolver) = &self.schema_resolver {
             let (schema_id, mut raw_payload) = extract_schema_id(payload)?;
             let writer_schema = resolver.get(schema_id).await?;
@@ -203,25 +225,67 @@ impl AvroParser {
                 }
             }
         };
-        // parse the valur to rw value
         if let Value::Record(fields) = avro_value {
-            writer.insert(|column| {
-                let tuple = fields.iter().find(|val| column.name.eq(&val.0)).unwrap();
-                from_avro_value(tuple.1.clone()).map_err(|e| {
-                    tracing::error!(
-                        "failed to process value ({}): {}",
-                        String::from_utf8_lossy(payload),
-                        e
-                    );
-                    e
-                })
-            })
+            Ok(fields)
         } else {
             Err(RwError::from(ProtocolError(
                 "avro parse unexpected value".to_string(),
             )))
         }
     }
+
+    /// Parses the payload as a row to write with `op`, which is either an insert or a delete. The
+    /// columns missing from the payload are read from the fields of `key`, if any.
+    pub(crate) async fn parse_inner(
+        &self,
+        payload: &[u8],
+        key: Option<&[(String, Value)]>,
+        mut writer: SourceStreamChunkRowWriter<'_>,
+        op: Op,
+    ) -> Result<WriteGuard> {
+        let fields = self.decode_record(payload).await?;
+        // parse the valur to rw value
+        let f = |column: &SourceColumnDesc| -> Result<Datum> {
+            // The column is not in the reader schema if it's removed from the schema since
+            // the source is created.
+            let tuple = match fields
+                .iter()
+                .chain(key.into_iter().flatten())
+                .find(|val| column.name.eq(&val.0))
+            {
+                Some(tuple) => tuple,
+                None => return Ok(None),
+            };
+            from_avro_value(tuple.1.clone()).map_err(|e| {
+                tracing::error!(
+                    "failed to process value ({}): {}",
+                    String::from_utf8_lossy(payload),
+                    e
+                );
+                e
+            })
+        };
+        match op {
+            Op::Delete => writer.delete(f),
+            _ => writer.insert(f),
+        }
+    }
+}
+
+/// Unwraps a union of null and another type, e.g. `["null", "string"]`, which is mapped to a
+/// column of the other type. Such a union is how a field is made nullable in Avro, e.g. the
+/// fields added by the evolution of a schema.
+fn unwrap_nullable(schema: &Schema) -> &Schema {
+    if let Schema::Union(union_schema) = schema {
+        let mut variants = union_schema
+            .variants()
+            .iter()
+            .filter(|variant| !matches!(variant, Schema::Null));
+        if let (Some(variant), None) = (variants.next(), variants.next()) {
+            return variant;
+        }
+    }
+    schema
 }
 
 /// Convert Avro value to datum.For now, support the following [Avro type](https://avro.apache.org/docs/current/spec.html).
@@ -233,9 +297,12 @@ impl AvroParser {
 ///  - string: String
 ///  - Date (the number of days from the unix epoch, 1970-1-1 UTC)
 ///  - Timestamp (the number of milliseconds from the unix epoch,  1970-1-1 00:00:00.000 UTC)
+///  - union of null and another type
 #[inline]
 fn from_avro_value(value: Value) -> Result<Datum> {
     let v = match value {
+        Value::Null => return Ok(None),
+        Value::Union(_, value) => return from_avro_value(*value),
         Value::Boolean(b) => ScalarImpl::Bool(b),
         Value::String(s) => ScalarImpl::Utf8(s.into_boxed_str()),
         Value::Int(i) => ScalarImpl::Int32(i),
@@ -316,7 +383,7 @@ impl SourceParser for AvroParser {
         'b: 'a,
         'c: 'a,
     {
-        self.parse_inner(payload, writer)
+        self.parse_inner(payload, None, writer, Op::Insert)
     }
 }
 
@@ -328,10 +395,11 @@ mod test {
 
     use apache_avro::types::{Record, Value};
     use apache_avro::{Codec, Days, Duration, Millis, Months, Schema, Writer};
+    use itertools::Itertools;
     use risingwave_common::array::Op;
     use risingwave_common::catalog::ColumnId;
     use risingwave_common::error;
-    use risingwave_common::row::Row2;
+    use risingwave_common::row::{Row, Row2};
     use risingwave_common::types::{
         DataType, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, ScalarImpl,
     };
@@ -484,6 +552,61 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_avro_parser_schema_evolution() {
+        let writer_schema = Schema::parse_str(
+            r#"{"type": "record", "name": "test", "fields": [{"name": "id", "type": "int"}]}"#,
+        )
+        .unwrap();
+        let reader_schema = Schema::parse_str(
+            r#"{"type": "record", "name": "test", "fields": [
+                {"name": "id", "type": "int"},
+                {"name": "name", "type": ["null", "string"], "default": null}
+            ]}"#,
+        )
+        .unwrap();
+        let avro_parser = AvroParser {
+            schema: reader_schema,
+            schema_resolver: None,
+        };
+        let columns = avro_parser.map_to_columns().unwrap();
+        assert_eq!(
+            columns
+                .iter()
+                .map(|c| (
+                    c.name.as_str(),
+                    DataType::from(c.column_type.as_ref().unwrap())
+                ))
+                .collect_vec(),
+            vec![("id", DataType::Int32), ("name", DataType::Varchar)]
+        );
+
+        // The message of the older schema is resolved to the reader schema, and the column not in
+        // the reader schema is null.
+        let mut record = Record::new(&writer_schema).unwrap();
+        record.put("id", 1);
+        let mut writer = Writer::new(&writer_schema, Vec::new());
+        writer.append(record).unwrap();
+        let input_data = writer.into_inner().unwrap();
+        let columns = vec![
+            SourceColumnDesc::simple("id", DataType::Int32, ColumnId::from(0)),
+            SourceColumnDesc::simple("name", DataType::Varchar, ColumnId::from(1)),
+            SourceColumnDesc::simple("removed", DataType::Int64, ColumnId::from(2)),
+        ];
+        let mut builder = SourceStreamChunkBuilder::with_capacity(columns, 1);
+        avro_parser
+            .parse(&input_data[..], builder.row_writer())
+            .await
+            .unwrap();
+        let chunk = builder.finish();
+        let (op, row) = chunk.rows().next().unwrap();
+        assert_eq!(op, Op::Insert);
+        assert_eq!(
+            row.into_owned_row(),
+            Row::new(vec![Some(ScalarImpl::Int32(1)), None, None])
+        );
+    }
+
     fn build_rw_columns() -> Vec<SourceColumnDesc> {
         vec![
             SourceColumnDesc {
@@ -15,7 +15,7 @@
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use num_traits::FromPrimitive;
-use risingwave_common::array::{ListValue, StructValue};
+use risingwave_common::array::{JsonbVal, ListValue, StructValue};
 use risingwave_common::types::{DataType, Datum, Decimal, ScalarImpl};
 use risingwave_expr::vector_op::cast::{
     i64_to_timestamp, i64_to_timestampz, str_to_date, str_to_time, str_to_timestamp,
@@ -61,10 +61,16 @@ fn do_parse_json_value(dtype: &DataType, v: &Value) -> Result<ScalarImpl> {
         DataType::Int64 => ensure_int!(v, i64).into(),
         DataType::Float32 => ScalarImpl::Float32((ensure_float!(v, f32) as f32).into()),
         DataType::Float64 => ScalarImpl::Float64((ensure_float!(v, f64)).into()),
-        // FIXME: decimal should have more precision than f64
-        DataType::Decimal => Decimal::from_f64(ensure_float!(v, Decimal))
-            .ok_or_else(|| anyhow!("expect decimal"))?
-            .into(),
+        // A decimal in a string keeps its precision, which is lost in a number parsed as f64.
+        DataType::Decimal => match v {
+            Value::String(s) => s
+                .parse::<Decimal>()
+                .map_err(|e| anyhow!("expect decimal: {}", e))?
+                .into(),
+            _ => Decimal::from_f64(ensure_float!(v, Decimal))
+                .ok_or_else(|| anyhow!("expect decimal"))?
+                .into(),
+        },
         DataType::Bytea => ensure_str!(v, "bytea").to_string().into(),
         DataType::Varchar => ensure_str!(v, "varchar").to_string().into(),
         DataType::Date => str_to_date(ensure_str!(v, "date"))?.into(),
@@ -105,6 +111,7 @@ fn do_parse_json_value(dtype: &DataType, v: &Value) -> Result<ScalarImpl> {
                 return Err(anyhow!(err_msg));
             }
         }
+        DataType::Jsonb => JsonbVal::from_serde(v.clone()).into(),
         DataType::Interval => unimplemented!(),
     };
     Ok(v)
@@ -150,10 +157,16 @@ fn do_parse_simd_json_value(dtype: &DataType, v: &BorrowedValue<'_>) -> Result<S
         DataType::Int64 => ensure_int!(v, i64).into(),
         DataType::Float32 => ScalarImpl::Float32((simd_json_ensure_float!(v, f32) as f32).into()),
         DataType::Float64 => ScalarImpl::Float64((simd_json_ensure_float!(v, f64)).into()),
-        // FIXME: decimal should have more precision than f64
-        DataType::Decimal => Decimal::from_f64(simd_json_ensure_float!(v, Decimal))
-            .ok_or_else(|| anyhow!("expect decimal"))?
-            .into(),
+        // A decimal in a string keeps its precision, which is lost in a number parsed as f64.
+        DataType::Decimal => match v {
+            BorrowedValue::String(s) => s
+                .parse::<Decimal>()
+                .map_err(|e| anyhow!("expect decimal: {}", e))?
+                .into(),
+            _ => Decimal::from_f64(simd_json_ensure_float!(v, Decimal))
+                .ok_or_else(|| anyhow!("expect decimal"))?
+                .into(),
+        },
         DataType::Varchar => ensure_str!(v, "varchar").to_string().into(),
         DataType::Bytea => ensure_str!(v, "bytea").to_string().into(),
         DataType::Date => str_to_date(ensure_str!(v, "date"))?.into(),
@@ -194,6 +207,7 @@ fn do_parse_simd_json_value(dtype: &DataType, v: &BorrowedValue<'_>) -> Result<S
                 return Err(anyhow!(err_msg));
             }
         }
+        DataType::Jsonb => v.to_string().parse::<JsonbVal>()?.into(),
         DataType::Interval => unimplemented!(),
     };
     Ok(v)
@@ -0,0 +1,375 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use parking_lot::RwLock;
+use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum, Decimal, IntervalUnit, ScalarImpl};
+use risingwave_connector::source::SplitId;
+use risingwave_expr::vector_op::cast::{
+    str_parse, str_to_bool, str_to_bytea, str_to_date, str_to_jsonb, str_to_list, str_to_time,
+    str_to_timestamp, str_to_timestampz,
+};
+
+use crate::{SourceStreamChunkRowWriter, WriteGuard};
+
+/// Parser for CSV format, where each payload is a line of the CSV file. Quoted fields with line
+/// breaks are rejected, as they span multiple lines.
+///
+/// The fields are mapped to the columns by position if there is no header. Otherwise they are
+/// mapped by the names in the header of the split, which is the first line read from it since the
+/// split is assigned: the first line of its file, or the header read again by the reader if the
+/// split is resumed from the middle of its file. The headers are forgotten by [`Self::reset`] once
+/// the splits are assigned again.
+#[derive(Debug)]
+pub struct CsvParser {
+    delimiter: u8,
+    has_header: bool,
+    /// The header of each split, as the files of a source may order their columns differently.
+    headers: RwLock<HashMap<SplitId, Vec<String>>>,
+}
+
+impl CsvParser {
+    pub fn new(delimiter: u8, has_header: bool) -> Self {
+        Self {
+            delimiter,
+            has_header,
+            headers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Forgets the headers of the splits, which are read again by the readers of the splits
+    /// assigned.
+    pub fn reset(&self) {
+        self.headers.write().clear();
+    }
+
+    /// Whether the line ends in a quoted field, which is only terminated on the following lines.
+    fn has_unterminated_quote(&self, payload: &[u8]) -> bool {
+        let mut in_quotes = false;
+        let mut field_start = true;
+        let mut bytes = payload.iter().peekable();
+        while let Some(&b) = bytes.next() {
+            if in_quotes {
+                if b == b'"' {
+                    // A quote in a quoted field is escaped by doubling it.
+                    if bytes.next_if_eq(&&b'"').is_none() {
+                        in_quotes = false;
+                    }
+                }
+            } else {
+                in_quotes = b == b'"' && field_start;
+                field_start = b == self.delimiter;
+            }
+        }
+        in_quotes
+    }
+
+    /// Splits a line into its fields, which may be quoted and escaped.
+    fn read_record(&self, payload: &[u8]) -> Result<Vec<String>> {
+        if self.has_unterminated_quote(payload) {
+            return Err(RwError::from(ProtocolError(
+                "quoted fields with line breaks are not supported in CSV".to_string(),
+            )));
+        }
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(false)
+            .from_reader(payload);
+        let record = reader
+            .records()
+            .next()
+            .transpose()
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?
+            .unwrap_or_default();
+        Ok(record.iter().map(str::to_string).collect())
+    }
+
+    pub fn parse(
+        &self,
+        split_id: &SplitId,
+        payload: &[u8],
+        mut writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        let fields = self.read_record(payload)?;
+
+        if !self.has_header {
+            let mut fields = fields.iter();
+            return writer.insert(|desc| {
+                csv_parse_value(&desc.data_type, fields.next().map(String::as_str)).map_err(|e| {
+                    tracing::error!(
+                        "failed to process value ({}): {}",
+                        String::from_utf8_lossy(payload),
+                        e
+                    );
+                    e.into()
+                })
+            });
+        }
+
+        {
+            let mut headers = self.headers.write();
+            if !headers.contains_key(split_id) {
+                headers.insert(split_id.clone(), fields);
+                return Ok(WriteGuard(()));
+            }
+        }
+        let headers = self.headers.read();
+        let headers = &headers[split_id];
+        writer.insert(|desc| {
+            let field = headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(&desc.name))
+                .and_then(|idx| fields.get(idx));
+            csv_parse_value(&desc.data_type, field.map(String::as_str)).map_err(|e| {
+                tracing::error!(
+                    "failed to process value ({}): {}",
+                    String::from_utf8_lossy(payload),
+                    e
+                );
+                e.into()
+            })
+        })
+    }
+}
+
+/// Parses a field of CSV into the type of its column. A missing or empty field is `NULL`.
+fn csv_parse_value(dtype: &DataType, value: Option<&str>) -> anyhow::Result<Datum> {
+    let v = match value {
+        None | Some("") => return Ok(None),
+        Some(v) => v,
+    };
+    let scalar = match dtype {
+        DataType::Boolean => str_to_bool(v)?.into(),
+        DataType::Int16 => str_parse::<i16>(v)?.into(),
+        DataType::Int32 => str_parse::<i32>(v)?.into(),
+        DataType::Int64 => str_parse::<i64>(v)?.into(),
+        DataType::Float32 => str_parse::<f32>(v)?.into(),
+        DataType::Float64 => str_parse::<f64>(v)?.into(),
+        DataType::Decimal => str_parse::<Decimal>(v)?.into(),
+        DataType::Varchar => v.to_string().into(),
+        DataType::Bytea => ScalarImpl::Bytea(str_to_bytea(v)?),
+        DataType::Date => str_to_date(v)?.into(),
+        DataType::Time => str_to_time(v)?.into(),
+        DataType::Timestamp => str_to_timestamp(v)?.into(),
+        DataType::Timestampz => str_to_timestampz(v)?.into(),
+        DataType::Interval => str_parse::<IntervalUnit>(v)?.into(),
+        DataType::Jsonb => str_to_jsonb(v)?.into(),
+        DataType::List { datatype } => str_to_list(v, datatype)?.into(),
+        DataType::Struct(_) => return Err(anyhow!("struct is not supported in CSV")),
+    };
+    Ok(Some(scalar))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::Op;
+    use risingwave_common::row::Row2;
+    use risingwave_common::types::ToOwnedDatum;
+
+    use super::*;
+    use crate::{SourceColumnDesc, SourceStreamChunkBuilder};
+
+    fn descs() -> Vec<SourceColumnDesc> {
+        vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            SourceColumnDesc::simple("score", DataType::Float64, 2.into()),
+        ]
+    }
+
+    fn parse(parser: &CsvParser, payloads: &[(&str, &[u8])]) -> Vec<Vec<Datum>> {
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs(), payloads.len());
+        for (split_id, payload) in payloads {
+            // The rows failed to parse are skipped, as the source does.
+            let _ = parser.parse(&(*split_id).into(), payload, builder.row_writer());
+        }
+        let chunk = builder.finish();
+        chunk
+            .rows()
+            .map(|(op, row)| {
+                assert_eq!(op, Op::Insert);
+                row.iter().map(|d| d.to_owned_datum()).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_csv_without_header() {
+        let parser = CsvParser::new(b'|', false);
+        let rows = parse(
+            &parser,
+            &[
+                ("a", b"1|alice|1.5"),
+                ("a", b"2|\"bob | \"\"the builder\"\"\"|"),
+                ("a", b"x|carol|2.5"),
+                ("a", b"3"),
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Utf8("alice".into())),
+                    Some(ScalarImpl::Float64(1.5.into())),
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Utf8("bob | \"the builder\"".into())),
+                    None,
+                ],
+                vec![Some(ScalarImpl::Int32(3)), None, None],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_with_header() {
+        let parser = CsvParser::new(b',', true);
+        let rows = parse(
+            &parser,
+            &[
+                ("a", b"score,ID,extra"),
+                ("a", b"1.5,1,a"),
+                ("a", b"2.5,2,b"),
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    None,
+                    Some(ScalarImpl::Float64(1.5.into())),
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(2)),
+                    None,
+                    Some(ScalarImpl::Float64(2.5.into())),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_header_per_split() {
+        let parser = CsvParser::new(b',', true);
+        let rows = parse(
+            &parser,
+            &[
+                ("a", b"id,name"),
+                ("b", b"name,score,id"),
+                ("a", b"1,alice"),
+                ("b", b"bob,2.5,2"),
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Utf8("alice".into())),
+                    None,
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Utf8("bob".into())),
+                    Some(ScalarImpl::Float64(2.5.into())),
+                ],
+            ]
+        );
+
+        // Once the splits are assigned again, e.g. after recovery, the reader reads the header of a
+        // split resumed from the middle of its file again before the remaining lines.
+        parser.reset();
+        let rows = parse(&parser, &[("b", b"name,score,id"), ("b", b"carol,3.5,3")]);
+        assert_eq!(
+            rows,
+            vec![vec![
+                Some(ScalarImpl::Int32(3)),
+                Some(ScalarImpl::Utf8("carol".into())),
+                Some(ScalarImpl::Float64(3.5.into())),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_csv_only_first_line_is_header() {
+        let parser = CsvParser::new(b',', true);
+        let rows = parse(
+            &parser,
+            &[
+                ("a", b"name,id"),
+                ("a", b"alice,1"),
+                // A row equal to the header is not skipped, and fails to parse.
+                ("a", b"name,id"),
+                ("a", b"id,2"),
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Utf8("alice".into())),
+                    None,
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Utf8("id".into())),
+                    None,
+                ],
+            ]
+        );
+
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs(), 1);
+        assert!(parser
+            .parse(&"a".into(), b"name,id", builder.row_writer())
+            .is_err());
+    }
+
+    #[test]
+    fn test_csv_quoted_line_break() {
+        let parser = CsvParser::new(b',', false);
+        let rows = parse(
+            &parser,
+            &[
+                ("a", b"1,\"alice \"\"the\"\", \"\"b\"\"\",1.5"),
+                // A field quoted across two lines is rejected.
+                ("a", b"2,\"bob"),
+                ("a", b"the builder\",2.5"),
+                ("a", b"3,c\"arol,3.5"),
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Utf8("alice \"the\", \"b\"".into())),
+                    Some(ScalarImpl::Float64(1.5.into())),
+                ],
+                vec![
+                    Some(ScalarImpl::Int32(3)),
+                    Some(ScalarImpl::Utf8("c\"arol".into())),
+                    Some(ScalarImpl::Float64(3.5.into())),
+                ],
+            ]
+        );
+    }
+}
@@ -13,10 +13,12 @@
 // limitations under the License.
 
 use futures::future::ready;
+use risingwave_common::array::Op;
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::Datum;
 
-use crate::{ParseFuture, SourceParser, SourceStreamChunkRowWriter, WriteGuard};
+use crate::{ParseFuture, SourceColumnDesc, SourceParser, SourceStreamChunkRowWriter, WriteGuard};
 
 /// Parser for JSON format
 #[derive(Debug)]
@@ -29,10 +31,14 @@ pub struct JsonParser;
     target_feature = "simd128"
 )))]
 impl JsonParser {
-    fn parse_inner(
+    /// Parses the payload as a row to write with `op`, which is either an insert or a delete. The
+    /// columns missing from the payload are read from `key`, if any.
+    pub(crate) fn parse_inner(
         &self,
         payload: &[u8],
+        key: Option<&[u8]>,
         mut writer: SourceStreamChunkRowWriter<'_>,
+        op: Op,
     ) -> Result<WriteGuard> {
         use serde_json::Value;
 
@@ -40,9 +46,16 @@ impl JsonParser {
 
         let value: Value = serde_json::from_slice(payload)
             .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+        let key: Option<Value> = key
+            .map(serde_json::from_slice)
+            .transpose()
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
 
-        writer.insert(|desc| {
-            json_parse_value(&desc.data_type, value.get(&desc.name)).map_err(|e| {
+        let f = |desc: &SourceColumnDesc| -> Result<Datum> {
+            let value = value
+                .get(&desc.name)
+                .or_else(|| key.as_ref().and_then(|key| key.get(&desc.name)));
+            json_parse_value(&desc.data_type, value).map_err(|e| {
                 tracing::error!(
                     "failed to process value ({}): {}",
                     String::from_utf8_lossy(payload),
@@ -50,7 +63,11 @@ impl JsonParser {
                 );
                 e.into()
             })
-        })
+        };
+        match op {
+            Op::Delete => writer.delete(f),
+            _ => writer.insert(f),
+        }
     }
 }
 
@@ -72,7 +89,7 @@ impl SourceParser for JsonParser {
         'b: 'a,
         'c: 'a,
     {
-        ready(self.parse_inner(payload, writer))
+        ready(self.parse_inner(payload, None, writer, Op::Insert))
     }
 }
 
@@ -83,22 +100,35 @@ impl SourceParser for JsonParser {
     target_feature = "simd128"
 ))]
 impl JsonParser {
-    fn parse_inner(
+    /// Parses the payload as a row to write with `op`, which is either an insert or a delete. The
+    /// columns missing from the payload are read from `key`, if any.
+    pub(crate) fn parse_inner(
         &self,
         payload: &[u8],
+        key: Option<&[u8]>,
         mut writer: SourceStreamChunkRowWriter<'_>,
+        op: Op,
     ) -> Result<WriteGuard> {
         use simd_json::{BorrowedValue, ValueAccess};
 
         use crate::parser::common::simd_json_parse_value;
 
         let mut payload_mut = payload.to_vec();
+        let mut key_mut = key.map(<[u8]>::to_vec);
 
         let value: BorrowedValue<'_> = simd_json::to_borrowed_value(&mut payload_mut)
             .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+        let key: Option<BorrowedValue<'_>> = key_mut
+            .as_mut()
+            .map(|key| simd_json::to_borrowed_value(key))
+            .transpose()
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
 
-        writer.insert(|desc| {
-            simd_json_parse_value(&desc.data_type, value.get(desc.name.as_str())).map_err(|e| {
+        let f = |desc: &SourceColumnDesc| -> Result<Datum> {
+            let value = value
+                .get(desc.name.as_str())
+                .or_else(|| key.as_ref().and_then(|key| key.get(desc.name.as_str())));
+            simd_json_parse_value(&desc.data_type, value).map_err(|e| {
                 tracing::error!(
                     "failed to process value ({}): {}",
                     String::from_utf8_lossy(payload),
@@ -106,7 +136,11 @@ impl JsonParser {
                 );
                 e.into()
             })
-        })
+        };
+        match op {
+            Op::Delete => writer.delete(f),
+            _ => writer.insert(f),
+        }
     }
 }
 
@@ -128,7 +162,7 @@ impl SourceParser for JsonParser {
         'b: 'a,
         'c: 'a,
     {
-        ready(self.parse_inner(payload, writer))
+        ready(self.parse_inner(payload, None, writer, Op::Insert))
     }
 }
 
@@ -162,11 +196,12 @@ mod tests {
             SourceColumnDesc::simple("decimal", DataType::Decimal, 10.into()),
         ];
 
-        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 2);
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 3);
 
         for payload in [
             br#"{"i32":1,"bool":true,"i16":1,"i64":12345678,"f32":1.23,"f64":1.2345,"varchar":"varchar","date":"2021-01-01","timestamp":"2021-01-01 16:06:12.269","decimal":12345.67890}"#.as_slice(),
             br#"{"i32":1,"f32":12345e+10,"f64":12345,"decimal":12345}"#.as_slice(),
+            br#"{"i32":1,"decimal":"12345678901234567890.123456789"}"#.as_slice(),
         ] {
             let writer = builder.row_writer();
             parser.parse(payload, writer).await.unwrap();
@@ -243,6 +278,17 @@ mod tests {
                 (Some(ScalarImpl::Decimal(12345.into())))
             );
         }
+
+        {
+            // A decimal in a string keeps all of its digits.
+            let (_, row) = rows.next().unwrap();
+            assert_eq!(
+                row.value_at(9).to_owned_datum(),
+                (Some(ScalarImpl::Decimal(
+                    Decimal::from_str("12345678901234567890.123456789").unwrap()
+                )))
+            );
+        }
     }
 
     #[tokio::test]
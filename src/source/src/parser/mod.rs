@@ -16,17 +16,22 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use arrow_array::RecordBatch;
 pub use avro::*;
 pub use canal::*;
+pub use csv_parser::*;
 pub use debezium::*;
 use futures::Future;
 use itertools::Itertools;
 pub use json_parser::*;
+pub use parquet_parser::*;
 pub use protobuf::*;
-use risingwave_common::array::{ArrayBuilderImpl, Op, StreamChunk};
+use risingwave_common::array::{ArrayBuilderImpl, ArrayImpl, Op, StreamChunk};
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::Datum;
+use risingwave_connector::source::SplitId;
+pub use upsert::*;
 
 use crate::parser::maxwell::MaxwellParser;
 use crate::{SourceColumnDesc, SourceFormat};
@@ -34,12 +39,15 @@ use crate::{SourceColumnDesc, SourceFormat};
 mod avro;
 mod canal;
 mod common;
+mod csv_parser;
 mod debezium;
 mod json_parser;
 mod macros;
 mod maxwell;
+mod parquet_parser;
 mod protobuf;
 mod schema_registry;
+mod upsert;
 mod util;
 
 /// A builder for building a [`StreamChunk`] from [`SourceColumnDesc`].
@@ -256,6 +264,55 @@ impl SourceStreamChunkRowWriter<'_> {
     ) -> Result<WriteGuard> {
         self.do_action::<OpActionUpdate>(f)
     }
+
+    /// Write `len` `Insert` records to the [`StreamChunk`] column by column, for the formats that
+    /// are decoded in columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `len`: The number of records to write.
+    /// * `f`: A failable closure that produced the column of `len` values by corresponding
+    ///   [`SourceColumnDesc`], or `None` if all of them are `NULL`. Nothing is written if it fails
+    ///   for any column.
+    pub fn insert_columns(
+        &mut self,
+        len: usize,
+        mut f: impl FnMut(&SourceColumnDesc) -> Result<Option<ArrayImpl>>,
+    ) -> Result<WriteGuard> {
+        let columns = self
+            .descs
+            .iter()
+            .map(|desc| {
+                if desc.skip_parse {
+                    return Ok(None);
+                }
+                let column = f(desc)?;
+                match &column {
+                    Some(column) if column.len() != len => {
+                        Err(RwError::from(ProtocolError(format!(
+                            "expect {} values for column {}, got {}",
+                            len,
+                            desc.name,
+                            column.len()
+                        ))))
+                    }
+                    _ => Ok(column),
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+            .inspect_err(|e| tracing::warn!("failed to parse source data: {}", e))?;
+
+        for (builder, column) in self.builders.iter_mut().zip_eq(columns) {
+            match column {
+                Some(column) => builder.append_array(&column),
+                None => (0..len).for_each(|_| builder.append_null()),
+            }
+        }
+        self.op_builder
+            .extend(std::iter::repeat(Op::Insert).take(len));
+
+        Ok(WriteGuard(()))
+    }
 }
 
 pub trait ParseFuture<'a, Out> = Future<Output = Out> + Send + 'a;
@@ -295,11 +352,17 @@ pub enum SourceParserImpl {
     Avro(AvroParser),
     Maxwell(MaxwellParser),
     CanalJson(CanalJsonParser),
+    Csv(CsvParser),
+    Parquet(ParquetParser),
+    UpsertJson(UpsertJsonParser),
+    UpsertAvro(UpsertAvroParser),
 }
 
 impl SourceParserImpl {
+    /// Parses the payload of a message read from the split.
     pub async fn parse(
         &self,
+        split_id: &SplitId,
         payload: &[u8],
         writer: SourceStreamChunkRowWriter<'_>,
     ) -> Result<WriteGuard> {
@@ -310,6 +373,56 @@ impl SourceParserImpl {
             Self::Avro(avro_parser) => avro_parser.parse(payload, writer).await,
             Self::Maxwell(maxwell_parser) => maxwell_parser.parse(payload, writer).await,
             Self::CanalJson(parser) => parser.parse(payload, writer).await,
+            Self::Csv(parser) => parser.parse(split_id, payload, writer),
+            Self::Parquet(_) => Err(RwError::from(ProtocolError(
+                "parquet messages are record batches without payload".to_string(),
+            ))),
+            Self::UpsertJson(parser) => parser.parse(None, Some(payload), writer),
+            Self::UpsertAvro(parser) => parser.parse(None, Some(payload), writer).await,
+        }
+    }
+
+    /// Parses a batch of rows read from a Parquet file.
+    pub fn parse_batch(
+        &self,
+        batch: &RecordBatch,
+        writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        match self {
+            Self::Parquet(parser) => parser.parse(batch, writer),
+            _ => Err(RwError::from(ProtocolError(
+                "record batches are only read from parquet files".to_string(),
+            ))),
+        }
+    }
+
+    /// Forgets the states of the splits kept by the parser, before the splits are assigned to new
+    /// readers.
+    pub fn reset(&self) {
+        if let Self::Csv(parser) = self {
+            parser.reset();
+        }
+    }
+
+    /// Whether the messages are upserts keyed by the message key, where a message without payload
+    /// is a delete of its key.
+    pub fn is_upsert(&self) -> bool {
+        matches!(self, Self::UpsertJson(_) | Self::UpsertAvro(_))
+    }
+
+    /// Parses a message of an upsert source, which is a delete of the key if there's no payload.
+    pub async fn parse_upsert(
+        &self,
+        key: Option<&[u8]>,
+        payload: Option<&[u8]>,
+        writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        match self {
+            Self::UpsertJson(parser) => parser.parse(key, payload, writer),
+            Self::UpsertAvro(parser) => parser.parse(key, payload, writer).await,
+            _ => Err(RwError::from(ProtocolError(
+                "not an upsert source".to_string(),
+            ))),
         }
     }
 
@@ -339,6 +452,15 @@ impl SourceParserImpl {
             ),
             SourceFormat::Maxwell => SourceParserImpl::Maxwell(MaxwellParser),
             SourceFormat::CanalJson => SourceParserImpl::CanalJson(CanalJsonParser),
+            SourceFormat::Csv {
+                delimiter,
+                has_header,
+            } => SourceParserImpl::Csv(CsvParser::new(*delimiter, *has_header)),
+            SourceFormat::Parquet => SourceParserImpl::Parquet(ParquetParser),
+            SourceFormat::UpsertJson => SourceParserImpl::UpsertJson(UpsertJsonParser),
+            SourceFormat::UpsertAvro => SourceParserImpl::UpsertAvro(
+                UpsertAvroParser::new(schema_location, properties.clone()).await?,
+            ),
             _ => {
                 return Err(RwError::from(ProtocolError(
                     "format not support".to_string(),
@@ -0,0 +1,371 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use arrow_array::{
+    Array, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, Int8Array, LargeBinaryArray, LargeListArray,
+    LargeStringArray, ListArray, RecordBatch, StringArray, StructArray, Time32MillisecondArray,
+    Time64MicrosecondArray, Time64NanosecondArray, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt16Array,
+    UInt32Array, UInt8Array,
+};
+use arrow_schema::{DataType as ArrowType, TimeUnit};
+use risingwave_common::array::{ArrayImpl, ListValue, StructValue};
+use risingwave_common::error::Result;
+use risingwave_common::types::{
+    DataType, Datum, Decimal, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper, ScalarImpl,
+    ToOwnedDatum, UNIX_EPOCH_DAYS,
+};
+
+use crate::{SourceStreamChunkRowWriter, WriteGuard};
+
+/// Parser for Parquet files. Unlike other formats, a message carries a batch of rows read from a
+/// row group instead of a payload, all of which are written with the columns mapped by name. The
+/// columns are converted from the Arrow arrays one by one, and the batch fails as a whole if any of
+/// them fails to convert.
+#[derive(Debug)]
+pub struct ParquetParser;
+
+impl ParquetParser {
+    pub fn parse(
+        &self,
+        batch: &RecordBatch,
+        mut writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        let schema = batch.schema();
+        writer.insert_columns(batch.num_rows(), |desc| {
+            match schema
+                .fields()
+                .iter()
+                .position(|field| field.name().eq_ignore_ascii_case(&desc.name))
+            {
+                Some(idx) => arrow_array_to_array(batch.column(idx).as_ref(), &desc.data_type)
+                    .map(Some)
+                    .map_err(Into::into),
+                None => Ok(None),
+            }
+        })?;
+        Ok(WriteGuard(()))
+    }
+}
+
+macro_rules! downcast {
+    ($array:expr, $ty:ty) => {
+        $array.as_any().downcast_ref::<$ty>().unwrap()
+    };
+}
+
+/// Converts an Arrow array into an array of the type of its column.
+fn arrow_array_to_array(array: &dyn Array, dtype: &DataType) -> anyhow::Result<ArrayImpl> {
+    let mut builder = dtype.create_array_builder(array.len());
+
+    // Appends the values into the builder, each of which is converted by `$scalar` if not null.
+    macro_rules! convert {
+        ($values:expr, | $v:ident | $scalar:expr) => {
+            for value in $values {
+                let datum: Datum = match value {
+                    Some($v) => Some($scalar.into()),
+                    None => None,
+                };
+                builder.append_datum(&datum);
+            }
+        };
+    }
+
+    match (dtype, array.data_type()) {
+        (DataType::Boolean, ArrowType::Boolean) => {
+            convert!(downcast!(array, BooleanArray).iter(), |v| v)
+        }
+        (DataType::Int16, ArrowType::Int16) => convert!(downcast!(array, Int16Array).iter(), |v| v),
+        (DataType::Int16, ArrowType::Int8) => {
+            convert!(downcast!(array, Int8Array).iter(), |v| i16::from(v))
+        }
+        (DataType::Int16, ArrowType::UInt8) => {
+            convert!(downcast!(array, UInt8Array).iter(), |v| i16::from(v))
+        }
+        (DataType::Int32, ArrowType::Int32) => convert!(downcast!(array, Int32Array).iter(), |v| v),
+        (DataType::Int32, ArrowType::UInt16) => {
+            convert!(downcast!(array, UInt16Array).iter(), |v| i32::from(v))
+        }
+        (DataType::Int64, ArrowType::Int64) => convert!(downcast!(array, Int64Array).iter(), |v| v),
+        (DataType::Int64, ArrowType::UInt32) => {
+            convert!(downcast!(array, UInt32Array).iter(), |v| i64::from(v))
+        }
+        (DataType::Float32, ArrowType::Float32) => {
+            convert!(downcast!(array, Float32Array).iter(), |v| v)
+        }
+        (DataType::Float64, ArrowType::Float64) => {
+            convert!(downcast!(array, Float64Array).iter(), |v| v)
+        }
+        (DataType::Float64, ArrowType::Float32) => {
+            convert!(downcast!(array, Float32Array).iter(), |v| f64::from(v))
+        }
+        (DataType::Decimal, ArrowType::Decimal128(_, scale)) => {
+            let scale = u32::try_from(*scale).map_err(|_| anyhow!("negative decimal scale"))?;
+            convert!(downcast!(array, Decimal128Array).iter(), |v| {
+                Decimal::from_i128_with_scale(v, scale)
+            })
+        }
+        (DataType::Varchar, ArrowType::Utf8) => {
+            convert!(downcast!(array, StringArray).iter(), |v| v)
+        }
+        (DataType::Varchar, ArrowType::LargeUtf8) => {
+            convert!(downcast!(array, LargeStringArray).iter(), |v| v)
+        }
+        (DataType::Bytea, ArrowType::Binary) => {
+            convert!(downcast!(array, BinaryArray).iter(), |v| ScalarImpl::Bytea(
+                v.into()
+            ))
+        }
+        (DataType::Bytea, ArrowType::LargeBinary) => {
+            convert!(downcast!(array, LargeBinaryArray).iter(), |v| {
+                ScalarImpl::Bytea(v.into())
+            })
+        }
+        (DataType::Date, ArrowType::Date32) => {
+            convert!(downcast!(array, Date32Array).iter(), |v| {
+                NaiveDateWrapper::from_protobuf(v + UNIX_EPOCH_DAYS)?
+            })
+        }
+        (DataType::Time, ArrowType::Time32(TimeUnit::Millisecond)) => {
+            convert!(downcast!(array, Time32MillisecondArray).iter(), |v| {
+                NaiveTimeWrapper::from_protobuf(v as u64 * 1_000_000)?
+            })
+        }
+        (DataType::Time, ArrowType::Time64(TimeUnit::Microsecond)) => {
+            convert!(downcast!(array, Time64MicrosecondArray).iter(), |v| {
+                NaiveTimeWrapper::from_protobuf(v as u64 * 1_000)?
+            })
+        }
+        (DataType::Time, ArrowType::Time64(TimeUnit::Nanosecond)) => {
+            convert!(downcast!(array, Time64NanosecondArray).iter(), |v| {
+                NaiveTimeWrapper::from_protobuf(v as u64)?
+            })
+        }
+        (DataType::Timestamp, ArrowType::Timestamp(unit, None)) => {
+            convert!(timestamp_micros(array, unit), |v| {
+                NaiveDateTimeWrapper::from_protobuf(v)?
+            })
+        }
+        // The timestamps with time zone are stored as the microseconds since the epoch in UTC.
+        (DataType::Timestampz, ArrowType::Timestamp(unit, Some(_))) => {
+            convert!(timestamp_micros(array, unit), |v| v)
+        }
+        (DataType::Struct(struct_type), ArrowType::Struct(_)) => {
+            let array = downcast!(array, StructArray);
+            let fields = struct_type
+                .field_names
+                .iter()
+                .zip(&struct_type.fields)
+                .map(|(name, dtype)| {
+                    array
+                        .column_by_name(name)
+                        .map(|column| arrow_array_to_array(column.as_ref(), dtype))
+                        .transpose()
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            convert!(
+                (0..array.len()).map(|idx| (!array.is_null(idx)).then_some(idx)),
+                |idx| {
+                    ScalarImpl::Struct(StructValue::new(
+                        fields
+                            .iter()
+                            .map(|field| field.as_ref().and_then(|field| field.datum_at(idx)))
+                            .collect(),
+                    ))
+                }
+            )
+        }
+        (DataType::List { datatype }, ArrowType::List(_)) => {
+            convert!(downcast!(array, ListArray).iter(), |v| {
+                arrow_list_to_scalar(v.as_ref(), datatype)?
+            })
+        }
+        (DataType::List { datatype }, ArrowType::LargeList(_)) => {
+            convert!(downcast!(array, LargeListArray).iter(), |v| {
+                arrow_list_to_scalar(v.as_ref(), datatype)?
+            })
+        }
+        (dtype, arrow_type) => {
+            return Err(anyhow!(
+                "failed to parse type '{}' from parquet type '{}'",
+                dtype,
+                arrow_type
+            ))
+        }
+    }
+    Ok(builder.finish())
+}
+
+fn arrow_list_to_scalar(values: &dyn Array, dtype: &DataType) -> anyhow::Result<ScalarImpl> {
+    let values = arrow_array_to_array(values, dtype)?;
+    Ok(ScalarImpl::List(ListValue::new(
+        values.iter().map(|v| v.to_owned_datum()).collect(),
+    )))
+}
+
+/// Returns the timestamps of an Arrow array as the microseconds since the epoch.
+fn timestamp_micros(array: &dyn Array, unit: &TimeUnit) -> Vec<Option<i64>> {
+    match unit {
+        TimeUnit::Second => downcast!(array, TimestampSecondArray)
+            .iter()
+            .map(|v| v.map(|v| v * 1_000_000))
+            .collect(),
+        TimeUnit::Millisecond => downcast!(array, TimestampMillisecondArray)
+            .iter()
+            .map(|v| v.map(|v| v * 1_000))
+            .collect(),
+        TimeUnit::Microsecond => downcast!(array, TimestampMicrosecondArray).iter().collect(),
+        TimeUnit::Nanosecond => downcast!(array, TimestampNanosecondArray)
+            .iter()
+            .map(|v| v.map(|v| v / 1_000))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::builder::{Int32Builder, ListBuilder};
+    use arrow_array::ArrayRef;
+    use arrow_schema::{Field, Schema};
+    use risingwave_common::array::Op;
+    use risingwave_common::row::Row2;
+    use risingwave_common::types::StructType;
+
+    use super::*;
+    use crate::{SourceColumnDesc, SourceStreamChunkBuilder};
+
+    #[test]
+    fn test_parquet_parser() {
+        let point = StructArray::from(vec![
+            (
+                Field::new("x", ArrowType::Int32, true),
+                Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+            ),
+            (
+                Field::new("y", ArrowType::Int32, true),
+                Arc::new(Int32Array::from(vec![4, 5, 6])) as ArrayRef,
+            ),
+        ]);
+        let mut tags = ListBuilder::new(Int32Builder::new());
+        tags.values().append_value(1);
+        tags.values().append_value(2);
+        tags.append(true);
+        tags.append(false);
+        tags.append(true);
+        let tags = tags.finish();
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "ID",
+                Arc::new(Int64Array::from(vec![Some(1), Some(2), None])) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["alice", "bob", "carol"])) as ArrayRef,
+            ),
+            ("point", Arc::new(point) as ArrayRef),
+            ("tags", Arc::new(tags) as ArrayRef),
+            // Fails to parse as `Int32` below.
+            (
+                "score",
+                Arc::new(Float64Array::from(vec![1.5, 2.5, 3.5])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let point_type = DataType::Struct(Arc::new(StructType {
+            fields: vec![DataType::Int32, DataType::Int32],
+            field_names: vec!["x".to_string(), "y".to_string()],
+        }));
+        let tags_type = DataType::List {
+            datatype: Box::new(DataType::Int32),
+        };
+        let descs = vec![
+            SourceColumnDesc::simple("id", DataType::Int64, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            // The fields of composite types are not used by the parser.
+            SourceColumnDesc {
+                name: "point".to_string(),
+                data_type: point_type,
+                column_id: 2.into(),
+                fields: vec![],
+                skip_parse: false,
+            },
+            SourceColumnDesc {
+                name: "tags".to_string(),
+                data_type: tags_type,
+                column_id: 3.into(),
+                fields: vec![],
+                skip_parse: false,
+            },
+            SourceColumnDesc::simple("missing", DataType::Int32, 4.into()),
+        ];
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs.clone(), 3);
+        ParquetParser.parse(&batch, builder.row_writer()).unwrap();
+        let chunk = builder.finish();
+        let rows: Vec<Vec<Datum>> = chunk
+            .rows()
+            .map(|(op, row)| {
+                assert_eq!(op, Op::Insert);
+                row.iter().map(|d| d.to_owned_datum()).collect()
+            })
+            .collect();
+
+        let point = |x, y| {
+            Some(ScalarImpl::Struct(StructValue::new(vec![
+                Some(ScalarImpl::Int32(x)),
+                Some(ScalarImpl::Int32(y)),
+            ])))
+        };
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int64(1)),
+                    Some(ScalarImpl::Utf8("alice".into())),
+                    point(1, 4),
+                    Some(ScalarImpl::List(ListValue::new(vec![
+                        Some(ScalarImpl::Int32(1)),
+                        Some(ScalarImpl::Int32(2)),
+                    ]))),
+                    None,
+                ],
+                vec![
+                    Some(ScalarImpl::Int64(2)),
+                    Some(ScalarImpl::Utf8("bob".into())),
+                    point(2, 5),
+                    None,
+                    None,
+                ],
+                vec![
+                    None,
+                    Some(ScalarImpl::Utf8("carol".into())),
+                    point(3, 6),
+                    Some(ScalarImpl::List(ListValue::new(vec![]))),
+                    None,
+                ],
+            ]
+        );
+
+        // The batch fails as a whole if any column fails to convert.
+        let mut descs = descs;
+        descs.push(SourceColumnDesc::simple("score", DataType::Int32, 5.into()));
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 3);
+        assert!(ParquetParser.parse(&batch, builder.row_writer()).is_err());
+        assert_eq!(builder.finish().cardinality(), 0);
+    }
+}
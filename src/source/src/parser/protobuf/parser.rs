@@ -166,15 +166,15 @@ impl ProtobufParser {
 
         let message = DynamicMessage::decode(self.message_descriptor.clone(), payload)
             .map_err(|e| ProtocolError(format!("parse message failed: {}", e)))?;
+        // The fields are decoded by their numbers, so that the messages of an older or newer
+        // schema can be decoded by the schema of the source.
         writer.insert(|column_desc| {
-            let field_desc = message
-                .descriptor()
-                .get_field_by_name(&column_desc.name)
-                .ok_or_else(|| {
-                    let err_msg = format!("protobuf schema don't have field {}", column_desc.name);
-                    tracing::error!(err_msg);
-                    RwError::from(ProtocolError(err_msg))
-                })?;
+            // The column is not in the schema if it's removed from the schema since the source is
+            // created.
+            let field_desc = match message.descriptor().get_field_by_name(&column_desc.name) {
+                Some(field_desc) => field_desc,
+                None => return Ok(None),
+            };
             let value = message.get_field(&field_desc);
             from_protobuf_value(&field_desc, &value).map_err(|e| {
                 tracing::error!(
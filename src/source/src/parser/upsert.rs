@@ -0,0 +1,229 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use risingwave_common::array::Op;
+use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::{Result, RwError};
+
+use crate::{AvroParser, JsonParser, SourceStreamChunkRowWriter, WriteGuard};
+
+fn empty_message_error() -> RwError {
+    RwError::from(ProtocolError(
+        "the message of an upsert source has neither key nor payload".to_string(),
+    ))
+}
+
+/// Parser for UPSERT_JSON format, of which a message is keyed by the primary key, which is a JSON
+/// object of the primary key columns. A message with payload is inserted, which is turned into an
+/// update of the row of the same key when materialized, with the columns missing from the payload
+/// read from the key. A message without payload, i.e. a tombstone, deletes the row of its key.
+#[derive(Debug)]
+pub struct UpsertJsonParser;
+
+impl UpsertJsonParser {
+    pub fn parse(
+        &self,
+        key: Option<&[u8]>,
+        payload: Option<&[u8]>,
+        writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        match (key, payload) {
+            (key, Some(payload)) => JsonParser.parse_inner(payload, key, writer, Op::Insert),
+            (Some(key), None) => JsonParser.parse_inner(key, None, writer, Op::Delete),
+            (None, None) => Err(empty_message_error()),
+        }
+    }
+}
+
+/// Parser for UPSERT_AVRO format, which is the same as [`UpsertJsonParser`] except that the key
+/// and the payload are encoded with the key schema and the value schema of the topic in the
+/// confluent schema registry. The key schema must be a record of the primary key columns.
+#[derive(Debug)]
+pub struct UpsertAvroParser {
+    key_parser: AvroParser,
+    value_parser: AvroParser,
+}
+
+impl UpsertAvroParser {
+    pub async fn new(schema_location: &str, props: HashMap<String, String>) -> Result<Self> {
+        let key_parser = AvroParser::new_key_parser(schema_location, &props).await?;
+        let value_parser = AvroParser::new(schema_location, true, props).await?;
+        Ok(Self {
+            key_parser,
+            value_parser,
+        })
+    }
+
+    pub async fn parse(
+        &self,
+        key: Option<&[u8]>,
+        payload: Option<&[u8]>,
+        writer: SourceStreamChunkRowWriter<'_>,
+    ) -> Result<WriteGuard> {
+        match (key, payload) {
+            (key, Some(payload)) => {
+                let key = match key {
+                    Some(key) => Some(self.key_parser.decode_record(key).await?),
+                    None => None,
+                };
+                self.value_parser
+                    .parse_inner(payload, key.as_deref(), writer, Op::Insert)
+                    .await
+            }
+            (Some(key), None) => {
+                self.key_parser
+                    .parse_inner(key, None, writer, Op::Delete)
+                    .await
+            }
+            (None, None) => Err(empty_message_error()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apache_avro::types::{Record, Value};
+    use apache_avro::{Schema, Writer};
+    use risingwave_common::array::{Op, StreamChunk};
+    use risingwave_common::row::Row2;
+    use risingwave_common::types::{DataType, Datum, ScalarImpl, ToOwnedDatum};
+
+    use super::*;
+    use crate::{SourceColumnDesc, SourceStreamChunkBuilder};
+
+    fn column_descs() -> Vec<SourceColumnDesc> {
+        vec![
+            SourceColumnDesc::simple("k", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("v", DataType::Varchar, 1.into()),
+        ]
+    }
+
+    fn rows(chunk: StreamChunk) -> Vec<(Op, Datum, Datum)> {
+        chunk
+            .rows()
+            .map(|(op, row)| {
+                (
+                    op,
+                    row.value_at(0).to_owned_datum(),
+                    row.value_at(1).to_owned_datum(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_upsert_json_parser() {
+        let mut builder = SourceStreamChunkBuilder::with_capacity(column_descs(), 3);
+
+        for (key, payload) in [
+            (
+                Some(br#"{"k":1}"#.as_slice()),
+                Some(br#"{"k":1,"v":"a"}"#.as_slice()),
+            ),
+            // The key column is missing from the payload.
+            (
+                Some(br#"{"k":2}"#.as_slice()),
+                Some(br#"{"v":"b"}"#.as_slice()),
+            ),
+            (Some(br#"{"k":1}"#.as_slice()), None),
+        ] {
+            UpsertJsonParser
+                .parse(key, payload, builder.row_writer())
+                .unwrap();
+        }
+        assert!(UpsertJsonParser
+            .parse(None, None, builder.row_writer())
+            .is_err());
+
+        assert_eq!(
+            rows(builder.finish()),
+            vec![
+                (
+                    Op::Insert,
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Utf8("a".into()))
+                ),
+                // The key column is read from the key.
+                (
+                    Op::Insert,
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Utf8("b".into()))
+                ),
+                // The tombstone deletes the row of the key, whose other columns are null.
+                (Op::Delete, Some(ScalarImpl::Int32(1)), None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upsert_avro_parser() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_schema = r#"{"type":"record","name":"key","fields":[{"name":"k","type":"int"}]}"#;
+        let value_schema =
+            r#"{"type":"record","name":"value","fields":[{"name":"v","type":"string"}]}"#;
+        let mut parsers = vec![];
+        for (name, schema) in [("key.avsc", key_schema), ("value.avsc", value_schema)] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, schema).unwrap();
+            let location = format!("file://{}", path.display());
+            parsers.push(
+                AvroParser::new(&location, false, HashMap::new())
+                    .await
+                    .unwrap(),
+            );
+        }
+        let value_parser = parsers.pop().unwrap();
+        let key_parser = parsers.pop().unwrap();
+        let parser = UpsertAvroParser {
+            key_parser,
+            value_parser,
+        };
+
+        let encode = |schema: &str, field: &str, value: Value| {
+            let schema = Schema::parse_str(schema).unwrap();
+            let mut record = Record::new(&schema).unwrap();
+            record.put(field, value);
+            let mut writer = Writer::new(&schema, vec![]);
+            writer.append(record).unwrap();
+            writer.into_inner().unwrap()
+        };
+        // None of the value fields is the key column, which is only in the key.
+        let key = encode(key_schema, "k", Value::Int(2));
+        let payload = encode(value_schema, "v", Value::String("b".to_string()));
+
+        let mut builder = SourceStreamChunkBuilder::with_capacity(column_descs(), 2);
+        parser
+            .parse(Some(&key), Some(&payload), builder.row_writer())
+            .await
+            .unwrap();
+        parser
+            .parse(Some(&key), None, builder.row_writer())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            rows(builder.finish()),
+            vec![
+                (
+                    Op::Insert,
+                    Some(ScalarImpl::Int32(2)),
+                    Some(ScalarImpl::Utf8("b".into()))
+                ),
+                (Op::Delete, Some(ScalarImpl::Int32(2)), None),
+            ]
+        );
+    }
+}
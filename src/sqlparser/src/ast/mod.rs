@@ -32,8 +32,8 @@ use serde::{Deserialize, Serialize};
 
 pub use self::data_type::{DataType, StructField};
 pub use self::ddl::{
-    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
-    ReferentialAction, TableConstraint,
+    AlterColumnOperation, AlterSourceOperation, AlterTableOperation, ColumnDef, ColumnOption,
+    ColumnOptionDef, ReferentialAction, TableConstraint,
 };
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
@@ -334,6 +334,8 @@ pub enum Expr {
     Array(Vec<Expr>),
     /// An array index expression e.g. `(ARRAY[1, 2])[1]` or `(current_schemas(FALSE))[1]`
     ArrayIndex { obj: Box<Expr>, index: Box<Expr> },
+    /// A parameter of prepared statements, e.g. `$1`. The index starts from 1.
+    Parameter { index: u64 },
 }
 
 impl fmt::Display for Expr {
@@ -407,6 +409,7 @@ impl fmt::Display for Expr {
             Expr::Collate { expr, collation } => write!(f, "{} COLLATE {}", expr, collation),
             Expr::Nested(ast) => write!(f, "({})", ast),
             Expr::Value(v) => write!(f, "{}", v),
+            Expr::Parameter { index } => write!(f, "${}", index),
             Expr::TypedString { data_type, value } => {
                 write!(f, "{}", data_type)?;
                 write!(f, " '{}'", &value::escape_single_quote_string(value))
@@ -782,6 +785,8 @@ pub struct ExplainOptions {
     pub trace: bool,
     // explain's plan type
     pub explain_type: ExplainType,
+    // Report the elapsed time and memory of each operator in `EXPLAIN ANALYZE`
+    pub timing: bool,
 }
 impl Default for ExplainOptions {
     fn default() -> Self {
@@ -789,6 +794,7 @@ impl Default for ExplainOptions {
             verbose: false,
             trace: false,
             explain_type: ExplainType::Physical,
+            timing: true,
         }
     }
 }
@@ -805,6 +811,9 @@ impl fmt::Display for ExplainOptions {
             if self.trace {
                 option_strs.push("TRACE".to_string());
             }
+            if !self.timing {
+                option_strs.push("TIMING FALSE".to_string());
+            }
             if self.explain_type == default.explain_type {
                 option_strs.push(self.explain_type.to_string());
             }
@@ -878,6 +887,8 @@ pub enum Statement {
         columns: Vec<ColumnDef>,
         constraints: Vec<TableConstraint>,
         with_options: Vec<SqlOption>,
+        /// `ROW FORMAT` of the connector in `with_options` the table is from
+        source_schema: Option<SourceSchema>,
         /// `AS ( query )`
         query: Option<Box<Query>>,
     },
@@ -915,6 +926,12 @@ pub enum Statement {
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// ALTER SOURCE
+    AlterSource {
+        /// Source name
+        name: ObjectName,
+        operation: AlterSourceOperation,
+    },
     /// DESCRIBE TABLE OR SOURCE
     Describe {
         /// Table or Source name
@@ -1185,6 +1202,7 @@ impl fmt::Display for Statement {
                 or_replace,
                 if_not_exists,
                 temporary,
+                source_schema,
                 query,
             } => {
                 // We want to allow the following options
@@ -1215,6 +1233,9 @@ impl fmt::Display for Statement {
                 if !with_options.is_empty() {
                     write!(f, " WITH ({})", display_comma_separated(with_options))?;
                 }
+                if let Some(source_schema) = source_schema {
+                    write!(f, " ROW FORMAT {}", source_schema)?;
+                }
                 if let Some(query) = query {
                     write!(f, " AS {}", query)?;
                 }
@@ -1264,6 +1285,9 @@ impl fmt::Display for Statement {
             Statement::AlterTable { name, operation } => {
                 write!(f, "ALTER TABLE {} {}", name, operation)
             }
+            Statement::AlterSource { name, operation } => {
+                write!(f, "ALTER SOURCE {} {}", name, operation)
+            }
             Statement::Drop(stmt) => write!(f, "DROP {}", stmt),
             Statement::SetVariable {
                 local,
@@ -1751,6 +1775,7 @@ pub enum ObjectType {
     Sink,
     Database,
     User,
+    Function,
 }
 
 impl fmt::Display for ObjectType {
@@ -1766,6 +1791,7 @@ impl fmt::Display for ObjectType {
             ObjectType::Sink => "SINK",
             ObjectType::Database => "DATABASE",
             ObjectType::User => "USER",
+            ObjectType::Function => "FUNCTION",
         })
     }
 }
@@ -1792,9 +1818,11 @@ impl ParseTo for ObjectType {
             ObjectType::Database
         } else if parser.parse_keyword(Keyword::USER) {
             ObjectType::User
+        } else if parser.parse_keyword(Keyword::FUNCTION) {
+            ObjectType::Function
         } else {
             return parser.expected(
-                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, SINK, SCHEMA, DATABASE or USER after DROP",
+                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, SINK, SCHEMA, DATABASE, USER or FUNCTION after DROP",
                 parser.peek_token(),
             );
         };
@@ -1988,6 +2016,10 @@ pub enum CreateFunctionBody {
     Behavior(FunctionBehavior),
     /// RETURN expression
     Return(Expr),
+    /// USING LINK 'url'
+    ///
+    /// The address of the external server that serves the function.
+    UsingLink(String),
 }
 
 impl fmt::Display for CreateFunctionBody {
@@ -1997,6 +2029,7 @@ impl fmt::Display for CreateFunctionBody {
             Self::Language(lang) => write!(f, "LANGUAGE {lang}"),
             Self::Behavior(behavior) => write!(f, "{behavior}"),
             Self::Return(expr) => write!(f, "RETURN {expr}"),
+            Self::UsingLink(link) => write!(f, "USING LINK '{link}'"),
         }
     }
 }
@@ -86,6 +86,11 @@ pub enum BinaryOperator {
     PGRegexIMatch,
     PGRegexNotMatch,
     PGRegexNotIMatch,
+    Arrow,
+    LongArrow,
+    HashArrow,
+    AtArrow,
+    QuestionMark,
 }
 
 impl fmt::Display for BinaryOperator {
@@ -121,6 +126,11 @@ impl fmt::Display for BinaryOperator {
             BinaryOperator::PGRegexIMatch => "~*",
             BinaryOperator::PGRegexNotMatch => "!~",
             BinaryOperator::PGRegexNotIMatch => "!~*",
+            BinaryOperator::Arrow => "->",
+            BinaryOperator::LongArrow => "->>",
+            BinaryOperator::HashArrow => "#>",
+            BinaryOperator::AtArrow => "@>",
+            BinaryOperator::QuestionMark => "?",
         })
     }
 }
@@ -331,6 +331,12 @@ pub enum TableFactor {
     Table {
         name: ObjectName,
         alias: Option<TableAlias>,
+        /// Whether the table is read as of the process time, i.e. `FOR SYSTEM_TIME AS OF
+        /// PROCTIME()`. It turns a join with the table into a temporal join.
+        for_system_time_as_of_proctime: bool,
+        /// The timestamp the table is read as of, i.e. `FOR SYSTEM_TIME AS OF '<timestamp>'`. It
+        /// turns the read into a time-travel query on a historical version of the table.
+        for_system_time_as_of_timestamp: Option<String>,
     },
     Derived {
         lateral: bool,
@@ -355,8 +361,23 @@ pub enum TableFactor {
 impl fmt::Display for TableFactor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TableFactor::Table { name, alias } => {
+            TableFactor::Table {
+                name,
+                alias,
+                for_system_time_as_of_proctime,
+                for_system_time_as_of_timestamp,
+            } => {
                 write!(f, "{}", name)?;
+                if *for_system_time_as_of_proctime {
+                    write!(f, " FOR SYSTEM_TIME AS OF PROCTIME()")?;
+                }
+                if let Some(timestamp) = for_system_time_as_of_timestamp {
+                    write!(
+                        f,
+                        " FOR SYSTEM_TIME AS OF '{}'",
+                        value::escape_single_quote_string(timestamp)
+                    )?;
+                }
                 if let Some(alias) = alias {
                     write!(f, " AS {}", alias)?;
                 }
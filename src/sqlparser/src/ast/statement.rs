@@ -21,6 +21,7 @@ use serde::{Deserialize, Serialize};
 use super::{Ident, ObjectType, Query};
 use crate::ast::{
     display_comma_separated, display_separated, ColumnDef, ObjectName, SqlOption, TableConstraint,
+    Value,
 };
 use crate::keywords::Keyword;
 use crate::parser::{IsOptional, Parser, ParserError};
@@ -85,11 +86,15 @@ pub struct CreateSourceStatement {
 pub enum SourceSchema {
     Protobuf(ProtobufSchema),
     // Keyword::PROTOBUF ProtobufSchema
-    Json,             // Keyword::JSON
-    DebeziumJson,     // Keyword::DEBEZIUM_JSON
-    Avro(AvroSchema), // Keyword::AVRO
-    Maxwell,          // Keyword::MAXWELL
-    CanalJson,        // Keyword::CANAL_JSON
+    Json,                   // Keyword::JSON
+    DebeziumJson,           // Keyword::DEBEZIUM_JSON
+    Avro(AvroSchema),       // Keyword::AVRO
+    Maxwell,                // Keyword::MAXWELL
+    CanalJson,              // Keyword::CANAL_JSON
+    Csv(CsvInfo),           // Keyword::CSV
+    Parquet,                // Keyword::PARQUET
+    UpsertJson,             // Keyword::UPSERT_JSON
+    UpsertAvro(AvroSchema), // Keyword::UPSERT_AVRO
 }
 
 impl ParseTo for SourceSchema {
@@ -108,9 +113,19 @@ impl ParseTo for SourceSchema {
             SourceSchema::Maxwell
         } else if p.parse_keywords(&[Keyword::CANAL_JSON]) {
             SourceSchema::CanalJson
+        } else if p.parse_keywords(&[Keyword::CSV]) {
+            impl_parse_to!(csv_info: CsvInfo, p);
+            SourceSchema::Csv(csv_info)
+        } else if p.parse_keywords(&[Keyword::PARQUET]) {
+            SourceSchema::Parquet
+        } else if p.parse_keywords(&[Keyword::UPSERT_JSON]) {
+            SourceSchema::UpsertJson
+        } else if p.parse_keywords(&[Keyword::UPSERT_AVRO]) {
+            impl_parse_to!(avro_schema: AvroSchema, p);
+            SourceSchema::UpsertAvro(avro_schema)
         } else {
             return Err(ParserError::ParserError(
-                "expected JSON | PROTOBUF | DEBEZIUM_JSON | AVRO | MAXWELL | CANAL_JSON after ROW FORMAT".to_string(),
+                "expected JSON | PROTOBUF | DEBEZIUM_JSON | AVRO | MAXWELL | CANAL_JSON | CSV | PARQUET | UPSERT_JSON | UPSERT_AVRO after ROW FORMAT".to_string(),
             ));
         };
         Ok(schema)
@@ -126,6 +141,10 @@ impl fmt::Display for SourceSchema {
             SourceSchema::DebeziumJson => write!(f, "DEBEZIUM JSON"),
             SourceSchema::Avro(avro_schema) => write!(f, "AVRO {}", avro_schema),
             SourceSchema::CanalJson => write!(f, "CANAL JSON"),
+            SourceSchema::Csv(csv_info) => write!(f, "CSV {}", csv_info),
+            SourceSchema::Parquet => write!(f, "PARQUET"),
+            SourceSchema::UpsertJson => write!(f, "UPSERT_JSON"),
+            SourceSchema::UpsertAvro(avro_schema) => write!(f, "UPSERT_AVRO {}", avro_schema),
         }
     }
 }
@@ -212,6 +231,75 @@ impl fmt::Display for AvroSchema {
     }
 }
 
+// sql_grammar!(CsvInfo {
+//     [Token::LParen, options: SqlOption, Token::RParen],
+// });
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CsvInfo {
+    pub delimiter: u8,
+    pub has_header: bool,
+}
+
+impl Default for CsvInfo {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            has_header: true,
+        }
+    }
+}
+
+impl ParseTo for CsvInfo {
+    fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
+        let mut csv_info = CsvInfo::default();
+        if !p.consume_token(&Token::LParen) {
+            return Ok(csv_info);
+        }
+        for option in p.parse_comma_separated(Parser::parse_sql_option)? {
+            match (option.name.real_value().as_str(), option.value) {
+                ("delimiter", Value::SingleQuotedString(s)) if s.len() == 1 && s.is_ascii() => {
+                    csv_info.delimiter = s.as_bytes()[0];
+                }
+                ("delimiter", value) => {
+                    return Err(ParserError::ParserError(format!(
+                        "expected a single ASCII character as the CSV delimiter, found: {}",
+                        value
+                    )))
+                }
+                ("without_header", Value::Boolean(without_header)) => {
+                    csv_info.has_header = !without_header;
+                }
+                ("without_header", value) => {
+                    return Err(ParserError::ParserError(format!(
+                        "expected a boolean for without_header, found: {}",
+                        value
+                    )))
+                }
+                (name, _) => {
+                    return Err(ParserError::ParserError(format!(
+                        "unrecognized CSV option: {}",
+                        name
+                    )))
+                }
+            }
+        }
+        p.expect_token(&Token::RParen)?;
+        Ok(csv_info)
+    }
+}
+
+impl fmt::Display for CsvInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "(delimiter = {}, without_header = {})",
+            Value::SingleQuotedString((self.delimiter as char).to_string()),
+            !self.has_header
+        )
+    }
+}
+
 impl ParseTo for CreateSourceStatement {
     fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
         impl_parse_to!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], p);
@@ -280,6 +280,7 @@ define_keywords!(
     LEVEL,
     LIKE,
     LIMIT,
+    LINK,
     LN,
     LOCAL,
     LOCALTIME,
@@ -368,6 +369,7 @@ define_keywords!(
     PRIMARY,
     PRIVILEGES,
     PROCEDURE,
+    PROCTIME,
     PROTOBUF,
     PURGE,
     RANGE,
@@ -380,6 +382,7 @@ define_keywords!(
     REF,
     REFERENCES,
     REFERENCING,
+    REFRESH,
     REGCLASS,
     REGISTRY,
     REGR_AVGX,
@@ -477,6 +480,7 @@ define_keywords!(
     TIES,
     TIME,
     TIMESTAMP,
+    TIMING,
     TIMEZONE_HOUR,
     TIMEZONE_MINUTE,
     TINYINT,
@@ -505,6 +509,8 @@ define_keywords!(
     UNNEST,
     UPDATE,
     UPPER,
+    UPSERT_AVRO,
+    UPSERT_JSON,
     USAGE,
     USER,
     USING,
@@ -476,6 +476,12 @@ impl Parser {
                 self.prev_token();
                 Ok(Expr::Value(self.parse_value()?))
             }
+            Token::Placeholder(s) => {
+                let index = s[1..].parse::<u64>().map_err(|e| {
+                    ParserError::ParserError(format!("Could not parse '{}' as u64: {}", s, e))
+                })?;
+                Ok(Expr::Parameter { index })
+            }
 
             Token::LParen => {
                 let expr =
@@ -1025,6 +1031,11 @@ impl Parser {
             Token::TildeAsterisk => Some(BinaryOperator::PGRegexIMatch),
             Token::ExclamationMarkTilde => Some(BinaryOperator::PGRegexNotMatch),
             Token::ExclamationMarkTildeAsterisk => Some(BinaryOperator::PGRegexNotIMatch),
+            Token::Arrow => Some(BinaryOperator::Arrow),
+            Token::LongArrow => Some(BinaryOperator::LongArrow),
+            Token::HashArrow => Some(BinaryOperator::HashArrow),
+            Token::AtArrow => Some(BinaryOperator::AtArrow),
+            Token::QuestionMark => Some(BinaryOperator::QuestionMark),
             Token::Word(w) => match w.keyword {
                 Keyword::AND => Some(BinaryOperator::And),
                 Keyword::OR => Some(BinaryOperator::Or),
@@ -1240,6 +1251,11 @@ impl Parser {
             | Token::Spaceship => Ok(20),
             Token::Pipe => Ok(21),
             Token::Caret | Token::Sharp | Token::ShiftRight | Token::ShiftLeft => Ok(22),
+            Token::Arrow
+            | Token::LongArrow
+            | Token::HashArrow
+            | Token::AtArrow
+            | Token::QuestionMark => Ok(22),
             Token::Ampersand => Ok(23),
             Token::Plus | Token::Minus => Ok(Self::PLUS_MINUS_PREC),
             Token::Mul | Token::Div | Token::Mod | Token::Concat => Ok(40),
@@ -1657,8 +1673,10 @@ impl Parser {
         } else if self.parse_keyword(Keyword::RETURN) {
             let expr = self.parse_expr()?;
             Ok(CreateFunctionBody::Return(expr))
+        } else if self.parse_keywords(&[Keyword::USING, Keyword::LINK]) {
+            Ok(CreateFunctionBody::UsingLink(self.parse_literal_string()?))
         } else {
-            self.expected("AS or LANGUAGE or RETURN", self.peek_token())
+            self.expected("AS or LANGUAGE or RETURN or USING LINK", self.peek_token())
         }
     }
 
@@ -1724,6 +1742,12 @@ impl Parser {
 
         // PostgreSQL supports `WITH ( options )`, before `AS`
         let with_options = self.parse_with_properties()?;
+        // Parse optional `ROW FORMAT`, if the table is from a connector
+        let source_schema = if self.parse_keywords(&[Keyword::ROW, Keyword::FORMAT]) {
+            Some(SourceSchema::parse_to(self)?)
+        } else {
+            None
+        };
         // Parse optional `AS ( query )`
         let query = if self.parse_keyword(Keyword::AS) {
             Some(Box::new(self.parse_query()?))
@@ -1739,6 +1763,7 @@ impl Parser {
             with_options,
             or_replace,
             if_not_exists,
+            source_schema,
             query,
         })
     }
@@ -1958,11 +1983,26 @@ impl Parser {
             self.parse_alter_table()
         } else if self.parse_keyword(Keyword::USER) {
             self.parse_alter_user()
+        } else if self.parse_keyword(Keyword::SOURCE) {
+            self.parse_alter_source()
         } else {
-            self.expected("TABLE or USER after ALTER", self.peek_token())
+            self.expected("TABLE, USER or SOURCE after ALTER", self.peek_token())
         }
     }
 
+    pub fn parse_alter_source(&mut self) -> Result<Statement, ParserError> {
+        let source_name = self.parse_object_name()?;
+        let operation = if self.parse_keywords(&[Keyword::REFRESH, Keyword::SCHEMA]) {
+            AlterSourceOperation::RefreshSchema
+        } else {
+            return self.expected("REFRESH SCHEMA after ALTER SOURCE", self.peek_token());
+        };
+        Ok(Statement::AlterSource {
+            name: source_name,
+            operation,
+        })
+    }
+
     pub fn parse_alter_user(&mut self) -> Result<Statement, ParserError> {
         Ok(Statement::AlterUser(AlterUserStatement::parse_to(self)?))
     }
@@ -2522,6 +2562,7 @@ impl Parser {
         let explain_key_words = [
             Keyword::VERBOSE,
             Keyword::TRACE,
+            Keyword::TIMING,
             Keyword::TYPE,
             Keyword::LOGICAL,
             Keyword::PHYSICAL,
@@ -2533,6 +2574,7 @@ impl Parser {
             match keyword {
                 Keyword::VERBOSE => options.verbose = parser.parse_optional_boolean(true),
                 Keyword::TRACE => options.trace = parser.parse_optional_boolean(true),
+                Keyword::TIMING => options.timing = parser.parse_optional_boolean(true),
                 Keyword::TYPE => {
                     let explain_type = parser.expect_one_of_keywords(&[
                         Keyword::LOGICAL,
@@ -3070,12 +3112,35 @@ impl Parser {
                 let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
                 Ok(TableFactor::TableFunction { name, alias, args })
             } else {
+                let (for_system_time_as_of_proctime, for_system_time_as_of_timestamp) =
+                    self.parse_for_system_time_as_of()?;
                 let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
-                Ok(TableFactor::Table { name, alias })
+                Ok(TableFactor::Table {
+                    name,
+                    alias,
+                    for_system_time_as_of_proctime,
+                    for_system_time_as_of_timestamp,
+                })
             }
         }
     }
 
+    /// Parses an optional `FOR SYSTEM_TIME AS OF PROCTIME()` or `FOR SYSTEM_TIME AS OF
+    /// '<timestamp>'` after a table name, returning whether it is as of the process time and the
+    /// timestamp it is as of.
+    pub fn parse_for_system_time_as_of(&mut self) -> Result<(bool, Option<String>), ParserError> {
+        if !self.parse_keywords(&[Keyword::FOR, Keyword::SYSTEM_TIME, Keyword::AS, Keyword::OF]) {
+            return Ok((false, None));
+        }
+        if self.parse_keyword(Keyword::PROCTIME) {
+            self.expect_token(&Token::LParen)?;
+            self.expect_token(&Token::RParen)?;
+            Ok((true, None))
+        } else {
+            Ok((false, Some(self.parse_literal_string()?)))
+        }
+    }
+
     pub fn parse_derived_table_factor(
         &mut self,
         lateral: IsLateral,
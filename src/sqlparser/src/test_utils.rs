@@ -139,6 +139,8 @@ pub fn table(name: impl Into<String>) -> TableFactor {
     TableFactor::Table {
         name: ObjectName(vec![Ident::new(name.into())]),
         alias: None,
+        for_system_time_as_of_proctime: false,
+        for_system_time_as_of_timestamp: None,
     }
 }
 
@@ -138,6 +138,18 @@ pub enum Token {
     PGSquareRoot,
     /// `||/` , a cube root math operator in PostgreSQL
     PGCubeRoot,
+    /// Parameter placeholder of prepared statements in PostgreSQL, e.g. `$1`
+    Placeholder(String),
+    /// `->`, access a field or an element of jsonb in PostgreSQL
+    Arrow,
+    /// `->>`, access a field or an element of jsonb as text in PostgreSQL
+    LongArrow,
+    /// `#>`, access a path of jsonb in PostgreSQL
+    HashArrow,
+    /// `@>`, a containment operator of jsonb in PostgreSQL
+    AtArrow,
+    /// Question Mark `?`, a key existence operator of jsonb in PostgreSQL
+    QuestionMark,
 }
 
 impl fmt::Display for Token {
@@ -193,6 +205,12 @@ impl fmt::Display for Token {
             Token::ShiftRight => f.write_str(">>"),
             Token::PGSquareRoot => f.write_str("|/"),
             Token::PGCubeRoot => f.write_str("||/"),
+            Token::Placeholder(ref s) => write!(f, "{}", s),
+            Token::Arrow => f.write_str("->"),
+            Token::LongArrow => f.write_str("->>"),
+            Token::HashArrow => f.write_str("#>"),
+            Token::AtArrow => f.write_str("@>"),
+            Token::QuestionMark => f.write_str("?"),
         }
     }
 }
@@ -334,6 +352,7 @@ impl<'a> Tokenizer<'a> {
                 Token::Word(w) if w.quote_style.is_some() => self.col += w.value.len() as u64 + 2,
                 Token::Number(s) => self.col += s.len() as u64,
                 Token::SingleQuotedString(s) => self.col += s.len() as u64,
+                Token::Placeholder(s) => self.col += s.len() as u64,
                 _ => self.col += 1,
             }
 
@@ -485,6 +504,13 @@ impl<'a> Tokenizer<'a> {
                                 comment,
                             })))
                         }
+                        Some('>') => {
+                            chars.next(); // consume the '>'
+                            match chars.peek() {
+                                Some('>') => self.consume_and_return(chars, Token::LongArrow),
+                                _ => Ok(Some(Token::Arrow)),
+                            }
+                        }
                         // a regular '-' operator
                         _ => Ok(Some(Token::Minus)),
                     }
@@ -586,8 +612,30 @@ impl<'a> Tokenizer<'a> {
                         _ => Ok(Some(Token::Tilde)),
                     }
                 }
-                '#' => self.consume_and_return(chars, Token::Sharp),
-                '@' => self.consume_and_return(chars, Token::AtSign),
+                '#' => {
+                    chars.next(); // consume
+                    match chars.peek() {
+                        Some('>') => self.consume_and_return(chars, Token::HashArrow),
+                        _ => Ok(Some(Token::Sharp)),
+                    }
+                }
+                '@' => {
+                    chars.next(); // consume
+                    match chars.peek() {
+                        Some('>') => self.consume_and_return(chars, Token::AtArrow),
+                        _ => Ok(Some(Token::AtSign)),
+                    }
+                }
+                '?' => self.consume_and_return(chars, Token::QuestionMark),
+                '$' => {
+                    chars.next(); // consume
+                    let s = peeking_take_while(chars, |ch| matches!(ch, '0'..='9'));
+                    if s.is_empty() {
+                        Ok(Some(Token::Char('$')))
+                    } else {
+                        Ok(Some(Token::Placeholder(format!("${}", s))))
+                    }
+                }
                 other => self.consume_and_return(chars, Token::Char(other)),
             },
             None => Ok(None),
@@ -775,6 +823,24 @@ mod tests {
         compare(expected, tokens);
     }
 
+    #[test]
+    fn tokenize_placeholder() {
+        let sql = String::from("SELECT $1, $23");
+        let mut tokenizer = Tokenizer::new(&sql);
+        let tokens = tokenizer.tokenize().unwrap();
+
+        let expected = vec![
+            Token::make_keyword("SELECT"),
+            Token::Whitespace(Whitespace::Space),
+            Token::Placeholder(String::from("$1")),
+            Token::Comma,
+            Token::Whitespace(Whitespace::Space),
+            Token::Placeholder(String::from("$23")),
+        ];
+
+        compare(expected, tokens);
+    }
+
     #[test]
     fn tokenize_select_float() {
         let sql = String::from("SELECT .1");
@@ -1753,6 +1753,14 @@ fn parse_explain_analyze_with_simple_select() {
             ..Default::default()
         },
     );
+    run_explain_analyze(
+        "EXPLAIN ANALYZE (TIMING false) SELECT sqrt(id) FROM foo",
+        true,
+        ExplainOptions {
+            timing: false,
+            ..Default::default()
+        },
+    );
 
     run_explain_analyze(
         "EXPLAIN (VERBOSE  , TRACE) SELECT sqrt(id) FROM foo",
@@ -1770,6 +1778,7 @@ fn parse_explain_analyze_with_simple_select() {
             trace: true,
             verbose: true,
             explain_type: ExplainType::DistSql,
+            timing: true,
         },
     );
     run_explain_analyze(
@@ -1779,6 +1788,7 @@ fn parse_explain_analyze_with_simple_select() {
             trace: false,
             verbose: true,
             explain_type: ExplainType::DistSql,
+            timing: true,
         },
     );
     run_explain_analyze(
@@ -1788,6 +1798,7 @@ fn parse_explain_analyze_with_simple_select() {
             trace: false,
             verbose: true,
             explain_type: ExplainType::DistSql,
+            timing: true,
         },
     );
 }
@@ -2159,7 +2170,7 @@ fn parse_delimited_identifiers() {
     );
     // check FROM
     match only(select.from).relation {
-        TableFactor::Table { name, alias } => {
+        TableFactor::Table { name, alias, .. } => {
             assert_eq!(vec![Ident::with_quote('"', "a table")], name.0);
             assert_eq!(Ident::with_quote('"', "alias"), alias.unwrap().name);
         }
@@ -2285,6 +2296,8 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t1".into()]),
                     alias: None,
+                    for_system_time_as_of_proctime: false,
+                    for_system_time_as_of_timestamp: None,
                 },
                 joins: vec![],
             },
@@ -2292,6 +2305,8 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
+                    for_system_time_as_of_proctime: false,
+                    for_system_time_as_of_timestamp: None,
                 },
                 joins: vec![],
             }
@@ -2307,11 +2322,15 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t1a".into()]),
                     alias: None,
+                    for_system_time_as_of_proctime: false,
+                    for_system_time_as_of_timestamp: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t1b".into()]),
                         alias: None,
+                        for_system_time_as_of_proctime: false,
+                        for_system_time_as_of_timestamp: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2320,11 +2339,15 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2a".into()]),
                     alias: None,
+                    for_system_time_as_of_proctime: false,
+                    for_system_time_as_of_timestamp: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t2b".into()]),
                         alias: None,
+                        for_system_time_as_of_proctime: false,
+                        for_system_time_as_of_timestamp: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2343,6 +2366,8 @@ fn parse_cross_join() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
+                for_system_time_as_of_proctime: false,
+                for_system_time_as_of_timestamp: None,
             },
             join_operator: JoinOperator::CrossJoin
         },
@@ -2361,6 +2386,8 @@ fn parse_joins_on() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
+                for_system_time_as_of_proctime: false,
+                for_system_time_as_of_timestamp: None,
             },
             join_operator: f(JoinConstraint::On(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("c1".into())),
@@ -2412,6 +2439,8 @@ fn parse_joins_using() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
+                for_system_time_as_of_proctime: false,
+                for_system_time_as_of_timestamp: None,
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
         }
@@ -2455,6 +2484,8 @@ fn parse_natural_join() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
+                for_system_time_as_of_proctime: false,
+                for_system_time_as_of_timestamp: None,
             },
             join_operator: f(JoinConstraint::Natural),
         }
@@ -2686,6 +2717,8 @@ fn parse_derived_tables() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
+                    for_system_time_as_of_proctime: false,
+                    for_system_time_as_of_timestamp: None,
                 },
                 join_operator: JoinOperator::Inner(JoinConstraint::Natural),
             }],
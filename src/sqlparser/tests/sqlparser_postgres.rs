@@ -688,6 +688,67 @@ fn parse_pg_regex_match_ops() {
     }
 }
 
+#[test]
+fn parse_pg_jsonb_ops() {
+    let pg_jsonb_ops = &[
+        ("->", BinaryOperator::Arrow),
+        ("->>", BinaryOperator::LongArrow),
+        ("#>", BinaryOperator::HashArrow),
+        ("@>", BinaryOperator::AtArrow),
+        ("?", BinaryOperator::QuestionMark),
+    ];
+
+    for (str_op, op) in pg_jsonb_ops {
+        let select = verified_only_select(&format!("SELECT a {} 'b'", &str_op));
+        assert_eq!(
+            SelectItem::UnnamedExpr(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("a"))),
+                op: op.clone(),
+                right: Box::new(Expr::Value(Value::SingleQuotedString("b".into()))),
+            }),
+            select.projection[0]
+        );
+    }
+
+    // Jsonb operators are left-associative and bind tighter than comparisons.
+    assert_eq!(
+        verified_expr("a -> 'b' ->> 'c' = 'd'"),
+        Expr::BinaryOp {
+            left: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(Ident::new("a"))),
+                    op: BinaryOperator::Arrow,
+                    right: Box::new(Expr::Value(Value::SingleQuotedString("b".into()))),
+                }),
+                op: BinaryOperator::LongArrow,
+                right: Box::new(Expr::Value(Value::SingleQuotedString("c".into()))),
+            }),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(Value::SingleQuotedString("d".into()))),
+        }
+    );
+}
+
+#[test]
+fn parse_parameters() {
+    assert_eq!(verified_expr("$1"), Expr::Parameter { index: 1 });
+    assert_eq!(
+        verified_expr("$1 + $12"),
+        Expr::BinaryOp {
+            left: Box::new(Expr::Parameter { index: 1 }),
+            op: BinaryOperator::Plus,
+            right: Box::new(Expr::Parameter { index: 12 }),
+        }
+    );
+    assert_eq!(
+        verified_expr("CAST($2 AS INT)"),
+        Expr::Cast {
+            expr: Box::new(Expr::Parameter { index: 2 }),
+            data_type: DataType::Int(None),
+        }
+    );
+}
+
 #[test]
 fn test_transaction_statement() {
     let statement = verified_stmt("SET TRANSACTION SNAPSHOT '000003A1-1'");
@@ -804,4 +865,22 @@ fn parse_create_function() {
             ],
         }
     );
+
+    let sql = "CREATE FUNCTION gcd(INT, INT) RETURNS INT LANGUAGE python USING LINK 'http://localhost:8815'";
+    assert_eq!(
+        verified_stmt(sql),
+        Statement::CreateFunction {
+            or_replace: false,
+            name: ObjectName(vec![Ident::new("gcd")]),
+            args: Some(vec![
+                CreateFunctionArg::unnamed(DataType::Int(None)),
+                CreateFunctionArg::unnamed(DataType::Int(None)),
+            ]),
+            return_type: Some(DataType::Int(None)),
+            bodies: vec![
+                CreateFunctionBody::Language("python".into()),
+                CreateFunctionBody::UsingLink("http://localhost:8815".into()),
+            ],
+        }
+    );
 }
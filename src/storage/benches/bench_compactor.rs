@@ -52,6 +52,7 @@ pub fn mock_sstable_store() -> SstableStoreRef {
         64 << 20,
         128 << 20,
         TieredCache::none(),
+        TieredCache::none(),
     ))
 }
 
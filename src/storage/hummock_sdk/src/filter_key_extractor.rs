@@ -18,7 +18,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use parking_lot::RwLock;
-use risingwave_common::catalog::ColumnDesc;
+use risingwave_common::catalog::{bloom_filter_prefix_len, ColumnDesc};
 use risingwave_common::hash::VirtualNode;
 use risingwave_common::util::ordered::OrderedRowSerde;
 use risingwave_common::util::sort_util::OrderType;
@@ -56,14 +56,20 @@ impl FilterKeyExtractorImpl {
             .map(|col_order| col_order.index as usize)
             .collect();
 
-        let match_read_pattern =
-            !dist_key_indices.is_empty() && pk_indices.starts_with(&dist_key_indices);
-        if !match_read_pattern {
+        match bloom_filter_prefix_len(
+            table_catalog.read_prefix_len_hint as usize,
+            &pk_indices,
+            &dist_key_indices,
+        ) {
+            Some(read_pattern_prefix_column) => {
+                FilterKeyExtractorImpl::Schema(SchemaFilterKeyExtractor::with_prefix_column(
+                    table_catalog,
+                    read_pattern_prefix_column,
+                ))
+            }
             // for now frontend had not infer the table_id_to_filter_key_extractor, so we
             // use FullKeyFilterKeyExtractor
-            FilterKeyExtractorImpl::FullKey(FullKeyFilterKeyExtractor::default())
-        } else {
-            FilterKeyExtractorImpl::Schema(SchemaFilterKeyExtractor::new(table_catalog))
+            None => FilterKeyExtractorImpl::FullKey(FullKeyFilterKeyExtractor::default()),
         }
     }
 }
@@ -165,8 +171,13 @@ impl FilterKeyExtractor for SchemaFilterKeyExtractor {
 
 impl SchemaFilterKeyExtractor {
     pub fn new(table_catalog: &Table) -> Self {
-        let read_pattern_prefix_column = table_catalog.distribution_key.len();
+        Self::with_prefix_column(table_catalog, table_catalog.distribution_key.len())
+    }
+
+    /// Creates an extractor with the prefix of the first `read_pattern_prefix_column` pk columns.
+    pub fn with_prefix_column(table_catalog: &Table, read_pattern_prefix_column: usize) -> Self {
         assert_ne!(0, read_pattern_prefix_column);
+        assert!(read_pattern_prefix_column <= table_catalog.pk.len());
 
         // column_index in pk
         let pk_indices: Vec<usize> = table_catalog
@@ -478,6 +489,7 @@ mod tests {
             value_indices: vec![0],
             definition: "".into(),
             handle_pk_conflict: false,
+            read_prefix_len_hint: 0,
         }
     }
 
@@ -513,6 +525,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_filter_key_extractor_with_read_prefix_len_hint() {
+        // The distribution key is not a prefix of the pk, so the full key is used by default.
+        let mut prost_table = build_table_with_prefix_column_num(1);
+        prost_table.distribution_key = vec![3];
+        assert!(matches!(
+            FilterKeyExtractorImpl::from_table(&prost_table),
+            FilterKeyExtractorImpl::FullKey(_)
+        ));
+
+        prost_table.read_prefix_len_hint = 1;
+        let filter_key_extractor = FilterKeyExtractorImpl::from_table(&prost_table);
+        assert!(matches!(
+            filter_key_extractor,
+            FilterKeyExtractorImpl::Schema(_)
+        ));
+
+        let order_types: Vec<OrderType> = vec![OrderType::Ascending, OrderType::Ascending];
+        let schema = vec![DataType::Int64, DataType::Varchar];
+        let serializer = OrderedRowSerde::new(schema, order_types);
+        let row = Row::new(vec![
+            Some(ScalarImpl::Int64(100)),
+            Some(ScalarImpl::Utf8("abc".into())),
+        ]);
+        let mut row_bytes = vec![];
+        serializer.serialize(&row, &mut row_bytes);
+
+        let table_prefix = {
+            let mut buf = BytesMut::with_capacity(TABLE_PREFIX_LEN);
+            buf.put_u32(1);
+            buf.to_vec()
+        };
+        let vnode_prefix = "v".as_bytes();
+        let full_key = [&table_prefix, vnode_prefix, &row_bytes].concat();
+        let output_key = filter_key_extractor.extract(&full_key);
+        assert_eq!(
+            TABLE_PREFIX_LEN + VirtualNode::SIZE + 1 + mem::size_of::<i64>(),
+            output_key.len()
+        );
+
+        // A hint longer than the pk is ignored.
+        prost_table.read_prefix_len_hint = 3;
+        assert!(matches!(
+            FilterKeyExtractorImpl::from_table(&prost_table),
+            FilterKeyExtractorImpl::FullKey(_)
+        ));
+    }
+
     #[test]
     fn test_multi_filter_key_extractor() {
         let mut multi_filter_key_extractor = MultiFilterKeyExtractor::default();
@@ -263,6 +263,7 @@ pub(crate) mod tests {
             1,
             TableOption {
                 retention_seconds: 64,
+                ..Default::default()
             },
         )]);
         compact_task.current_epoch_time = 0;
@@ -893,6 +894,7 @@ pub(crate) mod tests {
             existing_table_id,
             TableOption {
                 retention_seconds: retention_seconds_expire_second,
+                ..Default::default()
             },
         )]);
         compact_task.current_epoch_time = epoch;
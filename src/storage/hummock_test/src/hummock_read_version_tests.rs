@@ -158,6 +158,8 @@ async fn test_read_version_basic() {
                     stale_key_count: 1,
                     total_key_count: 1,
                     divide_version: 0,
+                    delete_key_count: 0,
+                    range_tombstone_count: 0,
                 }),
                 LocalSstableInfo::for_test(SstableInfo {
                     id: 2,
@@ -172,6 +174,8 @@ async fn test_read_version_basic() {
                     stale_key_count: 1,
                     total_key_count: 1,
                     divide_version: 0,
+                    delete_key_count: 0,
+                    range_tombstone_count: 0,
                 }),
             ],
             epoch_id_vec_for_clear,
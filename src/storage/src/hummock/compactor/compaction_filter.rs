@@ -31,6 +31,8 @@ pub struct DummyCompactionFilter;
 
 impl CompactionFilter for DummyCompactionFilter {}
 
+/// Deletes the keys of the dropped tables. They are deleted regardless of the time-travel retention
+/// of the tables, since a dropped table can no longer be read by time-travel queries.
 #[derive(Clone)]
 pub struct StateCleanUpCompactionFilter {
     existing_table_ids: HashSet<u32>,
@@ -60,6 +62,9 @@ impl CompactionFilter for StateCleanUpCompactionFilter {
     }
 }
 
+/// Deletes the keys older than the TTL of their tables. The TTL of a table with time-travel
+/// retention is extended by the retention, so that the keys are kept as long as they are not
+/// expired as of any epoch a time-travel query can read.
 #[derive(Clone)]
 pub struct TtlCompactionFilter {
     table_id_to_ttl: HashMap<u32, u32>,
@@ -82,7 +87,7 @@ impl CompactionFilter for TtlCompactionFilter {
             Some(ttl_second_u32) => {
                 assert!(*ttl_second_u32 != TABLE_OPTION_DUMMY_RETENTION_SECOND);
                 // default to zero.
-                let ttl_mill = (*ttl_second_u32 * 1000) as u64;
+                let ttl_mill = *ttl_second_u32 as u64 * 1000;
                 let min_epoch = Epoch(self.expire_epoch).subtract_ms(ttl_mill);
                 self.last_table_and_ttl = Some((table_id, ttl_mill));
                 Epoch(epoch) <= min_epoch
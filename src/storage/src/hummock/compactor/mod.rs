@@ -842,11 +842,15 @@ fn build_multi_compaction_filter(compact_task: &CompactTask) -> MultiCompactionF
         let id_to_ttl = compact_task
             .table_options
             .iter()
-            .filter(|id_to_option| {
+            .filter_map(|id_to_option| {
                 let table_option: TableOption = id_to_option.1.into();
-                table_option.retention_seconds.is_some()
+                let ttl = table_option.retention_seconds?.saturating_add(
+                    table_option
+                        .time_travel_retention_seconds
+                        .unwrap_or_default(),
+                );
+                Some((*id_to_option.0, ttl))
             })
-            .map(|id_to_option| (*id_to_option.0, id_to_option.1.retention_seconds))
             .collect();
 
         let ttl_filter = Box::new(TtlCompactionFilter::new(
@@ -21,7 +21,7 @@ use risingwave_common::cache::LruCache;
 use tokio::sync::Notify;
 
 use super::buffer::TwoLevelBuffer;
-use super::error::Result;
+use super::error::{Error, Result};
 use super::meta::SlotId;
 use super::metrics::FileCacheMetricsRef;
 use super::store::{FsType, Store, StoreOptions, StoreRef};
@@ -256,17 +256,30 @@ where
         let hash = self.hash_builder.hash_one(key);
         if let Some(holder) = self.buffer.get(hash, key) {
             timer.observe_duration();
+            self.metrics.cache_hit.inc();
             return Ok(Some(holder));
         }
 
         if let Some(entry) = self.indices.lookup(hash, key) {
             let slot = *entry.value();
-            let raw = self.store.get(slot).await?;
-            let value = V::decode(raw);
+            match self.store.get(slot).await {
+                Ok(raw) => {
+                    let value = V::decode(raw);
 
-            timer.observe_duration();
+                    timer.observe_duration();
+                    self.metrics.cache_hit.inc();
 
-            return Ok(Some(TieredCacheEntryHolder::from_owned_value(value)));
+                    return Ok(Some(TieredCacheEntryHolder::from_owned_value(value)));
+                }
+                Err(e @ Error::ChecksumMismatch { .. }) => {
+                    // Treat the corrupted entry as a miss and drop it, so that the caller can
+                    // fall back to the lower tier.
+                    tracing::warn!("drop corrupted file cache entry {:?}: {}", key, e);
+                    self.metrics.corrupted_entries.inc();
+                    self.indices.erase(hash, key);
+                }
+                Err(e) => return Err(e),
+            }
         }
 
         timer.observe_duration();
@@ -359,7 +372,7 @@ mod tests {
         FileCache::open_with_hasher(
             options,
             ModuloHasherBuilder,
-            Arc::new(FileCacheMetrics::new(Registry::new())),
+            Arc::new(FileCacheMetrics::new(Registry::new(), "test")),
         )
         .await
         .unwrap()
@@ -543,4 +556,91 @@ mod tests {
             assert_eq!(cache.get(&key).await.unwrap().as_deref(), slot.as_deref());
         }
     }
+
+    #[tokio::test]
+    async fn test_recovery_with_corruption() {
+        use std::os::unix::fs::FileExt;
+
+        let dir = tempdir();
+
+        let holder = Arc::new(FlushHolder::default());
+        let cache = create_file_cache_manager_for_test(dir.path(), vec![holder.clone()]).await;
+
+        for i in 0..SHARDSU64 {
+            cache.insert(key(i), vec![b'x'; BS]).unwrap();
+        }
+        holder.trigger();
+        holder.wait().await;
+        cache.buffer_flusher_notifier.notify_one();
+        holder.trigger();
+        holder.wait().await;
+        assert_eq!(cache.store.cache_file_len(), SHARDS * BS);
+
+        let cache_file_path = cache.store.cache_file_path();
+        drop(cache);
+
+        // Corrupt the entry at the head of the cache file.
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(cache_file_path)
+            .unwrap();
+        file.write_all_at(&[b'y'; 16], 0).unwrap();
+        file.sync_all().unwrap();
+        drop(file);
+
+        // The corrupted entry is restored without reading it, and dropped when it's read.
+        let cache = create_file_cache_manager_for_test(dir.path(), vec![holder.clone()]).await;
+        assert_eq!(cache.metrics.restored_entries.get(), SHARDSU64);
+        assert_eq!(cache.metrics.corrupted_entries.get(), 0);
+
+        let mut hits = 0;
+        for i in 0..SHARDSU64 {
+            if let Some(value) = cache.get(&key(i)).await.unwrap() {
+                assert_eq!(&*value, &vec![b'x'; BS]);
+                hits += 1;
+            }
+        }
+        assert_eq!(hits, SHARDS - 1);
+        assert_eq!(cache.metrics.corrupted_entries.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_with_incompatible_meta_file() {
+        use std::os::unix::fs::FileExt;
+
+        let dir = tempdir();
+
+        let holder = Arc::new(FlushHolder::default());
+        let cache = create_file_cache_manager_for_test(dir.path(), vec![holder.clone()]).await;
+
+        for i in 0..SHARDSU64 {
+            cache.insert(key(i), vec![b'x'; BS]).unwrap();
+        }
+        holder.trigger();
+        holder.wait().await;
+        cache.buffer_flusher_notifier.notify_one();
+        holder.trigger();
+        holder.wait().await;
+        assert_eq!(cache.store.cache_file_len(), SHARDS * BS);
+
+        let meta_file_path = cache.store.meta_file_path();
+        drop(cache);
+
+        // Overwrite the header as if the meta file was written in another layout.
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(meta_file_path)
+            .unwrap();
+        file.write_all_at(&[0; 8], 0).unwrap();
+        file.sync_all().unwrap();
+        drop(file);
+
+        // The whole cache is dropped.
+        let cache = create_file_cache_manager_for_test(dir.path(), vec![holder.clone()]).await;
+        assert_eq!(cache.metrics.restored_entries.get(), 0);
+        assert_eq!(cache.store.cache_file_len(), 0);
+        for i in 0..SHARDSU64 {
+            assert_eq!(cache.get(&key(i)).await.unwrap().as_deref(), None);
+        }
+    }
 }
@@ -22,6 +22,12 @@ pub enum Error {
     UnsupportedFilesystem(i64),
     #[error("invalid slot: {0}")]
     InvalidSlot(usize),
+    #[error("checksum mismatch of slot {slot}: expected {expected}, found {found}")]
+    ChecksumMismatch {
+        slot: usize,
+        expected: u64,
+        found: u64,
+    },
     #[error("other error: {0}")]
     Other(String),
 }
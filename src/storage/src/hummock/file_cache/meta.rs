@@ -14,6 +14,7 @@
 
 use std::collections::VecDeque;
 use std::fs::OpenOptions;
+use std::io::Read;
 use std::marker::PhantomData;
 use std::mem::{forget, ManuallyDrop};
 use std::os::unix::prelude::{AsRawFd, RawFd};
@@ -39,6 +40,8 @@ pub struct BlockLoc {
     pub bidx: u32,
     /// data len in bytes
     pub len: u32,
+    /// xxhash64 checksum of the data, verified when the entry is restored or read
+    pub checksum: u64,
 }
 
 impl BlockLoc {
@@ -50,26 +53,41 @@ impl BlockLoc {
 
     #[inline(always)]
     pub fn encoded_len() -> usize {
-        8
+        16
     }
 
     #[inline(always)]
     fn encode(&self, mut buf: &mut [u8]) {
         buf.put_u32(self.bidx);
         buf.put_u32(self.len);
+        buf.put_u64(self.checksum);
     }
 
     #[inline(always)]
     fn decode(mut buf: &[u8]) -> Self {
         let bidx = buf.get_u32();
         let len = buf.get_u32();
-        Self { bidx, len }
+        let checksum = buf.get_u64();
+        Self {
+            bidx,
+            len,
+            checksum,
+        }
     }
 }
 
+/// Magic number at the beginning of the meta file, i.e. "RWFCMETA".
+const META_FILE_MAGIC: u64 = 0x5257_4643_4d45_5441;
+/// Version of the layout of the meta file, which must be bumped on any change of it.
+const META_FILE_VERSION: u32 = 1;
+/// `| magic (8B) | version (4B) | slot info len (4B) |`
+const META_FILE_HEADER_LEN: usize = 16;
+
 /// [`MetaFile`] is a memory mapped file to record the locations of the cache file entries.
 ///
-/// The entire file will be memory mapped to a memory buffer.
+/// The entire file will be memory mapped to a memory buffer. It begins with a header of the magic
+/// number, the layout version and the slot size, followed by the slots. Each slot is laid out as
+/// `| bidx (4B) | len (4B) | checksum (8B) | key |`, and a slot with `len == 0` is free.
 pub struct MetaFile<K>
 where
     K: TieredCacheKey,
@@ -112,8 +130,8 @@ where
         forget(file);
 
         let stat = fstat(fd)?;
-        let size = if stat.st_blocks == 0 {
-            // newly created
+        let created = stat.st_blocks == 0;
+        let size = if created {
             fallocate(fd, FallocateFlags::empty(), 0, fallocate_unit as i64)?;
             fallocate_unit
         } else {
@@ -150,6 +168,10 @@ where
             _phantom: PhantomData,
         };
 
+        if created {
+            Self::encode_header(&mut meta.buffer[..META_FILE_HEADER_LEN]);
+        }
+
         for slot in 0..meta.slots() {
             if !meta.is_slot_valid(slot) {
                 meta.free.push_back(slot);
@@ -159,6 +181,33 @@ where
         Ok(meta)
     }
 
+    /// Returns whether the meta file at `path` is written in the current layout, or doesn't exist
+    /// yet.
+    pub fn is_compatible(path: impl AsRef<Path>) -> Result<bool> {
+        let mut file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+            Err(e) => return Err(e.into()),
+        };
+        if file.metadata()?.len() == 0 {
+            return Ok(true);
+        }
+        let mut header = [0; META_FILE_HEADER_LEN];
+        let mut expected = [0; META_FILE_HEADER_LEN];
+        Self::encode_header(&mut expected);
+        match file.read_exact(&mut header) {
+            Ok(()) => Ok(header == expected),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn encode_header(mut buf: &mut [u8]) {
+        buf.put_u64(META_FILE_MAGIC);
+        buf.put_u32(META_FILE_VERSION);
+        buf.put_u32(Self::slot_info_len() as u32);
+    }
+
     pub fn insert(&mut self, key: &K, bloc: &BlockLoc) -> Result<usize> {
         assert_ne!(bloc.len, 0);
         if self.free.is_empty() {
@@ -166,7 +215,7 @@ where
         }
         let slot = self.free.pop_front().unwrap();
 
-        let mut cursor = Self::slot_info_len() * slot;
+        let mut cursor = Self::slot_offset(slot);
         bloc.encode(&mut self.buffer[cursor..cursor + BlockLoc::encoded_len()]);
         cursor += BlockLoc::encoded_len();
         key.encode(&mut self.buffer[cursor..cursor + K::encoded_len()]);
@@ -176,10 +225,10 @@ where
 
     pub fn free(&mut self, slot: SlotId) -> Option<BlockLoc> {
         debug_assert!(
-            (slot + 1) * Self::slot_info_len() <= self.size,
+            Self::slot_offset(slot + 1) <= self.size,
             "slot: {}, offset: {}, size: {}",
             slot,
-            slot * Self::slot_info_len(),
+            Self::slot_offset(slot),
             self.size
         );
         if !self.is_slot_valid(slot) {
@@ -187,8 +236,8 @@ where
         }
 
         let bloc = BlockLoc::decode(
-            &self.buffer[Self::slot_info_len() * slot
-                ..Self::slot_info_len() * slot + BlockLoc::encoded_len()],
+            &self.buffer
+                [Self::slot_offset(slot)..Self::slot_offset(slot) + BlockLoc::encoded_len()],
         );
 
         self.invalidate_slot(slot);
@@ -199,17 +248,17 @@ where
 
     pub fn get(&self, slot: SlotId) -> Option<(BlockLoc, K)> {
         debug_assert!(
-            (slot + 1) * Self::slot_info_len() <= self.size,
+            Self::slot_offset(slot + 1) <= self.size,
             "slot: {}, offset: {}, size: {}",
             slot,
-            slot * Self::slot_info_len(),
+            Self::slot_offset(slot),
             self.size
         );
         if !self.is_slot_valid(slot) {
             return None;
         }
 
-        let mut cursor = Self::slot_info_len() * slot;
+        let mut cursor = Self::slot_offset(slot);
 
         let bloc = BlockLoc::decode(&self.buffer[cursor..cursor + BlockLoc::encoded_len()]);
         cursor += BlockLoc::encoded_len();
@@ -223,18 +272,27 @@ where
         self.size
     }
 
+    /// Schedules write-back of the memory mapped buffer, so that the slots survive a crash of the
+    /// process without waiting for [`MetaFile`] to be dropped.
+    pub fn flush(&self) -> Result<()> {
+        unsafe { msync(self.ptr as *mut c_void, self.size, MsFlags::MS_ASYNC)? };
+        Ok(())
+    }
+
     pub fn slots(&self) -> usize {
-        self.size / Self::slot_info_len()
+        Self::slots_of(self.size)
+    }
+
+    fn slots_of(size: usize) -> usize {
+        (size - META_FILE_HEADER_LEN) / Self::slot_info_len()
     }
 
     fn is_slot_valid(&self, slot: SlotId) -> bool {
-        (&self.buffer[slot * Self::slot_info_len() + 4..slot * Self::slot_info_len() + 8]).get_u32()
-            != 0
+        (&self.buffer[Self::slot_offset(slot) + 4..Self::slot_offset(slot) + 8]).get_u32() != 0
     }
 
     fn invalidate_slot(&mut self, slot: SlotId) {
-        (&mut self.buffer[slot * Self::slot_info_len() + 4..slot * Self::slot_info_len() + 8])
-            .put_u32(0);
+        (&mut self.buffer[Self::slot_offset(slot) + 4..Self::slot_offset(slot) + 8]).put_u32(0);
     }
 
     #[inline(always)]
@@ -242,6 +300,11 @@ where
         BlockLoc::encoded_len() + K::encoded_len()
     }
 
+    #[inline(always)]
+    fn slot_offset(slot: SlotId) -> usize {
+        META_FILE_HEADER_LEN + Self::slot_info_len() * slot
+    }
+
     fn grow(&mut self) -> Result<()> {
         let old_size = self.size;
         let new_size = old_size + self.fallocate_unit;
@@ -267,7 +330,7 @@ where
             (ptr, buffer)
         };
 
-        for slot in (old_size / Self::slot_info_len())..(new_size / Self::slot_info_len()) {
+        for slot in Self::slots_of(old_size)..Self::slots_of(new_size) {
             self.free.push_back(slot);
         }
 
@@ -305,14 +368,18 @@ mod tests {
     fn test_enc_dec() {
         let dir = tempfile::tempdir().unwrap();
         let mut map = HashMap::new();
-        let slot_info_len = MetaFile::<TestCacheKey>::slot_info_len();
 
         let mut mf: MetaFile<TestCacheKey> =
             MetaFile::open(dir.path().join("test-meta"), FALLOCATE_UNIT).unwrap();
         assert_eq!(mf.size(), FALLOCATE_UNIT);
+        let slots = mf.slots();
 
-        let bloc = BlockLoc { bidx: 1, len: 2 };
-        let key = TestCacheKey(3);
+        let bloc = BlockLoc {
+            bidx: 1,
+            len: 2,
+            checksum: 3,
+        };
+        let key = TestCacheKey(4);
         let slot = mf.insert(&key, &bloc).unwrap();
         let (bloc0, key0) = mf.get(slot).unwrap();
         assert_eq!(bloc0, bloc);
@@ -327,12 +394,13 @@ mod tests {
 
         map.insert(slot, (key, bloc));
 
-        for (i, _) in (0..mf.size).step_by(slot_info_len).enumerate() {
-            let i = i + 1;
+        // One more entry than the remaining slots grows the meta file.
+        for i in 1..=slots {
             let key = TestCacheKey(i as u64);
             let bloc = BlockLoc {
                 bidx: i as u32 * 2,
                 len: i as u32 * 3,
+                checksum: i as u64 * 5,
             };
             let slot = mf.insert(&key, &bloc).unwrap();
             map.insert(slot, (key, bloc));
@@ -344,7 +412,7 @@ mod tests {
             assert_eq!(gkey, *key);
         }
 
-        for slot in (FALLOCATE_UNIT / slot_info_len + 1)..(FALLOCATE_UNIT * 2 / slot_info_len) {
+        for slot in (slots + 1)..mf.slots() {
             assert_eq!(mf.get(slot), None);
         }
 
@@ -353,9 +421,27 @@ mod tests {
             assert_eq!(mf.free(slot), None);
         }
 
-        for slot in 0..(FALLOCATE_UNIT * 2 / slot_info_len) {
+        for slot in 0..mf.slots() {
             assert_eq!(mf.get(slot), None);
         }
-        assert_eq!(mf.free.len(), FALLOCATE_UNIT * 2 / slot_info_len);
+        assert_eq!(mf.free.len(), mf.slots());
+    }
+
+    #[test]
+    fn test_compatibility() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test-meta");
+        assert!(MetaFile::<TestCacheKey>::is_compatible(&path).unwrap());
+
+        let mf: MetaFile<TestCacheKey> = MetaFile::open(&path, FALLOCATE_UNIT).unwrap();
+        drop(mf);
+        assert!(MetaFile::<TestCacheKey>::is_compatible(&path).unwrap());
+
+        // The meta file written in the layout without the header, whose first slot is taken as
+        // the header.
+        let mut buf = std::fs::read(&path).unwrap();
+        (&mut buf[..META_FILE_HEADER_LEN]).put_u64(1);
+        std::fs::write(&path, buf).unwrap();
+        assert!(!MetaFile::<TestCacheKey>::is_compatible(&path).unwrap());
     }
 }
@@ -16,12 +16,17 @@ use std::sync::Arc;
 
 use prometheus::{
     register_counter_vec_with_registry, register_histogram_vec_with_registry,
-    register_int_counter_with_registry, Counter, Histogram, IntCounter, Registry,
+    register_int_counter_with_registry, Counter, Histogram, HistogramOpts, IntCounter, Opts,
+    Registry,
 };
 
 pub struct FileCacheMetrics {
+    pub cache_hit: IntCounter,
     pub cache_miss: IntCounter,
 
+    pub restored_entries: IntCounter,
+    pub corrupted_entries: IntCounter,
+
     pub disk_read_bytes: Counter,
     pub disk_read_latency: Histogram,
     pub disk_write_bytes: Counter,
@@ -35,56 +40,82 @@ pub struct FileCacheMetrics {
 }
 
 impl FileCacheMetrics {
-    pub fn new(registry: Registry) -> Self {
+    /// Registers the metrics of the file cache named `cache`. Multiple file caches can share the
+    /// same registry as long as their names differ.
+    pub fn new(registry: Registry, cache: &str) -> Self {
         let latency = register_histogram_vec_with_registry!(
-            "file_cache_latency",
-            "file cache latency",
+            HistogramOpts::new("file_cache_latency", "file cache latency")
+                .const_label("cache", cache)
+                .buckets(vec![
+                    0.0001, 0.001, 0.005, 0.01, 0.02, 0.03, 0.04, 0.05, 0.075, 0.1, 0.25, 0.5,
+                    0.75, 1.0
+                ]),
             &["op"],
-            vec![
-                0.0001, 0.001, 0.005, 0.01, 0.02, 0.03, 0.04, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75,
-                1.0
-            ],
             registry,
         )
         .unwrap();
         let disk_throughput = register_counter_vec_with_registry!(
-            "file_cache_disk_bytes",
-            "file cache disk bytes",
+            Opts::new("file_cache_disk_bytes", "file cache disk bytes").const_label("cache", cache),
             &["op"],
             registry,
         )
         .unwrap();
         let disk_latency = register_histogram_vec_with_registry!(
-            "file_cache_disk_latency",
-            "file cache disk latency",
+            HistogramOpts::new("file_cache_disk_latency", "file cache disk latency")
+                .const_label("cache", cache)
+                .buckets(vec![
+                    0.0001, 0.001, 0.005, 0.01, 0.02, 0.03, 0.04, 0.05, 0.075, 0.1, 0.25, 0.5,
+                    0.75, 1.0
+                ]),
             &["op"],
-            vec![
-                0.0001, 0.001, 0.005, 0.01, 0.02, 0.03, 0.04, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75,
-                1.0
-            ],
             registry,
         )
         .unwrap();
         let disk_io_size = register_histogram_vec_with_registry!(
-            "file_cache_disk_io_size",
-            "file cache disk io size",
+            HistogramOpts::new("file_cache_disk_io_size", "file cache disk io size")
+                .const_label("cache", cache)
+                .buckets(vec![
+                    0.1 * 1024.0 * 1024.0,
+                    0.5 * 1024.0 * 1024.0,
+                    1.0 * 1024.0 * 1024.0,
+                    2.0 * 1024.0 * 1024.0,
+                    4.0 * 1024.0 * 1024.0,
+                    8.0 * 1024.0 * 1024.0,
+                    16.0 * 1024.0 * 1024.0,
+                    64.0 * 1024.0 * 1024.0,
+                ]),
             &["op"],
-            vec![
-                0.1 * 1024.0 * 1024.0,
-                0.5 * 1024.0 * 1024.0,
-                1.0 * 1024.0 * 1024.0,
-                2.0 * 1024.0 * 1024.0,
-                4.0 * 1024.0 * 1024.0,
-                8.0 * 1024.0 * 1024.0,
-                16.0 * 1024.0 * 1024.0,
-                64.0 * 1024.0 * 1024.0,
-            ],
             registry,
         )
         .unwrap();
-        let cache_miss =
-            register_int_counter_with_registry!("file_cache_miss", "file cache miss", registry)
-                .unwrap();
+        let cache_hit = register_int_counter_with_registry!(
+            Opts::new("file_cache_hit", "file cache hit").const_label("cache", cache),
+            registry
+        )
+        .unwrap();
+        let cache_miss = register_int_counter_with_registry!(
+            Opts::new("file_cache_miss", "file cache miss").const_label("cache", cache),
+            registry
+        )
+        .unwrap();
+        let restored_entries = register_int_counter_with_registry!(
+            Opts::new(
+                "file_cache_restored_entries",
+                "file cache entries restored from disk on startup"
+            )
+            .const_label("cache", cache),
+            registry
+        )
+        .unwrap();
+        let corrupted_entries = register_int_counter_with_registry!(
+            Opts::new(
+                "file_cache_corrupted_entries",
+                "file cache entries dropped for failing checksum validation"
+            )
+            .const_label("cache", cache),
+            registry
+        )
+        .unwrap();
         let disk_read_throughput = disk_throughput
             .get_metric_with_label_values(&["read"])
             .unwrap();
@@ -108,7 +139,10 @@ impl FileCacheMetrics {
         let get_latency = latency.get_metric_with_label_values(&["get"]).unwrap();
 
         Self {
+            cache_hit,
             cache_miss,
+            restored_entries,
+            corrupted_entries,
             disk_read_bytes: disk_read_throughput,
             disk_read_latency,
             disk_write_bytes: disk_write_throughput,
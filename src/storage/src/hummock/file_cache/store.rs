@@ -93,11 +93,6 @@ where
     pub fn append<'b>(&'b mut self, key: K, value: &V) {
         let offset = self.data_len;
         let len = value.encoded_len();
-        let bloc = BlockLoc {
-            bidx: offset as u32 / self.block_size as u32,
-            len: len as u32,
-        };
-        self.blocs.push(bloc);
 
         let rotate_last_mut = |buffers: &'b mut Vec<_>| {
             buffers.push(DioBuffer::with_capacity_in(
@@ -122,8 +117,14 @@ where
             buffer.set_len(buffer_offset + buffer_len);
         }
         value.encode(&mut buffer[buffer_offset..buffer_offset + buffer_len]);
+        let checksum = utils::checksum(&buffer[buffer_offset..buffer_offset + len]);
         self.data_len += buffer_len;
 
+        self.blocs.push(BlockLoc {
+            bidx: offset as u32 / self.block_size as u32,
+            len: len as u32,
+            checksum,
+        });
         self.keys.push(key);
     }
 
@@ -203,6 +204,7 @@ where
         for (key, bloc) in self.keys.iter().zip_eq(self.blocs.iter()) {
             slots.push(guard.insert(key, bloc)?);
         }
+        guard.flush()?;
 
         Ok((self.keys, slots))
     }
@@ -273,10 +275,22 @@ where
             fallocate_unit: options.cache_file_fallocate_unit,
         };
 
-        let mf = MetaFile::open(
-            PathBuf::from(&options.dir).join(META_FILE_FILENAME),
-            options.cache_meta_fallocate_unit,
-        )?;
+        // The entries written in another format can't be read, so the whole cache is dropped.
+        let mf_path = PathBuf::from(&options.dir).join(META_FILE_FILENAME);
+        if !MetaFile::<K>::is_compatible(&mf_path)? {
+            tracing::warn!(
+                "drop file cache {} written in an incompatible format",
+                options.dir
+            );
+            for filename in [META_FILE_FILENAME, CACHE_FILE_FILENAME] {
+                match std::fs::remove_file(PathBuf::from(&options.dir).join(filename)) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                    _ => {}
+                }
+            }
+        }
+
+        let mf = MetaFile::open(mf_path, options.cache_meta_fallocate_unit)?;
 
         let cf = CacheFile::open(
             PathBuf::from(&options.dir).join(CACHE_FILE_FILENAME),
@@ -338,6 +352,11 @@ where
         PathBuf::from(&self.dir).join(CACHE_FILE_FILENAME)
     }
 
+    /// Rebuilds `indices` from the slots persisted in the meta file.
+    ///
+    /// Only the meta file is read. Entries out of the range of the cache file are freed, and the
+    /// other ones corrupted (e.g. written partially before a crash) are dropped when their
+    /// checksums mismatch on reading.
     pub async fn restore<S: HashBuilder>(
         &self,
         indices: &Arc<LruCache<K, SlotId>>,
@@ -345,17 +364,43 @@ where
     ) -> Result<()> {
         let slots = self.meta_file.read().await.slots();
 
+        let mut invalid_slots = vec![];
+
         for slot in 0..slots {
             // Wrap the read guard, or there will be deadlock when evicting entries.
             let res = { self.meta_file.read().await.get(slot) };
             if let Some((block_loc, key)) = res {
+                let offset = block_loc.bidx as usize * self.block_size;
+                let blen = block_loc.blen(self.block_size as u32) as usize;
+                if offset + blen > self.cache_file.len() {
+                    invalid_slots.push(slot);
+                    continue;
+                }
                 indices.insert(
                     key.clone(),
                     hash_builder.hash_one(&key),
                     utils::align_up(self.block_size, block_loc.len as usize),
                     slot,
                 );
+                self.metrics.restored_entries.inc();
+            }
+        }
+
+        if !invalid_slots.is_empty() {
+            tracing::warn!(
+                "drop {} invalid entries when restoring file cache {}",
+                invalid_slots.len(),
+                self.dir
+            );
+            self.metrics
+                .corrupted_entries
+                .inc_by(invalid_slots.len() as u64);
+
+            let mut guard = self.meta_file.write().await;
+            for slot in invalid_slots {
+                guard.free(slot);
             }
+            guard.flush()?;
         }
 
         Ok(())
@@ -392,7 +437,17 @@ where
 
         drop(guard);
 
-        Ok(buf[..bloc.len as usize].to_vec())
+        let data = &buf[..bloc.len as usize];
+        let checksum = utils::checksum(data);
+        if checksum != bloc.checksum {
+            return Err(Error::ChecksumMismatch {
+                slot,
+                expected: bloc.checksum,
+                found: checksum,
+            });
+        }
+
+        Ok(data.to_vec())
     }
 
     pub fn erase(&self, slot: SlotId) -> Result<()> {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::fmt::{Debug, Display};
+use std::hash::Hasher;
 use std::ops::{Add, BitAnd, Not, Sub};
 
 pub trait UnsignedTrait = Add<Output = Self>
@@ -76,6 +77,14 @@ pub fn align_down<U: Unsigned>(align: U, v: U) -> U {
     v & !(align - U::from(1))
 }
 
+/// Computes the checksum of a cache entry, which is persisted in the meta file along with its
+/// location.
+pub fn checksum(data: &[u8]) -> u64 {
+    let mut hasher = twox_hash::XxHash64::with_seed(0);
+    hasher.write(data);
+    hasher.finish()
+}
+
 macro_rules! bpf_buffer_trace {
     ($buf:expr, $span:expr) => {
         #[cfg(feature = "bpf")]
@@ -68,6 +68,7 @@ pub fn mock_sstable_store_with_object_store(store: ObjectStoreRef) -> SstableSto
         64 << 20,
         64 << 20,
         TieredCache::none(),
+        TieredCache::none(),
     ))
 }
 
@@ -389,6 +389,23 @@ pub fn hit_sstable_bloom_filter(
     !surely_not_have
 }
 
+/// Checks the prefix bloom filter of an sstable for an iterator with a fixed `prefix_hint`, which
+/// is counted separately from the point gets.
+pub fn hit_sstable_prefix_bloom_filter(
+    sstable_info_ref: &Sstable,
+    prefix_key: &[u8],
+    local_stats: &mut StoreLocalStatistic,
+) -> bool {
+    local_stats.iter_bloom_filter_check_counts += 1;
+    let surely_not_have = sstable_info_ref.surely_not_have_user_key(prefix_key);
+
+    if surely_not_have {
+        local_stats.iter_bloom_filter_true_negative_count += 1;
+    }
+
+    !surely_not_have
+}
+
 /// Get `user_value` from `OrderSortedUncommittedData`. If not get successful, return None.
 pub async fn get_from_order_sorted_uncommitted_data(
     sstable_store_ref: SstableStoreRef,
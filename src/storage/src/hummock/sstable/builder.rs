@@ -113,6 +113,8 @@ pub struct SstableBuilder<W: SstableWriter> {
     stale_key_count: u64,
     /// `total_key_count` counts range_tombstones as well.
     total_key_count: u64,
+    /// Number of keys with a delete tombstone as value.
+    delete_key_count: u64,
     /// Per table stats.
     table_stats: TableStatsMap,
     /// `last_table_stats` accumulates stats for `last_table_id` and finalizes it in `table_stats`
@@ -159,6 +161,7 @@ impl<W: SstableWriter> SstableBuilder<W> {
             last_bloom_filter_key_length: 0,
             stale_key_count: 0,
             total_key_count: 0,
+            delete_key_count: 0,
             table_stats: Default::default(),
             last_table_stats: Default::default(),
         }
@@ -221,6 +224,9 @@ impl<W: SstableWriter> SstableBuilder<W> {
         } else {
             self.stale_key_count += 1;
         }
+        if value.is_delete() {
+            self.delete_key_count += 1;
+        }
         self.total_key_count += 1;
         self.last_table_stats.total_key_count += 1;
 
@@ -328,6 +334,8 @@ impl<W: SstableWriter> SstableBuilder<W> {
             stale_key_count: self.stale_key_count,
             total_key_count: self.total_key_count,
             divide_version: 0,
+            delete_key_count: self.delete_key_count,
+            range_tombstone_count: meta.range_tombstone_list.len() as u64,
         };
         tracing::trace!(
             "meta_size {} bloom_filter_size {}  add_key_counts {} ",
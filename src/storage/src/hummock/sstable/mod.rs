@@ -177,6 +177,8 @@ impl Sstable {
             stale_key_count: 0,
             total_key_count: self.meta.key_count as u64,
             divide_version: 0,
+            delete_key_count: 0,
+            range_tombstone_count: self.meta.range_tombstone_list.len() as u64,
         }
     }
 }
@@ -82,6 +82,43 @@ impl TieredCacheValue for Box<Block> {
     }
 }
 
+impl TieredCacheKey for HummockSstableId {
+    fn encoded_len() -> usize {
+        8
+    }
+
+    fn encode(&self, mut buf: &mut [u8]) {
+        buf.put_u64(*self);
+    }
+
+    fn decode(mut buf: &[u8]) -> Self {
+        buf.get_u64()
+    }
+}
+
+impl TieredCacheValue for Box<Sstable> {
+    fn len(&self) -> usize {
+        self.estimate_size()
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.estimate_size()
+    }
+
+    fn encode(&self, mut buf: &mut [u8]) {
+        buf.put_u64(self.id);
+        buf.put_slice(&self.meta.encode_to_bytes());
+    }
+
+    fn decode(buf: Vec<u8>) -> Self {
+        let mut buf = &buf[..];
+        let id = buf.get_u64();
+        // The entry has been validated by the tiered cache, so the meta is intact here.
+        let meta = SstableMeta::decode(&mut buf).expect("decode sstable meta from tiered cache");
+        Box::new(Sstable::new(id, meta))
+    }
+}
+
 pub struct BlockCacheEventListener {
     tiered_cache: TieredCache<(HummockSstableId, u64), Box<Block>>,
 }
@@ -96,6 +133,26 @@ impl LruCacheEventListener for BlockCacheEventListener {
     }
 }
 
+pub struct MetaCacheEventListener {
+    tiered_cache: TieredCache<HummockSstableId, Box<Sstable>>,
+}
+
+impl LruCacheEventListener for MetaCacheEventListener {
+    type K = HummockSstableId;
+    type T = Box<Sstable>;
+
+    fn on_release(&self, key: Self::K, value: Self::T) {
+        // The meta is only dropped from the cache on failure, and fetched again when needed.
+        if let Err(e) = self.tiered_cache.insert(key, value) {
+            tracing::warn!(
+                "failed to insert meta of sst {} to tiered cache: {}",
+                key,
+                e
+            );
+        }
+    }
+}
+
 // END section for tiered cache
 
 // TODO: Define policy based on use cases (read / compaction / ...).
@@ -115,6 +172,7 @@ pub struct SstableStore {
     block_cache: BlockCache,
     meta_cache: Arc<LruCache<HummockSstableId, Box<Sstable>>>,
     tiered_cache: TieredCache<(HummockSstableId, u64), Box<Block>>,
+    meta_tiered_cache: TieredCache<HummockSstableId, Box<Sstable>>,
 }
 
 impl SstableStore {
@@ -124,12 +182,19 @@ impl SstableStore {
         block_cache_capacity: usize,
         meta_cache_capacity: usize,
         tiered_cache: TieredCache<(HummockSstableId, u64), Box<Block>>,
+        meta_tiered_cache: TieredCache<HummockSstableId, Box<Sstable>>,
     ) -> Self {
         let mut shard_bits = MAX_META_CACHE_SHARD_BITS;
         while (meta_cache_capacity >> shard_bits) < MIN_BUFFER_SIZE_PER_SHARD && shard_bits > 0 {
             shard_bits -= 1;
         }
-        let meta_cache = Arc::new(LruCache::new(shard_bits, meta_cache_capacity));
+        let meta_cache = Arc::new(LruCache::with_event_listener(
+            shard_bits,
+            meta_cache_capacity,
+            Arc::new(MetaCacheEventListener {
+                tiered_cache: meta_tiered_cache.clone(),
+            }),
+        ));
         let listener = Arc::new(BlockCacheEventListener {
             tiered_cache: tiered_cache.clone(),
         });
@@ -144,6 +209,7 @@ impl SstableStore {
             ),
             meta_cache,
             tiered_cache,
+            meta_tiered_cache,
         }
     }
 
@@ -157,12 +223,14 @@ impl SstableStore {
     ) -> Self {
         let meta_cache = Arc::new(LruCache::new(0, meta_cache_capacity));
         let tiered_cache = TieredCache::none();
+        let meta_tiered_cache = TieredCache::none();
         Self {
             path,
             store,
             block_cache: BlockCache::new(block_cache_capacity, 0),
             meta_cache,
             tiered_cache,
+            meta_tiered_cache,
         }
     }
 
@@ -171,7 +239,7 @@ impl SstableStore {
         self.store
             .delete(self.get_sst_data_path(sst_id).as_str())
             .await?;
-        self.meta_cache.erase(sst_id, &sst_id);
+        self.delete_cache(sst_id);
         Ok(())
     }
 
@@ -187,7 +255,7 @@ impl SstableStore {
 
         // Delete from cache.
         for &sst_id in sst_id_list {
-            self.meta_cache.erase(sst_id, &sst_id);
+            self.delete_cache(sst_id);
         }
 
         Ok(())
@@ -195,6 +263,14 @@ impl SstableStore {
 
     pub fn delete_cache(&self, sst_id: HummockSstableId) {
         self.meta_cache.erase(sst_id, &sst_id);
+        // The erased meta is moved to the tiered cache by the event listener, so erase it after.
+        if let Err(e) = self.meta_tiered_cache.erase(&sst_id) {
+            tracing::warn!(
+                "erase sstable meta {} from tiered cache failed: {}",
+                sst_id,
+                e
+            );
+        }
     }
 
     async fn put_sst_data(&self, sst_id: HummockSstableId, data: Bytes) -> HummockResult<()> {
@@ -332,6 +408,7 @@ impl SstableStore {
         self.meta_cache
             .lookup_with_request_dedup::<_, HummockError, _>(sst_id, sst_id, || {
                 let store = self.store.clone();
+                let meta_tiered_cache = self.meta_tiered_cache.clone();
                 let meta_path = self.get_sst_data_path(sst_id);
                 stats.cache_meta_block_miss += 1;
                 let stats_ptr = stats.remote_io_time.clone();
@@ -340,6 +417,16 @@ impl SstableStore {
                     size: (sst.file_size - sst.meta_offset) as usize,
                 };
                 async move {
+                    if let Some(holder) = meta_tiered_cache
+                        .get(&sst_id)
+                        .await
+                        .map_err(HummockError::tiered_cache)?
+                    {
+                        let sst = holder.into_owned();
+                        let charge = sst.meta.encoded_size();
+                        return Ok((sst, charge));
+                    }
+
                     let now = minstant::Instant::now();
                     let buf = store
                         .read(&meta_path, Some(loc))
@@ -680,7 +767,7 @@ mod tests {
         default_builder_opt_for_test, gen_test_sstable_data, put_sst,
     };
     use crate::hummock::value::HummockValue;
-    use crate::hummock::{CachePolicy, SstableIterator, SstableMeta};
+    use crate::hummock::{CachePolicy, Sstable, SstableIterator, SstableMeta, TieredCacheValue};
     use crate::monitor::StoreLocalStatistic;
 
     const SST_ID: HummockSstableId = 1;
@@ -782,4 +869,20 @@ mod tests {
         assert_eq!(data_path, "test/123.data");
         assert_eq!(sstable_store.get_sst_id_from_path(&data_path), sst_id);
     }
+
+    #[tokio::test]
+    async fn test_tiered_cache_sstable_enc_dec() {
+        let (_, meta) = gen_test_sstable_data(
+            default_builder_opt_for_test(),
+            (0..100).map(|x| (iterator_test_key_of(x), get_hummock_value(x))),
+        )
+        .await;
+        let sst = Box::new(Sstable::new(SST_ID, meta));
+
+        let mut buf = vec![0; sst.encoded_len()];
+        sst.encode(&mut buf);
+        let decoded = <Box<Sstable> as TieredCacheValue>::decode(buf);
+        assert_eq!(decoded.id, SST_ID);
+        assert_eq!(decoded.meta, sst.meta);
+    }
 }
@@ -41,7 +41,7 @@ use super::iterator::{
 };
 use super::utils::validate_epoch;
 use super::{
-    get_from_order_sorted_uncommitted_data, get_from_sstable_info, hit_sstable_bloom_filter,
+    get_from_order_sorted_uncommitted_data, get_from_sstable_info, hit_sstable_prefix_bloom_filter,
     HummockStorageV1, SstableIteratorType,
 };
 use crate::error::{StorageError, StorageResult};
@@ -336,7 +336,7 @@ impl HummockStorageV1 {
                             .in_span(Span::enter_with_local_parent("get_sstable"))
                             .await?;
 
-                        if hit_sstable_bloom_filter(
+                        if hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             bloom_filter_key.as_slice(),
                             &mut local_stats,
@@ -364,7 +364,7 @@ impl HummockStorageV1 {
                         .in_span(Span::enter_with_local_parent("get_sstable"))
                         .await?;
                     if let Some(bloom_filter_key) = bloom_filter_key.as_ref()
-                        && !hit_sstable_bloom_filter(
+                        && !hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             bloom_filter_key.as_slice(),
                             &mut local_stats,
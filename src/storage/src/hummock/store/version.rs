@@ -48,7 +48,7 @@ use crate::hummock::utils::{
     check_subset_preserve_order, filter_single_sst, prune_ssts, range_overlap, search_sst_idx,
 };
 use crate::hummock::{
-    get_from_batch, get_from_sstable_info, hit_sstable_bloom_filter, DeleteRangeAggregator,
+    get_from_batch, get_from_sstable_info, hit_sstable_prefix_bloom_filter, DeleteRangeAggregator,
     SstableDeleteRangeIterator, SstableIterator,
 };
 use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
@@ -552,7 +552,7 @@ impl HummockVersionReader {
                 .in_span(Span::enter_with_local_parent("get_sstable"))
                 .await?;
             if let Some(bloom_filter_key) = bloom_filter_key.as_ref() {
-                if !hit_sstable_bloom_filter(
+                if !hit_sstable_prefix_bloom_filter(
                     table_holder.value(),
                     bloom_filter_key.as_slice(),
                     &mut local_stats,
@@ -626,7 +626,7 @@ impl HummockVersionReader {
                         .in_span(Span::enter_with_local_parent("get_sstable"))
                         .await?;
                     if let Some(bloom_filter_key) = read_options.prefix_hint.as_ref() {
-                        if !hit_sstable_bloom_filter(
+                        if !hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             UserKey::new(read_options.table_id, TableKey(bloom_filter_key))
                                 .encode()
@@ -668,7 +668,7 @@ impl HummockVersionReader {
                         .in_span(Span::enter_with_local_parent("get_sstable"))
                         .await?;
                     if let Some(bloom_filter_key) = bloom_filter_key.as_ref()
-                        && !hit_sstable_bloom_filter(
+                        && !hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             bloom_filter_key.as_slice(),
                             &mut local_stats,
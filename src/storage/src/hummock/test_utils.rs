@@ -110,6 +110,8 @@ pub fn gen_dummy_sst_info(
         stale_key_count: 0,
         total_key_count: 0,
         divide_version: 0,
+        delete_key_count: 0,
+        range_tombstone_count: 0,
     }
 }
 
@@ -182,6 +184,8 @@ pub async fn put_sst(
         stale_key_count: 0,
         total_key_count: 0,
         divide_version: 0,
+        delete_key_count: 0,
+        range_tombstone_count: 0,
     };
     let writer_output = writer.finish(meta).await?;
     writer_output.await.unwrap()?;
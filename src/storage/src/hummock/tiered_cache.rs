@@ -130,8 +130,8 @@ impl TieredCacheMetricsBuilder {
     }
 
     #[cfg(target_os = "linux")]
-    pub fn file(self) -> file_cache::metrics::FileCacheMetrics {
-        file_cache::metrics::FileCacheMetrics::new(self.0.unwrap())
+    pub fn file(&self, cache: &str) -> file_cache::metrics::FileCacheMetrics {
+        file_cache::metrics::FileCacheMetrics::new(self.0.clone().unwrap(), cache)
     }
 }
 
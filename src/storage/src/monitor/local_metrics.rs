@@ -35,6 +35,8 @@ pub struct StoreLocalStatistic {
     pub remote_io_time: Arc<AtomicU64>,
     pub bloom_filter_check_counts: u64,
     pub get_shared_buffer_hit_counts: u64,
+    pub iter_bloom_filter_true_negative_count: u64,
+    pub iter_bloom_filter_check_counts: u64,
 
     #[cfg(all(debug_assertions, not(any(test, feature = "test"))))]
     reported: AtomicBool,
@@ -61,6 +63,8 @@ impl StoreLocalStatistic {
         self.bloom_filter_check_counts += other.bloom_filter_check_counts;
         self.total_key_count += other.total_key_count;
         self.get_shared_buffer_hit_counts += other.get_shared_buffer_hit_counts;
+        self.iter_bloom_filter_true_negative_count += other.iter_bloom_filter_true_negative_count;
+        self.iter_bloom_filter_check_counts += other.iter_bloom_filter_check_counts;
 
         #[cfg(all(debug_assertions, not(any(test, feature = "test"))))]
         if other.added.fetch_or(true, Ordering::Relaxed) || other.reported.load(Ordering::Relaxed) {
@@ -113,6 +117,19 @@ impl StoreLocalStatistic {
                 .bloom_filter_check_counts
                 .inc_by(self.bloom_filter_check_counts);
         }
+
+        if self.iter_bloom_filter_true_negative_count > 0 {
+            metrics
+                .iter_bloom_filter_true_negative_counts
+                .inc_by(self.iter_bloom_filter_true_negative_count);
+        }
+
+        if self.iter_bloom_filter_check_counts > 0 {
+            metrics
+                .iter_bloom_filter_check_counts
+                .inc_by(self.iter_bloom_filter_check_counts);
+        }
+
         if self.processed_key_count > 0 {
             metrics
                 .iter_scan_key_counts
@@ -170,6 +187,8 @@ impl StoreLocalStatistic {
             || self.bloom_filter_true_negative_count != 0
             || self.remote_io_time.load(Ordering::Relaxed) != 0
             || self.bloom_filter_check_counts != 0
+            || self.iter_bloom_filter_true_negative_count != 0
+            || self.iter_bloom_filter_check_counts != 0
     }
 }
 
@@ -35,6 +35,8 @@ macro_rules! for_all_metrics {
 
             bloom_filter_true_negative_counts: GenericCounter<AtomicU64>,
             bloom_filter_check_counts: GenericCounter<AtomicU64>,
+            iter_bloom_filter_true_negative_counts: GenericCounter<AtomicU64>,
+            iter_bloom_filter_check_counts: GenericCounter<AtomicU64>,
 
             range_scan_size: Histogram,
             range_scan_duration: Histogram,
@@ -152,6 +154,20 @@ impl StateStoreMetrics {
         )
         .unwrap();
 
+        let iter_bloom_filter_true_negative_counts = register_int_counter_with_registry!(
+            "state_store_iter_bloom_filter_true_negative_counts",
+            "Total number of sstables skipped by prefix bloom filters in iterators",
+            registry
+        )
+        .unwrap();
+
+        let iter_bloom_filter_check_counts = register_int_counter_with_registry!(
+            "state_store_iter_bloom_filter_check_counts",
+            "Total number of sstables checked by prefix bloom filters in iterators",
+            registry
+        )
+        .unwrap();
+
         // ----- range_scan -----
         let opts = histogram_opts!(
             "state_store_range_scan_size",
@@ -431,6 +447,8 @@ impl StateStoreMetrics {
 
             bloom_filter_true_negative_counts,
             bloom_filter_check_counts,
+            iter_bloom_filter_true_negative_counts,
+            iter_bloom_filter_check_counts,
 
             range_scan_size,
             range_scan_duration,
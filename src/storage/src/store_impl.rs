@@ -472,18 +472,20 @@ impl StateStoreImpl {
         tracing: Arc<risingwave_tracing::RwTracingService>,
     ) -> StorageResult<Self> {
         #[cfg(not(target_os = "linux"))]
-        let tiered_cache = TieredCache::none();
+        let (tiered_cache, meta_tiered_cache) = (TieredCache::none(), TieredCache::none());
 
         #[cfg(target_os = "linux")]
-        let tiered_cache = if file_cache_dir.is_empty() {
-            TieredCache::none()
+        let (tiered_cache, meta_tiered_cache) = if file_cache_dir.is_empty() {
+            (TieredCache::none(), TieredCache::none())
         } else {
+            use std::path::Path;
+
             use crate::hummock::file_cache::cache::FileCacheOptions;
             use crate::hummock::HummockError;
 
-            let options = FileCacheOptions {
-                dir: file_cache_dir.to_string(),
-                capacity: config.file_cache.capacity_mb * 1024 * 1024,
+            let file_cache_options = |dir: &Path, capacity_mb: usize| FileCacheOptions {
+                dir: dir.to_string_lossy().to_string(),
+                capacity: capacity_mb * 1024 * 1024,
                 total_buffer_capacity: config.file_cache.total_buffer_capacity_mb * 1024 * 1024,
                 cache_file_fallocate_unit: config.file_cache.cache_file_fallocate_unit_mb
                     * 1024
@@ -496,10 +498,25 @@ impl StateStoreImpl {
                     * 1024,
                 flush_buffer_hooks: vec![],
             };
-            let metrics = Arc::new(tiered_cache_metrics_builder.file());
-            TieredCache::file(options, metrics)
-                .await
-                .map_err(HummockError::tiered_cache)?
+
+            let tiered_cache = TieredCache::file(
+                file_cache_options(Path::new(file_cache_dir), config.file_cache.capacity_mb),
+                Arc::new(tiered_cache_metrics_builder.file("block")),
+            )
+            .await
+            .map_err(HummockError::tiered_cache)?;
+            // Sstable metas are cached in a sub-directory, which is never touched by the block
+            // cache as it only owns the `meta` and `cache` files under `file_cache_dir`.
+            let meta_tiered_cache = TieredCache::file(
+                file_cache_options(
+                    &Path::new(file_cache_dir).join("sstable_meta"),
+                    config.file_cache.sstable_meta_capacity_mb,
+                ),
+                Arc::new(tiered_cache_metrics_builder.file("sstable_meta")),
+            )
+            .await
+            .map_err(HummockError::tiered_cache)?;
+            (tiered_cache, meta_tiered_cache)
         };
 
         let store = match s {
@@ -526,6 +543,7 @@ impl StateStoreImpl {
                     config.block_cache_capacity_mb * (1 << 20),
                     config.meta_cache_capacity_mb * (1 << 20),
                     tiered_cache,
+                    meta_tiered_cache,
                 ));
                 let notification_client =
                     RpcNotificationClient::new(hummock_meta_client.get_inner().clone());
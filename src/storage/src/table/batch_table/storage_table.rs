@@ -23,7 +23,9 @@ use futures::{Stream, StreamExt};
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::buffer::Bitmap;
-use risingwave_common::catalog::{ColumnDesc, ColumnId, Schema, TableId, TableOption};
+use risingwave_common::catalog::{
+    bloom_filter_prefix_len, ColumnDesc, ColumnId, Schema, TableId, TableOption,
+};
 use risingwave_common::hash::VirtualNode;
 use risingwave_common::row::{self, Row, Row2, RowDeserializer, RowExt};
 use risingwave_common::util::ordered::*;
@@ -79,6 +81,10 @@ pub struct StorageTable<S: StateStore> {
     /// Note that the index is based on the primary key columns by `pk_indices`.
     dist_key_in_pk_indices: Vec<usize>,
 
+    /// The number of leading pk columns in the prefix of the bloom filter key, or `None` if the
+    /// full key is used.
+    read_prefix_len: Option<usize>,
+
     /// Virtual nodes that the table is partitioned into.
     ///
     /// Only the rows whose vnode of the primary key is in this set will be visible to the
@@ -112,6 +118,7 @@ impl<S: StateStore> StorageTable<S> {
         distribution: Distribution,
         table_options: TableOption,
         value_indices: Vec<usize>,
+        read_prefix_len_hint: usize,
     ) -> Self {
         Self::new_inner(
             store,
@@ -123,6 +130,7 @@ impl<S: StateStore> StorageTable<S> {
             distribution,
             table_options,
             value_indices,
+            read_prefix_len_hint,
         )
     }
 
@@ -145,6 +153,7 @@ impl<S: StateStore> StorageTable<S> {
             Distribution::fallback(),
             Default::default(),
             value_indices,
+            0,
         )
     }
 }
@@ -164,6 +173,7 @@ impl<S: StateStore> StorageTable<S> {
         }: Distribution,
         table_option: TableOption,
         value_indices: Vec<usize>,
+        read_prefix_len_hint: usize,
     ) -> Self {
         assert_eq!(order_types.len(), pk_indices.len());
 
@@ -197,6 +207,8 @@ impl<S: StateStore> StorageTable<S> {
                     })
             })
             .collect_vec();
+        let read_prefix_len =
+            bloom_filter_prefix_len(read_prefix_len_hint, &pk_indices, &dist_key_indices);
 
         Self {
             table_id,
@@ -208,6 +220,7 @@ impl<S: StateStore> StorageTable<S> {
             pk_indices,
             dist_key_indices,
             dist_key_in_pk_indices,
+            read_prefix_len,
             vnodes,
             table_option,
         }
@@ -249,13 +262,10 @@ impl<S: StateStore> StorageTable<S> {
         let serialized_pk =
             serialize_pk_with_vnode(&pk, &self.pk_serializer, self.compute_vnode_by_pk(&pk));
         assert!(pk.len() <= self.pk_indices.len());
-        let key_indices = (0..pk.len())
-            .into_iter()
-            .map(|index| self.pk_indices[index])
-            .collect_vec();
         let read_options = ReadOptions {
             prefix_hint: None,
-            check_bloom_filter: self.dist_key_indices == key_indices,
+            // The bloom filter is checked with the whole key.
+            check_bloom_filter: self.read_prefix_len == Some(pk.len()),
             retention_seconds: self.table_option.retention_seconds,
             ignore_range_tombstone: false,
             table_id: self.table_id,
@@ -433,21 +443,25 @@ impl<S: StateStore> StorageTable<S> {
             .into_iter()
             .map(|index| self.pk_indices[index])
             .collect_vec();
-        let prefix_hint = if self.dist_key_indices.is_empty()
-            || self.dist_key_indices != pk_prefix_indices
-        {
-            trace!(
-                "iter_with_pk_bounds dist_key_indices table_id {} not match prefix pk_prefix {:?} dist_key_indices {:?} pk_prefix_indices {:?}",
-                self.table_id,
-                pk_prefix,
-                self.dist_key_indices,
-                pk_prefix_indices
-            );
-            None
-        } else {
-            let pk_prefix_serializer = self.pk_serializer.prefix(pk_prefix.len());
-            let serialized_pk_prefix = serialize_pk(&pk_prefix, &pk_prefix_serializer);
-            Some(serialized_pk_prefix)
+        let prefix_hint = match self.read_prefix_len {
+            Some(read_prefix_len) if read_prefix_len <= pk_prefix.len() => {
+                let pk_prefix_serializer = self.pk_serializer.prefix(read_prefix_len);
+                let serialized_pk_prefix = serialize_pk(
+                    (&pk_prefix).project(&(0..read_prefix_len).collect_vec()),
+                    &pk_prefix_serializer,
+                );
+                Some(serialized_pk_prefix)
+            }
+            _ => {
+                trace!(
+                    "iter_with_pk_bounds table_id {} pk_prefix {:?} does not cover bloom filter prefix {:?} pk_prefix_indices {:?}",
+                    self.table_id,
+                    pk_prefix,
+                    self.read_prefix_len,
+                    pk_prefix_indices
+                );
+                None
+            }
         };
 
         trace!(
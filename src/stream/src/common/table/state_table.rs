@@ -26,7 +26,7 @@ use futures_async_stream::try_stream;
 use itertools::{izip, Itertools};
 use risingwave_common::array::{Op, StreamChunk, Vis};
 use risingwave_common::buffer::Bitmap;
-use risingwave_common::catalog::{ColumnDesc, TableId, TableOption};
+use risingwave_common::catalog::{bloom_filter_prefix_len, ColumnDesc, TableId, TableOption};
 use risingwave_common::hash::VirtualNode;
 use risingwave_common::row::{self, CompactedRow, Row, Row2, RowDeserializer, RowExt};
 use risingwave_common::types::ScalarImpl;
@@ -89,6 +89,10 @@ pub struct StateTable<S: StateStore> {
     /// Note that the index is based on the primary key columns by `pk_indices`.
     dist_key_in_pk_indices: Vec<usize>,
 
+    /// The number of leading pk columns in the prefix of the bloom filter key, or `None` if the
+    /// full key is used. Iterating with a pk prefix at least this long checks the bloom filter.
+    read_prefix_len: Option<usize>,
+
     /// Virtual nodes that the table is partitioned into.
     ///
     /// Only the rows whose vnode of the primary key is in this set will be visible to the
@@ -170,6 +174,11 @@ impl<S: StateStore> StateTable<S> {
                     })
             })
             .collect_vec();
+        let read_prefix_len = bloom_filter_prefix_len(
+            table_catalog.read_prefix_len_hint as usize,
+            &pk_indices,
+            &dist_key_indices,
+        );
 
         let local_state_store = store.new_local(table_id).await;
 
@@ -226,6 +235,7 @@ impl<S: StateStore> StateTable<S> {
             pk_indices: pk_indices.to_vec(),
             dist_key_indices,
             dist_key_in_pk_indices,
+            read_prefix_len,
             vnodes,
             table_option: TableOption::build_table_option(table_catalog.get_properties()),
             disable_sanity_check: false,
@@ -322,6 +332,7 @@ impl<S: StateStore> StateTable<S> {
                     })
             })
             .collect_vec();
+        let read_prefix_len = bloom_filter_prefix_len(0, &pk_indices, &dist_key_indices);
         Self {
             table_id,
             mem_table: MemTable::new(),
@@ -331,6 +342,7 @@ impl<S: StateStore> StateTable<S> {
             pk_indices,
             dist_key_indices,
             dist_key_in_pk_indices,
+            read_prefix_len,
             vnodes,
             table_option: Default::default(),
             disable_sanity_check: false,
@@ -467,13 +479,10 @@ impl<S: StateStore> StateTable<S> {
             },
             None => {
                 assert!(pk.len() <= self.pk_indices.len());
-                let key_indices = (0..pk.len())
-                    .into_iter()
-                    .map(|index| self.pk_indices[index])
-                    .collect_vec();
                 let read_options = ReadOptions {
                     prefix_hint: None,
-                    check_bloom_filter: self.dist_key_indices == key_indices,
+                    // The bloom filter is checked with the whole key.
+                    check_bloom_filter: self.read_prefix_len == Some(pk.len()),
                     retention_seconds: self.table_option.retention_seconds,
                     table_id: self.table_id,
                     ignore_range_tombstone: false,
@@ -1008,14 +1017,18 @@ impl<S: StateStore> StateTable<S> {
         let vnode = self.compute_prefix_vnode(&pk_prefix).to_be_bytes();
         let encoded_key_range_with_vnode = prefixed_range(encoded_key_range, &vnode);
 
-        // Construct prefix hint for prefix bloom filter.
+        // Construct prefix hint for prefix bloom filter, with the leading columns of the pk prefix
+        // that make up the bloom filter key.
         let pk_prefix_indices = &self.pk_indices[..pk_prefix.len()];
-        let prefix_hint = {
-            if self.dist_key_indices.is_empty() || self.dist_key_indices != pk_prefix_indices {
-                None
-            } else {
-                Some([&vnode, &encoded_prefix[..]].concat())
+        let prefix_hint = match self.read_prefix_len {
+            Some(read_prefix_len) if read_prefix_len <= pk_prefix.len() => {
+                let bloom_prefix_len = self
+                    .pk_serde
+                    .deserialize_prefix_len_with_column_indices(&encoded_prefix, 0..read_prefix_len)
+                    .unwrap();
+                Some([&vnode, &encoded_prefix[..bloom_prefix_len]].concat())
             }
+            _ => None,
         };
 
         trace!(
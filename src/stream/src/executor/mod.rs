@@ -70,16 +70,19 @@ mod managed_state;
 mod merge;
 mod mview;
 mod now;
+mod over_agg;
 mod project;
 mod project_set;
 mod rearranged_chain;
 mod receiver;
 pub mod row_id_gen;
+mod session_window;
 mod simple;
 mod sink;
 mod sort;
 pub mod source;
 pub mod subtask;
+mod temporal_join;
 mod top_n;
 mod union;
 mod watermark_filter;
@@ -111,15 +114,18 @@ pub use lookup_union::LookupUnionExecutor;
 pub use merge::MergeExecutor;
 pub use mview::*;
 pub use now::NowExecutor;
+pub use over_agg::OverAggExecutor;
 pub use project::ProjectExecutor;
 pub use project_set::*;
 pub use rearranged_chain::RearrangedChainExecutor;
 pub use receiver::ReceiverExecutor;
 use risingwave_pb::source::{ConnectorSplit, ConnectorSplits};
+pub use session_window::SessionWindowExecutor;
 use simple::{SimpleExecutor, SimpleExecutorWrapper};
-pub use sink::SinkExecutor;
+pub use sink::{sink_identifier, SinkExecutor};
 pub use sort::SortExecutor;
 pub use source::*;
+pub use temporal_join::TemporalJoinExecutor;
 pub use top_n::{AppendOnlyTopNExecutor, GroupTopNExecutor, TopNExecutor};
 pub use union::UnionExecutor;
 pub use watermark_filter::WatermarkFilterExecutor;
@@ -0,0 +1,754 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Unbounded};
+use std::ops::{Range, RangeInclusive};
+
+use futures::StreamExt;
+use futures_async_stream::{for_await, try_stream};
+use itertools::Itertools;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{DataChunk, Op, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::row::{Row, Row2, RowExt};
+use risingwave_expr::window_function::{
+    affected_rows, evaluate_partition_rows, rows_around_changes, WindowFuncCall,
+};
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message,
+    PkIndices, PkIndicesRef, StreamExecutorResult,
+};
+use crate::cache::{cache_may_stale, EvictableHashMap, ExecutorCache, LruManagerRef};
+use crate::common::table::state_table::StateTable;
+
+/// The output rows of a partition, i.e. the input rows with the results of the window functions
+/// appended, keyed by the memcomparable-encoded primary keys of the input rows in the state table,
+/// i.e. ordered by the `ORDER BY` of the window.
+type Partition = BTreeMap<Vec<u8>, Row>;
+
+/// [`OverAggExecutor`] evaluates window functions over its input. All input rows are kept in the
+/// state table, ordered by the partition key, the order key and then the stream key. The
+/// partitions recently affected are cached along with the outputs of their rows. For each
+/// partition affected by a chunk, the window functions are evaluated again only for the rows whose
+/// frames overlap with the changes, and the output rows whose results differ are retracted and
+/// emitted again. When all frames are bounded `ROWS` frames, only the rows around the changes are
+/// visited, instead of the whole partition.
+pub struct OverAggExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+
+    /// Taken (`Option.take()`) after executing.
+    input: Option<BoxedExecutor>,
+
+    calls: Vec<WindowFuncCall>,
+    /// The rows around the changes of a partition needed to evaluate `calls` again. `None` if
+    /// the whole partition is needed.
+    rows_around_changes: Option<usize>,
+    partition_by: Vec<usize>,
+    order_by: Vec<usize>,
+    state_table: StateTable<S>,
+    /// Partition key -> the partition.
+    partitions: ExecutorCache<Row, Partition>,
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    chunk_size: usize,
+}
+
+impl<S: StateStore> OverAggExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        calls: Vec<WindowFuncCall>,
+        partition_by: Vec<usize>,
+        order_by: Vec<usize>,
+        state_table: StateTable<S>,
+        executor_id: u64,
+        chunk_size: usize,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
+    ) -> Self {
+        let schema = input
+            .schema()
+            .fields()
+            .iter()
+            .cloned()
+            .chain(
+                calls
+                    .iter()
+                    .map(|call| Field::unnamed(call.return_type.clone())),
+            )
+            .collect();
+        let pk_indices = input.pk_indices().to_vec();
+        let partitions = if let Some(lru_manager) = lru_manager {
+            ExecutorCache::Managed(lru_manager.create_cache())
+        } else {
+            ExecutorCache::Local(EvictableHashMap::new(cache_size))
+        };
+        Self {
+            ctx,
+            input: Some(input),
+            rows_around_changes: rows_around_changes(&calls),
+            calls,
+            partition_by,
+            order_by,
+            state_table,
+            partitions,
+            schema,
+            pk_indices,
+            identity: format!("OverAggExecutor {:X}", executor_id),
+            chunk_size,
+        }
+    }
+
+    /// Loads all rows of the partition with `partition_key` from the state table and evaluates the
+    /// window functions over them.
+    async fn load_partition(&self, partition_key: &Row) -> StreamExecutorResult<Partition> {
+        let mut partition = Partition::new();
+        let iter = self.state_table.iter_with_pk_prefix(partition_key).await?;
+
+        #[for_await]
+        for row in iter {
+            let row: Cow<'_, Row> = row?;
+            partition.insert(
+                self.state_key(row.as_ref()),
+                self.pending_output(row.as_ref()),
+            );
+        }
+        let keys = partition.keys().cloned().collect_vec();
+        let chunk = self.rows_chunk(&partition, &keys);
+        self.evaluate(&mut partition, &keys, chunk, 0..keys.len())?;
+        Ok(partition)
+    }
+
+    /// Returns the memcomparable-encoded primary key of `row` in the state table.
+    fn state_key(&self, row: impl Row2) -> Vec<u8> {
+        row.project(self.state_table.pk_indices())
+            .memcmp_serialize(self.state_table.pk_serde())
+    }
+
+    /// Returns the output row of the input `row` whose results of the window functions are yet
+    /// to be evaluated.
+    fn pending_output(&self, row: impl Row2) -> Row {
+        row.chain(Row::new(vec![None; self.calls.len()]))
+            .into_owned_row()
+    }
+
+    /// Evaluates the window functions over the rows of `partition` with `keys`, which are also in
+    /// `chunk`, for the rows at `range`. Returns the outputs of these rows before they're updated.
+    fn evaluate(
+        &self,
+        partition: &mut Partition,
+        keys: &[Vec<u8>],
+        chunk: DataChunk,
+        range: Range<usize>,
+    ) -> StreamExecutorResult<Vec<Row>> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+        let outputs = evaluate_partition_rows(&self.calls, &self.order_by, &chunk, range.clone())?;
+        let input_len = self.schema.len() - self.calls.len();
+        let (mut columns, _) = chunk.into_parts();
+        columns.truncate(input_len);
+        let inputs = DataChunk::new(columns, keys.len());
+        let outputs = DataChunk::new(
+            outputs
+                .into_iter()
+                .map(|array| Column::new(array.into()))
+                .collect(),
+            range.len(),
+        );
+
+        Ok(range
+            .zip_eq(outputs.rows())
+            .map(|(row, results)| {
+                let new_output = inputs
+                    .row_at_unchecked_vis(row)
+                    .chain(results)
+                    .into_owned_row();
+                std::mem::replace(partition.get_mut(&keys[row]).unwrap(), new_output)
+            })
+            .collect())
+    }
+
+    /// Returns the rows of `partition` with `keys` as a chunk of the output schema.
+    fn rows_chunk(&self, partition: &Partition, keys: &[Vec<u8>]) -> DataChunk {
+        let mut builders = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| field.data_type.create_array_builder(keys.len()))
+            .collect_vec();
+        for key in keys {
+            for (datum, builder) in partition[key].iter().zip_eq(builders.iter_mut()) {
+                builder.append_datum(datum);
+            }
+        }
+        let columns = builders
+            .into_iter()
+            .map(|builder| Column::new(builder.finish().into()))
+            .collect();
+        DataChunk::new(columns, keys.len())
+    }
+
+    /// Returns the keys of the rows of `partition` needed to evaluate the window functions again
+    /// after the `changed` rows, and the positions of the first and the last changes among them,
+    /// where the position of a deleted row is that of the row after it.
+    fn rows_to_evaluate(
+        &self,
+        partition: &Partition,
+        changed: &BTreeMap<Vec<u8>, Option<Row>>,
+    ) -> Option<(Vec<Vec<u8>>, RangeInclusive<usize>)> {
+        let first = changed.keys().next()?;
+        let last = changed.keys().next_back()?;
+        let keys = match self.rows_around_changes {
+            Some(rows) => {
+                let mut before = partition
+                    .range::<Vec<u8>, _>(..first)
+                    .rev()
+                    .take(rows)
+                    .map(|(key, _)| key.clone())
+                    .collect_vec();
+                before.reverse();
+                // The position of the last change may be the row after it, if it's deleted.
+                let after = partition
+                    .range::<Vec<u8>, _>((Excluded(last), Unbounded))
+                    .take(rows + 1)
+                    .map(|(key, _)| key);
+                before
+                    .into_iter()
+                    .chain(
+                        partition
+                            .range::<Vec<u8>, _>(first..=last)
+                            .map(|(key, _)| key.clone()),
+                    )
+                    .chain(after.cloned())
+                    .collect_vec()
+            }
+            None => partition.keys().cloned().collect_vec(),
+        };
+        let position = |key: &Vec<u8>| keys.partition_point(|k| k < key);
+        let changes = position(first)..=position(last);
+        Some((keys, changes))
+    }
+
+    /// Applies the changes of a chunk, grouped by partition, to the state table and returns the
+    /// changes of the output.
+    async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<Vec<(Op, Row)>> {
+        let mut changes_by_partition: BTreeMap<Row, Vec<(Op, Row)>> = BTreeMap::new();
+        for (op, row) in chunk.rows() {
+            changes_by_partition
+                .entry(row.project(&self.partition_by).into_owned_row())
+                .or_default()
+                .push((op, row.into_owned_row()));
+        }
+
+        let mut deletes = vec![];
+        let mut updates = vec![];
+        let mut inserts = vec![];
+        for (partition_key, changes) in changes_by_partition {
+            let mut partition = match self.partitions.pop(&partition_key) {
+                Some(partition) => partition,
+                None => self.load_partition(&partition_key).await?,
+            };
+
+            // The outputs of the changed rows before the changes, or `None` if a row is new.
+            let mut old_outputs: BTreeMap<Vec<u8>, Option<Row>> = BTreeMap::new();
+            for (op, row) in changes {
+                let key = self.state_key(&row);
+                let old_output = match op {
+                    Op::Insert | Op::UpdateInsert => {
+                        self.state_table.insert(&row);
+                        partition.insert(key.clone(), self.pending_output(&row))
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        self.state_table.delete(&row);
+                        partition.remove(&key)
+                    }
+                };
+                old_outputs.entry(key).or_insert(old_output);
+            }
+
+            let (keys, range) = match self.rows_to_evaluate(&partition, &old_outputs) {
+                Some((keys, changes)) => {
+                    let chunk = self.rows_chunk(&partition, &keys);
+                    let range = affected_rows(&self.calls, &self.order_by, &chunk, changes);
+                    let evaluated = self.evaluate(&mut partition, &keys, chunk, range.clone())?;
+                    (keys, range.zip_eq(evaluated).collect_vec())
+                }
+                None => (vec![], vec![]),
+            };
+            for (row, evaluated) in range {
+                let key = &keys[row];
+                let new_output = partition[key].clone();
+                let old_output = match old_outputs.remove(key) {
+                    // The row is new.
+                    Some(None) => {
+                        inserts.push((Op::Insert, new_output));
+                        continue;
+                    }
+                    // The row is updated.
+                    Some(Some(old_output)) => old_output,
+                    // The row is unchanged, but its results may be different.
+                    None => evaluated,
+                };
+                if old_output != new_output {
+                    updates.push((Op::UpdateDelete, old_output));
+                    updates.push((Op::UpdateInsert, new_output));
+                }
+            }
+            // The rows left are deleted.
+            deletes.extend(
+                old_outputs
+                    .into_values()
+                    .flatten()
+                    .map(|old_output| (Op::Delete, old_output)),
+            );
+
+            if !partition.is_empty() {
+                self.partitions.push(partition_key, partition);
+            }
+        }
+
+        // Deletes go first, so that a row whose order key is updated never has two versions
+        // downstream at the same time.
+        deletes.extend(updates);
+        deletes.extend(inserts);
+        Ok(deletes)
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self) {
+        let mut input = self.input.take().unwrap().execute();
+        let data_types = self.schema.data_types();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state_table.init_epoch(barrier.epoch);
+        self.partitions.update_epoch(barrier.epoch.curr);
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                // The output columns are not ordered by the input columns any more, so the
+                // watermarks are not propagated.
+                Message::Watermark(_) => continue,
+                Message::Chunk(chunk) => {
+                    let changes = self.apply_chunk(chunk).await?;
+                    let mut rows = vec![];
+                    for (op, row) in changes {
+                        rows.push((op, row));
+                        // Never separate a pair of updates into different chunks.
+                        if rows.len() >= self.chunk_size && op != Op::UpdateDelete {
+                            yield Message::Chunk(StreamChunk::from_rows(&rows, &data_types));
+                            rows.clear();
+                        }
+                    }
+                    if !rows.is_empty() {
+                        yield Message::Chunk(StreamChunk::from_rows(&rows, &data_types));
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    self.state_table.commit(barrier.epoch).await?;
+                    self.partitions.evict();
+
+                    // Update the vnode bitmap for the state table if asked.
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        let previous_vnode_bitmap =
+                            self.state_table.update_vnode_bitmap(vnode_bitmap.clone());
+                        if cache_may_stale(&previous_vnode_bitmap, &vnode_bitmap) {
+                            self.partitions.clear();
+                        }
+                    }
+
+                    self.partitions.update_epoch(barrier.epoch.curr);
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for OverAggExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        (*self).execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_expr::window_function::{Frame, FrameBound, FrameUnits};
+    use risingwave_pb::expr::agg_call::{Arg, Type as AggType};
+    use risingwave_pb::expr::window_function::{GeneralType, Type as WindowFuncType};
+    use risingwave_pb::expr::{InputRefExpr, WindowFunction};
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::{MessageSender, MockSource};
+    use crate::executor::ActorContext;
+
+    /// Creates an [`OverAggExecutor`] evaluating `call` over `(partition, order)` rows, which
+    /// keeps at most `cache_size` partitions in its cache after a barrier.
+    async fn create_executor(
+        call: WindowFunction,
+        cache_size: usize,
+    ) -> (MessageSender, BoxedMessageStream) {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (tx, source) = MockSource::channel(schema, vec![1]);
+        let state_table = StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(1),
+            vec![
+                ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+            ],
+            vec![OrderType::Ascending, OrderType::Ascending],
+            vec![0, 1],
+        )
+        .await;
+        let over_agg = OverAggExecutor::new(
+            ActorContext::create(123),
+            Box::new(source),
+            vec![WindowFuncCall::from_protobuf(&call).unwrap()],
+            vec![0],
+            vec![1],
+            state_table,
+            1,
+            1024,
+            None,
+            cache_size,
+        );
+        (tx, Box::new(over_agg).execute())
+    }
+
+    #[tokio::test]
+    async fn test_over_agg_executor() {
+        let row_number = WindowFunction {
+            r#type: Some(WindowFuncType::General(GeneralType::RowNumber as i32)),
+            args: vec![],
+            return_type: Some(DataType::Int64.to_protobuf()),
+            frame: Some(Frame::default().to_protobuf()),
+        };
+        let (mut tx, mut over_agg) = create_executor(row_number, 1024).await;
+
+        tx.push_barrier(1, false);
+        over_agg.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 1 20
+            + 2 15",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I
+                + 1 10 1
+                + 1 20 2
+                + 2 15 1"
+            )
+        );
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 5",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I I  I
+                U- 1 10 1
+                U+ 1 10 2
+                U- 1 20 2
+                U+ 1 20 3
+                +  1 5  1"
+            )
+        );
+
+        tx.push_barrier(2, false);
+        over_agg.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            - 1 10",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I I  I
+                -  1 10 2
+                U- 1 20 3
+                U+ 1 20 2"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_over_agg_executor_reload_partition() {
+        // FIRST_VALUE(v) OVER (ROWS BETWEEN 1 PRECEDING AND 1 PRECEDING), which is how `LAG(v)` is
+        // planned.
+        let lag = WindowFunction {
+            r#type: Some(WindowFuncType::General(GeneralType::FirstValue as i32)),
+            args: vec![Arg {
+                input: Some(InputRefExpr { column_idx: 1 }),
+                r#type: Some(DataType::Int64.to_protobuf()),
+            }],
+            return_type: Some(DataType::Int64.to_protobuf()),
+            frame: Some(Frame::single_row(-1).to_protobuf()),
+        };
+        // No partitions are cached after barriers.
+        let (mut tx, mut over_agg) = create_executor(lag, 0).await;
+
+        tx.push_barrier(1, false);
+        over_agg.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 1 20
+            + 1 30",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I
+                + 1 10 .
+                + 1 20 10
+                + 1 30 20"
+            )
+        );
+
+        tx.push_barrier(2, false);
+        over_agg.next().await.unwrap().unwrap();
+
+        // The partition is loaded from the state table again, and only the rows next to the
+        // changes are evaluated.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 25",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I I  I
+                U- 1 30 20
+                U+ 1 30 25
+                +  1 25 20"
+            )
+        );
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            - 1 10",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I I  I
+                -  1 10 .
+                U- 1 20 10
+                U+ 1 20 ."
+            )
+        );
+    }
+
+    /// `COUNT(v)` over `frame`.
+    fn count(frame: Frame) -> WindowFunction {
+        WindowFunction {
+            r#type: Some(WindowFuncType::Aggregate(AggType::Count as i32)),
+            args: vec![Arg {
+                input: Some(InputRefExpr { column_idx: 1 }),
+                r#type: Some(DataType::Int64.to_protobuf()),
+            }],
+            return_type: Some(DataType::Int64.to_protobuf()),
+            frame: Some(frame.to_protobuf()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_over_agg_executor_running_aggregate() {
+        let (mut tx, mut over_agg) = create_executor(count(Frame::default()), 1024).await;
+
+        tx.push_barrier(1, false);
+        over_agg.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 1 20
+            + 1 30",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I
+                + 1 10 1
+                + 1 20 2
+                + 1 30 3"
+            )
+        );
+
+        // All rows after the change have a different count.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 15",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I I  I
+                U- 1 20 2
+                U+ 1 20 3
+                U- 1 30 3
+                U+ 1 30 4
+                +  1 15 2"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_over_agg_executor_bounded_frame() {
+        let frame = Frame {
+            units: FrameUnits::Rows,
+            start: FrameBound::Preceding(1),
+            end: FrameBound::Following(1),
+        };
+        let (mut tx, mut over_agg) = create_executor(count(frame), 1024).await;
+
+        tx.push_barrier(1, false);
+        over_agg.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 1 20
+            + 1 30
+            + 1 40
+            + 1 50",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I
+                + 1 10 2
+                + 1 20 3
+                + 1 30 3
+                + 1 40 3
+                + 1 50 2"
+            )
+        );
+
+        // Only the frames of the rows next to the changes are different.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 5
+            - 1 40",
+        ));
+        assert_eq!(
+            over_agg
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I I  I
+                -  1 40 3
+                U- 1 10 2
+                U+ 1 10 3
+                +  1 5  2"
+            )
+        );
+    }
+}
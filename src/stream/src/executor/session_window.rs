@@ -0,0 +1,540 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+
+use futures::StreamExt;
+use futures_async_stream::{for_await, try_stream};
+use itertools::Itertools;
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::row::{Row, Row2, RowExt};
+use risingwave_common::types::DataType;
+use risingwave_expr::vector_op::session::{
+    assign_sessions, time_to_usecs, usecs_to_time, usecs_to_window_bound,
+};
+use risingwave_storage::table::DEFAULT_VNODE;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message,
+    PkIndices, PkIndicesRef, StreamExecutorResult, Watermark,
+};
+use crate::common::table::state_table::StateTable;
+
+/// The key of a row of the state, i.e. its time in microseconds and its stream key.
+type StateKey = (i64, Row);
+
+/// [`SessionWindowExecutor`] assigns session windows to its input. Two rows belong to the same
+/// session if they are connected by a chain of rows, each within `gap` of the previous one.
+///
+/// All input rows are kept in the state table, ordered by time, and only the rows around the
+/// changes are read from it. For each chunk, the sessions around the changed rows are assigned
+/// again before and after the changes, and the rows whose sessions are merged or split are
+/// retracted and emitted again. Rows with `NULL` time belong to no session and are discarded.
+///
+/// A watermark on the time column finalizes the sessions that end before it, whose rows are then
+/// evicted from the state. Later changes to the evicted rows are ignored.
+pub struct SessionWindowExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+
+    /// Taken (`Option.take()`) after executing.
+    input: Option<BoxedExecutor>,
+
+    time_col_idx: usize,
+    time_col_type: DataType,
+    gap_usecs: i64,
+    window_type: DataType,
+    /// All rows with non-null time, ordered by time. It has a single vnode as the sessions are
+    /// assigned by a single actor.
+    state_table: StateTable<S>,
+
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    chunk_size: usize,
+}
+
+impl<S: StateStore> SessionWindowExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        time_col_idx: usize,
+        gap_usecs: i64,
+        state_table: StateTable<S>,
+        executor_id: u64,
+        chunk_size: usize,
+    ) -> Self {
+        let time_col_type = input.schema().fields()[time_col_idx].data_type();
+        let window_type = DataType::window_of(&time_col_type).unwrap();
+        let schema = input
+            .schema()
+            .fields()
+            .iter()
+            .cloned()
+            .chain([
+                Field::with_name(window_type.clone(), "window_start"),
+                Field::with_name(window_type.clone(), "window_end"),
+            ])
+            .collect();
+        let pk_indices = input.pk_indices().to_vec();
+        Self {
+            ctx,
+            input: Some(input),
+            time_col_idx,
+            time_col_type,
+            gap_usecs,
+            window_type,
+            state_table,
+            schema,
+            pk_indices,
+            identity: format!("SessionWindowExecutor {:X}", executor_id),
+            chunk_size,
+        }
+    }
+
+    /// Returns the key of `row` in the state, or `None` if its time is `NULL`.
+    fn state_key(&self, row: impl Row2) -> Option<StateKey> {
+        let time = time_to_usecs(row.datum_at(self.time_col_idx)?);
+        Some((time, row.project(&self.pk_indices).into_owned_row()))
+    }
+
+    /// Reads at most `limit` rows whose time is in `range` from the state table, ordered by time.
+    async fn rows_in(
+        &self,
+        range: (Bound<i64>, Bound<i64>),
+        limit: usize,
+    ) -> StreamExecutorResult<Vec<(StateKey, Row)>> {
+        // A `date` bound is rounded down to the day, so the rows are filtered by their exact time.
+        let pk_bound = |bound: Bound<i64>| match bound {
+            Included(usecs) | Excluded(usecs) => Included(Row::new(vec![Some(usecs_to_time(
+                usecs,
+                &self.time_col_type,
+            ))])),
+            Unbounded => Unbounded,
+        };
+        let pk_range = (pk_bound(range.0), pk_bound(range.1));
+        let iter = self
+            .state_table
+            .iter_with_pk_range(&pk_range, DEFAULT_VNODE)
+            .await?;
+
+        let mut rows = vec![];
+        #[for_await]
+        for row in iter {
+            let row: Cow<'_, Row> = row?;
+            let key = self.state_key(row.as_ref()).unwrap();
+            let after_start = match range.0 {
+                Included(lo) => key.0 >= lo,
+                Excluded(lo) => key.0 > lo,
+                Unbounded => true,
+            };
+            let before_end = match range.1 {
+                Included(hi) => key.0 <= hi,
+                Excluded(hi) => key.0 < hi,
+                Unbounded => true,
+            };
+            if !after_start {
+                continue;
+            }
+            if !before_end {
+                break;
+            }
+            rows.push((key, row.into_owned()));
+            if rows.len() >= limit {
+                break;
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Returns the rows whose time is in `[lo, hi]`.
+    async fn rows_between(&self, lo: i64, hi: i64) -> StreamExecutorResult<Vec<(StateKey, Row)>> {
+        if lo > hi {
+            return Ok(vec![]);
+        }
+        self.rows_in((Included(lo), Included(hi)), usize::MAX).await
+    }
+
+    /// Expands the time range `[lo, hi]` until no row is within `gap` of it, so that the sessions
+    /// of the rows in the range can be assigned without looking outside.
+    async fn expand(&self, mut lo: i64, mut hi: i64) -> StreamExecutorResult<(i64, i64)> {
+        // All rows within `gap` before `lo` are in the same session as `lo`, so jump to the
+        // earliest one.
+        while let Some(((time, _), _)) = self
+            .rows_in((Excluded(lo - self.gap_usecs), Excluded(lo)), 1)
+            .await?
+            .pop()
+        {
+            lo = time;
+        }
+        while let Some(((time, _), _)) = self
+            .rows_in((Excluded(hi), Excluded(hi + self.gap_usecs)), usize::MAX)
+            .await?
+            .pop()
+        {
+            hi = time;
+        }
+        Ok((lo, hi))
+    }
+
+    /// Assigns the sessions of `rows`, which are sorted by time and have no other rows within
+    /// `gap` around them, returning the output rows keyed by the state keys.
+    fn assign(&self, rows: Vec<(StateKey, Row)>) -> BTreeMap<StateKey, Row> {
+        let times = rows.iter().map(|((time, _), _)| *time).collect_vec();
+        let windows = assign_sessions(&times, self.gap_usecs);
+        rows.into_iter()
+            .zip_eq(windows)
+            .map(|((key, row), (start, end))| {
+                let window = [
+                    Some(usecs_to_window_bound(start, &self.window_type)),
+                    Some(usecs_to_window_bound(end, &self.window_type)),
+                ];
+                let output = row.chain(window).into_owned_row();
+                (key, output)
+            })
+            .collect()
+    }
+
+    /// Applies the changes of a chunk to the state table and returns the changes of the output.
+    async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<Vec<(Op, Row)>> {
+        let changes = chunk
+            .rows()
+            .filter_map(|(op, row)| Some((op, self.state_key(row)?.0, row.into_owned_row())))
+            .collect_vec();
+        let (min_time, max_time) = match changes.iter().map(|(_, time, _)| *time).minmax() {
+            itertools::MinMaxResult::NoElements => return Ok(vec![]),
+            itertools::MinMaxResult::OneElement(time) => (time, time),
+            itertools::MinMaxResult::MinMax(min, max) => (min, max),
+        };
+
+        // The rows around the changes before they are applied.
+        let (old_lo, old_hi) = self.expand(min_time, max_time).await?;
+        let old_rows = self.rows_between(old_lo, old_hi).await?;
+
+        for (op, _, row) in changes {
+            match op {
+                Op::Insert | Op::UpdateInsert => self.state_table.insert(&row),
+                Op::Delete | Op::UpdateDelete => {
+                    // The row may have been evicted by the watermark.
+                    let pk = (&row).project(self.state_table.pk_indices());
+                    if self.state_table.get_row(pk).await?.is_some() {
+                        self.state_table.delete(&row);
+                    }
+                }
+            }
+        }
+
+        // The range may grow after the changes are applied, e.g. when an inserted row bridges two
+        // sessions. Outside the original range the rows are not changed, so they are shared by
+        // both sides.
+        let (new_lo, new_hi) = self.expand(old_lo, old_hi).await?;
+        let old_rows = self
+            .rows_between(new_lo, old_lo - 1)
+            .await?
+            .into_iter()
+            .chain(old_rows)
+            .chain(self.rows_between(old_hi + 1, new_hi).await?)
+            .collect_vec();
+        let old_outputs = self.assign(old_rows);
+        let mut new_outputs = self.assign(self.rows_between(new_lo, new_hi).await?);
+
+        let mut deletes = vec![];
+        let mut updates = vec![];
+        for (key, old_output) in old_outputs {
+            match new_outputs.remove(&key) {
+                Some(new_output) => {
+                    if old_output != new_output {
+                        updates.push((Op::UpdateDelete, old_output));
+                        updates.push((Op::UpdateInsert, new_output));
+                    }
+                }
+                None => deletes.push((Op::Delete, old_output)),
+            }
+        }
+        let inserts = new_outputs
+            .into_values()
+            .map(|row| (Op::Insert, row))
+            .collect_vec();
+
+        deletes.extend(updates);
+        deletes.extend(inserts);
+        Ok(deletes)
+    }
+
+    /// Evicts the rows of the sessions that end before the `watermark` of the time column. Rows
+    /// arriving later are not earlier than the watermark, so these sessions can never change.
+    async fn evict(&mut self, watermark: i64) -> StreamExecutorResult<()> {
+        let threshold = watermark - self.gap_usecs;
+        let evict_before = match self
+            .rows_in((Excluded(threshold), Unbounded), 1)
+            .await?
+            .pop()
+        {
+            // The session of the first row after the threshold may still grow.
+            Some(((time, _), _)) => self.expand(time, time).await?.0,
+            None => i64::MAX,
+        };
+        for (_, row) in self
+            .rows_in((Unbounded, Excluded(evict_before)), usize::MAX)
+            .await?
+        {
+            self.state_table.delete(row);
+        }
+        Ok(())
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self) {
+        let mut input = self.input.take().unwrap().execute();
+        let data_types = self.schema.data_types();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state_table.init_epoch(barrier.epoch);
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                // The sessions can still be merged below the watermark, so the watermarks are not
+                // propagated.
+                Message::Watermark(Watermark { col_idx, val, .. }) => {
+                    if col_idx == self.time_col_idx {
+                        self.evict(time_to_usecs(val.as_scalar_ref_impl())).await?;
+                    }
+                }
+                Message::Chunk(chunk) => {
+                    let mut rows = vec![];
+                    for (op, row) in self.apply_chunk(chunk).await? {
+                        rows.push((op, row));
+                        // Never separate a pair of updates into different chunks.
+                        if rows.len() >= self.chunk_size && op != Op::UpdateDelete {
+                            yield Message::Chunk(StreamChunk::from_rows(&rows, &data_types));
+                            rows.clear();
+                        }
+                    }
+                    if !rows.is_empty() {
+                        yield Message::Chunk(StreamChunk::from_rows(&rows, &data_types));
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    self.state_table.commit(barrier.epoch).await?;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for SessionWindowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        (*self).execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use risingwave_common::types::{NaiveDateTimeWrapper, ScalarImpl};
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::{MessageSender, MockSource};
+    use crate::executor::ActorContext;
+
+    async fn create_state_table() -> StateTable<MemoryStateStore> {
+        StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(1),
+            vec![
+                ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::new(1), DataType::Timestamp),
+            ],
+            vec![OrderType::Ascending, OrderType::Ascending],
+            vec![1, 0],
+        )
+        .await
+    }
+
+    /// Creates a [`SessionWindowExecutor`] over `(id, time)` rows with a gap of 5 minutes.
+    fn create_executor(
+        state_table: StateTable<MemoryStateStore>,
+    ) -> (MessageSender, BoxedMessageStream) {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Timestamp),
+        ]);
+        let (tx, source) = MockSource::channel(schema, vec![0]);
+        let session_window = SessionWindowExecutor::new(
+            ActorContext::create(123),
+            Box::new(source),
+            1,
+            5 * 60 * 1_000_000,
+            state_table,
+            1,
+            1024,
+        );
+        (tx, Box::new(session_window).execute())
+    }
+
+    #[tokio::test]
+    async fn test_session_window_executor() {
+        let (mut tx, mut session_window) = create_executor(create_state_table().await);
+
+        tx.push_barrier(1, false);
+        session_window.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I TS
+            + 1 2022-02-02T10:00:00
+            + 2 2022-02-02T10:08:00
+            + 3 .",
+        ));
+        assert_eq!(
+            session_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I TS                  TS                  TS
+                + 1 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:05:00
+                + 2 2022-02-02T10:08:00 2022-02-02T10:08:00 2022-02-02T10:13:00"
+            )
+        );
+
+        // A late row merges the two sessions.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I TS
+            + 4 2022-02-02T10:04:00",
+        ));
+        assert_eq!(
+            session_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I TS                  TS                  TS
+                U- 1 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:05:00
+                U+ 1 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:13:00
+                U- 2 2022-02-02T10:08:00 2022-02-02T10:08:00 2022-02-02T10:13:00
+                U+ 2 2022-02-02T10:08:00 2022-02-02T10:00:00 2022-02-02T10:13:00
+                +  4 2022-02-02T10:04:00 2022-02-02T10:00:00 2022-02-02T10:13:00"
+            )
+        );
+
+        tx.push_barrier(2, false);
+        session_window.next().await.unwrap().unwrap();
+
+        // Deleting the row splits the session again.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I TS
+            - 4 2022-02-02T10:04:00",
+        ));
+        assert_eq!(
+            session_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I TS                  TS                  TS
+                -  4 2022-02-02T10:04:00 2022-02-02T10:00:00 2022-02-02T10:13:00
+                U- 1 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:13:00
+                U+ 1 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:05:00
+                U- 2 2022-02-02T10:08:00 2022-02-02T10:00:00 2022-02-02T10:13:00
+                U+ 2 2022-02-02T10:08:00 2022-02-02T10:08:00 2022-02-02T10:13:00"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_window_executor_recovery() {
+        let state_table = create_state_table().await;
+        let (mut tx, mut session_window) = create_executor(state_table.clone());
+
+        tx.push_barrier(1, false);
+        session_window.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I TS
+            + 1 2022-02-02T09:00:00
+            + 2 2022-02-02T10:00:00
+            + 3 2022-02-02T10:08:00",
+        ));
+        session_window.next().await.unwrap().unwrap();
+
+        // The session of the first row ends before the watermark, so the row is evicted.
+        tx.push_watermark(
+            1,
+            DataType::Timestamp,
+            ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper::from_timestamp_uncheck(1643796000, 0)),
+        );
+        tx.push_barrier(2, false);
+        session_window.next().await.unwrap().unwrap();
+
+        // The rows are read from the state table after recovery.
+        let (mut tx, mut session_window) = create_executor(state_table);
+        tx.push_barrier(3, false);
+        session_window.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I TS
+            - 1 2022-02-02T09:00:00
+            + 4 2022-02-02T10:04:00",
+        ));
+        assert_eq!(
+            session_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I TS                  TS                  TS
+                U- 2 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:05:00
+                U+ 2 2022-02-02T10:00:00 2022-02-02T10:00:00 2022-02-02T10:13:00
+                U- 3 2022-02-02T10:08:00 2022-02-02T10:08:00 2022-02-02T10:13:00
+                U+ 3 2022-02-02T10:08:00 2022-02-02T10:00:00 2022-02-02T10:13:00
+                +  4 2022-02-02T10:04:00 2022-02-02T10:00:00 2022-02-02T10:13:00"
+            )
+        );
+    }
+}
@@ -16,24 +16,37 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
-use futures::StreamExt;
+use futures::{pin_mut, Future, StreamExt};
 use futures_async_stream::try_stream;
-use risingwave_common::catalog::Schema;
+use itertools::Itertools;
+use prost::Message as _;
+use risingwave_common::array::StreamChunk;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::{Schema, TableId};
+use risingwave_common::row::Row;
+use risingwave_common::types::ScalarImpl;
+use risingwave_common::util::epoch::EpochPair;
 use risingwave_connector::sink::{Sink, SinkConfig, SinkImpl};
 use risingwave_connector::ConnectorParams;
+use risingwave_hummock_sdk::HummockReadEpoch;
+use risingwave_pb::data::StreamChunk as ProstStreamChunk;
 use risingwave_storage::StateStore;
 
 use super::error::{StreamExecutorError, StreamExecutorResult};
-use super::{BoxedExecutor, Executor, Message};
+use super::{expect_first_barrier, BoxedExecutor, Executor, Message};
+use crate::common::table::state_table::StateTable;
 use crate::executor::monitor::StreamingMetrics;
 use crate::executor::PkIndices;
+use crate::task::ActorId;
 
 pub struct SinkExecutor<S: StateStore> {
     input: BoxedExecutor,
-    _store: S,
+    store: S,
+    state: SinkState<S>,
     metrics: Arc<StreamingMetrics>,
     properties: HashMap<String, String>,
     identity: String,
+    actor_id: ActorId,
     connector_params: ConnectorParams,
     pk_indices: PkIndices,
 }
@@ -49,25 +62,227 @@ async fn build_sink(
     ))
 }
 
+/// Returns the identifier of the sink actor writing `vnodes` of the sink, which distinguishes it
+/// from the other actors in parallelism to prevent transaction execution errors. It's derived from
+/// the first vnode instead of the actor, so that it's kept the same after recovery, and the
+/// transaction left open by the actor writing the vnode before can be recovered.
+pub fn sink_identifier(sink_id: TableId, vnodes: Option<&Bitmap>) -> String {
+    match vnodes.and_then(|vnodes| vnodes.ones().next()) {
+        Some(vnode) => format!("sink-{}-{}", sink_id, vnode),
+        None => format!("sink-{}", sink_id),
+    }
+}
+
+/// The state of the transactions of a sink actor, kept in the state table of the sink under the
+/// slot of the actor, i.e. the first vnode it writes, or 0 if the sink is a singleton.
+///
+/// The header row `(slot, 0, transactional_id, epoch, NULL)` records the identifier of the actor,
+/// and the epoch of the transaction flushed on a checkpoint barrier until it's committed. The rows
+/// `(slot, seq, transactional_id, epoch, chunk)` after it record the chunks written in the
+/// transaction, so that it can be written again if the external system has aborted it.
+struct SinkState<S: StateStore> {
+    table: StateTable<S>,
+    /// The vnodes written by the actor, each of which may have been the slot of another actor
+    /// before rescaling.
+    vnodes: Option<Arc<Bitmap>>,
+    slot: i32,
+    identifier: String,
+    header: Row,
+    /// The epoch of the open transaction.
+    epoch: u64,
+    /// The rows of the chunks written in the open or flushed transaction.
+    rows: Vec<Row>,
+    seq: i64,
+}
+
+impl<S: StateStore> SinkState<S> {
+    fn new(table: StateTable<S>, vnodes: Option<Arc<Bitmap>>, identifier: String) -> Self {
+        let slot = vnodes
+            .as_ref()
+            .and_then(|vnodes| vnodes.ones().next())
+            .unwrap_or(0) as i32;
+        let header = Self::header(slot, &identifier, None);
+        Self {
+            table,
+            vnodes,
+            slot,
+            identifier,
+            header,
+            epoch: 0,
+            rows: vec![],
+            seq: 0,
+        }
+    }
+
+    fn header(slot: i32, identifier: &str, epoch: Option<u64>) -> Row {
+        Row::new(vec![
+            Some(ScalarImpl::Int32(slot)),
+            Some(ScalarImpl::Int64(0)),
+            Some(ScalarImpl::Utf8(identifier.into())),
+            epoch.map(|epoch| ScalarImpl::Int64(epoch as i64)),
+            None,
+        ])
+    }
+
+    /// Recovers the transactions flushed but not committed by the actors writing the vnodes
+    /// before, from the slots of all the vnodes of this actor, and records the identifier of
+    /// this actor in its slot.
+    ///
+    /// The transaction of this actor's own identifier is written again with `sink`. For any other
+    /// identifier recorded, i.e. the one of an actor before rescaling, a sink is built with it by
+    /// `build_fenced` to fence that actor, and its transaction is written again with the sink. An
+    /// actor that has never recorded its identifier can't have flushed any transaction, and the
+    /// one left open by it is aborted by the external system after its timeout.
+    async fn recover<K, F, Fut>(
+        &mut self,
+        epoch: EpochPair,
+        sink: &mut K,
+        build_fenced: F,
+        commits_on_checkpoint: bool,
+    ) -> StreamExecutorResult<()>
+    where
+        K: Sink,
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = StreamExecutorResult<K>>,
+    {
+        self.table.init_epoch(epoch);
+        let slots = match &self.vnodes {
+            Some(vnodes) => vnodes.ones().map(|vnode| vnode as i32).collect_vec(),
+            None => vec![0],
+        };
+        for slot in slots {
+            let rows = self.load_slot(slot).await?;
+            let Some(header) = rows.first() else {
+                continue;
+            };
+            let transactional_id = header[2].as_ref().unwrap().as_utf8().to_string();
+            let pending = match &header[3] {
+                Some(pending_epoch) => {
+                    let pending_epoch = pending_epoch.as_int64();
+                    let chunks = rows[1..]
+                        .iter()
+                        .filter(|row| {
+                            row[3].as_ref().map(|epoch| epoch.as_int64()) == Some(pending_epoch)
+                        })
+                        .map(|row| {
+                            let chunk = ProstStreamChunk::decode(
+                                row[4].as_ref().unwrap().as_bytea().as_ref(),
+                            )
+                            .map_err(anyhow::Error::from)?;
+                            Ok::<_, StreamExecutorError>(StreamChunk::from_protobuf(&chunk)?)
+                        })
+                        .collect::<StreamExecutorResult<Vec<_>>>()?;
+                    Some((*pending_epoch as u64, chunks))
+                }
+                None => None,
+            };
+
+            if transactional_id == self.identifier {
+                if let Some((pending_epoch, chunks)) = pending {
+                    sink.recover(pending_epoch, chunks).await?;
+                }
+            } else if commits_on_checkpoint {
+                tracing::info!(
+                    "fence the sink transactions of {} from {}",
+                    transactional_id,
+                    self.identifier
+                );
+                let mut fenced = build_fenced(transactional_id).await?;
+                if let Some((pending_epoch, chunks)) = pending {
+                    fenced.recover(pending_epoch, chunks).await?;
+                }
+            }
+            for row in rows {
+                self.table.delete(row);
+            }
+        }
+        self.table.insert(self.header.clone());
+        Ok(())
+    }
+
+    /// Returns all the rows of `slot`, with the header first.
+    async fn load_slot(&self, slot: i32) -> StreamExecutorResult<Vec<Row>> {
+        let iter = self
+            .table
+            .iter_with_pk_prefix(Row::new(vec![Some(ScalarImpl::Int32(slot))]))
+            .await?;
+        pin_mut!(iter);
+        let mut rows = vec![];
+        while let Some(row) = iter.next().await {
+            rows.push(row?.into_owned());
+        }
+        Ok(rows)
+    }
+
+    fn begin(&mut self, epoch: u64) {
+        self.epoch = epoch;
+    }
+
+    fn write(&mut self, chunk: &StreamChunk) {
+        self.seq += 1;
+        let row = Row::new(vec![
+            Some(ScalarImpl::Int32(self.slot)),
+            Some(ScalarImpl::Int64(self.seq)),
+            Some(ScalarImpl::Utf8(self.identifier.as_str().into())),
+            Some(ScalarImpl::Int64(self.epoch as i64)),
+            Some(ScalarImpl::Bytea(
+                chunk.to_protobuf().encode_to_vec().into_boxed_slice(),
+            )),
+        ]);
+        self.table.insert(row.clone());
+        self.rows.push(row);
+    }
+
+    /// Discards the chunks of the transaction aborted.
+    fn abort(&mut self) {
+        for row in self.rows.drain(..) {
+            self.table.delete(row);
+        }
+    }
+
+    /// Marks the open transaction flushed, to be committed after the checkpoint.
+    fn flush(&mut self) {
+        let header = Self::header(self.slot, &self.identifier, Some(self.epoch));
+        self.table
+            .update(std::mem::replace(&mut self.header, header.clone()), header);
+    }
+
+    /// Discards the transaction committed.
+    fn commit(&mut self) {
+        self.abort();
+        let header = Self::header(self.slot, &self.identifier, None);
+        self.table
+            .update(std::mem::replace(&mut self.header, header.clone()), header);
+    }
+}
+
 impl<S: StateStore> SinkExecutor<S> {
+    /// Creates the executor writing to the sink `sink_id` of `properties`, which records its
+    /// transactions in `state_table`.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         materialize_executor: BoxedExecutor,
-        _store: S,
+        store: S,
+        state_table: StateTable<S>,
+        vnodes: Option<Arc<Bitmap>>,
         metrics: Arc<StreamingMetrics>,
+        sink_id: TableId,
         mut properties: HashMap<String, String>,
         executor_id: u64,
+        actor_id: ActorId,
         connector_params: ConnectorParams,
     ) -> Self {
-        // This field can be used to distinguish a specific actor in parallelism to prevent
-        // transaction execution errors
-        properties.insert("identifier".to_string(), format!("sink-{:?}", executor_id));
         let pk_indices = materialize_executor.pk_indices().to_vec();
+        let identifier = sink_identifier(sink_id, vnodes.as_deref());
+        properties.insert("identifier".to_string(), identifier.clone());
         Self {
             input: materialize_executor,
-            _store,
+            store,
+            state: SinkState::new(state_table, vnodes, identifier),
             metrics,
             properties,
             identity: format!("SinkExecutor_{:?}", executor_id),
+            actor_id,
             pk_indices,
             connector_params,
         }
@@ -75,19 +290,13 @@ impl<S: StateStore> SinkExecutor<S> {
 
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(self) {
-        // the flag is required because kafka transaction requires at least one
-        // message, so we should abort the transaction if the flag is true.
-        let mut empty_epoch_flag = true;
-        let mut in_transaction = false;
-        let mut epoch = 0;
-
         let schema = self.schema().clone();
         let sink_config = SinkConfig::from_hashmap(self.properties.clone())?;
         let mut sink = build_sink(
             sink_config.clone(),
-            schema,
-            self.pk_indices,
-            self.connector_params,
+            schema.clone(),
+            self.pk_indices.clone(),
+            self.connector_params.clone(),
         )
         .await?;
 
@@ -95,8 +304,74 @@ impl<S: StateStore> SinkExecutor<S> {
         if sink.needs_preparation() {
             sink.prepare().await?;
         }
+        // Whether to keep the transaction open across the barriers until a checkpoint one.
+        let commits_on_checkpoint = sink.commits_on_checkpoint();
+
+        // Builds the sink with the identifier of another actor, which fences that actor.
+        let properties = self.properties.clone();
+        let pk_indices = self.pk_indices.clone();
+        let connector_params = self.connector_params.clone();
+        let build_fenced = move |identifier: String| {
+            let mut properties = properties.clone();
+            properties.insert("identifier".to_string(), identifier);
+            let schema = schema.clone();
+            let pk_indices = pk_indices.clone();
+            let connector_params = connector_params.clone();
+            async move {
+                let config = SinkConfig::from_hashmap(properties)?;
+                Ok::<_, StreamExecutorError>(
+                    *build_sink(config, schema, pk_indices, connector_params).await?,
+                )
+            }
+        };
+
+        #[for_await]
+        for msg in self.write_to_sink(
+            *sink,
+            build_fenced,
+            commits_on_checkpoint,
+            sink_config.get_connector(),
+        ) {
+            yield msg?;
+        }
+    }
 
-        let input = self.input.execute();
+    /// Writes the input to `sink` in transactions. If `commits_on_checkpoint`, a transaction is
+    /// kept open until a checkpoint barrier, where it's flushed, and then committed before writing
+    /// anything else once the checkpoint is committed, so that no data is committed to the
+    /// external system while the checkpoint may still be rolled back by a failure. The flushed
+    /// transaction is recorded in the state until it's committed, and written again on recovery
+    /// if the actor fails in between.
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn write_to_sink<K, F, Fut>(
+        mut self,
+        mut sink: K,
+        build_fenced: F,
+        commits_on_checkpoint: bool,
+        connector: &'static str,
+    ) where
+        K: Sink + Send + 'static,
+        F: Fn(String) -> Fut + Send + 'static,
+        Fut: Future<Output = StreamExecutorResult<K>> + Send + 'static,
+    {
+        let mut input = self.input.execute();
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state
+            .recover(
+                barrier.epoch,
+                &mut sink,
+                build_fenced,
+                commits_on_checkpoint,
+            )
+            .await?;
+        // the flag is required because kafka transaction requires at least one
+        // message, so we should abort the transaction if the flag is true.
+        let mut empty_epoch_flag = true;
+        let mut in_transaction = false;
+        let mut epoch = barrier.epoch.curr;
+        // The epoch of the checkpoint to be committed before committing the flushed transaction.
+        let mut pending_checkpoint = None;
+        yield Message::Barrier(barrier);
 
         #[for_await]
         for msg in input {
@@ -105,12 +380,27 @@ impl<S: StateStore> SinkExecutor<S> {
                     todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
                 }
                 Message::Chunk(chunk) => {
+                    if let Some(checkpoint) = pending_checkpoint.take() {
+                        commit_after_checkpoint(
+                            &self.store,
+                            &mut sink,
+                            checkpoint,
+                            &self.metrics,
+                            &[self.identity.as_str(), connector],
+                        )
+                        .await?;
+                        self.state.commit();
+                    }
                     if !in_transaction {
                         sink.begin_epoch(epoch).await?;
+                        self.state.begin(epoch);
                         in_transaction = true;
                     }
 
                     let visible_chunk = chunk.clone().compact();
+                    if commits_on_checkpoint {
+                        self.state.write(&visible_chunk);
+                    }
                     if let Err(e) = sink.write_batch(visible_chunk).await {
                         sink.abort().await?;
                         return Err(e.into());
@@ -120,35 +410,81 @@ impl<S: StateStore> SinkExecutor<S> {
                     yield Message::Chunk(chunk);
                 }
                 Message::Barrier(barrier) => {
-                    if in_transaction {
+                    if let Some(checkpoint) = pending_checkpoint.take() {
+                        commit_after_checkpoint(
+                            &self.store,
+                            &mut sink,
+                            checkpoint,
+                            &self.metrics,
+                            &[self.identity.as_str(), connector],
+                        )
+                        .await?;
+                        self.state.commit();
+                    }
+                    // The state of the actor is dropped with the sink by a stop barrier, so the
+                    // transaction can't be left to be recovered, and is committed without waiting
+                    // for the checkpoint, which is the only case it may be delivered twice if the
+                    // checkpoint fails.
+                    let is_stop = barrier.mutation.as_ref().map_or(false, |m| m.is_stop());
+                    if in_transaction && (barrier.checkpoint || !commits_on_checkpoint || is_stop) {
                         if empty_epoch_flag {
                             sink.abort().await?;
                             tracing::debug!(
                                 "transaction abort due to empty epoch, epoch: {:?}",
                                 epoch
                             );
+                        } else if commits_on_checkpoint && !is_stop {
+                            sink.flush().await?;
+                            self.state.flush();
+                            pending_checkpoint = Some(barrier.epoch.prev);
                         } else {
                             let start_time = Instant::now();
                             sink.commit().await?;
                             self.metrics
                                 .sink_commit_duration
-                                .with_label_values(&[
-                                    self.identity.as_str(),
-                                    sink_config.get_connector(),
-                                ])
+                                .with_label_values(&[self.identity.as_str(), connector])
                                 .observe(start_time.elapsed().as_millis() as f64);
+                            self.state.commit();
                         }
+                        in_transaction = false;
+                        empty_epoch_flag = true;
                     }
-                    in_transaction = false;
-                    empty_epoch_flag = true;
                     epoch = barrier.epoch.curr;
+                    self.state.table.commit(barrier.epoch).await?;
+
+                    // The transaction flushed on the barrier dropping the actor by rescaling is
+                    // recorded in the state, and committed by the actor writing the slot after it.
+                    let stops = barrier.is_stop_or_update_drop_actor(self.actor_id);
                     yield Message::Barrier(barrier);
+                    if stops {
+                        break;
+                    }
                 }
             }
         }
     }
 }
 
+/// Commits the transaction of `sink` once the checkpoint of `epoch` is committed.
+async fn commit_after_checkpoint(
+    store: &impl StateStore,
+    sink: &mut impl Sink,
+    epoch: u64,
+    metrics: &StreamingMetrics,
+    labels: &[&str],
+) -> StreamExecutorResult<()> {
+    store
+        .try_wait_epoch(HummockReadEpoch::Committed(epoch))
+        .await?;
+    let start_time = Instant::now();
+    sink.commit().await?;
+    metrics
+        .sink_commit_duration
+        .with_label_values(labels)
+        .observe(start_time.elapsed().as_millis() as f64);
+    Ok(())
+}
+
 impl<S: StateStore> Executor for SinkExecutor<S> {
     fn execute(self: Box<Self>) -> super::BoxedMessageStream {
         self.execute_inner().boxed()
@@ -169,20 +505,260 @@ impl<S: StateStore> Executor for SinkExecutor<S> {
 
 #[cfg(test)]
 mod test {
+    use parking_lot::Mutex;
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::buffer::BitmapBuilder;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field};
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_connector::sink::Result as SinkResult;
+    use risingwave_storage::memory::MemoryStateStore;
+
     use super::*;
     use crate::executor::test_utils::*;
+    use crate::executor::{Barrier, Mutation};
+
+    /// A sink recording the calls to it.
+    struct MockSink {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Sink for MockSink {
+        async fn write_batch(&mut self, chunk: StreamChunk) -> SinkResult<()> {
+            self.events
+                .lock()
+                .push(format!("write {}", chunk.cardinality()));
+            Ok(())
+        }
+
+        async fn begin_epoch(&mut self, epoch: u64) -> SinkResult<()> {
+            self.events.lock().push(format!("begin {}", epoch));
+            Ok(())
+        }
+
+        async fn commit(&mut self) -> SinkResult<()> {
+            self.events.lock().push("commit".to_string());
+            Ok(())
+        }
+
+        async fn flush(&mut self) -> SinkResult<()> {
+            self.events.lock().push("flush".to_string());
+            Ok(())
+        }
+
+        async fn abort(&mut self) -> SinkResult<()> {
+            self.events.lock().push("abort".to_string());
+            Ok(())
+        }
+
+        async fn recover(&mut self, epoch: u64, chunks: Vec<StreamChunk>) -> SinkResult<()> {
+            self.events
+                .lock()
+                .push(format!("recover {} {}", epoch, chunks.len()));
+            Ok(())
+        }
+    }
+
+    async fn state_table(store: MemoryStateStore) -> StateTable<MemoryStateStore> {
+        let columns = [
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Varchar,
+            DataType::Int64,
+            DataType::Bytea,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, data_type)| ColumnDesc::unnamed(ColumnId::new(i as i32), data_type))
+        .collect();
+        StateTable::new_without_distribution(
+            store,
+            TableId::new(1),
+            columns,
+            vec![OrderType::Ascending, OrderType::Ascending],
+            vec![0, 1],
+        )
+        .await
+    }
+
+    /// Writes `msgs` to a [`MockSink`] committing on checkpoints with the state in `store`, and
+    /// returns the calls to the sinks after each message.
+    async fn write_to_mock_sink(
+        store: MemoryStateStore,
+        vnodes: Option<Bitmap>,
+        msgs: Vec<Message>,
+    ) -> Vec<Vec<String>> {
+        let num_msgs = msgs.len();
+        let source = MockSource::with_messages(
+            Schema::new(vec![Field::with_name(DataType::Int64, "v")]),
+            PkIndices::new(),
+            msgs,
+        )
+        .stop_on_finish(false);
+        let executor = SinkExecutor::new(
+            Box::new(source),
+            store.clone(),
+            state_table(store).await,
+            vnodes.map(Arc::new),
+            Arc::new(StreamingMetrics::unused()),
+            TableId::new(7),
+            HashMap::new(),
+            0,
+            1,
+            Default::default(),
+        );
+        let events = Arc::new(Mutex::new(vec![]));
+        let fenced_events = events.clone();
+        let mut stream = executor
+            .write_to_sink(
+                MockSink {
+                    events: events.clone(),
+                },
+                move |identifier| {
+                    let events = fenced_events.clone();
+                    async move {
+                        events.lock().push(format!("fence {}", identifier));
+                        Ok::<_, StreamExecutorError>(MockSink { events })
+                    }
+                },
+                true,
+                "mock",
+            )
+            .boxed();
+        let mut calls = vec![];
+        for _ in 0..num_msgs {
+            stream.next().await.unwrap().unwrap();
+            calls.push(events.lock().clone());
+        }
+        calls
+    }
+
+    fn chunk() -> Message {
+        Message::Chunk(StreamChunk::from_pretty(
+            " I
+            + 1",
+        ))
+    }
+
+    fn barrier(epoch: u64, checkpoint: bool) -> Message {
+        Message::Barrier(Barrier {
+            checkpoint,
+            ..Barrier::new_test_barrier(epoch)
+        })
+    }
+
+    fn vnodes(ones: &[usize]) -> Bitmap {
+        let mut vnodes = BitmapBuilder::zeroed(8);
+        for vnode in ones {
+            vnodes.set(*vnode, true);
+        }
+        vnodes.finish()
+    }
 
-    #[ignore]
     #[tokio::test]
-    async fn test_mysqlsink() {
-        use risingwave_common::array::stream_chunk::StreamChunk;
-        use risingwave_common::array::StreamChunkTestExt;
-        use risingwave_common::catalog::Field;
-        use risingwave_common::types::DataType;
-        use risingwave_storage::memory::MemoryStateStore;
+    async fn test_commit_after_checkpoint() {
+        let calls = write_to_mock_sink(
+            MemoryStateStore::new(),
+            None,
+            vec![
+                barrier(1, true),
+                chunk(),
+                barrier(2, false),
+                chunk(),
+                barrier(3, true),
+                barrier(4, true),
+                barrier(5, true),
+            ],
+        )
+        .await;
+
+        // The transaction is kept open across the barrier that's not a checkpoint.
+        assert_eq!(calls[2], vec!["begin 1", "write 1"]);
+        // It's flushed on the checkpoint barrier, and only committed on the next message after the
+        // checkpoint is committed.
+        assert_eq!(calls[4], vec!["begin 1", "write 1", "write 1", "flush"]);
+        assert_eq!(
+            calls[5],
+            vec!["begin 1", "write 1", "write 1", "flush", "commit"]
+        );
+        // Nothing is written in the epochs after it.
+        assert_eq!(calls[6], calls[5]);
+    }
+
+    #[tokio::test]
+    async fn test_recover_before_commit() {
+        // The actor is killed after a checkpoint barrier before the checkpoint is committed, so
+        // its transaction is flushed but never committed.
+        let store = MemoryStateStore::new();
+        let calls = write_to_mock_sink(
+            store.clone(),
+            None,
+            vec![barrier(1, true), chunk(), barrier(2, true)],
+        )
+        .await;
+        assert_eq!(calls.last().unwrap(), &vec!["begin 1", "write 1", "flush"]);
+
+        // After recovery from the checkpoint, the transaction recorded in it is written again
+        // before anything else, and the one of the stop barrier is committed before yielding it.
+        let stop = Barrier::new_test_barrier(3).with_mutation(Mutation::Stop([1].into()));
+        let calls = write_to_mock_sink(
+            store.clone(),
+            None,
+            vec![barrier(2, true), chunk(), Message::Barrier(stop)],
+        )
+        .await;
+        assert_eq!(calls[0], vec!["recover 1 1"]);
+        assert_eq!(
+            calls[2],
+            vec!["recover 1 1", "begin 2", "write 1", "commit"]
+        );
 
-        use crate::executor::Barrier;
+        // Nothing is left to be recovered after the commit.
+        let calls = write_to_mock_sink(store, None, vec![barrier(4, true)]).await;
+        assert!(calls[0].is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fence_after_rescale() {
+        // The actor writing vnodes 3 and 5 is dropped by rescaling after flushing a transaction.
+        let store = MemoryStateStore::new();
+        let drop = Barrier::new_test_barrier(2).with_mutation(Mutation::Update {
+            dispatchers: Default::default(),
+            merges: Default::default(),
+            vnode_bitmaps: Default::default(),
+            dropped_actors: [1].into(),
+            actor_splits: Default::default(),
+        });
+        let calls = write_to_mock_sink(
+            store.clone(),
+            Some(vnodes(&[3, 5])),
+            vec![barrier(1, true), chunk(), Message::Barrier(drop)],
+        )
+        .await;
+        assert_eq!(calls.last().unwrap(), &vec!["begin 1", "write 1", "flush"]);
+
+        // The actor writing vnode 3 after it fences the one before and commits its transaction,
+        // while the one writing vnode 5 has nothing to do with the slot of vnode 3.
+        let calls =
+            write_to_mock_sink(store.clone(), Some(vnodes(&[5, 6])), vec![barrier(3, true)]).await;
+        assert!(calls[0].is_empty());
+        let calls = write_to_mock_sink(store, Some(vnodes(&[1, 3])), vec![barrier(3, true)]).await;
+        assert_eq!(calls[0], vec!["fence sink-7-3", "recover 1 1"]);
+    }
+
+    #[test]
+    fn test_sink_identifier() {
+        assert_eq!(
+            sink_identifier(TableId::new(7), Some(&vnodes(&[3, 5]))),
+            "sink-7-3"
+        );
+        assert_eq!(sink_identifier(TableId::new(7), None), "sink-7");
+    }
 
+    #[ignore]
+    #[tokio::test]
+    async fn test_mysqlsink() {
         let properties = maplit::hashmap! {
         "connector".into() => "mysql".into(),
         "endpoint".into() => "127.0.0.1:3306".into(),
@@ -200,11 +776,12 @@ mod test {
             ]),
             PkIndices::new(),
             vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
                 Message::Chunk(std::mem::take(&mut StreamChunk::from_pretty(
                     " I I I
             +  3 2 1",
                 ))),
-                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Barrier(Barrier::new_test_barrier(2)),
                 Message::Chunk(std::mem::take(&mut StreamChunk::from_pretty(
                     " I I I
             +  6 5 4",
@@ -212,12 +789,17 @@ mod test {
             ],
         );
 
+        let store = MemoryStateStore::new();
         let sink_executor = SinkExecutor::new(
             Box::new(mock),
-            MemoryStateStore::new(),
+            store.clone(),
+            state_table(store).await,
+            None,
             Arc::new(StreamingMetrics::unused()),
+            TableId::new(7),
             properties,
             0,
+            0,
             Default::default(),
         );
 
@@ -20,4 +20,32 @@ pub mod source_executor_v2;
 mod reader;
 pub mod state_table_handler;
 
+use anyhow::anyhow;
+use risingwave_source::dead_letter::DeadLetter;
+use risingwave_storage::StateStore;
 pub use state_table_handler::*;
+
+use crate::common::table::state_table::StateTable;
+use crate::executor::error::StreamExecutorResult;
+
+/// Writes the messages failed to parse to the dead-letter table, which is committed together with
+/// the offsets of the splits so that each of them is written exactly once. The table only exists if
+/// the error policy of the source is dead letter, so any dead letter without it is an error.
+fn write_dead_letters<S: StateStore>(
+    table: Option<&mut StateTable<S>>,
+    dead_letters: Vec<DeadLetter>,
+) -> StreamExecutorResult<()> {
+    if dead_letters.is_empty() {
+        return Ok(());
+    }
+    let table = table.ok_or_else(|| {
+        anyhow!(
+            "{} messages failed to parse are dead letters, but the source has no dead-letter table",
+            dead_letters.len()
+        )
+    })?;
+    for dead_letter in dead_letters {
+        table.insert(dead_letter.into_row());
+    }
+    Ok(())
+}
@@ -25,6 +25,7 @@ use risingwave_common::catalog::{ColumnId, Schema, TableId};
 use risingwave_common::util::epoch::UNIX_SINGULARITY_DATE_EPOCH;
 use risingwave_connector::source::{ConnectorState, SplitId, SplitImpl, SplitMetaData};
 use risingwave_source::connector_source::SourceContext;
+use risingwave_source::offset_committer::SourceOffsetCommitter;
 use risingwave_source::row_id::RowIdGenerator;
 use risingwave_source::*;
 use risingwave_storage::StateStore;
@@ -32,6 +33,8 @@ use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::Instant;
 
 use super::reader::SourceReaderStream;
+use super::write_dead_letters;
+use crate::common::table::state_table::StateTable;
 use crate::error::StreamResult;
 use crate::executor::error::StreamExecutorError;
 use crate::executor::monitor::StreamingMetrics;
@@ -70,8 +73,15 @@ pub struct SourceExecutor<S: StateStore> {
 
     split_state_store: SourceStateTableHandler<S>,
 
+    /// The messages failed to parse are written to this table if the error policy of the source
+    /// is dead letter.
+    dead_letter_table: Option<StateTable<S>>,
+
     state_cache: HashMap<SplitId, SplitImpl>,
 
+    /// Reports the checkpointed offsets to the external system, if the connector supports it.
+    offset_committer: Option<SourceOffsetCommitter>,
+
     /// Expected barrier latency
     expected_barrier_latency_ms: u64,
 }
@@ -85,6 +95,7 @@ impl<S: StateStore> SourceExecutor<S> {
         source_name: String,
         vnodes: Bitmap,
         state_table: SourceStateTableHandler<S>,
+        dead_letter_table: Option<StateTable<S>>,
         column_ids: Vec<ColumnId>,
         schema: Schema,
         pk_indices: PkIndices,
@@ -115,7 +126,9 @@ impl<S: StateStore> SourceExecutor<S> {
             stream_source_splits: vec![],
             source_identify: "Table_".to_string() + &source_id.table_id().to_string(),
             split_state_store: state_table,
+            dead_letter_table,
             state_cache: HashMap::new(),
+            offset_committer: None,
             expected_barrier_latency_ms,
         })
     }
@@ -219,6 +232,9 @@ impl<S: StateStore> SourceExecutor<S> {
         }
         // commit anyway, even if no message saved
         self.split_state_store.state_store.commit(epoch).await?;
+        if let Some(table) = &mut self.dead_letter_table {
+            table.commit(epoch).await?;
+        }
 
         Ok(())
     }
@@ -291,6 +307,9 @@ impl<S: StateStore> SourceExecutor<S> {
         }
 
         self.split_state_store.init_epoch(barrier.epoch);
+        if let Some(table) = &mut self.dead_letter_table {
+            table.init_epoch(barrier.epoch);
+        }
 
         let mut boot_state = self.stream_source_splits.clone();
         for ele in &mut boot_state {
@@ -303,6 +322,19 @@ impl<S: StateStore> SourceExecutor<S> {
             }
         }
 
+        if let SourceImpl::Connector(source) = &source_desc.source {
+            self.offset_committer = SourceOffsetCommitter::new(
+                source,
+                source_desc.metrics.clone(),
+                SourceContext::new(self.ctx.id, self.source_id),
+            )
+            .await
+            .map_err(StreamExecutorError::connector_error)?;
+        }
+        if let Some(committer) = &mut self.offset_committer {
+            committer.assign(&boot_state);
+        }
+
         let recover_state: ConnectorState = (!boot_state.is_empty()).then_some(boot_state);
         tracing::info!(actor_id = self.ctx.id, state = ?recover_state, "start with state");
 
@@ -369,6 +401,14 @@ impl<S: StateStore> SourceExecutor<S> {
                         }
                     }
                     self.take_snapshot(epoch).await?;
+                    if let Some(committer) = &mut self.offset_committer {
+                        committer.update(self.state_cache.values());
+                        if barrier.checkpoint {
+                            committer.checkpoint(
+                                self.split_state_store.wait_committed(barrier.epoch.prev),
+                            );
+                        }
+                    }
                     self.state_cache.clear();
                     yield Message::Barrier(barrier);
                 }
@@ -376,6 +416,7 @@ impl<S: StateStore> SourceExecutor<S> {
                 Either::Right(StreamChunkWithState {
                     mut chunk,
                     split_offset_mapping,
+                    dead_letters,
                 }) => {
                     if last_barrier_time.elapsed().as_millis() > max_wait_barrier_time_ms {
                         // Exceeds the max wait barrier time, the source will be paused. Currently
@@ -398,14 +439,16 @@ impl<S: StateStore> SourceExecutor<S> {
                                     });
 
                                 origin_split_impl.map(|split_impl| {
-                                    (split.clone(), split_impl.update(offset.clone()))
+                                    Ok((split.clone(), split_impl.update(offset.clone())?))
                                 })
                             })
-                            .collect();
+                            .collect::<anyhow::Result<_>>()?;
 
                         self.state_cache.extend(state);
                     }
 
+                    write_dead_letters(self.dead_letter_table.as_mut(), dead_letters)?;
+
                     // Refill row id column for source.
                     chunk = match &source_desc.source {
                         SourceImpl::Connector(_) => {
@@ -475,6 +518,9 @@ impl<S: StateStore> SourceExecutor<S> {
             .await?;
         stream.replace_source_stream(reader);
 
+        if let Some(committer) = &mut self.offset_committer {
+            committer.assign(&target_state);
+        }
         self.stream_source_splits = target_state;
 
         Ok(())
@@ -519,11 +565,13 @@ mod tests {
     use maplit::{convert_args, hashmap};
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::array::StreamChunk;
-    use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::DataType;
+    use risingwave_common::catalog::{ColumnDesc, Field, Schema};
+    use risingwave_common::row::Row;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_common::util::epoch::EpochPair;
     use risingwave_common::util::sort_util::{OrderPair, OrderType};
     use risingwave_connector::source::datagen::DatagenSplit;
+    use risingwave_connector::source::filesystem::posix_fs::PosixFsSplit;
     use risingwave_pb::catalog::source_info::SourceInfo as ProstSourceInfo;
     use risingwave_pb::catalog::{ColumnIndex as ProstColumnIndex, StreamSourceInfo};
     use risingwave_pb::data::data_type::TypeName;
@@ -595,6 +643,7 @@ mod tests {
             MOCK_SOURCE_NAME.to_string(),
             vnodes,
             state_table,
+            None,
             column_ids,
             schema,
             pk_indices,
@@ -699,6 +748,7 @@ mod tests {
             MOCK_SOURCE_NAME.to_string(),
             vnodes,
             state_table,
+            None,
             column_ids,
             schema,
             pk_indices,
@@ -739,13 +789,10 @@ mod tests {
         }
     }
 
-    fn mock_source_desc_builder(source_id: TableId) -> SourceDescBuilder {
-        let properties = convert_args!(hashmap!(
-            "connector" => "datagen",
-            "fields.v1.min" => "1",
-            "fields.v1.max" => "1000",
-            "fields.v1.seed" => "12345",
-        ));
+    fn mock_source_desc_builder(
+        source_id: TableId,
+        properties: HashMap<String, String>,
+    ) -> SourceDescBuilder {
         let columns = vec![
             ProstColumnCatalog {
                 column_desc: Some(ProstColumnDesc {
@@ -807,7 +854,13 @@ mod tests {
             Schema::new(fields)
         };
 
-        let source_builder = mock_source_desc_builder(source_table_id);
+        let properties = convert_args!(hashmap!(
+            "connector" => "datagen",
+            "fields.v1.min" => "1",
+            "fields.v1.max" => "1000",
+            "fields.v1.seed" => "12345",
+        ));
+        let source_builder = mock_source_desc_builder(source_table_id, properties);
         let source_desc = source_builder.build().await.unwrap();
         let mem_state_store = MemoryStateStore::new();
 
@@ -829,6 +882,7 @@ mod tests {
             MOCK_SOURCE_NAME.to_string(),
             vnodes,
             source_state_handler,
+            None,
             column_ids.clone(),
             schema,
             pk_indices,
@@ -953,4 +1007,144 @@ mod tests {
         let barrier = Barrier::new_test_barrier(4).with_mutation(Mutation::Resume);
         barrier_tx.send(barrier).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_dead_letter() {
+        let dir = std::env::temp_dir().join(format!("test_dead_letter_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.json"), "{\"v1\": 1}\nnot json\n{\"v1\": 2}\n").unwrap();
+
+        let source_table_id = TableId::default();
+        let properties = convert_args!(hashmap!(
+            "connector" => "posix_fs",
+            "posix_fs.root" => dir.to_str().unwrap(),
+            "error_policy" => "dead_letter",
+        ));
+        let source_builder = mock_source_desc_builder(source_table_id, properties);
+        let source_desc = source_builder.build().await.unwrap();
+        let mem_state_store = MemoryStateStore::new();
+
+        let column_ids = vec![ColumnId::from(0), ColumnId::from(1)];
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int32),
+        ]);
+        let (barrier_tx, barrier_rx) = unbounded_channel::<Barrier>();
+        let vnodes = Bitmap::from_bytes(Bytes::from_static(&[0b11111111]));
+        let source_state_handler = SourceStateTableHandler::from_table_catalog(
+            &default_source_internal_table(0x2333),
+            mem_state_store.clone(),
+        )
+        .await;
+        let dead_letter_table = || {
+            StateTable::new_without_distribution(
+                mem_state_store.clone(),
+                TableId::from(0x2334),
+                vec![
+                    ColumnDesc::unnamed(ColumnId::from(0), DataType::Varchar),
+                    ColumnDesc::unnamed(ColumnId::from(1), DataType::Varchar),
+                    ColumnDesc::unnamed(ColumnId::from(2), DataType::Bytea),
+                    ColumnDesc::unnamed(ColumnId::from(3), DataType::Varchar),
+                ],
+                vec![OrderType::Ascending; 2],
+                vec![0, 1],
+            )
+        };
+
+        let source_exec = SourceExecutor::new(
+            ActorContext::create(0),
+            source_builder,
+            source_table_id,
+            MOCK_SOURCE_NAME.to_string(),
+            vnodes,
+            source_state_handler,
+            Some(dead_letter_table().await),
+            column_ids,
+            schema,
+            vec![0],
+            barrier_rx,
+            1,
+            1,
+            "SourceExecutor".to_string(),
+            Arc::new(StreamingMetrics::unused()),
+            u64::MAX,
+        )
+        .unwrap();
+        let mut executor = Box::new(source_exec).execute();
+
+        let init_barrier = Barrier::new_test_barrier(1).with_mutation(Mutation::Add {
+            adds: HashMap::new(),
+            splits: hashmap! {
+                ActorId::default() => vec![
+                    SplitImpl::PosixFs(PosixFsSplit::new("a.json".to_string(), 0)),
+                ],
+            },
+        });
+        barrier_tx.send(init_barrier).unwrap();
+        executor
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+
+        // The message failed to parse is skipped in the chunk.
+        let chunk = executor
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_chunk()
+            .unwrap();
+        assert_eq!(
+            chunk.drop_row_id(),
+            StreamChunk::from_pretty(
+                " i
+                + 1
+                + 2",
+            )
+        );
+        assert_eq!(
+            source_desc
+                .metrics
+                .parse_error_count
+                .with_label_values(&[
+                    &ActorId::default().to_string(),
+                    &source_table_id.to_string(),
+                    "a.json",
+                    "dead_letter",
+                ])
+                .get(),
+            1
+        );
+
+        // The dead letter is committed with the barrier, keyed by the offset right after its line.
+        barrier_tx.send(Barrier::new_test_barrier(2)).unwrap();
+        executor
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+
+        let mut table = dead_letter_table().await;
+        table.init_epoch(EpochPair::new_test_epoch(3));
+        let row = table
+            .get_row(&Row::new(vec![
+                Some(ScalarImpl::Utf8("a.json".into())),
+                Some(ScalarImpl::Utf8("19".into())),
+            ]))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            row[2],
+            Some(ScalarImpl::Bytea(b"not json".to_vec().into_boxed_slice()))
+        );
+        assert!(row[3].is_some());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -25,7 +25,8 @@ use risingwave_storage::StateStore;
 use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::Instant;
 
-use super::SourceStateTableHandler;
+use super::{write_dead_letters, SourceStateTableHandler};
+use crate::common::table::state_table::StateTable;
 use crate::executor::monitor::StreamingMetrics;
 use crate::executor::source::reader::SourceReaderStream;
 use crate::executor::*;
@@ -55,6 +56,10 @@ pub struct StreamSourceCore<S: StateStore> {
     /// Stores information of the splits.
     split_state_store: SourceStateTableHandler<S>,
 
+    /// The messages failed to parse are written to this table if the error policy of the source
+    /// is dead letter.
+    dead_letter_table: Option<StateTable<S>>,
+
     /// In-memory cache for the splits.
     state_cache: HashMap<SplitId, SplitImpl>,
 }
@@ -232,6 +237,9 @@ impl<S: StateStore> SourceExecutorV2<S> {
         }
         // commit anyway, even if no message saved
         core.split_state_store.state_store.commit(epoch).await?;
+        if let Some(table) = &mut core.dead_letter_table {
+            table.commit(epoch).await?;
+        }
 
         core.state_cache.clear();
 
@@ -279,6 +287,9 @@ impl<S: StateStore> SourceExecutorV2<S> {
         }
 
         core.split_state_store.init_epoch(barrier.epoch);
+        if let Some(table) = &mut core.dead_letter_table {
+            table.init_epoch(barrier.epoch);
+        }
 
         let mut boot_state = core.stream_source_splits.clone();
         for ele in &mut boot_state {
@@ -358,7 +369,16 @@ impl<S: StateStore> SourceExecutorV2<S> {
                 Either::Right(StreamChunkWithState {
                     chunk,
                     split_offset_mapping,
+                    dead_letters,
                 }) => {
+                    write_dead_letters(
+                        self.stream_source_core
+                            .as_mut()
+                            .unwrap()
+                            .dead_letter_table
+                            .as_mut(),
+                        dead_letters,
+                    )?;
                     if last_barrier_time.elapsed().as_millis() > max_wait_barrier_time_ms {
                         // Exceeds the max wait barrier time, the source will be paused. Currently
                         // we can guarantee the source is not paused since it received stream
@@ -383,10 +403,10 @@ impl<S: StateStore> SourceExecutorV2<S> {
                                     });
 
                                 origin_split_impl.map(|split_impl| {
-                                    (split.clone(), split_impl.update(offset.clone()))
+                                    Ok((split.clone(), split_impl.update(offset.clone())?))
                                 })
                             })
-                            .collect();
+                            .collect::<anyhow::Result<_>>()?;
 
                         self.stream_source_core
                             .as_mut()
@@ -544,6 +564,7 @@ mod tests {
             source_desc_builder: Some(source_desc_builder),
             stream_source_splits: vec![],
             split_state_store,
+            dead_letter_table: None,
             state_cache: HashMap::new(),
             source_name: MOCK_SOURCE_NAME.to_string(),
         };
@@ -636,6 +657,7 @@ mod tests {
             source_desc_builder: Some(source_desc_builder),
             stream_source_splits: vec![],
             split_state_store,
+            dead_letter_table: None,
             state_cache: HashMap::new(),
             source_name: MOCK_SOURCE_NAME.to_string(),
         };
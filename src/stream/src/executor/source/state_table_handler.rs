@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::future::Future;
 use std::ops::Deref;
 
 use bytes::Bytes;
@@ -21,10 +22,12 @@ use risingwave_common::row::{Row, Row2};
 use risingwave_common::types::{ScalarImpl, ScalarRefImpl};
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_connector::source::{SplitId, SplitImpl, SplitMetaData};
+use risingwave_hummock_sdk::HummockReadEpoch;
 use risingwave_pb::catalog::Table as ProstTable;
 use risingwave_pb::data::data_type::TypeName;
 use risingwave_pb::data::DataType;
 use risingwave_pb::plan_common::{ColumnCatalog, ColumnDesc, ColumnOrder};
+use risingwave_storage::error::StorageResult;
 use risingwave_storage::StateStore;
 
 use crate::common::table::state_table::StateTable;
@@ -33,12 +36,28 @@ use crate::executor::StreamExecutorResult;
 
 pub struct SourceStateTableHandler<S: StateStore> {
     pub state_store: StateTable<S>,
+    store: S,
 }
 
 impl<S: StateStore> SourceStateTableHandler<S> {
     pub async fn from_table_catalog(table_catalog: &ProstTable, store: S) -> Self {
         Self {
-            state_store: StateTable::from_table_catalog(table_catalog, store, None).await,
+            state_store: StateTable::from_table_catalog(table_catalog, store.clone(), None).await,
+            store,
+        }
+    }
+
+    /// Returns a future that completes once the checkpoint of `epoch` is committed, after which
+    /// the source is recovered from the states taken no earlier than it.
+    pub fn wait_committed(
+        &self,
+        epoch: u64,
+    ) -> impl Future<Output = StorageResult<()>> + Send + 'static {
+        let store = self.store.clone();
+        async move {
+            store
+                .try_wait_epoch(HummockReadEpoch::Committed(epoch))
+                .await
         }
     }
 
@@ -0,0 +1,353 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, RowRef, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::row::{Row, Row2, RowExt};
+use risingwave_common::types::ScalarImpl;
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_hummock_sdk::HummockReadEpoch;
+use risingwave_storage::table::batch_table::storage_table::StorageTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message,
+    PkIndices, PkIndicesRef, StreamExecutorResult,
+};
+
+/// [`TemporalJoinExecutor`] joins an append-only stream with a table as of the process time. Each
+/// left row is joined with the rows looked up from the storage of the table by the prefix of its
+/// primary key, so neither side is kept in the state, and changes of the table never update the
+/// rows joined before.
+///
+/// The table is read at the previous epoch without waiting for it to be committed, so the lookups
+/// see a recent snapshot of the table, which is what the process-time semantics asks for.
+pub struct TemporalJoinExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+
+    /// Taken (`Option.take()`) after executing.
+    input: Option<BoxedExecutor>,
+
+    right_table: StorageTable<S>,
+    /// Whether the join is a left outer join, otherwise an inner join.
+    left_outer: bool,
+    left_join_keys: Vec<usize>,
+    right_join_keys: Vec<usize>,
+    null_safe: Vec<bool>,
+    /// The first `lookup_prefix_len` join keys are the prefix of the primary key of the table.
+    lookup_prefix_len: usize,
+    condition: Option<BoxedExpression>,
+    output_indices: Vec<usize>,
+
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    chunk_size: usize,
+}
+
+impl<S: StateStore> TemporalJoinExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        right_table: StorageTable<S>,
+        left_outer: bool,
+        left_join_keys: Vec<usize>,
+        right_join_keys: Vec<usize>,
+        null_safe: Vec<bool>,
+        lookup_prefix_len: usize,
+        condition: Option<BoxedExpression>,
+        output_indices: Vec<usize>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        chunk_size: usize,
+    ) -> Self {
+        let fields = input
+            .schema()
+            .fields()
+            .iter()
+            .chain(right_table.schema().fields())
+            .cloned()
+            .collect_vec();
+        let schema = output_indices
+            .iter()
+            .map(|&idx| fields[idx].clone())
+            .collect();
+        Self {
+            ctx,
+            input: Some(input),
+            right_table,
+            left_outer,
+            left_join_keys,
+            right_join_keys,
+            null_safe,
+            lookup_prefix_len,
+            condition,
+            output_indices,
+            schema,
+            pk_indices,
+            identity: format!("TemporalJoinExecutor {:X}", executor_id),
+            chunk_size,
+        }
+    }
+
+    /// Looks up the rows of the table at `epoch` that match `left_row` by the join keys.
+    async fn lookup(&self, left_row: RowRef<'_>, epoch: u64) -> StreamExecutorResult<Vec<Row>> {
+        // `NULL` never matches unless the key is null-safe.
+        if self
+            .left_join_keys
+            .iter()
+            .zip_eq(&self.null_safe)
+            .any(|(&idx, &null_safe)| left_row.datum_at(idx).is_none() && !null_safe)
+        {
+            return Ok(vec![]);
+        }
+
+        let prefix = left_row.project(&self.left_join_keys[..self.lookup_prefix_len]);
+        let iter = self
+            .right_table
+            .batch_iter_with_pk_bounds(HummockReadEpoch::NoWait(epoch), prefix, ..)
+            .await?;
+        pin_mut!(iter);
+
+        let mut rows = vec![];
+        while let Some((_, right_row)) = iter.next().await.transpose()? {
+            // The join keys out of the prefix are not used to look up.
+            let matched = self.left_join_keys[self.lookup_prefix_len..]
+                .iter()
+                .zip_eq(&self.right_join_keys[self.lookup_prefix_len..])
+                .all(|(&l, &r)| left_row.datum_at(l) == right_row.datum_at(r));
+            if matched {
+                rows.push(right_row);
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Joins the rows of a chunk with the table at `epoch`, returning the output rows.
+    async fn join_chunk(
+        &self,
+        chunk: StreamChunk,
+        epoch: u64,
+    ) -> StreamExecutorResult<Vec<(Op, Row)>> {
+        let right_len = self.right_table.schema().len();
+        let mut outputs = vec![];
+        for (op, left_row) in chunk.rows() {
+            let mut matched = false;
+            for right_row in self.lookup(left_row, epoch).await? {
+                let row = left_row.chain(right_row).into_owned_row();
+                if let Some(condition) = &self.condition
+                    && condition.eval_row(&row)? != Some(ScalarImpl::Bool(true))
+                {
+                    continue;
+                }
+                matched = true;
+                outputs.push((op, row.project(&self.output_indices).into_owned_row()));
+            }
+            if !matched && self.left_outer {
+                let row = left_row.chain(Row::new(vec![None; right_len]));
+                outputs.push((op, row.project(&self.output_indices).into_owned_row()));
+            }
+        }
+        Ok(outputs)
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self) {
+        let mut input = self.input.take().unwrap().execute();
+        let data_types = self.schema.data_types();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        let mut epoch = barrier.epoch.prev;
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                // TODO: propagate the watermarks of the left side.
+                Message::Watermark(_) => continue,
+                Message::Chunk(chunk) => {
+                    let outputs = self.join_chunk(chunk, epoch).await?;
+                    for rows in &outputs.into_iter().chunks(self.chunk_size) {
+                        let rows = rows.collect_vec();
+                        yield Message::Chunk(StreamChunk::from_rows(&rows, &data_types));
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    epoch = barrier.epoch.prev;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for TemporalJoinExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        (*self).execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::epoch::EpochPair;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::common::table::state_table::StateTable;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::ActorContext;
+
+    fn row(key: i64, value: &str) -> Row {
+        Row::new(vec![
+            Some(ScalarImpl::Int64(key)),
+            Some(ScalarImpl::Utf8(value.into())),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_temporal_join_executor() {
+        let store = MemoryStateStore::new();
+        let right_columns = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Varchar),
+        ];
+        let mut right_state = StateTable::new_without_distribution(
+            store.clone(),
+            TableId::new(1),
+            right_columns.clone(),
+            vec![OrderType::Ascending],
+            vec![0],
+        )
+        .await;
+        let right_table = StorageTable::for_test(
+            store,
+            TableId::new(1),
+            right_columns,
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+
+        // The table is written in epoch 1.
+        right_state.init_epoch(EpochPair::new_test_epoch(1));
+        right_state.insert(row(1, "a"));
+        right_state.insert(row(2, "b"));
+        right_state
+            .commit_for_test(EpochPair::new_test_epoch(2))
+            .await
+            .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (mut tx, source) = MockSource::channel(schema, vec![0]);
+        let temporal_join = TemporalJoinExecutor::new(
+            ActorContext::create(123),
+            Box::new(source),
+            right_table,
+            true,
+            vec![1],
+            vec![0],
+            vec![false],
+            1,
+            None,
+            vec![0, 1, 2, 3],
+            vec![0, 2],
+            1,
+            1024,
+        );
+        let mut temporal_join = Box::new(temporal_join).execute();
+
+        tx.push_barrier(2, false);
+        temporal_join.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            "  I  I
+            + 10 1
+            + 11 3
+            + 12 .",
+        ));
+        assert_eq!(
+            temporal_join
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I  I I T
+                + 10 1 1 a
+                + 11 3 . .
+                + 12 . . ."
+            )
+        );
+
+        // The table is updated in epoch 2, which does not update the joined rows.
+        right_state.delete(row(1, "a"));
+        right_state.insert(row(1, "c"));
+        right_state
+            .commit_for_test(EpochPair::new_test_epoch(3))
+            .await
+            .unwrap();
+
+        tx.push_barrier(3, false);
+        temporal_join
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            "  I  I
+            + 13 1",
+        ));
+        assert_eq!(
+            temporal_join
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                "  I  I I T
+                + 13 1 1 c"
+            )
+        );
+    }
+}
@@ -81,6 +81,7 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
             } else {
                 None
             },
+            time_travel_retention_seconds: None,
         };
         let value_indices = table_desc
             .get_value_indices()
@@ -97,6 +98,7 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
             distribution,
             table_option,
             value_indices,
+            table_desc.read_prefix_len_hint as usize,
         );
 
         let schema = table.schema().clone();
@@ -113,6 +113,7 @@ impl ExecutorBuilder for ChainExecutorBuilder {
                     } else {
                         None
                     },
+                    time_travel_retention_seconds: None,
                 };
                 let value_indices = table_desc
                     .get_value_indices()
@@ -130,6 +131,7 @@ impl ExecutorBuilder for ChainExecutorBuilder {
                     distribution,
                     table_option,
                     value_indices,
+                    table_desc.read_prefix_len_hint as usize,
                 );
 
                 BackfillExecutor::new(
@@ -32,12 +32,15 @@ mod lookup_union;
 mod merge;
 mod mview;
 mod now;
+mod over_agg;
 mod project;
 mod project_set;
 mod row_id_gen;
+mod session_window;
 mod sink;
 mod sort;
 mod source;
+mod temporal_join;
 mod top_n;
 mod top_n_appendonly;
 mod union;
@@ -66,12 +69,15 @@ use self::lookup_union::*;
 use self::merge::*;
 use self::mview::*;
 use self::now::NowExecutorBuilder;
+use self::over_agg::OverAggExecutorBuilder;
 use self::project::*;
 use self::project_set::*;
 use self::row_id_gen::RowIdGenExecutorBuilder;
+use self::session_window::SessionWindowExecutorBuilder;
 use self::sink::*;
 use self::sort::*;
 use self::source::*;
+use self::temporal_join::TemporalJoinExecutorBuilder;
 use self::top_n::*;
 use self::top_n_appendonly::*;
 use self::union::*;
@@ -142,9 +148,12 @@ pub async fn create_executor(
         NodeBody::ProjectSet => ProjectSetExecutorBuilder,
         NodeBody::GroupTopN => GroupTopNExecutorBuilder,
         NodeBody::Sort => SortExecutorBuilder,
+        NodeBody::OverAgg => OverAggExecutorBuilder,
+        NodeBody::SessionWindow => SessionWindowExecutorBuilder,
         NodeBody::WatermarkFilter => WatermarkFilterBuilder,
         NodeBody::Dml => DmlExecutorBuilder,
         NodeBody::RowIdGen => RowIdGenExecutorBuilder,
         NodeBody::Now => NowExecutorBuilder,
+        NodeBody::TemporalJoin => TemporalJoinExecutorBuilder,
     }
 }
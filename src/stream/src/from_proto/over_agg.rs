@@ -0,0 +1,69 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_expr::window_function::WindowFuncCall;
+use risingwave_pb::stream_plan::OverAggNode;
+
+use super::*;
+use crate::common::table::state_table::StateTable;
+use crate::executor::OverAggExecutor;
+
+pub struct OverAggExecutorBuilder;
+
+#[async_trait::async_trait]
+impl ExecutorBuilder for OverAggExecutorBuilder {
+    type Node = OverAggNode;
+
+    async fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &Self::Node,
+        store: impl StateStore,
+        stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let [input]: [_; 1] = params.input.try_into().unwrap();
+        let calls: Vec<_> = node
+            .get_calls()
+            .iter()
+            .map(WindowFuncCall::from_protobuf)
+            .try_collect()?;
+        let partition_by = node
+            .get_partition_by()
+            .iter()
+            .map(|&idx| idx as usize)
+            .collect();
+        let order_by = node
+            .get_order_by()
+            .iter()
+            .map(|column_order| column_order.index as usize)
+            .collect();
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table =
+            StateTable::from_table_catalog(node.get_state_table()?, store, vnodes).await;
+
+        Ok(Box::new(OverAggExecutor::new(
+            params.actor_context,
+            input,
+            calls,
+            partition_by,
+            order_by,
+            state_table,
+            params.executor_id,
+            params.env.config().developer.stream_chunk_size,
+            stream.context.lru_manager.clone(),
+            1 << 16,
+        )))
+    }
+}
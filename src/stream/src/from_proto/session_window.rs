@@ -0,0 +1,53 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_expr::vector_op::session::session_gap_usecs;
+use risingwave_pb::stream_plan::SessionWindowNode;
+
+use super::*;
+use crate::common::table::state_table::StateTable;
+use crate::executor::SessionWindowExecutor;
+
+pub struct SessionWindowExecutorBuilder;
+
+#[async_trait::async_trait]
+impl ExecutorBuilder for SessionWindowExecutorBuilder {
+    type Node = SessionWindowNode;
+
+    async fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &Self::Node,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let [input]: [_; 1] = params.input.try_into().unwrap();
+        let time_col_idx = node.get_time_col()?.column_idx as usize;
+        let gap_usecs = session_gap_usecs(node.get_gap()?.into())?;
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table =
+            StateTable::from_table_catalog(node.get_state_table()?, store, vnodes).await;
+
+        Ok(Box::new(SessionWindowExecutor::new(
+            params.actor_context,
+            input,
+            time_col_idx,
+            gap_usecs,
+            state_table,
+            params.executor_id,
+            params.env.config().developer.stream_chunk_size,
+        )))
+    }
+}
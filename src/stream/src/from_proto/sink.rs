@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use risingwave_common::catalog::{ColumnId, TableId};
 use risingwave_pb::stream_plan::SinkNode;
 
 use super::*;
+use crate::common::table::state_table::StateTable;
 use crate::executor::SinkExecutor;
 
 pub struct SinkExecutorBuilder;
@@ -32,21 +35,27 @@ impl ExecutorBuilder for SinkExecutorBuilder {
     ) -> StreamResult<BoxedExecutor> {
         let [materialize_executor]: [_; 1] = params.input.try_into().unwrap();
 
-        let _sink_id = TableId::from(node.table_id);
+        let sink_id = TableId::from(node.table_id);
         let _column_ids = node
             .get_column_ids()
             .iter()
             .map(|i| ColumnId::from(*i))
             .collect::<Vec<ColumnId>>();
 
-        let properties = node.get_properties();
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table =
+            StateTable::from_table_catalog(node.get_state_table()?, store.clone(), None).await;
 
         Ok(Box::new(SinkExecutor::new(
             materialize_executor,
             store,
+            state_table,
+            vnodes,
             stream.streaming_metrics.clone(),
-            properties.clone(),
+            sink_id,
+            node.get_properties().clone(),
             params.executor_id,
+            params.actor_context.id,
             params.env.connector_params(),
         )))
     }
@@ -19,6 +19,7 @@ use risingwave_source::SourceDescBuilder;
 use tokio::sync::mpsc::unbounded_channel;
 
 use super::*;
+use crate::common::table::state_table::StateTable;
 use crate::executor::state_table_handler::SourceStateTableHandler;
 use crate::executor::SourceExecutor;
 
@@ -74,9 +75,15 @@ impl ExecutorBuilder for SourceExecutorBuilder {
             .vnode_bitmap
             .expect("vnodes not set for source executor");
 
-        let state_table_handler =
-            SourceStateTableHandler::from_table_catalog(node.state_table.as_ref().unwrap(), store)
-                .await;
+        let state_table_handler = SourceStateTableHandler::from_table_catalog(
+            node.state_table.as_ref().unwrap(),
+            store.clone(),
+        )
+        .await;
+        let dead_letter_table = match &node.dead_letter_table {
+            Some(table) => Some(StateTable::from_table_catalog(table, store, None).await),
+            None => None,
+        };
 
         Ok(Box::new(SourceExecutor::new(
             params.actor_context,
@@ -85,6 +92,7 @@ impl ExecutorBuilder for SourceExecutorBuilder {
             source_name,
             vnodes,
             state_table_handler,
+            dead_letter_table,
             column_ids,
             schema,
             params.pk_indices,
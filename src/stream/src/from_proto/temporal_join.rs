@@ -0,0 +1,137 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId, TableOption};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_expr::expr::build_from_prost;
+use risingwave_pb::plan_common::{JoinType as JoinTypeProto, OrderType as ProstOrderType};
+use risingwave_pb::stream_plan::TemporalJoinNode;
+use risingwave_storage::table::batch_table::storage_table::StorageTable;
+use risingwave_storage::table::Distribution;
+
+use super::*;
+use crate::executor::TemporalJoinExecutor;
+
+pub struct TemporalJoinExecutorBuilder;
+
+#[async_trait::async_trait]
+impl ExecutorBuilder for TemporalJoinExecutorBuilder {
+    type Node = TemporalJoinNode;
+
+    async fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &Self::Node,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let [input]: [_; 1] = params.input.try_into().unwrap();
+
+        let left_outer = match node.get_join_type()? {
+            JoinTypeProto::Inner => false,
+            JoinTypeProto::LeftOuter => true,
+            join_type => unreachable!("unsupported join type of temporal join: {:?}", join_type),
+        };
+        let left_join_keys = node
+            .get_left_key()
+            .iter()
+            .map(|&key| key as usize)
+            .collect_vec();
+        let right_join_keys = node
+            .get_right_key()
+            .iter()
+            .map(|&key| key as usize)
+            .collect_vec();
+        let null_safe = node.get_null_safe().to_vec();
+        let condition = match node.get_condition() {
+            Ok(cond_prost) => Some(build_from_prost(cond_prost)?),
+            Err(_) => None,
+        };
+        let output_indices = node
+            .get_output_indices()
+            .iter()
+            .map(|&x| x as usize)
+            .collect_vec();
+
+        let table_desc = node.get_table_desc()?;
+        let table_id = TableId {
+            table_id: table_desc.table_id,
+        };
+        let column_descs = table_desc
+            .columns
+            .iter()
+            .map(ColumnDesc::from)
+            .collect_vec();
+        let column_ids = node
+            .get_table_output_column_ids()
+            .iter()
+            .copied()
+            .map(ColumnId::from)
+            .collect_vec();
+        let order_types = table_desc
+            .pk
+            .iter()
+            .map(|desc| OrderType::from_prost(&ProstOrderType::from_i32(desc.order_type).unwrap()))
+            .collect_vec();
+        let pk_indices = table_desc.pk.iter().map(|k| k.index as usize).collect_vec();
+        let dist_key_indices = table_desc
+            .dist_key_indices
+            .iter()
+            .map(|&k| k as usize)
+            .collect_vec();
+        // The lookup key always contains the distribution key, so all vnodes can be read.
+        let distribution = Distribution::all_vnodes(dist_key_indices);
+        let table_option = TableOption {
+            retention_seconds: if table_desc.retention_seconds > 0 {
+                Some(table_desc.retention_seconds)
+            } else {
+                None
+            },
+            time_travel_retention_seconds: None,
+        };
+        let value_indices = table_desc
+            .get_value_indices()
+            .iter()
+            .map(|&k| k as usize)
+            .collect_vec();
+        let right_table = StorageTable::new_partial(
+            store,
+            table_id,
+            column_descs,
+            column_ids,
+            order_types,
+            pk_indices,
+            distribution,
+            table_option,
+            value_indices,
+            table_desc.read_prefix_len_hint as usize,
+        );
+
+        Ok(TemporalJoinExecutor::new(
+            params.actor_context,
+            input,
+            right_table,
+            left_outer,
+            left_join_keys,
+            right_join_keys,
+            null_safe,
+            node.get_lookup_prefix_len() as usize,
+            condition,
+            output_indices,
+            params.pk_indices,
+            params.executor_id,
+            params.env.config().developer.stream_chunk_size,
+        )
+        .boxed())
+    }
+}
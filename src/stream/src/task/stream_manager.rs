@@ -491,6 +491,8 @@ impl LocalStreamManagerCore {
                     | NodeBody::Chain(_)
                     | NodeBody::DynamicFilter(_)
                     | NodeBody::GroupTopN(_)
+                    | NodeBody::OverAgg(_)
+                    | NodeBody::SessionWindow(_)
             )
         }
         let is_stateful = is_stateful_executor(node);
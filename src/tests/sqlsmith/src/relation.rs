@@ -65,6 +65,8 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
                 name: alias.as_str().into(),
                 columns: vec![],
             }),
+            for_system_time_as_of_proctime: false,
+            for_system_time_as_of_timestamp: None,
         };
         table.name = alias; // Rename the table.
         let columns = table.get_qualified_columns();
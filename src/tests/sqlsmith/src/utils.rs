@@ -67,6 +67,8 @@ pub(crate) fn create_table_factor_from_table(table: &Table) -> TableFactor {
     TableFactor::Table {
         name: ObjectName(vec![Ident::new(&table.name)]),
         alias: None,
+        for_system_time_as_of_proctime: false,
+        for_system_time_as_of_timestamp: None,
     }
 }
 
@@ -113,6 +115,6 @@ pub fn data_type_name_to_ast_data_type(type_name: DataTypeName) -> Option<DataTy
         DataTypeName::Timestampz => Some(DataType::Timestamp(true)),
         DataTypeName::Time => Some(DataType::Time(false)),
         DataTypeName::Interval => Some(DataType::Interval),
-        DataTypeName::Struct | DataTypeName::List => None,
+        DataTypeName::Struct | DataTypeName::List | DataTypeName::Jsonb => None,
     }
 }
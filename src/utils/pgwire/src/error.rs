@@ -35,6 +35,12 @@ pub enum PsqlError {
     #[error("ParseError: {0}")]
     ParseError(BoxedError),
 
+    #[error("BindError: {0}")]
+    BindError(BoxedError),
+
+    #[error("DescribeError: {0}")]
+    DescribeError(BoxedError),
+
     #[error("ExecuteError: {0}")]
     ExecuteError(BoxedError),
 
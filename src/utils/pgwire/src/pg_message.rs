@@ -89,7 +89,8 @@ pub struct FeBindMessage {
     //  true: binary
     pub result_format_code: bool,
 
-    pub params: Vec<Bytes>,
+    // `None` stands for a NULL parameter.
+    pub params: Vec<Option<Bytes>>,
     pub portal_name: Bytes,
     pub statement_name: Bytes,
 }
@@ -192,7 +193,12 @@ impl FeBindMessage {
         let params = (0..len)
             .map(|_| {
                 let val_len = buf.get_i32();
-                buf.copy_to_bytes(val_len as usize)
+                // The length -1 indicates a NULL parameter value.
+                if val_len == -1 {
+                    None
+                } else {
+                    Some(buf.copy_to_bytes(val_len as usize))
+                }
             })
             .collect();
 
@@ -24,14 +24,14 @@ use bytes::{Bytes, BytesMut};
 use futures::stream::StreamExt;
 use futures::Stream;
 use openssl::ssl::{SslAcceptor, SslContext, SslContextRef, SslMethod};
+use risingwave_common::types::DataType;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio_openssl::SslStream;
 use tracing::log::trace;
 use tracing::warn;
 
 use crate::error::{PsqlError, PsqlResult};
-use crate::pg_extended::{PgPortal, PgStatement, PreparedStatement};
-use crate::pg_field_descriptor::PgFieldDescriptor;
+use crate::pg_extended::PgPortal;
 use crate::pg_message::{
     BeCommandCompleteMessage, BeMessage, BeParameterStatusMessage, FeBindMessage, FeCancelMessage,
     FeCloseMessage, FeDescribeMessage, FeExecuteMessage, FeMessage, FeParseMessage,
@@ -57,16 +57,20 @@ where
     session_mgr: Arc<SM>,
     session: Option<Arc<SM::Session>>,
 
-    unnamed_statement: Option<PgStatement>,
-    unnamed_portal: Option<PgPortal<VS>>,
-    named_statements: HashMap<String, PgStatement>,
-    named_portals: HashMap<String, PgPortal<VS>>,
+    unnamed_statement: Option<PreparedStatement<SM, VS>>,
+    unnamed_portal: Option<PgPortal<VS, Portal<SM, VS>>>,
+    named_statements: HashMap<String, PreparedStatement<SM, VS>>,
+    named_portals: HashMap<String, PgPortal<VS, Portal<SM, VS>>>,
 
     // Used for ssl connection.
     // If None, not expected to build ssl connection (panic).
     tls_context: Option<SslContext>,
 }
 
+type PreparedStatement<SM, VS> =
+    <<SM as SessionManager<VS>>::Session as Session<VS>>::PreparedStatement;
+type Portal<SM, VS> = <<SM as SessionManager<VS>>::Session as Session<VS>>::Portal;
+
 /// Configures TLS encryption for connections.
 #[derive(Debug, Clone)]
 pub struct TlsConfig {
@@ -186,6 +190,8 @@ where
 
                     PsqlError::Internal(_)
                     | PsqlError::ParseError(_)
+                    | PsqlError::BindError(_)
+                    | PsqlError::DescribeError(_)
                     | PsqlError::ExecuteError(_) => {
                         self.stream
                             .write_no_flush(&BeMessage::ErrorResponse(Box::new(e)))
@@ -208,7 +214,7 @@ where
             FeMessage::Query(query_msg) => self.process_query_msg(query_msg.get_sql()).await?,
             FeMessage::CancelQuery(m) => self.process_cancel_msg(m)?,
             FeMessage::Terminate => self.process_terminate(),
-            FeMessage::Parse(m) => self.process_parse_msg(m).await?,
+            FeMessage::Parse(m) => self.process_parse_msg(m)?,
             FeMessage::Bind(m) => self.process_bind_msg(m)?,
             FeMessage::Execute(m) => self.process_execute_msg(m).await?,
             FeMessage::Describe(m) => self.process_describe_msg(m)?,
@@ -360,50 +366,37 @@ where
         self.is_terminate = true;
     }
 
-    async fn process_parse_msg(&mut self, msg: FeParseMessage) -> PsqlResult<()> {
+    fn process_parse_msg(&mut self, msg: FeParseMessage) -> PsqlResult<()> {
         let sql = cstr_to_str(&msg.sql_bytes).unwrap();
         tracing::trace!("(extended query)parse query: {}", sql);
 
-        // Flag indicate whether statement is a query statement.
-        let is_query_sql = {
-            let lower_sql = sql.to_ascii_lowercase();
-            lower_sql.starts_with("select")
-                || lower_sql.starts_with("values")
-                || lower_sql.starts_with("show")
-                || lower_sql.starts_with("with")
-                || lower_sql.starts_with("describe")
-                || lower_sql.starts_with("explain")
-        };
-
-        let prepared_statement = PreparedStatement::parse_statement(sql.to_string(), msg.type_ids)?;
-
-        // 2. Create the row description.
-        let fields: Vec<PgFieldDescriptor> = if is_query_sql {
-            let sql = prepared_statement.instance_default()?;
-
-            let session = self.session.clone().unwrap();
-            session
-                .infer_return_type(&sql)
-                .await
-                .map_err(PsqlError::ParseError)?
-        } else {
-            vec![]
-        };
+        // 1. Resolve the parameter types specified by the client, where 0 means unspecified.
+        let param_types = msg
+            .type_ids
+            .iter()
+            .map(|&oid| {
+                if oid == 0 {
+                    Ok(None)
+                } else {
+                    DataType::from_oid(oid)
+                        .map(Some)
+                        .map_err(|e| PsqlError::ParseError(Box::new(e)))
+                }
+            })
+            .collect::<PsqlResult<Vec<_>>>()?;
 
-        // 3. Create the statement.
-        let statement = PgStatement::new(
-            cstr_to_str(&msg.statement_name).unwrap().to_string(),
-            prepared_statement,
-            fields,
-            is_query_sql,
-        );
+        // 2. Prepare the statement.
+        let session = self.session.clone().unwrap();
+        let prepared_statement = session
+            .parse(sql, param_types)
+            .map_err(PsqlError::ParseError)?;
 
-        // 4. Insert the statement.
-        let name = statement.name();
+        // 3. Insert the statement.
+        let name = cstr_to_str(&msg.statement_name).unwrap().to_string();
         if name.is_empty() {
-            self.unnamed_statement.replace(statement);
+            self.unnamed_statement.replace(prepared_statement);
         } else {
-            self.named_statements.insert(name, statement);
+            self.named_statements.insert(name, prepared_statement);
         }
         self.stream.write_no_flush(&BeMessage::ParseComplete)?;
         Ok(())
@@ -416,30 +409,35 @@ where
             "(extended query)bind: get statement name: {}",
             &statement_name
         );
-        let statement = if statement_name.is_empty() {
+        let prepared_statement = if statement_name.is_empty() {
             self.unnamed_statement
-                .as_ref()
+                .clone()
                 .ok_or_else(PsqlError::no_statement)?
         } else {
             self.named_statements
                 .get(&statement_name)
+                .cloned()
                 .ok_or_else(PsqlError::no_statement)?
         };
 
-        // 2. Instance the statement to get the portal.
-        let portal_name = cstr_to_str(&msg.portal_name).unwrap().to_string();
-        let portal = statement.instance(
-            portal_name.clone(),
-            &msg.params,
-            msg.result_format_code,
-            msg.param_format_code,
-        )?;
+        // 2. Bind the parameters to the statement to get the portal.
+        let session = self.session.clone().unwrap();
+        let portal = session
+            .bind(
+                prepared_statement,
+                msg.params,
+                msg.param_format_code,
+                msg.result_format_code,
+            )
+            .map_err(PsqlError::BindError)?;
 
         // 3. Insert the Portal.
+        let portal_name = cstr_to_str(&msg.portal_name).unwrap().to_string();
         if portal_name.is_empty() {
-            self.unnamed_portal.replace(portal);
+            self.unnamed_portal.replace(PgPortal::new(portal));
         } else {
-            self.named_portals.insert(portal_name, portal);
+            self.named_portals
+                .insert(portal_name, PgPortal::new(portal));
         }
         self.stream.write_no_flush(&BeMessage::BindComplete)?;
         Ok(())
@@ -459,12 +457,12 @@ where
                 .ok_or_else(PsqlError::no_portal)?
         };
 
-        tracing::trace!("(extended query)execute query: {}", portal.query_string());
+        tracing::trace!("(extended query)execute query: {}", portal.portal());
 
         // 2. Execute instance statement using portal.
         let session = self.session.clone().unwrap();
         portal
-            .execute::<SM, S>(session, msg.max_rows.try_into().unwrap(), &mut self.stream)
+            .execute(session, msg.max_rows.try_into().unwrap(), &mut self.stream)
             .await?;
 
         // NOTE there is no ReadyForQuery message.
@@ -480,35 +478,30 @@ where
         );
 
         assert!(msg.kind == b'S' || msg.kind == b'P');
-        if msg.kind == b'S' {
+        let session = self.session.clone().unwrap();
+        let row_description = if msg.kind == b'S' {
             let name = cstr_to_str(&msg.name).unwrap().to_string();
-            let statement = if name.is_empty() {
+            let prepared_statement = if name.is_empty() {
                 self.unnamed_statement
-                    .as_ref()
+                    .clone()
                     .ok_or_else(PsqlError::no_statement)?
             } else {
                 // NOTE Error handle need modify later.
                 self.named_statements
                     .get(&name)
+                    .cloned()
                     .ok_or_else(PsqlError::no_statement)?
             };
+            let (param_types, row_description) = session
+                .describe_statement(prepared_statement)
+                .map_err(PsqlError::DescribeError)?;
 
             // 1. Send parameter description.
+            let param_oids = param_types.iter().map(|t| t.to_oid()).collect::<Vec<_>>();
             self.stream
-                .write_no_flush(&BeMessage::ParameterDescription(
-                    &statement.param_oid_desc(),
-                ))?;
-
-            // 2. Send row description.
-            if statement.is_query() {
-                self.stream
-                    .write_no_flush(&BeMessage::RowDescription(&statement.row_desc()))?;
-            } else {
-                // According https://www.postgresql.org/docs/current/protocol-flow.html#:~:text=The%20response%20is%20a%20RowDescri[…]0a%20query%20that%20will%20return%20rows%3B,
-                // return NoData message if the statement is not a query.
-                self.stream.write_no_flush(&BeMessage::NoData)?;
-            }
-        } else if msg.kind == b'P' {
+                .write_no_flush(&BeMessage::ParameterDescription(&param_oids))?;
+            row_description
+        } else {
             let name = cstr_to_str(&msg.name).unwrap().to_string();
             let portal = if name.is_empty() {
                 self.unnamed_portal
@@ -520,16 +513,19 @@ where
                     .get(&name)
                     .ok_or_else(PsqlError::no_portal)?
             };
+            session
+                .describe_portal(portal.portal())
+                .map_err(PsqlError::DescribeError)?
+        };
 
-            // 3. Send row description.
-            if portal.is_query() {
-                self.stream
-                    .write_no_flush(&BeMessage::RowDescription(&portal.row_desc()))?;
-            } else {
-                // According https://www.postgresql.org/docs/current/protocol-flow.html#:~:text=The%20response%20is%20a%20RowDescri[…]0a%20query%20that%20will%20return%20rows%3B,
-                // return NoData message if the statement is not a query.
-                self.stream.write_no_flush(&BeMessage::NoData)?;
-            }
+        // 2. Send row description.
+        if row_description.is_empty() {
+            // According https://www.postgresql.org/docs/current/protocol-flow.html#:~:text=The%20response%20is%20a%20RowDescri[…]0a%20query%20that%20will%20return%20rows%3B,
+            // return NoData message if the statement is not a query.
+            self.stream.write_no_flush(&BeMessage::NoData)?;
+        } else {
+            self.stream
+                .write_no_flush(&BeMessage::RowDescription(&row_description))?;
         }
         Ok(())
     }
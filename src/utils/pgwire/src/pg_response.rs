@@ -44,6 +44,7 @@ pub enum StatementType {
     CREATE_SCHEMA,
     CREATE_USER,
     CREATE_INDEX,
+    CREATE_FUNCTION,
     DESCRIBE_TABLE,
     GRANT_PRIVILEGE,
     DROP_TABLE,
@@ -55,6 +56,8 @@ pub enum StatementType {
     DROP_SCHEMA,
     DROP_DATABASE,
     DROP_USER,
+    DROP_FUNCTION,
+    ALTER_SOURCE,
     REVOKE_PRIVILEGE,
     // Introduce ORDER_BY statement type cuz Calcite unvalidated AST has SqlKind.ORDER_BY. Note
     // that Statement Type is not designed to be one to one mapping with SqlKind.
@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Display;
 use std::future::Future;
 use std::io;
 use std::result::Result;
 use std::sync::Arc;
 
+use bytes::Bytes;
 use futures::Stream;
+use risingwave_common::types::DataType;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpListener;
 use tracing::debug;
@@ -54,15 +57,48 @@ pub trait Session<VS>: Send + Sync
 where
     VS: Stream<Item = RowSetResult> + Unpin + Send,
 {
+    /// The statement prepared by a `Parse` message of the extended query protocol.
+    type PreparedStatement: Send + Clone + 'static;
+    /// The statement with its parameters bound by a `Bind` message of the extended query protocol.
+    type Portal: Send + Clone + Display + 'static;
+
     async fn run_statement(
         self: Arc<Self>,
         sql: &str,
         format: bool,
     ) -> Result<PgResponse<VS>, BoxedError>;
-    async fn infer_return_type(
+
+    /// Prepares the `sql` with parameters. `param_types` are the types of parameters specified by
+    /// the client, where `None` means the type is unspecified and should be inferred.
+    fn parse(
         self: Arc<Self>,
         sql: &str,
+        param_types: Vec<Option<DataType>>,
+    ) -> Result<Self::PreparedStatement, BoxedError>;
+
+    /// Binds the values of parameters to a prepared statement. `None` stands for a NULL value.
+    fn bind(
+        self: Arc<Self>,
+        prepare_statement: Self::PreparedStatement,
+        params: Vec<Option<Bytes>>,
+        param_format: bool,
+        result_format: bool,
+    ) -> Result<Self::Portal, BoxedError>;
+
+    async fn execute(self: Arc<Self>, portal: Self::Portal) -> Result<PgResponse<VS>, BoxedError>;
+
+    /// Returns the types of parameters and the row description of a prepared statement.
+    fn describe_statement(
+        self: Arc<Self>,
+        prepare_statement: Self::PreparedStatement,
+    ) -> Result<(Vec<DataType>, Vec<PgFieldDescriptor>), BoxedError>;
+
+    /// Returns the row description of a portal.
+    fn describe_portal(
+        self: Arc<Self>,
+        portal: Self::Portal,
     ) -> Result<Vec<PgFieldDescriptor>, BoxedError>;
+
     fn user_authenticator(&self) -> &UserAuthenticator;
 
     fn id(&self) -> SessionId;
@@ -159,6 +195,7 @@ mod tests {
     use bytes::Bytes;
     use futures::stream::BoxStream;
     use futures::StreamExt;
+    use risingwave_common::types::DataType;
     use tokio_postgres::types::*;
     use tokio_postgres::NoTls;
 
@@ -189,8 +226,22 @@ mod tests {
 
     struct MockSession {}
 
+    /// Returns a varchar column for each select item of a simple SELECT statement.
+    fn mock_row_description(sql: &str) -> Vec<PgFieldDescriptor> {
+        let count = sql.split(&[' ', ',', ';']).skip(1).count();
+        vec![
+            // 1043 is the oid of varchar type.
+            // -1 is the type len of varchar type.
+            PgFieldDescriptor::new("".to_string(), 1043, -1);
+            count
+        ]
+    }
+
     #[async_trait::async_trait]
     impl Session<BoxStream<'static, RowSetResult>> for MockSession {
+        type Portal = String;
+        type PreparedStatement = (String, Vec<DataType>);
+
         async fn run_statement(
             self: Arc<Self>,
             sql: &str,
@@ -212,18 +263,12 @@ mod tests {
                     )
                 })
                 .collect();
-            let len = res.len();
 
             Ok(PgResponse::new_for_stream(
                 StatementType::SELECT,
                 Some(1),
                 futures::stream::iter(vec![Ok(vec![Row::new(res)])]).boxed(),
-                vec![
-                    // 1043 is the oid of varchar type.
-                    // -1 is the type len of varchar type.
-                    PgFieldDescriptor::new("".to_string(), 1043, -1);
-                    len
-                ],
+                mock_row_description(sql),
             ))
         }
 
@@ -231,21 +276,55 @@ mod tests {
             &UserAuthenticator::None
         }
 
-        async fn infer_return_type(
+        fn parse(
             self: Arc<Self>,
             sql: &str,
+            param_types: Vec<Option<DataType>>,
+        ) -> Result<Self::PreparedStatement, super::BoxedError> {
+            let param_types = param_types
+                .into_iter()
+                .map(|data_type| data_type.unwrap_or(DataType::Varchar))
+                .collect();
+            Ok((sql.to_string(), param_types))
+        }
+
+        fn bind(
+            self: Arc<Self>,
+            prepare_statement: Self::PreparedStatement,
+            params: Vec<Option<Bytes>>,
+            _param_format: bool,
+            _result_format: bool,
+        ) -> Result<Self::Portal, super::BoxedError> {
+            // Replace `$n` with the quoted value of the n-th parameter. Replace the larger indexes
+            // first so that `$1` won't match the prefix of `$12`.
+            let (mut sql, _) = prepare_statement;
+            for (index, param) in params.iter().enumerate().rev() {
+                let value = std::str::from_utf8(param.as_deref().unwrap_or_default())?;
+                sql = sql.replace(&format!("${}", index + 1), &format!("'{}'", value));
+            }
+            Ok(sql)
+        }
+
+        async fn execute(
+            self: Arc<Self>,
+            portal: Self::Portal,
+        ) -> Result<PgResponse<BoxStream<'static, RowSetResult>>, super::BoxedError> {
+            self.run_statement(&portal, false).await
+        }
+
+        fn describe_statement(
+            self: Arc<Self>,
+            prepare_statement: Self::PreparedStatement,
+        ) -> Result<(Vec<DataType>, Vec<PgFieldDescriptor>), super::BoxedError> {
+            let (sql, param_types) = prepare_statement;
+            Ok((param_types, mock_row_description(&sql)))
+        }
+
+        fn describe_portal(
+            self: Arc<Self>,
+            portal: Self::Portal,
         ) -> Result<Vec<PgFieldDescriptor>, super::BoxedError> {
-            let count = sql.split(&[' ', ',', ';']).skip(1).count();
-            Ok(vec![
-                // 1043 is the oid of varchar type.
-                // -1 is the type len of varchar type.
-                PgFieldDescriptor::new(
-                    "".to_string(),
-                    1043,
-                    -1
-                );
-                count
-            ])
+            Ok(mock_row_description(&portal))
         }
 
         fn id(&self) -> SessionId {